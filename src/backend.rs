@@ -1,19 +1,54 @@
 //! Application backend.
 //! This includes all logic unrelated to UI.
 
+pub mod asset_locks;
+pub mod bookmarks;
+pub mod chain_status;
+pub mod connections;
+mod consensus_errors;
 pub mod contracts;
+mod core_rpc;
+mod debug_text;
+pub mod dependencies;
 pub mod documents;
+mod encryption;
 pub mod error;
+pub mod export;
 pub mod identities;
 pub mod insight;
+pub(crate) mod json_store;
+pub mod keep_funded;
+mod known_entities;
+pub mod lanes;
+pub mod network;
+pub mod network_changelog;
+pub mod petnames;
+pub mod platform;
 pub mod platform_info;
+pub mod progress;
+pub mod proofs;
+mod receipts;
+pub mod reorg;
+pub mod reports;
+pub mod schema_builder;
+pub mod search;
+pub mod settings;
+pub mod signer;
+pub mod signing_audit;
 pub mod state;
 pub mod strategies;
+pub mod task_history;
+mod tenderdash_rpc;
+pub mod trace_replay;
+pub mod traffic;
+pub mod tutorial;
 pub mod wallet;
 
 use std::{
-    collections::BTreeMap,
-    fmt::{self, Display},
+    collections::{hash_map::DefaultHasher, BTreeMap},
+    fmt::{self, Display, Write as _},
+    hash::Hasher,
+    path::Path,
     time::Duration,
 };
 
@@ -26,21 +61,38 @@ use dpp::{
 use serde::Serialize;
 pub(crate) use state::AppState;
 use strategy_tests::Strategy;
-use tokio::sync::{MappedMutexGuard, MutexGuard};
+use tokio::sync::{MappedMutexGuard, Mutex, MutexGuard};
 
-use self::state::KnownContractsMap;
 pub(crate) use self::{
     contracts::ContractTask,
     state::StrategyContractNames,
     strategies::StrategyTask,
-    wallet::{Wallet, WalletTask},
+    wallet::{profiles::WalletProfiles, FeeSettings, Wallet, WalletTask},
+};
+use self::{
+    asset_locks::{AssetLockTask, AssetLocks},
+    bookmarks::{BookmarkTask, Bookmarks},
+    chain_status::ChainStatus,
+    core_rpc::CoreRpcClient,
+    export::ExportTask,
+    json_store::JsonStore,
+    keep_funded::KeepFunded,
+    platform::{Platform, SimulatedPlatform},
+    proofs::{ProofDetails, ProvedFetch},
+    reorg::ReorgWatch,
+    state::KnownContractsMap,
+    strategies::{funding::FundingPlan, phases::RunPhases, resources::ResourceUsage},
+    tenderdash_rpc::TenderdashRpcClient,
 };
 use crate::{
     backend::{
         documents::DocumentTask, identities::IdentityTask, insight::InsightAPIClient,
-        platform_info::PlatformInfoTask, state::StrategiesMap,
+        keep_funded::KeepFundedTask, network::NetworkTask, platform_info::PlatformInfoTask,
+        schema_builder::SchemaBuilderTask, search::SearchTask, settings::SettingsTask,
+        state::StrategiesMap, tutorial::TutorialTask,
     },
-    config::Config,
+    config::{Config, StartupTask},
+    logging::{self, CorrelationId},
 };
 
 /// Unit of work for the backend.
@@ -56,6 +108,124 @@ pub enum Task {
     Identity(IdentityTask),
     Contract(ContractTask),
     Document(DocumentTask),
+    SetLogFilter(String),
+    Settings(SettingsTask),
+    SchemaBuilder(SchemaBuilderTask),
+    KeepFunded(KeepFundedTask),
+    Tutorial(TutorialTask),
+    Search(SearchTask),
+    Bookmark(BookmarkTask),
+    AssetLock(AssetLockTask),
+    Network(NetworkTask),
+    Export(ExportTask),
+    /// Fetch of an entity along with details of its proof
+    FetchWithProof(ProvedFetch),
+    /// Periodic check of wallet transactions for core chain reorgs, issued by
+    /// the application loop in background
+    CheckReorgs,
+    /// Requests to DAPI nodes establishing connections ahead of user queries
+    /// and keeping them alive, issued by the application loop in background
+    WarmUpConnections,
+    /// Poll of Core and Platform chain heights shown in the status bar, issued
+    /// by the application loop in background
+    PollChainStatus,
+}
+
+impl Task {
+    /// Short name of the task kind, recorded in logs instead of the whole task
+    /// that may carry secrets.
+    pub(crate) fn kind(&self) -> &'static str {
+        match self {
+            Task::FetchIdentityById(..) => "fetch_identity",
+            Task::PlatformInfo(_) => "platform_info",
+            Task::Strategy(_) => "strategy",
+            Task::Wallet(_) => "wallet",
+            Task::Identity(_) => "identity",
+            Task::Contract(_) => "contract",
+            Task::Document(_) => "document",
+            Task::SetLogFilter(_) => "set_log_filter",
+            Task::Settings(_) => "settings",
+            Task::SchemaBuilder(_) => "schema_builder",
+            Task::KeepFunded(_) => "keep_funded",
+            Task::Tutorial(_) => "tutorial",
+            Task::Search(_) => "search",
+            Task::Bookmark(_) => "bookmark",
+            Task::AssetLock(_) => "asset_lock",
+            Task::Network(_) => "network",
+            Task::Export(_) => "export",
+            Task::FetchWithProof(_) => "fetch_with_proof",
+            Task::CheckReorgs => "check_reorgs",
+            Task::WarmUpConnections => "warm_up_connections",
+            Task::PollChainStatus => "poll_chain_status",
+        }
+    }
+
+    /// Whether the task broadcasts a Core transaction or a state transition,
+    /// so submitting it twice spends twice.
+    pub(crate) fn sends_transaction(&self) -> bool {
+        matches!(
+            self,
+            Task::Identity(
+                IdentityTask::RegisterIdentity(_)
+                    | IdentityTask::RegisterIdentityWithAssetLock(_)
+                    | IdentityTask::ContinueRegistration
+                    | IdentityTask::SponsorIdentity { .. }
+                    | IdentityTask::TopUpIdentity(_)
+                    | IdentityTask::TopUpKnownIdentities { .. }
+                    | IdentityTask::WithdrawFromIdentity { .. }
+                    | IdentityTask::AddIdentityKey { .. }
+                    | IdentityTask::AddWithdrawalAddress(_)
+                    | IdentityTask::TransferCredits(..)
+                    | IdentityTask::RegisterDPNSName(_)
+            ) | Task::Wallet(WalletTask::SplitUTXOs(_))
+                | Task::Contract(ContractTask::RegisterContract(_))
+                | Task::SchemaBuilder(
+                    SchemaBuilderTask::RegisterContract(_) | SchemaBuilderTask::UpdateContract(_)
+                )
+                | Task::Document(
+                    DocumentTask::BroadcastRandomDocuments { .. }
+                        | DocumentTask::BroadcastDocument { .. }
+                        | DocumentTask::ReplaceDocument { .. }
+                        | DocumentTask::DeleteDocument(..)
+                        | DocumentTask::DeleteDocuments(..)
+                )
+                | Task::Strategy(
+                    StrategyTask::RunStrategy(..)
+                        | StrategyTask::ResumeStrategy(..)
+                        | StrategyTask::RunContractVariants(..)
+                        | StrategyTask::ExecuteFundingStep
+                )
+        )
+    }
+
+    /// Hash of the variant and all parameters, telling identical tasks apart.
+    /// Tasks have no equality and their debug representation may carry
+    /// secrets, so it's hashed as it's written and never kept.
+    pub(crate) fn fingerprint(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        let _ = write!(HashWriter(&mut hasher), "{:?}", self);
+        hasher.finish()
+    }
+}
+
+/// Feeds formatted text into a hasher without building the string.
+struct HashWriter<'h>(&'h mut DefaultHasher);
+
+impl fmt::Write for HashWriter<'_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.0.write(s.as_bytes());
+        Ok(())
+    }
+}
+
+impl From<StartupTask> for Task {
+    fn from(task: StartupTask) -> Self {
+        match task {
+            StartupTask::RefreshIdentity => Task::Identity(IdentityTask::Refresh),
+            StartupTask::RefreshWallet => Task::Wallet(WalletTask::Refresh),
+            StartupTask::RefreshContracts => Task::Contract(ContractTask::RefreshContracts),
+        }
+    }
 }
 
 /// A positive task execution result.
@@ -65,6 +235,14 @@ pub enum Task {
 pub enum CompletedTaskPayload {
     Documents(BTreeMap<Identifier, Option<Document>>),
     Document(Document),
+    SearchResults(Vec<search::SearchHit>),
+    Evonodes(Vec<network::Evonode>),
+    ConnectionSettings(settings::ConnectionSettings),
+    /// Fetched entity shown as text, with the proof it was verified with
+    Proved {
+        data: String,
+        proof: ProofDetails,
+    },
     String(String),
 }
 
@@ -83,7 +261,9 @@ impl From<&str> for CompletedTaskPayload {
 impl Display for CompletedTaskPayload {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            CompletedTaskPayload::String(s) => write!(f, "{}", s),
+            CompletedTaskPayload::String(s) | CompletedTaskPayload::Proved { data: s, .. } => {
+                write!(f, "{}", s)
+            }
             _ => write!(f, "Executed successfully"),
         }
     }
@@ -112,6 +292,47 @@ pub enum BackendEvent<'s> {
     None,
 }
 
+impl BackendEvent<'_> {
+    fn error_mut(&mut self) -> Option<&mut String> {
+        match self {
+            BackendEvent::TaskCompleted {
+                execution_result: Err(error),
+                ..
+            }
+            | BackendEvent::TaskCompletedStateChange {
+                execution_result: Err(error),
+                ..
+            }
+            | BackendEvent::StrategyError { error }
+            | BackendEvent::StrategyCompleted {
+                result: StrategyCompletionResult::PartiallyCompleted { reason: error, .. },
+                ..
+            } => Some(error),
+            _ => None,
+        }
+    }
+
+    /// Replaces errors of rejected state transitions with an explanation of
+    /// the consensus error.
+    fn with_consensus_error_explained(mut self) -> Self {
+        if let Some(error) = self.error_mut() {
+            if let Some(explanation) = consensus_errors::explain(error) {
+                *error = explanation;
+            }
+        }
+        self
+    }
+
+    /// Appends the task correlation ID to errors, so failures shown in the UI
+    /// can be found in logs.
+    fn with_correlation_id(mut self, correlation_id: CorrelationId) -> Self {
+        if let Some(error) = self.error_mut() {
+            error.push_str(&format!(" (correlation ID {})", correlation_id));
+        }
+        self
+    }
+}
+
 /// Backend state update data on a specific field.
 /// A screen implementation may handle specific updates to deliver a responsive
 /// UI.
@@ -136,6 +357,14 @@ pub(crate) enum AppStateUpdate<'s> {
     IdentityCreditsTransferred,
     DPNSNameRegistered(String),
     DPNSNameRegistrationFailed,
+    KeepFunded(MutexGuard<'s, KeepFunded>),
+    ReorgWatch(MutexGuard<'s, ReorgWatch>),
+    FeeSettings(MutexGuard<'s, FeeSettings>),
+    Bookmarks(MutexGuard<'s, JsonStore<Bookmarks>>),
+    AssetLocks(MutexGuard<'s, JsonStore<AssetLocks>>),
+    WalletProfiles(MutexGuard<'s, JsonStore<WalletProfiles>>),
+    FundingPlan(MutexGuard<'s, Option<FundingPlan>>),
+    ChainStatus(ChainStatus),
 }
 
 /// Represents the result of completing a strategy.
@@ -154,10 +383,26 @@ pub(crate) enum StrategyCompletionResult {
         success_percent: u64,
         dash_spent_identity: f64,
         dash_spent_wallet: f64,
+        /// Explorer process usage, if it could be measured
+        resource_usage: Option<ResourceUsage>,
+        /// Transitions submitted by block height
+        block_transition_counts: BTreeMap<u64, u64>,
+        /// Transitions included by actual block height, if waited for
+        block_inclusion_counts: BTreeMap<u64, u64>,
+        /// Transitions deferred to later blocks by the pacer, by block height
+        block_deferred_counts: BTreeMap<u64, u64>,
+        /// Phases left out of the counts, rates and run time
+        phases: RunPhases,
     },
     PartiallyCompleted {
         reached_block_height: u64,
         reason: String,
+        start_block_height: u64,
+        transition_count: u64,
+        success_count: u64,
+        block_transition_counts: BTreeMap<u64, u64>,
+        block_inclusion_counts: BTreeMap<u64, u64>,
+        block_deferred_counts: BTreeMap<u64, u64>,
     },
 }
 
@@ -166,7 +411,14 @@ pub struct Backend<'a> {
     pub sdk: &'a Sdk,
     app_state: AppState,
     insight: InsightAPIClient,
+    core_rpc: CoreRpcClient,
+    /// Counts blocks proposed by evonodes if configured
+    tenderdash_rpc: Option<TenderdashRpcClient>,
     pub config: Config,
+    /// Serves tasks instead of the network if configured
+    simulator: Option<SimulatedPlatform>,
+    /// Options saved from the settings screen by full variable names
+    saved_options: Mutex<BTreeMap<String, String>>,
 }
 
 impl<'a> Backend<'a> {
@@ -175,7 +427,14 @@ impl<'a> Backend<'a> {
             sdk,
             app_state: AppState::load(&insight, &config).await,
             insight,
+            core_rpc: CoreRpcClient::new(&config),
+            tenderdash_rpc: TenderdashRpcClient::from_config(&config),
+            simulator: config
+                .simulated_platform_fixtures
+                .as_ref()
+                .map(|path| SimulatedPlatform::load(Path::new(path))),
             config,
+            saved_options: Mutex::new(BTreeMap::new()),
         }
     }
 
@@ -183,26 +442,148 @@ impl<'a> Backend<'a> {
         &self.app_state
     }
 
+    /// Options saved since the backend was created, to load the configuration
+    /// with on a profile switch.
+    pub async fn saved_options(&self) -> BTreeMap<String, String> {
+        self.saved_options.lock().await.clone()
+    }
+
+    /// Runs a task in the interactive lane, background tasks wait for it.
     pub async fn run_task(&self, task: Task) -> BackendEvent {
+        let _interactive = lanes::enter_interactive();
+        self.run(task).await
+    }
+
+    /// Runs a task in the background lane, once interactive tasks are done.
+    pub async fn run_background_task(&self, task: Task) -> BackendEvent {
+        lanes::background_turn().await;
+        self.run(task).await
+    }
+
+    async fn run(&self, task: Task) -> BackendEvent {
+        let correlation_id = CorrelationId::next();
+        let event = correlation_id
+            .scope(task.kind(), self.execute_task(task))
+            .await
+            .with_consensus_error_explained()
+            .with_correlation_id(correlation_id);
+        // Fetched contracts and identities are kept even if the app isn't
+        // closed properly
+        match &event {
+            BackendEvent::AppStateUpdated(AppStateUpdate::KnownContracts(known_contracts))
+            | BackendEvent::TaskCompletedStateChange {
+                app_state_update: AppStateUpdate::KnownContracts(known_contracts),
+                ..
+            } => {
+                self.app_state
+                    .save_known_entities(&self.config, Some(&**known_contracts))
+                    .await
+            }
+            BackendEvent::TaskCompleted {
+                task: Task::FetchIdentityById(_, true),
+                execution_result: Ok(_),
+            } => self.app_state.save_known_entities(&self.config, None).await,
+            _ => {}
+        }
+        if let Some(tutorial) = self.app_state.tutorial.lock().await.as_mut() {
+            tutorial.on_backend_event(&event);
+        }
+        event
+    }
+
+    /// Fetches an identity and adds it to known identities if asked to.
+    async fn fetch_identity<'s>(
+        &'s self,
+        platform: &impl Platform,
+        task: Task,
+        base58_id: &str,
+        add_to_known_identities: bool,
+    ) -> BackendEvent<'s> {
+        let execution_result = identities::fetch_identity_by_b58_id(platform, base58_id).await;
+        if add_to_known_identities {
+            if let Ok((Some(identity), _)) = &execution_result {
+                let mut loaded_identities = self.app_state.known_identities.lock().await;
+                loaded_identities.insert(identity.id(), identity.clone());
+            }
+        }
+
+        let execution_info_result =
+            execution_result.map(|(_, result_info)| CompletedTaskPayload::String(result_info));
+
+        BackendEvent::TaskCompleted {
+            task,
+            execution_result: execution_info_result,
+        }
+    }
+
+    /// Runs tasks the simulated platform can serve, others are refused.
+    async fn execute_simulated_task<'s>(
+        &'s self,
+        simulator: &SimulatedPlatform,
+        task: Task,
+    ) -> BackendEvent<'s> {
         match task {
             Task::FetchIdentityById(ref base58_id, add_to_known_identities) => {
-                let execution_result =
-                    identities::fetch_identity_by_b58_id(self.sdk, base58_id).await;
-                if add_to_known_identities {
-                    if let Ok((Some(identity), _)) = &execution_result {
-                        let mut loaded_identities = self.app_state.known_identities.lock().await;
-                        loaded_identities.insert(identity.id(), identity.clone());
-                    }
-                }
+                let base58_id = base58_id.clone();
+                self.fetch_identity(simulator, task, &base58_id, add_to_known_identities)
+                    .await
+            }
+            Task::Contract(ContractTask::FetchContract(contract_id_string)) => {
+                contracts::fetch_contract(
+                    simulator,
+                    &self.app_state.known_contracts,
+                    contract_id_string,
+                )
+                .await
+            }
+            Task::Document(
+                document_task @ (DocumentTask::QueryDocuments(_)
+                | DocumentTask::QueryDocumentsWithClauses(_)
+                | DocumentTask::PrefetchDocuments(_)
+                | DocumentTask::RefreshDocuments(_)),
+            ) => {
+                self.app_state
+                    .run_document_query_task(simulator, document_task)
+                    .await
+            }
+            Task::SetLogFilter(_) | Task::Settings(_) | Task::Tutorial(_) | Task::Search(_) => {
+                self.execute_live_task(task).await
+            }
+            // Nothing to poll, the status bar keeps showing no chain status
+            Task::PollChainStatus => BackendEvent::None,
+            task => BackendEvent::TaskCompleted {
+                task,
+                execution_result: Err("Not available with the simulated platform".to_owned()),
+            },
+        }
+    }
 
-                let execution_info_result = execution_result
-                    .map(|(_, result_info)| CompletedTaskPayload::String(result_info));
+    async fn execute_task(&self, task: Task) -> BackendEvent {
+        match &self.simulator {
+            Some(simulator) => self.execute_simulated_task(simulator, task).await,
+            None => self.execute_live_task(task).await,
+        }
+    }
 
+    async fn execute_live_task(&self, task: Task) -> BackendEvent {
+        match task {
+            Task::FetchIdentityById(ref base58_id, add_to_known_identities) => {
+                let base58_id = base58_id.clone();
+                self.fetch_identity(self.sdk, task, &base58_id, add_to_known_identities)
+                    .await
+            }
+            Task::SetLogFilter(ref directives) => {
+                let execution_result = logging::set_filter(directives)
+                    .map(|filter| format!("Log filter set to `{}`", filter).into());
                 BackendEvent::TaskCompleted {
                     task,
-                    execution_result: execution_info_result,
+                    execution_result,
                 }
             }
+            Task::Settings(settings_task) => {
+                let mut saved_options = self.saved_options.lock().await;
+                settings::run_settings_task(&self.config, &mut saved_options, settings_task)
+            }
             Task::Strategy(strategy_task) => {
                 strategies::run_strategy_task(
                     &self.sdk,
@@ -216,11 +597,19 @@ impl<'a> Backend<'a> {
                 wallet::run_wallet_task(
                     self.sdk,
                     &self.app_state.loaded_wallet,
+                    &self.app_state.fee_settings,
+                    &self.app_state.wallet_profiles,
                     wallet_task,
                     &self.insight,
+                    self.config.core_network(),
                 )
                 .await
             }
+            Task::Contract(ContractTask::RegisterContract(path)) => {
+                self.app_state
+                    .run_contract_registration(self.sdk, path)
+                    .await
+            }
             Task::Contract(contract_task) => {
                 contracts::run_contract_task(
                     self.sdk,
@@ -240,8 +629,57 @@ impl<'a> Backend<'a> {
                     .await
             }
             Task::PlatformInfo(platform_info_task) => {
-                platform_info::run_platform_task(self.sdk, platform_info_task).await
+                platform_info::run_platform_task(
+                    self.sdk,
+                    &self.app_state.network_changelog,
+                    platform_info_task,
+                )
+                .await
+            }
+            Task::SchemaBuilder(schema_builder_task) => {
+                self.app_state
+                    .run_schema_builder_task(self.sdk, schema_builder_task)
+                    .await
+            }
+            Task::KeepFunded(keep_funded_task) => {
+                self.app_state
+                    .run_keep_funded_task(self.sdk, keep_funded_task)
+                    .await
+            }
+            Task::Tutorial(tutorial_task) => self.app_state.run_tutorial_task(tutorial_task).await,
+            Task::Search(search_task) => self.app_state.run_search_task(search_task).await,
+            Task::Bookmark(bookmark_task) => {
+                self.app_state
+                    .run_bookmark_task(self.sdk, bookmark_task)
+                    .await
+            }
+            Task::AssetLock(asset_lock_task) => {
+                self.app_state
+                    .run_asset_lock_task(self.sdk, &self.insight, asset_lock_task)
+                    .await
+            }
+            Task::Network(network_task) => {
+                network::run_network_task(
+                    self.sdk,
+                    &self.core_rpc,
+                    self.tenderdash_rpc.as_ref(),
+                    &self.app_state.evonode_rewards,
+                    self.config.evonode_pro_tx_hash.as_deref(),
+                    network_task,
+                )
+                .await
+            }
+            Task::Export(export_task) => export::run_export_task(self.sdk, export_task).await,
+            Task::FetchWithProof(fetch) => proofs::run_proved_fetch(self.sdk, fetch).await,
+            Task::CheckReorgs => self.app_state.check_reorgs(&self.insight).await,
+            Task::WarmUpConnections => {
+                connections::warm_up_connections(self.sdk, self.config.dapi_addresses_count())
+                    .await
             }
+            Task::PollChainStatus => BackendEvent::AppStateUpdated(AppStateUpdate::ChainStatus(
+                chain_status::fetch_chain_status(self.sdk, &self.core_rpc, &self.config.network)
+                    .await,
+            )),
         }
     }
 }
@@ -0,0 +1,468 @@
+//! Asset locks of the wallet that can still fund an identity.
+//! Every asset lock transaction made for a registration or a top up is
+//! recorded with its one-time credit output key in a JSON file of the profile,
+//! so a lock left behind by an interrupted run isn't lost. Asset locks of
+//! other tools are found among the wallet address transactions when their
+//! credit output pays to the wallet key.
+//!
+//! A lock is unused if it is known to the network and no identity was
+//! registered with it yet. Platform doesn't tell whether a lock was spent on a
+//! top up, so such locks are only known as used if topped up from here.
+//!
+//! Funds of asset lock transactions left the wallet UTXOs but aren't credits
+//! yet, so recorded locks known to the network and not used are reported as
+//! the locked part of the wallet balance.
+//!
+//! The proof of a registration's asset lock is recorded as soon as it's
+//! obtained, since the app state keeping the registration in progress is only
+//! saved on exit. A registration stopped between the broadcast and the
+//! identity create transition continues from the recorded proof.
+
+mod inspector;
+
+use std::{
+    collections::BTreeMap,
+    fmt::{self, Display},
+    path::PathBuf,
+};
+
+use chrono_humanize::{Accuracy, HumanTime, Tense};
+use dash_sdk::{platform::Fetch, Sdk};
+use dpp::{
+    dashcore::{
+        consensus::{deserialize, serialize},
+        hashes::Hash,
+        transaction::special_transaction::TransactionPayload,
+        Network, PrivateKey, ScriptBuf, Transaction, Txid,
+    },
+    identity::{accessors::IdentityGettersV0, state_transition::asset_lock_proof::AssetLockProof},
+    platform_value::string_encoding::Encoding,
+    prelude::{Identifier, Identity},
+    util::hash::hash_double,
+};
+use serde::{Deserialize, Serialize};
+
+use super::{
+    insight::InsightAPIClient, json_store::JsonStore, reports::current_timestamp, AppState, AppStateUpdate, BackendEvent,
+    CompletedTaskPayload, Task, Wallet,
+};
+
+/// Wallet address transactions looked through for asset locks of other tools.
+const ADDRESS_TRANSACTIONS_LIMIT: usize = 50;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) enum AssetLockSource {
+    /// Made by the explorer for a registration or a top up
+    Explorer,
+    /// Found among the wallet address transactions
+    WalletAddress,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct AssetLockRecord {
+    /// Hex encoded signed transaction
+    pub transaction: String,
+    /// Hex encoded private key of the credit output
+    pub private_key: String,
+    /// Locked amount in duffs
+    pub amount: u64,
+    pub created_at: u64,
+    pub source: AssetLockSource,
+    /// Consumed by a registration or a top up
+    pub used: bool,
+    /// Hex encoded proof, recorded once obtained by a registration
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub proof: Option<String>,
+}
+
+/// An asset lock found unused on the last check, offered for registration.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct UnusedAssetLock {
+    pub txid: String,
+    pub amount: u64,
+    pub created_at: u64,
+    pub source: AssetLockSource,
+}
+
+impl Display for UnusedAssetLock {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let created_ago = current_timestamp().saturating_sub(self.created_at);
+        let created = HumanTime::from(chrono::Duration::seconds(created_ago as i64))
+            .to_text_en(Accuracy::Rough, Tense::Past);
+        write!(
+            f,
+            "{:.8} Dash, {}…, {}",
+            self.amount as f64 / 100_000_000.0,
+            &self.txid[..16],
+            match self.source {
+                AssetLockSource::Explorer => format!("created {}", created),
+                AssetLockSource::WalletAddress => format!("found {}", created),
+            }
+        )
+    }
+}
+
+/// Wallet funds held by asset locks not used yet, as of the last check.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct LockedFunds {
+    /// Locked amount in duffs by asset lock transaction ID
+    pub amounts: BTreeMap<String, u64>,
+    pub checked_at: u64,
+}
+
+impl LockedFunds {
+    pub(crate) fn total(&self) -> u64 {
+        self.amounts.values().sum()
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub(crate) struct AssetLocks {
+    #[serde(skip)]
+    network: Option<Network>,
+    records: BTreeMap<String, AssetLockRecord>,
+    /// Result of the last check
+    #[serde(skip)]
+    unused: Vec<UnusedAssetLock>,
+    /// Result of the last locked funds check
+    #[serde(skip)]
+    locked: Option<LockedFunds>,
+}
+
+impl JsonStore<AssetLocks> {
+    /// Records an asset lock transaction made by the explorer.
+    pub(crate) fn record(&mut self, transaction: &Transaction, private_key: &PrivateKey) {
+        self.insert(transaction, private_key, AssetLockSource::Explorer);
+        self.save_or_log();
+    }
+
+    /// Records the proof of an asset lock right away, so the registration
+    /// continues from it even if the app doesn't exit cleanly.
+    pub(crate) fn record_proof(&mut self, txid: &Txid, proof: &AssetLockProof) {
+        let Some(record) = self.records.get_mut(&txid.to_string()) else {
+            return;
+        };
+        match bincode::encode_to_vec(proof, bincode::config::standard()) {
+            Ok(bytes) => {
+                record.proof = Some(hex::encode(bytes));
+                self.save_or_log();
+            }
+            Err(e) => tracing::error!("Unable to encode asset lock proof: {}", e),
+        }
+    }
+
+    /// Marks the asset lock as consumed by a registration or a top up.
+    pub(crate) fn mark_used(&mut self, txid: &Txid) {
+        let txid = txid.to_string();
+        if let Some(record) = self.records.get_mut(&txid) {
+            record.used = true;
+            self.unused.retain(|lock| lock.txid != txid);
+            if let Some(locked) = &mut self.locked {
+                locked.amounts.remove(&txid);
+            }
+            self.save_or_log();
+        }
+    }
+}
+
+impl AssetLocks {
+    /// Asset locks of the file, their keys are decoded for the network.
+    pub(crate) fn load(path: PathBuf, network: Network) -> JsonStore<Self> {
+        let mut asset_locks: JsonStore<Self> = JsonStore::load(path, "asset locks");
+        asset_locks.network = Some(network);
+        asset_locks
+    }
+
+    fn insert(
+        &mut self,
+        transaction: &Transaction,
+        private_key: &PrivateKey,
+        source: AssetLockSource,
+    ) {
+        self.records
+            .entry(transaction.txid().to_string())
+            .or_insert_with(|| AssetLockRecord {
+                transaction: hex::encode(serialize(transaction)),
+                private_key: hex::encode(private_key.inner.secret_bytes()),
+                amount: credit_output(transaction).map_or(0, |(amount, _)| amount),
+                created_at: current_timestamp(),
+                source,
+                used: false,
+                proof: None,
+            });
+    }
+
+    /// Recorded proof of an asset lock.
+    pub(crate) fn proof(&self, txid: &str) -> Option<AssetLockProof> {
+        let bytes = hex::decode(self.records.get(txid)?.proof.as_ref()?).ok()?;
+        bincode::decode_from_slice(&bytes, bincode::config::standard())
+            .map(|(proof, _)| proof)
+            .map_err(|e| tracing::error!("Unable to decode proof of asset lock {}: {}", txid, e))
+            .ok()
+    }
+
+    /// Latest asset lock with a proof that no identity was registered with
+    /// yet, left behind by an interrupted registration.
+    pub(crate) fn pending_registration(&self) -> Option<(Transaction, PrivateKey, AssetLockProof)> {
+        let (txid, _) = self
+            .records
+            .iter()
+            .filter(|(_, record)| !record.used && record.proof.is_some())
+            .max_by_key(|(_, record)| record.created_at)?;
+        let (transaction, private_key, _) = self.get(txid).ok()?;
+        Some((transaction, private_key, self.proof(txid)?))
+    }
+
+    pub(crate) fn unused(&self) -> &[UnusedAssetLock] {
+        &self.unused
+    }
+
+    pub(crate) fn locked(&self) -> Option<&LockedFunds> {
+        self.locked.as_ref()
+    }
+
+    /// Transaction, credit output key and amount of a recorded asset lock.
+    pub(crate) fn get(&self, txid: &str) -> Result<(Transaction, PrivateKey, u64), String> {
+        let record = self
+            .records
+            .get(txid)
+            .ok_or_else(|| format!("Unknown asset lock {}", txid))?;
+        if record.used {
+            return Err(format!("Asset lock {} is already used", txid));
+        }
+        let transaction = hex::decode(&record.transaction)
+            .ok()
+            .and_then(|bytes| deserialize::<Transaction>(&bytes).ok())
+            .ok_or_else(|| format!("Invalid transaction of asset lock {}", txid))?;
+        let private_key = hex::decode(&record.private_key)
+            .ok()
+            .and_then(|bytes| {
+                PrivateKey::from_slice(&bytes, self.network.unwrap_or(Network::Testnet)).ok()
+            })
+            .ok_or_else(|| format!("Invalid private key of asset lock {}", txid))?;
+        Ok((transaction, private_key, record.amount))
+    }
+}
+
+/// Amount and script of the asset lock transaction's credit output.
+fn credit_output(transaction: &Transaction) -> Option<(u64, &ScriptBuf)> {
+    match &transaction.special_transaction_payload {
+        Some(TransactionPayload::AssetLockPayloadType(payload)) => payload
+            .credit_outputs
+            .first()
+            .map(|output| (output.value, &output.script_pubkey)),
+        _ => None,
+    }
+}
+
+/// Id of the identity registered with the asset lock, hash of the credit
+/// outpoint.
+fn identity_id(txid: &Txid) -> Identifier {
+    let mut out_point = txid.to_byte_array().to_vec();
+    out_point.extend_from_slice(&0u32.to_le_bytes());
+    Identifier::from_bytes(&hash_double(out_point)).expect("double SHA-256 is 32 bytes")
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum AssetLockTask {
+    /// Look for asset locks of the wallet that can fund a registration
+    FindUnused,
+    /// Sum up wallet funds held by asset locks not used yet
+    CheckLocked,
+    /// Show the asset locks of the registration and top up in progress
+    Inspect,
+    /// Check again the proofs of the asset locks in progress, switching to
+    /// chain lock proofs where possible
+    RefetchProofs,
+}
+
+impl AppState {
+    pub(crate) async fn run_asset_lock_task<'s>(
+        &'s self,
+        sdk: &Sdk,
+        insight: &InsightAPIClient,
+        task: AssetLockTask,
+    ) -> BackendEvent<'s> {
+        match task {
+            AssetLockTask::FindUnused => {
+                let execution_result = self.find_unused_asset_locks(sdk, insight).await;
+                BackendEvent::TaskCompletedStateChange {
+                    task: Task::AssetLock(task),
+                    execution_result,
+                    app_state_update: AppStateUpdate::AssetLocks(self.asset_locks.lock().await),
+                }
+            }
+            AssetLockTask::CheckLocked => {
+                let execution_result = self.check_locked_funds(insight).await;
+                BackendEvent::TaskCompletedStateChange {
+                    task: Task::AssetLock(task),
+                    execution_result,
+                    app_state_update: AppStateUpdate::AssetLocks(self.asset_locks.lock().await),
+                }
+            }
+            AssetLockTask::Inspect | AssetLockTask::RefetchProofs => {
+                let refetch_proofs = task == AssetLockTask::RefetchProofs;
+                BackendEvent::TaskCompleted {
+                    execution_result: self.inspect_asset_locks(sdk, refetch_proofs).await,
+                    task: Task::AssetLock(task),
+                }
+            }
+        }
+    }
+
+    /// Only checks that recorded locks not known as used were broadcasted,
+    /// finding unused asset locks also tells which ones funded an identity.
+    async fn check_locked_funds(
+        &self,
+        insight: &InsightAPIClient,
+    ) -> Result<CompletedTaskPayload, String> {
+        let mut asset_locks = self.asset_locks.lock().await;
+
+        let mut amounts = BTreeMap::new();
+        let mut failed = 0;
+        for (txid, record) in asset_locks
+            .records
+            .iter()
+            .filter(|(_, record)| !record.used)
+        {
+            let Ok(parsed_txid) = txid.parse::<Txid>() else {
+                continue;
+            };
+            match insight.transaction_confirmations(&parsed_txid).await {
+                Ok(Some(_)) => {
+                    amounts.insert(txid.clone(), record.amount);
+                }
+                // Never broadcasted or dropped, the funds are still in the wallet
+                Ok(None) => {}
+                Err(e) => {
+                    tracing::warn!("Unable to check asset lock {}: {}", txid, e);
+                    failed += 1;
+                }
+            }
+        }
+        let locked = LockedFunds {
+            amounts,
+            checked_at: current_timestamp(),
+        };
+
+        let mut message = format!(
+            "{:.8} Dash locked in {} asset locks",
+            locked.total() as f64 / 100_000_000.0,
+            locked.amounts.len()
+        );
+        if failed > 0 {
+            message.push_str(&format!(", {} couldn't be checked", failed));
+        }
+        asset_locks.locked = Some(locked);
+        Ok(message.into())
+    }
+
+    async fn find_unused_asset_locks(
+        &self,
+        sdk: &Sdk,
+        insight: &InsightAPIClient,
+    ) -> Result<CompletedTaskPayload, String> {
+        let wallet_key = match self.loaded_wallet.lock().await.as_ref() {
+            Some(Wallet::SingleKeyWallet(wallet)) => Some((
+                wallet.address.clone(),
+                wallet.private_key,
+                ScriptBuf::new_p2pkh(&wallet.public_key.pubkey_hash()),
+            )),
+            None => None,
+        };
+
+        // Asset locks being consumed are continued the usual way
+        let in_progress: Vec<String> = [
+            self.identity_asset_lock_private_key_in_creation
+                .lock()
+                .await
+                .as_ref()
+                .map(|(transaction, ..)| transaction.txid().to_string()),
+            self.identity_asset_lock_private_key_in_top_up
+                .lock()
+                .await
+                .as_ref()
+                .map(|(transaction, ..)| transaction.txid().to_string()),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+
+        let mut asset_locks = self.asset_locks.lock().await;
+
+        if let Some((address, private_key, script)) = wallet_key {
+            // Other tools' locks are a bonus, the explorer's own are still
+            // checked if the lookup fails
+            match insight
+                .asset_lock_transactions_for_address(&address, ADDRESS_TRANSACTIONS_LIMIT)
+                .await
+            {
+                Ok(transactions) => {
+                    for transaction in transactions {
+                        if credit_output(&transaction)
+                            .is_some_and(|(_, credit_script)| credit_script == &script)
+                        {
+                            asset_locks.insert(
+                                &transaction,
+                                &private_key,
+                                AssetLockSource::WalletAddress,
+                            );
+                        }
+                    }
+                }
+                Err(e) => tracing::warn!("Unable to look up wallet asset locks: {}", e),
+            }
+        }
+
+        let mut unused = Vec::new();
+        let mut failed = 0;
+        for (txid, record) in asset_locks.records.iter_mut() {
+            if record.used || in_progress.contains(txid) {
+                continue;
+            }
+            let Ok(parsed_txid) = txid.parse::<Txid>() else {
+                continue;
+            };
+
+            match insight.transaction_confirmations(&parsed_txid).await {
+                Ok(Some(_)) => {}
+                // Never broadcasted or dropped, its inputs may be spent since
+                Ok(None) => continue,
+                Err(e) => {
+                    tracing::warn!("Unable to check asset lock {}: {}", txid, e);
+                    failed += 1;
+                    continue;
+                }
+            }
+
+            match Identity::fetch(sdk, identity_id(&parsed_txid)).await {
+                Ok(Some(identity)) => {
+                    tracing::info!(
+                        "Asset lock {} was used for identity {}",
+                        txid,
+                        identity.id().to_string(Encoding::Base58)
+                    );
+                    record.used = true;
+                }
+                Ok(None) => unused.push(UnusedAssetLock {
+                    txid: txid.clone(),
+                    amount: record.amount,
+                    created_at: record.created_at,
+                    source: record.source.clone(),
+                }),
+                Err(e) => {
+                    tracing::warn!("Unable to check asset lock {}: {}", txid, e);
+                    failed += 1;
+                }
+            }
+        }
+        asset_locks.unused = unused;
+        asset_locks.save_or_log();
+
+        let mut message = format!("Found {} unused asset locks", asset_locks.unused.len());
+        if failed > 0 {
+            message.push_str(&format!(", {} couldn't be checked", failed));
+        }
+        Ok(message.into())
+    }
+}
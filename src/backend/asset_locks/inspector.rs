@@ -0,0 +1,220 @@
+//! Inspection of the asset locks of the identity registration and top up in
+//! progress.
+//! An interrupted registration or top up keeps its asset lock transaction and
+//! the proof once obtained, so it continues where it stopped. The inspector
+//! shows them along with their status on the network to tell why one is stuck.
+//! Re-fetching the proof status gives the asset lock a chain lock proof as
+//! soon as its transaction is chain locked and Platform has caught up with its
+//! core height, which helps when the instant send lock never came or Platform
+//! keeps rejecting it.
+
+use dapi_grpc::core::v0::{GetTransactionRequest, GetTransactionResponse};
+use dash_sdk::Sdk;
+use dpp::{
+    dashcore::{consensus::serialize, OutPoint, Transaction},
+    identity::state_transition::asset_lock_proof::{chain::ChainAssetLockProof, AssetLockProof},
+    platform_value::string_encoding::Encoding,
+};
+use rs_dapi_client::{DapiRequestExecutor, RequestSettings};
+
+use super::{credit_output, identity_id};
+use crate::backend::{strategies::pacing::fetch_chain_tip, AppState, CompletedTaskPayload};
+
+fn yes_no(flag: bool) -> &'static str {
+    if flag {
+        "yes"
+    } else {
+        "no"
+    }
+}
+
+/// Details and status of an asset lock, its proof is replaced by a chain lock
+/// proof if `refetch_proof` is set and one can be used.
+async fn inspect_asset_lock(
+    sdk: &Sdk,
+    title: &str,
+    transaction: &Transaction,
+    proof: &mut Option<AssetLockProof>,
+    platform_core_height: &Result<u32, String>,
+    refetch_proof: bool,
+) -> String {
+    let txid = transaction.txid();
+    let mut lines = vec![title.to_owned(), format!("  Transaction: {}", txid)];
+    if let Some((amount, _)) = credit_output(transaction) {
+        lines.push(format!(
+            "  Locked: {:.8} Dash in credit output 0",
+            amount as f64 / 100_000_000.0
+        ));
+    }
+    lines.push(format!(
+        "  Inputs: {}, outputs: {}",
+        transaction.input.len(),
+        transaction.output.len()
+    ));
+    lines.push(format!(
+        "  Identity ID of the credit output: {}",
+        identity_id(&txid).to_string(Encoding::Base58)
+    ));
+
+    let status = sdk
+        .execute(
+            GetTransactionRequest {
+                id: txid.to_string(),
+            },
+            RequestSettings::default(),
+        )
+        .await
+        .map_err(|e| e.to_string());
+    match &status {
+        Ok(GetTransactionResponse {
+            height,
+            confirmations,
+            is_instant_locked,
+            is_chain_locked,
+            ..
+        }) => lines.push(format!(
+            "  Network: {} confirmations{}, instant locked: {}, chain locked: {}",
+            confirmations,
+            if *confirmations > 0 {
+                format!(" at core height {}", height)
+            } else {
+                String::new()
+            },
+            yes_no(*is_instant_locked),
+            yes_no(*is_chain_locked)
+        )),
+        Err(e) => lines.push(format!("  Network: transaction unknown ({})", e)),
+    }
+    match platform_core_height {
+        Ok(height) => lines.push(format!("  Platform core height: {}", height)),
+        Err(e) => lines.push(format!("  Platform core height: unknown ({})", e)),
+    }
+
+    let chain_proof = match (&status, platform_core_height) {
+        (Ok(response), Ok(platform_core_height))
+            if response.is_chain_locked && *platform_core_height >= response.height =>
+        {
+            Some(AssetLockProof::Chain(ChainAssetLockProof {
+                core_chain_locked_height: response.height,
+                // Funds of asset lock transactions are in the first output
+                out_point: OutPoint { txid, vout: 0 },
+            }))
+        }
+        _ => None,
+    };
+    let has_chain_proof = matches!(proof, Some(AssetLockProof::Chain(_)));
+    let switched = refetch_proof && chain_proof.is_some() && !has_chain_proof;
+    if switched {
+        *proof = chain_proof.clone();
+    }
+
+    match proof {
+        None => lines.push("  Proof: none yet".to_owned()),
+        Some(AssetLockProof::Instant(instant_proof)) => {
+            lines.push(format!(
+                "  Proof: instant send lock, output index {}",
+                instant_proof.output_index()
+            ));
+            lines.push(format!(
+                "  Instant send lock: {}",
+                hex::encode(serialize(instant_proof.instant_lock()))
+            ));
+        }
+        Some(AssetLockProof::Chain(chain_proof)) => lines.push(format!(
+            "  Proof: chain lock at core height {}, outpoint {}",
+            chain_proof.core_chain_locked_height, chain_proof.out_point
+        )),
+    }
+
+    let diagnosis = match &status {
+        _ if switched => "Chain lock proof obtained, continue to use it".to_owned(),
+        Err(_) => "The transaction is broadcast again when continued".to_owned(),
+        Ok(_) if has_chain_proof => "Continue to use the chain lock proof".to_owned(),
+        Ok(_) if chain_proof.is_some() => {
+            "Chain locked for Platform, re-fetch the proof status to use a chain lock proof"
+                .to_owned()
+        }
+        Ok(response) if response.is_chain_locked => format!(
+            "Chain locked, waiting for Platform to reach core height {}",
+            response.height
+        ),
+        Ok(_) if proof.is_some() => {
+            "Continue to use the instant send lock proof, if Platform rejects it re-fetch the \
+             proof status once the transaction is chain locked"
+                .to_owned()
+        }
+        Ok(response) if response.confirmations == 0 && !response.is_instant_locked => {
+            "Waiting for an instant send lock or for the transaction to be mined".to_owned()
+        }
+        Ok(_) => "Waiting for the chain lock of the transaction".to_owned(),
+    };
+    lines.push(format!("  {}", diagnosis));
+    lines.push(format!(
+        "  Raw transaction: {}",
+        hex::encode(serialize(transaction))
+    ));
+    lines.join("\n")
+}
+
+impl AppState {
+    /// Report of the asset locks in progress, with their proofs re-fetched if
+    /// `refetch_proofs` is set.
+    pub(super) async fn inspect_asset_locks(
+        &self,
+        sdk: &Sdk,
+        refetch_proofs: bool,
+    ) -> Result<CompletedTaskPayload, String> {
+        let platform_core_height = fetch_chain_tip(sdk).await.map(|tip| tip.core_height);
+        let mut sections = Vec::new();
+
+        // A running registration or top up holds its state while waiting for
+        // the proof, so it's not waited for
+        match self.identity_asset_lock_private_key_in_creation.try_lock() {
+            Ok(mut in_creation) => {
+                if let Some((transaction, _, proof, _)) = in_creation.as_mut() {
+                    sections.push(
+                        inspect_asset_lock(
+                            sdk,
+                            "Identity registration",
+                            transaction,
+                            proof,
+                            &platform_core_height,
+                            refetch_proofs,
+                        )
+                        .await,
+                    );
+                }
+            }
+            Err(_) => sections.push(
+                "Identity registration is running, its asset lock can be inspected once it stops"
+                    .to_owned(),
+            ),
+        }
+        match self.identity_asset_lock_private_key_in_top_up.try_lock() {
+            Ok(mut in_top_up) => {
+                if let Some((transaction, _, proof)) = in_top_up.as_mut() {
+                    sections.push(
+                        inspect_asset_lock(
+                            sdk,
+                            "Identity top up",
+                            transaction,
+                            proof,
+                            &platform_core_height,
+                            refetch_proofs,
+                        )
+                        .await,
+                    );
+                }
+            }
+            Err(_) => sections.push(
+                "Identity top up is running, its asset lock can be inspected once it stops"
+                    .to_owned(),
+            ),
+        }
+
+        if sections.is_empty() {
+            sections.push("No identity registration or top up in progress".to_owned());
+        }
+        Ok(sections.join("\n\n").into())
+    }
+}
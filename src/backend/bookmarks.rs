@@ -0,0 +1,263 @@
+//! Named bookmarks of documents.
+//! A bookmark refers a document by its contract, document type and id and
+//! carries a note. Bookmarks are kept in a JSON file of the profile and can be
+//! re-fetched all at once to find out which documents were updated or deleted
+//! since.
+
+use std::collections::BTreeMap;
+
+use chrono_humanize::{Accuracy, HumanTime, Tense};
+use dash_sdk::{
+    platform::{DocumentQuery, Fetch},
+    Sdk,
+};
+use dpp::{
+    data_contract::accessors::v0::DataContractV0Getters,
+    document::{Document, DocumentV0Getters},
+    platform_value::string_encoding::Encoding,
+    prelude::{DataContract, Identifier},
+};
+use serde::{Deserialize, Serialize};
+
+use super::{
+    reports::current_timestamp, AppState, AppStateUpdate, BackendEvent, CompletedTaskPayload, Task,
+};
+
+/// Bookmarks not checked for this many seconds are shown as stale.
+const STALE_AFTER_SECONDS: u64 = 24 * 60 * 60;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct DocumentBookmark {
+    /// Base58 encoded data contract id
+    pub contract_id: String,
+    pub document_type_name: String,
+    /// Base58 encoded document id
+    pub document_id: String,
+    pub note: String,
+    pub created_at: u64,
+    /// Revision of the document when bookmarked
+    pub bookmarked_revision: Option<u64>,
+    /// Revision of the document on the last check
+    pub latest_revision: Option<u64>,
+    /// Timestamp of the last successful check
+    pub checked_at: u64,
+    /// The document was not found on the last check
+    pub missing: bool,
+    pub last_error: Option<String>,
+}
+
+impl DocumentBookmark {
+    fn changed(&self) -> bool {
+        self.missing || self.latest_revision != self.bookmarked_revision
+    }
+
+    fn status(&self) -> &'static str {
+        if self.missing {
+            "deleted"
+        } else if self.changed() {
+            "updated"
+        } else {
+            "unchanged"
+        }
+    }
+
+    fn display(&self, name: &str, now: u64) -> String {
+        let checked_ago = now.saturating_sub(self.checked_at);
+        let checked = HumanTime::from(chrono::Duration::seconds(checked_ago as i64))
+            .to_text_en(Accuracy::Rough, Tense::Past);
+        let stale = if checked_ago >= STALE_AFTER_SECONDS {
+            " [stale]"
+        } else {
+            ""
+        };
+        let revision = |revision: Option<u64>| {
+            revision
+                .map(|r| r.to_string())
+                .unwrap_or_else(|| "-".to_owned())
+        };
+
+        let mut lines = vec![
+            format!("{}: {}, checked {}{}", name, self.status(), checked, stale),
+            format!(
+                "    {} / {} / {}",
+                self.contract_id, self.document_type_name, self.document_id
+            ),
+            format!(
+                "    revision: {} -> {}",
+                revision(self.bookmarked_revision),
+                revision(self.latest_revision)
+            ),
+        ];
+        if !self.note.is_empty() {
+            lines.push(format!("    note: {}", self.note));
+        }
+        if let Some(error) = &self.last_error {
+            lines.push(format!("    last check failed: {}", error));
+        }
+        lines.join("\n")
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub(crate) struct Bookmarks {
+    bookmarks: BTreeMap<String, DocumentBookmark>,
+}
+
+impl Bookmarks {
+    pub(crate) fn names(&self) -> Vec<String> {
+        self.bookmarks.keys().cloned().collect()
+    }
+
+    pub(crate) fn display(&self) -> String {
+        if self.bookmarks.is_empty() {
+            return "No bookmarks yet, bookmark a document from a documents queryset".to_owned();
+        }
+        let now = current_timestamp();
+        self.bookmarks
+            .iter()
+            .map(|(name, bookmark)| bookmark.display(name, now))
+            .collect::<Vec<_>>()
+            .join("\n\n")
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(crate) enum BookmarkTask {
+    Add {
+        name: String,
+        note: String,
+        query: DocumentQuery,
+        document: Document,
+    },
+    Remove(String),
+    /// Fetch every bookmarked document again to check for updates
+    RefreshAll,
+}
+
+impl AppState {
+    pub(crate) async fn run_bookmark_task<'s>(
+        &'s self,
+        sdk: &Sdk,
+        task: BookmarkTask,
+    ) -> BackendEvent<'s> {
+        let execution_result = match &task {
+            BookmarkTask::Add {
+                name,
+                note,
+                query,
+                document,
+            } => {
+                let now = current_timestamp();
+                let mut bookmarks = self.bookmarks.lock().await;
+                bookmarks.bookmarks.insert(
+                    name.clone(),
+                    DocumentBookmark {
+                        contract_id: query.data_contract.id().to_string(Encoding::Base58),
+                        document_type_name: query.document_type_name.clone(),
+                        document_id: document.id().to_string(Encoding::Base58),
+                        note: note.clone(),
+                        created_at: now,
+                        bookmarked_revision: document.revision(),
+                        latest_revision: document.revision(),
+                        checked_at: now,
+                        missing: false,
+                        last_error: None,
+                    },
+                );
+                bookmarks
+                    .save()
+                    .map(|_| format!("Bookmarked document as {}", name).into())
+            }
+            BookmarkTask::Remove(name) => {
+                let mut bookmarks = self.bookmarks.lock().await;
+                if bookmarks.bookmarks.remove(name).is_some() {
+                    bookmarks
+                        .save()
+                        .map(|_| format!("Removed bookmark {}", name).into())
+                } else {
+                    Err(format!("No bookmark named {}", name))
+                }
+            }
+            BookmarkTask::RefreshAll => self.refresh_bookmarks(sdk).await,
+        };
+
+        match execution_result {
+            Ok(_) => BackendEvent::TaskCompletedStateChange {
+                task: Task::Bookmark(task),
+                execution_result,
+                app_state_update: AppStateUpdate::Bookmarks(self.bookmarks.lock().await),
+            },
+            Err(_) => BackendEvent::TaskCompleted {
+                task: Task::Bookmark(task),
+                execution_result,
+            },
+        }
+    }
+
+    async fn refresh_bookmarks(&self, sdk: &Sdk) -> Result<CompletedTaskPayload, String> {
+        let mut bookmarks = self.bookmarks.lock().await;
+        let mut contracts: BTreeMap<String, DataContract> = self
+            .known_contracts
+            .lock()
+            .await
+            .values()
+            .map(|contract| (contract.id().to_string(Encoding::Base58), contract.clone()))
+            .collect();
+
+        let mut updated = 0;
+        let mut failed = 0;
+        for bookmark in bookmarks.bookmarks.values_mut() {
+            match fetch_bookmarked_document(sdk, &mut contracts, bookmark).await {
+                Ok(document) => {
+                    bookmark.checked_at = current_timestamp();
+                    bookmark.missing = document.is_none();
+                    bookmark.latest_revision = document.and_then(|d| d.revision());
+                    bookmark.last_error = None;
+                    if bookmark.changed() {
+                        updated += 1;
+                    }
+                }
+                Err(e) => {
+                    bookmark.last_error = Some(e);
+                    failed += 1;
+                }
+            }
+        }
+        bookmarks.save()?;
+
+        Ok(format!(
+            "Checked {} bookmarks: {} updated or deleted, {} failed",
+            bookmarks.bookmarks.len(),
+            updated,
+            failed
+        )
+        .into())
+    }
+}
+
+/// Fetches the document of a bookmark, contracts not known yet are fetched
+/// once and reused for other bookmarks.
+async fn fetch_bookmarked_document(
+    sdk: &Sdk,
+    contracts: &mut BTreeMap<String, DataContract>,
+    bookmark: &DocumentBookmark,
+) -> Result<Option<Document>, String> {
+    if !contracts.contains_key(&bookmark.contract_id) {
+        let contract_id = Identifier::from_string(&bookmark.contract_id, Encoding::Base58)
+            .map_err(|e| format!("Invalid contract id: {}", e))?;
+        let contract = DataContract::fetch(sdk, contract_id)
+            .await
+            .map_err(|e| e.to_string())?
+            .ok_or_else(|| "Data contract not found".to_owned())?;
+        contracts.insert(bookmark.contract_id.clone(), contract);
+    }
+    let contract = contracts[&bookmark.contract_id].clone();
+
+    let document_id = Identifier::from_string(&bookmark.document_id, Encoding::Base58)
+        .map_err(|e| format!("Invalid document id: {}", e))?;
+    let query = DocumentQuery::new(contract, &bookmark.document_type_name)
+        .map_err(|e| e.to_string())?
+        .with_document_id(&document_id);
+
+    Document::fetch(sdk, query).await.map_err(|e| e.to_string())
+}
@@ -0,0 +1,84 @@
+//! Chain status shown in the status bar.
+//! Core and Platform are polled independently by the application loop in
+//! background, one of them being unreachable doesn't hide the other's height.
+
+use dash_sdk::{platform::types::epoch::ExtendedEpochInfoEx, Sdk};
+use dpp::block::extended_epoch_info::ExtendedEpochInfo;
+use serde_json::{json, Value as JsonValue};
+
+use super::core_rpc::CoreRpcClient;
+
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ChainStatus {
+    /// Chain name reported by Core, the configured network if Core is
+    /// unreachable
+    pub(crate) network: String,
+    pub(crate) core: Option<CoreChainStatus>,
+    pub(crate) platform_height: Option<u64>,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct CoreChainStatus {
+    pub(crate) height: u64,
+    /// Verification progress from 0 to 1 while Core catches up with the
+    /// chain, `None` once synced
+    pub(crate) sync_progress: Option<f64>,
+}
+
+async fn fetch_core_status(core_rpc: &CoreRpcClient) -> Result<(String, CoreChainStatus), String> {
+    let info = core_rpc.call("getblockchaininfo", json!([])).await?;
+    let field = |key: &str| {
+        info.get(key)
+            .ok_or_else(|| format!("Invalid Core RPC response: no {}", key))
+    };
+
+    let chain = field("chain")?.as_str().unwrap_or_default().to_owned();
+    let height = field("blocks")?.as_u64().unwrap_or_default();
+    let headers = field("headers")?.as_u64().unwrap_or_default();
+    let initial_download = field("initialblockdownload")?.as_bool().unwrap_or_default();
+    let sync_progress = (initial_download || height < headers).then(|| {
+        info.get("verificationprogress")
+            .and_then(JsonValue::as_f64)
+            .unwrap_or_default()
+    });
+
+    Ok((
+        chain,
+        CoreChainStatus {
+            height,
+            sync_progress,
+        },
+    ))
+}
+
+pub(super) async fn fetch_chain_status(
+    sdk: &Sdk,
+    core_rpc: &CoreRpcClient,
+    configured_network: &str,
+) -> ChainStatus {
+    let (core_status, platform_status) = futures::join!(
+        fetch_core_status(core_rpc),
+        ExtendedEpochInfo::fetch_current_with_metadata(sdk)
+    );
+
+    let (network, core) = match core_status {
+        Ok((chain, core)) => (chain, Some(core)),
+        Err(e) => {
+            tracing::warn!("chain status: unable to poll Core: {}", e);
+            (configured_network.to_owned(), None)
+        }
+    };
+    let platform_height = match platform_status {
+        Ok((_, metadata)) => Some(metadata.height),
+        Err(e) => {
+            tracing::warn!("chain status: unable to poll Platform: {}", e);
+            None
+        }
+    };
+
+    ChainStatus {
+        network,
+        core,
+        platform_height,
+    }
+}
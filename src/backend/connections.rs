@@ -0,0 +1,39 @@
+//! DAPI connections warm-up and keepalive.
+//! Establishing a channel to a DAPI node takes a few seconds, so cheap
+//! requests are issued in background: to several nodes in parallel on startup
+//! for the first interactive query not to pay for it, and periodically later
+//! for idle sessions not to hit cold reconnects.
+
+use std::time::Instant;
+
+use dapi_grpc::core::v0::GetBlockchainStatusRequest;
+use dash_sdk::Sdk;
+use futures::future::join_all;
+use rs_dapi_client::{DapiRequestExecutor, RequestSettings};
+
+use super::BackendEvent;
+
+/// Sends `count` blockchain status requests in parallel, as DAPI nodes are
+/// picked randomly by the SDK this opens channels to several of them.
+pub(crate) async fn warm_up_connections<'s>(sdk: &Sdk, count: usize) -> BackendEvent<'s> {
+    let started = Instant::now();
+    let results = join_all((0..count).map(|_| async {
+        sdk.execute(GetBlockchainStatusRequest {}, RequestSettings::default())
+            .await
+            .map(|_| ())
+    }))
+    .await;
+
+    let failed = results.iter().filter(|result| result.is_err()).count();
+    if let Some(Err(e)) = results.iter().find(|result| result.is_err()) {
+        tracing::debug!("DAPI connection warm-up request failed: {}", e);
+    }
+    tracing::debug!(
+        "warmed up DAPI connections: {} of {} requests succeeded in {:?}",
+        count - failed,
+        count,
+        started.elapsed()
+    );
+
+    BackendEvent::None
+}
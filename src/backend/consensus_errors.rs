@@ -0,0 +1,140 @@
+//! Details of consensus errors.
+//!
+//! When Platform rejects a state transition, DAPI responds with a gRPC status
+//! carrying the consensus error code and the serialized error in its metadata.
+//! The SDK only keeps the `Debug` text of the status, so the error is decoded
+//! from it into an explanation naming the entities and fields it refers to.
+
+use dpp::{
+    consensus::{codes::ErrorWithCode, ConsensusError},
+    platform_value::string_encoding::{self, Encoding},
+    prelude::Identifier,
+    serialization::PlatformDeserializable,
+};
+
+use super::debug_text::DebugValue;
+
+/// gRPC metadata keys of a rejection.
+const SERIALIZED_ERROR_KEY: &str = "dash-serialized-consensus-error-bin";
+const CODE_KEY: &str = "code";
+
+/// Error fields naming the offending document or schema field.
+const FIELD_NAMES: [&str; 6] = [
+    "instance_path",
+    "property_name",
+    "property",
+    "field",
+    "field_name",
+    "path",
+];
+
+/// Value of the metadata key in the status text, quotes may be escaped if the
+/// status was formatted more than once.
+fn metadata_value<'a>(error: &'a str, key: &str) -> Option<&'a str> {
+    error.match_indices(key).find_map(|(start, _)| {
+        if !error[..start].trim_end_matches('\\').ends_with('"') {
+            return None;
+        }
+        let value = error[start + key.len()..]
+            .trim_start_matches('\\')
+            .strip_prefix("\": ")?
+            .trim_start_matches('\\')
+            .strip_prefix('"')?;
+        let end = value.find(['"', '\\'])?;
+        Some(&value[..end])
+    })
+}
+
+/// Binary metadata values are base64 encoded, possibly without padding.
+fn decode_base64(value: &str) -> Option<Vec<u8>> {
+    let padding = "=".repeat((4 - value.len() % 4) % 4);
+    string_encoding::decode(&format!("{}{}", value, padding), Encoding::Base64).ok()
+}
+
+fn category(error: &ConsensusError) -> &'static str {
+    match error {
+        ConsensusError::BasicError(_) => "basic",
+        ConsensusError::StateError(_) => "state",
+        ConsensusError::SignatureError(_) => "signature",
+        ConsensusError::FeeError(_) => "fee",
+        _ => "other",
+    }
+}
+
+/// Identifier of an error field, optional or not.
+fn identifier(value: &DebugValue) -> Option<Identifier> {
+    let value = match value {
+        DebugValue::Tuple(name, _) if name == "Some" => value.inner()?,
+        _ => value,
+    };
+    match value {
+        DebugValue::Tuple(name, _) if name == "Identifier" => {
+            Identifier::from_bytes(&value.find_tuple("IdentifierBytes32")?.inner()?.bytes()?).ok()
+        }
+        _ => None,
+    }
+}
+
+/// Entity name of an identifier field, `data_contract_id` is `Data contract`.
+fn entity_label(field: &str) -> String {
+    let words = field.trim_end_matches("_id").replace('_', " ");
+    let mut chars = words.chars();
+    match chars.next() {
+        Some(first) if field != "id" => first.to_uppercase().chain(chars).collect(),
+        _ => "ID".to_owned(),
+    }
+}
+
+/// Entities and fields the error refers to, one `Label: value` line each.
+fn references(error: &ConsensusError) -> Vec<String> {
+    let Ok(debug) = DebugValue::parse(&format!("{:?}", error)) else {
+        return Vec::new();
+    };
+    let mut references = Vec::new();
+    for (name, value) in debug.struct_fields() {
+        let reference = match value {
+            DebugValue::Str(field) if FIELD_NAMES.contains(&name) && !field.is_empty() => {
+                format!("Offending field: {}", field)
+            }
+            _ => match identifier(value) {
+                Some(id) => format!("{}: {}", entity_label(name), id.to_string(Encoding::Base58)),
+                None => continue,
+            },
+        };
+        if !references.contains(&reference) {
+            references.push(reference);
+        }
+    }
+    references
+}
+
+/// Explanation of the consensus error the error message carries, `None` if it
+/// carries none.
+pub(super) fn explain(error: &str) -> Option<String> {
+    let code = metadata_value(error, CODE_KEY).filter(|code| code.parse::<u32>().is_ok());
+    let Some(consensus_error) = metadata_value(error, SERIALIZED_ERROR_KEY)
+        .and_then(decode_base64)
+        .and_then(|bytes| ConsensusError::deserialize_from_bytes(&bytes).ok())
+    else {
+        // The error couldn't be decoded, its code is still more telling than
+        // the status
+        return code.map(|code| {
+            format!(
+                "State transition rejected: consensus error {}\n{}",
+                code, error
+            )
+        });
+    };
+
+    let mut explanation = format!(
+        "State transition rejected: {}\nConsensus error {} ({} error)",
+        consensus_error,
+        consensus_error.code(),
+        category(&consensus_error)
+    );
+    for reference in references(&consensus_error) {
+        explanation.push('\n');
+        explanation.push_str(&reference);
+    }
+    Some(explanation)
+}
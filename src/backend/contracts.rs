@@ -1,4 +1,8 @@
 //! Contracts backend.
+
+mod registration;
+mod schema_evolution;
+
 use std::sync::Arc;
 
 use dash_sdk::{
@@ -13,9 +17,16 @@ use dpp::{
     system_data_contracts::{dashpay_contract, dpns_contract},
 };
 use drive::query::{WhereClause, WhereOperator};
-use tokio::sync::Mutex;
+use futures::{stream, StreamExt};
+use tokio::sync::{Mutex, MutexGuard};
+
+use super::{
+    as_json_string, platform::Platform, state::KnownContractsMap, AppStateUpdate, BackendEvent,
+    Task,
+};
 
-use super::{as_json_string, state::KnownContractsMap, AppStateUpdate, BackendEvent, Task};
+/// Known contracts fetched at once during a refresh.
+const REFRESH_CONCURRENCY: usize = 8;
 
 #[derive(Debug, Clone, PartialEq)]
 pub(crate) enum ContractTask {
@@ -23,6 +34,16 @@ pub(crate) enum ContractTask {
     FetchDPNSContract,
     RemoveContract(String),
     FetchContract(String),
+    /// Fetch all known contracts again, reporting updated and vanished ones
+    RefreshContracts,
+    /// Check whether platform accepts an update of the contract to document
+    /// schemas of a JSON file, without broadcasting it
+    CheckSchemaUpdate {
+        contract_name: String,
+        schema_path: String,
+    },
+    /// Register a new contract of the loaded identity defined in a JSON file
+    RegisterContract(String),
 }
 
 pub(super) async fn run_contract_task<'s>(
@@ -99,32 +120,138 @@ pub(super) async fn run_contract_task<'s>(
                 app_state_update: AppStateUpdate::KnownContracts(contracts_lock),
             }
         }
-        ContractTask::FetchContract(ref contract_id_string) => {
-            let id = Identifier::from_string(&contract_id_string, Encoding::Base58)
-                .expect("Expected to convert contract_id_string to Identifier");
-            match DataContract::fetch(&sdk, id).await {
-                Ok(Some(data_contract)) => {
-                    let contract_str = as_json_string(&data_contract);
-                    let mut contracts_lock = known_contracts.lock().await;
-                    contracts_lock.insert(contract_id_string.to_string(), data_contract);
+        ContractTask::RefreshContracts => {
+            let (summary, contracts_lock) = refresh_contracts(sdk, known_contracts).await;
+            BackendEvent::TaskCompletedStateChange {
+                task: Task::Contract(task),
+                execution_result: Ok(summary.into()),
+                app_state_update: AppStateUpdate::KnownContracts(contracts_lock),
+            }
+        }
+        ContractTask::CheckSchemaUpdate {
+            ref contract_name,
+            ref schema_path,
+        } => {
+            let execution_result = match known_contracts.lock().await.get(contract_name) {
+                Some(data_contract) => {
+                    schema_evolution::check_schema_update(contract_name, data_contract, schema_path)
+                        .map(Into::into)
+                }
+                None => Err(format!("Contract {} is not known", contract_name)),
+            };
+            BackendEvent::TaskCompleted {
+                task: Task::Contract(task),
+                execution_result,
+            }
+        }
+        ContractTask::RegisterContract(_) => {
+            unreachable!("contract registration is run by the app state")
+        }
+        ContractTask::FetchContract(contract_id_string) => {
+            fetch_contract(sdk, known_contracts, contract_id_string).await
+        }
+    }
+}
 
-                    BackendEvent::TaskCompletedStateChange {
-                        task: Task::Contract(task),
-                        execution_result: Ok(contract_str.into()),
-                        app_state_update: AppStateUpdate::KnownContracts(contracts_lock),
-                    }
+/// Fetches a contract by its base 58 ID and adds it to known contracts.
+pub(super) async fn fetch_contract<'s>(
+    platform: &impl Platform,
+    known_contracts: &'s Mutex<KnownContractsMap>,
+    contract_id_string: String,
+) -> BackendEvent<'s> {
+    let id = Identifier::from_string(&contract_id_string, Encoding::Base58)
+        .expect("Expected to convert contract_id_string to Identifier");
+    let fetch_result = platform.fetch_data_contract(id).await;
+    let task = Task::Contract(ContractTask::FetchContract(contract_id_string.clone()));
+    match fetch_result {
+        Ok(Some(data_contract)) => {
+            let contract_str = as_json_string(&data_contract);
+            let mut contracts_lock = known_contracts.lock().await;
+            contracts_lock.insert(contract_id_string, data_contract);
+
+            BackendEvent::TaskCompletedStateChange {
+                task,
+                execution_result: Ok(contract_str.into()),
+                app_state_update: AppStateUpdate::KnownContracts(contracts_lock),
+            }
+        }
+        Ok(None) => BackendEvent::TaskCompleted {
+            task,
+            execution_result: Ok("No contract".into()),
+        },
+        Err(e) => BackendEvent::TaskCompleted {
+            task,
+            execution_result: Err(e),
+        },
+    }
+}
+
+/// Fetches known contracts concurrently and replaces cached ones, contracts
+/// still named by their identifier get a DPNS name if there is one now.
+async fn refresh_contracts<'s>(
+    sdk: &Sdk,
+    known_contracts: &'s Mutex<KnownContractsMap>,
+) -> (String, MutexGuard<'s, KnownContractsMap>) {
+    let cached: Vec<(String, DataContract)> = known_contracts
+        .lock()
+        .await
+        .iter()
+        .map(|(name, contract)| (name.clone(), contract.clone()))
+        .collect();
+    let total = cached.len();
+
+    let results: Vec<_> = stream::iter(cached)
+        .map(|(name, cached)| async move {
+            let fetched = DataContract::fetch(sdk, cached.id()).await;
+            let alias = if name == cached.id().to_string(Encoding::Base58) {
+                get_dpns_name(sdk, &cached.id()).await
+            } else {
+                None
+            };
+            (name, cached, fetched, alias)
+        })
+        .buffer_unordered(REFRESH_CONCURRENCY)
+        .collect()
+        .await;
+
+    let mut contracts_lock = known_contracts.lock().await;
+    let mut changes = Vec::new();
+    for (name, cached, fetched, alias) in results {
+        // Contracts removed while the refresh was running stay removed
+        if !contracts_lock.contains_key(&name) {
+            continue;
+        }
+        match fetched {
+            Ok(Some(contract)) => {
+                if contract.version() != cached.version() {
+                    changes.push(format!(
+                        "{}: updated from version {} to {}",
+                        name,
+                        cached.version(),
+                        contract.version()
+                    ));
                 }
-                Ok(None) => BackendEvent::TaskCompleted {
-                    task: Task::Contract(task),
-                    execution_result: Ok("No contract".into()),
-                },
-                Err(e) => BackendEvent::TaskCompleted {
-                    task: Task::Contract(task),
-                    execution_result: Err(e.to_string()),
-                },
+                let name = match alias {
+                    Some(alias) if alias != name && !contracts_lock.contains_key(&alias) => {
+                        contracts_lock.remove(&name);
+                        changes.push(format!("{}: now known as {}", name, alias));
+                        alias
+                    }
+                    _ => name,
+                };
+                contracts_lock.insert(name, contract);
             }
+            Ok(None) => changes.push(format!("{}: no longer found on the network", name)),
+            Err(e) => changes.push(format!("{}: unable to refresh: {}", name, e)),
         }
     }
+
+    let summary = if changes.is_empty() {
+        format!("Refreshed {} contracts, all are up to date", total)
+    } else {
+        format!("Refreshed {} contracts:\n{}", total, changes.join("\n"))
+    };
+    (summary, contracts_lock)
 }
 
 pub async fn get_dpns_name(sdk: &Sdk, id: &Identifier) -> Option<String> {
@@ -0,0 +1,266 @@
+//! Data contracts registration.
+//! Contracts are registered from document schemas of the schema builder draft
+//! or of a local JSON file, owned and signed by the loaded identity. Known
+//! contracts of the loaded identity are updated with a document type schema of
+//! the draft as a new version of the contract, once the update is checked to
+//! be accepted by platform.
+
+use std::{collections::HashSet, fs, path::Path};
+
+use dash_sdk::Sdk;
+use dpp::{
+    data_contract::{
+        accessors::v0::{DataContractV0Getters, DataContractV0Setters},
+        DataContractFactory,
+    },
+    identity::{
+        accessors::IdentityGettersV0,
+        identity_public_key::accessors::v0::IdentityPublicKeyGettersV0, Identity,
+        IdentityPublicKey, KeyType, Purpose, SecurityLevel,
+    },
+    platform_value::{self, string_encoding::Encoding, Value},
+    state_transition::{
+        data_contract_create_transition::{
+            methods::DataContractCreateTransitionMethodsV0, DataContractCreateTransition,
+        },
+        data_contract_update_transition::{
+            methods::DataContractUpdateTransitionMethodsV0, DataContractUpdateTransition,
+        },
+    },
+};
+use serde_json::{Map, Value as JsonValue};
+
+use super::{schema_evolution, ContractTask};
+use crate::backend::{as_json_string, AppState, AppStateUpdate, BackendEvent, Task};
+
+/// Key contracts of the identity are signed with.
+fn contract_signing_key(identity: &Identity) -> Result<&IdentityPublicKey, String> {
+    identity
+        .get_first_public_key_matching(
+            Purpose::AUTHENTICATION,
+            HashSet::from([SecurityLevel::CRITICAL]),
+            HashSet::from([KeyType::ECDSA_SECP256K1, KeyType::BLS12_381]),
+        )
+        .ok_or_else(|| "No critical authentication key in the loaded identity".to_owned())
+}
+
+/// Reads a contract definition file, either a contract JSON with
+/// `documentSchemas` and optionally `schemaDefs` or a map of document type
+/// schemas.
+fn load_contract_definition(path: &str) -> Result<(JsonValue, Option<JsonValue>), String> {
+    let content = fs::read_to_string(path.trim())
+        .map_err(|e| format!("Unable to read contract file: {}", e))?;
+    let json: JsonValue =
+        serde_json::from_str(&content).map_err(|e| format!("Invalid contract JSON: {}", e))?;
+    let definitions = json
+        .get("schemaDefs")
+        .filter(|defs| !defs.is_null())
+        .cloned();
+    match json.get("documentSchemas").unwrap_or(&json) {
+        schemas @ JsonValue::Object(_) => Ok((schemas.clone(), definitions)),
+        _ => Err("Contract file must contain an object of document schemas".to_owned()),
+    }
+}
+
+impl AppState {
+    /// Validates document schemas against the current platform version and
+    /// registers them as a new contract of the loaded identity, known under
+    /// the name afterwards.
+    pub(crate) async fn register_contract(
+        &self,
+        sdk: &Sdk,
+        contract_name: &str,
+        document_schemas: Value,
+        definitions: Option<Value>,
+    ) -> Result<String, String> {
+        let loaded_identity_lock = self.loaded_identity.lock().await;
+        let Some(identity) = loaded_identity_lock.as_ref() else {
+            return Err("No identity loaded".to_owned());
+        };
+
+        let identity_public_key = contract_signing_key(identity)?;
+        let signer = self.identity_signer(identity, "Data contract create").await;
+
+        let identity_nonce = sdk
+            .get_identity_nonce(identity.id(), true, None)
+            .await
+            .map_err(|e| format!("Unable to get identity nonce: {}", e))?;
+
+        let platform_version = sdk.version();
+        let factory = DataContractFactory::new(platform_version.protocol_version)
+            .map_err(|e| e.to_string())?;
+        let created_contract = factory
+            .create_with_value_config(
+                identity.id(),
+                identity_nonce,
+                document_schemas,
+                None,
+                definitions,
+            )
+            .map_err(|e| format!("Invalid schema: {}", e))?;
+        let data_contract = created_contract.data_contract().clone();
+
+        let transition = DataContractCreateTransition::new_from_data_contract(
+            data_contract.clone(),
+            identity_nonce,
+            &identity.clone().into_partial_identity_info(),
+            identity_public_key.id(),
+            &signer,
+            platform_version,
+            None,
+        )
+        .map_err(|e| format!("Unable to create state transition: {}", e))?;
+
+        let (_, receipt_path) = self
+            .broadcast_with_receipt(sdk, &transition.into(), identity, identity.balance())
+            .await
+            .map_err(|e| format!("Contract registration failed: {}", e))?;
+
+        tracing::info!(
+            "Registered contract {} with id {}, receipt: {:?}",
+            contract_name,
+            data_contract.id().to_string(Encoding::Base58),
+            receipt_path
+        );
+
+        let contract_json = as_json_string(&data_contract);
+        self.known_contracts
+            .lock()
+            .await
+            .insert(contract_name.to_owned(), data_contract);
+
+        Ok(contract_json)
+    }
+
+    /// Replaces or adds a document type schema of a known contract of the
+    /// loaded identity and broadcasts the next version of the contract.
+    pub(crate) async fn update_contract(
+        &self,
+        sdk: &Sdk,
+        contract_name: &str,
+        document_type_name: &str,
+        document_schema: JsonValue,
+    ) -> Result<String, String> {
+        let current_contract = self
+            .known_contracts
+            .lock()
+            .await
+            .get(contract_name)
+            .cloned()
+            .ok_or_else(|| format!("Unknown contract {}", contract_name))?;
+
+        let loaded_identity_lock = self.loaded_identity.lock().await;
+        let Some(identity) = loaded_identity_lock.as_ref() else {
+            return Err("No identity loaded".to_owned());
+        };
+        if current_contract.owner_id() != identity.id() {
+            return Err(format!(
+                "Contract {} isn't owned by the loaded identity",
+                contract_name
+            ));
+        }
+
+        let mut document_schemas: Map<String, JsonValue> =
+            schema_evolution::current_schemas(&current_contract)?
+                .into_iter()
+                .collect();
+        document_schemas.insert(document_type_name.to_owned(), document_schema);
+        schema_evolution::verify_schema_update(&current_contract, &document_schemas)?;
+
+        let identity_public_key = contract_signing_key(identity)?;
+        let signer = self.identity_signer(identity, "Data contract update").await;
+
+        let identity_contract_nonce = sdk
+            .get_identity_contract_nonce(identity.id(), current_contract.id(), true, None)
+            .await
+            .map_err(|e| format!("Unable to get identity contract nonce: {}", e))?;
+
+        // The factory makes a new contract out of the schemas, it gets the ID,
+        // configuration and definitions of the current one with the next version
+        let platform_version = sdk.version();
+        let factory = DataContractFactory::new(platform_version.protocol_version)
+            .map_err(|e| e.to_string())?;
+        let config = platform_value::to_value(current_contract.config())
+            .map_err(|e| format!("Unable to convert contract config: {}", e))?;
+        let definitions = current_contract
+            .schema_defs()
+            .map(platform_value::to_value)
+            .transpose()
+            .map_err(|e| format!("Unable to convert contract definitions: {}", e))?;
+        let mut data_contract = factory
+            .create_with_value_config(
+                identity.id(),
+                identity_contract_nonce,
+                Value::from(JsonValue::Object(document_schemas)),
+                Some(config),
+                definitions,
+            )
+            .map_err(|e| format!("Invalid schema: {}", e))?
+            .data_contract()
+            .clone();
+        data_contract.set_id(current_contract.id());
+        data_contract.set_version(current_contract.version() + 1);
+
+        let transition = DataContractUpdateTransition::new_from_data_contract(
+            data_contract.clone(),
+            &identity.clone().into_partial_identity_info(),
+            identity_public_key.id(),
+            identity_contract_nonce,
+            &signer,
+            platform_version,
+            None,
+        )
+        .map_err(|e| format!("Unable to create state transition: {}", e))?;
+
+        let (_, receipt_path) = self
+            .broadcast_with_receipt(sdk, &transition.into(), identity, identity.balance())
+            .await
+            .map_err(|e| format!("Contract update failed: {}", e))?;
+
+        tracing::info!(
+            "Updated contract {} to version {}, receipt: {:?}",
+            contract_name,
+            data_contract.version(),
+            receipt_path
+        );
+
+        let contract_json = as_json_string(&data_contract);
+        self.known_contracts
+            .lock()
+            .await
+            .insert(contract_name.to_owned(), data_contract);
+
+        Ok(contract_json)
+    }
+
+    /// Registers the contract defined in a JSON file, known under the file
+    /// name.
+    pub(crate) async fn run_contract_registration(&self, sdk: &Sdk, path: String) -> BackendEvent {
+        match self.register_contract_from_file(sdk, &path).await {
+            Ok(contract_json) => BackendEvent::TaskCompletedStateChange {
+                task: Task::Contract(ContractTask::RegisterContract(path)),
+                execution_result: Ok(contract_json.into()),
+                app_state_update: AppStateUpdate::KnownContracts(self.known_contracts.lock().await),
+            },
+            Err(e) => BackendEvent::TaskCompleted {
+                task: Task::Contract(ContractTask::RegisterContract(path)),
+                execution_result: Err(e),
+            },
+        }
+    }
+
+    async fn register_contract_from_file(&self, sdk: &Sdk, path: &str) -> Result<String, String> {
+        let (document_schemas, definitions) = load_contract_definition(path)?;
+        let contract_name = Path::new(path.trim())
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .ok_or_else(|| "Contract file path has no file name".to_owned())?;
+        self.register_contract(
+            sdk,
+            &contract_name,
+            Value::from(document_schemas),
+            definitions.map(Value::from),
+        )
+        .await
+    }
+}
@@ -0,0 +1,423 @@
+//! Compatibility check of document schema updates.
+//! Proposed document schemas are compared with the current ones of a contract
+//! before anything is broadcast. Platform accepts a schema update only if every
+//! document valid before stays valid, so restricting changes are rejected
+//! along with changes to document type settings and existing indices, while
+//! relaxing ones are accepted.
+
+use std::{collections::BTreeMap, fmt::Write, fs};
+
+use dpp::{data_contract::accessors::v0::DataContractV0Getters, prelude::DataContract};
+use serde_json::{Map, Value as JsonValue};
+
+/// Document type settings that can't be changed once the type exists.
+const IMMUTABLE_SETTINGS: [&str; 9] = [
+    "documentsMutable",
+    "documentsKeepHistory",
+    "canBeDeleted",
+    "transferable",
+    "tradeMode",
+    "creationRestrictionMode",
+    "requiresIdentityEncryptionBoundedKey",
+    "requiresIdentityDecryptionBoundedKey",
+    "signatureSecurityLevelRequirement",
+];
+
+/// Keywords of property schemas that can't be changed at all.
+const IMMUTABLE_KEYWORDS: [&str; 4] = ["type", "byteArray", "contentMediaType", "format"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Impact {
+    /// Accepted, existing documents stay valid
+    Relaxing,
+    /// Rejected, some existing documents may no longer be valid
+    Restricting,
+    /// Rejected by platform rules, documents aren't affected
+    Forbidden,
+}
+
+#[derive(Debug)]
+struct Finding {
+    impact: Impact,
+    /// Document type and property path the finding is about
+    path: String,
+    message: String,
+}
+
+#[derive(Default)]
+struct Findings(Vec<Finding>);
+
+impl Findings {
+    fn add(&mut self, impact: Impact, path: &str, message: impl Into<String>) {
+        self.0.push(Finding {
+            impact,
+            path: path.to_owned(),
+            message: message.into(),
+        });
+    }
+}
+
+fn object<'a>(schema: &'a JsonValue, key: &str) -> Option<&'a Map<String, JsonValue>> {
+    schema.get(key).and_then(JsonValue::as_object)
+}
+
+fn required(schema: &JsonValue) -> Vec<&str> {
+    schema
+        .get("required")
+        .and_then(JsonValue::as_array)
+        .map(|required| required.iter().filter_map(JsonValue::as_str).collect())
+        .unwrap_or_default()
+}
+
+fn compare_properties(
+    path: &str,
+    current: &JsonValue,
+    proposed: &JsonValue,
+    findings: &mut Findings,
+) {
+    let empty = Map::new();
+    let current_properties = object(current, "properties").unwrap_or(&empty);
+    let proposed_properties = object(proposed, "properties").unwrap_or(&empty);
+    let proposed_required = required(proposed);
+    let current_required = required(current);
+
+    for (name, current_property) in current_properties {
+        let property_path = format!("{}.{}", path, name);
+        match proposed_properties.get(name) {
+            None => findings.add(
+                Impact::Restricting,
+                &property_path,
+                "property removed, documents having it become invalid",
+            ),
+            Some(proposed_property) => compare_property(
+                &property_path,
+                current_property,
+                proposed_property,
+                findings,
+            ),
+        }
+    }
+
+    for (name, _) in proposed_properties
+        .iter()
+        .filter(|(name, _)| !current_properties.contains_key(*name))
+    {
+        let property_path = format!("{}.{}", path, name);
+        if proposed_required.contains(&name.as_str()) {
+            findings.add(
+                Impact::Restricting,
+                &property_path,
+                "required property added, existing documents don't have it",
+            );
+        } else {
+            findings.add(Impact::Relaxing, &property_path, "optional property added");
+        }
+    }
+
+    for name in &proposed_required {
+        if current_properties.contains_key(*name) && !current_required.contains(name) {
+            findings.add(
+                Impact::Restricting,
+                &format!("{}.{}", path, name),
+                "property became required",
+            );
+        }
+    }
+    for name in &current_required {
+        if proposed_properties.contains_key(*name) && !proposed_required.contains(name) {
+            findings.add(
+                Impact::Relaxing,
+                &format!("{}.{}", path, name),
+                "property is no longer required",
+            );
+        }
+    }
+
+    if current.get("additionalProperties") != proposed.get("additionalProperties") {
+        findings.add(Impact::Forbidden, path, "additionalProperties changed");
+    }
+}
+
+/// Compares a numeric limit, `upper` limits are restricted when lowered.
+fn compare_limit(
+    path: &str,
+    keyword: &str,
+    upper: bool,
+    current: &JsonValue,
+    proposed: &JsonValue,
+    findings: &mut Findings,
+) {
+    let current_limit = current.get(keyword).and_then(JsonValue::as_f64);
+    let proposed_limit = proposed.get(keyword).and_then(JsonValue::as_f64);
+    let restricted = match (current_limit, proposed_limit) {
+        (Some(current), Some(proposed)) if current == proposed => return,
+        (Some(current), Some(proposed)) => (proposed < current) == upper,
+        (None, Some(_)) => true,
+        (Some(_), None) => false,
+        (None, None) => return,
+    };
+    let describe = |limit: Option<f64>| limit.map_or("none".to_owned(), |limit| limit.to_string());
+    let message = format!(
+        "{} changed from {} to {}",
+        keyword,
+        describe(current_limit),
+        describe(proposed_limit)
+    );
+    if restricted {
+        findings.add(Impact::Restricting, path, message);
+    } else {
+        findings.add(Impact::Relaxing, path, message);
+    }
+}
+
+fn compare_property(
+    path: &str,
+    current: &JsonValue,
+    proposed: &JsonValue,
+    findings: &mut Findings,
+) {
+    for keyword in IMMUTABLE_KEYWORDS {
+        if current.get(keyword) != proposed.get(keyword) {
+            findings.add(
+                Impact::Restricting,
+                path,
+                format!("{} changed, existing values may not match", keyword),
+            );
+        }
+    }
+
+    for keyword in ["maxLength", "maxItems", "maximum"] {
+        compare_limit(path, keyword, true, current, proposed, findings);
+    }
+    for keyword in ["minLength", "minItems", "minimum"] {
+        compare_limit(path, keyword, false, current, proposed, findings);
+    }
+
+    match (current.get("pattern"), proposed.get("pattern")) {
+        (current, proposed) if current == proposed => {}
+        (Some(_), None) => findings.add(Impact::Relaxing, path, "pattern removed"),
+        _ => findings.add(Impact::Restricting, path, "pattern added or changed"),
+    }
+
+    match (
+        current.get("enum").and_then(JsonValue::as_array),
+        proposed.get("enum").and_then(JsonValue::as_array),
+    ) {
+        (Some(current), Some(proposed)) => {
+            if current.iter().any(|value| !proposed.contains(value)) {
+                findings.add(Impact::Restricting, path, "enum values removed");
+            } else if proposed.len() > current.len() {
+                findings.add(Impact::Relaxing, path, "enum values added");
+            }
+        }
+        (None, Some(_)) => findings.add(Impact::Restricting, path, "enum added"),
+        (Some(_), None) => findings.add(Impact::Relaxing, path, "enum removed"),
+        (None, None) => {}
+    }
+
+    if object(current, "properties").is_some() || object(proposed, "properties").is_some() {
+        compare_properties(path, current, proposed, findings);
+    }
+    if let (Some(current_items), Some(proposed_items)) =
+        (current.get("items"), proposed.get("items"))
+    {
+        compare_property(
+            &format!("{}[]", path),
+            current_items,
+            proposed_items,
+            findings,
+        );
+    }
+}
+
+fn indices(schema: &JsonValue) -> BTreeMap<&str, &JsonValue> {
+    schema
+        .get("indices")
+        .and_then(JsonValue::as_array)
+        .map(|indices| {
+            indices
+                .iter()
+                .filter_map(|index| Some((index.get("name")?.as_str()?, index)))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn compare_indices(path: &str, current: &JsonValue, proposed: &JsonValue, findings: &mut Findings) {
+    let current_indices = indices(current);
+    let proposed_indices = indices(proposed);
+
+    for (name, current_index) in &current_indices {
+        let index_path = format!("{} index {}", path, name);
+        match proposed_indices.get(name) {
+            None => findings.add(Impact::Forbidden, &index_path, "index removed"),
+            Some(proposed_index) if proposed_index != current_index => {
+                findings.add(Impact::Forbidden, &index_path, "index definition changed")
+            }
+            Some(_) => {}
+        }
+    }
+    for (name, proposed_index) in proposed_indices
+        .iter()
+        .filter(|(name, _)| !current_indices.contains_key(*name))
+    {
+        let index_path = format!("{} index {}", path, name);
+        if proposed_index.get("unique") == Some(&JsonValue::Bool(true)) {
+            findings.add(
+                Impact::Forbidden,
+                &index_path,
+                "unique index added to an existing document type",
+            );
+        } else {
+            findings.add(Impact::Relaxing, &index_path, "non-unique index added");
+        }
+    }
+}
+
+fn compare_document_types(
+    current: &BTreeMap<String, JsonValue>,
+    proposed: &Map<String, JsonValue>,
+    findings: &mut Findings,
+) {
+    for (name, current_schema) in current {
+        let Some(proposed_schema) = proposed.get(name) else {
+            findings.add(Impact::Forbidden, name, "document type removed");
+            continue;
+        };
+        for setting in IMMUTABLE_SETTINGS {
+            if current_schema.get(setting) != proposed_schema.get(setting) {
+                findings.add(
+                    Impact::Forbidden,
+                    name,
+                    format!("{} setting changed", setting),
+                );
+            }
+        }
+        compare_properties(name, current_schema, proposed_schema, findings);
+        compare_indices(name, current_schema, proposed_schema, findings);
+    }
+    for name in proposed.keys().filter(|name| !current.contains_key(*name)) {
+        findings.add(Impact::Relaxing, name, "document type added");
+    }
+}
+
+/// Reads document schemas of a file, either a contract JSON with
+/// `documentSchemas` or a map of document type schemas.
+fn load_proposed_schemas(path: &str) -> Result<Map<String, JsonValue>, String> {
+    let content = fs::read_to_string(path.trim())
+        .map_err(|e| format!("Unable to read proposed schema file: {}", e))?;
+    let json: JsonValue = serde_json::from_str(&content)
+        .map_err(|e| format!("Invalid proposed schema JSON: {}", e))?;
+    match json.get("documentSchemas").unwrap_or(&json) {
+        JsonValue::Object(schemas) => Ok(schemas.clone()),
+        _ => Err("Proposed schema file must contain an object of document schemas".to_owned()),
+    }
+}
+
+/// Document schemas of the contract as JSON.
+pub(super) fn current_schemas(
+    data_contract: &DataContract,
+) -> Result<BTreeMap<String, JsonValue>, String> {
+    data_contract
+        .document_schemas()
+        .iter()
+        .map(|(name, schema)| {
+            serde_json::to_value(schema)
+                .map(|schema| (name.clone(), schema))
+                .map_err(|e| format!("Unable to convert schema of {}: {}", name, e))
+        })
+        .collect()
+}
+
+fn schema_update_findings(
+    data_contract: &DataContract,
+    proposed: &Map<String, JsonValue>,
+) -> Result<Vec<Finding>, String> {
+    let current = current_schemas(data_contract)?;
+    let mut findings = Findings::default();
+    compare_document_types(&current, proposed, &mut findings);
+    Ok(findings.0)
+}
+
+/// Rejects an update of the contract document schemas platform won't accept
+/// or that may leave existing documents invalid, before it's broadcast.
+pub(super) fn verify_schema_update(
+    data_contract: &DataContract,
+    proposed: &Map<String, JsonValue>,
+) -> Result<(), String> {
+    let rejected: Vec<String> = schema_update_findings(data_contract, proposed)?
+        .into_iter()
+        .filter(|finding| finding.impact != Impact::Relaxing)
+        .map(|finding| format!("{}: {}", finding.path, finding.message))
+        .collect();
+    if rejected.is_empty() {
+        Ok(())
+    } else {
+        Err(format!(
+            "Schema update would be rejected: {}",
+            rejected.join(", ")
+        ))
+    }
+}
+
+/// Checks an update of the contract document schemas to ones of the file and
+/// predicts whether platform accepts it and existing documents stay valid.
+pub(super) fn check_schema_update(
+    contract_name: &str,
+    data_contract: &DataContract,
+    proposed_path: &str,
+) -> Result<String, String> {
+    let proposed = load_proposed_schemas(proposed_path)?;
+    let findings = schema_update_findings(data_contract, &proposed)?;
+
+    if findings.is_empty() {
+        return Ok(format!(
+            "Proposed schemas of {} have no changes",
+            contract_name
+        ));
+    }
+
+    let rejected = findings
+        .iter()
+        .any(|finding| finding.impact != Impact::Relaxing);
+    let documents_valid = !findings
+        .iter()
+        .any(|finding| finding.impact == Impact::Restricting);
+
+    let mut report = format!("Schema update of {}\n", contract_name);
+    let _ = writeln!(
+        report,
+        "Platform will accept the update: {}",
+        if rejected { "no" } else { "yes" }
+    );
+    let _ = writeln!(
+        report,
+        "Existing documents remain valid: {}",
+        if documents_valid {
+            "yes"
+        } else {
+            "not guaranteed"
+        }
+    );
+    for (impact, title) in [
+        (
+            Impact::Restricting,
+            "Rejected, existing documents may become invalid:",
+        ),
+        (Impact::Forbidden, "Rejected by platform rules:"),
+        (Impact::Relaxing, "Accepted:"),
+    ] {
+        let mut section = findings
+            .iter()
+            .filter(|finding| finding.impact == impact)
+            .peekable();
+        if section.peek().is_none() {
+            continue;
+        }
+        let _ = writeln!(report, "\n{}", title);
+        for finding in section {
+            let _ = writeln!(report, "  {}: {}", finding.path, finding.message);
+        }
+    }
+    Ok(report)
+}
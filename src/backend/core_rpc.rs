@@ -0,0 +1,59 @@
+//! Dash Core JSON-RPC client.
+//! The SDK requests only quorum keys from Core, other Core data shown by the
+//! explorer is requested with this client using the same RPC settings.
+
+use serde_json::{json, Value as JsonValue};
+
+use crate::config::Config;
+
+#[derive(Debug, Clone)]
+pub(crate) struct CoreRpcClient {
+    url: String,
+    user: String,
+    password: String,
+}
+
+impl CoreRpcClient {
+    pub(crate) fn new(config: &Config) -> Self {
+        CoreRpcClient {
+            url: format!("http://{}:{}", config.core_host, config.core_rpc_port),
+            user: config.core_rpc_user.clone(),
+            password: config.core_rpc_password.clone(),
+        }
+    }
+
+    /// Calls the RPC method, returning its result.
+    pub(crate) async fn call(&self, method: &str, params: JsonValue) -> Result<JsonValue, String> {
+        let response = reqwest::Client::new()
+            .post(&self.url)
+            .basic_auth(&self.user, Some(&self.password))
+            .json(&json!({
+                "jsonrpc": "1.0",
+                "id": "platform-explorer",
+                "method": method,
+                "params": params,
+            }))
+            .send()
+            .await
+            .map_err(|e| format!("Core RPC request failed: {}", e))?;
+        if response.status() == reqwest::StatusCode::UNAUTHORIZED {
+            return Err("Core RPC authentication failed".to_owned());
+        }
+
+        // Errors come with a JSON body too, whatever the status
+        let mut body: JsonValue = response
+            .json()
+            .await
+            .map_err(|e| format!("Invalid Core RPC response: {}", e))?;
+        match body.get("error") {
+            Some(error) if !error.is_null() => Err(format!(
+                "Core RPC request failed: {}",
+                error
+                    .get("message")
+                    .and_then(JsonValue::as_str)
+                    .unwrap_or("unknown error")
+            )),
+            _ => Ok(body["result"].take()),
+        }
+    }
+}
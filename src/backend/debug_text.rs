@@ -0,0 +1,230 @@
+//! Parser of `Debug` formatted values.
+//! DAPI traces record payloads as the `Debug` text of gRPC messages, and errors
+//! of the SDK carry the `Debug` text of DAPI client errors, which are parsed
+//! back into a tree of values to pick the fields needed.
+
+use std::{collections::BTreeMap, iter::Peekable, str::Chars};
+
+#[derive(Debug, Clone, PartialEq)]
+pub(super) enum DebugValue {
+    /// `Name { field: value, .. }`
+    Struct(String, BTreeMap<String, DebugValue>),
+    /// `Name(value, ..)`, like `Some(..)` or a `V0(..)` version
+    Tuple(String, Vec<DebugValue>),
+    /// `Name`, like `None` or a unit enum variant
+    Unit(String),
+    List(Vec<DebugValue>),
+    Str(String),
+    /// Numbers and booleans as written
+    Scalar(String),
+}
+
+impl DebugValue {
+    pub(super) fn parse(text: &str) -> Result<DebugValue, String> {
+        let mut parser = Parser {
+            chars: text.chars().peekable(),
+        };
+        let value = parser.value()?;
+        parser.skip_whitespace();
+        match parser.chars.next() {
+            None => Ok(value),
+            Some(c) => Err(format!("unexpected `{}` after value", c)),
+        }
+    }
+
+    /// First struct of the name, depth first.
+    pub(super) fn find_struct(&self, name: &str) -> Option<&DebugValue> {
+        self.find(&|value| matches!(value, DebugValue::Struct(n, _) if n == name))
+    }
+
+    /// First tuple of the name, depth first.
+    pub(super) fn find_tuple(&self, name: &str) -> Option<&DebugValue> {
+        self.find(&|value| matches!(value, DebugValue::Tuple(n, _) if n == name))
+    }
+
+    fn find(&self, predicate: &impl Fn(&DebugValue) -> bool) -> Option<&DebugValue> {
+        if predicate(self) {
+            return Some(self);
+        }
+        match self {
+            DebugValue::Struct(_, fields) => fields.values().find_map(|v| v.find(predicate)),
+            DebugValue::Tuple(_, items) | DebugValue::List(items) => {
+                items.iter().find_map(|v| v.find(predicate))
+            }
+            _ => None,
+        }
+    }
+
+    /// Fields of all structs of the tree with their values, depth first.
+    pub(super) fn struct_fields(&self) -> Vec<(&str, &DebugValue)> {
+        match self {
+            DebugValue::Struct(_, fields) => fields
+                .iter()
+                .flat_map(|(name, value)| {
+                    std::iter::once((name.as_str(), value)).chain(value.struct_fields())
+                })
+                .collect(),
+            DebugValue::Tuple(_, items) | DebugValue::List(items) => {
+                items.iter().flat_map(DebugValue::struct_fields).collect()
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    pub(super) fn field(&self, name: &str) -> Option<&DebugValue> {
+        match self {
+            DebugValue::Struct(_, fields) => fields.get(name),
+            _ => None,
+        }
+    }
+
+    /// Value wrapped into a single item tuple, like `Some(..)`.
+    pub(super) fn inner(&self) -> Option<&DebugValue> {
+        match self {
+            DebugValue::Tuple(_, items) if items.len() == 1 => items.first(),
+            _ => None,
+        }
+    }
+
+    /// Bytes of a `Vec<u8>` written as a list of numbers.
+    pub(super) fn bytes(&self) -> Option<Vec<u8>> {
+        match self {
+            DebugValue::List(items) => items
+                .iter()
+                .map(|item| match item {
+                    DebugValue::Scalar(n) => n.parse().ok(),
+                    _ => None,
+                })
+                .collect(),
+            _ => None,
+        }
+    }
+
+    pub(super) fn number(&self) -> Option<u64> {
+        match self {
+            DebugValue::Scalar(n) => n.parse().ok(),
+            _ => None,
+        }
+    }
+}
+
+struct Parser<'a> {
+    chars: Peekable<Chars<'a>>,
+}
+
+impl Parser<'_> {
+    fn skip_whitespace(&mut self) {
+        while self.chars.next_if(|c| c.is_whitespace()).is_some() {}
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), String> {
+        self.skip_whitespace();
+        match self.chars.next() {
+            Some(c) if c == expected => Ok(()),
+            Some(c) => Err(format!("expected `{}`, found `{}`", expected, c)),
+            None => Err(format!("expected `{}`, found end of text", expected)),
+        }
+    }
+
+    /// Whether the next character closes a sequence, consuming it if so.
+    fn closes(&mut self, close: char) -> bool {
+        self.skip_whitespace();
+        self.chars.next_if_eq(&close).is_some()
+    }
+
+    /// Comma separated items up to the closing character, trailing comma
+    /// allowed.
+    fn sequence<T>(
+        &mut self,
+        close: char,
+        mut item: impl FnMut(&mut Self) -> Result<T, String>,
+    ) -> Result<Vec<T>, String> {
+        let mut items = Vec::new();
+        while !self.closes(close) {
+            items.push(item(self)?);
+            if !self.closes(',') {
+                self.expect(close)?;
+                break;
+            }
+        }
+        Ok(items)
+    }
+
+    fn word(&mut self) -> String {
+        let mut word = String::new();
+        while let Some(c) = self
+            .chars
+            .next_if(|c| c.is_alphanumeric() || matches!(c, '_' | '.' | '-' | '+'))
+        {
+            word.push(c);
+        }
+        word
+    }
+
+    fn value(&mut self) -> Result<DebugValue, String> {
+        self.skip_whitespace();
+        match self.chars.peek() {
+            None => Err("unexpected end of text".to_owned()),
+            Some('"') => self.string().map(DebugValue::Str),
+            Some('[') => {
+                self.chars.next();
+                self.sequence(']', Self::value).map(DebugValue::List)
+            }
+            Some(c) if c.is_ascii_digit() || *c == '-' => Ok(DebugValue::Scalar(self.word())),
+            Some(c) if c.is_alphabetic() || *c == '_' => {
+                let name = self.word();
+                self.skip_whitespace();
+                if self.chars.next_if_eq(&'{').is_some() {
+                    let fields = self.sequence('}', |parser| {
+                        parser.skip_whitespace();
+                        let field = parser.word();
+                        parser.expect(':')?;
+                        Ok((field, parser.value()?))
+                    })?;
+                    Ok(DebugValue::Struct(name, fields.into_iter().collect()))
+                } else if self.chars.next_if_eq(&'(').is_some() {
+                    self.sequence(')', Self::value)
+                        .map(|items| DebugValue::Tuple(name, items))
+                } else if name == "true" || name == "false" {
+                    Ok(DebugValue::Scalar(name))
+                } else {
+                    Ok(DebugValue::Unit(name))
+                }
+            }
+            Some(c) => Err(format!("unexpected `{}`", c)),
+        }
+    }
+
+    fn string(&mut self) -> Result<String, String> {
+        self.expect('"')?;
+        let mut string = String::new();
+        loop {
+            match self.chars.next() {
+                None => return Err("unterminated string".to_owned()),
+                Some('"') => return Ok(string),
+                Some('\\') => match self.chars.next() {
+                    Some('n') => string.push('\n'),
+                    Some('r') => string.push('\r'),
+                    Some('t') => string.push('\t'),
+                    Some('0') => string.push('\0'),
+                    Some('u') => {
+                        self.expect('{')?;
+                        let mut code = String::new();
+                        while let Some(c) = self.chars.next_if(|c| *c != '}') {
+                            code.push(c);
+                        }
+                        self.expect('}')?;
+                        let c = u32::from_str_radix(&code, 16)
+                            .ok()
+                            .and_then(char::from_u32)
+                            .ok_or_else(|| format!("invalid unicode escape `{}`", code))?;
+                        string.push(c);
+                    }
+                    Some(c) => string.push(c),
+                    None => return Err("unterminated string".to_owned()),
+                },
+                Some(c) => string.push(c),
+            }
+        }
+    }
+}
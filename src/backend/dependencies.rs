@@ -0,0 +1,332 @@
+//! Dependency graph of data contracts.
+//! Contracts don't declare what their identifier properties point to, so edges
+//! are found among documents fetched during the session: an identifier value
+//! of a document is resolved to a known contract, a cached document or, if
+//! neither matches, an identity. Identities link contracts together, e.g. a
+//! Dashpay contact request and the DPNS domain of the same identity.
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::{self, Display},
+};
+
+use dash_sdk::platform::DocumentQuery;
+use dpp::{
+    data_contract::{
+        accessors::v0::DataContractV0Getters,
+        document_type::{accessors::DocumentTypeV0Getters, DocumentProperty, DocumentPropertyType},
+    },
+    document::{Document, DocumentV0Getters},
+    platform_value::{string_encoding::Encoding, Value},
+    prelude::{DataContract, Identifier},
+};
+
+use super::{search::DocumentsCache, state::KnownContractsMap};
+
+/// Label of the edge from a document to its owner.
+const OWNER_LABEL: &str = "$ownerId";
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) enum GraphNode {
+    /// Known contract by its name
+    Contract(String),
+    /// Cached document
+    Document(Identifier),
+    Identity(Identifier),
+}
+
+#[derive(Debug, Clone)]
+struct GraphEdge {
+    from: Identifier,
+    /// Identifier property path of the document, prefixed with its type
+    label: String,
+    to: GraphNode,
+}
+
+/// An edge as seen from one of its nodes.
+#[derive(Debug, Clone)]
+pub(crate) struct Neighbour {
+    pub description: String,
+    pub node: GraphNode,
+}
+
+impl Display for Neighbour {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.description)
+    }
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct DependencyGraph {
+    contracts: BTreeMap<String, DataContract>,
+    documents: BTreeMap<Identifier, (DocumentQuery, Document)>,
+    edges: Vec<GraphEdge>,
+}
+
+impl DependencyGraph {
+    pub(crate) fn build(known_contracts: &KnownContractsMap, cache: &DocumentsCache) -> Self {
+        let contract_names: BTreeMap<Identifier, &String> = known_contracts
+            .iter()
+            .map(|(name, contract)| (contract.id(), name))
+            .collect();
+
+        let mut edges = Vec::new();
+        for (id, (query, document)) in cache {
+            let Ok(document_type) = query
+                .data_contract
+                .document_type_for_name(&query.document_type_name)
+            else {
+                continue;
+            };
+            let mut paths = Vec::new();
+            identifier_paths(document_type.properties(), "", &mut paths);
+
+            edges.push(GraphEdge {
+                from: *id,
+                label: format!("{}.{}", query.document_type_name, OWNER_LABEL),
+                to: GraphNode::Identity(document.owner_id()),
+            });
+            for path in paths {
+                let Some(target) = value_at_path(document.properties(), &path).and_then(identifier)
+                else {
+                    continue;
+                };
+                let to = if let Some(name) = contract_names.get(&target) {
+                    GraphNode::Contract((*name).clone())
+                } else if cache.contains_key(&target) {
+                    GraphNode::Document(target)
+                } else {
+                    GraphNode::Identity(target)
+                };
+                edges.push(GraphEdge {
+                    from: *id,
+                    label: format!("{}.{}", query.document_type_name, path),
+                    to,
+                });
+            }
+        }
+
+        DependencyGraph {
+            contracts: known_contracts.clone(),
+            documents: cache.clone(),
+            edges,
+        }
+    }
+
+    /// Name of the known contract of a cached document.
+    fn document_contract_name(&self, id: &Identifier) -> Option<String> {
+        let (query, _) = self.documents.get(id)?;
+        let contract_id = query.data_contract.id();
+        self.contracts
+            .iter()
+            .find(|(_, contract)| contract.id() == contract_id)
+            .map(|(name, _)| name.clone())
+    }
+
+    fn document_summary(&self, id: &Identifier) -> String {
+        let contract = self
+            .document_contract_name(id)
+            .unwrap_or_else(|| "unknown contract".to_owned());
+        match self.documents.get(id) {
+            Some((query, _)) => format!(
+                "document {} of {}.{}",
+                id.to_string(Encoding::Base58),
+                contract,
+                query.document_type_name
+            ),
+            None => format!("document {}", id.to_string(Encoding::Base58)),
+        }
+    }
+
+    pub(crate) fn node_summary(&self, node: &GraphNode) -> String {
+        match node {
+            GraphNode::Contract(name) => format!("contract {}", name),
+            GraphNode::Document(id) => self.document_summary(id),
+            GraphNode::Identity(id) => match self.identity_name(id) {
+                Some(name) => format!("identity {} ({})", id.to_string(Encoding::Base58), name),
+                None => format!("identity {}", id.to_string(Encoding::Base58)),
+            },
+        }
+    }
+
+    /// DPNS name of the identity if its domain document was fetched.
+    fn identity_name(&self, id: &Identifier) -> Option<String> {
+        self.edges
+            .iter()
+            .filter(|edge| {
+                edge.to == GraphNode::Identity(*id) && !edge.label.ends_with(OWNER_LABEL)
+            })
+            .find_map(|edge| {
+                let (_, document) = self.documents.get(&edge.from)?;
+                let properties = document.properties();
+                match (
+                    properties.get("label"),
+                    properties.get("normalizedParentDomainName"),
+                ) {
+                    (Some(Value::Text(label)), Some(Value::Text(parent))) => {
+                        Some(format!("{}.{}", label, parent))
+                    }
+                    _ => None,
+                }
+            })
+    }
+
+    /// Nodes linked to the node, outgoing edges first.
+    pub(crate) fn neighbours(&self, node: &GraphNode) -> Vec<Neighbour> {
+        let mut neighbours = Vec::new();
+        let mut seen = BTreeSet::new();
+        let mut push = |description: String, node: GraphNode| {
+            if seen.insert((description.clone(), node.clone())) {
+                neighbours.push(Neighbour { description, node });
+            }
+        };
+
+        match node {
+            GraphNode::Contract(name) => {
+                let documents: Vec<&Identifier> = self
+                    .documents
+                    .keys()
+                    .filter(|id| self.document_contract_name(id).as_ref() == Some(name))
+                    .collect();
+                for edge in self
+                    .edges
+                    .iter()
+                    .filter(|edge| documents.contains(&&edge.from))
+                {
+                    push(
+                        format!("{} → {}", edge.label, self.node_summary(&edge.to)),
+                        edge.to.clone(),
+                    );
+                }
+                for edge in self
+                    .edges
+                    .iter()
+                    .filter(|edge| edge.to == *node && !documents.contains(&&edge.from))
+                {
+                    push(
+                        format!("← {} of {}", edge.label, self.document_summary(&edge.from)),
+                        GraphNode::Document(edge.from),
+                    );
+                }
+                for id in documents {
+                    push(
+                        format!("contains {}", self.document_summary(id)),
+                        GraphNode::Document(*id),
+                    );
+                }
+            }
+            GraphNode::Document(id) => {
+                if let Some(contract) = self.document_contract_name(id) {
+                    push("of contract".to_owned(), GraphNode::Contract(contract));
+                }
+                for edge in self.edges.iter().filter(|edge| edge.from == *id) {
+                    push(
+                        format!("{} → {}", edge.label, self.node_summary(&edge.to)),
+                        edge.to.clone(),
+                    );
+                }
+                for edge in self.edges.iter().filter(|edge| edge.to == *node) {
+                    push(
+                        format!("← {} of {}", edge.label, self.document_summary(&edge.from)),
+                        GraphNode::Document(edge.from),
+                    );
+                }
+            }
+            GraphNode::Identity(_) => {
+                for edge in self.edges.iter().filter(|edge| edge.to == *node) {
+                    push(
+                        format!("← {} of {}", edge.label, self.document_summary(&edge.from)),
+                        GraphNode::Document(edge.from),
+                    );
+                }
+            }
+        }
+        neighbours
+    }
+
+    /// Details of the node for display.
+    pub(crate) fn describe(&self, node: &GraphNode) -> String {
+        let mut lines = vec![self.node_summary(node)];
+        match node {
+            GraphNode::Contract(name) => {
+                if let Some(contract) = self.contracts.get(name) {
+                    lines.push(format!("id: {}", contract.id().to_string(Encoding::Base58)));
+                    lines.push("Identifier properties:".to_owned());
+                    for (type_name, document_type) in contract.document_types() {
+                        let mut paths = Vec::new();
+                        identifier_paths(document_type.properties(), "", &mut paths);
+                        for path in paths {
+                            lines.push(format!("  {}.{}", type_name, path));
+                        }
+                    }
+                }
+            }
+            GraphNode::Document(id) => {
+                if let Some((_, document)) = self.documents.get(id) {
+                    lines.push(super::as_json_string(document));
+                }
+            }
+            GraphNode::Identity(_) => {}
+        }
+        lines.push(String::new());
+        lines.push(
+            "Edges are found among documents fetched during the session, fetch more documents \
+             to extend the graph"
+                .to_owned(),
+        );
+        lines.join("\n")
+    }
+
+    /// Cached document with the query it was fetched with.
+    pub(crate) fn document(&self, id: &Identifier) -> Option<(DocumentQuery, Document)> {
+        self.documents.get(id).cloned()
+    }
+
+    pub(crate) fn document_type_names(&self, contract_name: &str) -> Vec<String> {
+        self.contracts
+            .get(contract_name)
+            .map(|contract| contract.document_types().keys().cloned().collect())
+            .unwrap_or_default()
+    }
+}
+
+/// Dotted paths of identifier properties, nested objects included.
+fn identifier_paths(
+    properties: &BTreeMap<String, DocumentProperty>,
+    prefix: &str,
+    paths: &mut Vec<String>,
+) {
+    for (name, property) in properties {
+        let path = format!("{}{}", prefix, name);
+        match &property.property_type {
+            DocumentPropertyType::Identifier => paths.push(path),
+            DocumentPropertyType::Object(inner) => {
+                identifier_paths(inner, &format!("{}.", path), paths)
+            }
+            _ => {}
+        }
+    }
+}
+
+fn value_at_path<'a>(properties: &'a BTreeMap<String, Value>, path: &str) -> Option<&'a Value> {
+    let mut keys = path.split('.');
+    let mut value = properties.get(keys.next()?)?;
+    for key in keys {
+        let Value::Map(entries) = value else {
+            return None;
+        };
+        value = entries
+            .iter()
+            .find(|(k, _)| matches!(k, Value::Text(text) if text == key))
+            .map(|(_, v)| v)?;
+    }
+    Some(value)
+}
+
+fn identifier(value: &Value) -> Option<Identifier> {
+    match value {
+        Value::Identifier(bytes) | Value::Bytes32(bytes) => Some(Identifier::new(*bytes)),
+        Value::Bytes(bytes) => Identifier::from_bytes(bytes).ok(),
+        _ => None,
+    }
+}
@@ -1,3 +1,10 @@
+pub(crate) mod binary;
+pub(crate) mod bulk;
+pub(crate) mod clauses;
+mod crud;
+pub(crate) mod joins;
+pub(crate) mod prefetch;
+
 use std::{
     collections::{BTreeMap, HashSet},
     iter,
@@ -6,7 +13,7 @@ use std::{
 };
 
 use dash_sdk::{
-    platform::{transition::put_document::PutDocument, DocumentQuery, FetchMany},
+    platform::{transition::put_document::PutDocument, DocumentQuery},
     Sdk,
 };
 use dpp::{
@@ -14,51 +21,319 @@ use dpp::{
         accessors::v0::DataContractV0Getters,
         document_type::{
             accessors::DocumentTypeV0Getters,
+            methods::DocumentTypeV0Methods,
             random_document::{CreateRandomDocument, DocumentFieldFillSize, DocumentFieldFillType},
             DocumentType,
         },
     },
-    document::Document,
+    document::{serialization_traits::DocumentPlatformConversionMethodsV0, Document},
     identity::{
         accessors::IdentityGettersV0,
         identity_public_key::accessors::v0::IdentityPublicKeyGettersV0, KeyType, Purpose,
     },
-    prelude::{DataContract, Identity, IdentityPublicKey},
+    platform_value::Value,
+    prelude::{DataContract, Identifier, Identity, IdentityPublicKey},
 };
 use futures::{stream::FuturesUnordered, Future, StreamExt};
 use rand::{prelude::StdRng, Rng, SeedableRng};
 use simple_signer::signer::SimpleSigner;
 
-use super::{state::IdentityPrivateKeysMap, AppStateUpdate, CompletedTaskPayload};
+use self::{bulk::ExportFormat, clauses::QueryClauses};
+use super::{
+    platform::Platform,
+    signing_audit::{AuditedSigner, SigningAudit},
+    state::IdentityPrivateKeysMap,
+    AppStateUpdate, CompletedTaskPayload,
+};
 use crate::backend::{error::Error, AppState, BackendEvent, Task};
 
 #[derive(Debug, Clone)]
 pub(crate) enum DocumentTask {
     QueryDocuments(DocumentQuery),
+    /// Fetch documents of a query composed in the query builder
+    QueryDocumentsWithClauses(QueryClauses),
     BroadcastRandomDocuments {
         data_contract_name: String,
         document_type_name: String,
         count: u16,
     },
+    ExportDocuments(Vec<Document>, ExportFormat),
+    /// Fetch documents of the query by ids again
+    RefetchDocuments(DocumentQuery, Vec<Identifier>),
+    /// Delete documents of the query's document type
+    DeleteDocuments(DocumentQuery, Vec<Document>),
+    /// Fetch documents of a query the user is likely to run next, results are
+    /// kept to answer it
+    PrefetchDocuments(DocumentQuery),
+    /// Fetch documents of the query again, its prefetched and cached results
+    /// are dropped first
+    RefreshDocuments(DocumentQuery),
+    /// Build a document of the properties filled in a schema form and check
+    /// it's valid for its type, without broadcasting
+    ComposeDocument {
+        data_contract_name: String,
+        document_type_name: String,
+        properties: Value,
+    },
+    /// Build a document of the properties filled in a schema form and
+    /// broadcast it, owned by the loaded identity
+    BroadcastDocument {
+        data_contract_name: String,
+        document_type_name: String,
+        properties: Value,
+    },
+    /// Replace properties of a document of the query's document type
+    ReplaceDocument {
+        document_query: DocumentQuery,
+        document: Document,
+        properties: Value,
+    },
+    /// Delete a document of the query's document type
+    DeleteDocument(DocumentQuery, Document),
+    /// Fetch documents the document refers according to join rules of its
+    /// type
+    JoinDocument(DocumentQuery, Document),
+    /// Write bytes of a byte array field of the document into a file
+    SaveBinaryField {
+        document: Document,
+        field: String,
+        path: String,
+    },
 }
 
 impl AppState {
+    /// Runs document tasks only reading from Platform, which can be served
+    /// by the simulated platform as well.
+    pub(super) async fn run_document_query_task<'s>(
+        &'s self,
+        platform: &impl Platform,
+        task: DocumentTask,
+    ) -> BackendEvent<'s> {
+        match &task {
+            DocumentTask::QueryDocuments(document_query) => {
+                let prefetched = self.prefetched_documents.lock().await.take(document_query);
+                let execution_result = match prefetched {
+                    Some(documents) => Ok(CompletedTaskPayload::Documents(documents)),
+                    None => platform
+                        .fetch_documents(document_query.clone())
+                        .await
+                        .map(CompletedTaskPayload::Documents),
+                };
+                if let Ok(CompletedTaskPayload::Documents(documents)) = &execution_result {
+                    self.cache_documents(document_query, documents).await;
+                }
+                BackendEvent::TaskCompleted {
+                    task: Task::Document(task),
+                    execution_result,
+                }
+            }
+            DocumentTask::QueryDocumentsWithClauses(clauses) => {
+                let execution_result = match clauses.document_query() {
+                    Ok(document_query) => {
+                        let result = platform
+                            .fetch_documents(document_query.clone())
+                            .await
+                            .map(CompletedTaskPayload::Documents);
+                        if let Ok(CompletedTaskPayload::Documents(documents)) = &result {
+                            self.cache_documents(&document_query, documents).await;
+                        }
+                        result
+                    }
+                    Err(e) => Err(e),
+                };
+                BackendEvent::TaskCompleted {
+                    task: Task::Document(task),
+                    execution_result,
+                }
+            }
+            DocumentTask::PrefetchDocuments(document_query) => {
+                if self
+                    .prefetched_documents
+                    .lock()
+                    .await
+                    .contains(document_query)
+                {
+                    return BackendEvent::TaskCompleted {
+                        task: Task::Document(task),
+                        execution_result: Ok("Already prefetched".into()),
+                    };
+                }
+                let execution_result = match platform.fetch_documents(document_query.clone()).await
+                {
+                    Ok(documents) => {
+                        self.cache_documents(document_query, &documents).await;
+                        let count = documents.len();
+                        self.prefetched_documents
+                            .lock()
+                            .await
+                            .insert(document_query, documents);
+                        tracing::debug!("Prefetched {} documents", count);
+                        Ok(format!("Prefetched {} documents", count).into())
+                    }
+                    Err(e) => {
+                        tracing::debug!("Unable to prefetch documents: {}", e);
+                        Err(e)
+                    }
+                };
+                BackendEvent::TaskCompleted {
+                    task: Task::Document(task),
+                    execution_result,
+                }
+            }
+            DocumentTask::RefreshDocuments(document_query) => {
+                self.prefetched_documents
+                    .lock()
+                    .await
+                    .remove(document_query);
+                self.uncache_documents(document_query).await;
+                let execution_result = platform
+                    .fetch_documents(document_query.clone())
+                    .await
+                    .map(CompletedTaskPayload::Documents);
+                if let Ok(CompletedTaskPayload::Documents(documents)) = &execution_result {
+                    self.cache_documents(document_query, documents).await;
+                }
+                BackendEvent::TaskCompleted {
+                    task: Task::Document(task),
+                    execution_result,
+                }
+            }
+            _ => BackendEvent::TaskCompleted {
+                task: Task::Document(task),
+                execution_result: Err("Not a document query task".to_owned()),
+            },
+        }
+    }
+
     pub(super) async fn run_document_task<'s>(
         &'s self,
         sdk: &Sdk,
         task: DocumentTask,
     ) -> BackendEvent<'s> {
         match &task {
-            DocumentTask::QueryDocuments(document_query) => {
-                let execution_result = Document::fetch_many(&sdk, document_query.clone())
+            DocumentTask::QueryDocuments(_)
+            | DocumentTask::QueryDocumentsWithClauses(_)
+            | DocumentTask::PrefetchDocuments(_)
+            | DocumentTask::RefreshDocuments(_) => self.run_document_query_task(sdk, task).await,
+            DocumentTask::ExportDocuments(documents, format) => {
+                let execution_result = bulk::export_documents(documents, *format).map(|path| {
+                    format!(
+                        "Exported {} documents into {}",
+                        documents.len(),
+                        path.display()
+                    )
+                    .into()
+                });
+                BackendEvent::TaskCompleted {
+                    task: Task::Document(task),
+                    execution_result,
+                }
+            }
+            DocumentTask::RefetchDocuments(document_query, document_ids) => {
+                let execution_result = bulk::refetch_documents(sdk, document_query, document_ids)
                     .await
-                    .map(CompletedTaskPayload::Documents)
+                    .map(CompletedTaskPayload::Documents);
+                if let Ok(CompletedTaskPayload::Documents(documents)) = &execution_result {
+                    self.cache_documents(document_query, documents).await;
+                }
+                BackendEvent::TaskCompleted {
+                    task: Task::Document(task),
+                    execution_result,
+                }
+            }
+            DocumentTask::DeleteDocuments(document_query, documents) => {
+                let execution_result = self
+                    .delete_documents(sdk, document_query, documents)
+                    .await
+                    .map(|stats| stats.info_display().into())
                     .map_err(|e| e.to_string());
                 BackendEvent::TaskCompleted {
                     task: Task::Document(task),
                     execution_result,
                 }
             }
+            DocumentTask::ComposeDocument {
+                data_contract_name,
+                document_type_name,
+                properties,
+            } => {
+                let execution_result = self
+                    .compose_document(
+                        sdk,
+                        data_contract_name,
+                        document_type_name,
+                        properties.clone(),
+                        StdRng::from_entropy().gen(),
+                    )
+                    .await
+                    .map(CompletedTaskPayload::Document);
+                BackendEvent::TaskCompleted {
+                    task: Task::Document(task),
+                    execution_result,
+                }
+            }
+            DocumentTask::BroadcastDocument {
+                data_contract_name,
+                document_type_name,
+                properties,
+            } => {
+                let execution_result = self
+                    .broadcast_document(
+                        sdk,
+                        data_contract_name,
+                        document_type_name,
+                        properties.clone(),
+                    )
+                    .await
+                    .map(CompletedTaskPayload::Document);
+                BackendEvent::TaskCompleted {
+                    task: Task::Document(task),
+                    execution_result,
+                }
+            }
+            DocumentTask::ReplaceDocument {
+                document_query,
+                document,
+                properties,
+            } => {
+                let execution_result = self
+                    .replace_document(sdk, document_query, document, properties.clone())
+                    .await
+                    .map(CompletedTaskPayload::Document);
+                BackendEvent::TaskCompleted {
+                    task: Task::Document(task),
+                    execution_result,
+                }
+            }
+            DocumentTask::DeleteDocument(document_query, document) => {
+                let execution_result = self
+                    .delete_single_document(sdk, document_query, document)
+                    .await
+                    .map(CompletedTaskPayload::String);
+                BackendEvent::TaskCompleted {
+                    task: Task::Document(task),
+                    execution_result,
+                }
+            }
+            DocumentTask::JoinDocument(document_query, document) => {
+                let execution_result = self
+                    .join_document(sdk, document_query, document)
+                    .await
+                    .map(CompletedTaskPayload::String);
+                BackendEvent::TaskCompleted {
+                    task: Task::Document(task),
+                    execution_result,
+                }
+            }
+            DocumentTask::SaveBinaryField {
+                document,
+                field,
+                path,
+            } => BackendEvent::TaskCompleted {
+                execution_result: binary::save_binary_field(document, field, path)
+                    .map(CompletedTaskPayload::String),
+                task: Task::Document(task),
+            },
             DocumentTask::BroadcastRandomDocuments {
                 data_contract_name,
                 document_type_name,
@@ -93,6 +368,7 @@ impl AppState {
                         sdk,
                         identity,
                         &identity_private_keys_lock,
+                        &self.signing_audit,
                         data_contract,
                         &document_type,
                         *count,
@@ -132,6 +408,43 @@ impl AppState {
     }
 }
 
+impl AppState {
+    /// Builds a document owned by the loaded identity, if any, and serializes it
+    /// to check the properties match the document type. The entropy makes the
+    /// document ID.
+    async fn compose_document(
+        &self,
+        sdk: &Sdk,
+        data_contract_name: &str,
+        document_type_name: &str,
+        properties: Value,
+        entropy: [u8; 32],
+    ) -> Result<Document, String> {
+        let known_contracts = self.known_contracts.lock().await;
+        let data_contract = known_contracts
+            .get(data_contract_name)
+            .ok_or_else(|| "Data contract not found".to_owned())?;
+        let document_type = data_contract
+            .document_type_for_name(document_type_name)
+            .map_err(|_| "Document type not found".to_owned())?;
+        let owner_id = self
+            .loaded_identity
+            .lock()
+            .await
+            .as_ref()
+            .map(|identity| identity.id())
+            .unwrap_or_default();
+
+        let document = document_type
+            .create_document_from_data(properties, owner_id, 0, 0, entropy, sdk.version())
+            .map_err(|e| format!("Invalid document: {}", e))?;
+        document
+            .serialize(document_type, sdk.version())
+            .map_err(|e| format!("Invalid document: {}", e))?;
+        Ok(document)
+    }
+}
+
 struct BroadcastRandomDocumentsStats {
     total: u16,
     completed: u16,
@@ -158,6 +471,7 @@ async fn broadcast_random_documents<'s>(
     sdk: &Sdk,
     identity: &Identity,
     identity_private_keys: &IdentityPrivateKeysMap,
+    signing_audit: &SigningAudit,
     data_contract: &DataContract,
     document_type: &DocumentType,
     count: u16,
@@ -192,13 +506,14 @@ async fn broadcast_random_documents<'s>(
     let data_contract = Arc::new(data_contract.clone());
     let mut signer = SimpleSigner::default();
     signer.add_key(identity_public_key.clone(), private_key.to_vec());
+    let signer = AuditedSigner::new(signer, signing_audit, identity.id(), "Documents batch");
 
     fn put_random_document<'a, 'r>(
         sdk: &'a Sdk,
         document_type: &'a DocumentType,
         identity: &'a Identity,
         rng: &'r mut StdRng,
-        signer: &'a SimpleSigner,
+        signer: &'a AuditedSigner<SimpleSigner>,
         identity_public_key: &'a IdentityPublicKey,
         data_contract: Arc<DataContract>,
     ) -> impl Future<Output = Result<(), String>> + 'a {
@@ -0,0 +1,108 @@
+//! Large binary document fields.
+//! Byte arrays like images or blobs are shown as their size, SHA-256 hash and
+//! a hex preview instead of every byte, and can be saved to a file as is.
+
+use std::fs;
+
+use dpp::{
+    document::{Document, DocumentV0Getters, DocumentV0Setters},
+    platform_value::Value,
+    util::hash::hash_single,
+};
+
+use crate::backend::as_json_string;
+
+/// Byte arrays up to this size are shown in full.
+const INLINE_LIMIT: usize = 64;
+
+/// Leading bytes shown of larger byte arrays.
+const PREVIEW_LEN: usize = 16;
+
+fn summary(bytes: &[u8]) -> String {
+    format!(
+        "<{} bytes, sha256 {}, {}…>",
+        bytes.len(),
+        hex::encode(hash_single(bytes)),
+        hex::encode(&bytes[..PREVIEW_LEN])
+    )
+}
+
+fn summarize(value: &mut Value) {
+    match value {
+        Value::Bytes(bytes) if bytes.len() > INLINE_LIMIT => *value = Value::Text(summary(bytes)),
+        Value::Map(entries) => entries.iter_mut().for_each(|(_, value)| summarize(value)),
+        Value::Array(values) => values.iter_mut().for_each(summarize),
+        _ => {}
+    }
+}
+
+/// JSON of the document with large byte arrays summarized.
+pub(crate) fn document_json(document: &Document) -> String {
+    let mut document = document.clone();
+    let mut properties = document.properties().clone();
+    properties.values_mut().for_each(summarize);
+    document.set_properties(properties);
+    as_json_string(&document)
+}
+
+fn collect_large_fields(path: String, value: &Value, fields: &mut Vec<String>) {
+    match value {
+        Value::Bytes(bytes) if bytes.len() > INLINE_LIMIT => fields.push(path),
+        Value::Map(entries) => {
+            for (key, value) in entries {
+                if let Value::Text(key) = key {
+                    collect_large_fields(format!("{}.{}", path, key), value, fields);
+                }
+            }
+        }
+        Value::Array(values) => {
+            for (index, value) in values.iter().enumerate() {
+                collect_large_fields(format!("{}.{}", path, index), value, fields);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Property paths of the document's summarized byte arrays, array items are
+/// referred by their index.
+pub(crate) fn large_binary_fields(document: &Document) -> Vec<String> {
+    let mut fields = Vec::new();
+    for (name, value) in document.properties() {
+        collect_large_fields(name.clone(), value, &mut fields);
+    }
+    fields
+}
+
+/// Writes bytes of the document field at the property path into a file.
+pub(super) fn save_binary_field(
+    document: &Document,
+    field: &str,
+    path: &str,
+) -> Result<String, String> {
+    let mut keys = field.split('.');
+    let first = keys
+        .next()
+        .and_then(|key| document.properties().get(key))
+        .ok_or_else(|| format!("No such field: {}", field))?;
+    let value = keys
+        .try_fold(first, |value, key| match value {
+            Value::Map(entries) => entries
+                .iter()
+                .find(|(k, _)| matches!(k, Value::Text(text) if text == key))
+                .map(|(_, value)| value),
+            Value::Array(values) => key.parse::<usize>().ok().and_then(|i| values.get(i)),
+            _ => None,
+        })
+        .ok_or_else(|| format!("No such field: {}", field))?;
+    let Value::Bytes(bytes) = value else {
+        return Err(format!("Not a byte array field: {}", field));
+    };
+    fs::write(path.trim(), bytes).map_err(|e| format!("Unable to write field bytes: {}", e))?;
+    Ok(format!(
+        "Saved {} bytes of {} into {}",
+        bytes.len(),
+        field,
+        path.trim()
+    ))
+}
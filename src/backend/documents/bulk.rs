@@ -0,0 +1,281 @@
+//! Actions on a selection of fetched documents.
+
+use std::{
+    collections::{BTreeMap, BTreeSet, HashSet},
+    fs,
+    path::{Path, PathBuf},
+};
+
+use dash_sdk::{
+    platform::{transition::broadcast::BroadcastStateTransition, DocumentQuery, Fetch},
+    Sdk,
+};
+use dpp::{
+    data_contract::{
+        accessors::v0::DataContractV0Getters,
+        document_type::{accessors::DocumentTypeV0Getters, DocumentTypeRef},
+    },
+    document::{Document, DocumentV0Getters},
+    identity::{
+        accessors::IdentityGettersV0,
+        identity_public_key::accessors::v0::IdentityPublicKeyGettersV0, KeyType, Purpose,
+    },
+    platform_value::{string_encoding::Encoding, Value},
+    prelude::{Identifier, Identity, IdentityPublicKey},
+    state_transition::documents_batch_transition::{
+        methods::v0::DocumentsBatchTransitionMethodsV0, DocumentsBatchTransition,
+    },
+};
+
+use crate::backend::{
+    as_json_string, error::Error, reports::current_timestamp, signer::IdentitySigner,
+    signing_audit::AuditedSigner, AppState,
+};
+
+const EXPORTS_DIR: &str = "supporting_files/document_exports";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, strum::Display)]
+pub(crate) enum ExportFormat {
+    #[strum(serialize = "JSON")]
+    Json,
+    #[strum(serialize = "CSV")]
+    Csv,
+}
+
+/// Writes documents into a new file of the exports directory, returning its
+/// path.
+pub(super) fn export_documents(
+    documents: &[Document],
+    format: ExportFormat,
+) -> Result<PathBuf, String> {
+    let (content, extension) = match format {
+        ExportFormat::Json => (as_json_string(&documents), "json"),
+        ExportFormat::Csv => (documents_csv(documents), "csv"),
+    };
+
+    fs::create_dir_all(EXPORTS_DIR)
+        .map_err(|e| format!("Failed to create exports directory: {}", e))?;
+    let path =
+        Path::new(EXPORTS_DIR).join(format!("documents_{}.{}", current_timestamp(), extension));
+    fs::write(&path, content).map_err(|e| format!("Failed to write export file: {}", e))?;
+
+    Ok(path)
+}
+
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_owned()
+    }
+}
+
+fn csv_value(value: &Value) -> String {
+    match value {
+        Value::Text(text) => text.clone(),
+        value => serde_json::to_string(value).unwrap_or_default(),
+    }
+}
+
+/// One row per document, properties of all documents become columns.
+fn documents_csv(documents: &[Document]) -> String {
+    let property_names: BTreeSet<&String> = documents
+        .iter()
+        .flat_map(|document| document.properties().keys())
+        .collect();
+
+    let header = ["$id", "$ownerId", "$revision", "$createdAt", "$updatedAt"]
+        .into_iter()
+        .chain(property_names.iter().map(|name| name.as_str()))
+        .map(csv_field)
+        .collect::<Vec<_>>()
+        .join(",");
+
+    let optional = |value: Option<u64>| value.map(|v| v.to_string()).unwrap_or_default();
+
+    let rows = documents.iter().map(|document| {
+        [
+            document.id().to_string(Encoding::Base58),
+            document.owner_id().to_string(Encoding::Base58),
+            optional(document.revision()),
+            optional(document.created_at()),
+            optional(document.updated_at()),
+        ]
+        .into_iter()
+        .chain(property_names.iter().map(|name| {
+            document
+                .properties()
+                .get(*name)
+                .map(csv_value)
+                .unwrap_or_default()
+        }))
+        .map(|field| csv_field(&field))
+        .collect::<Vec<_>>()
+        .join(",")
+    });
+
+    let mut lines = vec![header];
+    lines.extend(rows);
+    lines.push(String::new());
+    lines.join("\n")
+}
+
+/// Fetches documents by their ids again, every document comes with a proof
+/// verified by the SDK.
+pub(super) async fn refetch_documents(
+    sdk: &Sdk,
+    query: &DocumentQuery,
+    document_ids: &[Identifier],
+) -> Result<BTreeMap<Identifier, Option<Document>>, String> {
+    let mut documents = BTreeMap::new();
+    for document_id in document_ids {
+        let document = Document::fetch(sdk, query.clone().with_document_id(document_id))
+            .await
+            .map_err(|e| {
+                format!(
+                    "Unable to fetch document {}: {}",
+                    document_id.to_string(Encoding::Base58),
+                    e
+                )
+            })?;
+        documents.insert(*document_id, document);
+    }
+    Ok(documents)
+}
+
+pub(super) struct DeleteDocumentsStats {
+    total: usize,
+    deleted: usize,
+    last_error: Option<String>,
+}
+
+impl DeleteDocumentsStats {
+    pub(super) fn info_display(&self) -> String {
+        format!(
+            "Delete documents results:
+Deleted {} of {}
+Last error: {}",
+            self.deleted,
+            self.total,
+            self.last_error.as_deref().unwrap_or("")
+        )
+    }
+}
+
+impl AppState {
+    /// Authentication key of the identity meeting the document type security
+    /// level, the private key must be known unless a remote signer is used.
+    pub(super) async fn document_signing_key<'i>(
+        &self,
+        identity: &'i Identity,
+        document_type: DocumentTypeRef<'_>,
+    ) -> Result<&'i IdentityPublicKey, Error> {
+        let identity_public_key = identity
+            .get_first_public_key_matching(
+                Purpose::AUTHENTICATION,
+                HashSet::from([document_type.security_level_requirement()]),
+                HashSet::from([KeyType::ECDSA_SECP256K1, KeyType::BLS12_381]),
+            )
+            .ok_or(Error::DocumentSigningError(
+                "No public key matching security level requirements".to_string(),
+            ))?;
+
+        if self.remote_signer.is_none()
+            && !self
+                .identity_private_keys
+                .lock()
+                .await
+                .contains_key(&(identity.id(), identity_public_key.id()))
+        {
+            return Err(Error::DocumentSigningError(
+                "No private key for the signing public key".to_string(),
+            ));
+        }
+        Ok(identity_public_key)
+    }
+
+    /// Broadcasts a deletion transition for each document, the loaded identity
+    /// must own them.
+    pub(super) async fn delete_documents(
+        &self,
+        sdk: &Sdk,
+        query: &DocumentQuery,
+        documents: &[Document],
+    ) -> Result<DeleteDocumentsStats, Error> {
+        let loaded_identity = self.loaded_identity.lock().await;
+        let identity = loaded_identity
+            .as_ref()
+            .ok_or_else(|| Error::IdentityError("No loaded identity".to_owned()))?;
+
+        let data_contract = &query.data_contract;
+        let document_type = data_contract
+            .document_type_for_name(&query.document_type_name)
+            .map_err(|e| Error::DocumentSigningError(e.to_string()))?;
+
+        let identity_public_key = self.document_signing_key(identity, document_type).await?;
+        let signer = self.identity_signer(identity, "Documents batch").await;
+
+        let identity_contract_nonce = sdk
+            .get_identity_contract_nonce(identity.id(), data_contract.id(), true, None)
+            .await?;
+
+        let mut stats = DeleteDocumentsStats {
+            total: documents.len(),
+            deleted: 0,
+            last_error: None,
+        };
+
+        let mut nonce_offset = 0;
+        for document in documents {
+            if document.owner_id() != identity.id() {
+                stats.last_error = Some(format!(
+                    "Document {} is not owned by the loaded identity",
+                    document.id().to_string(Encoding::Base58)
+                ));
+                continue;
+            }
+
+            let result = delete_document(
+                sdk,
+                document,
+                document_type,
+                identity_public_key,
+                identity_contract_nonce + nonce_offset,
+                &signer,
+            )
+            .await;
+            nonce_offset += 1;
+            match result {
+                Ok(()) => stats.deleted += 1,
+                Err(e) => stats.last_error = Some(e),
+            }
+        }
+
+        Ok(stats)
+    }
+}
+
+pub(super) async fn delete_document(
+    sdk: &Sdk,
+    document: &Document,
+    document_type: DocumentTypeRef<'_>,
+    identity_public_key: &IdentityPublicKey,
+    identity_contract_nonce: u64,
+    signer: &AuditedSigner<IdentitySigner>,
+) -> Result<(), String> {
+    let transition = DocumentsBatchTransition::new_document_deletion_transition_from_document(
+        document.clone(),
+        document_type,
+        identity_public_key,
+        identity_contract_nonce,
+        0,
+        signer,
+        sdk.version(),
+        None,
+        None,
+        None,
+    )
+    .map_err(|e| e.to_string())?;
+
+    transition.broadcast(sdk).await.map_err(|e| e.to_string())
+}
@@ -0,0 +1,112 @@
+//! Document queries composed clause by clause.
+//! Values of conditions are entered as text, JSON when possible and plain text
+//! otherwise, with identifier fields given in base 58.
+
+use dapi_grpc::platform::v0::get_documents_request::get_documents_request_v0::Start;
+use dash_sdk::platform::{DocumentQuery, DriveQuery};
+use dpp::{
+    data_contract::{
+        accessors::v0::DataContractV0Getters,
+        document_type::{accessors::DocumentTypeV0Getters, DocumentPropertyType},
+    },
+    platform_value::{string_encoding::Encoding, Value},
+    prelude::{DataContract, Identifier},
+};
+use drive::query::{OrderClause, WhereClause, WhereOperator};
+use serde_json::Value as JsonValue;
+
+/// Operators available for conditions.
+pub(crate) const WHERE_OPERATORS: [&str; 7] = ["=", ">", ">=", "<", "<=", "in", "startsWith"];
+
+/// Document system fields that can be queried besides the properties.
+pub(crate) const SYSTEM_FIELDS: [&str; 4] = ["$id", "$ownerId", "$createdAt", "$updatedAt"];
+
+/// Query of a document type as composed in the query builder.
+#[derive(Debug, Clone)]
+pub(crate) struct QueryClauses {
+    pub(crate) data_contract: DataContract,
+    pub(crate) document_type_name: String,
+    /// Field, operator and value text of each condition
+    pub(crate) where_clauses: Vec<(String, String, String)>,
+    /// Field and whether it's sorted in ascending order
+    pub(crate) order_by: Vec<(String, bool)>,
+    /// Zero for the Platform default
+    pub(crate) limit: u32,
+    /// Base 58 ID of the first document
+    pub(crate) start_at: Option<String>,
+}
+
+fn where_operator(operator: &str) -> Result<WhereOperator, String> {
+    match operator {
+        "=" => Ok(WhereOperator::Equal),
+        ">" => Ok(WhereOperator::GreaterThan),
+        ">=" => Ok(WhereOperator::GreaterThanOrEquals),
+        "<" => Ok(WhereOperator::LessThan),
+        "<=" => Ok(WhereOperator::LessThanOrEquals),
+        "in" => Ok(WhereOperator::In),
+        "startsWith" => Ok(WhereOperator::StartsWith),
+        _ => Err(format!("Unknown operator: {}", operator)),
+    }
+}
+
+fn clause_value(json: JsonValue, is_identifier: bool) -> Result<Value, String> {
+    match json {
+        JsonValue::String(text) if is_identifier => {
+            Identifier::from_string(&text, Encoding::Base58)
+                .map(|id| Value::Identifier(id.to_buffer()))
+                .map_err(|e| format!("Invalid identifier: {} ({})", text, e))
+        }
+        JsonValue::Array(items) => items
+            .into_iter()
+            .map(|item| clause_value(item, is_identifier))
+            .collect::<Result<_, _>>()
+            .map(Value::Array),
+        json => Ok(Value::from(json)),
+    }
+}
+
+impl QueryClauses {
+    /// Translates the clauses into a document query, validated as a drive
+    /// query so unsupported combinations are reported before fetching.
+    pub(crate) fn document_query(&self) -> Result<DocumentQuery, String> {
+        let document_type = self
+            .data_contract
+            .document_type_for_name(&self.document_type_name)
+            .map_err(|e| e.to_string())?;
+        let mut query = DocumentQuery::new(self.data_contract.clone(), &self.document_type_name)
+            .map_err(|e| e.to_string())?;
+
+        for (field, operator, value) in &self.where_clauses {
+            let is_identifier = matches!(field.as_str(), "$id" | "$ownerId")
+                || matches!(
+                    document_type
+                        .flattened_properties()
+                        .get(field)
+                        .map(|property| &property.property_type),
+                    Some(DocumentPropertyType::Identifier)
+                );
+            let json = serde_json::from_str(value.trim())
+                .unwrap_or_else(|_| JsonValue::String(value.trim().to_owned()));
+            query.where_clauses.push(WhereClause {
+                field: field.clone(),
+                operator: where_operator(operator)?,
+                value: clause_value(json, is_identifier)?,
+            });
+        }
+        for (field, ascending) in &self.order_by {
+            query.order_by_clauses.push(OrderClause {
+                field: field.clone(),
+                ascending: *ascending,
+            });
+        }
+        query.limit = self.limit;
+        if let Some(start_at) = &self.start_at {
+            let id = Identifier::from_string(start_at.trim(), Encoding::Base58)
+                .map_err(|e| format!("Invalid start document ID: {}", e))?;
+            query.start = Some(Start::StartAt(id.to_vec()));
+        }
+
+        DriveQuery::try_from(&query).map_err(|e| format!("Invalid query: {}", e))?;
+        Ok(query)
+    }
+}
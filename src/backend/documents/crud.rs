@@ -0,0 +1,213 @@
+//! Single document create, replace and delete.
+//! Documents are built of the properties filled in a schema form, checked
+//! against their document type and signed by the loaded identity, which must
+//! own the documents it replaces or deletes.
+
+use std::sync::Arc;
+
+use dash_sdk::{
+    platform::{
+        transition::{broadcast::BroadcastStateTransition, put_document::PutDocument},
+        DocumentQuery,
+    },
+    Sdk,
+};
+use dpp::{
+    data_contract::{
+        accessors::v0::DataContractV0Getters,
+        document_type::{accessors::DocumentTypeV0Getters, methods::DocumentTypeV0Methods},
+    },
+    document::{
+        serialization_traits::DocumentPlatformConversionMethodsV0, Document, DocumentV0Getters,
+        DocumentV0Setters,
+    },
+    identity::accessors::IdentityGettersV0,
+    platform_value::{string_encoding::Encoding, Value},
+    prelude::Identity,
+    state_transition::documents_batch_transition::{
+        methods::v0::DocumentsBatchTransitionMethodsV0, DocumentsBatchTransition,
+    },
+};
+use rand::{prelude::StdRng, Rng, SeedableRng};
+
+use super::bulk::delete_document;
+use crate::backend::AppState;
+
+fn check_owner(identity: &Identity, document: &Document) -> Result<(), String> {
+    if document.owner_id() != identity.id() {
+        return Err(format!(
+            "Document {} is not owned by the loaded identity",
+            document.id().to_string(Encoding::Base58)
+        ));
+    }
+    Ok(())
+}
+
+impl AppState {
+    /// Broadcasts a new document of the properties, owned by the loaded
+    /// identity, and waits for platform to store it.
+    pub(super) async fn broadcast_document(
+        &self,
+        sdk: &Sdk,
+        data_contract_name: &str,
+        document_type_name: &str,
+        properties: Value,
+    ) -> Result<Document, String> {
+        let entropy: [u8; 32] = StdRng::from_entropy().gen();
+        let document = self
+            .compose_document(
+                sdk,
+                data_contract_name,
+                document_type_name,
+                properties,
+                entropy,
+            )
+            .await?;
+
+        let data_contract = self
+            .known_contracts
+            .lock()
+            .await
+            .get(data_contract_name)
+            .cloned()
+            .ok_or_else(|| "Data contract not found".to_owned())?;
+        let document_type = data_contract
+            .document_type_for_name(document_type_name)
+            .map_err(|_| "Document type not found".to_owned())?;
+
+        let loaded_identity = self.loaded_identity.lock().await;
+        let identity = loaded_identity
+            .as_ref()
+            .ok_or_else(|| "No loaded identity".to_owned())?;
+        let identity_public_key = self
+            .document_signing_key(identity, document_type)
+            .await
+            .map_err(|e| e.to_string())?
+            .clone();
+        let signer = self.identity_signer(identity, "Documents batch").await;
+
+        document
+            .put_to_platform_and_wait_for_response(
+                sdk,
+                document_type.to_owned_document_type(),
+                entropy,
+                identity_public_key,
+                Arc::new(data_contract.clone()),
+                &signer,
+            )
+            .await
+            .map_err(|e| e.to_string())
+    }
+
+    /// Broadcasts a replacement of the document properties, returning the
+    /// document as replaced.
+    pub(super) async fn replace_document(
+        &self,
+        sdk: &Sdk,
+        query: &DocumentQuery,
+        document: &Document,
+        properties: Value,
+    ) -> Result<Document, String> {
+        let data_contract = &query.data_contract;
+        let document_type = data_contract
+            .document_type_for_name(&query.document_type_name)
+            .map_err(|_| "Document type not found".to_owned())?;
+        if !document_type.documents_mutable() {
+            return Err(format!(
+                "Documents of {} can't be replaced",
+                query.document_type_name
+            ));
+        }
+
+        let loaded_identity = self.loaded_identity.lock().await;
+        let identity = loaded_identity
+            .as_ref()
+            .ok_or_else(|| "No loaded identity".to_owned())?;
+        check_owner(identity, document)?;
+
+        // A new document is built only to check the properties, the replacement
+        // keeps the document ID and has the next revision
+        let entropy: [u8; 32] = StdRng::from_entropy().gen();
+        let composed = document_type
+            .create_document_from_data(properties, identity.id(), 0, 0, entropy, sdk.version())
+            .map_err(|e| format!("Invalid document: {}", e))?;
+        let mut replacement = document.clone();
+        replacement.set_properties(composed.properties().clone());
+        replacement.set_revision(document.revision().map(|revision| revision + 1));
+        replacement
+            .serialize(document_type, sdk.version())
+            .map_err(|e| format!("Invalid document: {}", e))?;
+
+        let identity_public_key = self
+            .document_signing_key(identity, document_type)
+            .await
+            .map_err(|e| e.to_string())?;
+        let signer = self.identity_signer(identity, "Documents batch").await;
+        let identity_contract_nonce = sdk
+            .get_identity_contract_nonce(identity.id(), data_contract.id(), true, None)
+            .await
+            .map_err(|e| e.to_string())?;
+
+        let transition =
+            DocumentsBatchTransition::new_document_replacement_transition_from_document(
+                replacement.clone(),
+                document_type,
+                identity_public_key,
+                identity_contract_nonce,
+                0,
+                &signer,
+                sdk.version(),
+                None,
+                None,
+                None,
+            )
+            .map_err(|e| e.to_string())?;
+        transition.broadcast(sdk).await.map_err(|e| e.to_string())?;
+
+        Ok(replacement)
+    }
+
+    /// Broadcasts a deletion of the document.
+    pub(super) async fn delete_single_document(
+        &self,
+        sdk: &Sdk,
+        query: &DocumentQuery,
+        document: &Document,
+    ) -> Result<String, String> {
+        let data_contract = &query.data_contract;
+        let document_type = data_contract
+            .document_type_for_name(&query.document_type_name)
+            .map_err(|_| "Document type not found".to_owned())?;
+
+        let loaded_identity = self.loaded_identity.lock().await;
+        let identity = loaded_identity
+            .as_ref()
+            .ok_or_else(|| "No loaded identity".to_owned())?;
+        check_owner(identity, document)?;
+
+        let identity_public_key = self
+            .document_signing_key(identity, document_type)
+            .await
+            .map_err(|e| e.to_string())?;
+        let signer = self.identity_signer(identity, "Documents batch").await;
+        let identity_contract_nonce = sdk
+            .get_identity_contract_nonce(identity.id(), data_contract.id(), true, None)
+            .await
+            .map_err(|e| e.to_string())?;
+
+        delete_document(
+            sdk,
+            document,
+            document_type,
+            identity_public_key,
+            identity_contract_nonce,
+            &signer,
+        )
+        .await?;
+
+        Ok(format!(
+            "Deleted document {}",
+            document.id().to_string(Encoding::Base58)
+        ))
+    }
+}
@@ -0,0 +1,265 @@
+//! Joins of documents across contracts.
+//! A join rule tells which property of a document type refers documents of
+//! another contract, like the recipient of a Dashpay contact request being
+//! the identity of a DPNS domain. Rules are kept in a JSON file of the profile,
+//! edited by hand and read again on every join, so changes apply right away.
+//! Without the file the Dashpay to DPNS rules are used.
+
+use std::{collections::BTreeMap, fmt::Write, fs, path::PathBuf};
+
+use dash_sdk::{
+    platform::{DocumentQuery, Fetch, FetchMany},
+    Sdk,
+};
+use dpp::{
+    data_contract::accessors::v0::DataContractV0Getters,
+    document::{Document, DocumentV0Getters},
+    platform_value::{string_encoding::Encoding, Value},
+    prelude::{DataContract, Identifier},
+    system_data_contracts::{dashpay_contract, dpns_contract},
+};
+use drive::query::{WhereClause, WhereOperator};
+use serde::{Deserialize, Serialize};
+
+use crate::backend::AppState;
+
+/// Target documents shown per rule, a reference usually matches one.
+const JOIN_LIMIT: u32 = 5;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct JoinRule {
+    pub name: String,
+    /// Base58 encoded data contract id of documents holding the reference
+    pub source_contract_id: String,
+    pub source_document_type: String,
+    /// Property path of the reference, `$id` and `$ownerId` included
+    pub source_field: String,
+    /// Base58 encoded data contract id of referred documents
+    pub target_contract_id: String,
+    pub target_document_type: String,
+    /// Indexed property path of referred documents matching the reference
+    pub target_field: String,
+    /// Properties of referred documents to show, all if empty
+    #[serde(default)]
+    pub display_fields: Vec<String>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub(crate) struct JoinRules {
+    #[serde(skip)]
+    path: PathBuf,
+    rules: Vec<JoinRule>,
+}
+
+fn default_rules() -> Vec<JoinRule> {
+    let dashpay_id = Identifier::from(dashpay_contract::ID_BYTES).to_string(Encoding::Base58);
+    let dpns_id = Identifier::from(dpns_contract::ID_BYTES).to_string(Encoding::Base58);
+    let domain_rule = |name: &str, source_field: &str| JoinRule {
+        name: name.to_owned(),
+        source_contract_id: dashpay_id.clone(),
+        source_document_type: "contactRequest".to_owned(),
+        source_field: source_field.to_owned(),
+        target_contract_id: dpns_id.clone(),
+        target_document_type: "domain".to_owned(),
+        target_field: "records.dashUniqueIdentityId".to_owned(),
+        display_fields: vec!["label".to_owned(), "normalizedParentDomainName".to_owned()],
+    };
+    vec![
+        domain_rule("Sender DPNS name", "$ownerId"),
+        domain_rule("Recipient DPNS name", "toUserId"),
+    ]
+}
+
+impl JoinRules {
+    /// Reads rules of the file, the default ones if it doesn't exist.
+    pub(crate) fn load(path: PathBuf) -> Self {
+        let mut join_rules = JoinRules {
+            path,
+            rules: default_rules(),
+        };
+        join_rules.reload();
+        join_rules
+    }
+
+    /// Reads rules of the file again, keeping the current ones if it's invalid.
+    fn reload(&mut self) {
+        let Ok(bytes) = fs::read(&self.path) else {
+            return;
+        };
+        match serde_json::from_slice::<JoinRules>(&bytes) {
+            Ok(join_rules) => self.rules = join_rules.rules,
+            Err(e) => tracing::error!("Unable to load join rules: {}", e),
+        }
+    }
+}
+
+/// Value at the property path of the document.
+fn document_value(document: &Document, path: &str) -> Option<Value> {
+    match path {
+        "$id" => return Some(Value::Identifier(document.id().to_buffer())),
+        "$ownerId" => return Some(Value::Identifier(document.owner_id().to_buffer())),
+        _ => {}
+    }
+    let mut keys = path.split('.');
+    let first = document.properties().get(keys.next()?)?;
+    keys.try_fold(first, |value, key| match value {
+        Value::Map(map) => map
+            .iter()
+            .find(|(k, _)| matches!(k, Value::Text(text) if text == key))
+            .map(|(_, value)| value),
+        _ => None,
+    })
+    .cloned()
+}
+
+fn display_value(value: &Value) -> String {
+    match value {
+        Value::Identifier(bytes) => Identifier::from(*bytes).to_string(Encoding::Base58),
+        Value::Text(text) => text.clone(),
+        value => serde_json::to_string(value).unwrap_or_default(),
+    }
+}
+
+impl AppState {
+    /// Fetches documents referred by the document according to the join rules
+    /// of its type and renders them along with the references.
+    pub(super) async fn join_document(
+        &self,
+        sdk: &Sdk,
+        document_query: &DocumentQuery,
+        document: &Document,
+    ) -> Result<String, String> {
+        let source_contract_id = document_query
+            .data_contract
+            .id()
+            .to_string(Encoding::Base58);
+        let rules: Vec<JoinRule> = {
+            let mut join_rules = self.join_rules.lock().await;
+            join_rules.reload();
+            join_rules
+                .rules
+                .iter()
+                .filter(|rule| {
+                    rule.source_contract_id == source_contract_id
+                        && rule.source_document_type == document_query.document_type_name
+                })
+                .cloned()
+                .collect()
+        };
+        if rules.is_empty() {
+            return Err(format!(
+                "No join rules for document type: {}",
+                document_query.document_type_name
+            ));
+        }
+
+        let mut contracts: BTreeMap<String, DataContract> = BTreeMap::new();
+        let mut record = format!(
+            "{} {}\n",
+            document_query.document_type_name,
+            document.id().to_string(Encoding::Base58)
+        );
+        for rule in rules {
+            let _ = writeln!(record, "\n{}", rule.name);
+            let Some(reference) = document_value(document, &rule.source_field) else {
+                let _ = writeln!(record, "  {} is not set", rule.source_field);
+                continue;
+            };
+            let _ = writeln!(
+                record,
+                "  {}: {}",
+                rule.source_field,
+                display_value(&reference)
+            );
+            match self
+                .fetch_joined_documents(sdk, &mut contracts, &rule, reference)
+                .await
+            {
+                Ok(documents) if documents.is_empty() => {
+                    let _ = writeln!(record, "  no {} found", rule.target_document_type);
+                }
+                Ok(documents) => {
+                    for joined in documents {
+                        let fields: Vec<String> = if rule.display_fields.is_empty() {
+                            joined.properties().keys().cloned().collect()
+                        } else {
+                            rule.display_fields.clone()
+                        };
+                        let values = fields
+                            .iter()
+                            .map(|field| {
+                                format!(
+                                    "{}: {}",
+                                    field,
+                                    document_value(&joined, field)
+                                        .map(|value| display_value(&value))
+                                        .unwrap_or_default()
+                                )
+                            })
+                            .collect::<Vec<_>>()
+                            .join(", ");
+                        let _ = writeln!(
+                            record,
+                            "  → {} {}: {}",
+                            rule.target_document_type,
+                            joined.id().to_string(Encoding::Base58),
+                            values
+                        );
+                    }
+                }
+                Err(e) => {
+                    let _ = writeln!(record, "  unable to join: {}", e);
+                }
+            }
+        }
+        Ok(record)
+    }
+
+    /// Documents of the rule target matching the reference, contracts not
+    /// known yet are fetched once and reused for other rules.
+    async fn fetch_joined_documents(
+        &self,
+        sdk: &Sdk,
+        contracts: &mut BTreeMap<String, DataContract>,
+        rule: &JoinRule,
+        reference: Value,
+    ) -> Result<Vec<Document>, String> {
+        if !contracts.contains_key(&rule.target_contract_id) {
+            let contract_id = Identifier::from_string(&rule.target_contract_id, Encoding::Base58)
+                .map_err(|e| format!("Invalid contract id: {}", e))?;
+            let known = self
+                .known_contracts
+                .lock()
+                .await
+                .values()
+                .find(|contract| contract.id() == contract_id)
+                .cloned();
+            let contract = match known {
+                Some(contract) => contract,
+                None => DataContract::fetch(sdk, contract_id)
+                    .await
+                    .map_err(|e| e.to_string())?
+                    .ok_or_else(|| "Data contract not found".to_owned())?,
+            };
+            contracts.insert(rule.target_contract_id.clone(), contract);
+        }
+        let contract = contracts[&rule.target_contract_id].clone();
+
+        let mut query = DocumentQuery::new(contract, &rule.target_document_type)
+            .map_err(|e| e.to_string())?
+            .with_where(WhereClause {
+                field: rule.target_field.clone(),
+                operator: WhereOperator::Equal,
+                value: reference,
+            });
+        query.limit = JOIN_LIMIT;
+
+        Ok(Document::fetch_many(sdk, query)
+            .await
+            .map_err(|e| e.to_string())?
+            .into_values()
+            .flatten()
+            .collect())
+    }
+}
@@ -0,0 +1,77 @@
+//! Documents prefetched while the UI is idle.
+//! Screens hint at the query the user is likely to run next, its results are
+//! kept for a short while and served to the actual query without a request.
+
+use std::{
+    collections::BTreeMap,
+    time::{Duration, Instant},
+};
+
+use dash_sdk::platform::DocumentQuery;
+use dpp::{
+    data_contract::accessors::v0::DataContractV0Getters, document::Document, prelude::Identifier,
+};
+
+/// Prefetched results older than this are fetched again.
+const PREFETCH_TTL: Duration = Duration::from_secs(30);
+
+/// Prefetched queries kept at most, the oldest is dropped first.
+const MAX_ENTRIES: usize = 8;
+
+type Documents = BTreeMap<Identifier, Option<Document>>;
+
+#[derive(Debug, Default)]
+pub(crate) struct PrefetchedDocuments {
+    entries: Vec<(String, Instant, Documents)>,
+}
+
+/// Queries don't implement comparison, so they're matched by a string made of
+/// the parts that affect the results.
+pub(crate) fn query_key(query: &DocumentQuery) -> String {
+    format!(
+        "{:?}/{}/{:?}/{:?}/{}/{:?}",
+        query.data_contract.id(),
+        query.document_type_name,
+        query.where_clauses,
+        query.order_by_clauses,
+        query.limit,
+        query.start
+    )
+}
+
+impl PrefetchedDocuments {
+    fn remove_expired(&mut self) {
+        self.entries
+            .retain(|(_, fetched_at, _)| fetched_at.elapsed() < PREFETCH_TTL);
+    }
+
+    pub(crate) fn contains(&mut self, query: &DocumentQuery) -> bool {
+        self.remove_expired();
+        let key = query_key(query);
+        self.entries.iter().any(|(k, ..)| *k == key)
+    }
+
+    pub(crate) fn insert(&mut self, query: &DocumentQuery, documents: Documents) {
+        self.remove_expired();
+        let key = query_key(query);
+        self.entries.retain(|(k, ..)| *k != key);
+        if self.entries.len() >= MAX_ENTRIES {
+            self.entries.remove(0);
+        }
+        self.entries.push((key, Instant::now(), documents));
+    }
+
+    /// Drops results of the query if it was prefetched.
+    pub(crate) fn remove(&mut self, query: &DocumentQuery) {
+        let key = query_key(query);
+        self.entries.retain(|(k, ..)| *k != key);
+    }
+
+    /// Takes fresh results of the query if it was prefetched.
+    pub(crate) fn take(&mut self, query: &DocumentQuery) -> Option<Documents> {
+        self.remove_expired();
+        let key = query_key(query);
+        let index = self.entries.iter().position(|(k, ..)| *k == key)?;
+        Some(self.entries.remove(index).2)
+    }
+}
@@ -0,0 +1,54 @@
+//! Passphrase encryption of secrets written to files.
+//! Data is encrypted with ChaCha20-Poly1305 using a key derived from the
+//! passphrase with Argon2, the random salt and nonce are written before the
+//! ciphertext.
+
+use argon2::Argon2;
+use chacha20poly1305::{aead::Aead, ChaCha20Poly1305, Key, KeyInit, Nonce};
+use rand::RngCore;
+
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
+
+fn derive_key(passphrase: &str, salt: &[u8]) -> Result<Key, String> {
+    let mut key = Key::default();
+    Argon2::default()
+        .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+        .map_err(|e| format!("Unable to derive the encryption key: {}", e))?;
+    Ok(key)
+}
+
+/// Salt, nonce and ciphertext of the data.
+pub(crate) fn encrypt(passphrase: &str, plaintext: &[u8]) -> Result<Vec<u8>, String> {
+    if passphrase.is_empty() {
+        return Err("Passphrase must not be empty".to_owned());
+    }
+    let mut salt = [0u8; SALT_LEN];
+    let mut nonce = [0u8; NONCE_LEN];
+    rand::thread_rng().fill_bytes(&mut salt);
+    rand::thread_rng().fill_bytes(&mut nonce);
+    let cipher = ChaCha20Poly1305::new(&derive_key(passphrase, &salt)?);
+    let ciphertext = cipher
+        .encrypt(Nonce::from_slice(&nonce), plaintext)
+        .map_err(|_| "Unable to encrypt the data".to_owned())?;
+
+    let mut data = Vec::with_capacity(SALT_LEN + NONCE_LEN + ciphertext.len());
+    data.extend_from_slice(&salt);
+    data.extend_from_slice(&nonce);
+    data.extend_from_slice(&ciphertext);
+    Ok(data)
+}
+
+/// Plaintext of data written by [encrypt].
+pub(crate) fn decrypt(passphrase: &str, data: &[u8]) -> Result<Vec<u8>, String> {
+    const WRONG_PASSPHRASE: &str = "Wrong passphrase or corrupted data";
+    if data.len() <= SALT_LEN + NONCE_LEN {
+        return Err(WRONG_PASSPHRASE.to_owned());
+    }
+    let (salt, rest) = data.split_at(SALT_LEN);
+    let (nonce, ciphertext) = rest.split_at(NONCE_LEN);
+    let cipher = ChaCha20Poly1305::new(&derive_key(passphrase, salt)?);
+    cipher
+        .decrypt(Nonce::from_slice(nonce), ciphertext)
+        .map_err(|_| WRONG_PASSPHRASE.to_owned())
+}
@@ -0,0 +1,164 @@
+//! Export of fetched entities.
+//! The identity, contract or document shown on a screen is written into a JSON
+//! file, optionally along with its raw serialized bytes in a `.bin` file next
+//! to it, e.g. to archive the state of Platform at a given block. Identities
+//! are fetched again with proof so the export tells the block they were proven
+//! at, contracts and documents are written as displayed.
+
+use std::{fs, path::Path, sync::Arc};
+
+use dash_sdk::{platform::Fetch, Sdk};
+use dpp::{
+    data_contract::accessors::v0::DataContractV0Getters,
+    document::{serialization_traits::DocumentPlatformConversionMethodsV0, DocumentV0Getters},
+    platform_value::{string_encoding::Encoding, Identifier},
+    prelude::{DataContract, Identity},
+    serialization::{PlatformSerializable, PlatformSerializableWithPlatformVersion},
+};
+use serde::Serialize;
+
+use super::{
+    as_json_string, identities::key_status::ProvenAt, reports::current_timestamp, BackendEvent,
+    Task,
+};
+
+const EXPORTS_DIR: &str = "supporting_files/entity_exports";
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum ExportedEntity {
+    /// Base58 ID of the identity
+    Identity(String),
+    Contract(DataContract),
+    Document {
+        document: dpp::document::Document,
+        data_contract: Arc<DataContract>,
+        document_type_name: String,
+    },
+}
+
+impl ExportedEntity {
+    fn kind(&self) -> &'static str {
+        match self {
+            ExportedEntity::Identity(_) => "identity",
+            ExportedEntity::Contract(_) => "contract",
+            ExportedEntity::Document { .. } => "document",
+        }
+    }
+
+    fn id(&self) -> String {
+        match self {
+            ExportedEntity::Identity(id) => id.clone(),
+            ExportedEntity::Contract(contract) => contract.id().to_string(Encoding::Base58),
+            ExportedEntity::Document { document, .. } => document.id().to_string(Encoding::Base58),
+        }
+    }
+
+    /// Path offered for the export, in the exports directory.
+    pub(crate) fn default_path(&self) -> String {
+        format!("{}/{}_{}.json", EXPORTS_DIR, self.kind(), self.id())
+    }
+}
+
+/// Write an entity into a JSON file, and its serialized bytes into a `.bin`
+/// file of the same name if `raw` is set.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct ExportTask {
+    pub(crate) entity: ExportedEntity,
+    pub(crate) path: String,
+    pub(crate) raw: bool,
+}
+
+#[derive(Serialize)]
+struct EntityExport {
+    entity: &'static str,
+    id: String,
+    /// Platform state the entity was proven against, if fetched for the
+    /// export
+    proven_at: Option<ProvenAt>,
+    /// Unix timestamp in seconds of the export
+    exported_at: u64,
+    data: serde_json::Value,
+}
+
+/// JSON and serialized bytes of the entity, with the proof metadata of
+/// identities fetched again.
+async fn serialize_entity(sdk: &Sdk, entity: &ExportedEntity) -> Result<(String, Vec<u8>), String> {
+    let platform_version = sdk.version();
+    let (json, raw, proven_at) = match entity {
+        ExportedEntity::Identity(id) => {
+            let identity_id = Identifier::from_string(id, Encoding::Base58)
+                .map_err(|_| "Can't parse identifier as base58 string".to_owned())?;
+            let (identity, metadata) = Identity::fetch_with_metadata(sdk, identity_id, None)
+                .await
+                .map_err(|e| e.to_string())?;
+            let identity = identity.ok_or_else(|| format!("Identity not found: {}", id))?;
+            let raw = identity.serialize_to_bytes().map_err(|e| e.to_string())?;
+            (
+                serde_json::to_value(&identity).map_err(|e| e.to_string())?,
+                raw,
+                Some(metadata.into()),
+            )
+        }
+        ExportedEntity::Contract(contract) => (
+            serde_json::to_value(contract).map_err(|e| e.to_string())?,
+            contract
+                .serialize_to_bytes_with_platform_version(platform_version)
+                .map_err(|e| e.to_string())?,
+            None,
+        ),
+        ExportedEntity::Document {
+            document,
+            data_contract,
+            document_type_name,
+        } => {
+            let document_type = data_contract
+                .document_type_for_name(document_type_name)
+                .map_err(|e| e.to_string())?;
+            (
+                serde_json::to_value(document).map_err(|e| e.to_string())?,
+                document
+                    .serialize(document_type, platform_version)
+                    .map_err(|e| e.to_string())?,
+                None,
+            )
+        }
+    };
+
+    let export = EntityExport {
+        entity: entity.kind(),
+        id: entity.id(),
+        proven_at,
+        exported_at: current_timestamp(),
+        data: json,
+    };
+    Ok((as_json_string(&export), raw))
+}
+
+async fn export_entity(sdk: &Sdk, task: &ExportTask) -> Result<String, String> {
+    let (json, raw) = serialize_entity(sdk, &task.entity).await?;
+
+    let path = Path::new(&task.path);
+    if let Some(parent) = path
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+    {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create export directory: {}", e))?;
+    }
+    fs::write(path, json).map_err(|e| format!("Failed to write export file: {}", e))?;
+    let mut message = format!("Exported {} into {}", task.entity.kind(), path.display());
+
+    if task.raw {
+        let raw_path = path.with_extension("bin");
+        fs::write(&raw_path, raw).map_err(|e| format!("Failed to write export file: {}", e))?;
+        message.push_str(&format!(", serialized bytes into {}", raw_path.display()));
+    }
+    Ok(message)
+}
+
+pub(crate) async fn run_export_task(sdk: &Sdk, task: ExportTask) -> BackendEvent<'static> {
+    BackendEvent::TaskCompleted {
+        execution_result: export_entity(sdk, &task).await.map(Into::into),
+        task: Task::Export(task),
+    }
+}
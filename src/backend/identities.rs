@@ -1,10 +1,22 @@
 //! Identities backend logic.
 
+mod bulk_top_up;
+mod bundle;
+mod chain_asset_lock;
+pub(crate) mod dpns_check;
+mod key_export;
+pub(crate) mod key_status;
+mod sponsored;
+mod withdrawal_estimate;
+pub(crate) mod withdrawal_whitelist;
+
 use std::{
     collections::{BTreeMap, HashSet},
+    str::FromStr,
     time::Duration,
 };
 
+use chrono_humanize::{Accuracy, HumanTime, Tense};
 use clap::Id;
 use dapi_grpc::{
     core::v0::{
@@ -71,27 +83,77 @@ use simple_signer::signer::SimpleSigner;
 use tokio::sync::{MappedMutexGuard, MutexGuard};
 
 use super::{
-    insight::InsightError, state::IdentityPrivateKeysMap, wallet::WalletError, AppStateUpdate,
-    CompletedTaskPayload, Wallet,
+    insight::InsightError,
+    platform::Platform,
+    progress,
+    reports::current_timestamp,
+    signing_audit::{AuditedSigner, SigningAudit},
+    state::IdentityPrivateKeysMap,
+    wallet::WalletError,
+    AppStateUpdate, CompletedTaskPayload, Wallet,
+};
+use crate::{
+    backend::{
+        documents::bulk::ExportFormat, error::Error, stringify_result_keep_item, AppState,
+        BackendEvent, Task,
+    },
+    config::AssetLockProofKind,
 };
-use crate::backend::{error::Error, stringify_result_keep_item, AppState, BackendEvent, Task};
+
+/// Waiting for an instant send lock longer than that falls back to the chain
+/// lock proof in auto mode.
+const AUTO_INSTANT_LOCK_TIMEOUT: Duration = Duration::from_secs(60);
 
 pub(super) async fn fetch_identity_by_b58_id(
-    sdk: &Sdk,
+    platform: &impl Platform,
     base58_id: &str,
 ) -> Result<(Option<Identity>, String), String> {
     let id_bytes = Identifier::from_string(base58_id, Encoding::Base58)
         .map_err(|_| "Can't parse identifier as base58 string".to_owned())?;
 
-    let fetch_result = Identity::fetch(sdk, id_bytes).await;
+    let fetch_result = platform.fetch_identity(id_bytes).await;
     stringify_result_keep_item(fetch_result)
 }
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum IdentityTask {
     RegisterIdentity(u64),
+    /// Register an identity funded by an unused asset lock of the wallet,
+    /// referred by its transaction id
+    RegisterIdentityWithAssetLock(String),
+    /// Continue an interrupted identity registration, from the asset lock
+    /// proof recorded by the last run if the app didn't exit cleanly
+    ContinueRegistration,
+    /// Fund an identity of public keys given as JSON or by a JSON file path,
+    /// writing its create transition to a file for the keys owner to sign
+    SponsorIdentity {
+        keys: String,
+        amount: u64,
+        path: String,
+    },
     TopUpIdentity(u64),
-    WithdrawFromIdentity(u64),
+    /// Top up known identities given by base58 ids, all of them if none
+    /// given, to a target balance in credits from the wallet
+    TopUpKnownIdentities {
+        target_credits: u64,
+        identity_ids: Vec<String>,
+    },
+    /// Withdraw an amount to a Core address, required to be whitelisted if
+    /// the identity has address-bound transfer keys, otherwise the wallet
+    /// receive address is used by default
+    WithdrawFromIdentity {
+        amount: u64,
+        destination: Option<String>,
+    },
+    /// Estimate the fee and completion time of a withdrawal given the current
+    /// queue, the destination is kept for the withdrawal confirmed afterwards
+    EstimateWithdrawal {
+        amount: u64,
+        destination: Option<String>,
+    },
+    /// Add a transfer key bound to a Core address, whitelisting it for
+    /// withdrawals
+    AddWithdrawalAddress(String),
     Refresh,
     CopyIdentityId,
     AddIdentityKey {
@@ -102,13 +164,55 @@ pub enum IdentityTask {
     ClearLoadedIdentity,
     TransferCredits(String, f64),
     RegisterDPNSName(String),
+    /// Check a DPNS label against the contract rules and whether it's taken
+    /// or contested
+    CheckDPNSName(String),
+    /// Check with proof whether a key (id or hex data) of an identity is active
+    CheckKeyStatus {
+        identity_id: String,
+        key: String,
+    },
+    /// Export the loaded identity with its private keys into an encrypted
+    /// bundle file
+    ExportBundle {
+        path: String,
+        passphrase: String,
+    },
+    /// Load an identity from an encrypted bundle file
+    ImportBundle {
+        path: String,
+        passphrase: String,
+    },
+    /// Show the latest signing operations of the audit log
+    ShowSigningAudit,
+    /// Export the whole signing audit log into a CSV file
+    ExportSigningAudit,
+    /// Export public keys of the loaded identity into a file
+    ExportKeys(ExportFormat),
+    /// Give a local petname to an identity, an empty one removes it
+    SetPetname {
+        identity_id: String,
+        petname: String,
+    },
+    ShowPetnames,
 }
 
 impl AppState {
     pub async fn run_identity_task(&self, sdk: &Sdk, task: IdentityTask) -> BackendEvent {
         match task {
-            IdentityTask::RegisterIdentity(amount) => {
-                let result = self.register_new_identity(sdk, amount).await;
+            IdentityTask::RegisterIdentity(_)
+            | IdentityTask::RegisterIdentityWithAssetLock(_)
+            | IdentityTask::ContinueRegistration => {
+                let result = match &task {
+                    IdentityTask::RegisterIdentityWithAssetLock(txid) => {
+                        self.register_identity_with_asset_lock(sdk, txid).await
+                    }
+                    IdentityTask::ContinueRegistration => self.continue_registration(sdk).await,
+                    IdentityTask::RegisterIdentity(amount) => {
+                        self.register_new_identity(sdk, *amount).await
+                    }
+                    _ => unreachable!("matched above"),
+                };
                 let execution_result = result
                     .as_ref()
                     .map(|_| "Executed successfully".into())
@@ -172,8 +276,13 @@ impl AppState {
                     },
                 }
             }
-            IdentityTask::WithdrawFromIdentity(amount) => {
-                let result = self.withdraw_from_identity(sdk, amount).await;
+            IdentityTask::WithdrawFromIdentity {
+                amount,
+                ref destination,
+            } => {
+                let result = self
+                    .withdraw_from_identity(sdk, amount, destination.as_deref())
+                    .await;
                 let execution_result = result
                     .as_ref()
                     .map(|_| "Successful withdrawal".into())
@@ -190,6 +299,149 @@ impl AppState {
                     },
                 }
             }
+            IdentityTask::CheckKeyStatus {
+                ref identity_id,
+                ref key,
+            } => {
+                let execution_result = key_status::check_key_status(sdk, identity_id, key)
+                    .await
+                    .map(CompletedTaskPayload::String);
+                BackendEvent::TaskCompleted {
+                    task: Task::Identity(task),
+                    execution_result,
+                }
+            }
+            IdentityTask::ShowSigningAudit => BackendEvent::TaskCompleted {
+                task: Task::Identity(task),
+                execution_result: self
+                    .signing_audit
+                    .display()
+                    .map(CompletedTaskPayload::String),
+            },
+            IdentityTask::ExportSigningAudit => BackendEvent::TaskCompleted {
+                task: Task::Identity(task),
+                execution_result: self
+                    .signing_audit
+                    .export_csv()
+                    .map(|path| format!("Signing audit log exported to {}", path.display()).into()),
+            },
+            IdentityTask::ExportKeys(format) => {
+                let execution_result = match self.loaded_identity.lock().await.as_ref() {
+                    Some(identity) => key_export::export_keys(identity, format).map(|path| {
+                        format!(
+                            "Exported {} keys into {}",
+                            identity.public_keys().len(),
+                            path.display()
+                        )
+                        .into()
+                    }),
+                    None => Err("No identity loaded".to_owned()),
+                };
+                BackendEvent::TaskCompleted {
+                    task: Task::Identity(task),
+                    execution_result,
+                }
+            }
+            IdentityTask::SetPetname {
+                ref identity_id,
+                ref petname,
+            } => BackendEvent::TaskCompleted {
+                execution_result: self
+                    .petnames
+                    .lock()
+                    .await
+                    .set(identity_id, petname)
+                    .map(CompletedTaskPayload::String),
+                task: Task::Identity(task),
+            },
+            IdentityTask::ShowPetnames => BackendEvent::TaskCompleted {
+                task: Task::Identity(task),
+                execution_result: Ok(self.petnames.lock().await.display().into()),
+            },
+            IdentityTask::ExportBundle {
+                ref path,
+                ref passphrase,
+            } => {
+                let execution_result = match self.loaded_identity.lock().await.as_ref() {
+                    Some(identity) => {
+                        let private_keys = self
+                            .identity_private_keys
+                            .lock()
+                            .await
+                            .iter()
+                            .filter(|((identity_id, _), _)| *identity_id == identity.id())
+                            .map(|((_, key_id), key)| (*key_id, key.clone()))
+                            .collect();
+                        bundle::export_bundle(
+                            path,
+                            passphrase,
+                            &self.profile,
+                            identity,
+                            private_keys,
+                        )
+                        .map(CompletedTaskPayload::String)
+                    }
+                    None => Err("No identity loaded".to_owned()),
+                };
+                BackendEvent::TaskCompleted {
+                    task: Task::Identity(task),
+                    execution_result,
+                }
+            }
+            IdentityTask::SponsorIdentity {
+                ref keys,
+                amount,
+                ref path,
+            } => BackendEvent::TaskCompleted {
+                execution_result: self
+                    .sponsor_identity(sdk, keys, amount, path)
+                    .await
+                    .map(CompletedTaskPayload::String),
+                task: Task::Identity(task),
+            },
+            IdentityTask::ImportBundle {
+                ref path,
+                ref passphrase,
+            } => {
+                let mut loaded_identity = self.loaded_identity.lock().await;
+                if loaded_identity.is_some() {
+                    return BackendEvent::TaskCompleted {
+                        task: Task::Identity(task),
+                        execution_result: Err(
+                            "An identity is already loaded, clear it before importing".to_owned(),
+                        ),
+                    };
+                }
+                match bundle::import_bundle(path, passphrase, &self.profile) {
+                    Ok(bundle) => {
+                        let identity_id = bundle.identity.id();
+                        let mut identity_private_keys = self.identity_private_keys.lock().await;
+                        for (key_id, key) in bundle.private_keys {
+                            identity_private_keys.insert((identity_id, key_id), key);
+                        }
+                        let exported_ago = current_timestamp().saturating_sub(bundle.exported_at);
+                        let message = format!(
+                            "Imported identity {} exported {}",
+                            identity_id.to_string(Encoding::Base58),
+                            HumanTime::from(chrono::Duration::seconds(exported_ago as i64))
+                                .to_text_en(Accuracy::Rough, Tense::Past)
+                        );
+                        loaded_identity.replace(bundle.identity);
+                        BackendEvent::TaskCompletedStateChange {
+                            task: Task::Identity(task),
+                            execution_result: Ok(message.into()),
+                            app_state_update: AppStateUpdate::LoadedIdentity(MutexGuard::map(
+                                loaded_identity,
+                                |identity| identity.as_mut().expect("assigned above"),
+                            )),
+                        }
+                    }
+                    Err(e) => BackendEvent::TaskCompleted {
+                        task: Task::Identity(task),
+                        execution_result: Err(e),
+                    },
+                }
+            }
             IdentityTask::CopyIdentityId => {
                 if let Some(loaded_identity) = self.loaded_identity.lock().await.as_ref() {
                     let id = loaded_identity.id();
@@ -205,6 +457,73 @@ impl AppState {
                     }
                 }
             }
+            IdentityTask::TopUpKnownIdentities {
+                target_credits,
+                ref identity_ids,
+            } => BackendEvent::TaskCompleted {
+                execution_result: self
+                    .top_up_known_identities(sdk, target_credits, identity_ids)
+                    .await
+                    .map(Into::into),
+                task: Task::Identity(task),
+            },
+            IdentityTask::EstimateWithdrawal { amount, .. } => BackendEvent::TaskCompleted {
+                execution_result: withdrawal_estimate::estimate_withdrawal(sdk, amount)
+                    .await
+                    .map(Into::into),
+                task: Task::Identity(task),
+            },
+            IdentityTask::AddWithdrawalAddress(ref address) => {
+                let (key_type, key_data) =
+                    match withdrawal_whitelist::address_key_data(address, self.core_network) {
+                        Ok(key) => key,
+                        Err(e) => {
+                            return BackendEvent::TaskCompleted {
+                                task: Task::Identity(task),
+                                execution_result: Err(e),
+                            }
+                        }
+                    };
+
+                let loaded_identity_lock = self.loaded_identity.lock().await;
+                let loaded_identity = if loaded_identity_lock.is_some() {
+                    MutexGuard::map(loaded_identity_lock, |identity| {
+                        identity.as_mut().expect("checked above")
+                    })
+                } else {
+                    return BackendEvent::TaskCompleted {
+                        task: Task::Identity(task),
+                        execution_result: Err("No identity loaded".to_owned()),
+                    };
+                };
+
+                let identity_private_keys_lock = self.identity_private_keys.lock().await;
+                match add_identity_key(
+                    sdk,
+                    loaded_identity,
+                    identity_private_keys_lock,
+                    &self.signing_audit,
+                    key_type,
+                    KeySecurityLevel::CRITICAL,
+                    KeyPurpose::TRANSFER,
+                    Some(key_data),
+                )
+                .await
+                {
+                    Ok(app_state_update) => BackendEvent::TaskCompletedStateChange {
+                        execution_result: Ok(CompletedTaskPayload::String(format!(
+                            "Whitelisted withdrawal address {}",
+                            address.trim()
+                        ))),
+                        task: Task::Identity(task),
+                        app_state_update,
+                    },
+                    Err(e) => BackendEvent::TaskCompleted {
+                        task: Task::Identity(task),
+                        execution_result: Err(e),
+                    },
+                }
+            }
             IdentityTask::AddIdentityKey {
                 key_type,
                 security_level,
@@ -227,9 +546,11 @@ impl AppState {
                     sdk,
                     loaded_identity,
                     identity_private_keys_lock,
+                    &self.signing_audit,
                     key_type,
                     security_level,
                     purpose,
+                    None,
                 )
                 .await
                 {
@@ -283,21 +604,24 @@ impl AppState {
                         )
                         .expect("Expected to get a signing key");
 
-                    let loaded_identity_private_keys = self.identity_private_keys.lock().await;
-                    let Some(private_key) = loaded_identity_private_keys
-                        .get(&(identity.id(), identity_public_key.id()))
-                    else {
+                    if self.remote_signer.is_none()
+                        && !self
+                            .identity_private_keys
+                            .lock()
+                            .await
+                            .contains_key(&(identity.id(), identity_public_key.id()))
+                    {
                         return BackendEvent::TaskCompleted {
                             task: Task::Identity(task),
                             execution_result: Ok(CompletedTaskPayload::String(
                                 "No private key for transfer".to_string(),
                             )),
                         };
-                    };
-
-                    let mut signer = SimpleSigner::default();
+                    }
 
-                    signer.add_key(identity_public_key.clone(), private_key.to_vec());
+                    let signer = self
+                        .identity_signer(identity, "Identity credit transfer")
+                        .await;
 
                     if let Err(e) = transition.sign_external(
                         identity_public_key,
@@ -310,14 +634,59 @@ impl AppState {
                             execution_result: Err(e.to_string()),
                         }
                     } else {
-                        match transition.broadcast_and_wait(sdk, None).await {
-                            Ok(_) => BackendEvent::TaskCompletedStateChange {
-                                task: Task::Identity(task),
-                                execution_result: Ok(CompletedTaskPayload::String(
-                                    "Credit transfer successful.".to_owned(),
-                                )),
-                                app_state_update: AppStateUpdate::IdentityCreditsTransferred,
-                            },
+                        let expected_balance = identity
+                            .balance()
+                            .saturating_sub((amount * 100_000_000_000.0) as u64);
+                        match self
+                            .broadcast_with_receipt(sdk, &transition, identity, expected_balance)
+                            .await
+                        {
+                            Ok((proof_result, receipt_path)) => {
+                                let mut message = "Credit transfer successful.".to_owned();
+                                let mut sender_balance = None;
+                                if let StateTransitionProofResult::VerifiedBalanceTransfer(
+                                    sender,
+                                    recipient,
+                                ) = &proof_result
+                                {
+                                    sender_balance = sender.balance;
+                                    for (role, partial_identity) in
+                                        [("Sender", sender), ("Recipient", recipient)]
+                                    {
+                                        if let Some(balance) = partial_identity.balance {
+                                            message.push_str(&format!(
+                                                "\n{} {} balance: {} Dash",
+                                                role,
+                                                partial_identity.id.to_string(Encoding::Base58),
+                                                balance as f64 / 100_000_000_000.0
+                                            ));
+                                        }
+                                    }
+                                }
+                                if let Some(path) = receipt_path {
+                                    message.push_str(&format!(
+                                        "\nReceipt saved into {}",
+                                        path.display()
+                                    ));
+                                }
+                                // The proof has the new balance of the loaded identity
+                                let app_state_update = match sender_balance {
+                                    Some(balance) => AppStateUpdate::LoadedIdentity(
+                                        MutexGuard::map(loaded_identity, |identity| {
+                                            let identity =
+                                                identity.as_mut().expect("checked above");
+                                            identity.set_balance(balance);
+                                            identity
+                                        }),
+                                    ),
+                                    None => AppStateUpdate::IdentityCreditsTransferred,
+                                };
+                                BackendEvent::TaskCompletedStateChange {
+                                    task: Task::Identity(task),
+                                    execution_result: Ok(CompletedTaskPayload::String(message)),
+                                    app_state_update,
+                                }
+                            }
                             Err(e) => {
                                 tracing::error!("Error executing credit transfer: {e}");
                                 BackendEvent::TaskCompleted {
@@ -336,6 +705,15 @@ impl AppState {
                     }
                 }
             }
+            IdentityTask::CheckDPNSName(ref label) => {
+                let execution_result = dpns_check::check_dpns_name(sdk, label)
+                    .await
+                    .map(Into::into);
+                BackendEvent::TaskCompleted {
+                    task: Task::Identity(task),
+                    execution_result,
+                }
+            }
             IdentityTask::RegisterDPNSName(ref name) => {
                 let loaded_identity_lock = self.loaded_identity.lock().await;
                 let identity = match loaded_identity_lock.as_ref() {
@@ -377,6 +755,11 @@ impl AppState {
         identifier: &Identifier, // once contract names are enabled, we can use this field
         name: &str,
     ) -> Result<(), Error> {
+        // A preorder of a name that can't be registered would only waste credits
+        dpns_check::check_dpns_name(sdk, name)
+            .await
+            .map_err(Error::DPNSError)?;
+
         let mut rng = StdRng::from_entropy();
         let platform_version = PlatformVersion::latest();
 
@@ -451,25 +834,7 @@ impl AppState {
             Err(e) => return Err(Error::SdkError(e)),
         };
 
-        // TODO this is used in strategy tests too. It should be a function.
-        // Get signer from loaded_identity
-        // Convert loaded_identity to SimpleSigner
-        let identity_private_keys_lock = self.identity_private_keys.lock().await;
-        let signer = {
-            let mut new_signer = SimpleSigner::default();
-            let Identity::V0(identity_v0) = &*identity;
-            for (key_id, public_key) in &identity_v0.public_keys {
-                let identity_key_tuple = (identity_v0.id, *key_id);
-                if let Some(private_key_bytes) = identity_private_keys_lock.get(&identity_key_tuple)
-                {
-                    new_signer
-                        .private_keys
-                        .insert(public_key.clone(), private_key_bytes.clone());
-                }
-            }
-            new_signer
-        };
-        drop(identity_private_keys_lock);
+        let signer = self.identity_signer(identity, "Documents batch").await;
 
         let preorder_transition =
             DocumentsBatchTransition::new_document_creation_transition_from_document(
@@ -569,6 +934,14 @@ impl AppState {
         sdk: &Sdk,
         amount: u64,
     ) -> Result<MappedMutexGuard<'s, Identity>, Error> {
+        self.reorg_watch
+            .lock()
+            .await
+            .ensure_stable()
+            .map_err(Error::IdentityRegistrationError)?;
+
+        let fee_rate = self.fee_settings.lock().await.rate();
+
         // First we need to make the transaction from the wallet
         // We start by getting a lock on the wallet
 
@@ -608,7 +981,11 @@ impl AppState {
             )
         } else {
             let (asset_lock_transaction, asset_lock_proof_private_key) =
-                wallet.asset_lock_transaction(None, amount)?;
+                wallet.asset_lock_transaction(None, amount, fee_rate)?;
+            self.asset_locks
+                .lock()
+                .await
+                .record(&asset_lock_transaction, &asset_lock_proof_private_key);
 
             identity_asset_lock_private_key_in_creation.replace((
                 asset_lock_transaction.clone(),
@@ -632,11 +1009,16 @@ impl AppState {
                 sdk,
                 &asset_lock_transaction,
                 &wallet.receive_address(),
+                self.asset_lock_proof,
             )
             .await
             .map_err(|e| {
                 Error::SdkExplainedError("broadcasting transaction failed".to_string(), e)
             })?;
+            self.asset_locks
+                .lock()
+                .await
+                .record_proof(&asset_lock_transaction.txid(), &asset_lock);
 
             identity_asset_lock_private_key_in_creation.replace((
                 asset_lock_transaction.clone(),
@@ -704,8 +1086,13 @@ impl AppState {
             };
 
         let mut signer = SimpleSigner::default();
-
         signer.add_keys(keys);
+        let signer = AuditedSigner::new(
+            signer,
+            &self.signing_audit,
+            identity.id(),
+            "Identity create",
+        );
 
         let updated_identity = identity
             .put_to_platform_and_wait_for_response(
@@ -739,6 +1126,130 @@ impl AppState {
 
         identity_private_keys.extend(keys);
 
+        self.asset_locks
+            .lock()
+            .await
+            .mark_used(&asset_lock_transaction.txid());
+
+        Ok(identity_result)
+    }
+
+    /// Registers an identity with an asset lock made earlier instead of a new
+    /// one, the lock goes through the usual registration steps as if it was
+    /// made by an interrupted registration.
+    pub(crate) async fn register_identity_with_asset_lock<'s>(
+        &'s self,
+        sdk: &Sdk,
+        txid: &str,
+    ) -> Result<MappedMutexGuard<'s, Identity>, Error> {
+        let (asset_lock_transaction, asset_lock_proof_private_key, amount) = self
+            .asset_locks
+            .lock()
+            .await
+            .get(txid)
+            .map_err(Error::IdentityRegistrationError)?;
+        let asset_lock_proof = self.asset_locks.lock().await.proof(txid);
+
+        {
+            let mut identity_asset_lock_private_key_in_creation = self
+                .identity_asset_lock_private_key_in_creation
+                .lock()
+                .await;
+            if identity_asset_lock_private_key_in_creation.is_some() {
+                return Err(Error::IdentityRegistrationError(
+                    "Another identity registration is in progress, continue it first".to_string(),
+                ));
+            }
+            identity_asset_lock_private_key_in_creation.replace((
+                asset_lock_transaction,
+                asset_lock_proof_private_key,
+                asset_lock_proof,
+                None,
+            ));
+        }
+
+        self.register_new_identity(sdk, amount).await
+    }
+
+    /// Continues the registration in progress, or the one left behind with a
+    /// recorded asset lock proof. The identity create transition may have gone
+    /// through before the interruption, so an identity already registered with
+    /// the asset lock is loaded instead of being created again.
+    pub(crate) async fn continue_registration<'s>(
+        &'s self,
+        sdk: &Sdk,
+    ) -> Result<MappedMutexGuard<'s, Identity>, Error> {
+        let mut identity_asset_lock_private_key_in_creation = self
+            .identity_asset_lock_private_key_in_creation
+            .lock()
+            .await;
+
+        if identity_asset_lock_private_key_in_creation.is_none() {
+            let (asset_lock_transaction, asset_lock_proof_private_key, asset_lock_proof) = self
+                .asset_locks
+                .lock()
+                .await
+                .pending_registration()
+                .ok_or_else(|| {
+                    Error::IdentityRegistrationError(
+                        "No identity registration to continue".to_string(),
+                    )
+                })?;
+            identity_asset_lock_private_key_in_creation.replace((
+                asset_lock_transaction,
+                asset_lock_proof_private_key,
+                Some(asset_lock_proof),
+                None,
+            ));
+        }
+
+        let (asset_lock_transaction, _, maybe_asset_lock_proof, maybe_identity_info) =
+            identity_asset_lock_private_key_in_creation
+                .as_ref()
+                .expect("set above");
+        let txid = asset_lock_transaction.txid();
+
+        let registered_identity = match maybe_asset_lock_proof {
+            Some(asset_lock_proof) => {
+                let identity_id = asset_lock_proof
+                    .create_identifier()
+                    .expect("expected to create an identifier");
+                Identity::fetch(sdk, identity_id).await?
+            }
+            None => None,
+        };
+
+        let Some(registered_identity) = registered_identity else {
+            drop(identity_asset_lock_private_key_in_creation);
+            // The amount only matters if there is no asset lock yet
+            return self.register_new_identity(sdk, 0).await;
+        };
+
+        let keys = maybe_identity_info
+            .as_ref()
+            .map(|(_, keys)| keys.clone())
+            .unwrap_or_default();
+        identity_asset_lock_private_key_in_creation.take();
+        self.asset_locks.lock().await.mark_used(&txid);
+
+        if keys.is_empty() {
+            return Err(Error::IdentityRegistrationError(format!(
+                "Identity already registered with the asset lock: {}, its keys were not saved",
+                registered_identity.id().to_string(Encoding::Base58)
+            )));
+        }
+
+        let identity_id = registered_identity.id();
+        let mut loaded_identity = self.loaded_identity.lock().await;
+        loaded_identity.replace(registered_identity);
+        let identity_result =
+            MutexGuard::map(loaded_identity, |x| x.as_mut().expect("assigned above"));
+
+        self.identity_private_keys.lock().await.extend(
+            keys.into_iter()
+                .map(|(key, private_key)| ((identity_id, key.id()), private_key)),
+        );
+
         Ok(identity_result)
     }
 
@@ -747,6 +1258,14 @@ impl AppState {
         sdk: &Sdk,
         amount: u64,
     ) -> Result<MappedMutexGuard<'s, Identity>, Error> {
+        self.reorg_watch
+            .lock()
+            .await
+            .ensure_stable()
+            .map_err(Error::IdentityTopUpError)?;
+
+        let fee_rate = self.fee_settings.lock().await.rate();
+
         // First we need to make the transaction from the wallet
         // We start by getting a lock on the wallet
 
@@ -784,7 +1303,11 @@ impl AppState {
                 )
             } else {
                 let (asset_lock_transaction, asset_lock_proof_private_key) =
-                    wallet.asset_lock_transaction(None, amount)?;
+                    wallet.asset_lock_transaction(None, amount, fee_rate)?;
+                self.asset_locks
+                    .lock()
+                    .await
+                    .record(&asset_lock_transaction, &asset_lock_proof_private_key);
 
                 identity_asset_lock_private_key_in_top_up.replace((
                     asset_lock_transaction.clone(),
@@ -802,6 +1325,7 @@ impl AppState {
                 sdk,
                 &asset_lock_transaction,
                 &wallet.receive_address(),
+                self.asset_lock_proof,
             )
             .await
             .map_err(|e| {
@@ -837,8 +1361,12 @@ impl AppState {
                 if error_string.starts_with("Transport(Status { code: AlreadyExists, message: \"state transition already in chain\"") {
                     // This state transition already existed
                     tracing::info!("we are starting over as the previous top up already existed");
+                    let mut asset_locks = self.asset_locks.lock().await;
+                    asset_locks.mark_used(&asset_lock_transaction.txid());
                     let (new_asset_lock_transaction, new_asset_lock_proof_private_key) =
-                        wallet.asset_lock_transaction(None, amount)?;
+                        wallet.asset_lock_transaction(None, amount, fee_rate)?;
+                    asset_locks.record(&new_asset_lock_transaction, &new_asset_lock_proof_private_key);
+                    drop(asset_locks);
 
                     identity_asset_lock_private_key_in_top_up.replace((
                         new_asset_lock_transaction.clone(),
@@ -850,6 +1378,7 @@ impl AppState {
                         sdk,
                         &new_asset_lock_transaction,
                         &wallet.receive_address(),
+                        self.asset_lock_proof,
                     )
                         .await
                         .map_err(|e| {
@@ -872,7 +1401,10 @@ impl AppState {
             Err(e) => return Err(e.into()),
         }
 
-        identity_asset_lock_private_key_in_top_up.take(); // clear the top up
+        // clear the top up
+        if let Some((transaction, ..)) = identity_asset_lock_private_key_in_top_up.take() {
+            self.asset_locks.lock().await.mark_used(&transaction.txid());
+        }
 
         Ok(MutexGuard::map(identity_lock, |identity| {
             identity.as_mut().expect("checked above")
@@ -883,6 +1415,7 @@ impl AppState {
         &'s self,
         sdk: &Sdk,
         amount: u64,
+        destination: Option<&str>,
     ) -> Result<MappedMutexGuard<'s, Identity>, Error> {
         // First we need to make the transaction from the wallet
         // We start by getting a lock on the wallet
@@ -894,35 +1427,44 @@ impl AppState {
             ));
         };
 
-        let new_receive_address = wallet.receive_address();
-
         let mut identity_lock = self.loaded_identity.lock().await;
         let Some(identity) = identity_lock.as_mut() else {
             return Err(Error::IdentityTopUpError("No identity loaded".to_string()));
         };
 
-        let identity_public_key = identity
-            .get_first_public_key_matching(
-                KeyPurpose::TRANSFER,
-                KeySecurityLevel::full_range().into(),
-                KeyType::all_key_types().into(),
-            )
-            .ok_or(Error::IdentityWithdrawalError(
-                "no withdrawal public key".to_string(),
-            ))?;
-
-        let loaded_identity_private_keys = self.identity_private_keys.lock().await;
-        let Some(private_key) =
-            loaded_identity_private_keys.get(&(identity.id(), identity_public_key.id()))
-        else {
-            return Err(Error::IdentityTopUpError(
-                "No private key for withdrawal".to_string(),
-            ));
+        let whitelist = withdrawal_whitelist::withdrawal_whitelist(identity, self.core_network);
+        let new_receive_address = match destination {
+            Some(destination) => Address::from_str(destination.trim()).map_err(|e| {
+                Error::IdentityWithdrawalError(format!("invalid destination address: {e}"))
+            })?,
+            None if whitelist.is_empty() => wallet.receive_address(),
+            None => whitelist[0].clone(),
         };
+        if !whitelist.is_empty() && !whitelist.contains(&new_receive_address) {
+            return Err(Error::IdentityWithdrawalError(format!(
+                "{} is not a whitelisted withdrawal address",
+                new_receive_address
+            )));
+        }
 
-        let mut signer = SimpleSigner::default();
+        // Address-bound transfer keys have no private key and can't sign
+        let identity_private_keys = self.identity_private_keys.lock().await;
+        let can_sign = identity.public_keys().values().any(|key| {
+            key.purpose() == KeyPurpose::TRANSFER
+                && key.disabled_at().is_none()
+                && (self.remote_signer.is_some()
+                    || identity_private_keys.contains_key(&(identity.id(), key.id())))
+        });
+        drop(identity_private_keys);
+        if !can_sign {
+            return Err(Error::IdentityWithdrawalError(
+                "no withdrawal key to sign with".to_string(),
+            ));
+        }
 
-        signer.add_key(identity_public_key.clone(), private_key.to_vec());
+        let signer = self
+            .identity_signer(identity, "Identity credit withdrawal")
+            .await;
 
         //// Platform steps
 
@@ -941,6 +1483,7 @@ impl AppState {
         sdk: &Sdk,
         asset_lock_transaction: &Transaction,
         address: &Address,
+        proof_kind: AssetLockProofKind,
     ) -> Result<AssetLockProof, dash_sdk::Error> {
         let _span = tracing::debug_span!(
             "broadcast_and_retrieve_asset_lock",
@@ -1013,22 +1556,58 @@ impl AppState {
 
         tracing::debug!("waiting for asset lock proof");
 
-        sdk.wait_for_asset_lock_proof_for_transaction(
-            asset_lock_stream,
-            asset_lock_transaction,
-            Some(Duration::from_secs(4 * 60)),
-        )
-        .await
+        let result = match proof_kind {
+            AssetLockProofKind::Chain => {
+                chain_asset_lock::wait_for_chain_asset_lock_proof(sdk, asset_lock_transaction).await
+            }
+            AssetLockProofKind::Instant => {
+                progress::report("Asset lock: waiting for instant send lock".to_owned());
+                sdk.wait_for_asset_lock_proof_for_transaction(
+                    asset_lock_stream,
+                    asset_lock_transaction,
+                    Some(Duration::from_secs(4 * 60)),
+                )
+                .await
+            }
+            AssetLockProofKind::Auto => {
+                progress::report("Asset lock: waiting for instant send lock".to_owned());
+                match sdk
+                    .wait_for_asset_lock_proof_for_transaction(
+                        asset_lock_stream,
+                        asset_lock_transaction,
+                        Some(AUTO_INSTANT_LOCK_TIMEOUT),
+                    )
+                    .await
+                {
+                    Ok(asset_lock_proof) => Ok(asset_lock_proof),
+                    Err(e) => {
+                        tracing::warn!(
+                            "no instant send lock for the asset lock ({e}), waiting for chain lock"
+                        );
+                        chain_asset_lock::wait_for_chain_asset_lock_proof(
+                            sdk,
+                            asset_lock_transaction,
+                        )
+                        .await
+                    }
+                }
+            }
+        };
+        progress::clear();
+        result
     }
 
     pub async fn retrieve_asset_lock_proof(
         sdk: &Sdk,
         wallet: &mut Wallet,
         amount: u64,
+        fee_rate: u64,
+        proof_kind: AssetLockProofKind,
     ) -> Result<(AssetLockProof, PrivateKey), Error> {
         // Create the wallet registration transaction
-        let (asset_lock_transaction, asset_lock_proof_private_key) =
-            wallet.asset_lock_transaction(None, amount).map_err(|e| {
+        let (asset_lock_transaction, asset_lock_proof_private_key) = wallet
+            .asset_lock_transaction(None, amount, fee_rate)
+            .map_err(|e| {
                 Error::WalletError(WalletError::Insight(InsightError(format!(
                     "Wallet transaction error: {}",
                     e
@@ -1040,6 +1619,7 @@ impl AppState {
             sdk,
             &asset_lock_transaction,
             &wallet.receive_address(),
+            proof_kind,
         )
         .await
         {
@@ -1053,16 +1633,23 @@ async fn add_identity_key<'a>(
     sdk: &Sdk,
     mut loaded_identity: MappedMutexGuard<'a, Identity>,
     mut identity_private_keys: MutexGuard<'a, IdentityPrivateKeysMap>,
+    signing_audit: &SigningAudit,
     key_type: KeyType,
     security_level: KeySecurityLevel,
     purpose: KeyPurpose,
+    public_key_data: Option<Vec<u8>>,
 ) -> Result<AppStateUpdate<'a>, String> {
     let mut rng = StdRng::from_entropy();
     let platform_version = sdk.version();
 
-    let (public_key, private_key) = key_type
-        .random_public_and_private_key_data(&mut rng, &platform_version)
-        .map_err(|e| format!("Cannot generate key pair: {e}"))?;
+    // Keys bound to a Core address come without a private key
+    let (public_key, private_key) = match public_key_data {
+        Some(data) => (data, None),
+        None => key_type
+            .random_public_and_private_key_data(&mut rng, &platform_version)
+            .map(|(public_key, private_key)| (public_key, Some(private_key)))
+            .map_err(|e| format!("Cannot generate key pair: {e}"))?,
+    };
     let identity_public_key: IdentityPublicKey = IdentityPublicKeyV0 {
         id: loaded_identity.get_public_key_max_id() + 1,
         purpose,
@@ -1086,7 +1673,15 @@ async fn add_identity_key<'a>(
 
     let mut signer = SimpleSigner::default();
     signer.add_key(master_public_key.clone(), master_private_key.to_vec());
-    signer.add_key(identity_public_key.clone(), private_key.clone());
+    if let Some(private_key) = &private_key {
+        signer.add_key(identity_public_key.clone(), private_key.clone());
+    }
+    let signer = AuditedSigner::new(
+        signer,
+        signing_audit,
+        loaded_identity.id(),
+        "Identity update",
+    );
 
     let mut identity_updated = loaded_identity.clone();
     identity_updated.bump_revision();
@@ -1126,10 +1721,12 @@ async fn add_identity_key<'a>(
     loaded_identity.set_revision(revision);
     loaded_identity.set_public_keys(loaded_public_keys);
 
-    identity_private_keys.insert(
-        (loaded_identity.id(), identity_public_key.id()),
-        private_key,
-    );
+    if let Some(private_key) = private_key {
+        identity_private_keys.insert(
+            (loaded_identity.id(), identity_public_key.id()),
+            private_key,
+        );
+    }
 
     Ok(AppStateUpdate::LoadedIdentity(loaded_identity))
 }
@@ -0,0 +1,127 @@
+//! Top ups of many identities in one go.
+//!
+//! Every selected known identity is brought to the same target balance from
+//! the loaded wallet, one identity after another. A failed top up is reported
+//! in the summary and doesn't stop the remaining ones.
+
+use dash_sdk::{platform::Fetch, Sdk};
+use dpp::{
+    identity::accessors::{IdentityGettersV0, IdentitySettersV0},
+    platform_value::{string_encoding::Encoding, Identifier},
+    prelude::Identity,
+};
+
+use crate::backend::{progress, strategies::forecast::CREDITS_PER_DUFF, AppState};
+
+const CREDITS_PER_DASH: f64 = 100_000_000_000.0;
+
+fn credits_dash_str(credits: u64) -> String {
+    format!("{:.4} DASH", credits as f64 / CREDITS_PER_DASH)
+}
+
+enum TopUpOutcome {
+    AlreadyFunded(u64),
+    ToppedUp { duffs: u64, balance: u64 },
+}
+
+impl AppState {
+    /// Tops up the known identities given by base58 ids, or all of them if
+    /// none given, to the target balance in credits. Returns a summary with
+    /// the outcome per identity.
+    pub(super) async fn top_up_known_identities(
+        &self,
+        sdk: &Sdk,
+        target_credits: u64,
+        identity_ids: &[String],
+    ) -> Result<String, String> {
+        // Ids are all checked before spending anything
+        let identity_ids: Vec<Identifier> = if identity_ids.is_empty() {
+            self.known_identities.lock().await.keys().copied().collect()
+        } else {
+            identity_ids
+                .iter()
+                .map(|id| {
+                    Identifier::from_string(id, Encoding::Base58)
+                        .map_err(|_| format!("Invalid identity id: {}", id))
+                })
+                .collect::<Result<_, _>>()?
+        };
+        if identity_ids.is_empty() {
+            return Err("No known identities to top up".to_owned());
+        }
+
+        let total = identity_ids.len();
+        let mut lines = Vec::with_capacity(total);
+        let mut failed = 0;
+        let mut spent_duffs = 0;
+
+        for (i, identity_id) in identity_ids.into_iter().enumerate() {
+            let id_b58 = identity_id.to_string(Encoding::Base58);
+            progress::report(format!(
+                "Topping up identity {}/{}: {}",
+                i + 1,
+                total,
+                id_b58
+            ));
+
+            match self
+                .top_up_to_target(sdk, identity_id, target_credits)
+                .await
+            {
+                Ok(TopUpOutcome::AlreadyFunded(balance)) => lines.push(format!(
+                    "{}: already at {}",
+                    id_b58,
+                    credits_dash_str(balance)
+                )),
+                Ok(TopUpOutcome::ToppedUp { duffs, balance }) => {
+                    spent_duffs += duffs;
+                    lines.push(format!(
+                        "{}: topped up with {} duffs, balance is {}",
+                        id_b58,
+                        duffs,
+                        credits_dash_str(balance)
+                    ));
+                }
+                Err(e) => {
+                    failed += 1;
+                    lines.push(format!("{}: failed: {}", id_b58, e));
+                }
+            }
+        }
+        progress::clear();
+
+        Ok(format!(
+            "Topped up {} of {} identities to {}, spent {} duffs\n\n{}",
+            total - failed,
+            total,
+            credits_dash_str(target_credits),
+            spent_duffs,
+            lines.join("\n")
+        ))
+    }
+
+    async fn top_up_to_target(
+        &self,
+        sdk: &Sdk,
+        identity_id: Identifier,
+        target_credits: u64,
+    ) -> Result<TopUpOutcome, String> {
+        let identity = Identity::fetch(sdk, identity_id)
+            .await
+            .map_err(|e| format!("unable to fetch: {}", e))?
+            .ok_or_else(|| "not found".to_owned())?;
+
+        if identity.balance() >= target_credits {
+            return Ok(TopUpOutcome::AlreadyFunded(identity.balance()));
+        }
+        let duffs = (target_credits - identity.balance()).div_ceil(CREDITS_PER_DUFF);
+
+        let balance = self.top_up_from_wallet(sdk, &identity, duffs).await?;
+
+        if let Some(known_identity) = self.known_identities.lock().await.get_mut(&identity_id) {
+            known_identity.set_balance(balance);
+        }
+
+        Ok(TopUpOutcome::ToppedUp { duffs, balance })
+    }
+}
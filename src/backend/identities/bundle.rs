@@ -0,0 +1,89 @@
+//! Encrypted identity bundles.
+//!
+//! A bundle holds an identity with its private keys and a bit of metadata, so
+//! a test identity can be moved to another machine without sharing the whole
+//! app state. The content is encrypted with ChaCha20-Poly1305 using a key
+//! derived from a passphrase with Argon2.
+
+use std::fs;
+
+use bincode::{Decode, Encode};
+use dpp::{
+    identity::{accessors::IdentityGettersV0, KeyID},
+    platform_value::string_encoding::Encoding,
+    prelude::Identity,
+};
+
+use crate::backend::{encryption, reports::current_timestamp};
+
+const MAGIC: &[u8; 8] = b"PEXIDB01";
+
+#[derive(Debug, Encode, Decode)]
+pub(super) struct IdentityBundle {
+    /// Profile (network) the identity was exported from, keys shouldn't be
+    /// imported on a different one
+    pub profile: String,
+    pub exported_at: u64,
+    pub identity: Identity,
+    pub private_keys: Vec<(KeyID, Vec<u8>)>,
+}
+
+fn bincode_config() -> impl bincode::config::Config {
+    bincode::config::standard()
+        .with_big_endian()
+        .with_no_limit()
+}
+
+/// Writes an encrypted bundle of the identity and its private keys.
+pub(super) fn export_bundle(
+    path: &str,
+    passphrase: &str,
+    profile: &str,
+    identity: &Identity,
+    private_keys: Vec<(KeyID, Vec<u8>)>,
+) -> Result<String, String> {
+    let keys_count = private_keys.len();
+    let bundle = IdentityBundle {
+        profile: profile.to_owned(),
+        exported_at: current_timestamp(),
+        identity: identity.clone(),
+        private_keys,
+    };
+    let plaintext = bincode::encode_to_vec(bundle, bincode_config())
+        .map_err(|e| format!("Unable to serialize the bundle: {}", e))?;
+
+    let mut data = MAGIC.to_vec();
+    data.extend(encryption::encrypt(passphrase, &plaintext)?);
+    fs::write(path, data).map_err(|e| format!("Unable to write {}: {}", path, e))?;
+
+    Ok(format!(
+        "Exported identity {} with {} private keys to {}",
+        identity.id().to_string(Encoding::Base58),
+        keys_count,
+        path
+    ))
+}
+
+/// Reads and decrypts a bundle exported from the profile.
+pub(super) fn import_bundle(
+    path: &str,
+    passphrase: &str,
+    profile: &str,
+) -> Result<IdentityBundle, String> {
+    let data = fs::read(path).map_err(|e| format!("Unable to read {}: {}", path, e))?;
+    let Some(encrypted) = data.strip_prefix(MAGIC.as_slice()) else {
+        return Err(format!("{} is not an identity bundle", path));
+    };
+    let plaintext = encryption::decrypt(passphrase, encrypted)
+        .map_err(|_| "Wrong passphrase or corrupted bundle".to_owned())?;
+    let (bundle, _): (IdentityBundle, _) = bincode::decode_from_slice(&plaintext, bincode_config())
+        .map_err(|e| format!("Unable to deserialize the bundle: {}", e))?;
+
+    if bundle.profile != profile {
+        return Err(format!(
+            "Bundle was exported from profile {}, but {} is configured",
+            bundle.profile, profile
+        ));
+    }
+    Ok(bundle)
+}
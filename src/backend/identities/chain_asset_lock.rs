@@ -0,0 +1,89 @@
+//! Chain asset lock proofs.
+//! Where instant send locks are unreliable, an asset lock is proven by the
+//! chain lock of the block the transaction is mined in instead: the
+//! transaction is polled until it's chain locked and platform has caught up
+//! with its core height, so the proof is accepted.
+
+use std::time::{Duration, Instant};
+
+use dapi_grpc::core::v0::{GetTransactionRequest, GetTransactionResponse};
+use dash_sdk::Sdk;
+use dpp::{
+    dashcore::{OutPoint, Transaction},
+    identity::state_transition::asset_lock_proof::{chain::ChainAssetLockProof, AssetLockProof},
+};
+use rs_dapi_client::{DapiRequestExecutor, RequestSettings};
+
+use crate::backend::{progress, strategies::pacing::fetch_chain_tip};
+
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Blocks are mined every few minutes and chain locks may lag, so the wait is
+/// much longer than for an instant send lock.
+const CHAIN_LOCK_TIMEOUT: Duration = Duration::from_secs(30 * 60);
+
+/// Waits for the broadcasted asset lock transaction to be chain locked and
+/// known to platform, reporting confirmations progress.
+pub(super) async fn wait_for_chain_asset_lock_proof(
+    sdk: &Sdk,
+    asset_lock_transaction: &Transaction,
+) -> Result<AssetLockProof, dash_sdk::Error> {
+    let txid = asset_lock_transaction.txid();
+    let started = Instant::now();
+
+    loop {
+        if started.elapsed() > CHAIN_LOCK_TIMEOUT {
+            return Err(dash_sdk::Error::DapiClientError(format!(
+                "asset lock transaction {} wasn't chain locked in {} minutes",
+                txid,
+                CHAIN_LOCK_TIMEOUT.as_secs() / 60
+            )));
+        }
+
+        let GetTransactionResponse {
+            height,
+            confirmations,
+            is_chain_locked,
+            ..
+        } = sdk
+            .execute(
+                GetTransactionRequest {
+                    id: txid.to_string(),
+                },
+                RequestSettings::default(),
+            )
+            .await?;
+
+        if confirmations == 0 {
+            progress::report("Asset lock: waiting for the transaction to be mined".to_owned());
+        } else if !is_chain_locked {
+            progress::report(format!(
+                "Asset lock: mined at core height {}, {} confirmations, waiting for chain lock",
+                height, confirmations
+            ));
+        } else {
+            let platform_core_height = fetch_chain_tip(sdk)
+                .await
+                .map_err(dash_sdk::Error::DapiClientError)?
+                .core_height;
+            if platform_core_height >= height {
+                tracing::info!(
+                    "asset lock transaction {} is chain locked at core height {}",
+                    txid,
+                    height
+                );
+                return Ok(AssetLockProof::Chain(ChainAssetLockProof {
+                    core_chain_locked_height: height,
+                    // Funds of asset lock transactions are in the first output
+                    out_point: OutPoint { txid, vout: 0 },
+                }));
+            }
+            progress::report(format!(
+                "Asset lock: chain locked at core height {}, waiting for platform at {}",
+                height, platform_core_height
+            ));
+        }
+
+        tokio::time::sleep(POLL_INTERVAL).await;
+    }
+}
@@ -0,0 +1,128 @@
+//! DPNS label pre-check.
+//!
+//! Labels are validated locally with the rules of the DPNS contract and looked
+//! up on Platform before registration, so credits aren't spent on a preorder
+//! whose domain would be rejected or is already taken.
+
+use std::sync::Arc;
+
+use dash_sdk::{
+    platform::{DocumentQuery, Fetch},
+    Sdk,
+};
+use dpp::{
+    data_contract::DataContract,
+    document::{Document, DocumentV0Getters},
+    platform_value::{string_encoding::Encoding, Identifier, Value},
+    system_data_contracts::dpns_contract,
+    util::strings::convert_to_homograph_safe_chars,
+};
+use drive::query::{WhereClause, WhereOperator};
+
+const MIN_LABEL_LENGTH: usize = 3;
+const MAX_LABEL_LENGTH: usize = 63;
+
+/// Normalized labels up to this length made of letters, `0`, `1` and hyphens
+/// are contested.
+const MAX_CONTESTED_LABEL_LENGTH: usize = 19;
+
+/// Normalized label if the label follows the rules of the DPNS contract.
+pub(crate) fn validate_label(label: &str) -> Result<String, String> {
+    let length = label.chars().count();
+    if length < MIN_LABEL_LENGTH {
+        return Err(format!(
+            "Label is too short: {} characters, at least {}",
+            length, MIN_LABEL_LENGTH
+        ));
+    }
+    if length > MAX_LABEL_LENGTH {
+        return Err(format!(
+            "Label is too long: {} characters, at most {}",
+            length, MAX_LABEL_LENGTH
+        ));
+    }
+    if let Some(c) = label
+        .chars()
+        .find(|c| !c.is_ascii_alphanumeric() && *c != '-')
+    {
+        return Err(format!(
+            "Invalid character in label: '{}', only letters, digits and hyphens are allowed",
+            c
+        ));
+    }
+    if label.starts_with('-') || label.ends_with('-') {
+        return Err(format!("Label starts or ends with a hyphen: {}", label));
+    }
+    Ok(convert_to_homograph_safe_chars(label))
+}
+
+/// Whether masternodes vote on who gets the name instead of it going to the
+/// first registration.
+fn is_contested(normalized_label: &str) -> bool {
+    normalized_label.len() <= MAX_CONTESTED_LABEL_LENGTH
+        && normalized_label
+            .chars()
+            .all(|c| matches!(c, 'a'..='z' | '0' | '1' | '-'))
+}
+
+/// Owner of the domain registered under `.dash` with the normalized label.
+async fn fetch_domain_owner(
+    sdk: &Sdk,
+    normalized_label: &str,
+) -> Result<Option<Identifier>, String> {
+    let dpns_contract = DataContract::fetch(sdk, Into::<Identifier>::into(dpns_contract::ID_BYTES))
+        .await
+        .map_err(|e| format!("Failed to fetch DPNS contract: {}", e))?
+        .ok_or_else(|| "DPNS contract not found".to_owned())?;
+
+    let document_query = DocumentQuery {
+        data_contract: Arc::new(dpns_contract),
+        document_type_name: "domain".to_string(),
+        where_clauses: vec![
+            WhereClause {
+                field: "normalizedParentDomainName".to_string(),
+                operator: WhereOperator::Equal,
+                value: Value::Text("dash".to_string()),
+            },
+            WhereClause {
+                field: "normalizedLabel".to_string(),
+                operator: WhereOperator::Equal,
+                value: Value::Text(normalized_label.to_string()),
+            },
+        ],
+        order_by_clauses: vec![],
+        limit: 1,
+        start: None,
+    };
+
+    Document::fetch(sdk, document_query)
+        .await
+        .map(|document| document.map(|document| document.owner_id()))
+        .map_err(|e| format!("Failed to look up DPNS name: {}", e))
+}
+
+/// Availability and contest status of the label, an error if registering it
+/// is bound to fail.
+pub(super) async fn check_dpns_name(sdk: &Sdk, label: &str) -> Result<String, String> {
+    let normalized_label = validate_label(label)?;
+
+    if let Some(owner) = fetch_domain_owner(sdk, &normalized_label).await? {
+        return Err(format!(
+            "Name is already registered: {}.dash is owned by {}",
+            normalized_label,
+            owner.to_string(Encoding::Base58)
+        ));
+    }
+
+    let mut lines = vec![format!(
+        "{}.dash is available (normalized as {})",
+        label, normalized_label
+    )];
+    if is_contested(&normalized_label) {
+        lines.push(
+            "Contested name: masternodes vote on who gets it, registering it enters the contest"
+                .to_owned(),
+        );
+    }
+    Ok(lines.join("\n"))
+}
@@ -0,0 +1,110 @@
+//! Export of the loaded identity's public keys.
+//!
+//! Integrators building against an identity get its key set as a file instead
+//! of transcribing keys from the screen. Key data is hex encoded. Platform
+//! records when a key was disabled, not at which height, so disabled keys have
+//! the time in milliseconds they were disabled at.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use dpp::{
+    identity::{
+        accessors::IdentityGettersV0,
+        identity_public_key::accessors::v0::IdentityPublicKeyGettersV0, KeyID,
+    },
+    platform_value::string_encoding::Encoding,
+    prelude::{Identity, IdentityPublicKey},
+};
+use serde::Serialize;
+
+use crate::backend::{as_json_string, documents::bulk::ExportFormat, reports::current_timestamp};
+
+const EXPORTS_DIR: &str = "supporting_files/key_exports";
+
+const HEADER: &str = "id,type,purpose,security_level,read_only,data,disabled_at";
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ExportedKey {
+    id: KeyID,
+    #[serde(rename = "type")]
+    key_type: String,
+    purpose: String,
+    security_level: String,
+    read_only: bool,
+    /// Hex encoded key data
+    data: String,
+    /// Unix timestamp in milliseconds
+    disabled_at: Option<u64>,
+}
+
+impl From<&IdentityPublicKey> for ExportedKey {
+    fn from(key: &IdentityPublicKey) -> Self {
+        ExportedKey {
+            id: key.id(),
+            key_type: key.key_type().to_string(),
+            purpose: key.purpose().to_string(),
+            security_level: key.security_level().to_string(),
+            read_only: key.read_only(),
+            data: hex::encode(key.data().as_slice()),
+            disabled_at: key.disabled_at(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct KeysExport {
+    identity_id: String,
+    keys: Vec<ExportedKey>,
+}
+
+/// One row per key, ordered by key ID.
+fn keys_csv(keys: &[ExportedKey]) -> String {
+    let mut lines = vec![HEADER.to_owned()];
+    for key in keys {
+        lines.push(format!(
+            "{},{},{},{},{},{},{}",
+            key.id,
+            key.key_type,
+            key.purpose,
+            key.security_level,
+            key.read_only,
+            key.data,
+            key.disabled_at.map(|t| t.to_string()).unwrap_or_default()
+        ));
+    }
+    lines.push(String::new());
+    lines.join("\n")
+}
+
+/// Writes the identity's public keys into a new file of the exports
+/// directory, returning its path.
+pub(super) fn export_keys(identity: &Identity, format: ExportFormat) -> Result<PathBuf, String> {
+    let identity_id = identity.id().to_string(Encoding::Base58);
+    let keys: Vec<ExportedKey> = identity.public_keys().values().map(Into::into).collect();
+    let (content, extension) = match format {
+        ExportFormat::Json => (
+            as_json_string(&KeysExport {
+                identity_id: identity_id.clone(),
+                keys,
+            }),
+            "json",
+        ),
+        ExportFormat::Csv => (keys_csv(&keys), "csv"),
+    };
+
+    fs::create_dir_all(EXPORTS_DIR)
+        .map_err(|e| format!("Failed to create exports directory: {}", e))?;
+    let path = Path::new(EXPORTS_DIR).join(format!(
+        "keys_{}_{}.{}",
+        identity_id,
+        current_timestamp(),
+        extension
+    ));
+    fs::write(&path, content).map_err(|e| format!("Failed to write export file: {}", e))?;
+    Ok(path)
+}
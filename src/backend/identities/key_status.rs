@@ -0,0 +1,162 @@
+//! Identity public key status verification.
+//!
+//! Checks whether a public key is active, disabled or was never added to an
+//! identity. Identity keys are never removed, only disabled, so a key missing
+//! from a proven identity never existed for it.
+
+use std::{fs, path::Path};
+
+use dapi_grpc::platform::v0::ResponseMetadata;
+use dash_sdk::{platform::Fetch, Sdk};
+use dpp::{
+    identity::{
+        accessors::IdentityGettersV0,
+        identity_public_key::accessors::v0::IdentityPublicKeyGettersV0, KeyID,
+    },
+    platform_value::{string_encoding::Encoding, Identifier},
+    prelude::{Identity, IdentityPublicKey},
+};
+use serde::Serialize;
+
+use crate::backend::{as_json_string, reports::current_timestamp};
+
+const ATTESTATIONS_DIR: &str = "supporting_files/key_attestations";
+
+/// Public key to check, either by its id or by its data.
+enum KeySelector {
+    Id(KeyID),
+    Data(Vec<u8>),
+}
+
+impl KeySelector {
+    fn parse(input: &str) -> Result<Self, String> {
+        let input = input.trim();
+        if let Ok(id) = input.parse() {
+            return Ok(KeySelector::Id(id));
+        }
+        hex::decode(input)
+            .map(KeySelector::Data)
+            .map_err(|_| "Key must be either a key id or hex encoded public key data".to_owned())
+    }
+
+    fn find<'a>(&self, identity: &'a Identity) -> Option<&'a IdentityPublicKey> {
+        match self {
+            KeySelector::Id(id) => identity.public_keys().get(id),
+            KeySelector::Data(data) => identity
+                .public_keys()
+                .values()
+                .find(|key| key.data().as_slice() == data.as_slice()),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum KeyStatus {
+    Active,
+    Disabled,
+    NeverExisted,
+    IdentityNotFound,
+}
+
+/// State of the platform the proof was verified against.
+#[derive(Debug, Serialize)]
+pub(crate) struct ProvenAt {
+    chain_id: String,
+    height: u64,
+    core_chain_locked_height: u32,
+    epoch: u32,
+    time_ms: u64,
+    protocol_version: u32,
+}
+
+impl From<ResponseMetadata> for ProvenAt {
+    fn from(metadata: ResponseMetadata) -> Self {
+        ProvenAt {
+            chain_id: metadata.chain_id,
+            height: metadata.height,
+            core_chain_locked_height: metadata.core_chain_locked_height,
+            epoch: metadata.epoch,
+            time_ms: metadata.time_ms,
+            protocol_version: metadata.protocol_version,
+        }
+    }
+}
+
+/// Self-contained result of a key status check, meant to be handed over to
+/// other parties.
+#[derive(Debug, Serialize)]
+struct KeyStatusAttestation {
+    identity_id: String,
+    key_id: Option<KeyID>,
+    key_data: Option<String>,
+    status: KeyStatus,
+    /// Disabling timestamp in milliseconds
+    disabled_at: Option<u64>,
+    proven_at: ProvenAt,
+    /// Unix timestamp in seconds of the check
+    checked_at: u64,
+}
+
+/// Fetches the identity with proof and builds the key status attestation,
+/// which is also saved into the attestations directory.
+pub(super) async fn check_key_status(
+    sdk: &Sdk,
+    base58_identity_id: &str,
+    key: &str,
+) -> Result<String, String> {
+    let identity_id = Identifier::from_string(base58_identity_id, Encoding::Base58)
+        .map_err(|_| "Can't parse identifier as base58 string".to_owned())?;
+    let selector = KeySelector::parse(key)?;
+
+    let (identity, metadata) = Identity::fetch_with_metadata(sdk, identity_id, None)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let requested_id = match &selector {
+        KeySelector::Id(id) => Some(*id),
+        KeySelector::Data(_) => None,
+    };
+    let requested_data = match &selector {
+        KeySelector::Id(_) => None,
+        KeySelector::Data(data) => Some(hex::encode(data)),
+    };
+
+    let key = identity
+        .as_ref()
+        .and_then(|identity| selector.find(identity));
+    let status = match (&identity, key) {
+        (None, _) => KeyStatus::IdentityNotFound,
+        (Some(_), None) => KeyStatus::NeverExisted,
+        (Some(_), Some(key)) if key.disabled_at().is_some() => KeyStatus::Disabled,
+        (Some(_), Some(_)) => KeyStatus::Active,
+    };
+
+    let attestation = KeyStatusAttestation {
+        identity_id: identity_id.to_string(Encoding::Base58),
+        key_id: key.map(|key| key.id()).or(requested_id),
+        key_data: key
+            .map(|key| hex::encode(key.data().as_slice()))
+            .or(requested_data),
+        status,
+        disabled_at: key.and_then(|key| key.disabled_at()),
+        proven_at: metadata.into(),
+        checked_at: current_timestamp(),
+    };
+    let attestation_json = as_json_string(&attestation);
+
+    fs::create_dir_all(ATTESTATIONS_DIR)
+        .map_err(|e| format!("Failed to create attestations directory: {}", e))?;
+    let path = Path::new(ATTESTATIONS_DIR).join(format!(
+        "{}_{}.json",
+        attestation.identity_id, attestation.checked_at
+    ));
+    fs::write(&path, &attestation_json)
+        .map_err(|e| format!("Failed to write attestation file: {}", e))?;
+
+    Ok(format!(
+        "Attestation saved into {}\n\n{}",
+        path.display(),
+        attestation_json
+    ))
+}
@@ -0,0 +1,200 @@
+//! Sponsored identity registrations.
+//!
+//! The identity is made of public keys provided by someone else, pasted as
+//! JSON or read from a file, while the loaded wallet only funds the asset lock.
+//! The identity create transition is signed with the asset lock key and
+//! written to a file without the signatures of its public keys, for the keys
+//! owner to sign and broadcast it.
+
+use std::fs;
+
+use dash_sdk::Sdk;
+use dpp::{
+    bls::native_bls::NativeBlsModule,
+    identity::{
+        identity_public_key::{accessors::v0::IdentityPublicKeyGettersV0, v0::IdentityPublicKeyV0},
+        KeyType, Purpose as KeyPurpose, SecurityLevel as KeySecurityLevel,
+    },
+    platform_value::{string_encoding::Encoding, BinaryData},
+    prelude::IdentityPublicKey,
+    serialization::{PlatformSerializable, Signable},
+    state_transition::{
+        identity_create_transition::{v0::IdentityCreateTransitionV0, IdentityCreateTransition},
+        public_key_in_creation::v0::IdentityPublicKeyInCreationV0,
+        StateTransition,
+    },
+};
+use serde::Deserialize;
+
+use crate::backend::AppState;
+
+/// Public key of the sponsored identity, enums are given by their numeric
+/// values.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SponsoredKey {
+    purpose: u8,
+    security_level: u8,
+    #[serde(rename = "type")]
+    key_type: u8,
+    /// Hex encoded public key data
+    data: String,
+    #[serde(default)]
+    read_only: bool,
+}
+
+/// Reads keys from JSON, or from the file if it's not JSON. Key IDs are given
+/// in order.
+fn parse_keys(keys: &str) -> Result<Vec<IdentityPublicKey>, String> {
+    let sponsored_keys: Vec<SponsoredKey> = match serde_json::from_str(keys) {
+        Ok(sponsored_keys) => sponsored_keys,
+        Err(_) => {
+            let content = fs::read_to_string(keys.trim())
+                .map_err(|e| format!("Keys are neither JSON nor a readable file: {}", e))?;
+            serde_json::from_str(&content).map_err(|e| format!("Invalid keys file: {}", e))?
+        }
+    };
+    if sponsored_keys.is_empty() {
+        return Err("No public keys provided".to_owned());
+    }
+
+    let public_keys = sponsored_keys
+        .into_iter()
+        .enumerate()
+        .map(|(id, key)| {
+            let purpose = KeyPurpose::try_from(key.purpose)
+                .map_err(|_| format!("Key {}: unknown purpose {}", id, key.purpose))?;
+            let security_level = KeySecurityLevel::try_from(key.security_level).map_err(|_| {
+                format!("Key {}: unknown security level {}", id, key.security_level)
+            })?;
+            let key_type = KeyType::try_from(key.key_type)
+                .map_err(|_| format!("Key {}: unknown key type {}", id, key.key_type))?;
+            let data = hex::decode(&key.data)
+                .map_err(|e| format!("Key {}: invalid hex data: {}", id, e))?;
+            if data.len() != key_type.default_size() {
+                return Err(format!(
+                    "Key {}: {} bytes of data expected for {}",
+                    id,
+                    key_type.default_size(),
+                    key_type
+                ));
+            }
+            Ok(IdentityPublicKey::V0(IdentityPublicKeyV0 {
+                id: id as u32,
+                purpose,
+                security_level,
+                contract_bounds: None,
+                key_type,
+                read_only: key.read_only,
+                data: BinaryData::new(data),
+                disabled_at: None,
+            }))
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+
+    // Otherwise the funds would be locked into a transition rejected by Platform
+    if !public_keys.iter().any(|key| {
+        key.purpose() == KeyPurpose::AUTHENTICATION
+            && key.security_level() == KeySecurityLevel::MASTER
+    }) {
+        return Err("A master authentication key is required".to_owned());
+    }
+    Ok(public_keys)
+}
+
+impl AppState {
+    /// Funds an identity made of provided public keys and writes its create
+    /// transition to the file, signed with the asset lock key only.
+    pub(super) async fn sponsor_identity(
+        &self,
+        sdk: &Sdk,
+        keys: &str,
+        amount: u64,
+        path: &str,
+    ) -> Result<String, String> {
+        let public_keys = parse_keys(keys)?;
+
+        self.reorg_watch.lock().await.ensure_stable()?;
+        let fee_rate = self.fee_settings.lock().await.rate();
+
+        let mut loaded_wallet = self.loaded_wallet.lock().await;
+        let Some(wallet) = loaded_wallet.as_mut() else {
+            return Err("No wallet loaded".to_owned());
+        };
+
+        let (asset_lock_transaction, asset_lock_private_key) = wallet
+            .asset_lock_transaction(None, amount, fee_rate)
+            .map_err(|e| e.to_string())?;
+        self.asset_locks
+            .lock()
+            .await
+            .record(&asset_lock_transaction, &asset_lock_private_key);
+
+        let asset_lock_proof = Self::broadcast_and_retrieve_asset_lock(
+            sdk,
+            &asset_lock_transaction,
+            &wallet.receive_address(),
+            self.asset_lock_proof,
+        )
+        .await
+        .map_err(|e| format!("Broadcasting transaction failed: {}", e))?;
+        let identity_id = asset_lock_proof
+            .create_identifier()
+            .map_err(|e| e.to_string())?;
+
+        let key_ids: Vec<u32> = public_keys.iter().map(|key| key.id()).collect();
+        let mut state_transition: StateTransition =
+            IdentityCreateTransition::V0(IdentityCreateTransitionV0 {
+                public_keys: public_keys
+                    .into_iter()
+                    .map(|key| Into::<IdentityPublicKeyInCreationV0>::into(key).into())
+                    .collect(),
+                asset_lock_proof,
+                user_fee_increase: 0,
+                signature: BinaryData::default(),
+                identity_id,
+            })
+            .into();
+        state_transition
+            .sign_by_private_key(
+                &asset_lock_private_key.to_bytes(),
+                KeyType::ECDSA_HASH160,
+                &NativeBlsModule,
+            )
+            .map_err(|e| format!("Unable to sign the transition: {}", e))?;
+
+        let signable_bytes = state_transition
+            .signable_bytes()
+            .map_err(|e| e.to_string())?;
+        let transition_bytes = state_transition
+            .serialize_to_bytes()
+            .map_err(|e| e.to_string())?;
+        let export = serde_json::json!({
+            "identityId": identity_id.to_string(Encoding::Base58),
+            "assetLockTransactionId": asset_lock_transaction.txid().to_string(),
+            "keyIds": key_ids,
+            "stateTransition": hex::encode(transition_bytes),
+            "signableBytes": hex::encode(signable_bytes),
+        });
+        fs::write(
+            path,
+            serde_json::to_string_pretty(&export).map_err(|e| e.to_string())?,
+        )
+        .map_err(|e| format!("Unable to write the transition: {}", e))?;
+
+        // The asset lock is bound to the transition now, it can't fund another
+        // identity
+        self.asset_locks
+            .lock()
+            .await
+            .mark_used(&asset_lock_transaction.txid());
+
+        Ok(format!(
+            "Identity {} funded with {} Dash, its create transition was written to {}. Each \
+             public key signature must be added by the keys owner before broadcasting it.",
+            identity_id.to_string(Encoding::Base58),
+            amount as f64 / 100_000_000.0,
+            path
+        ))
+    }
+}
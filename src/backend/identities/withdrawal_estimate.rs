@@ -0,0 +1,130 @@
+//! Estimates of identity credit withdrawals.
+//!
+//! Withdrawals are queued on Platform, pooled a few per block into asset
+//! unlock transactions and paid out once Core chain locks the block including
+//! them. The Core fee of the transaction is deducted from the payout.
+//! Numbers are approximations meant to set expectations before withdrawing.
+
+use chrono_humanize::{Accuracy, HumanTime, Tense};
+use dash_sdk::{
+    platform::{DocumentQuery, Fetch, FetchMany},
+    Sdk,
+};
+use dpp::{
+    data_contracts::withdrawals_contract,
+    document::Document,
+    platform_value::Value,
+    prelude::{DataContract, Identifier},
+};
+use drive::query::{WhereClause, WhereOperator};
+
+use crate::backend::strategies::forecast::CREDITS_PER_DUFF;
+
+const WITHDRAWAL_DOCUMENT_TYPE: &str = "withdrawal";
+const STATUS_FIELD: &str = "status";
+/// Statuses of withdrawals not paid out yet, with their values in the
+/// withdrawals contract.
+const PENDING_STATUSES: [(&str, u8); 3] = [("queued", 0), ("pooled", 1), ("broadcasted", 2)];
+/// Pending withdrawals counted per status, the queue is at least that long
+/// if reached.
+const QUEUE_QUERY_LIMIT: u32 = 100;
+/// Queued withdrawals pooled into transactions on each Platform block.
+const WITHDRAWALS_PER_BLOCK: u64 = 4;
+/// Rough interval between Platform blocks.
+const PLATFORM_BLOCK_SECONDS: u64 = 30;
+/// Rough time for an asset unlock transaction to be mined and chain locked.
+const CORE_CONFIRMATION_SECONDS: u64 = 300;
+/// Size of an asset unlock transaction with a single output.
+const ASSET_UNLOCK_TRANSACTION_BYTES: u64 = 190;
+/// Core fee rate of withdrawals submitted by the explorer.
+const CORE_FEE_PER_BYTE: u64 = 1;
+
+/// Number of withdrawals of the status, capped to the query limit.
+async fn count_withdrawals(
+    sdk: &Sdk,
+    withdrawals_contract: &DataContract,
+    status: u8,
+) -> Result<usize, String> {
+    let mut query = DocumentQuery::new(withdrawals_contract.clone(), WITHDRAWAL_DOCUMENT_TYPE)
+        .map_err(|e| e.to_string())?;
+    query.where_clauses.push(WhereClause {
+        field: STATUS_FIELD.to_owned(),
+        operator: WhereOperator::Equal,
+        value: Value::U8(status),
+    });
+    query.limit = QUEUE_QUERY_LIMIT;
+    Document::fetch_many(sdk, query)
+        .await
+        .map(|documents| documents.len())
+        .map_err(|e| e.to_string())
+}
+
+/// Describes the fee deducted from a withdrawal of the amount of credits, the
+/// pooling schedule and when it's expected to be paid out given the current
+/// queue.
+pub(super) async fn estimate_withdrawal(sdk: &Sdk, amount: u64) -> Result<String, String> {
+    let withdrawals_contract =
+        DataContract::fetch(sdk, Identifier::from(withdrawals_contract::ID_BYTES))
+            .await
+            .map_err(|e| e.to_string())?
+            .ok_or_else(|| "Withdrawals contract not found".to_owned())?;
+
+    let mut pending = Vec::new();
+    for (name, status) in PENDING_STATUSES {
+        pending.push((
+            name,
+            count_withdrawals(sdk, &withdrawals_contract, status).await?,
+        ));
+    }
+    let capped = pending
+        .iter()
+        .any(|(_, count)| *count >= QUEUE_QUERY_LIMIT as usize);
+    let queued = pending[0].1 as u64;
+
+    let fee_duffs = ASSET_UNLOCK_TRANSACTION_BYTES * CORE_FEE_PER_BYTE;
+    let amount_duffs = amount / CREDITS_PER_DUFF;
+    let blocks = queued / WITHDRAWALS_PER_BLOCK + 1;
+    let seconds = blocks * PLATFORM_BLOCK_SECONDS + CORE_CONFIRMATION_SECONDS;
+
+    let mut lines = vec![
+        format!("Withdrawal of {} credits ({} duffs)", amount, amount_duffs),
+        format!(
+            "Core fee: about {} duffs ({} credits) deducted from the payout",
+            fee_duffs,
+            fee_duffs * CREDITS_PER_DUFF
+        ),
+    ];
+    if amount_duffs > fee_duffs {
+        lines.push(format!(
+            "Expected payout: {} duffs ({:.8} Dash), Platform processing fees are paid from the \
+             remaining balance",
+            amount_duffs - fee_duffs,
+            (amount_duffs - fee_duffs) as f64 / 100_000_000.0
+        ));
+    } else {
+        lines.push("The amount doesn't cover the Core fee, the withdrawal will fail".to_owned());
+    }
+    lines.push(String::new());
+    lines.push(format!(
+        "Pooling: queued withdrawals are pooled into asset unlock transactions, up to {} per \
+         Platform block, and paid out once Core chain locks them",
+        WITHDRAWALS_PER_BLOCK
+    ));
+    lines.push(format!(
+        "Pending withdrawals: {}{}",
+        pending
+            .iter()
+            .map(|(name, count)| format!("{} {}", count, name))
+            .collect::<Vec<_>>()
+            .join(", "),
+        if capped { " (at least)" } else { "" }
+    ));
+    lines.push(format!(
+        "Estimated completion: {} after submission, about {} Platform blocks and a Core chain \
+         lock",
+        HumanTime::from(chrono::Duration::seconds(seconds as i64))
+            .to_text_en(Accuracy::Rough, Tense::Present),
+        blocks
+    ));
+    Ok(lines.join("\n"))
+}
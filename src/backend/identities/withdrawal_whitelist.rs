@@ -0,0 +1,56 @@
+//! Withdrawal addresses whitelisted by identity keys.
+//!
+//! A transfer key can be bound to a Core address instead of holding a public
+//! key: its data is then the hash of the address. When an identity has such
+//! keys, withdrawals are only sent to these addresses.
+
+use std::str::FromStr;
+
+use dpp::{
+    dashcore::{address::Payload, hashes::Hash, Address, Network, PubkeyHash, ScriptHash},
+    identity::{
+        accessors::IdentityGettersV0,
+        identity_public_key::accessors::v0::IdentityPublicKeyGettersV0, KeyType, Purpose,
+    },
+    prelude::Identity,
+};
+
+/// Core addresses bound to enabled transfer keys of the identity.
+pub(crate) fn withdrawal_whitelist(identity: &Identity, network: Network) -> Vec<Address> {
+    identity
+        .public_keys()
+        .values()
+        .filter(|key| key.purpose() == Purpose::TRANSFER && key.disabled_at().is_none())
+        .filter_map(|key| {
+            let data = key.data().as_slice();
+            let payload = match key.key_type() {
+                KeyType::ECDSA_HASH160 => Payload::PubkeyHash(PubkeyHash::from_slice(data).ok()?),
+                KeyType::BIP13_SCRIPT_HASH => {
+                    Payload::ScriptHash(ScriptHash::from_slice(data).ok()?)
+                }
+                _ => return None,
+            };
+            Some(Address { payload, network })
+        })
+        .collect()
+}
+
+/// Key type and data of a transfer key bound to the Core address.
+pub(super) fn address_key_data(
+    address: &str,
+    network: Network,
+) -> Result<(KeyType, Vec<u8>), String> {
+    let address =
+        Address::from_str(address.trim()).map_err(|e| format!("Invalid Core address: {e}"))?;
+    if address.network != network {
+        return Err(format!(
+            "Address is for {} while {} is configured",
+            address.network, network
+        ));
+    }
+    match address.payload {
+        Payload::PubkeyHash(hash) => Ok((KeyType::ECDSA_HASH160, hash[..].to_vec())),
+        Payload::ScriptHash(hash) => Ok((KeyType::BIP13_SCRIPT_HASH, hash[..].to_vec())),
+        _ => Err("Only P2PKH and P2SH addresses can be whitelisted".to_owned()),
+    }
+}
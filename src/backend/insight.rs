@@ -1,9 +1,17 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 use dapi_grpc::tonic::transport::Uri;
-use dpp::dashcore::{Address, OutPoint, ScriptBuf, TxOut, Txid};
+use dpp::dashcore::{
+    psbt::serialize::Deserialize, Address, OutPoint, ScriptBuf, Transaction, TxOut, Txid,
+};
+
+use super::traffic::{self, Channel};
 
 const ADDRESS_UTXO_PATH: &str = "addrs/utxo";
+const ADDRESS_TRANSACTIONS_PATH: &str = "addrs";
+const TRANSACTION_PATH: &str = "tx";
+const RAW_TRANSACTION_PATH: &str = "rawtx";
+const ESTIMATE_FEE_PATH: &str = "utils/estimatefee";
 
 #[derive(Debug, thiserror::Error)]
 #[error("insight error: {0}")]
@@ -46,6 +54,20 @@ impl InsightAPIClient {
         &self,
         addresses: &[&Address],
     ) -> Result<HashMap<OutPoint, TxOut>, InsightError> {
+        Ok(self
+            .utxos_with_confirmations_for_addresses(addresses)
+            .await?
+            .into_iter()
+            .map(|(outpoint, (tx_out, _))| (outpoint, tx_out))
+            .collect())
+    }
+
+    /// Same as [Self::utxos_with_amount_for_addresses], but also returns the
+    /// number of confirmations of the transaction each UTXO belongs to.
+    pub async fn utxos_with_confirmations_for_addresses(
+        &self,
+        addresses: &[&Address],
+    ) -> Result<HashMap<OutPoint, (TxOut, u64)>, InsightError> {
         let url = format!("{}/{}", self.0, ADDRESS_UTXO_PATH);
 
         let addr_str = addresses
@@ -53,13 +75,15 @@ impl InsightAPIClient {
             .map(|address| address.to_string())
             .collect::<Vec<_>>()
             .join(",");
-        let resp = reqwest::Client::new()
-            .post(&url)
-            .header("Content-Type", "application/x-www-form-urlencoded")
-            .body(format!("addrs={}", addr_str))
-            .send()
-            .await
-            .map_err(|e| InsightError(e.to_string()))?;
+        let body = format!("addrs={}", addr_str);
+        let resp = send(
+            reqwest::Client::new()
+                .post(&url)
+                .header("Content-Type", "application/x-www-form-urlencoded"),
+            &url,
+            body,
+        )
+        .await?;
 
         let status = resp.status();
 
@@ -102,15 +126,205 @@ impl InsightAPIClient {
             let script = ScriptBuf::from_hex(script_buf_str)
                 .map_err(|_| InsightError("Invalid scriptPubKey format".into()))?;
 
+            // Unconfirmed outputs come without the field
+            let confirmations = utxo
+                .get("confirmations")
+                .and_then(|v| v.as_u64())
+                .unwrap_or_default();
+
             utxos.insert(
                 OutPoint { txid, vout },
-                TxOut {
-                    value,
-                    script_pubkey: script,
-                },
+                (
+                    TxOut {
+                        value,
+                        script_pubkey: script,
+                    },
+                    confirmations,
+                ),
             );
         }
 
         Ok(utxos)
     }
+
+    /// Fetches the number of confirmations of a transaction, `None` if the
+    /// transaction is unknown to the node (never relayed or dropped from the
+    /// mempool or the chain).
+    pub async fn transaction_confirmations(
+        &self,
+        txid: &Txid,
+    ) -> Result<Option<u64>, InsightError> {
+        let url = format!("{}/{}/{}", self.0, TRANSACTION_PATH, txid);
+
+        let resp = send(reqwest::Client::new().get(&url), &url, String::new()).await?;
+
+        let status = resp.status();
+
+        if status == reqwest::StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+
+        if !status.is_success() {
+            let error_body = resp
+                .text()
+                .await
+                .unwrap_or_else(|_| "Failed to read error body".to_string());
+            return Err(InsightError(format!(
+                "Request failed with status {}: {}",
+                status, error_body
+            )));
+        }
+
+        let json: serde_json::Value = resp.json().await.map_err(|e| InsightError(e.to_string()))?;
+
+        Ok(Some(
+            json.get("confirmations")
+                .and_then(|v| v.as_u64())
+                .unwrap_or_default(),
+        ))
+    }
+
+    /// Fetches the latest asset lock transactions involving the address, at
+    /// most `limit` of the address transactions are looked through.
+    pub async fn asset_lock_transactions_for_address(
+        &self,
+        address: &Address,
+        limit: usize,
+    ) -> Result<Vec<Transaction>, InsightError> {
+        /// Special transaction type of asset locks (DIP-27)
+        const ASSET_LOCK_TRANSACTION_TYPE: u64 = 8;
+
+        let url = format!(
+            "{}/{}/{}/txs?from=0&to={}",
+            self.0, ADDRESS_TRANSACTIONS_PATH, address, limit
+        );
+        let json = get_json(&url).await?.ok_or_else(|| {
+            InsightError(format!("Transactions of address {} not found", address))
+        })?;
+
+        let txids = json
+            .get("items")
+            .and_then(|items| items.as_array())
+            .ok_or_else(|| InsightError("Missing items".into()))?
+            .iter()
+            .filter(|tx| {
+                tx.get("type").and_then(|v| v.as_u64()) == Some(ASSET_LOCK_TRANSACTION_TYPE)
+            })
+            .filter_map(|tx| tx.get("txid").and_then(|v| v.as_str()))
+            .map(|txid| txid.to_owned())
+            .collect::<Vec<_>>();
+
+        let mut transactions = Vec::with_capacity(txids.len());
+        for txid in txids {
+            let url = format!("{}/{}/{}", self.0, RAW_TRANSACTION_PATH, txid);
+            let Some(json) = get_json(&url).await? else {
+                continue;
+            };
+            let raw_transaction = json
+                .get("rawtx")
+                .and_then(|v| v.as_str())
+                .ok_or_else(|| InsightError("Missing rawtx".into()))?;
+            let bytes = hex::decode(raw_transaction)
+                .map_err(|_| InsightError("Invalid rawtx format".into()))?;
+            transactions.push(
+                Transaction::deserialize(&bytes)
+                    .map_err(|_| InsightError("Invalid rawtx format".into()))?,
+            );
+        }
+
+        Ok(transactions)
+    }
+
+    /// Fetches fee rate estimates in duffs per kilobyte for transactions to be
+    /// confirmed within each of the given number of blocks. A target is
+    /// missing in the result if the node has no estimate for it.
+    pub async fn fee_rate_estimates(
+        &self,
+        target_blocks: &[u32],
+    ) -> Result<BTreeMap<u32, u64>, InsightError> {
+        let targets = target_blocks
+            .iter()
+            .map(|blocks| blocks.to_string())
+            .collect::<Vec<_>>()
+            .join(",");
+        let url = format!("{}/{}?nbBlocks={}", self.0, ESTIMATE_FEE_PATH, targets);
+
+        let resp = send(reqwest::Client::new().get(&url), &url, String::new()).await?;
+
+        let status = resp.status();
+
+        if !status.is_success() {
+            let error_body = resp
+                .text()
+                .await
+                .unwrap_or_else(|_| "Failed to read error body".to_string());
+            return Err(InsightError(format!(
+                "Request failed with status {}: {}",
+                status, error_body
+            )));
+        }
+
+        let json: serde_json::Map<String, serde_json::Value> =
+            resp.json().await.map_err(|e| InsightError(e.to_string()))?;
+
+        // Estimates are in Dash per kilobyte, -1 if unavailable
+        Ok(json
+            .iter()
+            .filter_map(|(blocks, rate)| {
+                let blocks = blocks.parse().ok()?;
+                let rate = rate.as_f64().filter(|rate| *rate > 0.0)?;
+                Some((blocks, (rate * 100_000_000.0).round() as u64))
+            })
+            .collect())
+    }
+}
+
+/// Sends the request with the body, counting it in the traffic meter.
+async fn send(
+    request: reqwest::RequestBuilder,
+    url: &str,
+    body: String,
+) -> Result<reqwest::Response, InsightError> {
+    traffic::record_request(Channel::Insight, url.len() + body.len());
+    let request = if body.is_empty() {
+        request
+    } else {
+        request.body(body)
+    };
+    let resp = request
+        .send()
+        .await
+        .map_err(|e| InsightError(e.to_string()))?;
+    traffic::record_response(
+        Channel::Insight,
+        resp.content_length().unwrap_or_default() as usize,
+    );
+    Ok(resp)
+}
+
+/// GET request of a JSON value, `None` if not found.
+async fn get_json(url: &str) -> Result<Option<serde_json::Value>, InsightError> {
+    let resp = send(reqwest::Client::new().get(url), url, String::new()).await?;
+
+    let status = resp.status();
+
+    if status == reqwest::StatusCode::NOT_FOUND {
+        return Ok(None);
+    }
+
+    if !status.is_success() {
+        let error_body = resp
+            .text()
+            .await
+            .unwrap_or_else(|_| "Failed to read error body".to_string());
+        return Err(InsightError(format!(
+            "Request failed with status {}: {}",
+            status, error_body
+        )));
+    }
+
+    resp.json()
+        .await
+        .map(Some)
+        .map_err(|e| InsightError(e.to_string()))
 }
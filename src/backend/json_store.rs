@@ -0,0 +1,64 @@
+//! Values kept in JSON files of the profile.
+//! Bookmarks, petnames, strategy settings and the like live outside of the
+//! state file, each in a file of its own, read on startup and written on
+//! every change.
+
+use std::{
+    fs,
+    ops::{Deref, DerefMut},
+    path::PathBuf,
+};
+
+use serde::{de::DeserializeOwned, Serialize};
+
+#[derive(Debug, Default)]
+pub(crate) struct JsonStore<T> {
+    path: PathBuf,
+    /// What the file holds, for error messages
+    name: &'static str,
+    value: T,
+}
+
+impl<T: Default + Serialize + DeserializeOwned> JsonStore<T> {
+    /// Reads the value of the file, the default one if the file doesn't exist
+    /// yet or can't be decoded.
+    pub(crate) fn load(path: PathBuf, name: &'static str) -> Self {
+        let value = fs::read(&path)
+            .ok()
+            .and_then(|bytes| {
+                serde_json::from_slice(&bytes)
+                    .map_err(|e| tracing::error!("Unable to load {}: {}", name, e))
+                    .ok()
+            })
+            .unwrap_or_default();
+        JsonStore { path, name, value }
+    }
+
+    pub(crate) fn save(&self) -> Result<(), String> {
+        let json = serde_json::to_string_pretty(&self.value)
+            .map_err(|e| format!("Failed to serialize {}: {}", self.name, e))?;
+        fs::write(&self.path, json).map_err(|e| format!("Failed to save {}: {}", self.name, e))
+    }
+
+    /// Saves the value for changes that can't fail, like those observed from
+    /// the network, failures are only logged.
+    pub(crate) fn save_or_log(&self) {
+        if let Err(e) = self.save() {
+            tracing::error!("{}", e);
+        }
+    }
+}
+
+impl<T> Deref for JsonStore<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<T> DerefMut for JsonStore<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.value
+    }
+}
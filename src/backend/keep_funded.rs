@@ -0,0 +1,412 @@
+//! Keep-funded mode.
+//! Selected identities are monitored periodically and topped up from the
+//! wallet once their balance drops below a per-identity threshold, limited by
+//! a daily cap on the amount of Dash spent. Intended for maintaining fleets of
+//! strategy actor identities. Watched identities, the cap and the amount spent
+//! today are kept in a JSON file of the profile so restarting the application
+//! neither resets the cap nor forgets the settings. A top up counts against
+//! the cap once its asset lock is broadcast, even if the top up then fails.
+
+use std::{
+    collections::{BTreeMap, VecDeque},
+    path::PathBuf,
+};
+
+use dash_sdk::{
+    platform::{transition::top_up_identity::TopUpIdentity, Fetch},
+    Sdk,
+};
+use dpp::{
+    identity::accessors::{IdentityGettersV0, IdentitySettersV0},
+    platform_value::{string_encoding::Encoding, Identifier},
+    prelude::Identity,
+};
+use serde::{Deserialize, Serialize};
+
+use super::{
+    json_store::JsonStore, lanes, reports::current_timestamp, AppState, AppStateUpdate,
+    BackendEvent, Task,
+};
+
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+const DUFFS_PER_DASH: f64 = 100_000_000.0;
+const CREDITS_PER_DASH: f64 = 100_000_000_000.0;
+/// Number of activity log entries to keep.
+const ACTIVITY_LOG_SIZE: usize = 50;
+
+fn credits_dash_str(credits: u64) -> String {
+    format!("{:.4} DASH", credits as f64 / CREDITS_PER_DASH)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct KeepFundedIdentity {
+    /// Balance in credits below which the identity is topped up
+    pub threshold_credits: u64,
+    /// Top up amount in duffs
+    pub top_up_duffs: u64,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub(crate) struct KeepFundedSettings {
+    #[serde(default)]
+    enabled: bool,
+    /// Watched identities by base58 ID
+    #[serde(default)]
+    watched: BTreeMap<String, KeepFundedIdentity>,
+    /// Maximum amount of duffs to spend on top ups per day, zero means nothing
+    /// is spent
+    #[serde(default)]
+    daily_cap_duffs: u64,
+    /// Day number (since Unix epoch) the spent amount refers to
+    day: u64,
+    /// Duffs spent on that day
+    duffs: u64,
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct KeepFunded {
+    settings: JsonStore<KeepFundedSettings>,
+    activity_log: VecDeque<String>,
+}
+
+impl KeepFunded {
+    /// Mode as saved in the file, disabled if there's none yet.
+    pub(crate) fn load(path: PathBuf) -> Self {
+        KeepFunded {
+            settings: JsonStore::load(path, "keep funded settings"),
+            ..Default::default()
+        }
+    }
+
+    pub(crate) fn enabled(&self) -> bool {
+        self.settings.enabled
+    }
+
+    fn log(&mut self, message: String) {
+        tracing::info!("keep funded: {}", message);
+        if self.activity_log.len() == ACTIVITY_LOG_SIZE {
+            self.activity_log.pop_front();
+        }
+        self.activity_log
+            .push_back(format!("[{}] {}", current_timestamp(), message));
+    }
+
+    /// Amount of duffs that can still be spent today.
+    fn remaining_today(&mut self) -> u64 {
+        let today = current_timestamp() / SECONDS_PER_DAY;
+        if self.settings.day != today {
+            self.settings.day = today;
+            self.settings.duffs = 0;
+            self.settings.save_or_log();
+        }
+        self.settings
+            .daily_cap_duffs
+            .saturating_sub(self.settings.duffs)
+    }
+
+    fn spend(&mut self, duffs: u64) {
+        self.settings.duffs += duffs;
+        self.settings.save_or_log();
+    }
+
+    pub(crate) fn display(&self) -> String {
+        let mut lines = vec![
+            format!(
+                "Keep funded mode: {}",
+                if self.settings.enabled {
+                    "enabled"
+                } else {
+                    "disabled"
+                }
+            ),
+            format!(
+                "Daily cap: {:.4} DASH, spent today: {:.4} DASH",
+                self.settings.daily_cap_duffs as f64 / DUFFS_PER_DASH,
+                self.settings.duffs as f64 / DUFFS_PER_DASH
+            ),
+            String::new(),
+            "Watched identities:".to_owned(),
+        ];
+
+        if self.settings.watched.is_empty() {
+            lines.push("    none".to_owned());
+        }
+        for (identity_id, entry) in self.settings.watched.iter() {
+            lines.push(format!(
+                "    {}: below {} top up {:.4} DASH",
+                identity_id,
+                credits_dash_str(entry.threshold_credits),
+                entry.top_up_duffs as f64 / DUFFS_PER_DASH
+            ));
+        }
+
+        lines.push(String::new());
+        lines.push("Activity:".to_owned());
+        if self.activity_log.is_empty() {
+            lines.push("    no activity yet".to_owned());
+        }
+        for entry in self.activity_log.iter().rev() {
+            lines.push(format!("    {}", entry));
+        }
+
+        lines.join("\n")
+    }
+}
+
+/// Failed top up from the wallet.
+pub(super) struct TopUpError {
+    message: String,
+    /// Whether the asset lock transaction may have been broadcast, spending
+    /// its amount whatever happens next
+    broadcast: bool,
+}
+
+impl TopUpError {
+    fn before_broadcast(message: impl Into<String>) -> Self {
+        TopUpError {
+            message: message.into(),
+            broadcast: false,
+        }
+    }
+
+    fn after_broadcast(message: String) -> Self {
+        TopUpError {
+            message,
+            broadcast: true,
+        }
+    }
+}
+
+impl From<TopUpError> for String {
+    fn from(error: TopUpError) -> String {
+        error.message
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum KeepFundedTask {
+    SetEnabled(bool),
+    /// Identity base58 id, threshold and top up amount in Dash
+    Watch(String, f64, f64),
+    Unwatch(String),
+    /// Daily cap in Dash
+    SetDailyCap(f64),
+    /// Periodic balances check, issued by the application loop in background,
+    /// so it reports with [BackendEvent::AppStateUpdated] instead of task
+    /// completion
+    Check,
+}
+
+impl AppState {
+    pub(crate) async fn run_keep_funded_task(
+        &self,
+        sdk: &Sdk,
+        task: KeepFundedTask,
+    ) -> BackendEvent {
+        let result = match &task {
+            KeepFundedTask::SetEnabled(enabled) => {
+                let mut keep_funded = self.keep_funded.lock().await;
+                keep_funded.settings.enabled = *enabled;
+                keep_funded.settings.save_or_log();
+                keep_funded.log(format!(
+                    "mode {}",
+                    if *enabled { "enabled" } else { "disabled" }
+                ));
+                Ok(())
+            }
+            KeepFundedTask::Watch(identity_id, threshold, top_up) => {
+                match Identifier::from_string(identity_id, Encoding::Base58) {
+                    Ok(id) if *threshold >= 0.0 && *top_up > 0.0 => {
+                        let mut keep_funded = self.keep_funded.lock().await;
+                        keep_funded.settings.watched.insert(
+                            id.to_string(Encoding::Base58),
+                            KeepFundedIdentity {
+                                threshold_credits: (threshold * CREDITS_PER_DASH) as u64,
+                                top_up_duffs: (top_up * DUFFS_PER_DASH) as u64,
+                            },
+                        );
+                        keep_funded.log(format!("watching {}", identity_id));
+                        keep_funded.settings.save()
+                    }
+                    Ok(_) => Err("Threshold and top up amount must be positive".to_owned()),
+                    Err(e) => Err(format!("Invalid identity id: {}", e)),
+                }
+            }
+            KeepFundedTask::Unwatch(identity_id) => {
+                match Identifier::from_string(identity_id, Encoding::Base58) {
+                    Ok(id) => {
+                        let mut keep_funded = self.keep_funded.lock().await;
+                        if keep_funded
+                            .settings
+                            .watched
+                            .remove(&id.to_string(Encoding::Base58))
+                            .is_some()
+                        {
+                            keep_funded.log(format!("stopped watching {}", identity_id));
+                            keep_funded.settings.save()
+                        } else {
+                            Err(format!("Identity {} is not watched", identity_id))
+                        }
+                    }
+                    Err(e) => Err(format!("Invalid identity id: {}", e)),
+                }
+            }
+            KeepFundedTask::SetDailyCap(cap) => {
+                let mut keep_funded = self.keep_funded.lock().await;
+                keep_funded.settings.daily_cap_duffs = (cap.max(0.0) * DUFFS_PER_DASH) as u64;
+                keep_funded.log(format!("daily cap set to {:.4} DASH", cap.max(0.0)));
+                keep_funded.settings.save()
+            }
+            KeepFundedTask::Check => {
+                if !self.keep_funded.lock().await.enabled() {
+                    return BackendEvent::None;
+                }
+                self.check_keep_funded(sdk).await;
+                return BackendEvent::AppStateUpdated(AppStateUpdate::KeepFunded(
+                    self.keep_funded.lock().await,
+                ));
+            }
+        };
+
+        let display = self.keep_funded.lock().await.display();
+        let execution_result = result.map(|_| display.into());
+
+        BackendEvent::TaskCompleted {
+            task: Task::KeepFunded(task),
+            execution_result,
+        }
+    }
+
+    /// Tops up every watched identity below its threshold, as long as the
+    /// daily cap allows it. Outcomes are written into the activity log.
+    async fn check_keep_funded(&self, sdk: &Sdk) {
+        let watched = self.keep_funded.lock().await.settings.watched.clone();
+
+        for (id_b58, entry) in watched {
+            // Each identity is a step of the check, user tasks go first
+            lanes::background_turn().await;
+            let Ok(identity_id) = Identifier::from_string(&id_b58, Encoding::Base58) else {
+                self.keep_funded
+                    .lock()
+                    .await
+                    .log(format!("invalid identity id {}", id_b58));
+                continue;
+            };
+
+            let identity = match Identity::fetch(sdk, identity_id).await {
+                Ok(Some(identity)) => identity,
+                Ok(None) => {
+                    self.keep_funded
+                        .lock()
+                        .await
+                        .log(format!("{} not found", id_b58));
+                    continue;
+                }
+                Err(e) => {
+                    self.keep_funded
+                        .lock()
+                        .await
+                        .log(format!("unable to fetch {}: {}", id_b58, e));
+                    continue;
+                }
+            };
+
+            if identity.balance() >= entry.threshold_credits {
+                continue;
+            }
+
+            {
+                let mut keep_funded = self.keep_funded.lock().await;
+                if keep_funded.remaining_today() < entry.top_up_duffs {
+                    keep_funded.log(format!(
+                        "{} is below the threshold ({}), daily cap reached",
+                        id_b58,
+                        credits_dash_str(identity.balance())
+                    ));
+                    continue;
+                }
+            }
+
+            match self
+                .top_up_from_wallet(sdk, &identity, entry.top_up_duffs)
+                .await
+            {
+                Ok(balance) => {
+                    let mut keep_funded = self.keep_funded.lock().await;
+                    keep_funded.spend(entry.top_up_duffs);
+                    keep_funded.log(format!(
+                        "topped up {} with {:.4} DASH, balance is {}",
+                        id_b58,
+                        entry.top_up_duffs as f64 / DUFFS_PER_DASH,
+                        credits_dash_str(balance)
+                    ));
+                }
+                Err(e) => {
+                    let mut keep_funded = self.keep_funded.lock().await;
+                    if e.broadcast {
+                        keep_funded.spend(entry.top_up_duffs);
+                    }
+                    keep_funded.log(format!("top up of {} failed: {}", id_b58, e.message));
+                }
+            }
+        }
+    }
+
+    /// Tops up the identity with an amount in duffs from the loaded wallet,
+    /// returning its new balance in credits.
+    pub(super) async fn top_up_from_wallet(
+        &self,
+        sdk: &Sdk,
+        identity: &Identity,
+        amount: u64,
+    ) -> Result<u64, TopUpError> {
+        self.reorg_watch
+            .lock()
+            .await
+            .ensure_stable()
+            .map_err(TopUpError::before_broadcast)?;
+
+        let fee_rate = self.fee_settings.lock().await.rate();
+
+        // The wallet is locked only to build the transaction, so wallet screens
+        // aren't blocked while the proof is awaited
+        let (asset_lock_transaction, asset_lock_proof_private_key, address) = {
+            let mut loaded_wallet = self.loaded_wallet.lock().await;
+            let Some(wallet) = loaded_wallet.as_mut() else {
+                return Err(TopUpError::before_broadcast("No wallet loaded"));
+            };
+            let (transaction, private_key) = wallet
+                .asset_lock_transaction(None, amount, fee_rate)
+                .map_err(|e| {
+                    TopUpError::before_broadcast(format!("Wallet transaction error: {}", e))
+                })?;
+            (transaction, private_key, wallet.receive_address())
+        };
+
+        let asset_lock_proof = Self::broadcast_and_retrieve_asset_lock(
+            sdk,
+            &asset_lock_transaction,
+            &address,
+            self.asset_lock_proof,
+        )
+        .await
+        .map_err(|e| {
+            TopUpError::after_broadcast(format!("Broadcasting transaction failed: {}", e))
+        })?;
+
+        let balance = identity
+            .top_up_identity(sdk, asset_lock_proof, &asset_lock_proof_private_key, None)
+            .await
+            .map_err(|e| TopUpError::after_broadcast(e.to_string()))?;
+
+        let mut loaded_identity = self.loaded_identity.lock().await;
+        if let Some(loaded_identity) = loaded_identity
+            .as_mut()
+            .filter(|loaded_identity| loaded_identity.id() == identity.id())
+        {
+            loaded_identity.set_balance(balance);
+        }
+
+        Ok(balance)
+    }
+}
@@ -0,0 +1,95 @@
+//! Known contracts and identities snapshot.
+//! The state file is only written on exit, so contracts and identities the
+//! user fetched are also written to a file of their own on every change. The
+//! snapshot is loaded over the state file ones at startup, keeping them after
+//! a crash or a killed process.
+
+use std::{collections::BTreeMap, fs, path::Path};
+
+use bincode::{Decode, Encode};
+use dpp::{
+    prelude::{DataContract, Identifier, Identity},
+    serialization::{
+        PlatformDeserializableWithPotentialValidationFromVersionedStructure,
+        PlatformSerializableWithPlatformVersion,
+    },
+    version::PlatformVersion,
+};
+
+use super::state::KnownContractsMap;
+
+#[derive(Encode, Decode)]
+struct KnownEntities {
+    /// Serialized contracts by their names
+    contracts: BTreeMap<String, Vec<u8>>,
+    identities: BTreeMap<Identifier, Identity>,
+}
+
+fn bincode_config() -> impl bincode::config::Config {
+    bincode::config::standard()
+        .with_big_endian()
+        .with_no_limit()
+}
+
+/// Writes the snapshot of known contracts and identities.
+pub(super) fn save(
+    path: &Path,
+    contracts: &KnownContractsMap,
+    identities: &BTreeMap<Identifier, Identity>,
+    platform_version: &PlatformVersion,
+) {
+    let contracts = contracts
+        .iter()
+        .map(|(name, contract)| {
+            contract
+                .serialize_to_bytes_with_platform_version(platform_version)
+                .map(|bytes| (name.clone(), bytes))
+        })
+        .collect::<Result<_, _>>();
+    let result = contracts
+        .map_err(|e| e.to_string())
+        .and_then(|contracts| {
+            bincode::encode_to_vec(
+                KnownEntities {
+                    contracts,
+                    identities: identities.clone(),
+                },
+                bincode_config(),
+            )
+            .map_err(|e| e.to_string())
+        })
+        .and_then(|bytes| fs::write(path, bytes).map_err(|e| e.to_string()));
+    if let Err(e) = result {
+        tracing::error!("Unable to save known contracts and identities: {}", e);
+    }
+}
+
+/// Reads the snapshot of known contracts and identities, `None` if there is
+/// none yet.
+pub(super) fn load(
+    path: &Path,
+    platform_version: &PlatformVersion,
+) -> Option<(KnownContractsMap, BTreeMap<Identifier, Identity>)> {
+    let bytes = fs::read(path).ok()?;
+    let result = bincode::decode_from_slice::<KnownEntities, _>(&bytes, bincode_config())
+        .map_err(|e| e.to_string())
+        .and_then(|(known_entities, _)| {
+            let contracts = known_entities
+                .contracts
+                .into_iter()
+                .map(|(name, bytes)| {
+                    DataContract::versioned_deserialize(&bytes, false, platform_version)
+                        .map(|contract| (name, contract))
+                        .map_err(|e| e.to_string())
+                })
+                .collect::<Result<KnownContractsMap, String>>()?;
+            Ok((contracts, known_entities.identities))
+        });
+    match result {
+        Ok(known_entities) => Some(known_entities),
+        Err(e) => {
+            tracing::error!("Unable to load known contracts and identities: {}", e);
+            None
+        }
+    }
+}
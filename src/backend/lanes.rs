@@ -0,0 +1,63 @@
+//! Priority lanes of the task executor.
+//! Tasks started from the UI run in the interactive lane, periodic checks,
+//! keepalives and prefetches in the background lane. Background tasks wait for
+//! the interactive lane to be idle before starting, and long ones between their
+//! steps, so they don't compete with the user for connections and app state
+//! locks. A background task isn't paused in the middle of a step as it may
+//! hold locks the interactive one waits on. Long interactive tasks such as
+//! strategy runs would starve the background lane, so background work goes on
+//! anyway after a while.
+
+use std::{
+    sync::atomic::{AtomicUsize, Ordering},
+    time::Duration,
+};
+
+use tokio::sync::Notify;
+
+/// Longest wait of a background task for the interactive lane.
+const MAX_BACKGROUND_WAIT: Duration = Duration::from_secs(30);
+
+static INTERACTIVE_TASKS: AtomicUsize = AtomicUsize::new(0);
+
+static INTERACTIVE_IDLE: Notify = Notify::const_new();
+
+/// Keeps the interactive lane busy until dropped.
+pub(crate) struct InteractiveTask(());
+
+impl Drop for InteractiveTask {
+    fn drop(&mut self) {
+        if INTERACTIVE_TASKS.fetch_sub(1, Ordering::SeqCst) == 1 {
+            INTERACTIVE_IDLE.notify_waiters();
+        }
+    }
+}
+
+pub(crate) fn enter_interactive() -> InteractiveTask {
+    INTERACTIVE_TASKS.fetch_add(1, Ordering::SeqCst);
+    InteractiveTask(())
+}
+
+async fn interactive_idle() {
+    loop {
+        // Registered before the check so a task finishing in between isn't
+        // missed
+        let idle = INTERACTIVE_IDLE.notified();
+        if INTERACTIVE_TASKS.load(Ordering::SeqCst) == 0 {
+            return;
+        }
+        idle.await;
+    }
+}
+
+/// Waits for the interactive lane to be idle, or for the longest wait.
+/// Background tasks call it before starting and between their steps, with no
+/// locks held.
+pub(crate) async fn background_turn() {
+    if tokio::time::timeout(MAX_BACKGROUND_WAIT, interactive_idle())
+        .await
+        .is_err()
+    {
+        tracing::debug!("Background task goes on alongside a long interactive task");
+    }
+}
@@ -0,0 +1,152 @@
+//! Network nodes backend module.
+//! Masternodes are listed from the deterministic masternode list of Core,
+//! evonodes being the ones also running Platform.
+
+pub(crate) mod rewards;
+
+use dash_sdk::Sdk;
+use serde_json::{json, Value as JsonValue};
+use tokio::sync::Mutex;
+
+use self::rewards::EvonodeRewardsLog;
+use super::{
+    core_rpc::CoreRpcClient, json_store::JsonStore, tenderdash_rpc::TenderdashRpcClient,
+    BackendEvent, CompletedTaskPayload, Task,
+};
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum NetworkTask {
+    FetchEvonodes,
+    /// Report rewards of the evonode given by ProTxHash, the configured one
+    /// if `None`
+    FetchEvonodeRewards(Option<String>),
+}
+
+/// Masternode types of evonodes, named `HighPerformance` by older Core
+/// versions.
+const EVONODE_TYPES: [&str; 2] = ["Evo", "HighPerformance"];
+
+/// Evonode of the deterministic masternode list.
+#[derive(Debug, Clone)]
+pub(crate) struct Evonode {
+    pub(crate) pro_tx_hash: String,
+    pub(crate) service: String,
+    pub(crate) payout_address: String,
+    pub(crate) pose_penalty: u64,
+    /// Height the node was PoSe banned at, if banned
+    pub(crate) pose_ban_height: Option<u64>,
+    pub(crate) platform_node_id: String,
+    pub(crate) platform_http_port: Option<u64>,
+    pub(crate) registered_height: u64,
+}
+
+impl Evonode {
+    fn from_protx(protx: &JsonValue) -> Option<Self> {
+        let masternode_type = protx.get("type")?.as_str()?;
+        if !EVONODE_TYPES.contains(&masternode_type) {
+            return None;
+        }
+        let state = protx.get("state")?;
+        let text = |key: &str| {
+            state
+                .get(key)
+                .and_then(JsonValue::as_str)
+                .unwrap_or_default()
+                .to_owned()
+        };
+        Some(Evonode {
+            pro_tx_hash: protx.get("proTxHash")?.as_str()?.to_owned(),
+            service: text("service"),
+            payout_address: text("payoutAddress"),
+            pose_penalty: state
+                .get("PoSePenalty")
+                .and_then(JsonValue::as_u64)
+                .unwrap_or_default(),
+            // Not banned nodes have a ban height of -1
+            pose_ban_height: state.get("PoSeBanHeight").and_then(JsonValue::as_u64),
+            platform_node_id: text("platformNodeID"),
+            platform_http_port: state.get("platformHTTPPort").and_then(JsonValue::as_u64),
+            registered_height: state
+                .get("registeredHeight")
+                .and_then(JsonValue::as_u64)
+                .unwrap_or_default(),
+        })
+    }
+
+    pub(crate) fn is_banned(&self) -> bool {
+        self.pose_ban_height.is_some()
+    }
+
+    /// All known details, one per line.
+    pub(crate) fn details(&self) -> String {
+        format!(
+            "ProTxHash: {}\nService: {}\nPayout address: {}\nPoSe penalty: {}\nPoSe banned: \
+             {}\nPlatform node ID: {}\nPlatform HTTP port: {}\nRegistered at height: {}",
+            self.pro_tx_hash,
+            self.service,
+            self.payout_address,
+            self.pose_penalty,
+            match self.pose_ban_height {
+                Some(height) => format!("yes, at height {}", height),
+                None => "no".to_owned(),
+            },
+            self.platform_node_id,
+            self.platform_http_port
+                .map(|port| port.to_string())
+                .unwrap_or_else(|| "-".to_owned()),
+            self.registered_height
+        )
+    }
+}
+
+/// Evonodes of the deterministic masternode list including banned ones,
+/// ordered by ProTxHash.
+async fn fetch_evonodes(core_rpc: &CoreRpcClient) -> Result<Vec<Evonode>, String> {
+    let masternodes = core_rpc
+        .call("protx", json!(["list", "registered", true]))
+        .await?;
+    let mut evonodes: Vec<Evonode> = masternodes
+        .as_array()
+        .ok_or_else(|| "Invalid Core RPC response: masternode list expected".to_owned())?
+        .iter()
+        .filter_map(Evonode::from_protx)
+        .collect();
+    evonodes.sort_by(|a, b| a.pro_tx_hash.cmp(&b.pro_tx_hash));
+    Ok(evonodes)
+}
+
+pub(super) async fn run_network_task<'s>(
+    sdk: &Sdk,
+    core_rpc: &CoreRpcClient,
+    tenderdash_rpc: Option<&TenderdashRpcClient>,
+    evonode_rewards: &Mutex<JsonStore<EvonodeRewardsLog>>,
+    configured_evonode: Option<&str>,
+    task: NetworkTask,
+) -> BackendEvent<'s> {
+    match task {
+        NetworkTask::FetchEvonodes => BackendEvent::TaskCompleted {
+            execution_result: fetch_evonodes(core_rpc)
+                .await
+                .map(CompletedTaskPayload::Evonodes),
+            task: Task::Network(task),
+        },
+        NetworkTask::FetchEvonodeRewards(ref pro_tx_hash) => {
+            let execution_result = match pro_tx_hash.as_deref().or(configured_evonode) {
+                Some(pro_tx_hash) => rewards::fetch_evonode_rewards(
+                    sdk,
+                    core_rpc,
+                    tenderdash_rpc,
+                    evonode_rewards,
+                    pro_tx_hash,
+                )
+                .await
+                .map(Into::into),
+                None => Err("No evonode configured".to_owned()),
+            };
+            BackendEvent::TaskCompleted {
+                task: Task::Network(task),
+                execution_result,
+            }
+        }
+    }
+}
@@ -0,0 +1,315 @@
+//! Rewards of an evonode.
+//!
+//! Blocks of each epoch are counted per proposer with Tenderdash RPC and
+//! compared with the evonode's fair share among active evonodes. Platform
+//! credits rewards to the identity of the evonode, so payouts are recorded as
+//! the balance of that identity first seen in each epoch, kept in a JSON file
+//! of the profile. A payout is the balance difference between two recorded
+//! epochs, withdrawals made in between hide it.
+
+use std::collections::BTreeMap;
+
+use chrono::{TimeZone, Utc};
+use dash_sdk::{
+    platform::{types::epoch::ExtendedEpochInfoEx, Fetch, FetchMany, LimitQuery},
+    Sdk,
+};
+use dpp::{
+    block::{
+        epoch::EpochIndex,
+        extended_epoch_info::{v0::ExtendedEpochInfoV0Getters, ExtendedEpochInfo},
+    },
+    identity::accessors::IdentityGettersV0,
+    prelude::{Identifier, Identity},
+};
+use serde::{Deserialize, Serialize};
+use tokio::sync::Mutex;
+
+use super::fetch_evonodes;
+use crate::backend::{
+    core_rpc::CoreRpcClient, json_store::JsonStore, progress, reports::current_timestamp,
+    tenderdash_rpc::TenderdashRpcClient,
+};
+
+/// Epochs shown in the report, the current one included.
+const REPORT_EPOCHS: u16 = 8;
+
+/// Blocks scanned for proposers at most per report, epochs beyond it are
+/// shown without proposed blocks.
+const MAX_SCANNED_BLOCKS: u64 = 20_000;
+
+const CREDITS_PER_DASH: f64 = 100_000_000_000.0;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct BalanceRecord {
+    epoch: EpochIndex,
+    balance: u64,
+    seen_at: u64,
+}
+
+/// Balances of evonode identities first seen in each epoch, by ProTxHash.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub(crate) struct EvonodeRewardsLog {
+    balances: BTreeMap<String, Vec<BalanceRecord>>,
+}
+
+impl EvonodeRewardsLog {
+    /// Balance increases between consecutive records, as the first and last
+    /// epochs of the span, credits and the time of the later record.
+    fn payouts(&self, pro_tx_hash: &str) -> Vec<(EpochIndex, EpochIndex, u64, u64)> {
+        self.balances
+            .get(pro_tx_hash)
+            .map(|records| {
+                records
+                    .windows(2)
+                    .filter(|pair| pair[1].balance > pair[0].balance)
+                    .map(|pair| {
+                        (
+                            pair[0].epoch,
+                            pair[1].epoch - 1,
+                            pair[1].balance - pair[0].balance,
+                            pair[1].seen_at,
+                        )
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
+
+impl JsonStore<EvonodeRewardsLog> {
+    /// Records the balance unless one was already seen in the epoch.
+    fn observe(&mut self, pro_tx_hash: &str, epoch: EpochIndex, balance: u64) {
+        let records = self.balances.entry(pro_tx_hash.to_owned()).or_default();
+        if records.last().is_some_and(|record| record.epoch >= epoch) {
+            return;
+        }
+        records.push(BalanceRecord {
+            epoch,
+            balance,
+            seen_at: current_timestamp(),
+        });
+        self.save_or_log();
+    }
+}
+
+struct EpochRewards {
+    index: EpochIndex,
+    blocks: u64,
+    /// `None` if proposers weren't counted
+    proposed: Option<u64>,
+}
+
+/// Evonode identity id, the same bytes as the ProTxHash.
+fn evonode_identity_id(pro_tx_hash: &str) -> Result<Identifier, String> {
+    hex::decode(pro_tx_hash)
+        .ok()
+        .and_then(|bytes| <[u8; 32]>::try_from(bytes).ok())
+        .map(Identifier::from)
+        .ok_or_else(|| format!("Invalid ProTxHash: {}", pro_tx_hash))
+}
+
+fn format_time(timestamp: u64) -> String {
+    Utc.timestamp_opt(timestamp as i64, 0)
+        .single()
+        .map(|time| time.format("%Y-%m-%d %H:%M:%S").to_string())
+        .unwrap_or_default()
+}
+
+/// Report of blocks proposed by the evonode over the latest epochs and of its
+/// recorded payouts.
+pub(super) async fn fetch_evonode_rewards(
+    sdk: &Sdk,
+    core_rpc: &CoreRpcClient,
+    tenderdash_rpc: Option<&TenderdashRpcClient>,
+    rewards_log: &Mutex<JsonStore<EvonodeRewardsLog>>,
+    pro_tx_hash: &str,
+) -> Result<String, String> {
+    let pro_tx_hash = pro_tx_hash.trim().to_lowercase();
+    let identity_id = evonode_identity_id(&pro_tx_hash)?;
+
+    let evonodes = fetch_evonodes(core_rpc).await?;
+    let evonode = evonodes
+        .iter()
+        .find(|evonode| evonode.pro_tx_hash == pro_tx_hash)
+        .ok_or_else(|| format!("Evonode not found in the masternode list: {}", pro_tx_hash))?;
+    let active_evonodes = evonodes
+        .iter()
+        .filter(|evonode| !evonode.is_banned())
+        .count();
+
+    let (current_epoch, metadata) = ExtendedEpochInfo::fetch_current_with_metadata(sdk)
+        .await
+        .map_err(|e| e.to_string())?;
+    let first_epoch = current_epoch.index().saturating_sub(REPORT_EPOCHS - 1);
+    let epoch_infos: Vec<ExtendedEpochInfo> = ExtendedEpochInfo::fetch_many(
+        sdk,
+        LimitQuery {
+            query: first_epoch,
+            limit: Some(REPORT_EPOCHS as u32),
+        },
+    )
+    .await
+    .map_err(|e| e.to_string())?
+    .into_values()
+    .flatten()
+    .collect();
+
+    // Latest epochs are scanned first, older ones get what is left of the
+    // budget
+    let mut epochs = Vec::with_capacity(epoch_infos.len());
+    let mut scanned_blocks = 0;
+    for (i, epoch_info) in epoch_infos.iter().enumerate().rev() {
+        let first_height = epoch_info.first_block_height();
+        let last_height = epoch_infos
+            .get(i + 1)
+            .map(|next| next.first_block_height().saturating_sub(1))
+            .unwrap_or(metadata.height);
+        let blocks = (last_height + 1).saturating_sub(first_height);
+
+        let proposed = match tenderdash_rpc {
+            Some(tenderdash_rpc) if scanned_blocks + blocks <= MAX_SCANNED_BLOCKS => {
+                progress::report(format!(
+                    "Counting proposed blocks of epoch {}",
+                    epoch_info.index()
+                ));
+                scanned_blocks += blocks;
+                match tenderdash_rpc
+                    .count_proposed_blocks(first_height, last_height, &pro_tx_hash)
+                    .await
+                {
+                    Ok(proposed) => Some(proposed),
+                    Err(e) => {
+                        tracing::warn!(
+                            "unable to count proposed blocks of epoch {}: {}",
+                            epoch_info.index(),
+                            e
+                        );
+                        None
+                    }
+                }
+            }
+            _ => None,
+        };
+        epochs.push(EpochRewards {
+            index: epoch_info.index(),
+            blocks,
+            proposed,
+        });
+    }
+    progress::clear();
+    epochs.reverse();
+
+    let balance = Identity::fetch(sdk, identity_id)
+        .await
+        .map_err(|e| e.to_string())?
+        .map(|identity| identity.balance());
+    let payouts = {
+        let mut rewards_log = rewards_log.lock().await;
+        if let Some(balance) = balance {
+            rewards_log.observe(&pro_tx_hash, current_epoch.index(), balance);
+        }
+        rewards_log.payouts(&pro_tx_hash)
+    };
+
+    // Credits per proposed block are estimated from payouts recorded over the
+    // completed epochs of the report
+    let completed = &epochs[..epochs.len().saturating_sub(1)];
+    let proposed_blocks: u64 = completed.iter().filter_map(|epoch| epoch.proposed).sum();
+    let paid_credits: u64 = payouts
+        .iter()
+        .filter(|(from, ..)| *from >= first_epoch)
+        .map(|(_, _, credits, _)| credits)
+        .sum();
+    let credits_per_block = (proposed_blocks > 0 && paid_credits > 0)
+        .then(|| paid_credits as f64 / proposed_blocks as f64);
+
+    let mut lines = vec![
+        format!("Evonode {}", pro_tx_hash),
+        format!(
+            "Service: {}{}",
+            evonode.service,
+            if evonode.is_banned() {
+                ", PoSe banned"
+            } else {
+                ""
+            }
+        ),
+        format!("Active evonodes: {}", active_evonodes),
+        match balance {
+            Some(balance) => format!(
+                "Identity balance: {:.4} DASH ({} credits)",
+                balance as f64 / CREDITS_PER_DASH,
+                balance
+            ),
+            None => "Identity balance: no identity yet, created with the first payout".to_owned(),
+        },
+        String::new(),
+        format!(
+            "{:>7} {:>8} {:>9} {:>16} {:>17}",
+            "Epoch", "Blocks", "Proposed", "Expected blocks", "Expected credits"
+        ),
+    ];
+    for epoch in &epochs {
+        let expected_blocks = epoch.blocks as f64 / active_evonodes.max(1) as f64;
+        let current = epoch.index == current_epoch.index();
+        lines.push(format!(
+            "{:>6}{} {:>8} {:>9} {:>16.1} {:>17}",
+            epoch.index,
+            if current { "*" } else { " " },
+            epoch.blocks,
+            epoch
+                .proposed
+                .map(|proposed| proposed.to_string())
+                .unwrap_or_else(|| "-".to_owned()),
+            expected_blocks,
+            credits_per_block
+                .map(|rate| format!("{:.0}", expected_blocks * rate))
+                .unwrap_or_else(|| "-".to_owned()),
+        ));
+    }
+    lines.push("* current epoch, in progress".to_owned());
+    if tenderdash_rpc.is_none() {
+        lines
+            .push("Proposed blocks are counted once a Tenderdash RPC URL is configured".to_owned());
+    }
+    match credits_per_block {
+        Some(rate) => {
+            lines.push(format!("Credits per proposed block: {:.0}", rate));
+            if let Some(proposed) = epochs.last().and_then(|epoch| epoch.proposed) {
+                lines.push(format!(
+                    "Expected payout of the current epoch so far: {:.0} credits",
+                    proposed as f64 * rate
+                ));
+            }
+        }
+        None => lines.push(
+            "Expected credits are estimated once payouts and proposed blocks are recorded"
+                .to_owned(),
+        ),
+    }
+
+    lines.push(String::new());
+    lines.push("Payouts recorded, latest first:".to_owned());
+    if payouts.is_empty() {
+        lines.push(
+            "  none yet, the identity balance is recorded each epoch the report is fetched"
+                .to_owned(),
+        );
+    }
+    lines.extend(payouts.iter().rev().map(|(from, to, credits, seen_at)| {
+        let epochs = if from == to {
+            format!("epoch {}", from)
+        } else {
+            format!("epochs {}-{}", from, to)
+        };
+        format!(
+            "  {} {}: +{} credits",
+            format_time(*seen_at),
+            epochs,
+            credits
+        )
+    }));
+
+    Ok(lines.join("\n"))
+}
@@ -0,0 +1,154 @@
+//! Changelog of platform network parameters.
+//! Parameters are observed whenever the current epoch info is fetched, a
+//! value different from the last observed one is logged with the time it was
+//! first seen. The changelog is kept in a JSON file of the profile.
+//! Validator set size isn't tracked as platform queries don't expose it.
+
+use std::{
+    collections::BTreeMap,
+    fmt::{self, Display},
+};
+
+use chrono::{TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::{json_store::JsonStore, reports::current_timestamp};
+
+/// Epoch length varies with block times, so smaller differences are not
+/// considered a change.
+const EPOCH_LENGTH_TOLERANCE_PERCENT: u64 = 10;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub(crate) enum NetworkParameter {
+    ProtocolVersion,
+    FeeMultiplier,
+    /// Rounded to minutes
+    EpochLength,
+}
+
+impl NetworkParameter {
+    fn changed(&self, old: &str, new: &str) -> bool {
+        match self {
+            NetworkParameter::EpochLength => match (old.parse::<u64>(), new.parse::<u64>()) {
+                (Ok(old), Ok(new)) => {
+                    old.abs_diff(new) * 100 > old * EPOCH_LENGTH_TOLERANCE_PERCENT
+                }
+                _ => old != new,
+            },
+            _ => old != new,
+        }
+    }
+}
+
+impl Display for NetworkParameter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NetworkParameter::ProtocolVersion => write!(f, "protocol version"),
+            NetworkParameter::FeeMultiplier => write!(f, "fee multiplier"),
+            NetworkParameter::EpochLength => write!(f, "epoch length (minutes)"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ParameterChange {
+    parameter: NetworkParameter,
+    /// `None` for the first observation
+    old_value: Option<String>,
+    new_value: String,
+    /// Platform height of the observation
+    height: u64,
+    seen_at: u64,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub(crate) struct NetworkChangelog {
+    current: BTreeMap<NetworkParameter, String>,
+    changes: Vec<ParameterChange>,
+    /// Index and start time in milliseconds of the latest observed epoch, to
+    /// measure the epoch length once the next one begins
+    last_epoch: Option<(u16, u64)>,
+}
+
+impl JsonStore<NetworkChangelog> {
+    /// Records parameters of a current epoch info response.
+    pub(crate) fn observe_current_epoch(
+        &mut self,
+        height: u64,
+        protocol_version: u32,
+        fee_multiplier: f64,
+        epoch_index: u16,
+        epoch_start_ms: u64,
+    ) {
+        self.observe(
+            NetworkParameter::ProtocolVersion,
+            protocol_version.to_string(),
+            height,
+        );
+        self.observe(
+            NetworkParameter::FeeMultiplier,
+            fee_multiplier.to_string(),
+            height,
+        );
+
+        let last_epoch = self.last_epoch.replace((epoch_index, epoch_start_ms));
+        if let Some((last_index, last_start_ms)) = last_epoch {
+            if last_index.checked_add(1) == Some(epoch_index) {
+                let minutes = epoch_start_ms.saturating_sub(last_start_ms) / 60_000;
+                self.observe(NetworkParameter::EpochLength, minutes.to_string(), height);
+            }
+        }
+        self.save_or_log();
+    }
+}
+
+impl NetworkChangelog {
+    fn observe(&mut self, parameter: NetworkParameter, value: String, height: u64) {
+        let old_value = self.current.get(&parameter);
+        if old_value.is_some_and(|old| !parameter.changed(old, &value)) {
+            return;
+        }
+        self.changes.push(ParameterChange {
+            parameter,
+            old_value: old_value.cloned(),
+            new_value: value.clone(),
+            height,
+            seen_at: current_timestamp(),
+        });
+        self.current.insert(parameter, value);
+    }
+
+    pub(crate) fn display(&self) -> String {
+        if self.changes.is_empty() {
+            return "No network parameters observed yet, fetch current epoch info to start \
+                    tracking"
+                .to_owned();
+        }
+        let mut lines = vec!["Current:".to_owned()];
+        lines.extend(
+            self.current
+                .iter()
+                .map(|(parameter, value)| format!("  {}: {}", parameter, value)),
+        );
+        lines.push(String::new());
+        lines.push("Changes, latest first:".to_owned());
+        lines.extend(self.changes.iter().rev().map(|change| {
+            let seen_at = Utc
+                .timestamp_opt(change.seen_at as i64, 0)
+                .single()
+                .map(|time| time.format("%Y-%m-%d %H:%M:%S").to_string())
+                .unwrap_or_default();
+            match &change.old_value {
+                Some(old_value) => format!(
+                    "  {} at height {}: {} {} -> {}",
+                    seen_at, change.height, change.parameter, old_value, change.new_value
+                ),
+                None => format!(
+                    "  {} at height {}: {} first seen as {}",
+                    seen_at, change.height, change.parameter, change.new_value
+                ),
+            }
+        }));
+        lines.join("\n")
+    }
+}
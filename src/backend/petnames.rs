@@ -0,0 +1,101 @@
+//! Local petnames of identities.
+//! A petname is a name given to an identity ID on this machine only, it's
+//! never published. Petnames are kept in a JSON file of the profile, and a
+//! copy is shared with the UI so every identity ID displayed is shown as its
+//! petname with the short ID instead.
+
+use std::{collections::BTreeMap, path::PathBuf, sync::RwLock};
+
+use dpp::{platform_value::string_encoding::Encoding, prelude::Identifier};
+use serde::{Deserialize, Serialize};
+
+use super::json_store::JsonStore;
+
+/// Characters of the ID shown next to a petname.
+const SHORT_ID_LENGTH: usize = 8;
+
+/// Petnames by Base58 encoded identity ID, as of the last change.
+static DISPLAYED: RwLock<BTreeMap<String, String>> = RwLock::new(BTreeMap::new());
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub(crate) struct Petnames {
+    /// Petnames by Base58 encoded identity ID
+    petnames: BTreeMap<String, String>,
+}
+
+impl Petnames {
+    /// Petnames of the file, shared with the UI right away.
+    pub(crate) fn load(path: PathBuf) -> JsonStore<Self> {
+        let petnames: JsonStore<Self> = JsonStore::load(path, "petnames");
+        petnames.publish();
+        petnames
+    }
+
+    fn publish(&self) {
+        *DISPLAYED.write().expect("petnames lock poisoned") = self.petnames.clone();
+    }
+
+    pub(crate) fn display(&self) -> String {
+        if self.petnames.is_empty() {
+            return "No petnames yet".to_owned();
+        }
+        self.petnames
+            .iter()
+            .map(|(identity_id, petname)| format!("{}: {}", petname, identity_id))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+impl JsonStore<Petnames> {
+    /// Gives a petname to the identity, an empty one removes it.
+    pub(crate) fn set(&mut self, identity_id: &str, petname: &str) -> Result<String, String> {
+        let identity_id = Identifier::from_string(identity_id.trim(), Encoding::Base58)
+            .map_err(|e| format!("Invalid identity ID: {}", e))?
+            .to_string(Encoding::Base58);
+        let petname = petname.trim();
+
+        let message = if petname.is_empty() {
+            if self.petnames.remove(&identity_id).is_none() {
+                return Err(format!("Identity {} has no petname", identity_id));
+            }
+            format!("Removed petname of {}", identity_id)
+        } else {
+            if let Some((other_id, _)) = self
+                .petnames
+                .iter()
+                .find(|(id, name)| name.as_str() == petname && **id != identity_id)
+            {
+                return Err(format!(
+                    "Petname {} is already given to {}",
+                    petname, other_id
+                ));
+            }
+            self.petnames
+                .insert(identity_id.clone(), petname.to_owned());
+            format!("Identity {} is now known as {}", identity_id, petname)
+        };
+
+        self.save()?;
+        self.publish();
+        Ok(message)
+    }
+}
+
+/// Replaces identity IDs having a petname in the displayed text with the
+/// petname and the short ID.
+pub(crate) fn annotate(text: &str) -> String {
+    let petnames = DISPLAYED.read().expect("petnames lock poisoned");
+    petnames
+        .iter()
+        .fold(text.to_owned(), |text, (identity_id, petname)| {
+            if text.contains(identity_id.as_str()) {
+                text.replace(
+                    identity_id.as_str(),
+                    &format!("{} ({}…)", petname, &identity_id[..SHORT_ID_LENGTH]),
+                )
+            } else {
+                text
+            }
+        })
+}
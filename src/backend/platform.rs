@@ -0,0 +1,187 @@
+//! Access to Platform data behind a trait, so the UI can be powered either by
+//! the network through the SDK or by an in-memory simulator.
+//! The simulator serves contracts and identities of a known entities snapshot,
+//! like the one written by a previous session, and random documents
+//! generated deterministically for each document type.
+
+use std::{collections::BTreeMap, path::Path};
+
+use dapi_grpc::platform::v0::get_documents_request::get_documents_request_v0::Start;
+use dash_sdk::{
+    platform::{DocumentQuery, Fetch, FetchMany},
+    Sdk,
+};
+use dpp::{
+    data_contract::{
+        accessors::v0::DataContractV0Getters,
+        document_type::random_document::{
+            CreateRandomDocument, DocumentFieldFillSize, DocumentFieldFillType,
+        },
+    },
+    document::{Document, DocumentV0Getters},
+    prelude::{DataContract, Identifier, Identity},
+    version::PlatformVersion,
+};
+use rand::{prelude::StdRng, Rng, SeedableRng};
+
+use super::known_entities;
+
+/// Documents the simulator holds for each document type.
+const SIMULATED_DOCUMENTS_PER_TYPE: usize = 250;
+
+/// Documents returned by the simulator when the query has no limit, the
+/// Platform default.
+const SIMULATED_DEFAULT_LIMIT: usize = 100;
+
+/// Creation time of simulated documents, fixed to keep them the same between
+/// runs.
+const SIMULATED_DOCUMENTS_TIME_MS: u64 = 1_700_000_000_000;
+
+/// Reads of Platform data the UI relies on.
+pub(crate) trait Platform {
+    async fn fetch_identity(&self, id: Identifier) -> Result<Option<Identity>, String>;
+
+    async fn fetch_data_contract(&self, id: Identifier) -> Result<Option<DataContract>, String>;
+
+    async fn fetch_documents(
+        &self,
+        query: DocumentQuery,
+    ) -> Result<BTreeMap<Identifier, Option<Document>>, String>;
+}
+
+impl Platform for Sdk {
+    async fn fetch_identity(&self, id: Identifier) -> Result<Option<Identity>, String> {
+        Identity::fetch(self, id).await.map_err(|e| e.to_string())
+    }
+
+    async fn fetch_data_contract(&self, id: Identifier) -> Result<Option<DataContract>, String> {
+        DataContract::fetch(self, id)
+            .await
+            .map_err(|e| e.to_string())
+    }
+
+    async fn fetch_documents(
+        &self,
+        query: DocumentQuery,
+    ) -> Result<BTreeMap<Identifier, Option<Document>>, String> {
+        Document::fetch_many(self, query)
+            .await
+            .map_err(|e| e.to_string())
+    }
+}
+
+/// In-memory platform for demos, offline development and deterministic UI
+/// tests.
+pub(crate) struct SimulatedPlatform {
+    contracts: BTreeMap<Identifier, DataContract>,
+    identities: BTreeMap<Identifier, Identity>,
+}
+
+impl SimulatedPlatform {
+    /// Loads contracts and identities of the snapshot, the simulated platform
+    /// is empty if it can't be read.
+    pub(crate) fn load(path: &Path) -> Self {
+        let (contracts, identities) = known_entities::load(path, PlatformVersion::latest())
+            .unwrap_or_else(|| {
+                tracing::warn!(
+                    "Simulated platform fixtures {} unavailable, starting empty",
+                    path.display()
+                );
+                Default::default()
+            });
+        tracing::info!(
+            "Simulated platform with {} contracts and {} identities",
+            contracts.len(),
+            identities.len()
+        );
+        SimulatedPlatform {
+            contracts: contracts
+                .into_values()
+                .map(|contract| (contract.id(), contract))
+                .collect(),
+            identities,
+        }
+    }
+
+    /// All simulated documents of the query's document type, the same ones
+    /// for every query as the generator is seeded from the contract ID and
+    /// the type name.
+    fn documents(&self, query: &DocumentQuery) -> Result<Vec<Document>, String> {
+        let document_type = query
+            .data_contract
+            .document_type_for_name(&query.document_type_name)
+            .map_err(|e| e.to_string())?
+            .to_owned_document_type();
+        let seed = query.document_type_name.bytes().fold(
+            query.data_contract.id().to_buffer(),
+            |mut seed, byte| {
+                seed.rotate_left(1);
+                seed[0] ^= byte;
+                seed
+            },
+        );
+        let mut rng = StdRng::from_seed(seed);
+        (0..SIMULATED_DOCUMENTS_PER_TYPE)
+            .map(|_| {
+                let entropy: [u8; 32] = rng.gen();
+                document_type
+                    .random_document_with_params(
+                        query.data_contract.owner_id(),
+                        entropy.into(),
+                        Some(SIMULATED_DOCUMENTS_TIME_MS),
+                        None,
+                        None,
+                        DocumentFieldFillType::FillIfNotRequired,
+                        DocumentFieldFillSize::AnyDocumentFillSize,
+                        &mut rng,
+                        PlatformVersion::latest(),
+                    )
+                    .map_err(|e| e.to_string())
+            })
+            .collect()
+    }
+}
+
+impl Platform for SimulatedPlatform {
+    async fn fetch_identity(&self, id: Identifier) -> Result<Option<Identity>, String> {
+        Ok(self.identities.get(&id).cloned())
+    }
+
+    async fn fetch_data_contract(&self, id: Identifier) -> Result<Option<DataContract>, String> {
+        Ok(self.contracts.get(&id).cloned())
+    }
+
+    /// Documents are returned ordered by ID starting from the query's start
+    /// and up to its limit, conditions and ordering clauses aren't applied.
+    async fn fetch_documents(
+        &self,
+        query: DocumentQuery,
+    ) -> Result<BTreeMap<Identifier, Option<Document>>, String> {
+        let documents: BTreeMap<Identifier, Document> = self
+            .documents(&query)?
+            .into_iter()
+            .map(|document| (document.id(), document))
+            .collect();
+        let start = match &query.start {
+            Some(Start::StartAt(bytes) | Start::StartAfter(bytes)) => Some((
+                Identifier::from_bytes(bytes).map_err(|e| e.to_string())?,
+                matches!(query.start, Some(Start::StartAt(_))),
+            )),
+            None => None,
+        };
+        let limit = match query.limit {
+            0 => SIMULATED_DEFAULT_LIMIT,
+            limit => limit as usize,
+        };
+        Ok(documents
+            .into_iter()
+            .filter(|(id, _)| match start {
+                Some((start_id, true)) => *id >= start_id,
+                Some((start_id, false)) => *id > start_id,
+                None => true,
+            })
+            .take(limit)
+            .map(|(id, document)| (id, Some(document)))
+            .collect())
+    }
+}
@@ -1,3 +1,6 @@
+mod epoch_export;
+mod upgrade_status;
+
 use chrono::{prelude::*, LocalResult};
 use chrono_humanize::{Accuracy, HumanTime, Tense};
 use dapi_grpc::platform::v0::ResponseMetadata;
@@ -12,8 +15,11 @@ use dpp::{
     },
     version::ProtocolVersionVoteCount,
 };
+use tokio::sync::Mutex;
 
-use crate::backend::{as_json_string, BackendEvent, Task};
+use crate::backend::{
+    as_json_string, json_store::JsonStore, network_changelog::NetworkChangelog, BackendEvent, Task,
+};
 
 #[derive(Debug, Clone, PartialEq)]
 pub(crate) enum PlatformInfoTask {
@@ -21,6 +27,13 @@ pub(crate) enum PlatformInfoTask {
     FetchCurrentVersionVotingState,
     FetchSpecificEpochInfo(u16),
     FetchManyEpochInfo(u16, u32), // second is count
+    ShowNetworkChangelog,
+    /// Export statistics of epochs starting from the first one into a CSV
+    /// file, the second is count
+    ExportEpochs(u16, u32),
+    /// Fetch votes for the next protocol versions, both tallied by Platform
+    /// and signaled by each masternode
+    FetchProtocolUpgradeStatus,
 }
 
 fn format_extended_epoch_info(
@@ -78,16 +91,30 @@ fn format_extended_epoch_info(
     )
 }
 
-pub(super) async fn run_platform_task<'s>(sdk: &Sdk, task: PlatformInfoTask) -> BackendEvent<'s> {
+pub(super) async fn run_platform_task<'s>(
+    sdk: &Sdk,
+    network_changelog: &Mutex<JsonStore<NetworkChangelog>>,
+    task: PlatformInfoTask,
+) -> BackendEvent<'s> {
     match task {
         PlatformInfoTask::FetchCurrentEpochInfo => {
             match ExtendedEpochInfo::fetch_current_with_metadata(sdk).await {
-                Ok((epoch_info, metadata)) => BackendEvent::TaskCompleted {
-                    task: Task::PlatformInfo(task),
-                    execution_result: Ok(
-                        format_extended_epoch_info(epoch_info, metadata, true).into()
-                    ),
-                },
+                Ok((epoch_info, metadata)) => {
+                    network_changelog.lock().await.observe_current_epoch(
+                        metadata.height,
+                        metadata.protocol_version,
+                        epoch_info.fee_multiplier(),
+                        epoch_info.index(),
+                        epoch_info.first_block_time(),
+                    );
+                    BackendEvent::TaskCompleted {
+                        task: Task::PlatformInfo(task),
+                        execution_result: Ok(format_extended_epoch_info(
+                            epoch_info, metadata, true,
+                        )
+                        .into()),
+                    }
+                }
                 Err(e) => BackendEvent::TaskCompleted {
                     task: Task::PlatformInfo(task),
                     execution_result: Err(e.to_string()),
@@ -133,6 +160,37 @@ pub(super) async fn run_platform_task<'s>(sdk: &Sdk, task: PlatformInfoTask) ->
                 },
             }
         }
+        PlatformInfoTask::ExportEpochs(epoch_num, limit) => {
+            let query: LimitQuery<EpochIndex> = LimitQuery {
+                query: epoch_num,
+                limit: Some(limit),
+            };
+
+            let execution_result = match ExtendedEpochInfo::fetch_many(&sdk, query).await {
+                Ok(epoch_infos) => {
+                    let epochs: Vec<ExtendedEpochInfo> =
+                        epoch_infos.into_values().flatten().collect();
+                    epoch_export::export_epochs(&epochs).map(|path| {
+                        format!("Exported {} epochs into {}", epochs.len(), path.display()).into()
+                    })
+                }
+                Err(e) => Err(e.to_string()),
+            };
+            BackendEvent::TaskCompleted {
+                task: Task::PlatformInfo(task),
+                execution_result,
+            }
+        }
+        PlatformInfoTask::FetchProtocolUpgradeStatus => BackendEvent::TaskCompleted {
+            task: Task::PlatformInfo(task),
+            execution_result: upgrade_status::fetch_upgrade_status(sdk)
+                .await
+                .map(Into::into),
+        },
+        PlatformInfoTask::ShowNetworkChangelog => BackendEvent::TaskCompleted {
+            task: Task::PlatformInfo(task),
+            execution_result: Ok(network_changelog.lock().await.display().into()),
+        },
         PlatformInfoTask::FetchCurrentVersionVotingState => {
             match ProtocolVersionVoteCount::fetch_many(&sdk, ()).await {
                 Ok(votes) => {
@@ -0,0 +1,68 @@
+//! Export of epoch statistics for offline analysis.
+//!
+//! Rows are made of what epoch info queries provide: epoch boundaries and the
+//! fee multiplier. Block counts and durations are derived from the start of the
+//! next epoch, so the latest fetched epoch has them empty.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use chrono::{TimeZone, Utc};
+use dpp::block::extended_epoch_info::{v0::ExtendedEpochInfoV0Getters, ExtendedEpochInfo};
+
+use crate::backend::reports::current_timestamp;
+
+const EXPORTS_DIR: &str = "supporting_files/epoch_exports";
+
+const HEADER: &str = "epoch,start_time,first_block_height,first_core_block_height,blocks,\
+                      duration_secs,fee_multiplier";
+
+/// One row per epoch, epochs are expected in ascending order.
+fn epochs_csv(epochs: &[ExtendedEpochInfo]) -> String {
+    let mut lines = vec![HEADER.to_owned()];
+    for (index, epoch) in epochs.iter().enumerate() {
+        let next = epochs.get(index + 1);
+        let start_time = Utc
+            .timestamp_millis_opt(epoch.first_block_time() as i64)
+            .single()
+            .map(|time| time.to_rfc3339())
+            .unwrap_or_default();
+        let (blocks, duration_secs) = match next {
+            Some(next) => {
+                let blocks = next
+                    .first_block_height()
+                    .saturating_sub(epoch.first_block_height());
+                let duration_ms = next
+                    .first_block_time()
+                    .saturating_sub(epoch.first_block_time());
+                (blocks.to_string(), (duration_ms / 1000).to_string())
+            }
+            None => Default::default(),
+        };
+        lines.push(format!(
+            "{},{},{},{},{},{},{}",
+            epoch.index(),
+            start_time,
+            epoch.first_block_height(),
+            epoch.first_core_block_height(),
+            blocks,
+            duration_secs,
+            epoch.fee_multiplier()
+        ));
+    }
+    lines.push(String::new());
+    lines.join("\n")
+}
+
+/// Writes epochs into a new CSV file of the exports directory, returning its
+/// path.
+pub(super) fn export_epochs(epochs: &[ExtendedEpochInfo]) -> Result<PathBuf, String> {
+    fs::create_dir_all(EXPORTS_DIR)
+        .map_err(|e| format!("Failed to create exports directory: {}", e))?;
+    let path = Path::new(EXPORTS_DIR).join(format!("epochs_{}.csv", current_timestamp()));
+    fs::write(&path, epochs_csv(epochs))
+        .map_err(|e| format!("Failed to write export file: {}", e))?;
+    Ok(path)
+}
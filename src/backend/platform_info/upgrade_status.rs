@@ -0,0 +1,146 @@
+//! Protocol version upgrade progress.
+//!
+//! Two views of the same vote: the upgrade state counts votes per protocol
+//! version as Platform tallies them for the next epoch, and the vote status
+//! lists the version each masternode signaled, paged through by ProTxHash.
+
+use std::collections::BTreeMap;
+
+use dash_sdk::{
+    platform::{types::epoch::ExtendedEpochInfoEx, FetchMany, LimitQuery},
+    query_types::MasternodeProtocolVote,
+    Sdk,
+};
+use dpp::{
+    block::extended_epoch_info::ExtendedEpochInfo, dashcore::ProTxHash,
+    util::deserializer::ProtocolVersion, version::ProtocolVersionVoteCount,
+};
+
+/// Masternode votes fetched per request.
+const VOTES_PAGE_SIZE: u32 = 100;
+/// Stops paging through votes past that many pages.
+const MAX_VOTES_PAGES: usize = 100;
+/// Share of signaling masternodes a version needs to be activated.
+const UPGRADE_THRESHOLD_PERCENT: f64 = 75.0;
+/// Characters of the longest bar.
+const BAR_WIDTH: usize = 30;
+
+/// Version voted by each masternode, paged through from the lowest
+/// ProTxHash.
+async fn fetch_masternode_votes(sdk: &Sdk) -> Result<BTreeMap<ProTxHash, ProtocolVersion>, String> {
+    let mut votes = BTreeMap::new();
+    let mut start = None;
+    for _ in 0..MAX_VOTES_PAGES {
+        let page = MasternodeProtocolVote::fetch_many(
+            sdk,
+            LimitQuery {
+                query: start,
+                limit: Some(VOTES_PAGE_SIZE),
+            },
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+        let page_len = page.len();
+        let mut new_votes = false;
+        for (pro_tx_hash, vote) in page {
+            if let Some(vote) = vote {
+                new_votes |= votes.insert(pro_tx_hash, vote.voted_version).is_none();
+            }
+            start = Some(pro_tx_hash);
+        }
+        // Pages may start with the last masternode of the previous one
+        if page_len < VOTES_PAGE_SIZE as usize || !new_votes {
+            break;
+        }
+    }
+    Ok(votes)
+}
+
+fn bar(count: u64, max: u64) -> String {
+    let length = (count * BAR_WIDTH as u64).div_ceil(max.max(1)) as usize;
+    format!("{:<width$}", "█".repeat(length), width = BAR_WIDTH)
+}
+
+/// Rows of versions with their counts and share of the total.
+fn version_rows(counts: &BTreeMap<ProtocolVersion, u64>) -> Vec<String> {
+    let total: u64 = counts.values().sum();
+    let max = counts.values().max().copied().unwrap_or_default();
+    counts
+        .iter()
+        .map(|(version, count)| {
+            format!(
+                "Version {:<4} {} {} ({:.1}%)",
+                version,
+                bar(*count, max),
+                count,
+                *count as f64 * 100.0 / total.max(1) as f64
+            )
+        })
+        .collect()
+}
+
+/// Fetches the current protocol version, the upgrade state and masternode
+/// votes, and describes how close each version is to activation.
+pub(super) async fn fetch_upgrade_status(sdk: &Sdk) -> Result<String, String> {
+    let (_, metadata) = ExtendedEpochInfo::fetch_current_with_metadata(sdk)
+        .await
+        .map_err(|e| e.to_string())?;
+    let upgrade_state = ProtocolVersionVoteCount::fetch_many(sdk, ())
+        .await
+        .map_err(|e| e.to_string())?;
+    let masternode_votes = fetch_masternode_votes(sdk).await?;
+
+    let tallied: BTreeMap<ProtocolVersion, u64> = upgrade_state
+        .into_iter()
+        .filter_map(|(version, count)| Some((version, count?)))
+        .collect();
+    let mut signaled: BTreeMap<ProtocolVersion, u64> = BTreeMap::new();
+    for version in masternode_votes.values() {
+        *signaled.entry(*version).or_default() += 1;
+    }
+
+    let mut lines = vec![
+        format!(
+            "Current protocol version: {} (block {})",
+            metadata.protocol_version, metadata.height
+        ),
+        format!(
+            "A version is activated at the start of an epoch once {}% of masternodes signal it\n",
+            UPGRADE_THRESHOLD_PERCENT
+        ),
+        "Upgrade state, votes tallied for the next epoch:".to_owned(),
+    ];
+    if tallied.is_empty() {
+        lines.push("No votes".to_owned());
+    } else {
+        lines.extend(version_rows(&tallied));
+    }
+
+    lines.push(format!(
+        "\nMasternode signals, {} masternodes signaled:",
+        masternode_votes.len()
+    ));
+    if signaled.is_empty() {
+        lines.push("No signals".to_owned());
+    } else {
+        lines.extend(version_rows(&signaled));
+    }
+    let total = masternode_votes.len() as f64;
+    if let Some((version, count)) = signaled
+        .iter()
+        .filter(|(version, _)| **version > metadata.protocol_version)
+        .max_by_key(|(_, count)| **count)
+    {
+        let percent = *count as f64 * 100.0 / total.max(1.0);
+        lines.push(if percent >= UPGRADE_THRESHOLD_PERCENT {
+            format!("\nVersion {} reached the activation threshold", version)
+        } else {
+            format!(
+                "\nVersion {} needs {:.1}% more of the signals to be activated",
+                version,
+                UPGRADE_THRESHOLD_PERCENT - percent
+            )
+        });
+    }
+    Ok(lines.join("\n"))
+}
@@ -0,0 +1,22 @@
+//! Progress of long running tasks.
+//! A task waiting on the network for minutes reports what it's waiting for,
+//! the status bar shows it instead of the generic message while the UI is
+//! blocked.
+
+use std::sync::RwLock;
+
+static PROGRESS: RwLock<Option<String>> = RwLock::new(None);
+
+pub(crate) fn report(message: String) {
+    tracing::debug!("{}", message);
+    *PROGRESS.write().expect("progress lock poisoned") = Some(message);
+}
+
+pub(crate) fn clear() {
+    *PROGRESS.write().expect("progress lock poisoned") = None;
+}
+
+/// Latest progress message of the running task, if any.
+pub(crate) fn current() -> Option<String> {
+    PROGRESS.read().expect("progress lock poisoned").clone()
+}
@@ -0,0 +1,303 @@
+//! Fetches with proof details.
+//!
+//! The SDK verifies proofs of everything it fetches, GroveDB proofs and the
+//! quorum signatures over their root hashes, with drive-proof-verifier, but
+//! keeps the proofs to itself. A fetch with proof details fetches the entity
+//! through the SDK and requests its proof once more, the GroveDB part of it is
+//! verified here to resolve the root hash the quorum signed. The proof is
+//! shown only if it proves the same entity the SDK verified.
+
+use dapi_grpc::platform::v0::{
+    get_data_contract_request, get_data_contract_response, get_documents_response,
+    get_identity_request, get_identity_response, GetDataContractRequest, GetDocumentsRequest,
+    GetIdentityRequest, Proof, ResponseMetadata,
+};
+use dash_sdk::{
+    platform::{DocumentQuery, DriveQuery, Fetch, FetchMany},
+    Sdk,
+};
+use dpp::{
+    document::{Document, DocumentV0Getters},
+    platform_value::string_encoding::Encoding,
+    prelude::{DataContract, Identifier, Identity},
+};
+use drive::drive::Drive;
+use rs_dapi_client::{DapiRequest, RequestSettings};
+use serde::Serialize;
+
+use super::{as_json_string, BackendEvent, CompletedTaskPayload, Task};
+use crate::logging::traced_dapi_request;
+
+/// Entity to fetch along with its proof.
+#[derive(Debug, Clone)]
+pub(crate) enum ProvedFetch {
+    /// Identity by its base58 ID
+    Identity(String),
+    /// Data contract by its base58 ID
+    Contract(String),
+    Documents(DocumentQuery),
+}
+
+/// Proof of a fetched entity and the Platform state it was proven against.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub(crate) struct ProofDetails {
+    /// Hex encoded root hash the GroveDB proof resolves to
+    pub(crate) root_hash: String,
+    pub(crate) height: u64,
+    pub(crate) core_chain_locked_height: u32,
+    pub(crate) epoch: u32,
+    pub(crate) time_ms: u64,
+    pub(crate) protocol_version: u32,
+    pub(crate) chain_id: String,
+    pub(crate) quorum_type: u32,
+    /// Hex encoded hash of the quorum that signed the root hash
+    pub(crate) quorum_hash: String,
+    /// Hex encoded quorum signature
+    pub(crate) signature: String,
+    pub(crate) round: u32,
+    /// Hex encoded hash of the block the proof was created for
+    pub(crate) block_id_hash: String,
+    /// Size in bytes of the GroveDB proof
+    pub(crate) grovedb_proof_size: usize,
+}
+
+impl ProofDetails {
+    fn new(proof: &Proof, metadata: &ResponseMetadata, root_hash: [u8; 32]) -> Self {
+        ProofDetails {
+            root_hash: hex::encode(root_hash),
+            height: metadata.height,
+            core_chain_locked_height: metadata.core_chain_locked_height,
+            epoch: metadata.epoch,
+            time_ms: metadata.time_ms,
+            protocol_version: metadata.protocol_version,
+            chain_id: metadata.chain_id.clone(),
+            quorum_type: proof.quorum_type,
+            quorum_hash: hex::encode(&proof.quorum_hash),
+            signature: hex::encode(&proof.signature),
+            round: proof.round,
+            block_id_hash: hex::encode(&proof.block_id_hash),
+            grovedb_proof_size: proof.grovedb_proof.len(),
+        }
+    }
+
+    /// One line summary of the proof.
+    pub(crate) fn summary(&self) -> String {
+        format!(
+            "Proof verified at height {}, root hash {}, quorum {}",
+            self.height,
+            shorten(&self.root_hash),
+            shorten(&self.quorum_hash)
+        )
+    }
+
+    /// All details of the proof, one per line.
+    pub(crate) fn details(&self) -> String {
+        format!(
+            "Root hash: {}\nBlock height: {}\nCore chain locked height: {}\nEpoch: {}\nBlock \
+             time (ms): {}\nProtocol version: {}\nChain ID: {}\nQuorum type: {}\nQuorum hash: \
+             {}\nRound: {}\nBlock ID hash: {}\nSignature: {}\nGroveDB proof size: {} bytes",
+            self.root_hash,
+            self.height,
+            self.core_chain_locked_height,
+            self.epoch,
+            self.time_ms,
+            self.protocol_version,
+            self.chain_id,
+            self.quorum_type,
+            self.quorum_hash,
+            self.round,
+            self.block_id_hash,
+            self.signature,
+            self.grovedb_proof_size
+        )
+    }
+}
+
+/// Leading and trailing characters of a hex string.
+fn shorten(hex: &str) -> String {
+    if hex.len() <= 16 {
+        return hex.to_owned();
+    }
+    format!("{}…{}", &hex[..8], &hex[hex.len() - 8..])
+}
+
+fn parse_identifier(base58_id: &str) -> Result<Identifier, String> {
+    Identifier::from_string(base58_id, Encoding::Base58)
+        .map_err(|_| "Can't parse identifier as base58 string".to_owned())
+}
+
+fn proof_error(e: impl ToString) -> String {
+    format!("Unable to verify proof: {}", e.to_string())
+}
+
+const NO_PROOF: &str = "Invalid response: no proof";
+
+async fn fetch_identity_with_proof(
+    sdk: &Sdk,
+    base58_id: &str,
+) -> Result<(String, ProofDetails), String> {
+    let id = parse_identifier(base58_id)?;
+    let identity = Identity::fetch(sdk, id).await.map_err(|e| e.to_string())?;
+
+    let request = GetIdentityRequest {
+        version: Some(get_identity_request::Version::V0(
+            get_identity_request::GetIdentityRequestV0 {
+                id: id.to_vec(),
+                prove: true,
+            },
+        )),
+    };
+    let response = traced_dapi_request(request, |request| {
+        request.execute(sdk, RequestSettings::default())
+    })
+    .await
+    .map_err(|e| e.to_string())?;
+    let Some(get_identity_response::Version::V0(response)) = response.version else {
+        return Err(NO_PROOF.to_owned());
+    };
+    let (
+        Some(get_identity_response::get_identity_response_v0::Result::Proof(proof)),
+        Some(metadata),
+    ) = (response.result, response.metadata)
+    else {
+        return Err(NO_PROOF.to_owned());
+    };
+
+    let (root_hash, proven_identity) = Drive::verify_full_identity_by_identity_id(
+        &proof.grovedb_proof,
+        false,
+        id.to_buffer(),
+        sdk.version(),
+    )
+    .map_err(proof_error)?;
+    if proven_identity != identity {
+        return Err(proof_error("identity changed since it was fetched"));
+    }
+
+    let data = match identity {
+        Some(identity) => as_json_string(&identity),
+        None => "No identity".to_owned(),
+    };
+    Ok((data, ProofDetails::new(&proof, &metadata, root_hash)))
+}
+
+async fn fetch_contract_with_proof(
+    sdk: &Sdk,
+    base58_id: &str,
+) -> Result<(String, ProofDetails), String> {
+    let id = parse_identifier(base58_id)?;
+    let contract = DataContract::fetch(sdk, id)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let request = GetDataContractRequest {
+        version: Some(get_data_contract_request::Version::V0(
+            get_data_contract_request::GetDataContractRequestV0 {
+                id: id.to_vec(),
+                prove: true,
+            },
+        )),
+    };
+    let response = traced_dapi_request(request, |request| {
+        request.execute(sdk, RequestSettings::default())
+    })
+    .await
+    .map_err(|e| e.to_string())?;
+    let Some(get_data_contract_response::Version::V0(response)) = response.version else {
+        return Err(NO_PROOF.to_owned());
+    };
+    let (
+        Some(get_data_contract_response::get_data_contract_response_v0::Result::Proof(proof)),
+        Some(metadata),
+    ) = (response.result, response.metadata)
+    else {
+        return Err(NO_PROOF.to_owned());
+    };
+
+    let (root_hash, proven_contract) = Drive::verify_contract(
+        &proof.grovedb_proof,
+        None,
+        false,
+        false,
+        id.to_buffer(),
+        sdk.version(),
+    )
+    .map_err(proof_error)?;
+    if proven_contract != contract {
+        return Err(proof_error("contract changed since it was fetched"));
+    }
+
+    let data = match contract {
+        Some(contract) => as_json_string(&contract),
+        None => "No contract".to_owned(),
+    };
+    Ok((data, ProofDetails::new(&proof, &metadata, root_hash)))
+}
+
+async fn fetch_documents_with_proof(
+    sdk: &Sdk,
+    query: &DocumentQuery,
+) -> Result<(String, ProofDetails), String> {
+    let documents = Document::fetch_many(sdk, query.clone())
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let request = GetDocumentsRequest::try_from(query.clone())
+        .map_err(|e| format!("Invalid query: {}", e))?;
+    let response = traced_dapi_request(request, |request| {
+        request.execute(sdk, RequestSettings::default())
+    })
+    .await
+    .map_err(|e| e.to_string())?;
+    let Some(get_documents_response::Version::V0(response)) = response.version else {
+        return Err(NO_PROOF.to_owned());
+    };
+    let (
+        Some(get_documents_response::get_documents_response_v0::Result::Proof(proof)),
+        Some(metadata),
+    ) = (response.result, response.metadata)
+    else {
+        return Err(NO_PROOF.to_owned());
+    };
+
+    let drive_query = DriveQuery::try_from(query).map_err(|e| format!("Invalid query: {}", e))?;
+    let (root_hash, proven_documents) = drive_query
+        .verify_proof(&proof.grovedb_proof, sdk.version())
+        .map_err(proof_error)?;
+    let fetched_ids = documents
+        .values()
+        .flatten()
+        .map(|document| document.id())
+        .collect::<Vec<_>>();
+    let proven_ids = proven_documents
+        .iter()
+        .map(|document| document.id())
+        .collect::<Vec<_>>();
+    if fetched_ids != proven_ids {
+        return Err(proof_error("documents changed since they were fetched"));
+    }
+
+    let data = format!(
+        "{} documents proven:\n{}",
+        proven_ids.len(),
+        proven_ids
+            .iter()
+            .map(|id| id.to_string(Encoding::Base58))
+            .collect::<Vec<_>>()
+            .join("\n")
+    );
+    Ok((data, ProofDetails::new(&proof, &metadata, root_hash)))
+}
+
+pub(crate) async fn run_proved_fetch(sdk: &Sdk, fetch: ProvedFetch) -> BackendEvent<'static> {
+    let execution_result = match &fetch {
+        ProvedFetch::Identity(base58_id) => fetch_identity_with_proof(sdk, base58_id).await,
+        ProvedFetch::Contract(base58_id) => fetch_contract_with_proof(sdk, base58_id).await,
+        ProvedFetch::Documents(query) => fetch_documents_with_proof(sdk, query).await,
+    };
+    BackendEvent::TaskCompleted {
+        task: Task::FetchWithProof(fetch),
+        execution_result: execution_result
+            .map(|(data, proof)| CompletedTaskPayload::Proved { data, proof }),
+    }
+}
@@ -0,0 +1,226 @@
+//! Receipts of executed state transitions.
+//!
+//! A transition broadcast this way is awaited with proof, the proof is
+//! verified and a receipt is saved with the block the transition was executed
+//! in and the root hash the proof resolves to. The receipt is signed with the
+//! identity key the transition was signed with, so anyone can check it against
+//! the identity on Platform. Receipts are meant to be archived or attached to
+//! support tickets as evidence of an operation.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use dapi_grpc::platform::v0::wait_for_state_transition_result_response::{
+    self, wait_for_state_transition_result_response_v0,
+};
+use dash_sdk::{
+    platform::{transition::broadcast_request::BroadcastRequestForStateTransition, Fetch},
+    Sdk,
+};
+use dpp::{
+    block::{block_info::BlockInfo, epoch::Epoch},
+    data_contract::{accessors::v0::DataContractV0Getters, DataContract},
+    identity::{accessors::IdentityGettersV0, signer::Signer, Identity},
+    platform_value::string_encoding::Encoding,
+    state_transition::{proof_result::StateTransitionProofResult, StateTransition},
+};
+use drive::drive::Drive;
+use rs_dapi_client::{DapiRequest, RequestSettings};
+use serde::Serialize;
+
+use super::{as_json_string, reports::current_timestamp, AppState};
+use crate::logging::traced_dapi_request;
+
+const RECEIPTS_DIR: &str = "supporting_files/receipts";
+
+#[derive(Debug, Serialize)]
+struct ReceiptContent {
+    transition: String,
+    /// Hex encoded hash Platform identifies the transition by
+    transition_hash: String,
+    identity_id: String,
+    block_height: u64,
+    core_chain_locked_height: u32,
+    block_time_ms: u64,
+    /// Credits the identity balance decreased by on top of the amount moved
+    /// by the transition, if the balance could be retrieved
+    fee_paid: Option<u64>,
+    /// Hex encoded root hash of the verified proof
+    proof_root_hash: String,
+    /// Unix timestamp in seconds of the receipt creation
+    timestamp: u64,
+}
+
+/// Receipt content with the signature of its JSON serialization.
+#[derive(Debug, Serialize)]
+struct Receipt {
+    #[serde(flatten)]
+    content: ReceiptContent,
+    key_id: u32,
+    /// Hex encoded signature
+    signature: String,
+}
+
+impl AppState {
+    /// Broadcasts the transition signed by the identity, waits for its
+    /// execution and verifies the proof of it. A receipt is then saved into
+    /// the receipts directory, a failure to do so is only logged as the
+    /// transition is executed anyway.
+    ///
+    /// The balance expected once the transition is executed is the identity
+    /// balance without fees, minus the credits the transition moves out.
+    pub(crate) async fn broadcast_with_receipt(
+        &self,
+        sdk: &Sdk,
+        transition: &StateTransition,
+        identity: &Identity,
+        expected_balance: u64,
+    ) -> Result<(StateTransitionProofResult, Option<PathBuf>), String> {
+        let broadcast_request = transition
+            .broadcast_request_for_state_transition()
+            .map_err(|e| e.to_string())?;
+        traced_dapi_request(broadcast_request, |request| {
+            request.execute(sdk, RequestSettings::default())
+        })
+        .await
+        .map_err(|e| e.to_string())?;
+        let wait_request = transition
+            .wait_for_state_transition_result_request()
+            .map_err(|e| e.to_string())?;
+        let response = traced_dapi_request(wait_request, |request| {
+            request.execute(sdk, RequestSettings::default())
+        })
+        .await
+        .map_err(|e| e.to_string())?;
+
+        let Some(wait_for_state_transition_result_response::Version::V0(response)) =
+            response.version
+        else {
+            return Err("Invalid state transition result response".to_owned());
+        };
+        let metadata = response
+            .metadata
+            .ok_or_else(|| "Invalid state transition result response: no metadata".to_owned())?;
+        let proof = match response.result {
+            Some(wait_for_state_transition_result_response_v0::Result::Proof(proof)) => proof,
+            Some(wait_for_state_transition_result_response_v0::Result::Error(error)) => {
+                return Err(format!("State transition failed: {}", error.message))
+            }
+            None => return Err("Invalid state transition result response: no proof".to_owned()),
+        };
+
+        let epoch = Epoch::new(metadata.epoch as u16).map_err(|e| e.to_string())?;
+        let known_contracts: Vec<Arc<DataContract>> = self
+            .known_contracts
+            .lock()
+            .await
+            .values()
+            .cloned()
+            .map(Arc::new)
+            .collect();
+        let (root_hash, proof_result) = Drive::verify_state_transition_was_executed_with_proof(
+            transition,
+            &BlockInfo {
+                time_ms: metadata.time_ms,
+                height: metadata.height,
+                core_height: metadata.core_chain_locked_height,
+                epoch,
+            },
+            proof.grovedb_proof.as_slice(),
+            &|id| {
+                Ok(known_contracts
+                    .iter()
+                    .find(|contract| contract.id() == *id)
+                    .cloned())
+            },
+            sdk.version(),
+        )
+        .map_err(|e| format!("Unable to verify state transition proof: {}", e))?;
+
+        let balance = match proven_balance(&proof_result, identity) {
+            Some(balance) => Some(balance),
+            None => Identity::fetch(sdk, identity.id())
+                .await
+                .ok()
+                .flatten()
+                .map(|identity| identity.balance()),
+        };
+
+        let content = ReceiptContent {
+            transition: transition.name().to_string(),
+            transition_hash: transition
+                .transaction_id()
+                .map(hex::encode)
+                .unwrap_or_default(),
+            identity_id: identity.id().to_string(Encoding::Base58),
+            block_height: metadata.height,
+            core_chain_locked_height: metadata.core_chain_locked_height,
+            block_time_ms: metadata.time_ms,
+            fee_paid: balance.map(|balance| expected_balance.saturating_sub(balance)),
+            proof_root_hash: hex::encode(root_hash),
+            timestamp: current_timestamp(),
+        };
+        let receipt_path = match self.save_receipt(transition, identity, content).await {
+            Ok(path) => Some(path),
+            Err(e) => {
+                tracing::error!("unable to save the transition receipt: {}", e);
+                None
+            }
+        };
+
+        Ok((proof_result, receipt_path))
+    }
+
+    async fn save_receipt(
+        &self,
+        transition: &StateTransition,
+        identity: &Identity,
+        content: ReceiptContent,
+    ) -> Result<PathBuf, String> {
+        let key_id = transition
+            .signature_public_key_id()
+            .ok_or_else(|| "Transition is not signed by an identity key".to_owned())?;
+        let public_key = identity
+            .get_public_key_by_id(key_id)
+            .ok_or_else(|| "Transition signing key not found".to_owned())?;
+        let content_json = serde_json::to_vec(&content).map_err(|e| e.to_string())?;
+        let signature = self
+            .identity_signer(identity, "Transition receipt")
+            .await
+            .sign(public_key, &content_json)
+            .map_err(|e| format!("Unable to sign the receipt: {}", e))?;
+
+        let dir = Path::new(RECEIPTS_DIR).join(&self.profile);
+        fs::create_dir_all(&dir)
+            .map_err(|e| format!("Failed to create receipts directory: {}", e))?;
+        let path = dir.join(format!(
+            "{}_{}.json",
+            content.timestamp, content.transition_hash
+        ));
+        let receipt = Receipt {
+            content,
+            key_id,
+            signature: hex::encode(signature.as_slice()),
+        };
+        fs::write(&path, as_json_string(&receipt))
+            .map_err(|e| format!("Failed to write receipt file: {}", e))?;
+
+        Ok(path)
+    }
+}
+
+/// Balance of the identity if the proof has it.
+fn proven_balance(proof_result: &StateTransitionProofResult, identity: &Identity) -> Option<u64> {
+    match proof_result {
+        StateTransitionProofResult::VerifiedPartialIdentity(partial_identity)
+        | StateTransitionProofResult::VerifiedBalanceTransfer(partial_identity, _)
+            if partial_identity.id == identity.id() =>
+        {
+            partial_identity.balance
+        }
+        _ => None,
+    }
+}
@@ -0,0 +1,228 @@
+//! Core chain reorganization handling.
+//! Confirmations of wallet UTXOs and of asset locks being used for identity
+//! funding are polled periodically. A transaction losing confirmations or
+//! disappearing is considered affected by a reorg: it's marked unstable and
+//! identity operations depending on wallet funds are paused until the
+//! transaction is confirmed again.
+
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+
+use dpp::dashcore::Txid;
+
+use super::{
+    insight::InsightAPIClient, reports::current_timestamp, AppState, AppStateUpdate, BackendEvent,
+    Wallet,
+};
+
+/// Number of activity log entries to keep.
+const ACTIVITY_LOG_SIZE: usize = 50;
+
+/// Confirmations required for an unstable transaction to be trusted again.
+const RECONFIRMATION_DEPTH: u64 = 1;
+
+#[derive(Debug, Default)]
+pub(crate) struct ReorgWatch {
+    /// Last seen confirmations count per tracked transaction
+    confirmations: BTreeMap<Txid, u64>,
+    /// Transactions affected by a reorg and not confirmed again yet
+    unstable: BTreeSet<Txid>,
+    activity_log: VecDeque<String>,
+}
+
+impl ReorgWatch {
+    fn log(&mut self, message: String) {
+        tracing::warn!("reorg watch: {}", message);
+        if self.activity_log.len() == ACTIVITY_LOG_SIZE {
+            self.activity_log.pop_front();
+        }
+        self.activity_log
+            .push_back(format!("[{}] {}", current_timestamp(), message));
+    }
+
+    /// Records a confirmations count observation, `None` means the transaction
+    /// is unknown to the node. Returns whether the stability of the
+    /// transaction has changed.
+    fn observe(&mut self, txid: Txid, confirmations: Option<u64>) -> bool {
+        let previous = self.confirmations.get(&txid).copied();
+
+        match (previous, confirmations) {
+            // Not seen by the node yet, likely still propagating
+            (None, None) => false,
+            (Some(_), None) => {
+                self.confirmations.remove(&txid);
+                if self.unstable.insert(txid) {
+                    self.log(format!("{} disappeared", txid));
+                    true
+                } else {
+                    false
+                }
+            }
+            (previous, Some(current)) => {
+                self.confirmations.insert(txid, current);
+                if self.unstable.contains(&txid) {
+                    if current >= RECONFIRMATION_DEPTH {
+                        self.unstable.remove(&txid);
+                        self.log(format!("{} confirmed again ({})", txid, current));
+                        return true;
+                    }
+                    false
+                } else if previous.is_some_and(|previous| current < previous) {
+                    self.unstable.insert(txid);
+                    self.log(format!(
+                        "{} confirmations dropped from {} to {}",
+                        txid,
+                        previous.unwrap_or_default(),
+                        current
+                    ));
+                    true
+                } else {
+                    false
+                }
+            }
+        }
+    }
+
+    /// Stops tracking transactions that are no longer relevant, unless they're
+    /// unstable, as their outcome is still to be seen.
+    fn retain(&mut self, relevant: &BTreeSet<Txid>) {
+        let unstable = &self.unstable;
+        self.confirmations
+            .retain(|txid, _| relevant.contains(txid) || unstable.contains(txid));
+    }
+
+    pub(crate) fn is_stable(&self) -> bool {
+        self.unstable.is_empty()
+    }
+
+    /// Error if operations funded by the wallet shall wait for the unstable
+    /// transactions to be confirmed again.
+    pub(crate) fn ensure_stable(&self) -> Result<(), String> {
+        if self.is_stable() {
+            Ok(())
+        } else {
+            Err(format!(
+                "paused until re-confirmation of transactions affected by a core chain reorg: {}",
+                self.unstable
+                    .iter()
+                    .map(|txid| txid.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ))
+        }
+    }
+
+    pub(crate) fn display(&self) -> String {
+        let mut lines = vec!["Core chain reorg affected transactions:".to_owned()];
+        if self.unstable.is_empty() {
+            lines.push("    none".to_owned());
+        }
+        for txid in self.unstable.iter() {
+            lines.push(format!(
+                "    {} ({} confirmations)",
+                txid,
+                self.confirmations.get(txid).copied().unwrap_or_default()
+            ));
+        }
+
+        lines.push(String::new());
+        lines.push("Activity:".to_owned());
+        if self.activity_log.is_empty() {
+            lines.push("    no activity yet".to_owned());
+        }
+        for entry in self.activity_log.iter().rev() {
+            lines.push(format!("    {}", entry));
+        }
+
+        lines.join("\n")
+    }
+}
+
+impl AppState {
+    /// Periodic reorg check, issued by the application loop in background, so
+    /// it reports with [BackendEvent::AppStateUpdated] and only if stability
+    /// of any transaction has changed.
+    pub(crate) async fn check_reorgs(&self, insight: &InsightAPIClient) -> BackendEvent {
+        let mut observations: BTreeMap<Txid, Option<u64>> = BTreeMap::new();
+
+        let wallet_utxos = match self.loaded_wallet.lock().await.as_ref() {
+            Some(Wallet::SingleKeyWallet(wallet)) => Some((wallet.address.clone(), {
+                wallet
+                    .utxos
+                    .keys()
+                    .map(|outpoint| outpoint.txid)
+                    .collect::<BTreeSet<_>>()
+            })),
+            None => None,
+        };
+
+        if let Some((address, local_txids)) = wallet_utxos {
+            match insight
+                .utxos_with_confirmations_for_addresses(&[&address])
+                .await
+            {
+                Ok(utxos) => {
+                    for (outpoint, (_, confirmations)) in utxos {
+                        observations.insert(outpoint.txid, Some(confirmations));
+                    }
+                }
+                Err(e) => tracing::error!("reorg watch: unable to fetch wallet UTXOs: {}", e),
+            }
+            // UTXOs known locally but missing on the node could be either
+            // spent elsewhere or reorged out, only the transaction lookup tells
+            for txid in local_txids {
+                if !observations.contains_key(&txid) {
+                    self.observe_transaction(insight, txid, &mut observations)
+                        .await;
+                }
+            }
+        }
+
+        let asset_lock_txids: Vec<Txid> = [
+            self.identity_asset_lock_private_key_in_creation
+                .lock()
+                .await
+                .as_ref()
+                .map(|(transaction, ..)| transaction.txid()),
+            self.identity_asset_lock_private_key_in_top_up
+                .lock()
+                .await
+                .as_ref()
+                .map(|(transaction, ..)| transaction.txid()),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+
+        for txid in asset_lock_txids {
+            self.observe_transaction(insight, txid, &mut observations)
+                .await;
+        }
+
+        let mut reorg_watch = self.reorg_watch.lock().await;
+        let mut changed = false;
+        for (txid, confirmations) in observations.iter() {
+            changed |= reorg_watch.observe(*txid, *confirmations);
+        }
+        reorg_watch.retain(&observations.keys().copied().collect());
+
+        if changed {
+            BackendEvent::AppStateUpdated(AppStateUpdate::ReorgWatch(reorg_watch))
+        } else {
+            BackendEvent::None
+        }
+    }
+
+    async fn observe_transaction(
+        &self,
+        insight: &InsightAPIClient,
+        txid: Txid,
+        observations: &mut BTreeMap<Txid, Option<u64>>,
+    ) {
+        match insight.transaction_confirmations(&txid).await {
+            Ok(confirmations) => {
+                observations.insert(txid, confirmations);
+            }
+            Err(e) => tracing::error!("reorg watch: unable to fetch {}: {}", txid, e),
+        }
+    }
+}
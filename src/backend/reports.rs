@@ -0,0 +1,302 @@
+//! Strategy run reports.
+//! Every successfully completed strategy run is saved as a JSON report, so runs
+//! made against different platform versions can be compared later.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use serde::{Deserialize, Serialize};
+
+use super::strategies::{phases::RunPhases, resources::ResourceUsage};
+
+const REPORTS_DIR: &str = "supporting_files/strategy_reports";
+
+/// Relative change below this percentage is considered noise.
+const NOISE_THRESHOLD_PERCENT: f64 = 5.0;
+
+/// Relative change above this percentage is considered significant.
+const SIGNIFICANT_THRESHOLD_PERCENT: f64 = 15.0;
+
+/// Metrics of a single strategy run persisted on disk.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct StrategyReport {
+    pub strategy_name: String,
+    pub timestamp: u64,
+    pub protocol_version: u32,
+    pub block_mode: bool,
+    pub blocks_or_seconds: u64,
+    pub transition_count: u64,
+    pub success_count: u64,
+    pub run_time_secs: u64,
+    pub init_time_secs: u64,
+    pub rate: u64,
+    pub success_rate: u64,
+    pub success_percent: u64,
+    pub dash_spent_identity: f64,
+    pub dash_spent_wallet: f64,
+    /// Observed only when the run was paced by actual blocks
+    #[serde(default)]
+    pub average_block_time_ms: Option<u64>,
+    /// Explorer process usage, missing in older reports
+    #[serde(default)]
+    pub resource_usage: Option<ResourceUsage>,
+    /// Correlation ID of the run task, to find its log lines
+    #[serde(default)]
+    pub correlation_id: Option<String>,
+    /// Phases left out of the counts, rates and run time, none in older
+    /// reports
+    #[serde(default)]
+    pub phases: RunPhases,
+    /// Target size in bytes document transitions were padded to, 0 if they
+    /// weren't
+    #[serde(default)]
+    pub padding: u32,
+}
+
+impl StrategyReport {
+    fn file_name(&self) -> String {
+        format!(
+            "{}_{}.json",
+            file_name_safe(&self.strategy_name),
+            self.timestamp
+        )
+    }
+}
+
+/// Strategy name usable as part of a file name, characters other than ASCII
+/// letters, digits, `_` and `-` are replaced with `_` so names can't point
+/// outside of the directory.
+pub(crate) fn file_name_safe(strategy_name: &str) -> String {
+    strategy_name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '_' || c == '-' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+pub(crate) fn current_timestamp() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("time went backwards")
+        .as_secs()
+}
+
+/// Writes the report into the reports directory, returning the path of the
+/// created file.
+pub(crate) fn save_report(report: &StrategyReport) -> Result<PathBuf, String> {
+    fs::create_dir_all(REPORTS_DIR)
+        .map_err(|e| format!("Failed to create reports directory: {}", e))?;
+    let path = Path::new(REPORTS_DIR).join(report.file_name());
+    let json = serde_json::to_string_pretty(report)
+        .map_err(|e| format!("Failed to serialize report: {}", e))?;
+    fs::write(&path, json).map_err(|e| format!("Failed to write report: {}", e))?;
+    Ok(path)
+}
+
+pub(crate) fn load_report(file_name: &str) -> Result<StrategyReport, String> {
+    let path = Path::new(REPORTS_DIR).join(file_name);
+    let bytes =
+        fs::read(&path).map_err(|e| format!("Failed to read report {}: {}", path.display(), e))?;
+    serde_json::from_slice(&bytes)
+        .map_err(|e| format!("Failed to parse report {}: {}", path.display(), e))
+}
+
+/// File names of all saved reports, newest first.
+pub(crate) fn list_reports() -> Vec<String> {
+    let Ok(entries) = fs::read_dir(REPORTS_DIR) else {
+        return Vec::new();
+    };
+    let mut reports: Vec<(SystemTime, String)> = entries
+        .filter_map(|e| e.ok())
+        .filter(|e| e.path().extension().and_then(|s| s.to_str()) == Some("json"))
+        .filter_map(|e| {
+            let modified = e.metadata().and_then(|m| m.modified()).ok()?;
+            Some((modified, e.file_name().to_string_lossy().into_owned()))
+        })
+        .collect();
+    reports.sort_by(|a, b| b.0.cmp(&a.0));
+    reports.into_iter().map(|(_, name)| name).collect()
+}
+
+/// A hint on whether a difference between two values could be caused by run to
+/// run variance only.
+fn significance_hint(a: f64, b: f64, counts: bool) -> &'static str {
+    if a == 0.0 && b == 0.0 {
+        return "";
+    }
+
+    // For event counts a Poisson approximation gives a better idea than a
+    // relative change, as it accounts for small samples
+    if counts {
+        let z = (b - a).abs() / (a + b).sqrt();
+        return if z < 2.0 {
+            "~ noise"
+        } else if z < 3.0 {
+            "? possible"
+        } else {
+            "! significant"
+        };
+    }
+
+    let relative = if a == 0.0 {
+        100.0
+    } else {
+        ((b - a) / a).abs() * 100.0
+    };
+    if relative < NOISE_THRESHOLD_PERCENT {
+        "~ noise"
+    } else if relative < SIGNIFICANT_THRESHOLD_PERCENT {
+        "? possible"
+    } else {
+        "! significant"
+    }
+}
+
+fn delta_string(a: f64, b: f64) -> String {
+    let delta = b - a;
+    if a == 0.0 {
+        format!("{:+.2}", delta)
+    } else {
+        format!("{:+.2} ({:+.1}%)", delta, delta / a * 100.0)
+    }
+}
+
+/// Renders two reports side by side with deltas and significance hints.
+pub(crate) fn compare_reports(a: &StrategyReport, b: &StrategyReport) -> String {
+    let mode = |report: &StrategyReport| {
+        if report.block_mode {
+            "block"
+        } else {
+            "time"
+        }
+    };
+
+    let mut lines = vec![
+        format!("A: {} (protocol v{})", a.strategy_name, a.protocol_version),
+        format!("B: {} (protocol v{})", b.strategy_name, b.protocol_version),
+        String::new(),
+    ];
+
+    if a.block_mode != b.block_mode || a.blocks_or_seconds != b.blocks_or_seconds {
+        lines.push(format!(
+            "Warning: runs are not directly comparable ({} x {} vs {} x {})",
+            mode(a),
+            a.blocks_or_seconds,
+            mode(b),
+            b.blocks_or_seconds
+        ));
+        lines.push(String::new());
+    }
+    if a.phases != b.phases {
+        lines.push(format!(
+            "Warning: runs exclude different warm-up and cool-down ({} + {} vs {} + {})",
+            a.phases.warm_up, a.phases.cool_down, b.phases.warm_up, b.phases.cool_down
+        ));
+        lines.push(String::new());
+    }
+
+    lines.push(format!(
+        "{:<28}{:>14}{:>14}{:>24}  {}",
+        "Metric", "A", "B", "Delta", "Hint"
+    ));
+
+    let usage = |report: &StrategyReport| report.resource_usage.clone().unwrap_or_default();
+    let (usage_a, usage_b) = (usage(a), usage(b));
+
+    let metrics: [(&str, f64, f64, bool); 13] = [
+        (
+            "Transitions attempted",
+            a.transition_count as f64,
+            b.transition_count as f64,
+            true,
+        ),
+        (
+            "Transitions succeeded",
+            a.success_count as f64,
+            b.success_count as f64,
+            true,
+        ),
+        (
+            "Run time (s)",
+            a.run_time_secs as f64,
+            b.run_time_secs as f64,
+            false,
+        ),
+        (
+            "Init time (s)",
+            a.init_time_secs as f64,
+            b.init_time_secs as f64,
+            false,
+        ),
+        ("Attempted rate (tx/s)", a.rate as f64, b.rate as f64, false),
+        (
+            "Successful rate (tx/s)",
+            a.success_rate as f64,
+            b.success_rate as f64,
+            false,
+        ),
+        (
+            "Success percentage",
+            a.success_percent as f64,
+            b.success_percent as f64,
+            false,
+        ),
+        (
+            "Dash spent (Identity)",
+            a.dash_spent_identity,
+            b.dash_spent_identity,
+            false,
+        ),
+        (
+            "Dash spent (Wallet)",
+            a.dash_spent_wallet,
+            b.dash_spent_wallet,
+            false,
+        ),
+        (
+            "Blocks or seconds",
+            a.blocks_or_seconds as f64,
+            b.blocks_or_seconds as f64,
+            false,
+        ),
+        (
+            "CPU usage (%)",
+            usage_a.cpu_percent,
+            usage_b.cpu_percent,
+            false,
+        ),
+        (
+            "Peak memory (MB)",
+            usage_a.peak_memory_mb,
+            usage_b.peak_memory_mb,
+            false,
+        ),
+        (
+            "Peak open sockets",
+            usage_a.peak_open_sockets as f64,
+            usage_b.peak_open_sockets as f64,
+            false,
+        ),
+    ];
+
+    for (name, value_a, value_b, counts) in metrics {
+        lines.push(format!(
+            "{:<28}{:>14.2}{:>14.2}{:>24}  {}",
+            name,
+            value_a,
+            value_b,
+            delta_string(value_a, value_b),
+            significance_hint(value_a, value_b, counts)
+        ));
+    }
+
+    lines.join("\n")
+}
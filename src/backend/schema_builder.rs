@@ -0,0 +1,445 @@
+//! Document type schema builder.
+//! A draft of a single document type schema is kept in the app state and
+//! modified step by step from the UI, once ready it's registered as a new data
+//! contract owned by the loaded identity. A document type of a known contract
+//! can be loaded into the draft instead, to be changed and broadcast as an
+//! update of that contract, or the draft added to a contract as a new type.
+
+use dash_sdk::Sdk;
+use dpp::{data_contract::accessors::v0::DataContractV0Getters, platform_value::Value};
+use serde_json::{json, Map, Value as JsonValue};
+
+use super::{as_json_string, AppState, AppStateUpdate, BackendEvent, Task};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, strum::Display)]
+pub(crate) enum PropertyType {
+    #[strum(serialize = "string")]
+    String,
+    #[strum(serialize = "integer")]
+    Integer,
+    #[strum(serialize = "number")]
+    Number,
+    #[strum(serialize = "boolean")]
+    Boolean,
+    #[strum(serialize = "byteArray")]
+    ByteArray,
+}
+
+/// Keywords of document type schemas the draft keeps.
+const DOCUMENT_TYPE_KEYWORDS: [&str; 5] = [
+    "type",
+    "properties",
+    "required",
+    "additionalProperties",
+    "indices",
+];
+
+/// Keywords of property schemas the draft keeps.
+const PROPERTY_KEYWORDS: [&str; 6] = [
+    "type",
+    "position",
+    "maxLength",
+    "maximum",
+    "maxItems",
+    "byteArray",
+];
+
+/// Keywords of indices the draft keeps.
+const INDEX_KEYWORDS: [&str; 3] = ["name", "properties", "unique"];
+
+fn unsupported_keyword<'a>(
+    schema: &'a Map<String, JsonValue>,
+    supported: &[&str],
+) -> Option<&'a String> {
+    schema.keys().find(|key| !supported.contains(&key.as_str()))
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct PropertyDraft {
+    pub name: String,
+    pub property_type: PropertyType,
+    pub required: bool,
+    /// `maxLength` for strings, `maximum` for numbers and `maxItems` for byte
+    /// arrays
+    pub limit: Option<u64>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct IndexDraft {
+    pub name: String,
+    pub properties: Vec<String>,
+    pub unique: bool,
+}
+
+#[derive(Debug, Clone, Default)]
+pub(crate) struct DocumentTypeDraft {
+    pub name: String,
+    pub properties: Vec<PropertyDraft>,
+    pub indices: Vec<IndexDraft>,
+}
+
+impl DocumentTypeDraft {
+    /// Draft of an existing document type schema, refused if the schema uses
+    /// anything the builder can't represent, so an update doesn't drop it.
+    pub(crate) fn from_schema(name: &str, schema: &JsonValue) -> Result<Self, String> {
+        let unsupported = |keyword: &str| {
+            format!(
+                "Document type {} uses {}, which the schema builder doesn't support",
+                name, keyword
+            )
+        };
+        let schema = schema
+            .as_object()
+            .ok_or_else(|| format!("Schema of {} is not an object", name))?;
+        if let Some(keyword) = unsupported_keyword(schema, &DOCUMENT_TYPE_KEYWORDS) {
+            return Err(unsupported(keyword));
+        }
+
+        let required: Vec<&str> = schema
+            .get("required")
+            .and_then(JsonValue::as_array)
+            .map(|required| required.iter().filter_map(JsonValue::as_str).collect())
+            .unwrap_or_default();
+
+        let mut properties = Vec::new();
+        for (property_name, property) in schema
+            .get("properties")
+            .and_then(JsonValue::as_object)
+            .into_iter()
+            .flatten()
+        {
+            let property = property
+                .as_object()
+                .ok_or_else(|| unsupported(property_name))?;
+            if let Some(keyword) = unsupported_keyword(property, &PROPERTY_KEYWORDS) {
+                return Err(unsupported(&format!("{}.{}", property_name, keyword)));
+            }
+            let byte_array = property.get("byteArray") == Some(&JsonValue::Bool(true));
+            let property_type = match property.get("type").and_then(JsonValue::as_str) {
+                Some("string") => PropertyType::String,
+                Some("integer") => PropertyType::Integer,
+                Some("number") => PropertyType::Number,
+                Some("boolean") => PropertyType::Boolean,
+                Some("array") if byte_array => PropertyType::ByteArray,
+                _ => return Err(unsupported(&format!("{}.type", property_name))),
+            };
+            let limit = ["maxLength", "maximum", "maxItems"]
+                .iter()
+                .find_map(|key| property.get(*key).and_then(JsonValue::as_u64));
+            let position = property
+                .get("position")
+                .and_then(JsonValue::as_u64)
+                .unwrap_or(u64::MAX);
+            properties.push((
+                position,
+                PropertyDraft {
+                    name: property_name.clone(),
+                    property_type,
+                    required: required.contains(&property_name.as_str()),
+                    limit,
+                },
+            ));
+        }
+        properties.sort_by_key(|(position, _)| *position);
+        if let Some(field) = required
+            .iter()
+            .find(|field| !properties.iter().any(|(_, p)| p.name == **field))
+        {
+            return Err(unsupported(&format!("required {}", field)));
+        }
+
+        let mut indices = Vec::new();
+        for index in schema
+            .get("indices")
+            .and_then(JsonValue::as_array)
+            .into_iter()
+            .flatten()
+        {
+            let index = index.as_object().ok_or_else(|| unsupported("indices"))?;
+            if let Some(keyword) = unsupported_keyword(index, &INDEX_KEYWORDS) {
+                return Err(unsupported(&format!("indices.{}", keyword)));
+            }
+            let index_name = index
+                .get("name")
+                .and_then(JsonValue::as_str)
+                .unwrap_or_default();
+            let mut index_properties = Vec::new();
+            for property in index
+                .get("properties")
+                .and_then(JsonValue::as_array)
+                .into_iter()
+                .flatten()
+            {
+                match property.as_object().and_then(|p| p.iter().next()) {
+                    Some((property_name, order)) if order == "asc" => {
+                        index_properties.push(property_name.clone())
+                    }
+                    _ => return Err(unsupported(&format!("index {} ordering", index_name))),
+                }
+            }
+            indices.push(IndexDraft {
+                name: index_name.to_owned(),
+                properties: index_properties,
+                unique: index.get("unique") == Some(&JsonValue::Bool(true)),
+            });
+        }
+
+        Ok(DocumentTypeDraft {
+            name: name.to_owned(),
+            properties: properties
+                .into_iter()
+                .map(|(_, property)| property)
+                .collect(),
+            indices,
+        })
+    }
+
+    /// Schema of all document types of a contract, that is a single one for the
+    /// draft.
+    pub(crate) fn documents_schema(&self) -> JsonValue {
+        let properties: Map<String, JsonValue> = self
+            .properties
+            .iter()
+            .enumerate()
+            .map(|(position, property)| {
+                let mut schema = json!({
+                    "type": property.property_type.to_string(),
+                    "position": position,
+                });
+                let limit_key = match property.property_type {
+                    PropertyType::String => Some("maxLength"),
+                    PropertyType::Integer | PropertyType::Number => Some("maximum"),
+                    PropertyType::ByteArray => Some("maxItems"),
+                    PropertyType::Boolean => None,
+                };
+                if let (Some(key), Some(limit)) = (limit_key, property.limit) {
+                    schema[key] = limit.into();
+                }
+                if property.property_type == PropertyType::ByteArray {
+                    schema["type"] = "array".into();
+                    schema["byteArray"] = true.into();
+                }
+                (property.name.clone(), schema)
+            })
+            .collect();
+
+        let required: Vec<&str> = self
+            .properties
+            .iter()
+            .filter(|property| property.required)
+            .map(|property| property.name.as_str())
+            .collect();
+
+        let indices: Vec<JsonValue> = self
+            .indices
+            .iter()
+            .map(|index| {
+                json!({
+                    "name": index.name,
+                    "properties": index
+                        .properties
+                        .iter()
+                        .map(|property| json!({ property: "asc" }))
+                        .collect::<Vec<_>>(),
+                    "unique": index.unique,
+                })
+            })
+            .collect();
+
+        let mut document_type = json!({
+            "type": "object",
+            "properties": properties,
+            "required": required,
+            "additionalProperties": false,
+        });
+        if !indices.is_empty() {
+            document_type["indices"] = indices.into();
+        }
+
+        let name = if self.name.is_empty() {
+            "unnamed"
+        } else {
+            self.name.as_str()
+        };
+        json!({ name: document_type })
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        if self.name.is_empty() {
+            return Err("Document type name is not set".to_owned());
+        }
+        if self.properties.is_empty() {
+            return Err("Document type has no properties".to_owned());
+        }
+        for index in self.indices.iter() {
+            for property_name in index.properties.iter() {
+                let Some(property) = self.properties.iter().find(|p| &p.name == property_name)
+                else {
+                    return Err(format!(
+                        "Index {} refers to unknown property {}",
+                        index.name, property_name
+                    ));
+                };
+                if property.property_type == PropertyType::String && property.limit.is_none() {
+                    return Err(format!(
+                        "Indexed string property {} must have a max length",
+                        property_name
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum SchemaBuilderTask {
+    SetDocumentTypeName(String),
+    AddProperty(PropertyDraft),
+    RemoveLastProperty,
+    AddIndex(IndexDraft),
+    RemoveLastIndex,
+    Clear,
+    RegisterContract(String),
+    /// Replace the draft with a document type of a known contract
+    LoadDocumentType {
+        contract_name: String,
+        document_type_name: String,
+    },
+    /// Broadcast a new version of a known contract with the draft document type
+    UpdateContract(String),
+}
+
+impl AppState {
+    pub(crate) async fn run_schema_builder_task(
+        &self,
+        sdk: &Sdk,
+        task: SchemaBuilderTask,
+    ) -> BackendEvent {
+        let mut draft = self.schema_draft.lock().await;
+        let result = match &task {
+            SchemaBuilderTask::SetDocumentTypeName(name) => {
+                draft.name = name.clone();
+                Ok(())
+            }
+            SchemaBuilderTask::AddProperty(property) => {
+                if draft.properties.iter().any(|p| p.name == property.name) {
+                    Err(format!("Property {} already exists", property.name))
+                } else {
+                    draft.properties.push(property.clone());
+                    Ok(())
+                }
+            }
+            SchemaBuilderTask::RemoveLastProperty => {
+                draft.properties.pop();
+                Ok(())
+            }
+            SchemaBuilderTask::AddIndex(index) => {
+                draft.indices.push(index.clone());
+                Ok(())
+            }
+            SchemaBuilderTask::RemoveLastIndex => {
+                draft.indices.pop();
+                Ok(())
+            }
+            SchemaBuilderTask::Clear => {
+                *draft = DocumentTypeDraft::default();
+                Ok(())
+            }
+            SchemaBuilderTask::LoadDocumentType {
+                contract_name,
+                document_type_name,
+            } => self
+                .load_document_type(contract_name, document_type_name)
+                .await
+                .map(|loaded| *draft = loaded),
+            SchemaBuilderTask::RegisterContract(contract_name)
+            | SchemaBuilderTask::UpdateContract(contract_name) => {
+                let draft_snapshot = draft.clone();
+                drop(draft);
+                let result = if matches!(task, SchemaBuilderTask::UpdateContract(_)) {
+                    self.update_draft_contract(sdk, contract_name, &draft_snapshot)
+                        .await
+                } else {
+                    self.register_draft_contract(sdk, contract_name, &draft_snapshot)
+                        .await
+                };
+                return match result {
+                    Ok(contract_json) => BackendEvent::TaskCompletedStateChange {
+                        task: Task::SchemaBuilder(task),
+                        execution_result: Ok(contract_json.into()),
+                        app_state_update: AppStateUpdate::KnownContracts(
+                            self.known_contracts.lock().await,
+                        ),
+                    },
+                    Err(e) => BackendEvent::TaskCompleted {
+                        task: Task::SchemaBuilder(task),
+                        execution_result: Err(e),
+                    },
+                };
+            }
+        };
+
+        let execution_result = result.map(|_| as_json_string(&draft.documents_schema()).into());
+
+        BackendEvent::TaskCompleted {
+            task: Task::SchemaBuilder(task),
+            execution_result,
+        }
+    }
+
+    async fn load_document_type(
+        &self,
+        contract_name: &str,
+        document_type_name: &str,
+    ) -> Result<DocumentTypeDraft, String> {
+        let known_contracts = self.known_contracts.lock().await;
+        let contract = known_contracts
+            .get(contract_name)
+            .ok_or_else(|| format!("Unknown contract {}", contract_name))?;
+        let schema = contract
+            .document_schemas()
+            .get(document_type_name)
+            .ok_or_else(|| {
+                format!(
+                    "Contract {} has no document type {}",
+                    contract_name, document_type_name
+                )
+            })?;
+        let schema = serde_json::to_value(schema)
+            .map_err(|e| format!("Unable to convert schema of {}: {}", document_type_name, e))?;
+        DocumentTypeDraft::from_schema(document_type_name, &schema)
+    }
+
+    async fn update_draft_contract(
+        &self,
+        sdk: &Sdk,
+        contract_name: &str,
+        draft: &DocumentTypeDraft,
+    ) -> Result<String, String> {
+        draft.validate()?;
+        let document_schema = draft
+            .documents_schema()
+            .get(&draft.name)
+            .cloned()
+            .unwrap_or_default();
+        self.update_contract(sdk, contract_name, &draft.name, document_schema)
+            .await
+    }
+
+    async fn register_draft_contract(
+        &self,
+        sdk: &Sdk,
+        contract_name: &str,
+        draft: &DocumentTypeDraft,
+    ) -> Result<String, String> {
+        draft.validate()?;
+        self.register_contract(
+            sdk,
+            contract_name,
+            Value::from(draft.documents_schema()),
+            None,
+        )
+        .await
+    }
+}
@@ -0,0 +1,212 @@
+//! Search across locally cached contracts and documents.
+
+use std::collections::BTreeMap;
+
+use dash_sdk::platform::DocumentQuery;
+use dpp::{
+    data_contract::{
+        accessors::v0::DataContractV0Getters, document_type::accessors::DocumentTypeV0Getters,
+    },
+    document::{Document, DocumentV0Getters},
+    platform_value::{string_encoding::Encoding, Value},
+    prelude::Identifier,
+};
+
+use super::{documents::prefetch::query_key, AppState, BackendEvent, CompletedTaskPayload, Task};
+
+/// Documents fetched during the session with queries they were fetched with.
+pub(crate) type DocumentsCache = BTreeMap<Identifier, (DocumentQuery, Document)>;
+
+/// Score of a match per kind of matched field.
+const CONTRACT_NAME_SCORE: u32 = 10;
+const DOCUMENT_TYPE_NAME_SCORE: u32 = 6;
+const PROPERTY_NAME_SCORE: u32 = 4;
+const DOCUMENT_VALUE_SCORE: u32 = 3;
+
+/// Maximum number of returned search results.
+const MAX_RESULTS: usize = 100;
+
+#[derive(Debug, Clone)]
+pub(crate) enum SearchTarget {
+    Contract {
+        name: String,
+        document_type_names: Vec<String>,
+    },
+    Document {
+        query: DocumentQuery,
+        document: Document,
+    },
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct SearchHit {
+    pub(crate) score: u32,
+    pub(crate) summary: String,
+    pub(crate) target: SearchTarget,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum SearchTask {
+    Search(String),
+}
+
+/// Lowercased search terms, each of them must match for an entity to be found.
+struct Terms(Vec<String>);
+
+impl Terms {
+    fn new(query: &str) -> Self {
+        Terms(query.split_whitespace().map(str::to_lowercase).collect())
+    }
+
+    /// Score of a field for a term, an exact match weights twice a partial one.
+    fn field_score(term: &str, field: &str, weight: u32) -> u32 {
+        let field = field.to_lowercase();
+        if field == term {
+            weight * 2
+        } else if field.contains(term) {
+            weight
+        } else {
+            0
+        }
+    }
+
+    /// Total score of weighted fields, `None` if any term matches nothing.
+    fn score<'a>(&self, fields: impl Iterator<Item = (&'a str, u32)> + Clone) -> Option<u32> {
+        let mut total = 0;
+        for term in &self.0 {
+            let term_score: u32 = fields
+                .clone()
+                .map(|(field, weight)| Self::field_score(term, field, weight))
+                .sum();
+            if term_score == 0 {
+                return None;
+            }
+            total += term_score;
+        }
+        Some(total)
+    }
+}
+
+fn collect_strings<'a>(value: &'a Value, strings: &mut Vec<&'a str>) {
+    match value {
+        Value::Text(text) => strings.push(text),
+        Value::Array(values) => values.iter().for_each(|v| collect_strings(v, strings)),
+        Value::Map(entries) => entries
+            .iter()
+            .for_each(|(_, v)| collect_strings(v, strings)),
+        _ => {}
+    }
+}
+
+impl AppState {
+    /// Puts fetched documents into the cache, those found missing are removed.
+    pub(super) async fn cache_documents(
+        &self,
+        query: &DocumentQuery,
+        documents: &BTreeMap<Identifier, Option<Document>>,
+    ) {
+        let mut cache = self.documents_cache.lock().await;
+        for (id, document) in documents {
+            match document {
+                Some(document) => {
+                    cache.insert(*id, (query.clone(), document.clone()));
+                }
+                None => {
+                    cache.remove(id);
+                }
+            }
+        }
+    }
+
+    /// Removes documents fetched by the query from the cache, including those
+    /// the query wouldn't return anymore.
+    pub(super) async fn uncache_documents(&self, query: &DocumentQuery) {
+        let key = query_key(query);
+        self.documents_cache
+            .lock()
+            .await
+            .retain(|_, (cached_query, _)| query_key(cached_query) != key);
+    }
+
+    async fn search(&self, query: &str) -> Vec<SearchHit> {
+        let terms = Terms::new(query);
+        if terms.0.is_empty() {
+            return Vec::new();
+        }
+
+        let mut hits = Vec::new();
+
+        for (name, contract) in self.known_contracts.lock().await.iter() {
+            let document_types = contract.document_types();
+            let property_names: Vec<&String> = document_types
+                .values()
+                .flat_map(|document_type| document_type.properties().keys())
+                .collect();
+            let fields = [(name.as_str(), CONTRACT_NAME_SCORE)]
+                .into_iter()
+                .chain(
+                    document_types
+                        .keys()
+                        .map(|type_name| (type_name.as_str(), DOCUMENT_TYPE_NAME_SCORE)),
+                )
+                .chain(
+                    property_names
+                        .iter()
+                        .map(|property| (property.as_str(), PROPERTY_NAME_SCORE)),
+                );
+            if let Some(score) = terms.score(fields) {
+                hits.push(SearchHit {
+                    score,
+                    summary: format!(
+                        "Contract {} ({})",
+                        name,
+                        contract.id().to_string(Encoding::Base58)
+                    ),
+                    target: SearchTarget::Contract {
+                        name: name.clone(),
+                        document_type_names: document_types.keys().cloned().collect(),
+                    },
+                });
+            }
+        }
+
+        for (id, (document_query, document)) in self.documents_cache.lock().await.iter() {
+            let mut strings = Vec::new();
+            document
+                .properties()
+                .values()
+                .for_each(|value| collect_strings(value, &mut strings));
+            let fields = strings.iter().map(|string| (*string, DOCUMENT_VALUE_SCORE));
+            if let Some(score) = terms.score(fields) {
+                hits.push(SearchHit {
+                    score,
+                    summary: format!(
+                        "Document {} of {}",
+                        id.to_string(Encoding::Base58),
+                        document_query.document_type_name
+                    ),
+                    target: SearchTarget::Document {
+                        query: document_query.clone(),
+                        document: document.clone(),
+                    },
+                });
+            }
+        }
+
+        hits.sort_by(|a, b| b.score.cmp(&a.score));
+        hits.truncate(MAX_RESULTS);
+        hits
+    }
+
+    pub(crate) async fn run_search_task(&self, task: SearchTask) -> BackendEvent {
+        match &task {
+            SearchTask::Search(query) => {
+                let hits = self.search(query).await;
+                BackendEvent::TaskCompleted {
+                    task: Task::Search(task),
+                    execution_result: Ok(CompletedTaskPayload::SearchResults(hits)),
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,138 @@
+//! Connection settings editable from the TUI.
+//! Edited settings are validated and saved into the `.env` file, as options of
+//! the selected network profile if any. The SDK and RPC clients are built once
+//! on start, so saved settings take effect on the next start or profile switch.
+
+use std::{collections::BTreeMap, str::FromStr};
+
+use dash_sdk::sdk::Uri;
+
+use super::{BackendEvent, CompletedTaskPayload, Task};
+use crate::config::Config;
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum SettingsTask {
+    ShowConnection,
+    SaveConnection(ConnectionSettings),
+}
+
+/// Settings of the connections to Platform and Core.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct ConnectionSettings {
+    /// Comma-separated DAPI addresses
+    pub(crate) dapi_addresses: String,
+    pub(crate) core_host: String,
+    pub(crate) core_rpc_port: u16,
+    pub(crate) core_rpc_user: String,
+    pub(crate) insight_api_url: String,
+    /// Whether a wallet private key is configured, the key itself is never
+    /// shown nor edited here
+    pub(crate) wallet_private_key_set: bool,
+}
+
+impl ConnectionSettings {
+    fn from_config(config: &Config) -> Self {
+        ConnectionSettings {
+            dapi_addresses: config.dapi_addresses.clone(),
+            core_host: config.core_host.clone(),
+            core_rpc_port: config.core_rpc_port,
+            core_rpc_user: config.core_rpc_user.clone(),
+            insight_api_url: config.insight_api_url.clone(),
+            wallet_private_key_set: config
+                .wallet_private_key
+                .as_ref()
+                .is_some_and(|key| !key.is_empty()),
+        }
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        let mut dapi_addresses = self
+            .dapi_addresses
+            .split(',')
+            .map(str::trim)
+            .filter(|address| !address.is_empty())
+            .peekable();
+        if dapi_addresses.peek().is_none() {
+            return Err("No DAPI addresses given".to_owned());
+        }
+        if let Some(address) = dapi_addresses.find(|address| !is_url(address)) {
+            return Err(format!("Invalid DAPI address: {}", address));
+        }
+
+        if self.core_host.trim().is_empty() || self.core_host.contains(char::is_whitespace) {
+            return Err(format!("Invalid Core host: {}", self.core_host));
+        }
+        if self.core_rpc_port == 0 {
+            return Err("Invalid Core RPC port: 0".to_owned());
+        }
+        if self.core_rpc_user.is_empty() {
+            return Err("Core RPC user is empty".to_owned());
+        }
+
+        if !is_url(&self.insight_api_url) {
+            return Err(format!("Invalid Insight API URL: {}", self.insight_api_url));
+        }
+        Ok(())
+    }
+
+    /// Settings one per line.
+    pub(crate) fn details(&self) -> String {
+        format!(
+            "DAPI addresses: {}\nCore host: {}\nCore RPC port: {}\nCore RPC user: {}\nInsight \
+             API URL: {}\nWallet private key: {}",
+            self.dapi_addresses.replace(',', ", "),
+            self.core_host,
+            self.core_rpc_port,
+            self.core_rpc_user,
+            self.insight_api_url,
+            if self.wallet_private_key_set {
+                "set"
+            } else {
+                "not set"
+            }
+        )
+    }
+}
+
+/// Whether the value is a full URL with scheme and host.
+fn is_url(value: &str) -> bool {
+    Uri::from_str(value).is_ok_and(|uri| uri.scheme().is_some() && uri.host().is_some())
+}
+
+fn save_connection_settings(
+    config: &Config,
+    settings: &ConnectionSettings,
+) -> Result<Vec<(String, String)>, String> {
+    settings.validate()?;
+    config.save_options(&[
+        ("DAPI_ADDRESSES", settings.dapi_addresses.clone()),
+        ("CORE_HOST", settings.core_host.clone()),
+        ("CORE_RPC_PORT", settings.core_rpc_port.to_string()),
+        ("CORE_RPC_USER", settings.core_rpc_user.clone()),
+        ("INSIGHT_API_URL", settings.insight_api_url.clone()),
+    ])
+}
+
+/// Saved options are recorded by full variable names for the next
+/// configuration load.
+pub(super) fn run_settings_task<'s>(
+    config: &Config,
+    saved_options: &mut BTreeMap<String, String>,
+    task: SettingsTask,
+) -> BackendEvent<'s> {
+    match task {
+        SettingsTask::ShowConnection => BackendEvent::TaskCompleted {
+            execution_result: Ok(CompletedTaskPayload::ConnectionSettings(
+                ConnectionSettings::from_config(config),
+            )),
+            task: Task::Settings(task),
+        },
+        SettingsTask::SaveConnection(ref settings) => BackendEvent::TaskCompleted {
+            execution_result: save_connection_settings(config, settings).map(|options| {
+                saved_options.extend(options);
+                CompletedTaskPayload::ConnectionSettings(settings.clone())
+            }),
+            task: Task::Settings(task),
+        },
+    }
+}
@@ -0,0 +1,219 @@
+//! Signing of state transitions.
+//! By default transitions are signed in process with the private keys kept in
+//! the application state. With a remote signer configured, signing requests
+//! made of the data digest and the key reference are sent over HTTP to an
+//! external signer service that holds the keys, so they never get into this
+//! process.
+//!
+//! The signer service receives a JSON POST request:
+//! `{"identity_id", "key_id", "key_type", "purpose", "security_level",
+//! "digest"}` with the base58 identity id and the hex encoded double SHA-256
+//! of the signable bytes, and responds with `{"signature"}`, a hex encoded
+//! signature in the format platform expects for the key type (65 bytes
+//! recoverable compact signature for ECDSA keys). Only ECDSA keys can be
+//! signed with remotely, a BLS or EdDSA signature isn't made over the double
+//! SHA-256 digest, and requests that take longer than the configured timeout
+//! fail.
+
+use std::time::Duration;
+
+use dpp::{
+    identity::{
+        accessors::IdentityGettersV0,
+        identity_public_key::accessors::v0::IdentityPublicKeyGettersV0, signer::Signer, Identity,
+        IdentityPublicKey, KeyType,
+    },
+    platform_value::{string_encoding::Encoding, BinaryData},
+    prelude::Identifier,
+    util::hash::hash_double,
+    ProtocolError,
+};
+use serde::{Deserialize, Serialize};
+use simple_signer::signer::SimpleSigner;
+use tokio::runtime::Handle;
+
+use super::{signing_audit::AuditedSigner, AppState};
+use crate::config::Config;
+
+#[derive(Debug, Serialize)]
+struct SignRequest {
+    identity_id: String,
+    key_id: u32,
+    key_type: String,
+    purpose: String,
+    security_level: String,
+    digest: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct SignResponse {
+    signature: String,
+}
+
+#[derive(Clone)]
+pub(crate) struct RemoteSigner {
+    url: String,
+    token: Option<String>,
+    client: reqwest::Client,
+    timeout: Duration,
+}
+
+impl std::fmt::Debug for RemoteSigner {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RemoteSigner")
+            .field("url", &self.url)
+            .finish_non_exhaustive()
+    }
+}
+
+impl RemoteSigner {
+    /// Remote signer of the configuration, `None` if signing is local.
+    pub(crate) fn from_config(config: &Config) -> Option<Self> {
+        config
+            .remote_signer_url
+            .as_ref()
+            .filter(|url| !url.is_empty())
+            .and_then(|url| {
+                let timeout = config.remote_signer_timeout();
+                let client = reqwest::Client::builder()
+                    .timeout(timeout)
+                    .connect_timeout(config.remote_signer_connect_timeout())
+                    .build()
+                    .map_err(|e| tracing::error!("Unable to set up the remote signer: {}", e))
+                    .ok()?;
+                Some(RemoteSigner {
+                    url: url.clone(),
+                    token: config.remote_signer_token.clone(),
+                    client,
+                    timeout,
+                })
+            })
+    }
+
+    async fn request_signature(
+        &self,
+        identity_id: Identifier,
+        identity_public_key: &IdentityPublicKey,
+        data: &[u8],
+    ) -> Result<Vec<u8>, String> {
+        let key_type = identity_public_key.key_type();
+        if !matches!(key_type, KeyType::ECDSA_SECP256K1 | KeyType::ECDSA_HASH160) {
+            return Err(format!(
+                "remote signer can't sign with {:?} key {}, only ECDSA keys are supported",
+                key_type,
+                identity_public_key.id()
+            ));
+        }
+
+        let request = SignRequest {
+            identity_id: identity_id.to_string(Encoding::Base58),
+            key_id: identity_public_key.id(),
+            key_type: format!("{:?}", key_type),
+            purpose: format!("{:?}", identity_public_key.purpose()),
+            security_level: format!("{:?}", identity_public_key.security_level()),
+            digest: hex::encode(hash_double(data)),
+        };
+
+        let mut builder = self.client.post(&self.url).json(&request);
+        if let Some(token) = &self.token {
+            builder = builder.bearer_auth(token);
+        }
+        let response = builder
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(|e| self.request_error(e))?;
+        let SignResponse { signature } = response.json().await.map_err(|e| {
+            if e.is_timeout() {
+                self.request_error(e)
+            } else {
+                format!("invalid remote signer response: {}", e)
+            }
+        })?;
+
+        hex::decode(signature).map_err(|e| format!("invalid remote signer signature: {}", e))
+    }
+
+    fn request_error(&self, error: reqwest::Error) -> String {
+        if error.is_timeout() {
+            format!(
+                "remote signer didn't respond within {} seconds",
+                self.timeout.as_secs()
+            )
+        } else {
+            format!("remote signer request failed: {}", error)
+        }
+    }
+}
+
+/// Signer of an identity's keys, either with local private keys or through the
+/// remote signer.
+#[derive(Debug)]
+pub(crate) enum IdentitySigner {
+    Local(SimpleSigner),
+    Remote {
+        identity_id: Identifier,
+        signer: RemoteSigner,
+    },
+}
+
+impl Signer for IdentitySigner {
+    fn sign(
+        &self,
+        identity_public_key: &IdentityPublicKey,
+        data: &[u8],
+    ) -> Result<BinaryData, ProtocolError> {
+        match self {
+            IdentitySigner::Local(signer) => signer.sign(identity_public_key, data),
+            IdentitySigner::Remote {
+                identity_id,
+                signer,
+            } => {
+                tracing::info!(
+                    identity_id = identity_id.to_string(Encoding::Base58),
+                    key_id = identity_public_key.id(),
+                    "requesting remote signature"
+                );
+                // Signer interface is synchronous while called from async
+                // tasks, so the worker thread is handed over while waiting
+                tokio::task::block_in_place(|| {
+                    Handle::current().block_on(signer.request_signature(
+                        *identity_id,
+                        identity_public_key,
+                        data,
+                    ))
+                })
+                .map(BinaryData::new)
+                .map_err(ProtocolError::Generic)
+            }
+        }
+    }
+}
+
+impl AppState {
+    /// Signer for keys of the identity: the remote signer if configured,
+    /// otherwise a local signer with all known private keys of the identity.
+    /// Signatures are recorded into the audit log under the transition name.
+    pub(crate) async fn identity_signer(
+        &self,
+        identity: &Identity,
+        transition: &'static str,
+    ) -> AuditedSigner<IdentitySigner> {
+        let signer = if let Some(signer) = &self.remote_signer {
+            IdentitySigner::Remote {
+                identity_id: identity.id(),
+                signer: signer.clone(),
+            }
+        } else {
+            let identity_private_keys = self.identity_private_keys.lock().await;
+            let mut signer = SimpleSigner::default();
+            for (key_id, public_key) in identity.public_keys() {
+                if let Some(private_key) = identity_private_keys.get(&(identity.id(), *key_id)) {
+                    signer.add_key(public_key.clone(), private_key.clone());
+                }
+            }
+            IdentitySigner::Local(signer)
+        };
+        AuditedSigner::new(signer, &self.signing_audit, identity.id(), transition)
+    }
+}
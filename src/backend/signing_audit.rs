@@ -0,0 +1,220 @@
+//! Audit trail of signing operations.
+//! Every signature made with identity keys is appended to a JSON lines file of
+//! the profile with the key used, the kind of transition, the digest of the
+//! signed data and the time, so the use of hot keys during a test campaign can
+//! be reviewed afterwards. Records are never rewritten, the log can be viewed
+//! and exported to CSV from the identities screen.
+
+use std::{
+    fs::{self, OpenOptions},
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+use chrono::{TimeZone, Utc};
+use dpp::{
+    identity::{
+        identity_public_key::accessors::v0::IdentityPublicKeyGettersV0, signer::Signer,
+        IdentityPublicKey,
+    },
+    platform_value::{string_encoding::Encoding, BinaryData},
+    prelude::Identifier,
+    util::hash::hash_double,
+    ProtocolError,
+};
+use serde::{Deserialize, Serialize};
+
+use super::reports::current_timestamp;
+use crate::logging::CorrelationId;
+
+const EXPORTS_DIR: &str = "supporting_files/signing_audit_exports";
+
+/// Most recent records shown in the app, the export has all of them.
+const DISPLAY_LIMIT: usize = 100;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct SigningRecord {
+    timestamp: u64,
+    identity_id: String,
+    key_id: u32,
+    key_type: String,
+    purpose: String,
+    security_level: String,
+    transition: String,
+    /// Hex encoded double SHA-256 of the signable bytes
+    digest: String,
+    correlation_id: Option<String>,
+    /// Set if the signer failed to sign
+    error: Option<String>,
+}
+
+impl SigningRecord {
+    fn time(&self) -> String {
+        Utc.timestamp_opt(self.timestamp as i64, 0)
+            .single()
+            .map(|time| time.to_rfc3339())
+            .unwrap_or_default()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct SigningAudit {
+    path: PathBuf,
+}
+
+impl SigningAudit {
+    pub(crate) fn new(path: PathBuf) -> Self {
+        SigningAudit { path }
+    }
+
+    /// Appends the record as one line, so records of concurrent signers don't
+    /// interleave.
+    fn append(&self, record: &SigningRecord) {
+        let result = serde_json::to_string(record)
+            .map_err(|e| e.to_string())
+            .and_then(|json| {
+                OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(&self.path)
+                    .and_then(|mut file| file.write_all(format!("{}\n", json).as_bytes()))
+                    .map_err(|e| e.to_string())
+            });
+        if let Err(e) = result {
+            tracing::error!("Failed to append to signing audit log: {}", e);
+        }
+    }
+
+    fn records(&self) -> Result<Vec<SigningRecord>, String> {
+        let content = match fs::read_to_string(&self.path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(format!("Unable to read signing audit log: {}", e)),
+        };
+        Ok(content
+            .lines()
+            .filter(|line| !line.is_empty())
+            .filter_map(|line| {
+                serde_json::from_str(line)
+                    .map_err(|e| tracing::warn!("Skipping malformed signing audit record: {}", e))
+                    .ok()
+            })
+            .collect())
+    }
+
+    /// Latest records first.
+    pub(crate) fn display(&self) -> Result<String, String> {
+        let records = self.records()?;
+        if records.is_empty() {
+            return Ok("No signing operations recorded yet".to_owned());
+        }
+        let mut lines = vec![format!(
+            "{} signing operations recorded, latest first:",
+            records.len()
+        )];
+        for record in records.iter().rev().take(DISPLAY_LIMIT) {
+            lines.push(String::new());
+            lines.push(format!("{} {}", record.time(), record.transition));
+            lines.push(format!(
+                "  identity {} key {} ({}, {}, {})",
+                record.identity_id,
+                record.key_id,
+                record.key_type,
+                record.purpose,
+                record.security_level
+            ));
+            lines.push(format!("  digest {}", record.digest));
+            if let Some(correlation_id) = &record.correlation_id {
+                lines.push(format!("  correlation ID {}", correlation_id));
+            }
+            if let Some(error) = &record.error {
+                lines.push(format!("  failed: {}", error));
+            }
+        }
+        Ok(lines.join("\n"))
+    }
+
+    /// Writes all records into a new CSV file of the exports directory,
+    /// returning its path.
+    pub(crate) fn export_csv(&self) -> Result<PathBuf, String> {
+        let mut lines = vec![
+            "timestamp,time,identity_id,key_id,key_type,purpose,security_level,transition,\
+             digest,correlation_id,error"
+                .to_owned(),
+        ];
+        for record in self.records()? {
+            lines.push(format!(
+                "{},{},{},{},{},{},{},{},{},{},\"{}\"",
+                record.timestamp,
+                record.time(),
+                record.identity_id,
+                record.key_id,
+                record.key_type,
+                record.purpose,
+                record.security_level,
+                record.transition,
+                record.digest,
+                record.correlation_id.as_deref().unwrap_or_default(),
+                record.error.unwrap_or_default().replace('"', "\"\"")
+            ));
+        }
+        lines.push(String::new());
+
+        fs::create_dir_all(EXPORTS_DIR)
+            .map_err(|e| format!("Failed to create exports directory: {}", e))?;
+        let path =
+            Path::new(EXPORTS_DIR).join(format!("signing_audit_{}.csv", current_timestamp()));
+        fs::write(&path, lines.join("\n"))
+            .map_err(|e| format!("Failed to write export file: {}", e))?;
+        Ok(path)
+    }
+}
+
+/// Signer recording every signature it makes into the audit log.
+#[derive(Debug)]
+pub(crate) struct AuditedSigner<S> {
+    signer: S,
+    audit: SigningAudit,
+    identity_id: Identifier,
+    /// Kind of state transition signed, for the log only
+    transition: &'static str,
+}
+
+impl<S> AuditedSigner<S> {
+    pub(crate) fn new(
+        signer: S,
+        audit: &SigningAudit,
+        identity_id: Identifier,
+        transition: &'static str,
+    ) -> Self {
+        AuditedSigner {
+            signer,
+            audit: audit.clone(),
+            identity_id,
+            transition,
+        }
+    }
+}
+
+impl<S: Signer> Signer for AuditedSigner<S> {
+    fn sign(
+        &self,
+        identity_public_key: &IdentityPublicKey,
+        data: &[u8],
+    ) -> Result<BinaryData, ProtocolError> {
+        let result = self.signer.sign(identity_public_key, data);
+        self.audit.append(&SigningRecord {
+            timestamp: current_timestamp(),
+            identity_id: self.identity_id.to_string(Encoding::Base58),
+            key_id: identity_public_key.id(),
+            key_type: format!("{:?}", identity_public_key.key_type()),
+            purpose: format!("{:?}", identity_public_key.purpose()),
+            security_level: format!("{:?}", identity_public_key.security_level()),
+            transition: self.transition.to_owned(),
+            digest: hex::encode(hash_double(data)),
+            correlation_id: CorrelationId::current().map(|id| id.to_string()),
+            error: result.as_ref().err().map(|e| e.to_string()),
+        });
+        result
+    }
+}
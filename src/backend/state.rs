@@ -29,8 +29,33 @@ use strategy_tests::Strategy;
 use tokio::sync::Mutex;
 use walkdir::{DirEntry, WalkDir};
 
-use super::wallet::{add_wallet_by_private_key, Wallet};
-use crate::{backend::insight::InsightAPIClient, config::Config};
+use super::{
+    asset_locks::AssetLocks,
+    bookmarks::Bookmarks,
+    documents::{joins::JoinRules, prefetch::PrefetchedDocuments},
+    json_store::JsonStore,
+    keep_funded::KeepFunded,
+    known_entities,
+    network::rewards::EvonodeRewardsLog,
+    network_changelog::NetworkChangelog,
+    petnames::Petnames,
+    reorg::ReorgWatch,
+    schema_builder::DocumentTypeDraft,
+    search::DocumentsCache,
+    signer::RemoteSigner,
+    signing_audit::SigningAudit,
+    strategies::{
+        funding::FundingPlan, padding::StrategyPadding, phases::StrategyPhases,
+        safety::SafetyLimits,
+    },
+    task_history::TaskHistory,
+    tutorial::Tutorial,
+    wallet::{add_wallet_by_private_key, profiles::WalletProfiles, FeeSettings, Wallet},
+};
+use crate::{
+    backend::insight::InsightAPIClient,
+    config::{AssetLockProofKind, Config},
+};
 
 const CURRENT_PROTOCOL_VERSION: ProtocolVersion = 1;
 
@@ -50,6 +75,9 @@ pub type IdentityPrivateKeysMap = BTreeMap<(Identifier, KeyID), Vec<u8>>;
 // finishing
 // #[derive(Debug)]
 pub struct AppState {
+    /// Profile (network) the state belongs to, a state is never loaded for a
+    /// different profile so keys can't be used on a wrong network
+    pub profile: String,
     pub loaded_identity: Mutex<Option<Identity>>,
     pub identity_private_keys: Mutex<IdentityPrivateKeysMap>,
     pub loaded_wallet: Mutex<Option<Wallet>>,
@@ -75,10 +103,59 @@ pub struct AppState {
     >,
     pub identity_asset_lock_private_key_in_top_up:
         Mutex<Option<(Transaction, PrivateKey, Option<AssetLockProof>)>>,
+    /// Document type schema being built, not persisted
+    pub schema_draft: Mutex<DocumentTypeDraft>,
+    pub keep_funded: Mutex<KeepFunded>,
+    pub tutorial: Mutex<Option<Tutorial>>,
+    pub documents_cache: Mutex<DocumentsCache>,
+    /// Results of queries fetched ahead while the UI is idle, not persisted
+    pub prefetched_documents: Mutex<PrefetchedDocuments>,
+    /// Wallet transactions affected by core chain reorgs, not persisted
+    pub reorg_watch: Mutex<ReorgWatch>,
+    /// Fee rate for wallet transactions, not persisted
+    pub fee_settings: Mutex<FeeSettings>,
+    /// Funding prepared for a strategy run, not persisted
+    pub funding_plan: Mutex<Option<FundingPlan>>,
+    /// Tasks submitted from the UI, not persisted
+    pub(crate) task_history: Mutex<TaskHistory>,
+    /// Kept in a separate file of the profile
+    pub bookmarks: Mutex<JsonStore<Bookmarks>>,
+    /// Kept in a separate file of the profile
+    pub asset_locks: Mutex<JsonStore<AssetLocks>>,
+    /// Kept in a separate file of the profile
+    pub(crate) wallet_profiles: Mutex<JsonStore<WalletProfiles>>,
+    /// Kept in a separate file of the profile
+    pub network_changelog: Mutex<JsonStore<NetworkChangelog>>,
+    /// Kept in a separate file of the profile
+    pub(crate) evonode_rewards: Mutex<JsonStore<EvonodeRewardsLog>>,
+    /// Kept in a separate file of the profile
+    pub(crate) petnames: Mutex<JsonStore<Petnames>>,
+    /// Kept in a separate file of the profile
+    pub(crate) join_rules: Mutex<JoinRules>,
+    /// Kept in a separate file of the profile
+    pub(crate) strategy_phases: Mutex<JsonStore<StrategyPhases>>,
+    /// Kept in a separate file of the profile
+    pub(crate) strategy_padding: Mutex<JsonStore<StrategyPadding>>,
+    /// Kept in a separate file of the profile
+    pub(crate) signing_audit: SigningAudit,
+    /// Signer service used instead of local private keys, from configuration
+    pub(crate) remote_signer: Option<RemoteSigner>,
+    /// Limits of unattended strategy runs, from configuration
+    pub(crate) strategy_safety: SafetyLimits,
+    /// Core network of addresses, from configuration
+    pub(crate) core_network: Network,
+    /// How asset locks are proven, from configuration
+    pub(crate) asset_lock_proof: AssetLockProofKind,
 }
 
-impl Default for AppState {
-    fn default() -> Self {
+impl AppState {
+    fn new(config: &Config) -> Self {
+        Self::new_with_drive_path(config, config.drive_path())
+    }
+
+    /// Fresh state with the local Drive stored at a custom location, tests use
+    /// it to keep away from the profile's Drive.
+    pub(crate) fn new_with_drive_path(config: &Config, drive_path: PathBuf) -> Self {
         let platform_version = PlatformVersion::get(CURRENT_PROTOCOL_VERSION).unwrap();
 
         // Helper function to check if a file is a JSON file
@@ -105,7 +182,7 @@ impl Default for AppState {
         }
 
         let (drive, _protocol_version) =
-            Drive::open("explorer.drive", None).expect("expected to open Drive successfully");
+            Drive::open(drive_path, None).expect("expected to open Drive successfully");
 
         if drive
             .grove
@@ -119,6 +196,7 @@ impl Default for AppState {
         }
 
         AppState {
+            profile: config.profile_name().to_owned(),
             loaded_identity: None.into(),
             identity_private_keys: Default::default(),
             loaded_wallet: Mutex::new(None),
@@ -131,10 +209,49 @@ impl Default for AppState {
             identity_asset_lock_private_key_in_creation: None.into(),
             identity_asset_lock_private_key_in_top_up: None.into(),
             available_strategies_contract_names: BTreeMap::new().into(),
+            schema_draft: Default::default(),
+            keep_funded: KeepFunded::load(config.keep_funded_file_path()).into(),
+            tutorial: Default::default(),
+            documents_cache: Default::default(),
+            prefetched_documents: Default::default(),
+            reorg_watch: Default::default(),
+            fee_settings: Default::default(),
+            funding_plan: Default::default(),
+            task_history: Default::default(),
+            bookmarks: JsonStore::load(config.bookmarks_file_path(), "bookmarks").into(),
+            asset_locks: AssetLocks::load(config.asset_locks_file_path(), config.core_network())
+                .into(),
+            wallet_profiles: JsonStore::load(config.wallets_file_path(), "wallets").into(),
+            network_changelog: JsonStore::load(
+                config.network_changelog_file_path(),
+                "network changelog",
+            )
+            .into(),
+            evonode_rewards: JsonStore::load(
+                config.evonode_rewards_file_path(),
+                "evonode rewards log",
+            )
+            .into(),
+            petnames: Petnames::load(config.petnames_file_path()).into(),
+            join_rules: JoinRules::load(config.join_rules_file_path()).into(),
+            strategy_phases: JsonStore::load(config.strategy_phases_file_path(), "strategy phases")
+                .into(),
+            strategy_padding: JsonStore::load(
+                config.strategy_padding_file_path(),
+                "strategy padding",
+            )
+            .into(),
+            signing_audit: SigningAudit::new(config.signing_audit_file_path()),
+            remote_signer: RemoteSigner::from_config(config),
+            strategy_safety: SafetyLimits::from_config(config),
+            core_network: config.core_network(),
+            asset_lock_proof: config.asset_lock_proof,
         }
     }
 }
 
+/// The profile is written after the state rather than in it, so state files
+/// written before profiles existed are still decoded.
 #[derive(Clone, Debug, Encode, Decode)]
 struct AppStateInSerializationFormat {
     pub loaded_identity: Option<Identity>,
@@ -172,6 +289,7 @@ impl PlatformSerializableWithPlatformVersion for AppState {
         platform_version: &PlatformVersion,
     ) -> Result<Vec<u8>, ProtocolError> {
         let AppState {
+            profile,
             loaded_identity,
             identity_private_keys,
             loaded_wallet,
@@ -184,6 +302,29 @@ impl PlatformSerializableWithPlatformVersion for AppState {
             identity_asset_lock_private_key_in_creation,
             available_strategies_contract_names,
             identity_asset_lock_private_key_in_top_up,
+            schema_draft: _,
+            keep_funded: _,
+            tutorial: _,
+            documents_cache: _,
+            prefetched_documents: _,
+            reorg_watch: _,
+            fee_settings: _,
+            funding_plan: _,
+            task_history: _,
+            bookmarks: _,
+            asset_locks: _,
+            wallet_profiles,
+            network_changelog: _,
+            evonode_rewards: _,
+            petnames: _,
+            join_rules: _,
+            strategy_phases: _,
+            strategy_padding: _,
+            signing_audit: _,
+            remote_signer: _,
+            strategy_safety: _,
+            core_network: _,
+            asset_lock_proof: _,
         } = self;
 
         let known_contracts_in_serialization_format = known_contracts
@@ -245,7 +386,12 @@ impl PlatformSerializableWithPlatformVersion for AppState {
         let app_state_in_serialization_format = AppStateInSerializationFormat {
             loaded_identity: loaded_identity.blocking_lock().clone(),
             identity_private_keys: identity_private_keys.blocking_lock().clone(),
-            loaded_wallet: loaded_wallet.blocking_lock().clone(),
+            // Keys of registered wallets are only kept encrypted, they're switched
+            // to again after a restart
+            loaded_wallet: loaded_wallet
+                .blocking_lock()
+                .clone()
+                .filter(|wallet| wallet_profiles.blocking_lock().label_of(wallet).is_none()),
             known_identities: known_identities.blocking_lock().clone(),
             known_contracts: known_contracts_in_serialization_format,
             supporting_contracts: supporting_contracts_in_serialization_format,
@@ -261,31 +407,44 @@ impl PlatformSerializableWithPlatformVersion for AppState {
         let config = bincode::config::standard()
             .with_big_endian()
             .with_no_limit();
-        bincode::encode_to_vec(app_state_in_serialization_format, config).map_err(|e| {
-            PlatformSerializationError(format!("unable to serialize App State: {}", e))
-        })
+        let mut bytes =
+            bincode::encode_to_vec(app_state_in_serialization_format, config).map_err(|e| {
+                PlatformSerializationError(format!("unable to serialize App State: {}", e))
+            })?;
+        bytes.extend(bincode::encode_to_vec(profile, config).map_err(|e| {
+            PlatformSerializationError(format!("unable to serialize profile: {}", e))
+        })?);
+        Ok(bytes)
     }
 }
 
-impl PlatformDeserializableWithPotentialValidationFromVersionedStructure for AppState {
-    fn versioned_deserialize(
+impl AppState {
+    fn versioned_deserialize_for_profile(
         data: &[u8],
         validate: bool,
         platform_version: &PlatformVersion,
-    ) -> Result<Self, ProtocolError>
-    where
-        Self: Sized,
-    {
+        app_config: &Config,
+    ) -> Result<Self, ProtocolError> {
         let config = bincode::config::standard()
             .with_big_endian()
             .with_no_limit();
-        let app_state: AppStateInSerializationFormat =
-            bincode::borrow_decode_from_slice(data, config)
+        let (app_state, read): (AppStateInSerializationFormat, usize) =
+            bincode::borrow_decode_from_slice(data, config).map_err(|e| {
+                let msg = format!("Error decoding AppStateInSerializationFormat: {}", e);
+                PlatformDeserializationError(msg)
+            })?;
+
+        // State files written before profiles existed belong to the configured
+        // one, as they were already named after the network
+        let profile = if read < data.len() {
+            bincode::decode_from_slice::<String, _>(&data[read..], config)
                 .map_err(|e| {
-                    let msg = format!("Error decoding AppStateInSerializationFormat: {}", e);
-                    PlatformDeserializationError(msg)
+                    PlatformDeserializationError(format!("Error decoding profile: {}", e))
                 })?
-                .0;
+                .0
+        } else {
+            app_config.profile_name().to_owned()
+        };
 
         let AppStateInSerializationFormat {
             loaded_identity,
@@ -301,6 +460,14 @@ impl PlatformDeserializableWithPotentialValidationFromVersionedStructure for App
             identity_asset_lock_private_key_in_top_up,
         } = app_state;
 
+        if profile != app_config.profile_name() {
+            return Err(PlatformDeserializationError(format!(
+                "State file belongs to profile {}, but {} is configured",
+                profile,
+                app_config.profile_name()
+            )));
+        }
+
         let known_contracts = known_contracts
             .into_iter()
             .map(|(key, contract)| {
@@ -361,8 +528,7 @@ impl PlatformDeserializableWithPotentialValidationFromVersionedStructure for App
                     (
                         Transaction::deserialize(&transaction)
                             .expect("expected to deserialize transaction"),
-                        // TODO: Should use network from config
-                        PrivateKey::from_slice(&private_key, Network::Testnet)
+                        PrivateKey::from_slice(&private_key, app_config.core_network())
                             .expect("expected private key"),
                         asset_lock_proof,
                         identity_info,
@@ -375,22 +541,25 @@ impl PlatformDeserializableWithPotentialValidationFromVersionedStructure for App
                 (
                     Transaction::deserialize(&transaction)
                         .expect("expected to deserialize transaction"),
-                    // TODO: Should use network from config
-                    PrivateKey::from_slice(&private_key, Network::Testnet)
+                    PrivateKey::from_slice(&private_key, app_config.core_network())
                         .expect("expected private key"),
                     asset_lock_proof,
                 )
             });
 
-        let (drive, _protocol_version) =
-            Drive::open("explorer.drive", None).expect("expected to open Drive successfully");
+        let (drive, _protocol_version) = Drive::open(app_config.drive_path(), None)
+            .expect("expected to open Drive successfully");
 
         // Deserialize the wallet state and wrap it in Arc<Mutex<_>>
         let deserialized_wallet_state = loaded_wallet
-            .map(|wallet| Mutex::new(Some(wallet)))
+            .map(|mut wallet| {
+                wallet.set_network(app_config.core_network());
+                Mutex::new(Some(wallet))
+            })
             .unwrap_or_else(|| Mutex::new(None));
 
         Ok(AppState {
+            profile,
             loaded_identity: loaded_identity.into(),
             identity_private_keys: identity_private_keys.into(),
             loaded_wallet: deserialized_wallet_state,
@@ -405,6 +574,49 @@ impl PlatformDeserializableWithPotentialValidationFromVersionedStructure for App
                 identity_asset_lock_private_key_in_creation.into(),
             identity_asset_lock_private_key_in_top_up: identity_asset_lock_private_key_in_top_up
                 .into(),
+            schema_draft: Default::default(),
+            keep_funded: KeepFunded::load(app_config.keep_funded_file_path()).into(),
+            tutorial: Default::default(),
+            documents_cache: Default::default(),
+            prefetched_documents: Default::default(),
+            reorg_watch: Default::default(),
+            fee_settings: Default::default(),
+            funding_plan: Default::default(),
+            task_history: Default::default(),
+            bookmarks: JsonStore::load(app_config.bookmarks_file_path(), "bookmarks").into(),
+            asset_locks: AssetLocks::load(
+                app_config.asset_locks_file_path(),
+                app_config.core_network(),
+            )
+            .into(),
+            wallet_profiles: JsonStore::load(app_config.wallets_file_path(), "wallets").into(),
+            network_changelog: JsonStore::load(
+                app_config.network_changelog_file_path(),
+                "network changelog",
+            )
+            .into(),
+            evonode_rewards: JsonStore::load(
+                app_config.evonode_rewards_file_path(),
+                "evonode rewards log",
+            )
+            .into(),
+            petnames: Petnames::load(app_config.petnames_file_path()).into(),
+            join_rules: JoinRules::load(app_config.join_rules_file_path()).into(),
+            strategy_phases: JsonStore::load(
+                app_config.strategy_phases_file_path(),
+                "strategy phases",
+            )
+            .into(),
+            strategy_padding: JsonStore::load(
+                app_config.strategy_padding_file_path(),
+                "strategy padding",
+            )
+            .into(),
+            signing_audit: SigningAudit::new(app_config.signing_audit_file_path()),
+            remote_signer: RemoteSigner::from_config(app_config),
+            strategy_safety: SafetyLimits::from_config(app_config),
+            core_network: app_config.core_network(),
+            asset_lock_proof: app_config.asset_lock_proof,
         })
     }
 }
@@ -414,44 +626,44 @@ impl AppState {
         let path = config.state_file_path();
 
         let Ok(read_result) = fs::read(path.clone()) else {
-            let state = AppState::default();
-            if let Some(private_key) = &config.wallet_private_key {
-                let wallet_state = &state.loaded_wallet;
-                add_wallet_by_private_key(&wallet_state, private_key).await;
-            }
+            let state = AppState::new(config);
+            state.load_configured_wallet(config).await;
+            state.load_known_entities(config).await;
             return state;
         };
 
-        let Ok(app_state) = AppState::versioned_deserialize(
+        let app_state = match AppState::versioned_deserialize_for_profile(
             read_result.as_slice(),
             false,
             PlatformVersion::get(CURRENT_PROTOCOL_VERSION).unwrap(),
-        ) else {
-            let start = SystemTime::now();
-            let since_the_epoch = start
-                .duration_since(UNIX_EPOCH)
-                .expect("Time went backwards");
-            let timestamp = since_the_epoch.as_secs();
-
-            let backup_path_str = format!("{}.{}.backup", path.display(), timestamp);
-            let backup_path = PathBuf::from(backup_path_str);
-
-            if let Err(e) = fs::rename(path, &backup_path) {
-                eprintln!("Failed to backup old file: {}", e);
-            }
-
-            let state = AppState::default();
-            if let Some(private_key) = &config.wallet_private_key {
-                let wallet_state = &state.loaded_wallet;
-                add_wallet_by_private_key(&wallet_state, private_key).await;
+            config,
+        ) {
+            Ok(app_state) => app_state,
+            Err(e) => {
+                tracing::error!("Unable to load state file: {}", e);
+
+                let start = SystemTime::now();
+                let since_the_epoch = start
+                    .duration_since(UNIX_EPOCH)
+                    .expect("Time went backwards");
+                let timestamp = since_the_epoch.as_secs();
+
+                let backup_path_str = format!("{}.{}.backup", path.display(), timestamp);
+                let backup_path = PathBuf::from(backup_path_str);
+
+                if let Err(e) = fs::rename(path, &backup_path) {
+                    eprintln!("Failed to backup old file: {}", e);
+                }
+
+                let state = AppState::new(config);
+                state.load_configured_wallet(config).await;
+                state.load_known_entities(config).await;
+                return state;
             }
-            return state;
         };
 
-        if let Some(private_key) = &config.wallet_private_key {
-            let wallet_state = &app_state.loaded_wallet;
-            add_wallet_by_private_key(&wallet_state, private_key).await;
-        }
+        app_state.load_configured_wallet(config).await;
+        app_state.load_known_entities(config).await;
 
         // Load supporting contracts
         let platform_version = PlatformVersion::get(CURRENT_PROTOCOL_VERSION).unwrap();
@@ -477,6 +689,52 @@ impl AppState {
         app_state
     }
 
+    async fn load_configured_wallet(&self, config: &Config) {
+        if let Some(private_key) = &config.wallet_private_key {
+            if let Err(e) =
+                add_wallet_by_private_key(&self.loaded_wallet, private_key, config.core_network())
+                    .await
+            {
+                tracing::error!("Unable to load configured wallet: {}", e);
+            }
+        }
+    }
+
+    /// Replaces known contracts and identities with the snapshot written on
+    /// their latest change, which is never older than the state file.
+    async fn load_known_entities(&self, config: &Config) {
+        let platform_version = PlatformVersion::get(CURRENT_PROTOCOL_VERSION).unwrap();
+        if let Some((contracts, identities)) =
+            known_entities::load(&config.known_entities_file_path(), platform_version)
+        {
+            *self.known_contracts.lock().await = contracts;
+            *self.known_identities.lock().await = identities;
+        }
+    }
+
+    /// Writes the snapshot of known contracts and identities, taking contracts
+    /// from the caller if it holds their lock already.
+    pub(super) async fn save_known_entities(
+        &self,
+        config: &Config,
+        known_contracts: Option<&KnownContractsMap>,
+    ) {
+        let platform_version = PlatformVersion::get(CURRENT_PROTOCOL_VERSION).unwrap();
+        let path = config.known_entities_file_path();
+        let identities = self.known_identities.lock().await;
+        match known_contracts {
+            Some(contracts) => {
+                known_entities::save(&path, contracts, &identities, platform_version)
+            }
+            None => known_entities::save(
+                &path,
+                &*self.known_contracts.lock().await,
+                &identities,
+                platform_version,
+            ),
+        }
+    }
+
     /// Used in backend destructor, must not panic
     pub fn save(&self, config: &Config) {
         let platform_version = PlatformVersion::get(CURRENT_PROTOCOL_VERSION).unwrap();
@@ -1,6 +1,19 @@
 //! Strategies management backend module.
 
+mod churn;
+pub(crate) mod forecast;
+pub(crate) mod funding;
+pub(crate) mod pacing;
+pub(crate) mod padding;
+pub(crate) mod phases;
+mod progress;
+pub(crate) mod resources;
+pub(crate) mod safety;
+pub(crate) mod templates;
+mod variants;
+
 use std::{
+    cell::RefCell,
     collections::{BTreeMap, BTreeSet, VecDeque},
     fs::File,
     io::Write,
@@ -29,7 +42,10 @@ use dpp::{
     data_contract::{
         accessors::v0::{DataContractV0Getters, DataContractV0Setters},
         created_data_contract::CreatedDataContract,
-        document_type::random_document::{DocumentFieldFillSize, DocumentFieldFillType},
+        document_type::{
+            accessors::DocumentTypeV0Getters,
+            random_document::{DocumentFieldFillSize, DocumentFieldFillType},
+        },
         DataContract,
     },
     identity::{
@@ -45,7 +61,7 @@ use dpp::{
         documents_batch_transition::{
             document_base_transition::v0::v0_methods::DocumentBaseTransitionV0Methods,
             document_transition::DocumentTransition, DocumentCreateTransition,
-            DocumentsBatchTransition,
+            DocumentReplaceTransition, DocumentsBatchTransition,
         },
         StateTransition, StateTransitionLike,
     },
@@ -71,10 +87,26 @@ use strategy_tests::{
 };
 use tokio::sync::{Mutex, MutexGuard};
 
-use crate::backend::{wallet::SingleKeyWallet, Wallet};
+use self::{
+    churn::DocumentPool,
+    funding::AssetLocksNeeded,
+    pacing::BlockPacer,
+    padding::TransitionPadder,
+    phases::{RunPhases, SteadyWindow},
+    progress::RunProgress,
+    resources::ResourceSampler,
+    safety::HaltSnapshot,
+    templates::{StrategyTemplate, TemplateParameters},
+    variants::run_contract_variants,
+};
+use crate::{
+    backend::{wallet::SingleKeyWallet, Wallet},
+    logging::{traced_dapi_request, CorrelationId},
+};
 
 use super::{
     insight::InsightAPIClient,
+    reports::{self, StrategyReport},
     state::{ContractFileName, KnownContractsMap},
     AppState, AppStateUpdate, BackendEvent, StrategyCompletionResult, StrategyContractNames, Task,
 };
@@ -82,6 +114,8 @@ use super::{
 #[derive(Debug, PartialEq, Clone)]
 pub enum StrategyTask {
     CreateStrategy(String),
+    /// Strategy name, template and its parameters
+    CreateStrategyFromTemplate(String, StrategyTemplate, TemplateParameters),
     ImportStrategy(String),
     ExportStrategy(String),
     SelectStrategy(String),
@@ -101,18 +135,38 @@ pub enum StrategyTask {
         add_transfer_key: bool,
     },
     SetStartIdentitiesBalance(String, u64),
+    /// Strategy name and warm-up and cool-down phases left out of run
+    /// statistics
+    SetRunPhases(String, RunPhases),
+    /// Strategy name and target serialized size in bytes of its document
+    /// transitions, 0 to leave them unpadded
+    SetTransitionPadding(String, u32),
     AddOperation {
         strategy_name: String,
         operation: Operation,
     },
     RegisterDocsToAllContracts(String, u16, DocumentFieldFillSize, DocumentFieldFillType),
-    RunStrategy(String, u64, bool, bool),
+    /// Strategy name, blocks or seconds count, verify proofs, block mode and
+    /// optional target of transitions per observed block
+    RunStrategy(String, u64, bool, bool, Option<u16>),
+    /// Strategy name, verify proofs and optional target of transitions per
+    /// observed block of the halted run to resume
+    ResumeStrategy(String, bool, Option<u16>),
+    /// Strategy name, blocks or seconds count of each run, verify proofs and
+    /// block mode to run the strategy against each variant of its start
+    /// contracts
+    RunContractVariants(String, u64, bool, bool),
+    CompareReports(String, String),
     RemoveLastContract(String),
     ClearContracts(String),
     ClearOperations(String),
     RemoveIdentityInserts(String),
     RemoveStartIdentities(String),
     RemoveLastOperation(String),
+    /// Strategy name and blocks count to plan actors funding for
+    PlanFunding(String, u64),
+    /// Execute the next step of the funding plan
+    ExecuteFundingStep,
 }
 
 pub async fn run_strategy_task<'s>(
@@ -120,6 +174,21 @@ pub async fn run_strategy_task<'s>(
     app_state: &'s AppState,
     task: StrategyTask,
     insight: &'s InsightAPIClient,
+) -> BackendEvent<'s> {
+    match task {
+        task @ StrategyTask::RunContractVariants(..) => {
+            run_contract_variants(sdk, app_state, task, insight).await
+        }
+        task => run_single_strategy_task(sdk, app_state, task, insight).await,
+    }
+}
+
+/// Runs a task that doesn't involve other strategy tasks.
+async fn run_single_strategy_task<'s>(
+    sdk: &Sdk,
+    app_state: &'s AppState,
+    task: StrategyTask,
+    insight: &'s InsightAPIClient,
 ) -> BackendEvent<'s> {
     match task {
         StrategyTask::CreateStrategy(strategy_name) => {
@@ -142,6 +211,41 @@ pub async fn run_strategy_task<'s>(
                 }),
             ))
         }
+        StrategyTask::CreateStrategyFromTemplate(strategy_name, template, parameters) => {
+            let contract = match &parameters.contract_name {
+                Some(contract_name) => app_state
+                    .known_contracts
+                    .lock()
+                    .await
+                    .get(contract_name)
+                    .cloned(),
+                None => None,
+            };
+
+            match templates::instantiate(template, &parameters, contract, sdk.version()) {
+                Ok((strategy, strategy_contract_names)) => {
+                    let mut strategies_lock = app_state.available_strategies.lock().await;
+                    let mut contract_names_lock =
+                        app_state.available_strategies_contract_names.lock().await;
+                    let mut selected_strategy_lock = app_state.selected_strategy.lock().await;
+
+                    strategies_lock.insert(strategy_name.clone(), strategy);
+                    *selected_strategy_lock = Some(strategy_name.clone());
+                    contract_names_lock.insert(strategy_name.clone(), strategy_contract_names);
+
+                    BackendEvent::AppStateUpdated(AppStateUpdate::SelectedStrategy(
+                        strategy_name.clone(),
+                        MutexGuard::map(strategies_lock, |strategies| {
+                            strategies.get_mut(&strategy_name).expect("strategy exists")
+                        }),
+                        MutexGuard::map(contract_names_lock, |names| {
+                            names.get_mut(&strategy_name).expect("inconsistent data")
+                        }),
+                    ))
+                }
+                Err(error) => BackendEvent::StrategyError { error },
+            }
+        }
         StrategyTask::ImportStrategy(url) => {
             match reqwest::get(&url).await {
                 Ok(response) => {
@@ -322,6 +426,16 @@ pub async fn run_strategy_task<'s>(
             if strategies_lock.contains_key(&strategy_name) {
                 strategies_lock.remove(&strategy_name);
                 contract_names_lock.remove(&strategy_name);
+                app_state
+                    .strategy_phases
+                    .lock()
+                    .await
+                    .remove(&strategy_name);
+                app_state
+                    .strategy_padding
+                    .lock()
+                    .await
+                    .remove(&strategy_name);
 
                 // If the deleted strategy was the selected one, unset the selected strategy
                 if let Some(selected) = selected_strategy_lock.as_ref() {
@@ -356,6 +470,16 @@ pub async fn run_strategy_task<'s>(
 
                     strategies_lock.insert(new_strategy_name.clone(), cloned_strategy);
                     contract_names_lock.insert(new_strategy_name.clone(), cloned_display_data);
+                    app_state
+                        .strategy_phases
+                        .lock()
+                        .await
+                        .copy(selected_strategy_name, &new_strategy_name);
+                    app_state
+                        .strategy_padding
+                        .lock()
+                        .await
+                        .copy(selected_strategy_name, &new_strategy_name);
 
                     *selected_strategy_lock = Some(new_strategy_name.clone());
 
@@ -777,12 +901,101 @@ pub async fn run_strategy_task<'s>(
                 }
             }
         }
-        StrategyTask::RunStrategy(
-            strategy_name,
-            num_blocks_or_seconds,
-            verify_proofs,
-            block_mode,
-        ) => {
+        StrategyTask::SetRunPhases(ref strategy_name, phases) => {
+            let execution_result = if app_state
+                .available_strategies
+                .lock()
+                .await
+                .contains_key(strategy_name)
+            {
+                app_state
+                    .strategy_phases
+                    .lock()
+                    .await
+                    .set(strategy_name, phases)
+                    .map(|_| {
+                        if phases.is_empty() {
+                            "Warm-up and cool-down removed".into()
+                        } else {
+                            phases.display("blocks or seconds").into()
+                        }
+                    })
+            } else {
+                Err("Strategy doesn't exist in app state.".to_owned())
+            };
+            BackendEvent::TaskCompleted {
+                task: Task::Strategy(task),
+                execution_result,
+            }
+        }
+        StrategyTask::SetTransitionPadding(ref strategy_name, target) => {
+            let execution_result = if app_state
+                .available_strategies
+                .lock()
+                .await
+                .contains_key(strategy_name)
+            {
+                app_state
+                    .strategy_padding
+                    .lock()
+                    .await
+                    .set(strategy_name, target)
+                    .map(|_| {
+                        if target == 0 {
+                            "Transition padding removed".into()
+                        } else {
+                            padding::display_target(target).into()
+                        }
+                    })
+            } else {
+                Err("Strategy doesn't exist in app state.".to_owned())
+            };
+            BackendEvent::TaskCompleted {
+                task: Task::Strategy(task),
+                execution_result,
+            }
+        }
+        StrategyTask::CompareReports(ref report_a, ref report_b) => {
+            let execution_result = reports::load_report(report_a).and_then(|a| {
+                reports::load_report(report_b).map(|b| reports::compare_reports(&a, &b).into())
+            });
+            BackendEvent::TaskCompleted {
+                task: Task::Strategy(task),
+                execution_result,
+            }
+        }
+        task @ (StrategyTask::RunStrategy(..) | StrategyTask::ResumeStrategy(..)) => {
+            let (
+                strategy_name,
+                num_blocks_or_seconds,
+                verify_proofs,
+                block_mode,
+                transitions_per_block,
+                resumed_progress,
+            ) = match task {
+                StrategyTask::RunStrategy(name, count, verify_proofs, block_mode, per_block) => {
+                    (name, count, verify_proofs, block_mode, per_block, None)
+                }
+                StrategyTask::ResumeStrategy(name, verify_proofs, per_block) => {
+                    match RunProgress::load(&name) {
+                        Ok(progress) => (
+                            name,
+                            progress.remaining,
+                            verify_proofs,
+                            progress.block_mode,
+                            per_block,
+                            Some(progress),
+                        ),
+                        Err(error) => return BackendEvent::StrategyError { error },
+                    }
+                }
+                _ => unreachable!("only strategy runs are matched"),
+            };
+            let phases = app_state.strategy_phases.lock().await.get(&strategy_name);
+            if let Err(error) = phases.validate(num_blocks_or_seconds) {
+                return BackendEvent::StrategyError { error };
+            }
+            let padding = app_state.strategy_padding.lock().await.get(&strategy_name);
             tracing::info!("-----Starting strategy '{}'-----", strategy_name);
             let init_start_time = Instant::now(); // Start time of strategy initialization plus execution of first two blocks
             let mut init_time = Duration::new(0, 0); // Will set this to the time it takes for all initialization plus the first two blocks to complete
@@ -874,6 +1087,14 @@ pub async fn run_strategy_task<'s>(
                 }
                 initial_block_info.height += 1; // Add one because we'll be submitting to the next block
 
+                // A resumed run keeps the start height of the halted one, so start identities
+                // and contracts aren't created again
+                let strategy_start_height = resumed_progress
+                    .as_ref()
+                    .map_or(initial_block_info.height, |progress| {
+                        progress.start_block_height
+                    });
+
                 // Get signer from loaded_identity
                 // Convert loaded_identity to SimpleSigner
                 let identity_private_keys_lock = app_state.identity_private_keys.lock().await;
@@ -896,12 +1117,41 @@ pub async fn run_strategy_task<'s>(
                     strategy_signer.clone()
                 };
                 drop(identity_private_keys_lock);
+                if let Some(progress) = &resumed_progress {
+                    progress.add_keys_to(&mut signer);
+                }
 
                 // Set initial current_identities to loaded_identity
                 // During strategy execution, newly created identities will be added to current_identities
                 let mut loaded_identity_clone = loaded_identity_lock.clone();
                 let mut current_identities: Vec<Identity> = vec![loaded_identity_clone.clone()];
 
+                // Reuse identities created by the halted run, fetched again for their balances
+                if let Some(progress) = &resumed_progress {
+                    for (identity, _) in &progress.identities {
+                        match Identity::fetch(sdk, identity.id()).await {
+                            Ok(Some(identity)) => current_identities.push(identity),
+                            Ok(None) => tracing::warn!(
+                                "Identity {} of the halted run was never created, skipping it",
+                                identity.id().to_string(Encoding::Base58)
+                            ),
+                            Err(e) => {
+                                tracing::error!(
+                                    "Failed to fetch identity {} of the halted run, using the saved one: {}",
+                                    identity.id().to_string(Encoding::Base58),
+                                    e
+                                );
+                                current_identities.push(identity.clone());
+                            }
+                        }
+                    }
+                    tracing::info!(
+                        "Resuming strategy '{}' with {} identities of the halted run",
+                        strategy_name,
+                        current_identities.len() - 1
+                    );
+                }
+
                 // Set the nonce counters
                 let used_contract_ids = strategy.used_contract_ids();
                 let mut identity_nonce_counter = BTreeMap::new();
@@ -960,9 +1210,52 @@ pub async fn run_strategy_task<'s>(
                     );
                 }
 
+                // Identities reused from the halted run have already spent nonces
+                for identity in current_identities.iter().skip(1) {
+                    let settings =
+                        Some(dash_sdk::platform::transition::put_settings::PutSettings {
+                            request_settings: RequestSettings::default(),
+                            identity_nonce_stale_time_s: Some(0),
+                            user_fee_increase: None,
+                        });
+                    match sdk.get_identity_nonce(identity.id(), false, settings).await {
+                        Ok(nonce) => {
+                            identity_nonce_counter.insert(identity.id(), nonce);
+                        }
+                        Err(e) => tracing::error!(
+                            "Couldn't get identity nonce of {}: {}",
+                            identity.id().to_string(Encoding::Base58),
+                            e
+                        ),
+                    }
+                    for contract_id in used_contract_ids.iter() {
+                        match sdk
+                            .get_identity_contract_nonce(
+                                identity.id(),
+                                *contract_id,
+                                false,
+                                settings,
+                            )
+                            .await
+                        {
+                            Ok(nonce) => {
+                                contract_nonce_counter.insert((identity.id(), *contract_id), nonce);
+                            }
+                            Err(e) => tracing::error!(
+                                "Couldn't get identity contract nonce of {}: {}",
+                                identity.id().to_string(Encoding::Base58),
+                                e
+                            ),
+                        }
+                    }
+                }
+
                 // Get a lock on the local drive for the following two callbacks
                 let drive_lock = app_state.drive.lock().await;
 
+                // Documents created during this run, the local Drive doesn't receive them
+                let document_pool = RefCell::new(DocumentPool::default());
+
                 // Callback used to fetch documents from the local Drive instance
                 // Used for DocumentReplace and DocumentDelete transitions
                 let mut document_query_callback = |query: LocalDocumentQuery| {
@@ -971,6 +1264,14 @@ pub async fn run_strategy_task<'s>(
                             let document_type = random_query.document_type;
                             let data_contract = random_query.data_contract;
 
+                            // Prefer documents created earlier in the run, rotating over them
+                            let pooled_documents = document_pool
+                                .borrow_mut()
+                                .next_documents(data_contract.id(), document_type.name());
+                            if !pooled_documents.is_empty() {
+                                return pooled_documents;
+                            }
+
                             // Construct a DriveQuery based on the document_type and
                             // data_contract
                             let drive_query =
@@ -1042,24 +1343,25 @@ pub async fn run_strategy_task<'s>(
                         }
                     };
 
-                // Create asset lock proofs for all the identity creates and top ups
-                let num_identity_inserts = (strategy
-                    .identity_inserts
-                    .frequency
-                    .times_per_block_range
-                    .start) as u64;
-                let num_start_identities = strategy.start_identities.number_of_identities as u64;
-                let mut num_top_ups: u64 = 0;
-                for operation in &strategy.operations {
-                    if operation.op_type == OperationType::IdentityTopUp {
-                        num_top_ups += (operation.frequency.times_per_block_range.start) as u64;
-                    }
+                // Create asset lock proofs for all the identity creates and top ups,
+                // those prepared by the funding plan are used first
+                let mut asset_lock_proofs: Vec<(AssetLockProof, PrivateKey)> = app_state
+                    .funding_plan
+                    .lock()
+                    .await
+                    .as_mut()
+                    .map(|plan| plan.take_prepared(&strategy_name))
+                    .unwrap_or_default();
+                let mut asset_locks_needed =
+                    AssetLocksNeeded::for_strategy(strategy, num_blocks_or_seconds);
+                if resumed_progress.is_some() {
+                    asset_locks_needed.start_identities = 0;
                 }
-                let num_asset_lock_proofs_needed = num_identity_inserts * num_blocks_or_seconds
-                    + num_start_identities
-                    + num_top_ups;
-                let mut asset_lock_proofs: Vec<(AssetLockProof, PrivateKey)> = Vec::new();
+                let num_asset_lock_proofs_needed = asset_locks_needed
+                    .total()
+                    .saturating_sub(asset_lock_proofs.len() as u64);
                 if num_asset_lock_proofs_needed > 0 {
+                    let fee_rate = app_state.fee_settings.lock().await.rate();
                     let mut wallet_lock = app_state.loaded_wallet.lock().await;
                     let num_available_utxos = match wallet_lock
                         .clone()
@@ -1088,12 +1390,14 @@ pub async fn run_strategy_task<'s>(
                             match wallet.asset_lock_transaction(
                                 None,
                                 strategy.start_identities.starting_balances,
+                                fee_rate,
                             ) {
                                 Ok((asset_lock_transaction, asset_lock_proof_private_key)) => {
                                     match AppState::broadcast_and_retrieve_asset_lock(
                                         sdk,
                                         &asset_lock_transaction,
                                         &wallet.receive_address(),
+                                        app_state.asset_lock_proof,
                                     )
                                     .await
                                     {
@@ -1152,6 +1456,28 @@ pub async fn run_strategy_task<'s>(
                 let oks = Arc::new(AtomicUsize::new(0)); // Atomic counter for successful broadcasts
                 let errs = Arc::new(AtomicUsize::new(0)); // Atomic counter for failed broadcasts
                 let mempool_document_counter = BTreeMap::<(Identifier, Identifier), u64>::new(); // Map to track how many documents an identity has in the mempool per contract
+                let mut pacer = transitions_per_block
+                    .filter(|_| block_mode)
+                    .map(BlockPacer::new); // Paces submission by observed blocks if a target is set
+                let mut safety_monitor = app_state
+                    .strategy_safety
+                    .monitor(loaded_identity_clone.id()); // Halts broadcasting if configured limits are exceeded
+                let mut halt_reason = None;
+                let mut resource_sampler = ResourceSampler::start(); // Tracks the explorer's own CPU, memory and sockets
+                let mut block_transition_counts = BTreeMap::<u64, u64>::new(); // Transitions submitted by block height, for run summaries
+                let mut block_inclusion_counts = BTreeMap::<u64, u64>::new(); // Transitions included by actual block height, for the timeline
+                let mut block_deferred_counts = BTreeMap::<u64, u64>::new(); // Transitions the pacer left for later blocks, by block height
+                let mut steady_window = SteadyWindow::new(phases, num_blocks_or_seconds); // Counts between the warm-up and cool-down phases
+                let mut padder = (padding > 0).then(|| TransitionPadder::new(padding)); // Pads document transitions to the target size if set
+                let remaining_to_run = move |current_height: u64, run_time: Duration| {
+                    if block_mode {
+                        (initial_block_info.height + num_blocks_or_seconds + 2)
+                            .saturating_sub(current_height)
+                            .saturating_sub(2) // the resumed run has its own two initialization blocks
+                    } else {
+                        num_blocks_or_seconds.saturating_sub(run_time.as_secs())
+                    }
+                }; // Blocks or seconds left, for the progress saved to resume the run
 
                 // Now loop through the number of blocks or seconds the user asked for, preparing and processing state transitions
                 while (block_mode && current_block_info.height < (initial_block_info.height + num_blocks_or_seconds + 2)) // +2 because we don't count the first two initialization blocks
@@ -1161,11 +1487,28 @@ pub async fn run_strategy_task<'s>(
                     let errs_clone = errs.clone();
                     let loop_start_time = Instant::now();
 
+                    // Snapshot counters where the warm-up ends and the cool-down begins
+                    if index > 2 {
+                        let position = if block_mode {
+                            current_block_info
+                                .height
+                                .saturating_sub(initial_block_info.height + 2)
+                        } else {
+                            load_start_time.elapsed().as_secs()
+                        };
+                        let successes = if block_mode {
+                            success_count
+                        } else {
+                            oks.load(Ordering::SeqCst)
+                        };
+                        steady_window.observe(position, transition_count as u64, successes as u64);
+                    }
+
                     // Need to pass app_state.known_contracts to state_transitions_for_block
                     let mut known_contracts_lock = app_state.known_contracts.lock().await;
 
                     // Get the state transitions for the block (or second)
-                    let (transitions, finalize_operations, mut new_identities) = strategy
+                    let (mut transitions, finalize_operations, mut new_identities) = strategy
                         .state_transitions_for_block(
                             &mut document_query_callback,
                             &mut identity_fetch_callback,
@@ -1179,13 +1522,23 @@ pub async fn run_strategy_task<'s>(
                             // mempool_document_counter.clone(),
                             &mut rng,
                             &StrategyConfig {
-                                start_block_height: initial_block_info.height,
+                                start_block_height: strategy_start_height,
                                 number_of_blocks: num_blocks_or_seconds,
                             },
                             sdk.version(),
                         )
                         .await;
 
+                    // Pad document transitions before they're tracked and broadcast
+                    if let Some(padder) = padder.as_mut() {
+                        padder.pad(
+                            &mut transitions,
+                            &known_contracts_lock,
+                            &current_identities,
+                            &signer,
+                        );
+                    }
+
                     drop(known_contracts_lock);
 
                     // Add the identities that will be created to current_identities.
@@ -1211,8 +1564,11 @@ pub async fn run_strategy_task<'s>(
                         };
                     }
 
-                    // TO-DO: for DocumentDelete and DocumentReplace strategy operations, we need to
-                    // add documents from state transitions to the local Drive instance here
+                    // Track created documents for DocumentReplace and DocumentDelete operations,
+                    // the local Drive instance isn't updated
+                    document_pool
+                        .borrow_mut()
+                        .record_transitions(&transitions, &current_block_info);
 
                     // Process each FinalizeBlockOperation, which so far is just adding keys to identities
                     for operation in finalize_operations {
@@ -1239,8 +1595,24 @@ pub async fn run_strategy_task<'s>(
                         *loaded_identity_lock = modified_identity.clone();
                     }
 
+                    // Submit only the target number of transitions per block, the rest waits for next blocks
+                    let transitions = match pacer.as_mut() {
+                        Some(pacer) => pacer.next_batch(transitions),
+                        None => transitions,
+                    };
+                    if let Some(pending_count) = pacer
+                        .as_ref()
+                        .map(|pacer| pacer.pending_count())
+                        .filter(|count| *count > 0)
+                    {
+                        block_deferred_counts
+                            .insert(current_block_info.height, pending_count as u64);
+                    }
+
                     // Now process the state transitions
                     if !transitions.is_empty() {
+                        block_transition_counts
+                            .insert(current_block_info.height, transitions.len() as u64);
                         tracing::info!(
                             "Prepared {} state transitions for {} {}",
                             transitions.len(),
@@ -1294,6 +1666,7 @@ pub async fn run_strategy_task<'s>(
                                                         if let Some(wait_for_state_transition_result_response::Version::V0(v0_response)) = &wait_response.version {
                                                             if let Some(metadata) = &v0_response.metadata {
                                                                 success_count += 1;
+                                                                *block_inclusion_counts.entry(metadata.height).or_default() += 1;
                                                                 if !verify_proofs {
                                                                     tracing::info!("Successfully processed state transition {} ({}) for {} {} (Actual block height: {})", st_queue_index, transition_type, mode_string, index, metadata.height);
                                                                 }
@@ -1378,7 +1751,10 @@ pub async fn run_strategy_task<'s>(
                                 let future = async move {
                                     match transition_clone.broadcast_request_for_state_transition() {
                                         Ok(broadcast_request) => {
-                                            let broadcast_result = broadcast_request.execute(sdk, request_settings).await;
+                                            let broadcast_result = traced_dapi_request(broadcast_request, |request| {
+                                                request.execute(sdk, request_settings)
+                                            })
+                                            .await;
                                             match broadcast_result {
                                                 Ok(_) => {
                                                     oks.fetch_add(1, Ordering::SeqCst);
@@ -1450,7 +1826,16 @@ pub async fn run_strategy_task<'s>(
                                                                     .base
                                                                     .data_contract_id(),
                                                             ),
-                                                            // Add handling for Replace and Delete transitions if necessary
+                                                            DocumentTransition::Replace(
+                                                                DocumentReplaceTransition::V0(
+                                                                    replace_transition,
+                                                                ),
+                                                            ) => Some(
+                                                                replace_transition
+                                                                    .base
+                                                                    .data_contract_id(),
+                                                            ),
+                                                            // Add handling for Delete transitions if necessary
                                                             _ => None,
                                                         }
                                                     },
@@ -1483,9 +1868,13 @@ pub async fn run_strategy_task<'s>(
                                                 .wait_for_state_transition_result_request()
                                             {
                                                 Ok(wait_request) => {
-                                                    wait_request
-                                                        .execute(sdk, RequestSettings::default())
-                                                        .await
+                                                    traced_dapi_request(wait_request, |request| {
+                                                        request.execute(
+                                                            sdk,
+                                                            RequestSettings::default(),
+                                                        )
+                                                    })
+                                                    .await
                                                 }
                                                 Err(e) => {
                                                     tracing::error!(
@@ -1574,7 +1963,12 @@ pub async fn run_strategy_task<'s>(
                                                                     // nothing
                                                                 }
                                                             }
+                                                            Some(metadata.height)
+                                                        } else {
+                                                            None
                                                         }
+                                                    } else {
+                                                        None
                                                     })
                                                 }
                                                 Err(e) => {
@@ -1600,11 +1994,14 @@ pub async fn run_strategy_task<'s>(
                             // Wait for all state transition result futures to complete
                             let wait_results = join_all(wait_futures).await;
 
-                            // Log the actual block height for each state transition
+                            // Record the actual block height for each state transition
                             for (_, actual_block_height) in wait_results.into_iter().enumerate() {
                                 match actual_block_height {
-                                    Some(_) => {
+                                    Some(height) => {
                                         success_count += 1;
+                                        if let Some(height) = height {
+                                            *block_inclusion_counts.entry(height).or_default() += 1;
+                                        }
                                     }
                                     None => continue,
                                 }
@@ -1632,12 +2029,26 @@ pub async fn run_strategy_task<'s>(
                     }
 
                     // Update current_block_info and index for next loop iteration
-                    current_block_info.height += 1;
-                    let current_time_ms = SystemTime::now()
-                        .duration_since(UNIX_EPOCH)
-                        .expect("time went backwards")
-                        .as_millis();
-                    current_block_info.time_ms = current_time_ms as u64;
+                    let observed_block_info = match pacer.as_mut() {
+                        Some(pacer) => match pacer.wait_for_block(sdk, &current_block_info).await {
+                            Ok(block_info) => Some(block_info),
+                            Err(e) => {
+                                tracing::error!("Failed to observe next block, falling back to counting loops: {}", e);
+                                None
+                            }
+                        },
+                        None => None,
+                    };
+                    if let Some(block_info) = observed_block_info {
+                        current_block_info = block_info;
+                    } else {
+                        current_block_info.height += 1;
+                        let current_time_ms = SystemTime::now()
+                            .duration_since(UNIX_EPOCH)
+                            .expect("time went backwards")
+                            .as_millis();
+                        current_block_info.time_ms = current_time_ms as u64;
+                    }
                     index += 1;
 
                     // Make sure the loop doesn't iterate faster than once per second in time mode
@@ -1648,6 +2059,36 @@ pub async fn run_strategy_task<'s>(
                             tokio::time::sleep(remaining_time).await;
                         }
                     }
+
+                    resource_sampler.sample();
+
+                    // Checkpoint the run so it can be resumed however it exits, an error or a
+                    // panic included, and not only when the safety monitor halts it
+                    if index > 2 {
+                        let progress = RunProgress::new(
+                            &strategy_name,
+                            block_mode,
+                            remaining_to_run(current_block_info.height, load_start_time.elapsed()),
+                            strategy_start_height,
+                            &current_identities[1..], // without the loaded identity
+                            &signer,
+                        );
+                        if let Err(e) = progress.save() {
+                            tracing::error!("Failed to save strategy run progress: {}", e);
+                        }
+                    }
+
+                    // Check the run against safety limits
+                    if let Some(monitor) = safety_monitor.as_mut() {
+                        if let Some(reason) = monitor
+                            .check(sdk, oks.load(Ordering::SeqCst), errs.load(Ordering::SeqCst))
+                            .await
+                        {
+                            tracing::error!("Halting strategy '{}': {}", strategy_name, reason);
+                            halt_reason = Some(reason);
+                            break;
+                        }
+                    }
                 }
 
                 // Strategy execution is finished
@@ -1660,6 +2101,25 @@ pub async fn run_strategy_task<'s>(
                     errs.load(Ordering::SeqCst)
                 );
 
+                let resource_usage = resource_sampler.finish();
+                if let Some(usage) = &resource_usage {
+                    tracing::info!("Explorer resource usage:\n{}", usage.display());
+                }
+
+                // Log what pacing observed
+                let average_block_time_ms = pacer.as_ref().and_then(|p| p.average_block_time_ms());
+                if let Some(pacer) = &pacer {
+                    tracing::info!(
+                        "Observed average block time: {:?} ms, transitions left unsubmitted: {}",
+                        average_block_time_ms,
+                        pacer.pending_count()
+                    );
+                }
+                tracing::info!(
+                    "Documents created during the run and available for replaces: {}",
+                    document_pool.borrow().document_count()
+                );
+
                 // Time the execution took
                 let load_execution_run_time = load_start_time.elapsed();
                 if !block_mode {
@@ -1679,6 +2139,32 @@ pub async fn run_strategy_task<'s>(
 
                 // Withdraw all funds from newly created identities back to the wallet
                 current_identities.remove(0); // Remove loaded identity from the vector
+
+                // A halted run keeps its identities funded to be resumed, a finished one drops
+                // the progress checkpointed while running
+                let remaining =
+                    remaining_to_run(current_block_info.height, load_execution_run_time);
+                let progress_path = if halt_reason.is_some() || remaining > 0 {
+                    let progress = RunProgress::new(
+                        &strategy_name,
+                        block_mode,
+                        remaining,
+                        strategy_start_height,
+                        &current_identities,
+                        &signer,
+                    );
+                    progress
+                        .save()
+                        .map_err(|e| tracing::error!("Failed to save strategy run progress: {}", e))
+                        .ok()
+                } else {
+                    None
+                };
+                if progress_path.is_some() {
+                    current_identities.clear();
+                } else {
+                    RunProgress::remove(&strategy_name);
+                }
                 let wallet_lock = app_state
                     .loaded_wallet
                     .lock()
@@ -1856,14 +2342,128 @@ pub async fn run_strategy_task<'s>(
                     );
                 }
 
+                // Snapshot the state of a halted run instead of reporting it
+                if let Some(reason) = halt_reason {
+                    let snapshot = HaltSnapshot {
+                        strategy_name: strategy_name.clone(),
+                        timestamp: reports::current_timestamp(),
+                        reason: reason.clone(),
+                        block_height: current_block_info.height,
+                        transition_count: transition_count as u64,
+                        broadcasts_succeeded: oks.load(Ordering::SeqCst),
+                        broadcasts_failed: errs.load(Ordering::SeqCst),
+                        identity_balance: safety_monitor
+                            .as_ref()
+                            .and_then(|monitor| monitor.last_balance()),
+                        unused_asset_lock_proofs: asset_lock_proofs.len(),
+                        new_identity_ids,
+                        new_contract_ids,
+                        correlation_id: CorrelationId::current().map(|id| id.to_string()),
+                    };
+                    let mut reason = match snapshot.save() {
+                        Ok(path) => {
+                            format!("{}, state snapshot saved to {}", reason, path.display())
+                        }
+                        Err(e) => {
+                            tracing::error!("Failed to save strategy halt snapshot: {}", e);
+                            reason
+                        }
+                    };
+                    if let Some(path) = progress_path {
+                        reason.push_str(&format!(
+                            ", progress to resume the run saved to {}",
+                            path.display()
+                        ));
+                    }
+                    return BackendEvent::StrategyCompleted {
+                        strategy_name: strategy_name.clone(),
+                        result: StrategyCompletionResult::PartiallyCompleted {
+                            reached_block_height: current_block_info.height,
+                            reason,
+                            start_block_height: initial_block_info.height,
+                            transition_count: transition_count as u64,
+                            success_count: success_count as u64,
+                            block_transition_counts,
+                            block_inclusion_counts,
+                            block_deferred_counts,
+                        },
+                    };
+                }
+
+                // Statistics leave out the warm-up and cool-down phases, timelines keep them
+                let (
+                    transition_count,
+                    success_count,
+                    load_execution_run_time,
+                    tps,
+                    successful_tps,
+                    success_percent,
+                ) = match steady_window.finish(transition_count as u64, success_count as u64) {
+                    Some(steady) => {
+                        let steady_run_time = steady.run_time.as_secs().max(1);
+                        tracing::info!(
+                            "Steady state: {} transitions attempted, {} succeeded in {} seconds. {}",
+                            steady.transition_count,
+                            steady.success_count,
+                            steady.run_time.as_secs(),
+                            phases.display(if block_mode { "blocks" } else { "seconds" })
+                        );
+                        (
+                            steady.transition_count,
+                            steady.success_count,
+                            steady.run_time,
+                            steady.transition_count / steady_run_time,
+                            steady.success_count / steady_run_time,
+                            (steady.success_count * 100)
+                                .checked_div(steady.transition_count)
+                                .unwrap_or(0),
+                        )
+                    }
+                    None => (
+                        transition_count as u64,
+                        success_count as u64,
+                        load_execution_run_time,
+                        tps,
+                        successful_tps,
+                        success_percent,
+                    ),
+                };
+
+                // Persist run metrics so they can be compared against other runs later
+                let report = StrategyReport {
+                    strategy_name: strategy_name.clone(),
+                    timestamp: reports::current_timestamp(),
+                    protocol_version: sdk.version().protocol_version,
+                    block_mode,
+                    blocks_or_seconds: num_blocks_or_seconds,
+                    transition_count,
+                    success_count,
+                    run_time_secs: load_execution_run_time.as_secs(),
+                    init_time_secs: init_time.as_secs(),
+                    rate: tps,
+                    success_rate: successful_tps,
+                    success_percent,
+                    dash_spent_identity,
+                    dash_spent_wallet,
+                    average_block_time_ms,
+                    resource_usage: resource_usage.clone(),
+                    correlation_id: CorrelationId::current().map(|id| id.to_string()),
+                    phases,
+                    padding,
+                };
+                match reports::save_report(&report) {
+                    Ok(path) => tracing::info!("Saved strategy report to {}", path.display()),
+                    Err(e) => tracing::error!("Failed to save strategy report: {}", e),
+                }
+
                 BackendEvent::StrategyCompleted {
                     strategy_name: strategy_name.clone(),
                     result: StrategyCompletionResult::Success {
                         block_mode: block_mode,
                         final_block_height: current_block_info.height,
                         start_block_height: initial_block_info.height,
-                        success_count: success_count.try_into().unwrap(),
-                        transition_count: transition_count.try_into().unwrap(),
+                        success_count,
+                        transition_count,
                         rate: tps,
                         success_rate: successful_tps,
                         success_percent: success_percent,
@@ -1871,6 +2471,11 @@ pub async fn run_strategy_task<'s>(
                         init_time: init_time,
                         dash_spent_identity,
                         dash_spent_wallet,
+                        resource_usage,
+                        block_transition_counts,
+                        block_inclusion_counts,
+                        block_deferred_counts,
+                        phases,
                     },
                 }
             } else {
@@ -2023,6 +2628,11 @@ pub async fn run_strategy_task<'s>(
                 }
             }
         }
+        StrategyTask::RunContractVariants(..) => {
+            unreachable!("contract variants are run through run_contract_variants")
+        }
+        StrategyTask::PlanFunding(..) => app_state.plan_strategy_funding(sdk, insight, task).await,
+        StrategyTask::ExecuteFundingStep => app_state.execute_funding_step(sdk, insight).await,
     }
 }
 
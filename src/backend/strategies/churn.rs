@@ -0,0 +1,119 @@
+//! Documents replace churn support.
+//! The local Drive instance used by strategies to look up documents for
+//! replace operations is never populated, so documents created during a run
+//! are tracked here instead and handed out in rotation. Every generated
+//! replace bumps the tracked revision, so the next replace of the same
+//! document is built on top of it.
+
+use std::collections::{BTreeMap, VecDeque};
+
+use dpp::{
+    block::block_info::BlockInfo,
+    document::{Document, DocumentV0, DocumentV0Getters, DocumentV0Setters},
+    platform_value::Identifier,
+    state_transition::{
+        documents_batch_transition::{
+            document_base_transition::v0::v0_methods::DocumentBaseTransitionV0Methods,
+            document_transition::DocumentTransition, DocumentCreateTransition,
+            DocumentDeleteTransition, DocumentReplaceTransition, DocumentsBatchTransition,
+        },
+        StateTransition,
+    },
+};
+
+/// Documents per contract id and document type name.
+#[derive(Default)]
+pub(super) struct DocumentPool {
+    documents: BTreeMap<(Identifier, String), VecDeque<Document>>,
+}
+
+impl DocumentPool {
+    /// Documents of a type to pick replace targets from. The pool is rotated on
+    /// every call so consecutive replaces spread over different documents.
+    pub(super) fn next_documents(
+        &mut self,
+        contract_id: Identifier,
+        document_type_name: &str,
+    ) -> Vec<Document> {
+        let Some(documents) = self
+            .documents
+            .get_mut(&(contract_id, document_type_name.to_owned()))
+        else {
+            return Vec::new();
+        };
+        documents.rotate_left(1.min(documents.len()));
+        documents.iter().cloned().collect()
+    }
+
+    /// Tracks documents created by the transitions and applies replaces to
+    /// tracked ones, deleted documents are no longer handed out.
+    pub(super) fn record_transitions(
+        &mut self,
+        transitions: &[StateTransition],
+        block_info: &BlockInfo,
+    ) {
+        for transition in transitions {
+            let StateTransition::DocumentsBatch(DocumentsBatchTransition::V0(batch)) = transition
+            else {
+                continue;
+            };
+            for document_transition in batch.transitions.iter() {
+                match document_transition {
+                    DocumentTransition::Create(DocumentCreateTransition::V0(create)) => {
+                        let document = Document::V0(DocumentV0 {
+                            id: create.base.id(),
+                            owner_id: batch.owner_id,
+                            properties: create.data.clone(),
+                            revision: Some(1),
+                            created_at: Some(block_info.time_ms),
+                            updated_at: Some(block_info.time_ms),
+                            created_at_block_height: Some(block_info.height),
+                            updated_at_block_height: Some(block_info.height),
+                            created_at_core_block_height: Some(block_info.core_height),
+                            updated_at_core_block_height: Some(block_info.core_height),
+                        });
+                        self.documents
+                            .entry((
+                                create.base.data_contract_id(),
+                                create.base.document_type_name().clone(),
+                            ))
+                            .or_default()
+                            .push_back(document);
+                    }
+                    DocumentTransition::Replace(DocumentReplaceTransition::V0(replace)) => {
+                        let Some(document) = self
+                            .documents
+                            .get_mut(&(
+                                replace.base.data_contract_id(),
+                                replace.base.document_type_name().clone(),
+                            ))
+                            .and_then(|documents| {
+                                documents
+                                    .iter_mut()
+                                    .find(|document| document.id() == replace.base.id())
+                            })
+                        else {
+                            continue;
+                        };
+                        document.set_revision(Some(replace.revision));
+                        document.set_properties(replace.data.clone());
+                        document.set_updated_at(Some(block_info.time_ms));
+                    }
+                    DocumentTransition::Delete(DocumentDeleteTransition::V0(delete)) => {
+                        if let Some(documents) = self.documents.get_mut(&(
+                            delete.base.data_contract_id(),
+                            delete.base.document_type_name().clone(),
+                        )) {
+                            documents.retain(|document| document.id() != delete.base.id());
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Number of tracked documents.
+    pub(super) fn document_count(&self) -> usize {
+        self.documents.values().map(VecDeque::len).sum()
+    }
+}
@@ -0,0 +1,104 @@
+//! Rough estimation of credits a strategy is going to consume.
+//!
+//! Numbers are approximations based on average document and contract sizes and
+//! are meant to catch obviously underfunded strategies before running them,
+//! not to predict exact fees.
+
+use dpp::data_contract::{
+    accessors::v0::DataContractV0Getters, document_type::accessors::DocumentTypeV0Getters,
+};
+use strategy_tests::{
+    frequency::Frequency,
+    operations::{DocumentAction, OperationType},
+    Strategy,
+};
+
+/// Platform storage fee per byte.
+const STORAGE_CREDITS_PER_BYTE: u64 = 27_000;
+/// Approximate processing fee of a single state transition.
+const PROCESSING_CREDITS_PER_TRANSITION: u64 = 10_000_000;
+const AVERAGE_PROPERTY_BYTES: u64 = 32;
+const DOCUMENT_OVERHEAD_BYTES: u64 = 100;
+const IDENTITY_KEY_BYTES: u64 = 100;
+const CONTRACT_OVERHEAD_BYTES: u64 = 500;
+const CONTRACT_DOCUMENT_TYPE_BYTES: u64 = 1_000;
+pub(crate) const CREDITS_PER_DUFF: u64 = 1_000;
+
+pub(crate) struct CreditsForecast {
+    pub blocks: u64,
+    /// Fees paid by identities for state transitions
+    pub fees: u64,
+    /// Credits converted from the wallet through asset locks
+    pub funding: u64,
+}
+
+/// Expected number of times an event happens per block.
+fn expected_per_block(frequency: &Frequency) -> f64 {
+    let range = &frequency.times_per_block_range;
+    let average = if range.end > range.start {
+        (range.start as f64 + (range.end - 1) as f64) / 2.0
+    } else {
+        0.0
+    };
+    average * frequency.chance_per_block.unwrap_or(1.0)
+}
+
+fn storage_fee(bytes: u64) -> u64 {
+    PROCESSING_CREDITS_PER_TRANSITION + bytes * STORAGE_CREDITS_PER_BYTE
+}
+
+fn operation_fee(op_type: &OperationType) -> u64 {
+    match op_type {
+        OperationType::Document(op) => match op.action {
+            DocumentAction::DocumentActionDelete => PROCESSING_CREDITS_PER_TRANSITION,
+            _ => storage_fee(
+                DOCUMENT_OVERHEAD_BYTES
+                    + op.document_type.properties().len() as u64 * AVERAGE_PROPERTY_BYTES,
+            ),
+        },
+        OperationType::IdentityUpdate(_) => storage_fee(IDENTITY_KEY_BYTES),
+        OperationType::ContractCreate(_, document_types) => storage_fee(
+            CONTRACT_OVERHEAD_BYTES
+                + (document_types.start + document_types.end) as u64 / 2
+                    * CONTRACT_DOCUMENT_TYPE_BYTES,
+        ),
+        OperationType::ContractUpdate(_) => storage_fee(CONTRACT_DOCUMENT_TYPE_BYTES),
+        OperationType::IdentityTopUp
+        | OperationType::IdentityWithdrawal
+        | OperationType::IdentityTransfer => PROCESSING_CREDITS_PER_TRANSITION,
+    }
+}
+
+pub(crate) fn forecast_strategy_credits(strategy: &Strategy, blocks: u64) -> CreditsForecast {
+    let start_contracts_fees: u64 = strategy
+        .start_contracts
+        .iter()
+        .map(|(contract, _)| {
+            storage_fee(
+                CONTRACT_OVERHEAD_BYTES
+                    + contract.data_contract().document_types().len() as u64
+                        * CONTRACT_DOCUMENT_TYPE_BYTES,
+            )
+        })
+        .sum();
+
+    let operations_fees_per_block: f64 = strategy
+        .operations
+        .iter()
+        .map(|op| expected_per_block(&op.frequency) * operation_fee(&op.op_type) as f64)
+        .sum();
+
+    let start_identities_funding = strategy.start_identities.number_of_identities as u64
+        * strategy.start_identities.starting_balances
+        * CREDITS_PER_DUFF;
+    let identity_inserts_funding = (expected_per_block(&strategy.identity_inserts.frequency)
+        * blocks as f64) as u64
+        * strategy.start_identities.starting_balances
+        * CREDITS_PER_DUFF;
+
+    CreditsForecast {
+        blocks,
+        fees: start_contracts_fees + (operations_fees_per_block * blocks as f64) as u64,
+        funding: start_identities_funding + identity_inserts_funding,
+    }
+}
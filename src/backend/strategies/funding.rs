@@ -0,0 +1,428 @@
+//! Funding plan of a strategy run.
+//! Actors of a run are the loaded identity paying state transition fees, and
+//! start identities, identity inserts and top ups funded from the wallet with
+//! an asset lock each. The plan compares what they need for a number of
+//! blocks with the wallet and identity balances, and prepares the funds ahead
+//! of the run one step at a time: splitting wallet UTXOs, topping up the
+//! loaded identity and obtaining asset lock proofs that the run consumes
+//! instead of making its own.
+
+use dash_sdk::Sdk;
+use dpp::{
+    dashcore::PrivateKey, identity::accessors::IdentityGettersV0,
+    identity::state_transition::asset_lock_proof::AssetLockProof,
+};
+use strategy_tests::{operations::OperationType, Strategy};
+
+use super::forecast::{forecast_strategy_credits, CREDITS_PER_DUFF};
+use crate::backend::{
+    insight::InsightAPIClient, AppState, AppStateUpdate, BackendEvent, StrategyTask, Task, Wallet,
+};
+
+/// Balance the wallet keeps aside when splitting its UTXOs, in duffs.
+const SPLIT_UTXOS_RESERVE: u64 = 1_000_000_000;
+
+/// Asset locks a strategy run consumes, counted the way the run does.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct AssetLocksNeeded {
+    pub start_identities: u64,
+    pub identity_inserts: u64,
+    pub top_ups: u64,
+}
+
+impl AssetLocksNeeded {
+    pub(crate) fn for_strategy(strategy: &Strategy, blocks: u64) -> Self {
+        AssetLocksNeeded {
+            start_identities: strategy.start_identities.number_of_identities as u64,
+            identity_inserts: strategy
+                .identity_inserts
+                .frequency
+                .times_per_block_range
+                .start as u64
+                * blocks,
+            top_ups: strategy
+                .operations
+                .iter()
+                .filter(|operation| operation.op_type == OperationType::IdentityTopUp)
+                .map(|operation| operation.frequency.times_per_block_range.start as u64)
+                .sum(),
+        }
+    }
+
+    pub(crate) fn total(&self) -> u64 {
+        self.start_identities + self.identity_inserts + self.top_ups
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum FundingStep {
+    /// Split wallet funds into this many UTXOs
+    SplitUtxos(u32),
+    /// Top up the loaded identity by an amount in duffs
+    TopUpIdentity(u64),
+    /// Obtain an asset lock proof for the run
+    AssetLock,
+}
+
+impl FundingStep {
+    fn description(&self) -> String {
+        match self {
+            FundingStep::SplitUtxos(count) => format!("split wallet funds into {} UTXOs", count),
+            FundingStep::TopUpIdentity(amount) => {
+                format!("top up loaded identity with {}", dash_str(*amount))
+            }
+            FundingStep::AssetLock => "obtain an asset lock proof".to_owned(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct FundingPlan {
+    pub strategy_name: String,
+    blocks: u64,
+    asset_locks: AssetLocksNeeded,
+    /// Amount of each asset lock in duffs
+    asset_lock_amount: u64,
+    asset_lock_fee: u64,
+    /// Fees of the loaded identity in credits
+    fees: u64,
+    identity_balance: u64,
+    wallet_balance: u64,
+    wallet_utxos: usize,
+    steps: Vec<FundingStep>,
+    completed_steps: usize,
+    failure: Option<String>,
+    /// Asset lock proofs waiting for the run
+    prepared: Vec<(AssetLockProof, PrivateKey)>,
+}
+
+impl FundingPlan {
+    fn new(
+        strategy_name: String,
+        strategy: &Strategy,
+        blocks: u64,
+        asset_lock_fee: u64,
+        identity_balance: u64,
+        wallet_balance: u64,
+        wallet_utxos: usize,
+    ) -> Self {
+        let asset_locks = AssetLocksNeeded::for_strategy(strategy, blocks);
+        let fees = forecast_strategy_credits(strategy, blocks).fees;
+
+        let mut steps = Vec::new();
+        let top_up = fees
+            .saturating_sub(identity_balance)
+            .div_ceil(CREDITS_PER_DUFF);
+        let utxos_needed = asset_locks.total() as usize + (top_up > 0) as usize;
+        if wallet_utxos < utxos_needed {
+            steps.push(FundingStep::SplitUtxos(utxos_needed as u32));
+        }
+        if top_up > 0 {
+            steps.push(FundingStep::TopUpIdentity(top_up));
+        }
+        steps.extend((0..asset_locks.total()).map(|_| FundingStep::AssetLock));
+
+        FundingPlan {
+            strategy_name,
+            blocks,
+            asset_locks,
+            asset_lock_amount: strategy.start_identities.starting_balances,
+            asset_lock_fee,
+            fees,
+            identity_balance,
+            wallet_balance,
+            wallet_utxos,
+            steps,
+            completed_steps: 0,
+            failure: None,
+            prepared: Vec::new(),
+        }
+    }
+
+    /// Dash needed on the wallet to execute the plan, in duffs.
+    fn wallet_needed(&self) -> u64 {
+        let top_ups: u64 = self
+            .steps
+            .iter()
+            .filter_map(|step| match step {
+                FundingStep::TopUpIdentity(amount) => Some(amount + self.asset_lock_fee),
+                _ => None,
+            })
+            .sum();
+        let split_reserve = if self
+            .steps
+            .iter()
+            .any(|step| matches!(step, FundingStep::SplitUtxos(_)))
+        {
+            SPLIT_UTXOS_RESERVE
+        } else {
+            0
+        };
+        self.asset_locks.total() * (self.asset_lock_amount + self.asset_lock_fee)
+            + top_ups
+            + split_reserve
+    }
+
+    fn next_step(&self) -> Option<&FundingStep> {
+        self.steps.get(self.completed_steps)
+    }
+
+    /// The preparation can go on with the next step.
+    pub(crate) fn has_next_step(&self) -> bool {
+        self.failure.is_none() && self.next_step().is_some()
+    }
+
+    /// Takes prepared asset lock proofs for a run of the strategy.
+    pub(crate) fn take_prepared(
+        &mut self,
+        strategy_name: &str,
+    ) -> Vec<(AssetLockProof, PrivateKey)> {
+        if self.strategy_name == strategy_name {
+            std::mem::take(&mut self.prepared)
+        } else {
+            Vec::new()
+        }
+    }
+
+    pub(crate) fn display(&self) -> String {
+        let lock = self.asset_lock_amount + self.asset_lock_fee;
+        let actor = |name: &str, count: u64| {
+            format!(
+                "    {}: {} asset locks of {} (+{} fee), {}",
+                name,
+                count,
+                dash_str(self.asset_lock_amount),
+                dash_str(self.asset_lock_fee),
+                dash_str(count * lock)
+            )
+        };
+        let mut lines = vec![
+            format!(
+                "Funding plan of {} for {} blocks",
+                self.strategy_name, self.blocks
+            ),
+            String::new(),
+            "Actors:".to_owned(),
+            format!(
+                "    Loaded identity: {} credits of fees (approx), has {}",
+                self.fees, self.identity_balance
+            ),
+            actor("Start identities", self.asset_locks.start_identities),
+            actor("Identity inserts", self.asset_locks.identity_inserts),
+            actor("Top ups", self.asset_locks.top_ups),
+            String::new(),
+            format!(
+                "Wallet: needs {}, has {} in {} UTXOs",
+                dash_str(self.wallet_needed()),
+                dash_str(self.wallet_balance),
+                self.wallet_utxos
+            ),
+        ];
+        if self.wallet_balance < self.wallet_needed() {
+            lines.push(format!(
+                "    WARNING: {} short, top up the wallet before executing the plan",
+                dash_str(self.wallet_needed() - self.wallet_balance)
+            ));
+        }
+
+        lines.push(String::new());
+        if self.steps.is_empty() {
+            lines.push("Nothing to prepare".to_owned());
+        } else {
+            lines.push(format!(
+                "Preparation: {}/{} steps done, {} asset lock proofs ready",
+                self.completed_steps,
+                self.steps.len(),
+                self.prepared.len()
+            ));
+            let mut descriptions: Vec<(String, usize)> = Vec::new();
+            for step in &self.steps {
+                match descriptions.last_mut() {
+                    Some((description, count)) if *description == step.description() => *count += 1,
+                    _ => descriptions.push((step.description(), 1)),
+                }
+            }
+            lines.extend(descriptions.into_iter().map(|(description, count)| {
+                if count > 1 {
+                    format!("    {} x {}", count, description)
+                } else {
+                    format!("    {}", description)
+                }
+            }));
+        }
+        if let Some(failure) = &self.failure {
+            lines.push(format!("Preparation failed: {}", failure));
+        }
+        lines.join("\n")
+    }
+}
+
+fn dash_str(duffs: u64) -> String {
+    format!("{:.8} Dash", duffs as f64 / 100_000_000.0)
+}
+
+impl AppState {
+    pub(super) async fn plan_strategy_funding<'s>(
+        &'s self,
+        sdk: &Sdk,
+        insight: &InsightAPIClient,
+        task: StrategyTask,
+    ) -> BackendEvent<'s> {
+        let StrategyTask::PlanFunding(strategy_name, blocks) = &task else {
+            unreachable!("only funding planning tasks are passed");
+        };
+
+        let Some(strategy) = self
+            .available_strategies
+            .lock()
+            .await
+            .get(strategy_name)
+            .cloned()
+        else {
+            return BackendEvent::StrategyError {
+                error: format!("No strategy named {}", strategy_name),
+            };
+        };
+
+        let identity_balance = match self.refresh_identity(sdk).await {
+            Ok(identity) => identity.balance(),
+            Err(_) => 0,
+        };
+
+        let (wallet_balance, wallet_utxos) = {
+            let mut wallet_lock = self.loaded_wallet.lock().await;
+            let Some(wallet) = wallet_lock.as_mut() else {
+                return BackendEvent::StrategyError {
+                    error: "No wallet loaded".to_owned(),
+                };
+            };
+            if let Err(e) = wallet.reload_utxos(insight).await {
+                tracing::warn!("Unable to refresh wallet UTXOs for the funding plan: {}", e);
+            }
+            let wallet_utxos = match wallet {
+                Wallet::SingleKeyWallet(wallet) => wallet.utxos.len(),
+            };
+            (wallet.balance(), wallet_utxos)
+        };
+
+        let fee_settings = self.fee_settings.lock().await;
+        let asset_lock_fee = fee_settings.asset_lock_fee_for(fee_settings.level);
+        drop(fee_settings);
+
+        let mut funding_plan = self.funding_plan.lock().await;
+        funding_plan.replace(FundingPlan::new(
+            strategy_name.clone(),
+            &strategy,
+            *blocks,
+            asset_lock_fee,
+            identity_balance,
+            wallet_balance,
+            wallet_utxos,
+        ));
+
+        BackendEvent::TaskCompletedStateChange {
+            task: Task::Strategy(task),
+            execution_result: Ok("Funding plan ready".into()),
+            app_state_update: AppStateUpdate::FundingPlan(funding_plan),
+        }
+    }
+
+    pub(super) async fn execute_funding_step<'s>(
+        &'s self,
+        sdk: &Sdk,
+        insight: &InsightAPIClient,
+    ) -> BackendEvent<'s> {
+        let mut funding_plan = self.funding_plan.lock().await;
+        let execution_result = match funding_plan.as_mut() {
+            None => Err("No funding plan to execute".to_owned()),
+            Some(plan) if plan.wallet_balance < plan.wallet_needed() => {
+                let error = "not enough wallet balance, top up the wallet and plan again";
+                plan.failure = Some(error.to_owned());
+                Err(error.to_owned())
+            }
+            Some(plan) => match plan.next_step().cloned() {
+                None => Ok("Funding plan is already executed".into()),
+                Some(step) => match self.run_funding_step(sdk, insight, plan, &step).await {
+                    Ok(()) => {
+                        plan.completed_steps += 1;
+                        Ok(format!(
+                            "Funding step {}/{} done: {}",
+                            plan.completed_steps,
+                            plan.steps.len(),
+                            step.description()
+                        )
+                        .into())
+                    }
+                    Err(e) => {
+                        let error = format!("failed to {}: {}", step.description(), e);
+                        plan.failure = Some(error.clone());
+                        Err(error)
+                    }
+                },
+            },
+        };
+
+        BackendEvent::TaskCompletedStateChange {
+            task: Task::Strategy(StrategyTask::ExecuteFundingStep),
+            execution_result,
+            app_state_update: AppStateUpdate::FundingPlan(funding_plan),
+        }
+    }
+
+    async fn run_funding_step(
+        &self,
+        sdk: &Sdk,
+        insight: &InsightAPIClient,
+        plan: &mut FundingPlan,
+        step: &FundingStep,
+    ) -> Result<(), String> {
+        match step {
+            FundingStep::SplitUtxos(count) => {
+                let mut wallet_lock = self.loaded_wallet.lock().await;
+                let Some(Wallet::SingleKeyWallet(wallet)) = wallet_lock.as_mut() else {
+                    return Err("No wallet loaded".to_owned());
+                };
+                wallet
+                    .split_utxos(sdk, *count as usize)
+                    .await
+                    .map_err(|e| e.to_string())?;
+                let wallet = wallet_lock.as_mut().expect("checked above");
+                wallet
+                    .reload_utxos(insight)
+                    .await
+                    .map_err(|e| e.to_string())?;
+                Ok(())
+            }
+            FundingStep::TopUpIdentity(amount) => self
+                .top_up_identity(sdk, *amount)
+                .await
+                .map(|_| ())
+                .map_err(|e| e.to_string()),
+            FundingStep::AssetLock => {
+                let fee_rate = self.fee_settings.lock().await.rate();
+                let mut wallet_lock = self.loaded_wallet.lock().await;
+                let Some(wallet) = wallet_lock.as_mut() else {
+                    return Err("No wallet loaded".to_owned());
+                };
+                let (asset_lock_transaction, asset_lock_proof_private_key) = wallet
+                    .asset_lock_transaction(None, plan.asset_lock_amount, fee_rate)
+                    .map_err(|e| e.to_string())?;
+                self.asset_locks
+                    .lock()
+                    .await
+                    .record(&asset_lock_transaction, &asset_lock_proof_private_key);
+                let asset_lock_proof = AppState::broadcast_and_retrieve_asset_lock(
+                    sdk,
+                    &asset_lock_transaction,
+                    &wallet.receive_address(),
+                    self.asset_lock_proof,
+                )
+                .await
+                .map_err(|e| e.to_string())?;
+                plan.prepared
+                    .push((asset_lock_proof, asset_lock_proof_private_key));
+                Ok(())
+            }
+        }
+    }
+}
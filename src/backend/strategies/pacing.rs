@@ -0,0 +1,134 @@
+//! Block-time-aware pacing of strategy runs.
+//! Instead of assuming a block per loop iteration, the runner observes the
+//! actual platform chain tip and submits a target number of transitions per
+//! observed block, postponing the rest to the following blocks.
+
+use std::{
+    collections::VecDeque,
+    time::{Duration, Instant},
+};
+
+use dapi_grpc::platform::v0::{
+    get_epochs_info_request, get_epochs_info_response, GetEpochsInfoRequest,
+};
+use dash_sdk::Sdk;
+use dpp::{
+    block::{block_info::BlockInfo, epoch::Epoch},
+    state_transition::StateTransition,
+};
+use rs_dapi_client::{DapiRequestExecutor, RequestSettings};
+
+/// How often the chain tip is polled while waiting for a block.
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Waiting for a block longer than that is considered a stalled chain.
+const BLOCK_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Fetches info of the latest block using the response metadata.
+pub(crate) async fn fetch_chain_tip(sdk: &Sdk) -> Result<BlockInfo, String> {
+    let request = GetEpochsInfoRequest {
+        version: Some(get_epochs_info_request::Version::V0(
+            get_epochs_info_request::GetEpochsInfoRequestV0 {
+                start_epoch: None,
+                count: 1,
+                ascending: false,
+                prove: false,
+            },
+        )),
+    };
+    let response = sdk
+        .execute(request, RequestSettings::default())
+        .await
+        .map_err(|e| e.to_string())?;
+    let Some(get_epochs_info_response::Version::V0(response_v0)) = response.version else {
+        return Err("Unknown epochs info response version".to_owned());
+    };
+    let metadata = response_v0
+        .metadata
+        .ok_or_else(|| "No metadata in epochs info response".to_owned())?;
+
+    Ok(BlockInfo {
+        time_ms: metadata.time_ms,
+        height: metadata.height,
+        core_height: metadata.core_chain_locked_height,
+        epoch: Epoch::new(metadata.epoch as u16).map_err(|e| e.to_string())?,
+    })
+}
+
+pub(super) struct BlockPacer {
+    transitions_per_block: usize,
+    pending_transitions: VecDeque<StateTransition>,
+    last_observed_block: Option<BlockInfo>,
+    observed_blocks: u64,
+    observed_time_ms: u64,
+}
+
+impl BlockPacer {
+    pub(super) fn new(transitions_per_block: u16) -> Self {
+        BlockPacer {
+            transitions_per_block: transitions_per_block.max(1) as usize,
+            pending_transitions: VecDeque::new(),
+            last_observed_block: None,
+            observed_blocks: 0,
+            observed_time_ms: 0,
+        }
+    }
+
+    /// Queues transitions prepared for a block and returns those to submit
+    /// into it, keeping the order so nonces stay sequential.
+    pub(super) fn next_batch(&mut self, transitions: Vec<StateTransition>) -> Vec<StateTransition> {
+        self.pending_transitions.extend(transitions);
+        let count = self
+            .pending_transitions
+            .len()
+            .min(self.transitions_per_block);
+        self.pending_transitions.drain(..count).collect()
+    }
+
+    /// Transitions that were prepared but never submitted.
+    pub(super) fn pending_count(&self) -> usize {
+        self.pending_transitions.len()
+    }
+
+    /// Waits until the block transitions were submitted to is committed and
+    /// returns info of the next block to submit to.
+    pub(super) async fn wait_for_block(
+        &mut self,
+        sdk: &Sdk,
+        submitted_block: &BlockInfo,
+    ) -> Result<BlockInfo, String> {
+        let started = Instant::now();
+        let tip = loop {
+            let tip = fetch_chain_tip(sdk).await?;
+            if tip.height >= submitted_block.height {
+                break tip;
+            }
+            if started.elapsed() > BLOCK_TIMEOUT {
+                return Err(format!(
+                    "No block at height {} after {} seconds",
+                    submitted_block.height,
+                    BLOCK_TIMEOUT.as_secs()
+                ));
+            }
+            tokio::time::sleep(POLL_INTERVAL).await;
+        };
+
+        if let Some(last) = &self.last_observed_block {
+            if tip.height > last.height {
+                self.observed_blocks += tip.height - last.height;
+                self.observed_time_ms += tip.time_ms.saturating_sub(last.time_ms);
+            }
+        }
+        self.last_observed_block = Some(tip.clone());
+
+        Ok(BlockInfo {
+            height: tip.height + 1,
+            ..tip
+        })
+    }
+
+    /// Average time between observed blocks in milliseconds.
+    pub(super) fn average_block_time_ms(&self) -> Option<u64> {
+        (self.observed_blocks > 0).then(|| self.observed_time_ms / self.observed_blocks)
+    }
+}
@@ -0,0 +1,310 @@
+//! Padding of strategy document transitions to a target size.
+//! Throughput depends on the size of transitions as much as on their count, so
+//! generated document create and replace transitions can be padded to a target
+//! serialized size, like 1 KB or 10 KB, to keep size a controlled variable of
+//! runs. The filler goes into the non indexed string or byte array property of
+//! the document type allowing the longest value, is the same for every
+//! transition of a given size, and transitions are signed again once padded.
+//! Targets are kept by strategy name in a JSON file of the profile.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use dpp::{
+    data_contract::{
+        accessors::v0::DataContractV0Getters, document_type::accessors::DocumentTypeV0Getters,
+        DataContract,
+    },
+    identity::{accessors::IdentityGettersV0, Identity},
+    platform_value::{Identifier, Value},
+    serialization::PlatformSerializable,
+    state_transition::{
+        documents_batch_transition::{
+            document_base_transition::v0::v0_methods::DocumentBaseTransitionV0Methods,
+            document_transition::DocumentTransition, DocumentCreateTransition,
+            DocumentReplaceTransition, DocumentsBatchTransition,
+        },
+        StateTransition,
+    },
+    ProtocolError,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use simple_signer::signer::SimpleSigner;
+
+use crate::backend::json_store::JsonStore;
+
+const FILLER: &[u8] = b"0123456789abcdefghijklmnopqrstuvwxyz";
+
+/// Sizing a filler shifts varint lengths, so sizes are measured again a few
+/// times until the target is met.
+const MAX_SIZING_ATTEMPTS: usize = 3;
+
+/// Target sizes in bytes by strategy name.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub(crate) struct StrategyPadding {
+    targets: BTreeMap<String, u32>,
+}
+
+impl StrategyPadding {
+    /// Target size of the strategy transitions, 0 if they aren't padded.
+    pub(crate) fn get(&self, strategy_name: &str) -> u32 {
+        self.targets.get(strategy_name).copied().unwrap_or_default()
+    }
+}
+
+impl JsonStore<StrategyPadding> {
+    /// Sets the target size of the strategy transitions, 0 removes it.
+    pub(crate) fn set(&mut self, strategy_name: &str, target: u32) -> Result<(), String> {
+        if target == 0 {
+            self.targets.remove(strategy_name);
+        } else {
+            self.targets.insert(strategy_name.to_owned(), target);
+        }
+        self.save()
+    }
+
+    /// Keeps targets in sync with a deleted strategy, failures are only logged
+    /// as the strategy is gone anyway.
+    pub(crate) fn remove(&mut self, strategy_name: &str) {
+        if self.targets.remove(strategy_name).is_some() {
+            self.save_or_log();
+        }
+    }
+
+    /// Gives a cloned strategy the target of the original one.
+    pub(crate) fn copy(&mut self, from: &str, to: &str) {
+        let target = self.get(from);
+        if let Err(e) = self.set(to, target) {
+            tracing::error!("{}", e);
+        }
+    }
+}
+
+pub(crate) fn display_target(target: u32) -> String {
+    format!("Document transitions padded to {} bytes", target)
+}
+
+/// Property of a document type taking the filler.
+#[derive(Debug, Clone)]
+struct FillerProperty {
+    name: String,
+    byte_array: bool,
+    min_length: usize,
+    max_length: usize,
+}
+
+impl FillerProperty {
+    fn value(&self, length: usize) -> Value {
+        let filler = FILLER.iter().copied().cycle().take(length);
+        if self.byte_array {
+            Value::Bytes(filler.collect())
+        } else {
+            Value::Text(filler.map(char::from).collect())
+        }
+    }
+
+    fn length(&self, value: Option<&Value>) -> usize {
+        match value {
+            Some(Value::Text(text)) => text.len(),
+            Some(Value::Bytes(bytes)) => bytes.len(),
+            _ => 0,
+        }
+    }
+}
+
+/// The non indexed string or byte array property allowing the longest value,
+/// properties constrained by a format, pattern or enumeration are left out as
+/// the filler wouldn't match them.
+fn filler_property(document_schema: &JsonValue) -> Option<FillerProperty> {
+    let indexed: BTreeSet<&String> = document_schema
+        .get("indices")
+        .and_then(JsonValue::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(|index| index.get("properties").and_then(JsonValue::as_array))
+        .flatten()
+        .filter_map(JsonValue::as_object)
+        .flat_map(|property| property.keys())
+        .collect();
+    document_schema
+        .get("properties")?
+        .as_object()?
+        .iter()
+        .filter(|(name, property)| {
+            !indexed.contains(name)
+                && ["format", "pattern", "enum", "const", "contentMediaType"]
+                    .iter()
+                    .all(|key| property.get(key).is_none())
+        })
+        .filter_map(|(name, property)| {
+            let byte_array = match property.get("type")?.as_str()? {
+                "string" => false,
+                "array" if property.get("byteArray")?.as_bool()? => true,
+                _ => return None,
+            };
+            let (min_key, max_key) = if byte_array {
+                ("minItems", "maxItems")
+            } else {
+                ("minLength", "maxLength")
+            };
+            Some(FillerProperty {
+                name: name.clone(),
+                byte_array,
+                min_length: property
+                    .get(min_key)
+                    .and_then(JsonValue::as_u64)
+                    .unwrap_or_default() as usize,
+                max_length: property.get(max_key)?.as_u64()? as usize,
+            })
+        })
+        .max_by_key(|property| property.max_length)
+}
+
+/// Schema of a document type, from contracts of the run.
+fn document_type_schema(
+    contracts: &BTreeMap<String, DataContract>,
+    contract_id: Identifier,
+    document_type_name: &str,
+) -> Option<JsonValue> {
+    let contract = contracts
+        .values()
+        .find(|contract| contract.id() == contract_id)?;
+    let schema = contract.document_schemas().get(document_type_name)?.clone();
+    serde_json::to_value(schema).ok()
+}
+
+/// Pads document transitions of a run to the target size.
+pub(crate) struct TransitionPadder {
+    target: usize,
+    /// Filler property by contract id and document type name, `None` for
+    /// document types that can't be padded
+    properties: BTreeMap<(Identifier, String), Option<FillerProperty>>,
+}
+
+impl TransitionPadder {
+    pub(crate) fn new(target: u32) -> Self {
+        TransitionPadder {
+            target: target as usize,
+            properties: BTreeMap::new(),
+        }
+    }
+
+    fn property(
+        &mut self,
+        contracts: &BTreeMap<String, DataContract>,
+        contract_id: Identifier,
+        document_type_name: &String,
+    ) -> Option<FillerProperty> {
+        self.properties
+            .entry((contract_id, document_type_name.clone()))
+            .or_insert_with(|| {
+                let property = document_type_schema(contracts, contract_id, document_type_name)
+                    .as_ref()
+                    .and_then(filler_property);
+                if property.is_none() {
+                    tracing::warn!(
+                        "Documents of type {} have no property to pad transitions with",
+                        document_type_name
+                    );
+                }
+                property
+            })
+            .clone()
+    }
+
+    /// Pads documents batch transitions through the first document they create
+    /// or replace and signs them again with their owner keys.
+    pub(crate) fn pad(
+        &mut self,
+        transitions: &mut [StateTransition],
+        contracts: &BTreeMap<String, DataContract>,
+        identities: &[Identity],
+        signer: &SimpleSigner,
+    ) {
+        for transition in transitions.iter_mut() {
+            let StateTransition::DocumentsBatch(DocumentsBatchTransition::V0(batch)) = transition
+            else {
+                continue;
+            };
+            let (owner_id, key_id) = (batch.owner_id, batch.signature_public_key_id);
+            let Some((property, index)) =
+                batch
+                    .transitions
+                    .iter()
+                    .enumerate()
+                    .find_map(|(index, document_transition)| {
+                        let base = match document_transition {
+                            DocumentTransition::Create(DocumentCreateTransition::V0(create)) => {
+                                &create.base
+                            }
+                            DocumentTransition::Replace(DocumentReplaceTransition::V0(replace)) => {
+                                &replace.base
+                            }
+                            _ => return None,
+                        };
+                        let property = self.property(
+                            contracts,
+                            base.data_contract_id(),
+                            base.document_type_name(),
+                        )?;
+                        Some((property, index))
+                    })
+            else {
+                continue;
+            };
+
+            for _ in 0..MAX_SIZING_ATTEMPTS {
+                let Ok(size) = transition.serialize_to_bytes().map(|bytes| bytes.len()) else {
+                    break;
+                };
+                let StateTransition::DocumentsBatch(DocumentsBatchTransition::V0(batch)) =
+                    transition
+                else {
+                    break;
+                };
+                let data = match &mut batch.transitions[index] {
+                    DocumentTransition::Create(DocumentCreateTransition::V0(create)) => {
+                        &mut create.data
+                    }
+                    DocumentTransition::Replace(DocumentReplaceTransition::V0(replace)) => {
+                        &mut replace.data
+                    }
+                    _ => break,
+                };
+                let length = property.length(data.get(&property.name));
+                let padded_length = (length + self.target)
+                    .saturating_sub(size)
+                    .clamp(property.min_length, property.max_length);
+                if size == self.target || padded_length == length {
+                    break;
+                }
+                data.insert(property.name.clone(), property.value(padded_length));
+            }
+
+            let Some(key) = identities
+                .iter()
+                .find(|identity| identity.id() == owner_id)
+                .and_then(|identity| identity.public_keys().get(&key_id))
+            else {
+                tracing::error!("No owner key to sign a padded transition with");
+                continue;
+            };
+            let security_level = |contract_id: Identifier, document_type_name: String| {
+                contracts
+                    .values()
+                    .find(|contract| contract.id() == contract_id)
+                    .and_then(|contract| contract.document_type_for_name(&document_type_name).ok())
+                    .map(|document_type| document_type.security_level_requirement())
+                    .ok_or_else(|| {
+                        ProtocolError::Generic(format!(
+                            "Unknown document type {}",
+                            document_type_name
+                        ))
+                    })
+            };
+            if let Err(e) = transition.sign_external(key, signer, Some(security_level)) {
+                tracing::error!("Unable to sign the padded transition: {}", e);
+            }
+        }
+    }
+}
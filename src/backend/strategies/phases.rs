@@ -0,0 +1,155 @@
+//! Warm-up and cool-down phases of strategy runs.
+//! The first blocks or seconds of a run pay for cold connections and nonce
+//! fetches, the last ones for transitions still in flight, so both skew the
+//! steady-state throughput. Transitions of the phases are still submitted and
+//! shown in timelines, only the final statistics leave them out. Phases are
+//! kept by strategy name in a JSON file of the profile.
+
+use std::{
+    collections::BTreeMap,
+    time::{Duration, Instant},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::backend::json_store::JsonStore;
+
+/// Blocks in block mode or seconds in time mode at both ends of a run.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct RunPhases {
+    pub(crate) warm_up: u64,
+    pub(crate) cool_down: u64,
+}
+
+impl RunPhases {
+    pub(crate) fn is_empty(&self) -> bool {
+        self.warm_up == 0 && self.cool_down == 0
+    }
+
+    /// Checks that some of the run is left between the phases.
+    pub(crate) fn validate(&self, length: u64) -> Result<(), String> {
+        if !self.is_empty() && self.warm_up + self.cool_down >= length {
+            return Err(format!(
+                "Warm-up and cool-down leave nothing of the run: {} + {} of {}",
+                self.warm_up, self.cool_down, length
+            ));
+        }
+        Ok(())
+    }
+
+    pub(crate) fn display(&self, unit: &str) -> String {
+        format!(
+            "Warm-up of {} and cool-down of {} {} excluded from statistics",
+            self.warm_up, self.cool_down, unit
+        )
+    }
+}
+
+/// Run phases by strategy name.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub(crate) struct StrategyPhases {
+    phases: BTreeMap<String, RunPhases>,
+}
+
+impl StrategyPhases {
+    pub(crate) fn get(&self, strategy_name: &str) -> RunPhases {
+        self.phases.get(strategy_name).copied().unwrap_or_default()
+    }
+}
+
+impl JsonStore<StrategyPhases> {
+    /// Sets phases of the strategy, empty ones remove them.
+    pub(crate) fn set(&mut self, strategy_name: &str, phases: RunPhases) -> Result<(), String> {
+        if phases.is_empty() {
+            self.phases.remove(strategy_name);
+        } else {
+            self.phases.insert(strategy_name.to_owned(), phases);
+        }
+        self.save()
+    }
+
+    /// Keeps phases in sync with a deleted strategy, failures are only logged
+    /// as the strategy is gone anyway.
+    pub(crate) fn remove(&mut self, strategy_name: &str) {
+        if self.phases.remove(strategy_name).is_some() {
+            self.save_or_log();
+        }
+    }
+
+    /// Gives a cloned strategy the phases of the original one.
+    pub(crate) fn copy(&mut self, from: &str, to: &str) {
+        let phases = self.get(from);
+        if let Err(e) = self.set(to, phases) {
+            tracing::error!("{}", e);
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Snapshot {
+    transition_count: u64,
+    success_count: u64,
+    at: Instant,
+}
+
+/// Counts of the steady part of a run, between the phases.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct SteadyStats {
+    pub(crate) transition_count: u64,
+    pub(crate) success_count: u64,
+    pub(crate) run_time: Duration,
+}
+
+/// Snapshots counters where the warm-up ends and the cool-down begins.
+pub(crate) struct SteadyWindow {
+    phases: RunPhases,
+    length: u64,
+    start: Option<Snapshot>,
+    end: Option<Snapshot>,
+}
+
+impl SteadyWindow {
+    pub(crate) fn new(phases: RunPhases, length: u64) -> Self {
+        SteadyWindow {
+            phases,
+            length,
+            start: None,
+            end: None,
+        }
+    }
+
+    /// Called before each load block or second, `position` counts them from
+    /// the start of the load.
+    pub(crate) fn observe(&mut self, position: u64, transition_count: u64, success_count: u64) {
+        if self.phases.is_empty() {
+            return;
+        }
+        let snapshot = Snapshot {
+            transition_count,
+            success_count,
+            at: Instant::now(),
+        };
+        if self.start.is_none() && position >= self.phases.warm_up {
+            self.start = Some(snapshot);
+        }
+        if self.end.is_none() && position >= self.length.saturating_sub(self.phases.cool_down) {
+            self.end = Some(snapshot);
+        }
+    }
+
+    /// Counts between the phases, `None` without phases or if the run never
+    /// got past the warm-up.
+    pub(crate) fn finish(&self, transition_count: u64, success_count: u64) -> Option<SteadyStats> {
+        let start = self.start?;
+        let end = self.end.unwrap_or(Snapshot {
+            transition_count,
+            success_count,
+            at: Instant::now(),
+        });
+        Some(SteadyStats {
+            transition_count: end.transition_count - start.transition_count,
+            success_count: end.success_count.saturating_sub(start.success_count),
+            run_time: end.at - start.at,
+        })
+    }
+}
@@ -0,0 +1,125 @@
+//! Progress of halted strategy runs.
+//! While a run goes, what's left to run and the identities created so far,
+//! with their private keys, are saved after every block or second so the run
+//! can be resumed from there if it's halted by the safety monitor or exits
+//! early on an error. The resumed run reuses those identities and the
+//! contracts created by the halted run instead of creating them again, and the
+//! identities aren't withdrawn from until the run is finished.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use bincode::{Decode, Encode};
+use dpp::{
+    identity::{accessors::IdentityGettersV0, KeyID},
+    prelude::Identity,
+};
+use simple_signer::signer::SimpleSigner;
+
+use crate::backend::reports::file_name_safe;
+
+const PROGRESS_DIR: &str = "supporting_files/strategy_progress";
+
+#[derive(Debug, Encode, Decode)]
+pub(super) struct RunProgress {
+    pub strategy_name: String,
+    pub block_mode: bool,
+    /// Blocks or seconds to run when resuming
+    pub remaining: u64,
+    /// Height the first run started at, start identities and contracts are
+    /// only created on that block
+    pub start_block_height: u64,
+    /// Identities created by the run and their private keys
+    pub identities: Vec<(Identity, Vec<(KeyID, Vec<u8>)>)>,
+}
+
+fn bincode_config() -> impl bincode::config::Config {
+    bincode::config::standard()
+        .with_big_endian()
+        .with_no_limit()
+}
+
+fn progress_path(strategy_name: &str) -> PathBuf {
+    Path::new(PROGRESS_DIR).join(format!("{}.bin", file_name_safe(strategy_name)))
+}
+
+impl RunProgress {
+    /// Progress of the run, keeping private keys the signer has for the
+    /// identities.
+    pub(super) fn new(
+        strategy_name: &str,
+        block_mode: bool,
+        remaining: u64,
+        start_block_height: u64,
+        identities: &[Identity],
+        signer: &SimpleSigner,
+    ) -> Self {
+        let identities = identities
+            .iter()
+            .map(|identity| {
+                let private_keys = identity
+                    .public_keys()
+                    .iter()
+                    .filter_map(|(key_id, public_key)| {
+                        signer
+                            .private_keys
+                            .get(public_key)
+                            .map(|private_key| (*key_id, private_key.clone()))
+                    })
+                    .collect();
+                (identity.clone(), private_keys)
+            })
+            .collect();
+        RunProgress {
+            strategy_name: strategy_name.to_owned(),
+            block_mode,
+            remaining,
+            start_block_height,
+            identities,
+        }
+    }
+
+    /// Progress of the last halted run of the strategy.
+    pub(super) fn load(strategy_name: &str) -> Result<Self, String> {
+        let bytes = fs::read(progress_path(strategy_name))
+            .map_err(|_| format!("No halted run of strategy {} to resume", strategy_name))?;
+        bincode::decode_from_slice(&bytes, bincode_config())
+            .map(|(progress, _)| progress)
+            .map_err(|e| format!("Unable to read strategy run progress: {}", e))
+    }
+
+    pub(super) fn save(&self) -> Result<PathBuf, String> {
+        fs::create_dir_all(PROGRESS_DIR)
+            .map_err(|e| format!("Failed to create progress directory: {}", e))?;
+        let path = progress_path(&self.strategy_name);
+        let bytes = bincode::encode_to_vec(self, bincode_config())
+            .map_err(|e| format!("Failed to serialize progress: {}", e))?;
+        fs::write(&path, bytes).map_err(|e| format!("Failed to write progress: {}", e))?;
+        Ok(path)
+    }
+
+    /// Forgets the progress once the run is finished.
+    pub(super) fn remove(strategy_name: &str) {
+        let path = progress_path(strategy_name);
+        if path.exists() {
+            if let Err(e) = fs::remove_file(&path) {
+                tracing::error!("Failed to remove strategy run progress: {}", e);
+            }
+        }
+    }
+
+    /// Adds the private keys of the identities to the signer.
+    pub(super) fn add_keys_to(&self, signer: &mut SimpleSigner) {
+        for (identity, private_keys) in &self.identities {
+            for (key_id, private_key) in private_keys {
+                if let Some(public_key) = identity.public_keys().get(key_id) {
+                    signer
+                        .private_keys
+                        .insert(public_key.clone(), private_key.clone());
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,132 @@
+//! Resource usage of the explorer process during strategy runs.
+//! Client-side saturation limits throughput as well as the network does, so
+//! CPU, memory and open sockets are sampled along the run and reported next to
+//! its rates. Figures are read from procfs, on other systems they're missing.
+
+use std::{
+    fs,
+    time::{Duration, Instant},
+};
+
+use serde::{Deserialize, Serialize};
+
+/// Samples are taken at most this often.
+const SAMPLE_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Clock ticks per second of procfs CPU times, fixed for the userspace ABI on
+/// Linux.
+const CLOCK_TICKS_PER_SECOND: f64 = 100.0;
+
+/// Resource usage summary of a strategy run.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub(crate) struct ResourceUsage {
+    /// CPU time over run time, above 100% if several cores were used
+    pub cpu_percent: f64,
+    pub average_memory_mb: f64,
+    pub peak_memory_mb: f64,
+    pub average_open_sockets: f64,
+    pub peak_open_sockets: u64,
+}
+
+impl ResourceUsage {
+    pub(crate) fn display(&self) -> String {
+        format!(
+            "CPU usage: {:.1}%\nMemory (average / peak): {:.1} / {:.1} MB\nOpen sockets (average \
+             / peak): {:.1} / {}",
+            self.cpu_percent,
+            self.average_memory_mb,
+            self.peak_memory_mb,
+            self.average_open_sockets,
+            self.peak_open_sockets
+        )
+    }
+}
+
+/// CPU time of the process in clock ticks, user and system time of all
+/// threads.
+fn cpu_ticks() -> Option<u64> {
+    let stat = fs::read_to_string("/proc/self/stat").ok()?;
+    // Process name may contain spaces, so fields are counted after it
+    let mut fields = stat.rsplit_once(')')?.1.split_whitespace();
+    let utime: u64 = fields.nth(11)?.parse().ok()?;
+    let stime: u64 = fields.next()?.parse().ok()?;
+    Some(utime + stime)
+}
+
+fn resident_memory_kb() -> Option<u64> {
+    let status = fs::read_to_string("/proc/self/status").ok()?;
+    status
+        .lines()
+        .find_map(|line| line.strip_prefix("VmRSS:"))?
+        .trim()
+        .trim_end_matches("kB")
+        .trim()
+        .parse()
+        .ok()
+}
+
+fn open_sockets() -> Option<u64> {
+    let count = fs::read_dir("/proc/self/fd")
+        .ok()?
+        .filter_map(|entry| fs::read_link(entry.ok()?.path()).ok())
+        .filter(|target| target.to_string_lossy().starts_with("socket:"))
+        .count();
+    Some(count as u64)
+}
+
+pub(super) struct ResourceSampler {
+    started_at: Instant,
+    start_cpu_ticks: Option<u64>,
+    last_sample: Option<Instant>,
+    memory_samples: Vec<u64>,
+    socket_samples: Vec<u64>,
+}
+
+impl ResourceSampler {
+    pub(super) fn start() -> Self {
+        ResourceSampler {
+            started_at: Instant::now(),
+            start_cpu_ticks: cpu_ticks(),
+            last_sample: None,
+            memory_samples: Vec::new(),
+            socket_samples: Vec::new(),
+        }
+    }
+
+    /// Takes a sample if enough time passed since the previous one.
+    pub(super) fn sample(&mut self) {
+        if self
+            .last_sample
+            .is_some_and(|last| last.elapsed() < SAMPLE_INTERVAL)
+        {
+            return;
+        }
+        self.last_sample = Some(Instant::now());
+        self.memory_samples.extend(resident_memory_kb());
+        self.socket_samples.extend(open_sockets());
+    }
+
+    /// Summary of the samples, `None` if nothing could be measured.
+    pub(super) fn finish(mut self) -> Option<ResourceUsage> {
+        self.last_sample = None;
+        self.sample();
+        let cpu_ticks = cpu_ticks()
+            .zip(self.start_cpu_ticks)
+            .map(|(end, start)| end.saturating_sub(start))?;
+        if self.memory_samples.is_empty() {
+            return None;
+        }
+
+        let run_time = self.started_at.elapsed().as_secs_f64().max(f64::EPSILON);
+        let average =
+            |samples: &[u64]| samples.iter().sum::<u64>() as f64 / samples.len().max(1) as f64;
+        let peak = |samples: &[u64]| samples.iter().max().copied().unwrap_or_default();
+        Some(ResourceUsage {
+            cpu_percent: cpu_ticks as f64 / CLOCK_TICKS_PER_SECOND / run_time * 100.0,
+            average_memory_mb: average(&self.memory_samples) / 1024.0,
+            peak_memory_mb: peak(&self.memory_samples) as f64 / 1024.0,
+            average_open_sockets: average(&self.socket_samples),
+            peak_open_sockets: peak(&self.socket_samples),
+        })
+    }
+}
@@ -0,0 +1,204 @@
+//! Safety monitor of unattended strategy runs.
+//! With any limit configured, the run is checked periodically and broadcasting
+//! is halted once the share of failed broadcasts, the loaded identity balance
+//! drop rate or consecutive DAPI health check failures go over the limits. The
+//! run state at that moment is saved as a JSON snapshot.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+use dapi_grpc::{
+    core::v0::GetBlockchainStatusRequest,
+    platform::v0::{
+        get_identity_balance_request::{self, GetIdentityBalanceRequestV0},
+        GetIdentityBalanceRequest,
+    },
+};
+use dash_sdk::{platform::Fetch, Sdk};
+use dpp::prelude::Identifier;
+use rs_dapi_client::{DapiRequestExecutor, RequestSettings};
+use serde::Serialize;
+
+use crate::config::Config;
+
+const SNAPSHOTS_DIR: &str = "supporting_files/strategy_halts";
+
+/// How often the run is checked against the limits.
+const CHECK_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Error rate isn't judged on fewer broadcasts since the previous check.
+const MIN_BROADCASTS_FOR_ERROR_RATE: usize = 20;
+
+/// Limits of a strategy run, the monitor is disabled if none is set.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct SafetyLimits {
+    max_error_percent: Option<u8>,
+    max_balance_drop_per_minute: Option<u64>,
+    max_dapi_failures: Option<u32>,
+}
+
+impl SafetyLimits {
+    pub(crate) fn from_config(config: &Config) -> Self {
+        SafetyLimits {
+            max_error_percent: config.strategy_halt_error_percent,
+            max_balance_drop_per_minute: config.strategy_halt_balance_drop_per_minute,
+            max_dapi_failures: config.strategy_halt_dapi_failures.filter(|n| *n > 0),
+        }
+    }
+
+    pub(super) fn monitor(&self, identity_id: Identifier) -> Option<SafetyMonitor> {
+        let enabled = self.max_error_percent.is_some()
+            || self.max_balance_drop_per_minute.is_some()
+            || self.max_dapi_failures.is_some();
+        enabled.then(|| SafetyMonitor {
+            limits: self.clone(),
+            identity_id,
+            last_check: Instant::now(),
+            last_oks: 0,
+            last_errs: 0,
+            last_balance: None,
+            dapi_failures: 0,
+        })
+    }
+}
+
+pub(super) struct SafetyMonitor {
+    limits: SafetyLimits,
+    identity_id: Identifier,
+    last_check: Instant,
+    last_oks: usize,
+    last_errs: usize,
+    last_balance: Option<u64>,
+    dapi_failures: u32,
+}
+
+impl SafetyMonitor {
+    /// Checks the run if it's time to, returning the reason to halt it if a
+    /// limit is exceeded.
+    pub(super) async fn check(&mut self, sdk: &Sdk, oks: usize, errs: usize) -> Option<String> {
+        let elapsed = self.last_check.elapsed();
+        if elapsed < CHECK_INTERVAL {
+            return None;
+        }
+        self.last_check = Instant::now();
+
+        let new_oks = oks.saturating_sub(self.last_oks);
+        let new_errs = errs.saturating_sub(self.last_errs);
+        self.last_oks = oks;
+        self.last_errs = errs;
+        if let Some(max_percent) = self.limits.max_error_percent {
+            let broadcasts = new_oks + new_errs;
+            if broadcasts >= MIN_BROADCASTS_FOR_ERROR_RATE
+                && new_errs * 100 > broadcasts * max_percent as usize
+            {
+                return Some(format!(
+                    "{} of {} broadcasts failed since the previous check, limit is {}%",
+                    new_errs, broadcasts, max_percent
+                ));
+            }
+        }
+
+        if let Some(max_failures) = self.limits.max_dapi_failures {
+            match sdk
+                .execute(GetBlockchainStatusRequest {}, RequestSettings::default())
+                .await
+            {
+                Ok(_) => self.dapi_failures = 0,
+                Err(e) => {
+                    self.dapi_failures += 1;
+                    tracing::warn!(
+                        "DAPI health check failed ({} in a row): {}",
+                        self.dapi_failures,
+                        e
+                    );
+                    if self.dapi_failures >= max_failures {
+                        return Some(format!(
+                            "{} DAPI health checks failed in a row",
+                            self.dapi_failures
+                        ));
+                    }
+                }
+            }
+        }
+
+        if let Some(max_drop) = self.limits.max_balance_drop_per_minute {
+            match self.fetch_balance(sdk).await {
+                Ok(balance) => {
+                    let last_balance = self.last_balance.replace(balance);
+                    let drop = last_balance.map_or(0, |last| last.saturating_sub(balance));
+                    let drop_per_minute =
+                        (drop as u128 * 60 / elapsed.as_secs().max(1) as u128) as u64;
+                    if drop_per_minute > max_drop {
+                        return Some(format!(
+                            "loaded identity balance dropped by {} credits per minute, limit \
+                             is {}",
+                            drop_per_minute, max_drop
+                        ));
+                    }
+                }
+                Err(e) => tracing::warn!("Unable to check loaded identity balance: {}", e),
+            }
+        }
+
+        None
+    }
+
+    async fn fetch_balance(&self, sdk: &Sdk) -> Result<u64, String> {
+        u64::fetch(
+            sdk,
+            GetIdentityBalanceRequest {
+                version: Some(get_identity_balance_request::Version::V0(
+                    GetIdentityBalanceRequestV0 {
+                        id: self.identity_id.to_vec(),
+                        prove: true,
+                    },
+                )),
+            },
+        )
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "identity not found".to_owned())
+    }
+
+    /// Balance of the loaded identity at the latest check.
+    pub(super) fn last_balance(&self) -> Option<u64> {
+        self.last_balance
+    }
+}
+
+/// State of a strategy run halted by the safety monitor.
+#[derive(Debug, Serialize)]
+pub(super) struct HaltSnapshot {
+    pub strategy_name: String,
+    pub timestamp: u64,
+    pub reason: String,
+    pub block_height: u64,
+    pub transition_count: u64,
+    pub broadcasts_succeeded: usize,
+    pub broadcasts_failed: usize,
+    pub identity_balance: Option<u64>,
+    /// Asset lock proofs obtained for the run and not consumed
+    pub unused_asset_lock_proofs: usize,
+    pub new_identity_ids: Vec<String>,
+    pub new_contract_ids: Vec<String>,
+    /// Correlation ID of the run task, to find its log lines
+    pub correlation_id: Option<String>,
+}
+
+impl HaltSnapshot {
+    /// Writes the snapshot into the snapshots directory, returning the path of
+    /// the created file.
+    pub(super) fn save(&self) -> Result<PathBuf, String> {
+        fs::create_dir_all(SNAPSHOTS_DIR)
+            .map_err(|e| format!("Failed to create snapshots directory: {}", e))?;
+        let path = Path::new(SNAPSHOTS_DIR)
+            .join(format!("{}_{}.json", self.strategy_name, self.timestamp));
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|e| format!("Failed to serialize snapshot: {}", e))?;
+        fs::write(&path, json).map_err(|e| format!("Failed to write snapshot: {}", e))?;
+        Ok(path)
+    }
+}
@@ -0,0 +1,257 @@
+//! Built-in strategy templates.
+//! A template is a ready-made combination of start identities, contracts and
+//! operations for a common kind of load, instantiated from a couple of
+//! parameters so a first stress test doesn't require assembling operations one
+//! by one. The result is a regular strategy that can be edited further.
+
+use std::{
+    collections::BTreeMap,
+    fmt::{self, Display},
+};
+
+use dpp::{
+    data_contract::{
+        accessors::v0::DataContractV0Getters,
+        created_data_contract::CreatedDataContract,
+        document_type::{
+            random_document::{DocumentFieldFillSize, DocumentFieldFillType},
+            v0::random_document_type::{
+                FieldMinMaxBounds, FieldTypeWeights, RandomDocumentTypeParameters,
+            },
+        },
+        DataContract,
+    },
+    version::PlatformVersion,
+};
+use strategy_tests::{
+    frequency::Frequency,
+    operations::{DocumentAction, DocumentOp, Operation, OperationType},
+    IdentityInsertInfo, StartIdentities, Strategy,
+};
+
+use crate::backend::StrategyContractNames;
+
+/// Starting balance of template identities, 1 dash in strategy forms units.
+const START_IDENTITIES_BALANCE: u64 = 100_000_000;
+
+/// Keys of each template start identity.
+const START_IDENTITIES_KEYS: u8 = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StrategyTemplate {
+    /// Random documents inserted into every document type of a contract
+    DocumentFlood,
+    /// New contracts with random document types registered every block
+    ContractChurn,
+    /// Document inserts and replaces along with identity top ups, transfers,
+    /// new identities and an occasional contract
+    MixedLoad,
+}
+
+impl StrategyTemplate {
+    pub(crate) const ALL: [StrategyTemplate; 3] = [
+        StrategyTemplate::DocumentFlood,
+        StrategyTemplate::ContractChurn,
+        StrategyTemplate::MixedLoad,
+    ];
+
+    /// Whether the template's operations target an existing contract.
+    pub(crate) fn requires_contract(&self) -> bool {
+        !matches!(self, StrategyTemplate::ContractChurn)
+    }
+}
+
+impl Display for StrategyTemplate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StrategyTemplate::DocumentFlood => {
+                write!(f, "Document flood: random documents into a contract")
+            }
+            StrategyTemplate::ContractChurn => {
+                write!(f, "Contract churn: new random contracts every block")
+            }
+            StrategyTemplate::MixedLoad => write!(
+                f,
+                "Mixed realistic load: documents, identity operations and contracts"
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TemplateParameters {
+    pub start_identities: u16,
+    /// Transitions per block of the template's main operation
+    pub transitions_per_block: u16,
+    /// Name of a known contract for templates that require one
+    pub contract_name: Option<String>,
+}
+
+/// Builds a strategy out of a template, `contract` is the known contract
+/// picked in parameters.
+pub(crate) fn instantiate(
+    template: StrategyTemplate,
+    parameters: &TemplateParameters,
+    contract: Option<DataContract>,
+    platform_version: &PlatformVersion,
+) -> Result<(Strategy, StrategyContractNames), String> {
+    let mut strategy = Strategy {
+        start_identities: StartIdentities {
+            number_of_identities: parameters.start_identities,
+            keys_per_identity: START_IDENTITIES_KEYS,
+            starting_balances: START_IDENTITIES_BALANCE,
+            extra_keys: BTreeMap::new(),
+        },
+        ..Default::default()
+    };
+    let mut contract_names = StrategyContractNames::new();
+
+    let contract = match (template.requires_contract(), contract) {
+        (true, None) => return Err("The template requires a contract".to_owned()),
+        (true, Some(contract)) => {
+            // Real identity nonces are set during strategy execution
+            let created_contract = CreatedDataContract::from_contract_and_identity_nonce(
+                contract.clone(),
+                1,
+                platform_version,
+            )
+            .map_err(|e| format!("Unable to use the contract: {}", e))?;
+            strategy.start_contracts.push((created_contract, None));
+            contract_names.push((parameters.contract_name.clone().unwrap_or_default(), None));
+            Some(contract)
+        }
+        (false, _) => None,
+    };
+
+    let rate = parameters.transitions_per_block.max(1);
+    match (template, contract) {
+        (StrategyTemplate::DocumentFlood, Some(contract)) => {
+            strategy.operations.extend(document_operations(
+                &contract,
+                DocumentAction::DocumentActionInsertRandom(
+                    DocumentFieldFillType::FillIfNotRequired,
+                    DocumentFieldFillSize::AnyDocumentFillSize,
+                ),
+                every_block(rate),
+            ));
+        }
+        (StrategyTemplate::ContractChurn, _) => {
+            strategy
+                .operations
+                .push(contract_create_operation(every_block(rate)));
+        }
+        (StrategyTemplate::MixedLoad, Some(contract)) => {
+            strategy.operations.extend(document_operations(
+                &contract,
+                DocumentAction::DocumentActionInsertRandom(
+                    DocumentFieldFillType::DoNotFillIfNotRequired,
+                    DocumentFieldFillSize::AnyDocumentFillSize,
+                ),
+                every_block(rate),
+            ));
+            strategy.operations.extend(document_operations(
+                &contract,
+                DocumentAction::DocumentActionReplace,
+                with_chance((rate / 2).max(1), 0.5),
+            ));
+            strategy.operations.push(Operation {
+                op_type: OperationType::IdentityTopUp,
+                frequency: with_chance(1, 0.5),
+            });
+            strategy.operations.push(Operation {
+                op_type: OperationType::IdentityTransfer,
+                frequency: with_chance(1, 0.25),
+            });
+            strategy
+                .operations
+                .push(contract_create_operation(with_chance(1, 0.05)));
+            strategy.identity_inserts = IdentityInsertInfo {
+                frequency: with_chance(1, 0.1),
+                start_keys: START_IDENTITIES_KEYS,
+                extra_keys: BTreeMap::new(),
+            };
+        }
+        (_, None) => unreachable!("contract presence is checked above"),
+    }
+
+    Ok((strategy, contract_names))
+}
+
+fn every_block(times: u16) -> Frequency {
+    Frequency {
+        times_per_block_range: times..times + 1,
+        chance_per_block: None,
+    }
+}
+
+fn with_chance(times: u16, chance: f64) -> Frequency {
+    Frequency {
+        times_per_block_range: times..times + 1,
+        chance_per_block: Some(chance),
+    }
+}
+
+/// An operation per document type of the contract.
+fn document_operations(
+    contract: &DataContract,
+    action: DocumentAction,
+    frequency: Frequency,
+) -> Vec<Operation> {
+    contract
+        .document_types()
+        .values()
+        .map(|document_type| Operation {
+            op_type: OperationType::Document(DocumentOp {
+                contract: contract.clone(),
+                document_type: document_type.clone(),
+                action: action.clone(),
+            }),
+            frequency: frequency.clone(),
+        })
+        .collect()
+}
+
+/// Contract registration with moderately sized random document types.
+fn contract_create_operation(frequency: Frequency) -> Operation {
+    let parameters = RandomDocumentTypeParameters {
+        new_fields_optional_count_range: 1..5,
+        new_fields_required_count_range: 1..5,
+        new_indexes_count_range: 1..3,
+        field_weights: FieldTypeWeights {
+            string_weight: 50,
+            float_weight: 10,
+            integer_weight: 20,
+            date_weight: 10,
+            boolean_weight: 5,
+            byte_array_weight: 5,
+        },
+        field_bounds: FieldMinMaxBounds {
+            string_min_len: 1..10,
+            string_has_min_len_chance: 0.5,
+            string_max_len: 10..63,
+            string_has_max_len_chance: 0.5,
+            integer_min: 1..10,
+            integer_has_min_chance: 0.5,
+            integer_max: 10..10000,
+            integer_has_max_chance: 0.5,
+            float_min: 0.1..10.0,
+            float_has_min_chance: 0.5,
+            float_max: 10.0..1000.0,
+            float_has_max_chance: 0.5,
+            date_min: 0,
+            date_max: 1_000_000,
+            byte_array_min_len: 1..10,
+            byte_array_has_min_len_chance: 0.5,
+            byte_array_max_len: 10..255,
+            byte_array_has_max_len_chance: 0.5,
+        },
+        keep_history_chance: 0.25,
+        documents_mutable_chance: 0.75,
+        documents_can_be_deleted_chance: 0.5,
+    };
+
+    Operation {
+        op_type: OperationType::ContractCreate(parameters, 1..3),
+        frequency,
+    }
+}
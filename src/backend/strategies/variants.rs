@@ -0,0 +1,371 @@
+//! Runs of a strategy against variants of its start contracts.
+//! The same operation mix is run once per variant, with indices removed or
+//! property sizes changed in document schemas of every start contract, and
+//! the runs are compared in a single matrix of throughput and fees.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use dash_sdk::Sdk;
+use dpp::{
+    data_contract::{
+        accessors::v0::DataContractV0Getters, document_type::accessors::DocumentTypeV0Getters,
+        DataContractFactory,
+    },
+    platform_value::Value,
+    version::PlatformVersion,
+};
+use serde_json::Value as JsonValue;
+use strategy_tests::{operations::OperationType, Strategy};
+
+use super::{run_single_strategy_task, StrategyTask};
+use crate::backend::{
+    insight::InsightAPIClient, AppState, BackendEvent, StrategyCompletionResult, Task,
+};
+
+/// Changes made to document schemas of start contracts.
+struct ContractVariant {
+    name: &'static str,
+    /// Indices kept for each document type, all of them if not set
+    indices: Option<usize>,
+    /// Percentage applied to max sizes of non indexed strings and byte arrays
+    field_size_percent: u64,
+}
+
+const VARIANTS: [ContractVariant; 5] = [
+    ContractVariant {
+        name: "original",
+        indices: None,
+        field_size_percent: 100,
+    },
+    ContractVariant {
+        name: "no indices",
+        indices: Some(0),
+        field_size_percent: 100,
+    },
+    ContractVariant {
+        name: "single index",
+        indices: Some(1),
+        field_size_percent: 100,
+    },
+    ContractVariant {
+        name: "half field sizes",
+        indices: None,
+        field_size_percent: 50,
+    },
+    ContractVariant {
+        name: "double field sizes",
+        indices: None,
+        field_size_percent: 200,
+    },
+];
+
+/// Metrics of a completed run of a variant.
+struct VariantOutcome {
+    transition_count: u64,
+    success_count: u64,
+    success_percent: u64,
+    rate: u64,
+    success_rate: u64,
+    run_time_secs: u64,
+    dash_spent_identity: f64,
+}
+
+impl ContractVariant {
+    fn is_original(&self) -> bool {
+        self.indices.is_none() && self.field_size_percent == 100
+    }
+
+    fn apply(&self, document_schema: &mut JsonValue) {
+        if let Some(count) = self.indices {
+            if let Some(indices) = document_schema
+                .get_mut("indices")
+                .and_then(JsonValue::as_array_mut)
+            {
+                indices.truncate(count);
+            }
+            if count == 0 {
+                if let Some(schema) = document_schema.as_object_mut() {
+                    schema.remove("indices");
+                }
+            }
+        }
+        if self.field_size_percent != 100 {
+            scale_field_sizes(document_schema, self.field_size_percent);
+        }
+    }
+}
+
+/// Scales max sizes of properties, leaving indexed ones and fixed size ones,
+/// like identifiers, as they are.
+fn scale_field_sizes(document_schema: &mut JsonValue, percent: u64) {
+    let indexed: BTreeSet<String> = document_schema
+        .get("indices")
+        .and_then(JsonValue::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(|index| index.get("properties").and_then(JsonValue::as_array))
+        .flatten()
+        .filter_map(JsonValue::as_object)
+        .flat_map(|property| property.keys().cloned())
+        .collect();
+    let Some(properties) = document_schema
+        .get_mut("properties")
+        .and_then(JsonValue::as_object_mut)
+    else {
+        return;
+    };
+    for (name, property) in properties.iter_mut() {
+        if indexed.contains(name) || property.get("contentMediaType").is_some() {
+            continue;
+        }
+        for (min_key, max_key) in [("minLength", "maxLength"), ("minItems", "maxItems")] {
+            let Some(max) = property.get(max_key).and_then(JsonValue::as_u64) else {
+                continue;
+            };
+            let min = property
+                .get(min_key)
+                .and_then(JsonValue::as_u64)
+                .unwrap_or_default();
+            if min == max {
+                continue;
+            }
+            property[max_key] = (max * percent / 100).max(min).max(1).into();
+        }
+    }
+}
+
+/// Copy of the strategy with the variant applied to its start contracts and
+/// document operations on them, `None` if no schema is changed. Contract
+/// updates are dropped as they are defined against the original schemas.
+fn apply_variant(
+    strategy: &Strategy,
+    variant: &ContractVariant,
+    platform_version: &PlatformVersion,
+) -> Result<Option<Strategy>, String> {
+    let mut variant_strategy = strategy.clone();
+    let mut changed = false;
+    for (created_contract, updates) in variant_strategy.start_contracts.iter_mut() {
+        let contract = created_contract.data_contract();
+        let schemas: BTreeMap<String, JsonValue> = contract
+            .document_schemas()
+            .iter()
+            .map(|(name, schema)| {
+                serde_json::to_value(schema)
+                    .map(|schema| (name.clone(), schema))
+                    .map_err(|e| format!("Unable to convert schema of {}: {}", name, e))
+            })
+            .collect::<Result<_, _>>()?;
+        let mut variant_schemas = schemas.clone();
+        variant_schemas
+            .values_mut()
+            .for_each(|schema| variant.apply(schema));
+        if variant_schemas == schemas {
+            continue;
+        }
+        changed = true;
+
+        let definitions = contract
+            .schema_defs()
+            .map(serde_json::to_value)
+            .transpose()
+            .map_err(|e| format!("Unable to convert schema definitions: {}", e))?
+            .map(Value::from);
+        let document_schemas = serde_json::to_value(variant_schemas)
+            .map_err(|e| format!("Unable to convert schemas: {}", e))?;
+        let factory = DataContractFactory::new(platform_version.protocol_version)
+            .map_err(|e| e.to_string())?;
+        // Set a fake identity nonce like for other start contracts, real ones
+        // are set during strategy execution.
+        let variant_contract = factory
+            .create_with_value_config(
+                contract.owner_id(),
+                1,
+                Value::from(document_schemas),
+                None,
+                definitions,
+            )
+            .map_err(|e| format!("Invalid schema of the {} variant: {}", variant.name, e))?;
+
+        let original_id = contract.id();
+        for operation in variant_strategy.operations.iter_mut() {
+            if let OperationType::Document(document_op) = &mut operation.op_type {
+                if document_op.contract.id() != original_id {
+                    continue;
+                }
+                document_op.document_type = variant_contract
+                    .data_contract()
+                    .document_type_for_name(document_op.document_type.name())
+                    .map_err(|e| e.to_string())?
+                    .to_owned_document_type();
+                document_op.contract = variant_contract.data_contract().clone();
+            }
+        }
+        *created_contract = variant_contract;
+        *updates = None;
+    }
+    Ok(changed.then_some(variant_strategy))
+}
+
+fn variant_outcome(event: BackendEvent) -> Result<VariantOutcome, String> {
+    match event {
+        BackendEvent::StrategyCompleted {
+            result:
+                StrategyCompletionResult::Success {
+                    transition_count,
+                    success_count,
+                    success_percent,
+                    rate,
+                    success_rate,
+                    run_time,
+                    dash_spent_identity,
+                    ..
+                },
+            ..
+        } => Ok(VariantOutcome {
+            transition_count,
+            success_count,
+            success_percent,
+            rate,
+            success_rate,
+            run_time_secs: run_time.as_secs(),
+            dash_spent_identity,
+        }),
+        BackendEvent::StrategyCompleted {
+            result: StrategyCompletionResult::PartiallyCompleted { reason, .. },
+            ..
+        } => Err(format!("halted: {}", reason)),
+        BackendEvent::StrategyError { error } => Err(error),
+        _ => Err("unexpected run result".to_owned()),
+    }
+}
+
+fn render_matrix(
+    strategy_name: &str,
+    block_mode: bool,
+    count: u64,
+    outcomes: &[(&str, Result<VariantOutcome, String>)],
+) -> String {
+    let mut lines = vec![
+        format!(
+            "Contract variants of '{}', {} {} per run",
+            strategy_name,
+            count,
+            if block_mode { "blocks" } else { "seconds" }
+        ),
+        String::new(),
+        format!(
+            "{:<20}{:>10}{:>10}{:>10}{:>10}{:>10}{:>10}{:>14}{:>16}",
+            "Variant",
+            "Attempted",
+            "Succeeded",
+            "Success %",
+            "tx/s",
+            "OK tx/s",
+            "Time (s)",
+            "Dash spent",
+            "Dash per OK tx"
+        ),
+    ];
+    for (variant, outcome) in outcomes {
+        lines.push(match outcome {
+            Ok(outcome) => format!(
+                "{:<20}{:>10}{:>10}{:>10}{:>10}{:>10}{:>10}{:>14.8}{:>16}",
+                variant,
+                outcome.transition_count,
+                outcome.success_count,
+                outcome.success_percent,
+                outcome.rate,
+                outcome.success_rate,
+                outcome.run_time_secs,
+                outcome.dash_spent_identity,
+                if outcome.success_count > 0 {
+                    format!(
+                        "{:.8}",
+                        outcome.dash_spent_identity / outcome.success_count as f64
+                    )
+                } else {
+                    "-".to_owned()
+                }
+            ),
+            Err(error) => format!("{:<20}{}", variant, error),
+        });
+    }
+    lines.join("\n")
+}
+
+/// Runs the strategy once per contract variant and renders the comparison
+/// matrix. Each run is made under a temporary strategy named after the
+/// variant, so its report is saved like any other run.
+pub(super) async fn run_contract_variants<'s>(
+    sdk: &Sdk,
+    app_state: &'s AppState,
+    task: StrategyTask,
+    insight: &'s InsightAPIClient,
+) -> BackendEvent<'s> {
+    let StrategyTask::RunContractVariants(ref strategy_name, count, verify_proofs, block_mode) =
+        task
+    else {
+        unreachable!("only contract variants runs are matched");
+    };
+    let Some(strategy) = app_state
+        .available_strategies
+        .lock()
+        .await
+        .get(strategy_name)
+        .cloned()
+    else {
+        return BackendEvent::StrategyError {
+            error: format!("No strategy loaded with name \"{}\"", strategy_name),
+        };
+    };
+
+    let mut outcomes = Vec::new();
+    for variant in VARIANTS.iter() {
+        let variant_strategy = match apply_variant(&strategy, variant, sdk.version()) {
+            Ok(Some(variant_strategy)) => variant_strategy,
+            Ok(None) if variant.is_original() => strategy.clone(),
+            Ok(None) => {
+                outcomes.push((
+                    variant.name,
+                    Err("schemas are the same as the original ones, skipped".to_owned()),
+                ));
+                continue;
+            }
+            Err(e) => {
+                outcomes.push((variant.name, Err(e)));
+                continue;
+            }
+        };
+        let variant_strategy_name = format!("{} ({})", strategy_name, variant.name);
+        tracing::info!("Running contract variant '{}'", variant_strategy_name);
+        app_state
+            .available_strategies
+            .lock()
+            .await
+            .insert(variant_strategy_name.clone(), variant_strategy);
+        let event = run_single_strategy_task(
+            sdk,
+            app_state,
+            StrategyTask::RunStrategy(
+                variant_strategy_name.clone(),
+                count,
+                verify_proofs,
+                block_mode,
+                None,
+            ),
+            insight,
+        )
+        .await;
+        outcomes.push((variant.name, variant_outcome(event)));
+        app_state
+            .available_strategies
+            .lock()
+            .await
+            .remove(&variant_strategy_name);
+    }
+
+    BackendEvent::TaskCompleted {
+        execution_result: Ok(render_matrix(strategy_name, block_mode, count, &outcomes).into()),
+        task: Task::Strategy(task),
+    }
+}
@@ -0,0 +1,256 @@
+//! History of tasks submitted from the UI.
+//! Tasks are kept with their parameters during the session to be found again
+//! and rerun. Tasks with a template can be rerun with parameters edited as
+//! text, others are rerun as they were.
+
+use std::collections::VecDeque;
+
+use chrono::{DateTime, Local};
+
+use super::{
+    contracts::ContractTask,
+    documents::{clauses::QueryClauses, DocumentTask},
+    platform_info::PlatformInfoTask,
+    search::SearchTask,
+    Task,
+};
+
+/// Oldest entries are dropped past this number.
+const HISTORY_LIMIT: usize = 200;
+
+#[derive(Debug, Clone)]
+pub(crate) struct TaskHistoryEntry {
+    pub(crate) task: Task,
+    fingerprint: u64,
+    /// Whether the task blocked the UI when submitted
+    pub(crate) block: bool,
+    submitted_at: DateTime<Local>,
+    /// Unknown until the task completes
+    succeeded: Option<bool>,
+}
+
+impl TaskHistoryEntry {
+    /// One line description, parameters are shown for templated tasks only as
+    /// others may carry secrets.
+    pub(crate) fn summary(&self) -> String {
+        let status = match self.succeeded {
+            None => "…",
+            Some(true) => "ok",
+            Some(false) => "failed",
+        };
+        let parameters = template(&self.task)
+            .map(|parameters| {
+                parameters
+                    .into_iter()
+                    .map(|(name, value)| format!("{}: {}", name, value))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            })
+            .unwrap_or_default();
+        format!(
+            "{} [{:^6}] {} {}",
+            self.submitted_at.format("%H:%M:%S"),
+            status,
+            self.task.kind(),
+            parameters
+        )
+        .trim_end()
+        .to_owned()
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub(crate) struct TaskHistory {
+    /// Newest entries first
+    entries: VecDeque<TaskHistoryEntry>,
+}
+
+impl TaskHistory {
+    pub(crate) fn record(&mut self, task: &Task, block: bool) {
+        self.entries.push_front(TaskHistoryEntry {
+            task: task.clone(),
+            fingerprint: task.fingerprint(),
+            block,
+            submitted_at: Local::now(),
+            succeeded: None,
+        });
+        self.entries.truncate(HISTORY_LIMIT);
+    }
+
+    /// Sets the outcome of the latest pending entry of the task, `false` if
+    /// the task isn't in the history.
+    pub(crate) fn record_result(&mut self, task: &Task, succeeded: bool) -> bool {
+        let fingerprint = task.fingerprint();
+        match self
+            .entries
+            .iter_mut()
+            .find(|entry| entry.succeeded.is_none() && entry.fingerprint == fingerprint)
+        {
+            Some(entry) => {
+                entry.succeeded = Some(succeeded);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Entries with summaries containing every word of the query, newest
+    /// first.
+    pub(crate) fn search(&self, query: &str) -> Vec<TaskHistoryEntry> {
+        let terms: Vec<String> = query.split_whitespace().map(str::to_lowercase).collect();
+        self.entries
+            .iter()
+            .filter(|entry| {
+                let summary = entry.summary().to_lowercase();
+                terms.iter().all(|term| summary.contains(term))
+            })
+            .cloned()
+            .collect()
+    }
+}
+
+fn format_conditions(clauses: &QueryClauses) -> String {
+    clauses
+        .where_clauses
+        .iter()
+        .map(|(field, operator, value)| format!("{} {} {}", field, operator, value))
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+fn parse_conditions(text: &str) -> Result<Vec<(String, String, String)>, String> {
+    text.split(';')
+        .map(str::trim)
+        .filter(|condition| !condition.is_empty())
+        .map(|condition| {
+            let mut parts = condition.splitn(3, ' ');
+            match (parts.next(), parts.next(), parts.next()) {
+                (Some(field), Some(operator), Some(value)) => Ok((
+                    field.to_owned(),
+                    operator.to_owned(),
+                    value.trim().to_owned(),
+                )),
+                _ => Err(format!("Invalid condition: {}", condition)),
+            }
+        })
+        .collect()
+}
+
+fn format_order_by(clauses: &QueryClauses) -> String {
+    clauses
+        .order_by
+        .iter()
+        .map(|(field, ascending)| format!("{} {}", field, if *ascending { "asc" } else { "desc" }))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn parse_order_by_clause(clause: &str) -> Result<(String, bool), String> {
+    match clause.split_whitespace().collect::<Vec<_>>()[..] {
+        [field] | [field, "asc"] => Ok((field.to_owned(), true)),
+        [field, "desc"] => Ok((field.to_owned(), false)),
+        _ => Err(format!("Invalid order by clause: {}", clause)),
+    }
+}
+
+fn parse_order_by(text: &str) -> Result<Vec<(String, bool)>, String> {
+    text.split(',')
+        .map(str::trim)
+        .filter(|clause| !clause.is_empty())
+        .map(parse_order_by_clause)
+        .collect()
+}
+
+fn parse<T: std::str::FromStr>(name: &str, value: &str) -> Result<T, String> {
+    value
+        .trim()
+        .parse()
+        .map_err(|_| format!("Invalid parameter: {} ({})", name, value))
+}
+
+/// Editable parameters of the task with their current values, `None` if the
+/// task has no template.
+pub(crate) fn template(task: &Task) -> Option<Vec<(&'static str, String)>> {
+    let parameters = match task {
+        Task::FetchIdentityById(id, proof) => vec![
+            ("Identity ID", id.clone()),
+            ("With proof", proof.to_string()),
+        ],
+        Task::Contract(ContractTask::FetchContract(id)) => vec![("Contract ID", id.clone())],
+        Task::PlatformInfo(PlatformInfoTask::FetchSpecificEpochInfo(epoch)) => {
+            vec![("Epoch number", epoch.to_string())]
+        }
+        Task::PlatformInfo(
+            PlatformInfoTask::FetchManyEpochInfo(epoch, count)
+            | PlatformInfoTask::ExportEpochs(epoch, count),
+        ) => vec![
+            ("First epoch", epoch.to_string()),
+            ("Epochs count", count.to_string()),
+        ],
+        Task::Search(SearchTask::Search(query)) => vec![("Search", query.clone())],
+        Task::SetLogFilter(directives) => vec![("Filter directives", directives.clone())],
+        Task::Document(DocumentTask::QueryDocuments(query)) => {
+            vec![("Limit", query.limit.to_string())]
+        }
+        Task::Document(DocumentTask::QueryDocumentsWithClauses(clauses)) => vec![
+            ("Conditions", format_conditions(clauses)),
+            ("Order by", format_order_by(clauses)),
+            ("Limit", clauses.limit.to_string()),
+            ("Start at", clauses.start_at.clone().unwrap_or_default()),
+        ],
+        _ => return None,
+    };
+    Some(parameters)
+}
+
+/// The task with parameters of its template replaced by the values, in the
+/// template order.
+pub(crate) fn apply_template(task: &Task, values: &[String]) -> Result<Task, String> {
+    let value = |index: usize| values.get(index).map(String::as_str).unwrap_or_default();
+    let task = match task {
+        Task::FetchIdentityById(..) => {
+            Task::FetchIdentityById(value(0).trim().to_owned(), parse("With proof", value(1))?)
+        }
+        Task::Contract(ContractTask::FetchContract(_)) => {
+            Task::Contract(ContractTask::FetchContract(value(0).trim().to_owned()))
+        }
+        Task::PlatformInfo(PlatformInfoTask::FetchSpecificEpochInfo(_)) => Task::PlatformInfo(
+            PlatformInfoTask::FetchSpecificEpochInfo(parse("Epoch number", value(0))?),
+        ),
+        Task::PlatformInfo(PlatformInfoTask::FetchManyEpochInfo(..)) => {
+            Task::PlatformInfo(PlatformInfoTask::FetchManyEpochInfo(
+                parse("First epoch", value(0))?,
+                parse("Epochs count", value(1))?,
+            ))
+        }
+        Task::PlatformInfo(PlatformInfoTask::ExportEpochs(..)) => {
+            Task::PlatformInfo(PlatformInfoTask::ExportEpochs(
+                parse("First epoch", value(0))?,
+                parse("Epochs count", value(1))?,
+            ))
+        }
+        Task::Search(SearchTask::Search(_)) => {
+            Task::Search(SearchTask::Search(value(0).to_owned()))
+        }
+        Task::SetLogFilter(_) => Task::SetLogFilter(value(0).trim().to_owned()),
+        Task::Document(DocumentTask::QueryDocuments(query)) => {
+            let mut query = query.clone();
+            query.limit = parse("Limit", value(0))?;
+            Task::Document(DocumentTask::QueryDocuments(query))
+        }
+        Task::Document(DocumentTask::QueryDocumentsWithClauses(clauses)) => {
+            let clauses = QueryClauses {
+                where_clauses: parse_conditions(value(0))?,
+                order_by: parse_order_by(value(1))?,
+                limit: parse("Limit", value(2))?,
+                start_at: Some(value(3).trim().to_owned()).filter(|id| !id.is_empty()),
+                ..clauses.clone()
+            };
+            // Reported now rather than after the form is closed
+            clauses.document_query()?;
+            Task::Document(DocumentTask::QueryDocumentsWithClauses(clauses))
+        }
+        _ => return Err("The task has no parameters to edit".to_owned()),
+    };
+    Ok(task)
+}
@@ -0,0 +1,80 @@
+//! Tenderdash RPC client.
+//! Platform queries don't tell who proposed a block, so proposers are read
+//! from block headers of a Platform node's Tenderdash, usually the operator's
+//! own node as Tenderdash RPC is rarely exposed publicly.
+
+use serde_json::Value as JsonValue;
+
+use crate::config::Config;
+
+/// Block metas Tenderdash returns at most per request.
+const BLOCK_METAS_PAGE_SIZE: u64 = 20;
+
+#[derive(Debug, Clone)]
+pub(crate) struct TenderdashRpcClient {
+    url: String,
+}
+
+impl TenderdashRpcClient {
+    pub(crate) fn from_config(config: &Config) -> Option<Self> {
+        config
+            .tenderdash_rpc_url
+            .as_ref()
+            .filter(|url| !url.is_empty())
+            .map(|url| TenderdashRpcClient {
+                url: url.trim_end_matches('/').to_owned(),
+            })
+    }
+
+    /// ProTxHashes of the proposers of blocks in the height range, both ends
+    /// included, in hex.
+    async fn block_proposers(
+        &self,
+        min_height: u64,
+        max_height: u64,
+    ) -> Result<Vec<String>, String> {
+        let mut body: JsonValue = reqwest::Client::new()
+            .get(format!("{}/blockchain", self.url))
+            .query(&[("minHeight", min_height), ("maxHeight", max_height)])
+            .send()
+            .await
+            .map_err(|e| format!("Tenderdash RPC request failed: {}", e))?
+            .json()
+            .await
+            .map_err(|e| format!("Invalid Tenderdash RPC response: {}", e))?;
+        if let Some(error) = body.get("error").filter(|error| !error.is_null()) {
+            return Err(format!("Tenderdash RPC request failed: {}", error));
+        }
+
+        let block_metas = body["result"]["block_metas"].take();
+        Ok(block_metas
+            .as_array()
+            .ok_or_else(|| "Invalid Tenderdash RPC response: no block metas".to_owned())?
+            .iter()
+            .filter_map(|meta| meta["header"]["proposer_pro_tx_hash"].as_str())
+            .map(str::to_lowercase)
+            .collect())
+    }
+
+    /// Number of blocks in the height range proposed by the masternode.
+    pub(crate) async fn count_proposed_blocks(
+        &self,
+        min_height: u64,
+        max_height: u64,
+        pro_tx_hash: &str,
+    ) -> Result<u64, String> {
+        let mut count = 0;
+        let mut page_start = min_height;
+        while page_start <= max_height {
+            let page_end = (page_start + BLOCK_METAS_PAGE_SIZE - 1).min(max_height);
+            count += self
+                .block_proposers(page_start, page_end)
+                .await?
+                .iter()
+                .filter(|proposer| proposer.eq_ignore_ascii_case(pro_tx_hash))
+                .count() as u64;
+            page_start = page_end + 1;
+        }
+        Ok(count)
+    }
+}
@@ -0,0 +1,373 @@
+//! Replay of recorded DAPI sessions.
+//!
+//! Read queries of a DAPI trace recorded with payloads are issued again
+//! against the current network, and their results compared with the recorded
+//! ones to detect state drift or regressions between Platform versions.
+//! Identities and data contracts fetched by ID are replayed, using the last
+//! recorded response of each query. Recorded proofs are verified with Drive to
+//! get the entity they proved, but not their quorum signatures as quorums may
+//! have rotated since.
+
+use std::{collections::BTreeMap, fmt, fs, path::Path};
+
+use dash_sdk::{platform::Fetch, Sdk};
+use dpp::{
+    platform_value::string_encoding::Encoding,
+    prelude::{DataContract, Identifier, Identity},
+    serialization::{
+        PlatformDeserializable, PlatformDeserializableWithPotentialValidationFromVersionedStructure,
+    },
+    version::PlatformVersion,
+};
+use drive::drive::Drive;
+use serde::Serialize;
+
+use super::debug_text::DebugValue;
+
+/// Read query found in a trace.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum ReplayQuery {
+    Identity(Identifier),
+    DataContract(Identifier),
+}
+
+impl ReplayQuery {
+    /// Query of a recorded request, `None` for requests that aren't replayed.
+    fn from_request(request: &DebugValue) -> Option<Self> {
+        let (query, id): (fn(Identifier) -> Self, _) =
+            if let Some(v0) = request.find_struct("GetIdentityRequestV0") {
+                (ReplayQuery::Identity, v0.field("id"))
+            } else if let Some(v0) = request.find_struct("GetDataContractRequestV0") {
+                (ReplayQuery::DataContract, v0.field("id"))
+            } else {
+                return None;
+            };
+        let id = Identifier::from_bytes(&id?.bytes()?).ok()?;
+        Some(query(id))
+    }
+
+    /// Name of the response variant carrying the serialized entity.
+    fn entity_variant(&self) -> &'static str {
+        match self {
+            ReplayQuery::Identity(_) => "Identity",
+            ReplayQuery::DataContract(_) => "DataContract",
+        }
+    }
+
+    /// Entity of a recorded response as JSON, `Null` if it didn't exist.
+    fn recorded_entity(
+        &self,
+        response: &DebugValue,
+        platform_version: &PlatformVersion,
+    ) -> Result<serde_json::Value, String> {
+        let invalid = |e: &dyn fmt::Display| format!("Invalid recorded response: {}", e);
+        if let Some(proof) = response.find_struct("Proof") {
+            let proof = proof
+                .field("grovedb_proof")
+                .and_then(DebugValue::bytes)
+                .ok_or_else(|| invalid(&"no GroveDB proof"))?;
+            return match self {
+                ReplayQuery::Identity(id) => {
+                    let (_, identity) = Drive::verify_full_identity_by_identity_id(
+                        &proof,
+                        false,
+                        id.to_buffer(),
+                        platform_version,
+                    )
+                    .map_err(|e| invalid(&e))?;
+                    to_json(identity)
+                }
+                ReplayQuery::DataContract(id) => {
+                    let (_, contract) = Drive::verify_contract(
+                        &proof,
+                        None,
+                        false,
+                        false,
+                        id.to_buffer(),
+                        platform_version,
+                    )
+                    .map_err(|e| invalid(&e))?;
+                    to_json(contract)
+                }
+            };
+        }
+
+        let bytes = response
+            .find_tuple(self.entity_variant())
+            .and_then(DebugValue::inner)
+            .and_then(DebugValue::bytes)
+            .ok_or_else(|| invalid(&"no proof nor entity"))?;
+        if bytes.is_empty() {
+            return Ok(serde_json::Value::Null);
+        }
+        match self {
+            ReplayQuery::Identity(_) => to_json(Some(
+                Identity::deserialize_from_bytes(&bytes).map_err(|e| invalid(&e))?,
+            )),
+            ReplayQuery::DataContract(_) => to_json(Some(
+                DataContract::versioned_deserialize(&bytes, false, platform_version)
+                    .map_err(|e| invalid(&e))?,
+            )),
+        }
+    }
+
+    /// Current entity as JSON, `Null` if it doesn't exist.
+    async fn current_entity(&self, sdk: &Sdk) -> Result<serde_json::Value, String> {
+        match self {
+            ReplayQuery::Identity(id) => {
+                to_json(Identity::fetch(sdk, *id).await.map_err(|e| e.to_string())?)
+            }
+            ReplayQuery::DataContract(id) => to_json(
+                DataContract::fetch(sdk, *id)
+                    .await
+                    .map_err(|e| e.to_string())?,
+            ),
+        }
+    }
+}
+
+impl fmt::Display for ReplayQuery {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReplayQuery::Identity(id) => write!(f, "identity {}", id.to_string(Encoding::Base58)),
+            ReplayQuery::DataContract(id) => {
+                write!(f, "data contract {}", id.to_string(Encoding::Base58))
+            }
+        }
+    }
+}
+
+fn to_json(entity: Option<impl Serialize>) -> Result<serde_json::Value, String> {
+    serde_json::to_value(entity).map_err(|e| e.to_string())
+}
+
+/// Top level fields that differ between the recorded and current entities.
+fn differences(recorded: &serde_json::Value, current: &serde_json::Value) -> Vec<String> {
+    match (recorded, current) {
+        _ if recorded == current => Vec::new(),
+        (serde_json::Value::Null, _) => vec!["created since recorded".to_owned()],
+        (_, serde_json::Value::Null) => vec!["removed since recorded".to_owned()],
+        (serde_json::Value::Object(recorded), serde_json::Value::Object(current)) => {
+            let mut fields: Vec<&String> = recorded.keys().chain(current.keys()).collect();
+            fields.sort();
+            fields.dedup();
+            fields
+                .into_iter()
+                .filter_map(|field| {
+                    let before = recorded.get(field).unwrap_or(&serde_json::Value::Null);
+                    let after = current.get(field).unwrap_or(&serde_json::Value::Null);
+                    if before == after {
+                        None
+                    } else if before.is_object()
+                        || before.is_array()
+                        || after.is_object()
+                        || after.is_array()
+                    {
+                        Some(format!("{} changed", field))
+                    } else {
+                        Some(format!("{}: {} -> {}", field, before, after))
+                    }
+                })
+                .collect()
+        }
+        _ => vec![format!("{} -> {}", recorded, current)],
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReplayOutcome {
+    Unchanged,
+    Drifted,
+    /// Either response couldn't be read, or the replayed request failed
+    Failed,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ReplayedQuery {
+    pub query: String,
+    /// Unix timestamp in milliseconds of the recorded request
+    pub recorded_at: Option<u64>,
+    /// Block height of the recorded response
+    pub recorded_height: Option<u64>,
+    pub outcome: ReplayOutcome,
+    /// Differing top level fields of drifted entities
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub differences: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Results of a trace replay.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ReplayReport {
+    /// Trace entries with recorded payloads
+    pub entries: usize,
+    /// Requests of entries that aren't replayed, like state transitions
+    pub skipped: usize,
+    pub queries: Vec<ReplayedQuery>,
+}
+
+impl ReplayReport {
+    fn count(&self, outcome: ReplayOutcome) -> usize {
+        self.queries
+            .iter()
+            .filter(|query| query.outcome == outcome)
+            .count()
+    }
+
+    pub fn drifted(&self) -> usize {
+        self.count(ReplayOutcome::Drifted)
+    }
+
+    pub fn failed(&self) -> usize {
+        self.count(ReplayOutcome::Failed)
+    }
+}
+
+impl fmt::Display for ReplayReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for query in &self.queries {
+            match query.outcome {
+                ReplayOutcome::Unchanged => writeln!(f, "unchanged {}", query.query)?,
+                ReplayOutcome::Drifted => {
+                    writeln!(f, "drifted   {}", query.query)?;
+                    for difference in &query.differences {
+                        writeln!(f, "          {}", difference)?;
+                    }
+                }
+                ReplayOutcome::Failed => writeln!(
+                    f,
+                    "failed    {}: {}",
+                    query.query,
+                    query.error.as_deref().unwrap_or_default()
+                )?,
+            }
+        }
+        write!(
+            f,
+            "{} queries replayed from {} entries: {} unchanged, {} drifted, {} failed, {} \
+             requests skipped",
+            self.queries.len(),
+            self.entries,
+            self.count(ReplayOutcome::Unchanged),
+            self.drifted(),
+            self.failed(),
+            self.skipped
+        )
+    }
+}
+
+/// Recorded request and response of a trace entry.
+struct RecordedExchange {
+    started_at: Option<u64>,
+    request: String,
+    response: Option<String>,
+}
+
+/// Request and last response payloads of an entry, from its fields or events.
+fn recorded_exchange(entry: &serde_json::Value) -> Option<RecordedExchange> {
+    let fields = std::iter::once(&entry["fields"]).chain(
+        entry["events"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .map(|event| &event["fields"]),
+    );
+    let mut request = None;
+    let mut response = None;
+    for fields in fields {
+        if let Some(payload) = fields["request"].as_str() {
+            request.get_or_insert_with(|| payload.to_owned());
+        }
+        if let Some(payload) = fields["response"].as_str() {
+            response = Some(payload.to_owned());
+        }
+    }
+    Some(RecordedExchange {
+        started_at: entry["started_at"].as_u64(),
+        request: request?,
+        response,
+    })
+}
+
+/// Replays the read queries of the trace file.
+pub async fn replay_trace(sdk: &Sdk, path: &Path) -> Result<ReplayReport, String> {
+    let trace = fs::read_to_string(path)
+        .map_err(|e| format!("Unable to read trace {}: {}", path.display(), e))?;
+
+    let mut report = ReplayReport::default();
+    // Last recorded exchange of each query
+    let mut recorded = BTreeMap::new();
+    for (number, line) in trace.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let entry: serde_json::Value = serde_json::from_str(line)
+            .map_err(|e| format!("Invalid trace entry on line {}: {}", number + 1, e))?;
+        let Some(exchange) = recorded_exchange(&entry) else {
+            continue;
+        };
+        report.entries += 1;
+        match DebugValue::parse(&exchange.request)
+            .ok()
+            .as_ref()
+            .and_then(ReplayQuery::from_request)
+        {
+            // Failed requests don't replace a recorded response
+            Some(query) if exchange.response.is_some() || !recorded.contains_key(&query) => {
+                recorded.insert(query, exchange);
+            }
+            Some(_) => {}
+            None => report.skipped += 1,
+        }
+    }
+    if report.entries == 0 {
+        return Err(
+            "No payloads in the trace, record it with EXPLORER_DAPI_TRACE_PAYLOADS=true".to_owned(),
+        );
+    }
+
+    for (query, exchange) in recorded {
+        tracing::info!("Replaying {}", query);
+        let response = exchange
+            .response
+            .as_deref()
+            .ok_or_else(|| "No recorded response".to_owned())
+            .and_then(|response| {
+                DebugValue::parse(response).map_err(|e| format!("Invalid recorded response: {}", e))
+            });
+        let recorded_height = response.as_ref().ok().and_then(|response| {
+            response
+                .find_struct("ResponseMetadata")?
+                .field("height")?
+                .number()
+        });
+        let result = match response {
+            Ok(response) => match query.recorded_entity(&response, sdk.version()) {
+                Ok(recorded) => query
+                    .current_entity(sdk)
+                    .await
+                    .map(|current| differences(&recorded, &current)),
+                Err(e) => Err(e),
+            },
+            Err(e) => Err(e),
+        };
+        let (outcome, differences, error) = match result {
+            Ok(differences) if differences.is_empty() => {
+                (ReplayOutcome::Unchanged, differences, None)
+            }
+            Ok(differences) => (ReplayOutcome::Drifted, differences, None),
+            Err(e) => (ReplayOutcome::Failed, Vec::new(), Some(e)),
+        };
+        report.queries.push(ReplayedQuery {
+            query: query.to_string(),
+            recorded_at: exchange.started_at,
+            recorded_height,
+            outcome,
+            differences,
+            error,
+        });
+    }
+    Ok(report)
+}
@@ -0,0 +1,135 @@
+//! Network traffic meter.
+//! Requests and bytes exchanged with DAPI and Insight over the last minute are
+//! shown in the status bar, to spot runaway polling loops and see the network
+//! cost of screens or strategies. Insight traffic is counted by its client,
+//! response sizes being those announced by the server. DAPI traffic is taken
+//! from the DAPI client traces, sizes are approximated by the size of payloads
+//! debug representation. Core RPC requests are issued by the SDK internally
+//! for quorum keys and aren't counted.
+
+use std::{
+    collections::VecDeque,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Mutex,
+    },
+    time::{Duration, Instant},
+};
+
+/// Traffic older than this isn't shown.
+const WINDOW: Duration = Duration::from_secs(60);
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+static SAMPLES: Mutex<VecDeque<Sample>> = Mutex::new(VecDeque::new());
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Channel {
+    Dapi,
+    Insight,
+}
+
+impl Channel {
+    fn name(self) -> &'static str {
+        match self {
+            Channel::Dapi => "DAPI",
+            Channel::Insight => "Insight",
+        }
+    }
+}
+
+struct Sample {
+    at: Instant,
+    channel: Channel,
+    requests: u64,
+    sent: u64,
+    received: u64,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+struct Traffic {
+    requests: u64,
+    sent: u64,
+    received: u64,
+}
+
+pub(crate) fn enable() {
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+pub(crate) fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+fn record(channel: Channel, requests: u64, sent: usize, received: usize) {
+    if !is_enabled() {
+        return;
+    }
+    let now = Instant::now();
+    let mut samples = SAMPLES.lock().expect("traffic lock poisoned");
+    while samples
+        .front()
+        .is_some_and(|sample| now.duration_since(sample.at) > WINDOW)
+    {
+        samples.pop_front();
+    }
+    samples.push_back(Sample {
+        at: now,
+        channel,
+        requests,
+        sent: sent as u64,
+        received: received as u64,
+    });
+}
+
+/// Counts a request of `bytes` sent on the channel.
+pub(crate) fn record_request(channel: Channel, bytes: usize) {
+    record(channel, 1, bytes, 0);
+}
+
+/// Counts `bytes` received on the channel.
+pub(crate) fn record_response(channel: Channel, bytes: usize) {
+    record(channel, 0, 0, bytes);
+}
+
+fn last_minute(channel: Channel) -> Traffic {
+    let now = Instant::now();
+    SAMPLES
+        .lock()
+        .expect("traffic lock poisoned")
+        .iter()
+        .filter(|sample| sample.channel == channel && now.duration_since(sample.at) <= WINDOW)
+        .fold(Traffic::default(), |traffic, sample| Traffic {
+            requests: traffic.requests + sample.requests,
+            sent: traffic.sent + sample.sent,
+            received: traffic.received + sample.received,
+        })
+}
+
+fn bytes_str(bytes: u64) -> String {
+    match bytes {
+        0..=1023 => format!("{}B", bytes),
+        1024..=1_048_575 => format!("{:.1}K", bytes as f64 / 1024.0),
+        _ => format!("{:.1}M", bytes as f64 / 1_048_576.0),
+    }
+}
+
+/// Traffic of the last minute by channel, `None` if the meter is disabled.
+pub(crate) fn display() -> Option<String> {
+    is_enabled().then(|| {
+        [Channel::Dapi, Channel::Insight]
+            .into_iter()
+            .map(|channel| {
+                let traffic = last_minute(channel);
+                format!(
+                    "{} {}/min ↑{} ↓{}",
+                    channel.name(),
+                    traffic.requests,
+                    bytes_str(traffic.sent),
+                    bytes_str(traffic.received)
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(" · ")
+    })
+}
@@ -0,0 +1,201 @@
+//! Guided tutorial.
+//! Walks a new user through the basic flow step by step, a step is considered
+//! done once the backend reports a successful execution of a matching task.
+
+use super::{
+    contracts::ContractTask, documents::DocumentTask, identities::IdentityTask,
+    platform_info::PlatformInfoTask, AppState, AppStateUpdate, BackendEvent, Task,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum TutorialStep {
+    FundWallet,
+    RegisterIdentity,
+    FetchDpnsContract,
+    QueryDocuments,
+    CheckVersionVoting,
+}
+
+impl TutorialStep {
+    const ALL: [TutorialStep; 5] = [
+        TutorialStep::FundWallet,
+        TutorialStep::RegisterIdentity,
+        TutorialStep::FetchDpnsContract,
+        TutorialStep::QueryDocuments,
+        TutorialStep::CheckVersionVoting,
+    ];
+
+    fn title(&self) -> &'static str {
+        match self {
+            TutorialStep::FundWallet => "Fund a wallet",
+            TutorialStep::RegisterIdentity => "Register an identity",
+            TutorialStep::FetchDpnsContract => "Fetch the DPNS contract",
+            TutorialStep::QueryDocuments => "Query documents",
+            TutorialStep::CheckVersionVoting => "Check protocol version voting",
+        }
+    }
+
+    fn instructions(&self) -> &'static str {
+        match self {
+            TutorialStep::FundWallet => {
+                "Go to Wallet (w on the main screen) and add a wallet by private key (a). \
+                 Send some Dash to its receive address (c copies it) and refresh the balance \
+                 (b) until it's not zero."
+            }
+            TutorialStep::RegisterIdentity => {
+                "On the Wallet screen press i to register an identity funded from the wallet. \
+                 Registration waits for an asset lock, so it may take a while."
+            }
+            TutorialStep::FetchDpnsContract => {
+                "Go to Contracts (c on the main screen), open Fetch system contract (s) and \
+                 fetch the DPNS contract (n)."
+            }
+            TutorialStep::QueryDocuments => {
+                "On the Contracts screen select the DPNS contract with Enter, pick the domain \
+                 document type and query its documents (f)."
+            }
+            TutorialStep::CheckVersionVoting => {
+                "Go to Platform information (p on the main screen) and check how masternodes \
+                 vote for protocol versions (v)."
+            }
+        }
+    }
+
+    /// Whether the backend event proves the step was done.
+    fn is_completed_by(&self, event: &BackendEvent) -> bool {
+        if let (
+            TutorialStep::FundWallet,
+            BackendEvent::TaskCompletedStateChange {
+                execution_result: Ok(_),
+                app_state_update: AppStateUpdate::LoadedWallet(wallet),
+                ..
+            },
+        ) = (self, event)
+        {
+            return wallet.balance() > 0;
+        }
+
+        let (BackendEvent::TaskCompleted {
+            task,
+            execution_result: Ok(_),
+        }
+        | BackendEvent::TaskCompletedStateChange {
+            task,
+            execution_result: Ok(_),
+            ..
+        }) = event
+        else {
+            return false;
+        };
+
+        matches!(
+            (self, task),
+            (
+                TutorialStep::RegisterIdentity,
+                Task::Identity(
+                    IdentityTask::RegisterIdentity(_)
+                        | IdentityTask::RegisterIdentityWithAssetLock(_)
+                        | IdentityTask::ContinueRegistration
+                )
+            ) | (
+                TutorialStep::FetchDpnsContract,
+                Task::Contract(ContractTask::FetchDPNSContract)
+            ) | (
+                TutorialStep::QueryDocuments,
+                Task::Document(
+                    DocumentTask::QueryDocuments(_) | DocumentTask::QueryDocumentsWithClauses(_)
+                )
+            ) | (
+                TutorialStep::CheckVersionVoting,
+                Task::PlatformInfo(PlatformInfoTask::FetchCurrentVersionVotingState)
+            )
+        )
+    }
+}
+
+/// Progress of a running tutorial.
+#[derive(Debug, Default)]
+pub(crate) struct Tutorial {
+    /// Index of the current step, equals to the steps count once finished
+    step_index: usize,
+}
+
+impl Tutorial {
+    fn current_step(&self) -> Option<TutorialStep> {
+        TutorialStep::ALL.get(self.step_index).copied()
+    }
+
+    /// Moves to the next step if the event completes the current one.
+    pub(crate) fn on_backend_event(&mut self, event: &BackendEvent) {
+        if let Some(step) = self.current_step() {
+            if step.is_completed_by(event) {
+                tracing::info!("tutorial step done: {}", step.title());
+                self.step_index += 1;
+            }
+        }
+    }
+
+    /// Short progress line for the status bar.
+    pub(crate) fn status_hint(&self) -> String {
+        match self.current_step() {
+            Some(step) => format!(
+                "Tutorial {}/{}: {}",
+                self.step_index + 1,
+                TutorialStep::ALL.len(),
+                step.title()
+            ),
+            None => "Tutorial finished".to_owned(),
+        }
+    }
+
+    pub(crate) fn display(&self) -> String {
+        let mut lines: Vec<String> = TutorialStep::ALL
+            .iter()
+            .enumerate()
+            .map(|(i, step)| {
+                let mark = if i < self.step_index { "x" } else { " " };
+                format!("[{}] {}. {}", mark, i + 1, step.title())
+            })
+            .collect();
+
+        lines.push(String::new());
+        match self.current_step() {
+            Some(step) => {
+                lines.push(format!("Next: {}", step.title()));
+                lines.push(step.instructions().to_owned());
+            }
+            None => lines.push(
+                "All steps are done, you're ready to explore Dash Platform on your own!".to_owned(),
+            ),
+        }
+
+        lines.join("\n")
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum TutorialTask {
+    Start,
+    Stop,
+}
+
+impl AppState {
+    pub(crate) async fn run_tutorial_task(&self, task: TutorialTask) -> BackendEvent {
+        let mut tutorial = self.tutorial.lock().await;
+        let message = match task {
+            TutorialTask::Start => {
+                let started = tutorial.insert(Tutorial::default());
+                started.display()
+            }
+            TutorialTask::Stop => {
+                *tutorial = None;
+                "Tutorial stopped".to_owned()
+            }
+        };
+
+        BackendEvent::TaskCompleted {
+            task: Task::Tutorial(task),
+            execution_result: Ok(message.into()),
+        }
+    }
+}
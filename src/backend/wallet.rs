@@ -1,5 +1,8 @@
+mod fees;
+pub(crate) mod profiles;
+
 use std::{
-    collections::{BTreeMap, HashMap},
+    collections::{BTreeMap, BTreeSet, HashMap},
     ops::{Deref, DerefMut},
     str::FromStr,
 };
@@ -28,55 +31,144 @@ use rand::{prelude::StdRng, Rng, SeedableRng};
 use rs_dapi_client::DapiRequestExecutor;
 use tokio::sync::{Mutex, MutexGuard};
 
-use super::{AppStateUpdate, BackendEvent, CompletedTaskPayload, Task};
+use self::fees::{
+    asset_lock_transaction_size, fee_for_size, ECONOMY_TARGET_BLOCKS, MIN_RELAY_FEE_RATE,
+    NORMAL_TARGET_BLOCKS, PRIORITY_TARGET_BLOCKS,
+};
+pub(crate) use self::fees::{FeeEstimates, FeeLevel, FeeSettings};
+use self::profiles::WalletProfiles;
+use super::{json_store::JsonStore, AppStateUpdate, BackendEvent, CompletedTaskPayload, Task};
 use crate::backend::insight::{InsightAPIClient, InsightError};
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum WalletTask {
     AddByPrivateKey(String),
     Refresh,
+    /// Check whether unconfirmed UTXOs were mined since the last refresh
+    RefreshUnconfirmed,
     CopyAddress,
     ClearLoadedWallet,
     SplitUTXOs(u32),
+    EstimateFees,
+    SetFeeLevel(FeeLevel),
+    /// Register a wallet under a label and load it, its key is encrypted with
+    /// the passphrase
+    AddWallet {
+        label: String,
+        private_key: String,
+        passphrase: String,
+    },
+    /// Load a registered wallet in place of the current one
+    SwitchWallet {
+        label: String,
+        passphrase: String,
+    },
+    ListWallets,
+    /// Pick the UTXOs funding the next asset locks, any UTXOs are used if
+    /// none are picked
+    SetCoinControl(Vec<OutPoint>),
 }
 
 pub async fn add_wallet_by_private_key<'s>(
     wallet_state: &'s Mutex<Option<Wallet>>,
     private_key: &String,
-) {
-    let private_key = if private_key.len() == 64 {
+    network: Network,
+) -> Result<(), String> {
+    let wallet = wallet_from_private_key(private_key, network)?;
+
+    let mut wallet_guard = wallet_state.lock().await;
+    *wallet_guard = Some(wallet);
+    Ok(())
+}
+
+/// Wallet of a hex or WIF private key, with no UTXOs loaded yet.
+pub(crate) fn wallet_from_private_key(
+    private_key: &str,
+    network: Network,
+) -> Result<Wallet, String> {
+    let mut private_key = if private_key.len() == 64 {
         // hex
-        let bytes = hex::decode(private_key).expect("expected hex"); // TODO error hadling
-        PrivateKey::from_slice(bytes.as_slice(), Network::Testnet).expect("expected private key")
+        let bytes = hex::decode(private_key).map_err(|e| format!("Invalid hex key: {}", e))?;
+        PrivateKey::from_slice(bytes.as_slice(), network)
+            .map_err(|e| format!("Invalid private key: {}", e))?
     } else {
-        PrivateKey::from_wif(private_key.as_str()).expect("expected WIF key")
-        // TODO error handling
+        PrivateKey::from_wif(private_key).map_err(|e| format!("Invalid WIF key: {}", e))?
     };
 
+    // WIF keys carry their network, testnet and local networks share the prefix
+    // so only mainnet and non-mainnet keys can be told apart
+    if (private_key.network == Network::Dash) != (network == Network::Dash) {
+        return Err(format!(
+            "Private key belongs to {} network, but the explorer is configured for {}",
+            private_key.network, network
+        ));
+    }
+    private_key.network = network;
+
     let secp = Secp256k1::new();
     let public_key = private_key.public_key(&secp);
-    // todo: make the network be part of state
-    let address = Address::p2pkh(&public_key, Network::Testnet);
-    let wallet = Wallet::SingleKeyWallet(SingleKeyWallet {
+    let address = Address::p2pkh(&public_key, network);
+    Ok(Wallet::SingleKeyWallet(SingleKeyWallet {
         private_key,
         public_key,
         address,
         utxos: Default::default(),
-    });
+        unconfirmed_utxos: Default::default(),
+        confirmations: Default::default(),
+        coin_control: Default::default(),
+    }))
+}
 
-    let mut wallet_guard = wallet_state.lock().await;
-    *wallet_guard = Some(wallet);
+/// Checks the loaded wallet can be replaced by the wallet without losing its
+/// key, that is it's registered or the same wallet.
+fn ensure_replaceable(
+    current: Option<&Wallet>,
+    wallet_profiles: &WalletProfiles,
+    wallet: &Wallet,
+) -> Result<(), String> {
+    match current {
+        Some(current)
+            if wallet_profiles.label_of(current).is_none() && !current.same_key(wallet) =>
+        {
+            Err(format!(
+                "The loaded wallet {} isn't registered, add it with a label first so its key \
+                 isn't lost",
+                current.receive_address()
+            ))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Loads the wallet in place of the current one. Returns the error of the
+/// wallet refresh, the wallet is loaded anyway.
+async fn load_wallet(
+    wallet_state: &Mutex<Option<Wallet>>,
+    mut wallet: Wallet,
+    insight: &InsightAPIClient,
+) -> Result<(), String> {
+    let refreshed = wallet.reload_utxos(insight).await.map(|_| ());
+    *wallet_state.lock().await = Some(wallet);
+    refreshed.map_err(|e| format!("Unable to refresh wallet: {}", e))
 }
 
 pub(super) async fn run_wallet_task<'s>(
     sdk: &Sdk,
     wallet_state: &'s Mutex<Option<Wallet>>,
+    fee_settings: &'s Mutex<FeeSettings>,
+    wallet_profiles: &'s Mutex<JsonStore<WalletProfiles>>,
     task: WalletTask,
     insight: &'s InsightAPIClient,
+    network: Network,
 ) -> BackendEvent<'s> {
     match task {
         WalletTask::AddByPrivateKey(ref private_key) => {
-            add_wallet_by_private_key(&wallet_state, private_key).await;
+            if let Err(e) = add_wallet_by_private_key(&wallet_state, private_key, network).await {
+                return BackendEvent::TaskCompleted {
+                    task: Task::Wallet(task),
+                    execution_result: Err(e),
+                };
+            }
 
             let wallet_guard = wallet_state.lock().await;
             let loaded_wallet_update = MutexGuard::map(wallet_guard, |opt| {
@@ -115,6 +207,36 @@ pub(super) async fn run_wallet_task<'s>(
                 }
             }
         }
+        WalletTask::RefreshUnconfirmed => {
+            let mut wallet_guard = wallet_state.lock().await;
+            if let Some(wallet) = wallet_guard.deref_mut() {
+                match wallet.refresh_unconfirmed(&insight).await {
+                    Ok(confirmed) => {
+                        let loaded_wallet_update = MutexGuard::map(wallet_guard, |opt| {
+                            opt.as_mut().expect("wallet was set above")
+                        });
+                        BackendEvent::TaskCompletedStateChange {
+                            task: Task::Wallet(task),
+                            execution_result: Ok(format!(
+                                "{} unconfirmed UTXOs got confirmed",
+                                confirmed
+                            )
+                            .into()),
+                            app_state_update: AppStateUpdate::LoadedWallet(loaded_wallet_update),
+                        }
+                    }
+                    Err(err) => BackendEvent::TaskCompleted {
+                        task: Task::Wallet(task),
+                        execution_result: Err(err.to_string()),
+                    },
+                }
+            } else {
+                BackendEvent::TaskCompleted {
+                    task: Task::Wallet(task),
+                    execution_result: Err(format!("No wallet loaded")),
+                }
+            }
+        }
         WalletTask::CopyAddress => {
             let wallet_guard = wallet_state.lock().await;
             if let Some(wallet) = wallet_guard.deref() {
@@ -166,9 +288,170 @@ pub(super) async fn run_wallet_task<'s>(
                 }
             }
         }
+        WalletTask::EstimateFees => {
+            let execution_result = match insight
+                .fee_rate_estimates(&[
+                    ECONOMY_TARGET_BLOCKS,
+                    NORMAL_TARGET_BLOCKS,
+                    PRIORITY_TARGET_BLOCKS,
+                ])
+                .await
+            {
+                Ok(estimates) => {
+                    let rate_for = |blocks| {
+                        estimates
+                            .get(&blocks)
+                            .copied()
+                            .unwrap_or(MIN_RELAY_FEE_RATE)
+                            .max(MIN_RELAY_FEE_RATE)
+                    };
+                    fee_settings.lock().await.estimates = Some(FeeEstimates {
+                        economy: rate_for(ECONOMY_TARGET_BLOCKS),
+                        normal: rate_for(NORMAL_TARGET_BLOCKS),
+                        priority: rate_for(PRIORITY_TARGET_BLOCKS),
+                    });
+                    Ok("Fetched fee rate estimates".into())
+                }
+                Err(e) => Err(format!("Unable to fetch fee rate estimates: {}", e)),
+            };
+
+            // Fee rate can still be picked from the previous or default
+            // estimates, so the state is provided even on failure
+            BackendEvent::TaskCompletedStateChange {
+                task: Task::Wallet(task),
+                execution_result,
+                app_state_update: AppStateUpdate::FeeSettings(fee_settings.lock().await),
+            }
+        }
+        WalletTask::SetFeeLevel(level) => {
+            let mut fee_settings_guard = fee_settings.lock().await;
+            fee_settings_guard.level = level;
+            let rate = fee_settings_guard.rate();
+            BackendEvent::TaskCompletedStateChange {
+                task: Task::Wallet(task),
+                execution_result: Ok(format!("Fee rate set to {} duffs/kB", rate).into()),
+                app_state_update: AppStateUpdate::FeeSettings(fee_settings_guard),
+            }
+        }
+        WalletTask::AddWallet {
+            ref label,
+            ref private_key,
+            ref passphrase,
+        } => {
+            // The wallet isn't locked along with profiles, screens lock them the
+            // other way around
+            let current = wallet_state.lock().await.clone();
+            let added = match wallet_from_private_key(private_key, network) {
+                Ok(wallet) => {
+                    let mut profiles = wallet_profiles.lock().await;
+                    ensure_replaceable(current.as_ref(), &profiles, &wallet)
+                        .and_then(|_| profiles.add(label, &wallet, private_key, passphrase))
+                        .map(|_| wallet)
+                }
+                Err(e) => Err(e),
+            };
+            let wallet = match added {
+                Ok(wallet) => wallet,
+                Err(e) => {
+                    return BackendEvent::TaskCompleted {
+                        task: Task::Wallet(task),
+                        execution_result: Err(e),
+                    }
+                }
+            };
+            let loaded = load_wallet(wallet_state, wallet, insight).await;
+            let execution_result =
+                loaded.map(|_| format!("Added and loaded wallet {}", label).into());
+            BackendEvent::TaskCompletedStateChange {
+                task: Task::Wallet(task),
+                execution_result,
+                app_state_update: AppStateUpdate::LoadedWallet(MutexGuard::map(
+                    wallet_state.lock().await,
+                    |opt| opt.as_mut().expect("wallet was set above"),
+                )),
+            }
+        }
+        WalletTask::SwitchWallet {
+            ref label,
+            ref passphrase,
+        } => {
+            let current = wallet_state.lock().await.clone();
+            let unlocked = {
+                let profiles = wallet_profiles.lock().await;
+                profiles
+                    .wallet(label, passphrase, network)
+                    .and_then(|wallet| {
+                        ensure_replaceable(current.as_ref(), &profiles, &wallet)?;
+                        Ok(wallet)
+                    })
+            };
+            let wallet = match unlocked {
+                Ok(wallet) => wallet,
+                Err(e) => {
+                    return BackendEvent::TaskCompleted {
+                        task: Task::Wallet(task),
+                        execution_result: Err(e),
+                    }
+                }
+            };
+            let loaded = load_wallet(wallet_state, wallet, insight).await;
+            let execution_result = loaded.map(|_| format!("Switched to wallet {}", label).into());
+            BackendEvent::TaskCompletedStateChange {
+                task: Task::Wallet(task),
+                execution_result,
+                app_state_update: AppStateUpdate::LoadedWallet(MutexGuard::map(
+                    wallet_state.lock().await,
+                    |opt| opt.as_mut().expect("wallet was set above"),
+                )),
+            }
+        }
+        WalletTask::ListWallets => {
+            let profiles = wallet_profiles.lock().await;
+            let count = profiles.labels().len();
+            BackendEvent::TaskCompletedStateChange {
+                task: Task::Wallet(task),
+                execution_result: Ok(format!("{} wallets registered", count).into()),
+                app_state_update: AppStateUpdate::WalletProfiles(profiles),
+            }
+        }
+        WalletTask::SetCoinControl(ref outpoints) => {
+            let mut wallet_guard = wallet_state.lock().await;
+            let Some(wallet) = wallet_guard.as_mut() else {
+                return BackendEvent::TaskCompleted {
+                    task: Task::Wallet(task),
+                    execution_result: Err(format!("No wallet loaded")),
+                };
+            };
+            let execution_result = wallet.set_coin_control(outpoints).map(|_| {
+                if outpoints.is_empty() {
+                    "Asset locks are funded from any UTXOs".into()
+                } else {
+                    format!("{} UTXOs picked to fund asset locks", outpoints.len()).into()
+                }
+            });
+            BackendEvent::TaskCompletedStateChange {
+                task: Task::Wallet(task),
+                execution_result,
+                app_state_update: AppStateUpdate::LoadedWallet(MutexGuard::map(
+                    wallet_guard,
+                    |opt| opt.as_mut().expect("wallet was set above"),
+                )),
+            }
+        }
     }
 }
 
+/// Wallet UTXO as listed to the user.
+#[derive(Debug, Clone)]
+pub(crate) struct Utxo {
+    pub(crate) outpoint: OutPoint,
+    pub(crate) amount: u64,
+    /// Unknown until the wallet is refreshed
+    pub(crate) confirmations: Option<u64>,
+    /// Whether the UTXO is picked to fund asset locks
+    pub(crate) coin_control: bool,
+}
+
 #[derive(Debug, thiserror::Error)]
 pub enum WalletError {
     #[error(transparent)]
@@ -183,6 +466,17 @@ pub enum Wallet {
 }
 
 impl Wallet {
+    /// Wallet keys are decoded from the state file without a network, so it
+    /// shall be set afterwards according to the configuration
+    pub(crate) fn set_network(&mut self, network: Network) {
+        match self {
+            Wallet::SingleKeyWallet(wallet) => {
+                wallet.private_key.network = network;
+                wallet.address = Address::p2pkh(&wallet.public_key, network);
+            }
+        }
+    }
+
     pub(crate) fn private_key_for_address(&self, address: &Address) -> &PrivateKey {
         match self {
             Wallet::SingleKeyWallet(single_wallet) => {
@@ -191,16 +485,18 @@ impl Wallet {
         }
     }
 
+    /// Builds and signs an asset lock transaction, `fee_rate` is in duffs per
+    /// kilobyte.
     pub(crate) fn asset_lock_transaction(
         &mut self,
         seed: Option<u64>,
         amount: u64,
+        fee_rate: u64,
     ) -> Result<(Transaction, PrivateKey), WalletError> {
         let mut rng = match seed {
             None => StdRng::from_entropy(),
             Some(seed_value) => StdRng::seed_from_u64(seed_value),
         };
-        let fee = 10_000;
         let random_private_key: [u8; 32] = rng.gen();
         let private_key = PrivateKey::from_slice(&random_private_key, Network::Testnet)
             .expect("expected a private key");
@@ -210,8 +506,11 @@ impl Wallet {
 
         let one_time_key_hash = asset_lock_public_key.pubkey_hash();
 
+        // Each additional input makes the transaction larger
         let (mut utxos, change) = self
-            .take_unspent_utxos_for(amount + fee)
+            .take_unspent_utxos_for(amount, |inputs| {
+                fee_for_size(fee_rate, asset_lock_transaction_size(inputs))
+            })
             .ok_or(WalletError::Balance)?;
 
         let change_address = self.change_address();
@@ -224,12 +523,8 @@ impl Wallet {
             value: amount,
             script_pubkey: ScriptBuf::new_op_return(&[]),
         };
-        if change < fee {
-            tracing::error!("change < fee");
-            return Err(WalletError::Balance);
-        }
         let change_output = TxOut {
-            value: change - fee,
+            value: change,
             script_pubkey: change_address.script_pubkey(),
         };
         let payload = AssetLockPayload {
@@ -324,6 +619,21 @@ impl Wallet {
         }
     }
 
+    pub(crate) fn private_key_wif(&self) -> String {
+        match self {
+            Wallet::SingleKeyWallet(wallet) => wallet.private_key.to_wif(),
+        }
+    }
+
+    /// Whether both wallets are made of the same keys.
+    pub(crate) fn same_key(&self, other: &Wallet) -> bool {
+        match (self, other) {
+            (Wallet::SingleKeyWallet(wallet), Wallet::SingleKeyWallet(other)) => {
+                wallet.private_key.inner == other.private_key.inner
+            }
+        }
+    }
+
     pub fn change_address(&self) -> Address {
         match self {
             Wallet::SingleKeyWallet(wallet) => wallet.change_address(),
@@ -358,9 +668,49 @@ impl Wallet {
     pub fn take_unspent_utxos_for(
         &mut self,
         amount: u64,
+        fee_for_inputs: impl Fn(usize) -> u64,
     ) -> Option<(BTreeMap<OutPoint, (TxOut, PublicKey, Address)>, u64)> {
         match self {
-            Wallet::SingleKeyWallet(wallet) => wallet.take_unspent_utxos_for(amount),
+            Wallet::SingleKeyWallet(wallet) => {
+                wallet.take_unspent_utxos_for(amount, fee_for_inputs)
+            }
+        }
+    }
+
+    /// UTXOs of the wallet, the most confirmed first.
+    pub(crate) fn utxos(&self) -> Vec<Utxo> {
+        match self {
+            Wallet::SingleKeyWallet(wallet) => {
+                let mut utxos: Vec<Utxo> = wallet
+                    .utxos
+                    .iter()
+                    .map(|(outpoint, tx_out)| Utxo {
+                        outpoint: *outpoint,
+                        amount: tx_out.value,
+                        confirmations: wallet.confirmations.get(outpoint).copied(),
+                        coin_control: wallet.coin_control.contains(outpoint),
+                    })
+                    .collect();
+                utxos.sort_by_key(|utxo| (std::cmp::Reverse(utxo.confirmations), utxo.outpoint));
+                utxos
+            }
+        }
+    }
+
+    /// Picks the UTXOs funding the next asset locks, all of them are spent
+    /// at once. An empty list restores the automatic selection.
+    pub(crate) fn set_coin_control(&mut self, outpoints: &[OutPoint]) -> Result<(), String> {
+        match self {
+            Wallet::SingleKeyWallet(wallet) => {
+                if let Some(outpoint) = outpoints
+                    .iter()
+                    .find(|outpoint| !wallet.utxos.contains_key(outpoint))
+                {
+                    return Err(format!("UTXO not in the wallet: {}", outpoint));
+                }
+                wallet.coin_control = outpoints.iter().copied().collect();
+                Ok(())
+            }
         }
     }
 
@@ -371,18 +721,67 @@ impl Wallet {
         match self {
             Wallet::SingleKeyWallet(wallet) => {
                 match insight
-                    .utxos_with_amount_for_addresses(&[&wallet.address])
+                    .utxos_with_confirmations_for_addresses(&[&wallet.address])
                     .await
                 {
                     Ok(utxos) => {
-                        wallet.utxos = utxos.clone();
-                        Ok(utxos)
+                        wallet.unconfirmed_utxos = utxos
+                            .iter()
+                            .filter(|(_, (_, confirmations))| *confirmations == 0)
+                            .map(|(outpoint, _)| *outpoint)
+                            .collect();
+                        wallet.confirmations = utxos
+                            .iter()
+                            .map(|(outpoint, (_, confirmations))| (*outpoint, *confirmations))
+                            .collect();
+                        wallet.utxos = utxos
+                            .into_iter()
+                            .map(|(outpoint, (tx_out, _))| (outpoint, tx_out))
+                            .collect();
+                        // Picked UTXOs spent elsewhere are no longer picked
+                        let utxos = &wallet.utxos;
+                        wallet
+                            .coin_control
+                            .retain(|outpoint| utxos.contains_key(outpoint));
+                        Ok(wallet.utxos.clone())
                     }
                     Err(err) => Err(err),
                 }
             }
         }
     }
+
+    /// Checks only transactions of unconfirmed UTXOs, which is cheaper than
+    /// reloading all UTXOs. UTXOs of transactions the network no longer knows
+    /// are dropped. Returns the number of UTXOs that got confirmed.
+    pub async fn refresh_unconfirmed(
+        &mut self,
+        insight: &InsightAPIClient,
+    ) -> Result<usize, InsightError> {
+        match self {
+            Wallet::SingleKeyWallet(wallet) => {
+                let mut confirmed = 0;
+                for outpoint in wallet.unconfirmed_utxos.clone() {
+                    match insight.transaction_confirmations(&outpoint.txid).await? {
+                        Some(0) => {}
+                        Some(confirmations) => {
+                            wallet.unconfirmed_utxos.remove(&outpoint);
+                            wallet.confirmations.insert(outpoint, confirmations);
+                            confirmed += 1;
+                        }
+                        None => {
+                            tracing::warn!("Transaction of UTXO {} was dropped", outpoint);
+                            wallet.unconfirmed_utxos.remove(&outpoint);
+                            wallet.confirmations.remove(&outpoint);
+                            wallet.coin_control.remove(&outpoint);
+                            wallet.utxos.remove(&outpoint);
+                        }
+                    }
+                }
+                Ok(confirmed)
+            }
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -391,6 +790,13 @@ pub struct SingleKeyWallet {
     pub public_key: PublicKey,
     pub address: Address,
     pub utxos: HashMap<OutPoint, TxOut>,
+    /// Outpoints of `utxos` not mined as of the last refresh, not persisted so
+    /// unknown until the wallet is refreshed
+    pub unconfirmed_utxos: BTreeSet<OutPoint>,
+    /// Confirmations of `utxos` as of the last refresh, not persisted either
+    pub confirmations: HashMap<OutPoint, u64>,
+    /// Outpoints of `utxos` picked to fund asset locks, not persisted
+    pub coin_control: BTreeSet<OutPoint>,
 }
 
 impl Clone for SingleKeyWallet {
@@ -400,6 +806,9 @@ impl Clone for SingleKeyWallet {
             public_key: self.public_key.clone(),
             address: self.address.clone(),
             utxos: self.utxos.clone(),
+            unconfirmed_utxos: self.unconfirmed_utxos.clone(),
+            confirmations: self.confirmations.clone(),
+            coin_control: self.coin_control.clone(),
         }
     }
 }
@@ -461,6 +870,9 @@ impl Decode for SingleKeyWallet {
             public_key,
             address,
             utxos,
+            unconfirmed_utxos: BTreeSet::new(),
+            confirmations: HashMap::new(),
+            coin_control: BTreeSet::new(),
         })
     }
 }
@@ -504,6 +916,9 @@ impl<'a> BorrowDecode<'a> for SingleKeyWallet {
             public_key,
             address,
             utxos,
+            unconfirmed_utxos: BTreeSet::new(),
+            confirmations: HashMap::new(),
+            coin_control: BTreeSet::new(),
         })
     }
 }
@@ -519,35 +934,60 @@ impl SingleKeyWallet {
         self.utxos.iter().map(|(_, out)| out.value).sum()
     }
 
+    /// Balance of UTXOs not mined yet as of the last refresh.
+    pub fn unconfirmed_balance(&self) -> u64 {
+        self.unconfirmed_utxos
+            .iter()
+            .filter_map(|outpoint| self.utxos.get(outpoint))
+            .map(|out| out.value)
+            .sum()
+    }
+
+    /// Takes UTXOs covering the amount and the fee of spending them, which
+    /// grows with the number of inputs, returning them with the change left.
+    /// If any UTXOs are picked with coin control, all of them and only them
+    /// are taken. Nothing is taken if they fall short.
     pub fn take_unspent_utxos_for(
         &mut self,
         amount: u64,
+        fee_for_inputs: impl Fn(usize) -> u64,
     ) -> Option<(BTreeMap<OutPoint, (TxOut, PublicKey, Address)>, u64)> {
-        let mut required: i64 = amount as i64;
+        let mut gathered: u64 = 0;
         let mut taken_utxos = BTreeMap::new();
+        let coin_control = !self.coin_control.is_empty();
 
         for (outpoint, utxo) in self.utxos.iter() {
-            if required <= 0 {
+            if coin_control {
+                if !self.coin_control.contains(outpoint) {
+                    continue;
+                }
+            } else if !taken_utxos.is_empty()
+                && gathered >= amount + fee_for_inputs(taken_utxos.len())
+            {
                 break;
             }
-            required -= utxo.value as i64;
+            gathered += utxo.value;
             taken_utxos.insert(
                 outpoint.clone(),
                 (utxo.clone(), self.public_key, self.address.clone()),
             );
         }
 
-        // If we didn't gather enough UTXOs to cover the required amount
-        if required > 0 {
+        // If we didn't gather enough UTXOs to cover the amount and the fee
+        let required = amount + fee_for_inputs(taken_utxos.len());
+        if taken_utxos.is_empty() || gathered < required {
             return None;
         }
 
         // Remove taken UTXOs from the original list
         for (outpoint, _) in &taken_utxos {
             self.utxos.remove(outpoint);
+            self.unconfirmed_utxos.remove(outpoint);
+            self.confirmations.remove(outpoint);
+            self.coin_control.remove(outpoint);
         }
 
-        Some((taken_utxos, required.abs() as u64))
+        Some((taken_utxos, gathered - required))
     }
 
     /// Takes a usize `desired_utxo_count` specifying the desired number of UTXOs one wants the wallet to have
@@ -710,6 +1150,7 @@ impl SingleKeyWallet {
             for outpoint in selected_utxos.iter() {
                 remaining_utxos_in_wallet.remove(outpoint);
                 self.utxos.remove(outpoint);
+                self.unconfirmed_utxos.remove(outpoint);
             }
             let txid = tx.txid();
             for (index, output) in tx.output.iter().enumerate() {
@@ -727,6 +1168,10 @@ impl SingleKeyWallet {
                     },
                     output.clone(),
                 );
+                self.unconfirmed_utxos.insert(OutPoint {
+                    txid,
+                    vout: index as u32,
+                });
             }
 
             num_utxos_remaining_to_create -= tx.output.len();
@@ -750,3 +1195,59 @@ impl SingleKeyWallet {
         &self.private_key
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use dpp::dashcore::{hashes::Hash, Network, OutPoint, TxOut, Txid};
+
+    use super::{wallet_from_private_key, Wallet, WalletError};
+
+    /// Duffs per kilobyte, the fee is 239 duffs with one input and 387 with
+    /// two.
+    const FEE_RATE: u64 = 1000;
+
+    fn wallet_with_utxos(values: &[u64]) -> Wallet {
+        let mut wallet =
+            wallet_from_private_key(&"01".repeat(32), Network::Testnet).expect("valid key");
+        let Wallet::SingleKeyWallet(single_key_wallet) = &mut wallet;
+        for (vout, value) in values.iter().enumerate() {
+            let script_pubkey = single_key_wallet.address.script_pubkey();
+            single_key_wallet.utxos.insert(
+                OutPoint {
+                    txid: Txid::all_zeros(),
+                    vout: vout as u32,
+                },
+                TxOut {
+                    value: *value,
+                    script_pubkey,
+                },
+            );
+        }
+        wallet
+    }
+
+    #[test]
+    fn asset_lock_keeps_utxos_if_change_is_less_than_fee() {
+        // Both UTXOs cover the amount with the fee of a single input, but not
+        // with the fee of the two of them
+        let mut wallet = wallet_with_utxos(&[1000, 1000]);
+
+        let result = wallet.asset_lock_transaction(Some(0), 1700, FEE_RATE);
+
+        assert!(matches!(result, Err(WalletError::Balance)));
+        assert_eq!(wallet.balance(), 2000);
+    }
+
+    #[test]
+    fn asset_lock_change_pays_fee_of_every_input() {
+        let mut wallet = wallet_with_utxos(&[1000, 1000]);
+
+        let (transaction, _) = wallet
+            .asset_lock_transaction(Some(0), 1500, FEE_RATE)
+            .expect("enough balance");
+
+        assert_eq!(transaction.input.len(), 2);
+        assert_eq!(transaction.output[1].value, 2000 - 1500 - 387);
+        assert_eq!(wallet.balance(), 0);
+    }
+}
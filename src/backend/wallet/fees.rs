@@ -0,0 +1,112 @@
+//! Wallet transactions fee rate selection.
+//! Rates are expressed in duffs per kilobyte the way Core and Insight do.
+//! Network estimates are fetched for a few confirmation targets and the user
+//! picks one of them or sets a custom rate.
+
+use std::fmt::{self, Display};
+
+/// Core's minimum relay fee rate, used for all levels until estimates are
+/// fetched or if the node has no estimate for a target.
+pub(crate) const MIN_RELAY_FEE_RATE: u64 = 1_000;
+
+/// Confirmation targets in blocks of the estimated fee levels.
+pub(crate) const ECONOMY_TARGET_BLOCKS: u32 = 12;
+pub(crate) const NORMAL_TARGET_BLOCKS: u32 = 6;
+pub(crate) const PRIORITY_TARGET_BLOCKS: u32 = 2;
+
+/// Fee rate picked by user for wallet transactions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FeeLevel {
+    Economy,
+    #[default]
+    Normal,
+    Priority,
+    /// Custom rate in duffs per kilobyte
+    Custom(u64),
+}
+
+impl Display for FeeLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FeeLevel::Economy => write!(f, "economy ({} blocks)", ECONOMY_TARGET_BLOCKS),
+            FeeLevel::Normal => write!(f, "normal ({} blocks)", NORMAL_TARGET_BLOCKS),
+            FeeLevel::Priority => write!(f, "priority ({} blocks)", PRIORITY_TARGET_BLOCKS),
+            FeeLevel::Custom(_) => write!(f, "custom"),
+        }
+    }
+}
+
+/// Network fee rate estimates in duffs per kilobyte.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct FeeEstimates {
+    pub economy: u64,
+    pub normal: u64,
+    pub priority: u64,
+}
+
+#[derive(Debug, Clone, Default)]
+pub(crate) struct FeeSettings {
+    pub level: FeeLevel,
+    /// Latest network estimates, not fetched yet if `None`
+    pub estimates: Option<FeeEstimates>,
+}
+
+impl FeeSettings {
+    /// Fee rate in duffs per kilobyte of the selected level.
+    pub(crate) fn rate(&self) -> u64 {
+        self.rate_for(self.level)
+    }
+
+    /// Fee rate in duffs per kilobyte of any level according to current
+    /// estimates.
+    pub(crate) fn rate_for(&self, level: FeeLevel) -> u64 {
+        match (level, self.estimates) {
+            (FeeLevel::Custom(rate), _) => rate,
+            (_, None) => MIN_RELAY_FEE_RATE,
+            (FeeLevel::Economy, Some(estimates)) => estimates.economy,
+            (FeeLevel::Normal, Some(estimates)) => estimates.normal,
+            (FeeLevel::Priority, Some(estimates)) => estimates.priority,
+        }
+    }
+
+    /// Fee in duffs of a single input asset lock transaction at the level's
+    /// rate.
+    pub(crate) fn asset_lock_fee_for(&self, level: FeeLevel) -> u64 {
+        fee_for_size(self.rate_for(level), asset_lock_transaction_size(1))
+    }
+
+    pub(crate) fn display(&self) -> String {
+        format!(
+            "Fee rate: {}, {} duffs/kB\nAsset lock fee: {} duffs{}",
+            self.level,
+            self.rate(),
+            self.asset_lock_fee_for(self.level),
+            if self.estimates.is_none() {
+                " (network estimates not fetched)"
+            } else {
+                ""
+            }
+        )
+    }
+}
+
+/// Fee in duffs for a transaction of `size` bytes.
+pub(crate) fn fee_for_size(rate: u64, size: usize) -> u64 {
+    (rate * size as u64).div_ceil(1000)
+}
+
+/// Size in bytes of a signed asset lock transaction spending `inputs` P2PKH
+/// outputs, with the burn output, change output and a single credit output in
+/// the payload.
+pub(crate) fn asset_lock_transaction_size(inputs: usize) -> usize {
+    // version, type, input and output counts and lock time
+    const HEADER_SIZE: usize = 10;
+    // signed P2PKH input
+    const INPUT_SIZE: usize = 148;
+    // OP_RETURN burn output and P2PKH change output
+    const OUTPUTS_SIZE: usize = 10 + 34;
+    // payload size, version, credit outputs count and P2PKH credit output
+    const PAYLOAD_SIZE: usize = 3 + 34;
+
+    HEADER_SIZE + inputs * INPUT_SIZE + OUTPUTS_SIZE + PAYLOAD_SIZE
+}
@@ -0,0 +1,99 @@
+//! Named wallets.
+//! Wallets are registered with a label and kept in a JSON file of the profile,
+//! so the loaded wallet, which funds registrations, top ups and strategies,
+//! can be switched without restarting.
+//!
+//! Private keys are encrypted with a passphrase given when the wallet is
+//! registered and asked again to switch to it, only the address is kept in
+//! clear to recognize the wallet. Registered wallets are left out of the
+//! state file, they're switched to again after a restart.
+
+use std::collections::BTreeMap;
+
+use dpp::dashcore::Network;
+use serde::{Deserialize, Serialize};
+
+use super::{wallet_from_private_key, Wallet};
+use crate::backend::{encryption, json_store::JsonStore};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct StoredKey {
+    address: String,
+    /// Hex encoded private key as entered, hex or WIF, encrypted with the
+    /// wallet passphrase
+    encrypted_key: String,
+}
+
+impl StoredKey {
+    fn encrypt(wallet: &Wallet, private_key: &str, passphrase: &str) -> Result<Self, String> {
+        let encrypted = encryption::encrypt(passphrase, private_key.as_bytes())?;
+        Ok(StoredKey {
+            address: wallet.receive_address().to_string(),
+            encrypted_key: hex::encode(encrypted),
+        })
+    }
+
+    fn private_key(&self, passphrase: &str) -> Result<String, String> {
+        let encrypted = hex::decode(&self.encrypted_key)
+            .map_err(|e| format!("Invalid encrypted wallet key: {}", e))?;
+        let private_key = encryption::decrypt(passphrase, &encrypted)?;
+        String::from_utf8(private_key).map_err(|e| format!("Invalid encrypted wallet key: {}", e))
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub(crate) struct WalletProfiles {
+    /// Private keys by wallet label
+    wallets: BTreeMap<String, StoredKey>,
+}
+
+impl WalletProfiles {
+    pub(crate) fn labels(&self) -> Vec<String> {
+        self.wallets.keys().cloned().collect()
+    }
+
+    pub(crate) fn wallet(
+        &self,
+        label: &str,
+        passphrase: &str,
+        network: Network,
+    ) -> Result<Wallet, String> {
+        let private_key = self
+            .wallets
+            .get(label)
+            .ok_or_else(|| format!("No wallet labeled {}", label))?
+            .private_key(passphrase)?;
+        wallet_from_private_key(&private_key, network)
+    }
+
+    /// Label the wallet is registered with, if any.
+    pub(crate) fn label_of(&self, wallet: &Wallet) -> Option<String> {
+        let address = wallet.receive_address().to_string();
+        self.wallets
+            .iter()
+            .find(|(_, stored_key)| stored_key.address == address)
+            .map(|(label, _)| label.clone())
+    }
+}
+
+impl JsonStore<WalletProfiles> {
+    /// Registers a wallet, the key is expected to be validated already.
+    pub(crate) fn add(
+        &mut self,
+        label: &str,
+        wallet: &Wallet,
+        private_key: &str,
+        passphrase: &str,
+    ) -> Result<(), String> {
+        let label = label.trim();
+        if label.is_empty() {
+            return Err("Wallet label can't be empty".to_owned());
+        }
+        if self.wallets.contains_key(label) {
+            return Err(format!("Wallet {} is already registered", label));
+        }
+        let stored_key = StoredKey::encrypt(wallet, private_key.trim(), passphrase)?;
+        self.wallets.insert(label.to_owned(), stored_key);
+        self.save()
+    }
+}
@@ -3,7 +3,7 @@ use std::thread::sleep;
 use std::{
     collections::HashSet,
     num::NonZeroU32,
-    panic,
+    panic, process,
     sync::{
         atomic::{AtomicU64, AtomicUsize, Ordering},
         Arc,
@@ -144,7 +144,10 @@ async fn main() {
     }));
 
     // Load configuration
-    let config = Config::load();
+    let config = Config::load().unwrap_or_else(|e| {
+        eprintln!("{}", e);
+        process::exit(1)
+    });
 
     // Setup Platform SDK
     let address_list = config.dapi_address_list();
@@ -3,7 +3,7 @@ use std::str::FromStr;
 use std::{
     fmt,
     num::NonZeroU32,
-    panic,
+    panic, process,
     sync::{
         atomic::{AtomicU64, Ordering},
         Arc,
@@ -113,7 +113,10 @@ async fn main() {
         default_panic_hook(panic_info);
     }));
 
-    let config = Config::load();
+    let config = Config::load().unwrap_or_else(|e| {
+        eprintln!("{}", e);
+        process::exit(1)
+    });
 
     let rate = Rate::new(args.rate, args.rate_unit);
 
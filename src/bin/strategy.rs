@@ -1,8 +1,14 @@
-use std::{fs::File, panic, time::Duration};
+use std::{
+    fs::File,
+    panic::{self, AssertUnwindSafe},
+    process::ExitCode,
+    time::Duration,
+};
 
 use clap::{ArgAction, Parser};
 use dash_sdk::{RequestSettings, SdkBuilder};
 use dpp::{identity::accessors::IdentityGettersV0, version::PlatformVersion};
+use futures::FutureExt;
 use rs_platform_explorer::{
     backend::{
         self,
@@ -12,6 +18,7 @@ use rs_platform_explorer::{
         Backend, Task,
     },
     config::Config,
+    headless::{task_failure, Failure, FailureCategory, OutputFormat, RunSummary},
 };
 
 #[derive(Parser, Debug)]
@@ -23,6 +30,9 @@ struct Args {
     #[arg(short, long, action = ArgAction::SetTrue, help = "Enables state transition proof verification.")]
     prove: bool,
 
+    #[arg(short, long, action = ArgAction::SetTrue, help = "Resumes the halted run of the stress test, in its mode and for its remaining blocks or seconds.")]
+    resume: bool,
+
     #[arg(short, long, action = ArgAction::SetTrue, help = "Enables per-second execution of state transitions rather than per-block.")]
     time_mode: bool,
 
@@ -34,21 +44,46 @@ struct Args {
     )]
     blocks: u64,
 
+    #[arg(
+        long,
+        help = "Paces block mode by observed blocks, submitting this many transitions per block."
+    )]
+    transitions_per_block: Option<u16>,
+
     #[arg(
         short,
         long,
         help = "Specifies the minimum amount of Dash the loaded identity should have."
     )]
     dash: Option<u64>,
+
+    #[arg(
+        short,
+        long,
+        value_enum,
+        default_value_t = OutputFormat::Text,
+        help = "Prints a JSON summary of the run to stdout with `json`."
+    )]
+    output: OutputFormat,
 }
 
 #[tokio::main]
-async fn main() {
+async fn main() -> ExitCode {
     let args = Args::parse();
 
     // Initialize logger
     let cli_action_taken = args.test.is_some();
-    if cli_action_taken {
+    if cli_action_taken && args.output == OutputFormat::Json {
+        // Stdout is kept for the JSON summary
+        let subscriber = tracing_subscriber::fmt()
+            .with_env_filter("info")
+            .with_writer(std::io::stderr)
+            .with_ansi(false)
+            .finish();
+
+        tracing::subscriber::set_global_default(subscriber)
+            .expect("Setting default subscriber failed");
+    } else if cli_action_taken {
         let subscriber = tracing_subscriber::fmt()
             .with_env_filter("info")
             .with_writer(std::io::stdout)
@@ -77,10 +112,12 @@ async fn main() {
     let default_panic_hook = panic::take_hook();
 
     panic::set_hook(Box::new(move |panic_info| {
-        let message = panic_info
-            .payload()
+        let payload = panic_info.payload();
+        let message = payload
             .downcast_ref::<&str>()
-            .unwrap_or(&"unknown");
+            .copied()
+            .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+            .unwrap_or("unknown");
 
         let location = panic_info
             .location()
@@ -93,10 +130,30 @@ async fn main() {
         );
 
         default_panic_hook(panic_info);
+
+        Failure::new(
+            FailureCategory::Internal,
+            "panic",
+            format!("{} at {}", message, location),
+        )
+        .report();
     }));
 
+    // A panic unwinds out of the run, dropping the backend which saves the state
+    AssertUnwindSafe(run(args))
+        .catch_unwind()
+        .await
+        .unwrap_or_else(|_| FailureCategory::Internal.exit_code())
+}
+
+/// Runs the identity tasks and the strategy, failures are returned rather than
+/// exiting so the backend is dropped and the state saved either way.
+async fn run(args: Args) -> ExitCode {
     // Load configuration
-    let config = Config::load();
+    let config = match Config::load() {
+        Ok(config) => config,
+        Err(e) => return Failure::new(FailureCategory::Config, "invalid_config", e).report(),
+    };
 
     // Setup Platform SDK
     let address_list = config.dapi_address_list();
@@ -122,27 +179,55 @@ async fn main() {
 
     let backend = Backend::new(&sdk, insight.clone(), config.clone()).await;
 
+    let mut summary = RunSummary::default();
+
     // Create wallet if not initialized
     if backend.state().loaded_wallet.lock().await.is_none() {
         let Some(private_key) = config.wallet_private_key else {
-            panic!("Wallet not initialized and no private key provided");
+            return summary.fail(
+                args.output,
+                Failure::new(
+                    FailureCategory::Config,
+                    "wallet_not_configured",
+                    "Wallet not initialized and no private key provided",
+                ),
+            );
         };
 
         tracing::info!("Wallet not initialized, creating new wallet with configured private key");
 
-        backend
-            .run_task(Task::Wallet(WalletTask::AddByPrivateKey(
-                private_key.clone(),
-            )))
-            .await;
+        if let Some(failure) = task_failure(
+            &backend
+                .run_task(Task::Wallet(WalletTask::AddByPrivateKey(
+                    private_key.clone(),
+                )))
+                .await,
+            FailureCategory::Config,
+            "invalid_wallet_private_key",
+        ) {
+            return summary.fail(args.output, failure);
+        }
     }
 
     // Refresh wallet core balance
-    backend.run_task(Task::Wallet(WalletTask::Refresh)).await;
+    if let Some(failure) = task_failure(
+        &backend.run_task(Task::Wallet(WalletTask::Refresh)).await,
+        FailureCategory::Network,
+        "wallet_refresh_failed",
+    ) {
+        return summary.fail(args.output, failure);
+    }
 
     // Return if there's no loaded identity and the --dash flag is not set
     if backend.state().loaded_identity.lock().await.is_none() && args.dash.is_none() {
-        panic!("There's no loaded identity and the --dash argument is not passed");
+        return summary.fail(
+            args.output,
+            Failure::new(
+                FailureCategory::Config,
+                "no_identity",
+                "There's no loaded identity and the --dash argument is not passed",
+            ),
+        );
     }
 
     // Add loaded identity to known identities if it's not already there
@@ -173,15 +258,29 @@ async fn main() {
                 start_dash
             );
 
-            backend
+            let event = backend
                 .run_task(Task::Identity(IdentityTask::RegisterIdentity(amount)))
                 .await;
+            summary.record_task("register_identity", &event);
+            if let Some(failure) = task_failure(
+                &event,
+                FailureCategory::Funding,
+                "identity_registration_failed",
+            ) {
+                return summary.fail(args.output, failure);
+            }
         // Else, if there is a loaded identity, if the balance is less than start_dash, top it up
         } else {
             backend.run_task(Task::Wallet(WalletTask::Refresh)).await;
-            backend
+            let event = backend
                 .run_task(Task::Identity(IdentityTask::Refresh))
                 .await;
+            summary.record_task("refresh_identity", &event);
+            if let Some(failure) =
+                task_failure(&event, FailureCategory::Network, "identity_refresh_failed")
+            {
+                return summary.fail(args.output, failure);
+            }
 
             let balance = backend
                 .state()
@@ -203,25 +302,46 @@ async fn main() {
                     (start_dash as f64 * 100000000000.0 - balance as f64) / 100000000000.0
                 );
                 let amount = (start_dash * 100000000000 - balance) / 1000; // duffs to go into asset lock transaction
-                backend
+                let event = backend
                     .run_task(Task::Identity(IdentityTask::TopUpIdentity(amount)))
                     .await;
+                summary.record_task("top_up_identity", &event);
+                if let Some(failure) =
+                    task_failure(&event, FailureCategory::Funding, "identity_top_up_failed")
+                {
+                    return summary.fail(args.output, failure);
+                }
             }
         }
     }
     if let Some(test_name) = args.test {
         let block_mode = if args.time_mode { false } else { true };
-        backend::strategies::run_strategy_task(
-            &sdk,
-            &backend.state(),
+        let task = if args.resume {
+            backend::strategies::StrategyTask::ResumeStrategy(
+                test_name.to_string(),
+                args.prove,
+                args.transitions_per_block,
+            )
+        } else {
             backend::strategies::StrategyTask::RunStrategy(
                 test_name.to_string(),
                 args.blocks,
                 args.prove,
                 block_mode,
-            ),
-            &insight,
-        )
-        .await;
+                args.transitions_per_block,
+            )
+        };
+        let event =
+            backend::strategies::run_strategy_task(&sdk, &backend.state(), task, &insight).await;
+        summary.record_strategy(&event);
+        if let Some(failure) = task_failure(&event, FailureCategory::Strategy, "strategy_failed") {
+            return summary.fail(args.output, failure);
+        }
     }
+
+    if args.output == OutputFormat::Json {
+        summary.print();
+    }
+
+    ExitCode::SUCCESS
 }
@@ -0,0 +1,124 @@
+//! Replays the read queries of a DAPI session trace against the configured
+//! network and reports those whose results drifted since they were recorded.
+//! The trace must be recorded with `EXPLORER_DAPI_TRACE_PAYLOADS=true`.
+
+use std::{
+    panic::{self, AssertUnwindSafe},
+    path::PathBuf,
+    process::ExitCode,
+    time::Duration,
+};
+
+use clap::Parser;
+use dash_sdk::{RequestSettings, SdkBuilder};
+use dpp::version::PlatformVersion;
+use futures::FutureExt;
+use rs_platform_explorer::{
+    backend::trace_replay::replay_trace,
+    config::Config,
+    headless::{Failure, FailureCategory, OutputFormat},
+};
+
+#[derive(Parser, Debug)]
+#[clap(about, long_about = None)]
+struct Args {
+    #[arg(help = "DAPI trace file recorded with payloads.")]
+    trace: PathBuf,
+
+    #[arg(
+        short,
+        long,
+        value_enum,
+        default_value_t = OutputFormat::Text,
+        help = "Prints the replay report as JSON with `json`."
+    )]
+    output: OutputFormat,
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let args = Args::parse();
+
+    // Stdout is kept for the report
+    let subscriber = tracing_subscriber::fmt()
+        .with_env_filter("info")
+        .with_writer(std::io::stderr)
+        .with_ansi(false)
+        .finish();
+
+    tracing::subscriber::set_global_default(subscriber).expect("Setting default subscriber failed");
+
+    // Log panics
+    let default_panic_hook = panic::take_hook();
+
+    panic::set_hook(Box::new(move |panic_info| {
+        let payload = panic_info.payload();
+        let message = payload
+            .downcast_ref::<&str>()
+            .copied()
+            .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+            .unwrap_or("unknown");
+
+        default_panic_hook(panic_info);
+
+        Failure::new(FailureCategory::Internal, "panic", message).report();
+    }));
+
+    AssertUnwindSafe(run(args))
+        .catch_unwind()
+        .await
+        .unwrap_or_else(|_| FailureCategory::Internal.exit_code())
+}
+
+async fn run(args: Args) -> ExitCode {
+    let config = match Config::load() {
+        Ok(config) => config,
+        Err(e) => return Failure::new(FailureCategory::Config, "invalid_config", e).report(),
+    };
+
+    let request_settings = RequestSettings {
+        connect_timeout: Some(Duration::from_secs(10)),
+        timeout: Some(Duration::from_secs(10)),
+        retries: None,
+        ban_failed_address: Some(false),
+    };
+    let sdk = SdkBuilder::new(config.dapi_address_list())
+        .with_version(PlatformVersion::get(1).unwrap())
+        .with_core(
+            &config.core_host,
+            config.core_rpc_port,
+            &config.core_rpc_user,
+            &config.core_rpc_password,
+        )
+        .with_settings(request_settings)
+        .build()
+        .expect("expected to build sdk");
+
+    let report = match replay_trace(&sdk, &args.trace).await {
+        Ok(report) => report,
+        Err(e) => return Failure::new(FailureCategory::Config, "invalid_trace", e).report(),
+    };
+
+    match args.output {
+        OutputFormat::Text => println!("{}", report),
+        OutputFormat::Json => match serde_json::to_string(&report) {
+            Ok(json) => println!("{}", json),
+            Err(e) => tracing::error!("Unable to serialize replay report: {}", e),
+        },
+    }
+
+    if report.drifted() > 0 {
+        return Failure::new(
+            FailureCategory::Drift,
+            "state_drift",
+            format!(
+                "{} of {} replayed queries drifted",
+                report.drifted(),
+                report.queries.len()
+            ),
+        )
+        .report();
+    }
+
+    ExitCode::SUCCESS
+}
@@ -1,19 +1,37 @@
-use std::{path::PathBuf, str::FromStr};
+mod dashmate;
+mod env_file;
+mod option_names;
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    env, fs,
+    path::{Path, PathBuf},
+    str::FromStr,
+    time::Duration,
+};
 
 use dash_sdk::sdk::Uri;
 use dpp::dashcore::Network;
 use rs_dapi_client::AddressList;
 use serde::Deserialize;
 
+/// Local Drive database of versions without network profiles.
+const LEGACY_DRIVE_PATH: &str = "explorer.drive";
+
 #[derive(Debug, Deserialize, Clone)]
 /// Configuration for platform explorer.
 ///
 /// Content of this configuration is loaded from environment variables or `.env`
 /// file when the [Config::load()] is called.
 /// Variable names in the enviroment and `.env` file must be prefixed with
-/// either [LOCAL_EXPLORER_](Config::CONFIG_PREFIX) or
-/// [TESTNET_EXPLORER_](Config::CONFIG_PREFIX) and written as
-/// SCREAMING_SNAKE_CASE (e.g. `EXPLORER_DAPI_ADDRESSES`).
+/// [EXPLORER_](Config::CONFIG_PREFIX) and written as SCREAMING_SNAKE_CASE
+/// (e.g. `EXPLORER_DAPI_ADDRESSES`).
+///
+/// Options of a network profile are additionally prefixed with the profile
+/// name (e.g. `TESTNET_EXPLORER_DAPI_ADDRESSES` for the `testnet` profile) and
+/// override the unprefixed ones once the profile is selected. In the `.env`
+/// file they can also be written without the profile prefix following a
+/// `[profile.testnet]` line.
 pub struct Config {
     /// Hostname of the Dash Platform node to connect to
     pub dapi_addresses: String,
@@ -29,8 +47,192 @@ pub struct Config {
     pub insight_api_url: String,
     /// Network name
     pub network: String,
-    /// Optional wallet private key to instantiate the wallet
+    /// Network profile the options are taken from, the unprefixed options
+    /// only are used if unset
+    pub profile: Option<String>,
+    /// Optional wallet private key to instantiate the wallet, profiles only
+    /// use their own prefixed one
     pub wallet_private_key: Option<String>,
+    /// ProTxHash of the evonode whose rewards are reported
+    pub evonode_pro_tx_hash: Option<String>,
+    /// URL of the Tenderdash RPC of a Platform node, blocks proposed by
+    /// evonodes aren't counted if unset
+    pub tenderdash_rpc_url: Option<String>,
+    /// How asset locks funding identities are proven to platform
+    #[serde(default)]
+    pub asset_lock_proof: AssetLockProofKind,
+    /// Notify when a task that took at least this many seconds completes,
+    /// notifications are disabled if unset
+    pub notify_after_seconds: Option<u64>,
+    /// How to notify about long task completion
+    #[serde(default)]
+    pub notify_method: NotifyMethod,
+    /// How often the keep-funded mode checks balances of watched identities,
+    /// 60 seconds if unset
+    pub keep_funded_check_seconds: Option<u64>,
+    /// How often wallet transactions are checked for core chain reorgs, 60
+    /// seconds if unset
+    pub reorg_check_seconds: Option<u64>,
+    /// How often idle DAPI connections are kept alive with a request, 30
+    /// seconds if unset, 0 disables keepalive
+    pub dapi_keepalive_seconds: Option<u64>,
+    /// How often Core and Platform chain heights shown in the status bar are
+    /// polled, 30 seconds if unset, 0 disables polling
+    pub chain_status_seconds: Option<u64>,
+    /// Seconds of UI inactivity after which data the user is likely to need
+    /// next is prefetched, 3 seconds if unset, 0 disables prefetching
+    pub prefetch_idle_seconds: Option<u64>,
+    /// Format of the log file
+    #[serde(default)]
+    pub log_format: LogFormat,
+    /// File to record DAPI requests and responses trace into, tracing is
+    /// disabled if unset
+    pub dapi_trace_file: Option<String>,
+    /// Record request and response payloads into the DAPI trace, not only
+    /// their sizes
+    #[serde(default)]
+    pub dapi_trace_payloads: bool,
+    /// Show requests and bytes exchanged with DAPI and Insight over the last
+    /// minute in the status bar
+    #[serde(default)]
+    pub traffic_meter: bool,
+    /// Identical tasks sending transactions submitted within this many seconds
+    /// are considered duplicates, the check is disabled if unset
+    pub duplicate_task_window_seconds: Option<u64>,
+    /// What to do with a duplicate task
+    #[serde(default)]
+    pub duplicate_task_action: DuplicateTaskAction,
+    /// URL of the remote signer service, transitions are signed with local
+    /// private keys if unset
+    pub remote_signer_url: Option<String>,
+    /// Bearer token sent to the remote signer
+    pub remote_signer_token: Option<String>,
+    /// Seconds to wait for the remote signer to sign, 30 if unset
+    pub remote_signer_timeout_seconds: Option<u64>,
+    /// Seconds to wait for a connection to the remote signer, 5 if unset
+    pub remote_signer_connect_timeout_seconds: Option<u64>,
+    /// Halt strategy runs when more than this percentage of broadcasts fail
+    /// between safety checks
+    pub strategy_halt_error_percent: Option<u8>,
+    /// Halt strategy runs when the loaded identity balance drops faster than
+    /// this many credits per minute
+    pub strategy_halt_balance_drop_per_minute: Option<u64>,
+    /// Halt strategy runs after this many consecutive failed DAPI health
+    /// checks
+    pub strategy_halt_dapi_failures: Option<u32>,
+    /// Language of the UI
+    #[serde(default)]
+    pub language: Language,
+    /// Spacing of lists and tables
+    #[serde(default)]
+    pub display_density: Density,
+    /// Screen the TUI opens on
+    #[serde(default)]
+    pub startup_screen: StartupScreen,
+    /// Task executed once the TUI is started
+    pub startup_task: Option<StartupTask>,
+    /// Snapshot of known contracts and identities served by an in-memory
+    /// platform simulator instead of the network, the network is used if
+    /// unset
+    pub simulated_platform_fixtures: Option<String>,
+}
+
+/// Notification kinds fired on long task completion.
+#[derive(Debug, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum NotifyMethod {
+    /// Terminal bell character
+    #[default]
+    Bell,
+    /// OSC 9 escape sequence, shown as a desktop notification by supporting
+    /// terminals
+    Desktop,
+    /// Both bell and desktop notification
+    Both,
+}
+
+/// Proofs of asset locks funding identity registrations and top ups.
+#[derive(Debug, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum AssetLockProofKind {
+    /// Instant send lock, falling back to the chain lock if none arrives in a
+    /// minute
+    #[default]
+    Auto,
+    /// Instant send lock only
+    Instant,
+    /// Chain lock of the block the transaction is mined in, for networks
+    /// where instant send locks are unreliable
+    Chain,
+}
+
+/// Log file formats.
+#[derive(Debug, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum LogFormat {
+    /// Human readable lines
+    #[default]
+    Text,
+    /// JSON lines with span fields like task correlation IDs, for log
+    /// processing tools
+    Json,
+}
+
+/// Handling of a task identical to one submitted shortly before.
+#[derive(Debug, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum DuplicateTaskAction {
+    /// Ask whether to run the task again
+    #[default]
+    Confirm,
+    /// Drop the duplicate
+    Coalesce,
+}
+
+/// Languages the UI is translated to.
+#[derive(Debug, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Language {
+    /// English
+    #[default]
+    En,
+    /// Spanish
+    Es,
+}
+
+/// Display densities of lists and tables.
+#[derive(Debug, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Density {
+    /// Bordered widgets
+    #[default]
+    Comfortable,
+    /// Borders trimmed to fit more rows
+    Compact,
+}
+
+/// Screens the TUI can open on, the main menu stays below the others.
+#[derive(Debug, Deserialize, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum StartupScreen {
+    #[default]
+    Main,
+    Contracts,
+    Identities,
+    Wallet,
+    Strategies,
+}
+
+/// Tasks executed on startup.
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum StartupTask {
+    /// Fetch the loaded identity again
+    RefreshIdentity,
+    /// Reload UTXOs and balance of the loaded wallet
+    RefreshWallet,
+    /// Fetch all known contracts again
+    RefreshContracts,
 }
 
 impl Config {
@@ -45,21 +247,157 @@ impl Config {
     /// `.env` file. Variable names in the
     /// environment and `.env` file must be converted to SCREAMING_SNAKE_CASE
     /// and prefixed with [LOCAL_EXPLORER_](Config::CONFIG_PREFIX).
-    pub fn load() -> Self {
-        // load config from .env file
-        if let Err(err) = dotenvy::from_path(".env") {
-            tracing::warn!(?err, "failed to load config file");
+    pub fn load() -> Result<Self, String> {
+        Self::from_options(Self::read_options())
+    }
+
+    /// Loads the configuration like [Config::load()] with options of the
+    /// network profile, whatever profile is selected in the environment.
+    /// Options saved from the TUI since the start take precedence over the
+    /// environment.
+    pub fn load_profile(
+        profile: &str,
+        saved_options: &BTreeMap<String, String>,
+    ) -> Result<Self, String> {
+        let mut options = Self::read_options();
+        options.extend(saved_options.clone());
+        options.insert(
+            format!("{}PROFILE", Self::CONFIG_PREFIX),
+            profile.to_owned(),
+        );
+        Self::from_options(options)
+    }
+
+    /// Loads the configuration like [Config::load()], with connection
+    /// settings of a local network read from the dashmate configuration
+    /// instead.
+    ///
+    /// DAPI addresses, Core RPC settings, Insight API URL and network name
+    /// found in the dashmate configuration take precedence over environment
+    /// variables and `.env` file. The dashmate configuration in the home
+    /// directory is used if no path is given.
+    pub fn load_with_dashmate(path: Option<PathBuf>) -> Result<Self, String> {
+        let mut options = Self::read_options();
+
+        let path = path
+            .or_else(dashmate::default_path)
+            .ok_or("dashmate configuration path is unknown")?;
+        for (name, value) in dashmate::read_options(&path)? {
+            options.insert(format!("{}{}", Self::CONFIG_PREFIX, name), value);
+        }
+
+        Self::from_options(options)
+    }
+
+    /// Options of the `.env` file and the environment, the environment taking
+    /// precedence.
+    fn read_options() -> BTreeMap<String, String> {
+        let mut options = BTreeMap::new();
+        match env_file::read_options(Path::new(".env")) {
+            Ok(file_options) => options.extend(file_options),
+            Err(err) => tracing::warn!(?err, "failed to load config file"),
+        }
+        options.extend(env::vars());
+        options
+    }
+
+    fn from_options(mut options: BTreeMap<String, String>) -> Result<Self, String> {
+        if let Some(profile) = options
+            .get(&format!("{}PROFILE", Self::CONFIG_PREFIX))
+            .cloned()
+        {
+            if profile.is_empty()
+                || !profile
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+            {
+                return Err(format!(
+                    "Invalid profile name {:?}, only letters, digits, _ and - are allowed",
+                    profile
+                ));
+            }
+            let profile_prefix = format!("{}_{}", profile.to_uppercase(), Self::CONFIG_PREFIX);
+            let profile_options: Vec<(String, String)> = options
+                .iter()
+                .filter_map(|(name, value)| {
+                    let option = name.strip_prefix(&profile_prefix)?;
+                    Some((format!("{}{}", Self::CONFIG_PREFIX, option), value.clone()))
+                })
+                .collect();
+            if profile_options.is_empty() {
+                let profiles = Self::profiles_of(&options);
+                return Err(if profiles.is_empty() {
+                    format!("Unknown profile {}, no profiles are configured", profile)
+                } else {
+                    format!(
+                        "Unknown profile {}, available profiles: {}",
+                        profile,
+                        profiles.join(", ")
+                    )
+                });
+            }
+            // Wallet keys are never shared between networks, a profile only gets
+            // its own
+            options.remove(&format!("{}WALLET_PRIVATE_KEY", Self::CONFIG_PREFIX));
+            options.extend(profile_options);
         }
 
         let config: Self = envy::prefixed(Self::CONFIG_PREFIX)
-            .from_env()
-            .expect("configuration error");
+            .from_iter(options)
+            .map_err(|e| format!("Configuration error: {}", e))?;
 
         if !config.is_valid() {
-            panic!("invalid configuration: {:?}", config);
+            return Err(format!("Invalid configuration: {:?}", config));
         }
 
-        config
+        Ok(config)
+    }
+
+    /// Saves options given by names without prefix into the `.env` file, as
+    /// options of the selected network profile if any. Returns the saved
+    /// options by full variable names, to pass to the next configuration
+    /// load so they're in effect over the environment as well.
+    pub(crate) fn save_options(
+        &self,
+        options: &[(&str, String)],
+    ) -> Result<Vec<(String, String)>, String> {
+        let options: Vec<(String, String)> = options
+            .iter()
+            .map(|(name, value)| (format!("{}{}", Self::CONFIG_PREFIX, name), value.clone()))
+            .collect();
+        env_file::save_options(Path::new(".env"), self.profile.as_deref(), &options)?;
+
+        let prefix = match &self.profile {
+            Some(profile) => format!("{}_", profile.to_uppercase()),
+            None => String::new(),
+        };
+        Ok(options
+            .into_iter()
+            .map(|(name, value)| (format!("{}{}", prefix, name), value))
+            .collect())
+    }
+
+    /// Names of the network profiles having options in the environment and
+    /// `.env` file.
+    pub fn available_profiles() -> Vec<String> {
+        Self::profiles_of(&Self::read_options())
+    }
+
+    /// Names of the network profiles having options among the variables, only
+    /// variables named after a configuration option count.
+    fn profiles_of(options: &BTreeMap<String, String>) -> Vec<String> {
+        let option_names = option_names::option_names();
+        let profile_suffix = format!("_{}", Self::CONFIG_PREFIX);
+        options
+            .keys()
+            .filter_map(|name| {
+                let (profile, option) = name.split_once(&profile_suffix)?;
+                (!profile.is_empty() && option_names.contains(&option.to_lowercase().as_str()))
+                    .then(|| profile.to_lowercase())
+            })
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect()
     }
 
     /// Check if configuration is set
@@ -81,14 +419,160 @@ impl Config {
         AddressList::from(self.dapi_addresses.as_str())
     }
 
+    /// Number of configured DAPI addresses
+    pub fn dapi_addresses_count(&self) -> usize {
+        self.dapi_addresses
+            .split(',')
+            .filter(|address| !address.trim().is_empty())
+            .count()
+    }
+
     /// Insight API URI
     pub fn insight_api_uri(&self) -> Uri {
         Uri::from_str(&self.insight_api_url).expect("invalid insight API URL")
     }
 
+    /// Interval between keep-funded balance checks
+    pub fn keep_funded_check_interval(&self) -> Duration {
+        Duration::from_secs(self.keep_funded_check_seconds.unwrap_or(60))
+    }
+
+    /// Time the remote signer has to respond to a signing request
+    pub fn remote_signer_timeout(&self) -> Duration {
+        Duration::from_secs(self.remote_signer_timeout_seconds.unwrap_or(30))
+    }
+
+    /// Time the remote signer has to accept a connection
+    pub fn remote_signer_connect_timeout(&self) -> Duration {
+        Duration::from_secs(self.remote_signer_connect_timeout_seconds.unwrap_or(5))
+    }
+
+    /// Interval between core chain reorg checks
+    pub fn reorg_check_interval(&self) -> Duration {
+        Duration::from_secs(self.reorg_check_seconds.unwrap_or(60))
+    }
+
+    /// Interval between DAPI connections keepalive requests, disabled if
+    /// `None`
+    pub fn dapi_keepalive_interval(&self) -> Option<Duration> {
+        match self.dapi_keepalive_seconds {
+            Some(0) => None,
+            seconds => Some(Duration::from_secs(seconds.unwrap_or(30))),
+        }
+    }
+
+    /// Interval between chain status polls, disabled if `None`
+    pub fn chain_status_interval(&self) -> Option<Duration> {
+        match self.chain_status_seconds {
+            Some(0) => None,
+            seconds => Some(Duration::from_secs(seconds.unwrap_or(30))),
+        }
+    }
+
+    /// UI inactivity before prefetching, disabled if `None`
+    pub fn prefetch_idle_delay(&self) -> Option<Duration> {
+        match self.prefetch_idle_seconds {
+            Some(0) => None,
+            seconds => Some(Duration::from_secs(seconds.unwrap_or(3))),
+        }
+    }
+
+    /// Window to detect duplicate task submissions in
+    pub fn duplicate_task_window(&self) -> Option<Duration> {
+        self.duplicate_task_window_seconds
+            .filter(|seconds| *seconds > 0)
+            .map(Duration::from_secs)
+    }
+
+    /// Name of the profile used to separate persistent state of different
+    /// networks, the network name if no profile is selected
+    pub fn profile_name(&self) -> &str {
+        self.profile.as_deref().unwrap_or(&self.network)
+    }
+
     /// Returns path to the state file
     pub fn state_file_path(&self) -> PathBuf {
-        format!("{}_explorer.state", self.network).into()
+        format!("{}_explorer.state", self.profile_name()).into()
+    }
+
+    /// Returns path to the snapshot of known contracts and identities
+    pub fn known_entities_file_path(&self) -> PathBuf {
+        format!("{}_known_entities.bin", self.profile_name()).into()
+    }
+
+    /// Returns path to the document bookmarks file
+    pub fn bookmarks_file_path(&self) -> PathBuf {
+        format!("{}_bookmarks.json", self.profile_name()).into()
+    }
+
+    /// Returns path to the file of asset locks made by the wallet
+    pub fn asset_locks_file_path(&self) -> PathBuf {
+        format!("{}_asset_locks.json", self.profile_name()).into()
+    }
+
+    /// Returns path to the changelog of observed network parameters
+    pub fn network_changelog_file_path(&self) -> PathBuf {
+        format!("{}_network_changelog.json", self.profile_name()).into()
+    }
+
+    /// Returns path to the file of named wallets
+    pub fn wallets_file_path(&self) -> PathBuf {
+        format!("{}_wallets.json", self.profile_name()).into()
+    }
+
+    /// Returns path to the file of identity petnames
+    pub fn petnames_file_path(&self) -> PathBuf {
+        format!("{}_petnames.json", self.profile_name()).into()
+    }
+
+    /// Returns path to the file of document join rules
+    pub fn join_rules_file_path(&self) -> PathBuf {
+        format!("{}_join_rules.json", self.profile_name()).into()
+    }
+
+    /// Returns path to the file of strategy warm-up and cool-down phases
+    pub fn strategy_phases_file_path(&self) -> PathBuf {
+        format!("{}_strategy_phases.json", self.profile_name()).into()
+    }
+
+    /// Returns path to the file of strategy transition padding targets
+    pub fn strategy_padding_file_path(&self) -> PathBuf {
+        format!("{}_strategy_padding.json", self.profile_name()).into()
+    }
+
+    /// Returns path to the file of keep-funded mode settings and Dash spent
+    /// today
+    pub fn keep_funded_file_path(&self) -> PathBuf {
+        format!("{}_keep_funded.json", self.profile_name()).into()
+    }
+
+    /// Returns path to the log of evonode identity balances
+    pub fn evonode_rewards_file_path(&self) -> PathBuf {
+        format!("{}_evonode_rewards.json", self.profile_name()).into()
+    }
+
+    /// Returns path to the append-only log of signing operations
+    pub fn signing_audit_file_path(&self) -> PathBuf {
+        format!("{}_signing_audit.jsonl", self.profile_name()).into()
+    }
+
+    /// Returns path to the local Drive database. The one shared by every
+    /// network before profiles existed is moved to the first profile opening
+    /// its Drive, so it's never shared again.
+    pub fn drive_path(&self) -> PathBuf {
+        let path: PathBuf = format!("{}_explorer.drive", self.profile_name()).into();
+        if !path.exists() && Path::new(LEGACY_DRIVE_PATH).exists() {
+            match fs::rename(LEGACY_DRIVE_PATH, &path) {
+                Ok(()) => tracing::info!("Moved {} to {}", LEGACY_DRIVE_PATH, path.display()),
+                Err(e) => tracing::error!(
+                    "Unable to move {} to {}: {}",
+                    LEGACY_DRIVE_PATH,
+                    path.display(),
+                    e
+                ),
+            }
+        }
+        path
     }
 
     fn core_network_name(&self) -> &str {
@@ -0,0 +1,148 @@
+//! Explorer settings of a local network set up with dashmate.
+//! Dashmate keeps the full configuration of every node it manages in a single
+//! JSON file, nodes of a local network belonging to the same group. DAPI
+//! addresses are collected from all nodes of the default group, Core RPC and
+//! Insight settings are taken from the first node providing them.
+
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+use serde_json::Value as JsonValue;
+
+/// Location of the dashmate configuration relative to the home directory.
+const DASHMATE_CONFIG_PATH: &str = ".dashmate/config.json";
+
+/// Host services of a local network are reachable on.
+const LOCAL_HOST: &str = "127.0.0.1";
+
+/// Core RPC user created by dashmate for its own use, other users may have
+/// restricted RPC methods.
+const DASHMATE_RPC_USER: &str = "dashmate";
+
+/// Default location of the dashmate configuration.
+pub(super) fn default_path() -> Option<PathBuf> {
+    env::var_os("HOME").map(|home| Path::new(&home).join(DASHMATE_CONFIG_PATH))
+}
+
+fn lookup<'a>(config: &'a JsonValue, path: &[&str]) -> Option<&'a JsonValue> {
+    path.iter().try_fold(config, |value, key| value.get(key))
+}
+
+/// Port of the node's DAPI, either behind the gateway of recent dashmate
+/// versions or the Envoy proxy of older ones.
+fn dapi_port(config: &JsonValue) -> Option<u64> {
+    lookup(
+        config,
+        &["platform", "gateway", "listeners", "dapiAndDrive", "port"],
+    )
+    .or_else(|| lookup(config, &["platform", "dapi", "envoy", "http", "port"]))
+    .and_then(JsonValue::as_u64)
+}
+
+/// Core RPC user and password, either from the users map of recent dashmate
+/// versions or the single user of older ones.
+fn core_rpc_credentials(rpc: &JsonValue) -> Option<(String, String)> {
+    if let Some(users) = rpc.get("users").and_then(JsonValue::as_object) {
+        let (user, settings) = users
+            .get_key_value(DASHMATE_RPC_USER)
+            .or_else(|| users.iter().next())?;
+        let password = settings.get("password")?.as_str()?;
+        return Some((user.clone(), password.to_owned()));
+    }
+    Some((
+        rpc.get("user")?.as_str()?.to_owned(),
+        rpc.get("password")?.as_str()?.to_owned(),
+    ))
+}
+
+fn network_name(dashmate_network: &str) -> &str {
+    match dashmate_network {
+        "mainnet" => "dash",
+        network => network,
+    }
+}
+
+/// Configuration options, without the prefix, read from the dashmate
+/// configuration file.
+pub(super) fn read_options(path: &Path) -> Result<Vec<(&'static str, String)>, String> {
+    let content = fs::read_to_string(path).map_err(|e| {
+        format!(
+            "Unable to read dashmate configuration {}: {}",
+            path.display(),
+            e
+        )
+    })?;
+    let dashmate: JsonValue = serde_json::from_str(&content)
+        .map_err(|e| format!("Invalid dashmate configuration: {}", e))?;
+    let configs = dashmate
+        .get("configs")
+        .and_then(JsonValue::as_object)
+        .ok_or_else(|| "No configs in the dashmate configuration".to_owned())?;
+
+    let nodes: Vec<&JsonValue> = match dashmate.get("defaultGroupName").and_then(JsonValue::as_str)
+    {
+        Some(group) => configs
+            .values()
+            .filter(|config| config.get("group").and_then(JsonValue::as_str) == Some(group))
+            .collect(),
+        None => dashmate
+            .get("defaultConfigName")
+            .and_then(JsonValue::as_str)
+            .and_then(|name| configs.get(name))
+            .into_iter()
+            .collect(),
+    };
+    if nodes.is_empty() {
+        return Err("No default config or group in the dashmate configuration".to_owned());
+    }
+
+    let mut options = Vec::new();
+
+    let dapi_addresses: Vec<String> = nodes
+        .iter()
+        .filter(|node| {
+            lookup(node, &["platform", "enable"]).and_then(JsonValue::as_bool) != Some(false)
+        })
+        .filter_map(|node| dapi_port(node))
+        .map(|port| format!("http://{}:{}", LOCAL_HOST, port))
+        .collect();
+    if dapi_addresses.is_empty() {
+        return Err("No Platform nodes in the dashmate configuration".to_owned());
+    }
+    options.push(("DAPI_ADDRESSES", dapi_addresses.join(",")));
+
+    let (rpc, (user, password)) = nodes
+        .iter()
+        .filter_map(|node| lookup(node, &["core", "rpc"]))
+        .find_map(|rpc| Some((rpc, core_rpc_credentials(rpc)?)))
+        .ok_or_else(|| "No Core RPC credentials in the dashmate configuration".to_owned())?;
+    let rpc_port = rpc
+        .get("port")
+        .and_then(JsonValue::as_u64)
+        .ok_or_else(|| "No Core RPC port in the dashmate configuration".to_owned())?;
+    options.push(("CORE_HOST", LOCAL_HOST.to_owned()));
+    options.push(("CORE_RPC_PORT", rpc_port.to_string()));
+    options.push(("CORE_RPC_USER", user));
+    options.push(("CORE_RPC_PASSWORD", password));
+
+    // Insight is optional in dashmate, the configured URL is kept without it
+    if let Some(port) = nodes
+        .iter()
+        .filter_map(|node| lookup(node, &["core", "insight"]))
+        .filter(|insight| insight.get("enabled").and_then(JsonValue::as_bool) == Some(true))
+        .find_map(|insight| insight.get("port").and_then(JsonValue::as_u64))
+    {
+        options.push((
+            "INSIGHT_API_URL",
+            format!("http://{}:{}/insight-api", LOCAL_HOST, port),
+        ));
+    }
+
+    if let Some(network) = nodes[0].get("network").and_then(JsonValue::as_str) {
+        options.push(("NETWORK", network_name(network).to_owned()));
+    }
+
+    Ok(options)
+}
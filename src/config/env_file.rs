@@ -0,0 +1,147 @@
+//! Options of the `.env` file.
+//! Options following a `[profile.NAME]` line belong to the network profile
+//! of that name, written without the profile prefix, up to the next profile
+//! section. Lines of the options are replaced in place keeping comments and
+//! the order of the file, options not found yet are appended at the end of
+//! their section. Values are written double quoted and escaped unless they're
+//! made of characters the parser reads as is.
+
+use std::{fs, io, ops::Range, path::Path};
+
+/// Profile name of a section header line.
+fn section_name(line: &str) -> Option<&str> {
+    line.trim().strip_prefix("[profile.")?.strip_suffix(']')
+}
+
+fn profile_option_name(profile: &str, name: &str) -> String {
+    format!("{}_{}", profile.to_uppercase(), name)
+}
+
+/// Reads the options of the file by full variable names, options of profile
+/// sections prefixed with the profile name. The environment is left
+/// untouched.
+pub(super) fn read_options(path: &Path) -> Result<Vec<(String, String)>, String> {
+    let content = fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+
+    let mut sections: Vec<(Option<&str>, String)> = vec![(None, String::new())];
+    for line in content.lines() {
+        match section_name(line) {
+            Some(profile) => sections.push((Some(profile), String::new())),
+            None => {
+                let (_, text) = sections.last_mut().expect("global section");
+                text.push_str(line);
+                text.push('\n');
+            }
+        }
+    }
+
+    let mut options = Vec::new();
+    for (profile, text) in sections {
+        for option in dotenvy::from_read_iter(text.as_bytes()) {
+            let (name, value) = option.map_err(|e| format!("Invalid {}: {}", path.display(), e))?;
+            options.push(match profile {
+                Some(profile) => (profile_option_name(profile, &name), value),
+                None => (name, value),
+            });
+        }
+    }
+    Ok(options)
+}
+
+/// Value as written in the file, quoted if it has spaces, comment, quote,
+/// substitution or escape characters.
+fn quote(value: &str) -> String {
+    let is_plain = value
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || "-_.,:/@+".contains(c));
+    if is_plain {
+        return value.to_owned();
+    }
+    let mut quoted = String::with_capacity(value.len() + 2);
+    quoted.push('"');
+    for c in value.chars() {
+        match c {
+            '\\' | '"' | '$' => {
+                quoted.push('\\');
+                quoted.push(c);
+            }
+            '\n' => quoted.push_str("\\n"),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// Lines of the profile section, of the options preceding any section if no
+/// profile is given. `None` if the file has no section for the profile.
+fn section_lines(lines: &[String], profile: Option<&str>) -> Option<Range<usize>> {
+    let start = match profile {
+        Some(profile) => {
+            lines
+                .iter()
+                .position(|line| section_name(line) == Some(profile))?
+                + 1
+        }
+        None => 0,
+    };
+    let end = lines[start..]
+        .iter()
+        .position(|line| section_name(line).is_some())
+        .map_or(lines.len(), |offset| start + offset);
+    Some(start..end)
+}
+
+/// Sets the options in the file given by variable names without profile
+/// prefix, the file is created if missing. Options of a profile go to its
+/// section, or are prefixed with the profile name if the file has none.
+pub(super) fn save_options(
+    path: &Path,
+    profile: Option<&str>,
+    options: &[(String, String)],
+) -> Result<(), String> {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+        Err(e) => return Err(format!("Failed to read {}: {}", path.display(), e)),
+    };
+
+    let mut lines: Vec<String> = content.lines().map(ToOwned::to_owned).collect();
+    for (name, value) in options {
+        let (name, section) = match profile {
+            Some(profile) => match section_lines(&lines, Some(profile)) {
+                Some(section) => (name.clone(), section),
+                None => (
+                    profile_option_name(profile, name),
+                    section_lines(&lines, None).expect("global section"),
+                ),
+            },
+            None => (
+                name.clone(),
+                section_lines(&lines, None).expect("global section"),
+            ),
+        };
+
+        let line = format!("{}={}", name, quote(value));
+        let prefix = format!("{}=", name);
+        match lines[section.clone()]
+            .iter()
+            .position(|line| line.trim_start().starts_with(&prefix))
+        {
+            Some(index) => lines[section.start + index] = line,
+            None => {
+                // Keep blank lines separating the section from the next one
+                let end = lines[section.clone()]
+                    .iter()
+                    .rposition(|line| !line.trim().is_empty())
+                    .map_or(section.start, |index| section.start + index + 1);
+                lines.insert(end, line);
+            }
+        }
+    }
+
+    let mut content = lines.join("\n");
+    content.push('\n');
+    fs::write(path, content).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
@@ -0,0 +1,262 @@
+//! Failure reporting of headless runs.
+//! A failed headless run prints a JSON line describing the failure to stderr
+//! and exits with the code of its category, so orchestration scripts can
+//! branch on failure types instead of grepping logs. The JSON has `category`,
+//! `code` (a stable snake_case identifier of the failure), `message` and
+//! `retryable` fields. The category is given by the step that failed, only
+//! network failures are retryable.
+//!
+//! | Exit code | Category   | Meaning                                        |
+//! |-----------|------------|------------------------------------------------|
+//! | 0         |            | Success                                        |
+//! | 2         |            | Invalid command line arguments                 |
+//! | 3         | `config`   | Missing or invalid configuration               |
+//! | 4         | `network`  | DAPI, Core or Insight unreachable or timing out |
+//! | 5         | `funding`  | Wallet or identity funding failed              |
+//! | 6         | `strategy` | Strategy couldn't be run                       |
+//! | 7         | `halted`   | Strategy stopped before its last block         |
+//! | 8         | `drift`    | Replayed queries got other results than traced |
+//! | 70        | `internal` | Unexpected error like a panic                  |
+//!
+//! With `--output json`, a summary of the identity tasks and strategy run is
+//! printed as one JSON object to stdout when the run ends, failed or not.
+
+use std::{collections::BTreeMap, process::ExitCode};
+
+use clap::ValueEnum;
+use serde::Serialize;
+
+use crate::backend::{BackendEvent, StrategyCompletionResult};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FailureCategory {
+    Config,
+    Network,
+    Funding,
+    Strategy,
+    Halted,
+    Drift,
+    Internal,
+}
+
+impl FailureCategory {
+    pub fn exit_code(self) -> ExitCode {
+        ExitCode::from(match self {
+            FailureCategory::Config => 3,
+            FailureCategory::Network => 4,
+            FailureCategory::Funding => 5,
+            FailureCategory::Strategy => 6,
+            FailureCategory::Halted => 7,
+            FailureCategory::Drift => 8,
+            FailureCategory::Internal => 70,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Failure {
+    pub category: FailureCategory,
+    pub code: &'static str,
+    pub message: String,
+    /// Whether running again as is may succeed
+    pub retryable: bool,
+}
+
+impl Failure {
+    /// Failure of the category, retryable if it's a network failure.
+    pub fn new(category: FailureCategory, code: &'static str, message: impl Into<String>) -> Self {
+        Failure {
+            category,
+            code,
+            message: message.into(),
+            retryable: category == FailureCategory::Network,
+        }
+    }
+
+    /// Prints the failure JSON and returns the category code for `main` to
+    /// exit with, so the backend is dropped and the state saved on the way.
+    pub fn report(&self) -> ExitCode {
+        tracing::error!(code = self.code, "{}", self.message);
+        match serde_json::to_string(self) {
+            Ok(json) => eprintln!("{}", json),
+            Err(_) => eprintln!("{}", self.message),
+        }
+        self.category.exit_code()
+    }
+}
+
+/// Failure of a task executed headless, `None` if it succeeded. Errors are of
+/// the given category and code, strategies that stopped early are `halted`.
+pub fn task_failure(
+    event: &BackendEvent,
+    category: FailureCategory,
+    code: &'static str,
+) -> Option<Failure> {
+    match event {
+        BackendEvent::TaskCompleted {
+            execution_result: Err(error),
+            ..
+        }
+        | BackendEvent::TaskCompletedStateChange {
+            execution_result: Err(error),
+            ..
+        }
+        | BackendEvent::StrategyError { error } => Some(Failure::new(category, code, error)),
+        BackendEvent::StrategyCompleted {
+            result: StrategyCompletionResult::PartiallyCompleted { reason, .. },
+            ..
+        } => Some(Failure::new(
+            FailureCategory::Halted,
+            "strategy_stopped_early",
+            reason,
+        )),
+        _ => None,
+    }
+}
+
+/// Format of the results of a headless run.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    /// Results are only logged
+    #[default]
+    Text,
+    /// A JSON summary is printed to stdout as well
+    Json,
+}
+
+/// Outcome of a task run before the strategy, like an identity registration.
+#[derive(Debug, Clone, Serialize)]
+pub struct TaskOutcome {
+    pub task: &'static str,
+    pub success: bool,
+    /// Error message of a failed task
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct StrategySummary {
+    pub name: String,
+    /// Whether all blocks (or seconds) were run
+    pub completed: bool,
+    pub transitions_sent: u64,
+    pub successes: u64,
+    pub failures: u64,
+    pub start_block_height: u64,
+    pub final_block_height: u64,
+    /// Fees paid, unknown for halted runs
+    pub dash_spent_identity: Option<f64>,
+    pub dash_spent_wallet: Option<f64>,
+    /// Transitions submitted by block height
+    pub transitions_per_block: BTreeMap<u64, u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub halt_reason: Option<String>,
+}
+
+/// Machine readable summary of a headless run.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct RunSummary {
+    pub identity_tasks: Vec<TaskOutcome>,
+    pub strategy: Option<StrategySummary>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub failure: Option<Failure>,
+}
+
+impl RunSummary {
+    /// Records the outcome of the task event under the task code.
+    pub fn record_task(&mut self, task: &'static str, event: &BackendEvent) {
+        let error = match event {
+            BackendEvent::TaskCompleted {
+                execution_result: Err(error),
+                ..
+            }
+            | BackendEvent::TaskCompletedStateChange {
+                execution_result: Err(error),
+                ..
+            } => Some(error.clone()),
+            _ => None,
+        };
+        self.identity_tasks.push(TaskOutcome {
+            task,
+            success: error.is_none(),
+            error,
+        });
+    }
+
+    /// Records the result of a strategy run, errors before it started are left
+    /// to the failure.
+    pub fn record_strategy(&mut self, event: &BackendEvent) {
+        let BackendEvent::StrategyCompleted {
+            strategy_name,
+            result,
+        } = event
+        else {
+            return;
+        };
+        let summary = match result {
+            StrategyCompletionResult::Success {
+                start_block_height,
+                final_block_height,
+                success_count,
+                transition_count,
+                dash_spent_identity,
+                dash_spent_wallet,
+                block_transition_counts,
+                ..
+            } => StrategySummary {
+                name: strategy_name.clone(),
+                completed: true,
+                transitions_sent: *transition_count,
+                successes: *success_count,
+                failures: transition_count.saturating_sub(*success_count),
+                start_block_height: *start_block_height,
+                final_block_height: *final_block_height,
+                dash_spent_identity: Some(*dash_spent_identity),
+                dash_spent_wallet: Some(*dash_spent_wallet),
+                transitions_per_block: block_transition_counts.clone(),
+                halt_reason: None,
+            },
+            StrategyCompletionResult::PartiallyCompleted {
+                reached_block_height,
+                reason,
+                start_block_height,
+                transition_count,
+                success_count,
+                block_transition_counts,
+                ..
+            } => StrategySummary {
+                name: strategy_name.clone(),
+                completed: false,
+                transitions_sent: *transition_count,
+                successes: *success_count,
+                failures: transition_count.saturating_sub(*success_count),
+                start_block_height: *start_block_height,
+                final_block_height: *reached_block_height,
+                dash_spent_identity: None,
+                dash_spent_wallet: None,
+                transitions_per_block: block_transition_counts.clone(),
+                halt_reason: Some(reason.clone()),
+            },
+        };
+        self.strategy = Some(summary);
+    }
+
+    /// Prints the summary JSON to stdout.
+    pub fn print(&self) {
+        match serde_json::to_string(self) {
+            Ok(json) => println!("{}", json),
+            Err(e) => tracing::error!("Unable to serialize run summary: {}", e),
+        }
+    }
+
+    /// Prints the summary with the failure if JSON output is enabled, then
+    /// reports the failure.
+    pub fn fail(mut self, output: OutputFormat, failure: Failure) -> ExitCode {
+        if output == OutputFormat::Json {
+            self.failure = Some(failure.clone());
+            self.print();
+        }
+        failure.report()
+    }
+}
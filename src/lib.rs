@@ -1,12 +1,17 @@
 pub mod backend;
 pub mod config;
+pub mod headless;
+pub mod logging;
+pub mod notification;
 pub mod ui;
 
 use backend::BackendEvent;
+use crossterm::event::MouseEvent;
 use tuirealm::event::KeyEvent;
 
 pub enum Event<'s> {
     Key(KeyEvent),
+    Mouse(MouseEvent),
     Backend(BackendEvent<'s>),
     RedrawDebounceTimeout,
 }
@@ -0,0 +1,98 @@
+//! Logging setup.
+//!
+//! The log filter is wrapped into a reloadable layer, so the verbosity of
+//! specific targets can be changed from the UI without restarting the explorer.
+//! Optionally DAPI requests are traced into a separate session file, regardless
+//! of the log filter, and counted by the traffic meter. Log lines are written
+//! as text or, for log processing tools, as JSON.
+
+mod correlation;
+mod dapi_trace;
+mod traffic;
+
+use std::{fs::File, sync::OnceLock};
+
+use tracing_subscriber::{fmt, layer::SubscriberExt, reload, EnvFilter, Layer, Registry};
+
+pub(crate) use self::{correlation::CorrelationId, dapi_trace::traced_dapi_request};
+use self::{dapi_trace::DapiTraceLayer, traffic::TrafficLayer};
+use crate::config::{Config, LogFormat};
+
+const DEFAULT_FILTER: &str = "info";
+
+static FILTER_HANDLE: OnceLock<reload::Handle<EnvFilter, Registry>> = OnceLock::new();
+
+/// Sets up the global subscriber writing into the log file and, if configured,
+/// into the DAPI trace file.
+pub fn init(log_file: File, config: &Config) {
+    let (filter, handle) = reload::Layer::new(EnvFilter::new(DEFAULT_FILTER));
+
+    // The explorer runs without the trace if its file can't be created, the
+    // error is logged once the logging is set up
+    let (dapi_trace, dapi_trace_error) = match config.dapi_trace_file.as_ref().map(File::create) {
+        Some(Ok(trace_file)) => (
+            Some(
+                DapiTraceLayer::new(trace_file, config.dapi_trace_payloads)
+                    .with_filter(dapi_trace::targets()),
+            ),
+            None,
+        ),
+        Some(Err(e)) => (None, Some(e)),
+        None => (None, None),
+    };
+
+    let traffic = config.traffic_meter.then(|| {
+        crate::backend::traffic::enable();
+        TrafficLayer.with_filter(traffic::targets())
+    });
+
+    let log_layer = fmt::layer().with_writer(log_file).with_ansi(false);
+    let log_layer: Box<dyn Layer<Registry> + Send + Sync> = match config.log_format {
+        LogFormat::Text => log_layer.boxed(),
+        LogFormat::Json => log_layer.json().with_current_span(true).boxed(),
+    };
+
+    let subscriber = tracing_subscriber::registry()
+        .with(log_layer.with_filter(filter))
+        .with(dapi_trace)
+        .with(traffic);
+
+    tracing::subscriber::set_global_default(subscriber)
+        .expect("Unable to set global default subscriber");
+
+    if let Some(e) = dapi_trace_error {
+        tracing::error!(
+            "Unable to create the DAPI trace file, tracing is disabled: {}",
+            e
+        );
+    }
+
+    FILTER_HANDLE
+        .set(handle)
+        .expect("logging is initialized only once");
+}
+
+/// Current filter directives, if the logging was initialized by [init].
+pub(crate) fn current_filter() -> Option<String> {
+    FILTER_HANDLE
+        .get()?
+        .with_current(|filter| filter.to_string())
+        .ok()
+}
+
+/// Replaces the log filter with new directives (the same syntax as `RUST_LOG`,
+/// e.g. `info,dash_sdk=debug`).
+pub(crate) fn set_filter(directives: &str) -> Result<String, String> {
+    let handle = FILTER_HANDLE
+        .get()
+        .ok_or_else(|| "Logging has no reloadable filter".to_owned())?;
+    let filter = EnvFilter::try_new(directives)
+        .map_err(|e| format!("Invalid log filter `{}`: {}", directives, e))?;
+    let filter_string = filter.to_string();
+    handle
+        .reload(filter)
+        .map_err(|e| format!("Unable to reload log filter: {}", e))?;
+
+    tracing::info!("Log filter changed to `{}`", filter_string);
+    Ok(filter_string)
+}
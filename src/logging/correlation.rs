@@ -0,0 +1,57 @@
+//! Task correlation IDs.
+//!
+//! Every task executed by the backend gets an ID recorded on its tracing span,
+//! so all log lines of an operation can be found by it. The same ID is shown
+//! with task errors in the UI and saved into strategy reports.
+
+use std::{
+    fmt::Display,
+    future::Future,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        OnceLock,
+    },
+};
+
+use tracing::Instrument;
+
+static NEXT_ID: AtomicU64 = AtomicU64::new(1);
+
+tokio::task_local! {
+    static CURRENT: CorrelationId;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct CorrelationId {
+    /// Random per explorer run, so IDs of different runs don't collide in
+    /// shared logs
+    session: u32,
+    sequence: u64,
+}
+
+impl CorrelationId {
+    pub(crate) fn next() -> Self {
+        static SESSION: OnceLock<u32> = OnceLock::new();
+        CorrelationId {
+            session: *SESSION.get_or_init(rand::random),
+            sequence: NEXT_ID.fetch_add(1, Ordering::Relaxed),
+        }
+    }
+
+    /// ID of the task being executed, if any.
+    pub(crate) fn current() -> Option<Self> {
+        CURRENT.try_with(|id| *id).ok()
+    }
+
+    /// Runs the task future within a span carrying the ID.
+    pub(crate) async fn scope<F: Future>(self, task: &str, future: F) -> F::Output {
+        let span = tracing::info_span!("task", correlation_id = %self, task);
+        CURRENT.scope(self, future.instrument(span)).await
+    }
+}
+
+impl Display for CorrelationId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:08x}-{}", self.session, self.sequence)
+    }
+}
@@ -0,0 +1,263 @@
+//! DAPI session trace.
+//!
+//! Records spans and events of the DAPI client and the SDK (request method,
+//! duration, outcome and reported fields) as JSON lines, one entry per finished
+//! span, suitable for later analysis or attaching to platform bug reports.
+//! The gRPC code of failed requests is taken from the reported status.
+//!
+//! Payloads of the DAPI client traces are only available as debug text, so
+//! the encoded sizes of requests and responses are recorded for requests the
+//! explorer sends itself through [traced_dapi_request].
+
+use std::{
+    collections::BTreeMap,
+    fmt::Debug,
+    fs::File,
+    future::Future,
+    io::{self, BufWriter, Write},
+    sync::Mutex,
+    time::{Instant, SystemTime, UNIX_EPOCH},
+};
+
+use dapi_grpc::Message;
+use serde::Serialize;
+use tracing::{
+    field::{self, Field, Visit},
+    span::{Attributes, Id, Record},
+    Instrument, Level, Subscriber,
+};
+use tracing_subscriber::{filter::Targets, layer::Context, registry::LookupSpan, Layer};
+
+/// Fields that carry request or response payloads.
+const PAYLOAD_FIELDS: [&str; 2] = ["request", "response"];
+
+/// Target of the spans of requests sent by the explorer.
+const REQUEST_TARGET: &str = "dapi_request";
+
+/// Targets traced into the session file.
+pub(super) fn targets() -> Targets {
+    Targets::new()
+        .with_target("rs_dapi_client", Level::TRACE)
+        .with_target("dash_sdk", Level::TRACE)
+        .with_target(REQUEST_TARGET, Level::TRACE)
+}
+
+/// Sends the request within a span recording the encoded sizes of the request
+/// and its response.
+pub(crate) async fn traced_dapi_request<R, T, E, F>(
+    request: R,
+    execute: impl FnOnce(R) -> F,
+) -> Result<T, E>
+where
+    R: Message,
+    T: Message,
+    F: Future<Output = Result<T, E>>,
+{
+    let method = std::any::type_name::<R>()
+        .rsplit("::")
+        .next()
+        .unwrap_or_default();
+    let span = tracing::trace_span!(
+        target: REQUEST_TARGET,
+        "dapi_request",
+        method,
+        request_size = request.encoded_len(),
+        response_size = field::Empty,
+    );
+    let result = execute(request).instrument(span.clone()).await;
+    if let Ok(response) = &result {
+        span.record("response_size", response.encoded_len());
+    }
+    result
+}
+
+/// gRPC code of a status in debug text, as in `Status { code: NotFound, .. }`.
+fn grpc_code(value: &str) -> Option<String> {
+    let (_, rest) = value.split_once("code: ")?;
+    let code: String = rest
+        .chars()
+        .take_while(|c| c.is_ascii_alphanumeric())
+        .collect();
+    (!code.is_empty()).then_some(code)
+}
+
+#[derive(Serialize)]
+struct TraceEvent {
+    level: String,
+    fields: BTreeMap<String, String>,
+}
+
+#[derive(Serialize)]
+struct TraceEntry {
+    /// Unix timestamp in milliseconds
+    started_at: u128,
+    target: String,
+    name: String,
+    duration_ms: u128,
+    status: &'static str,
+    /// gRPC code of the response, if one was reported
+    #[serde(skip_serializing_if = "Option::is_none")]
+    grpc_code: Option<String>,
+    fields: BTreeMap<String, String>,
+    events: Vec<TraceEvent>,
+}
+
+/// Span data accumulated until the span is closed.
+struct SpanTrace {
+    started: Instant,
+    entry: TraceEntry,
+}
+
+#[derive(Default)]
+struct FieldsVisitor(BTreeMap<String, String>);
+
+impl Visit for FieldsVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.insert(field.name().to_owned(), value.to_owned());
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        self.0
+            .insert(field.name().to_owned(), format!("{:?}", value));
+    }
+}
+
+pub(super) struct DapiTraceLayer {
+    writer: Mutex<BufWriter<File>>,
+    payloads: bool,
+}
+
+impl DapiTraceLayer {
+    pub(super) fn new(file: File, payloads: bool) -> Self {
+        DapiTraceLayer {
+            writer: Mutex::new(BufWriter::new(file)),
+            payloads,
+        }
+    }
+
+    /// Drops payloads unless payloads recording is enabled.
+    fn collect_fields(&self, fields: BTreeMap<String, String>) -> BTreeMap<String, String> {
+        fields
+            .into_iter()
+            .filter(|(name, _)| self.payloads || !PAYLOAD_FIELDS.contains(&name.as_str()))
+            .collect()
+    }
+
+    fn write_entry(&self, entry: &TraceEntry) {
+        let Ok(mut writer) = self.writer.lock() else {
+            return;
+        };
+        // Logging the failure from here would be traced again, so it's dropped
+        let _ = write_json_line(&mut *writer, entry);
+    }
+}
+
+fn write_json_line(writer: &mut impl Write, entry: &TraceEntry) -> io::Result<()> {
+    serde_json::to_writer(&mut *writer, entry)?;
+    writeln!(writer)?;
+    writer.flush()
+}
+
+fn unix_millis() -> u128 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis())
+        .unwrap_or_default()
+}
+
+fn is_failure(level: &Level) -> bool {
+    *level == Level::ERROR || *level == Level::WARN
+}
+
+impl<S> Layer<S> for DapiTraceLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else {
+            return;
+        };
+        let mut visitor = FieldsVisitor::default();
+        attrs.record(&mut visitor);
+
+        span.extensions_mut().insert(SpanTrace {
+            started: Instant::now(),
+            entry: TraceEntry {
+                started_at: unix_millis(),
+                target: attrs.metadata().target().to_owned(),
+                name: attrs.metadata().name().to_owned(),
+                duration_ms: 0,
+                status: "ok",
+                grpc_code: None,
+                fields: self.collect_fields(visitor.0),
+                events: Vec::new(),
+            },
+        });
+    }
+
+    fn on_record(&self, id: &Id, values: &Record<'_>, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else {
+            return;
+        };
+        let mut visitor = FieldsVisitor::default();
+        values.record(&mut visitor);
+        let fields = self.collect_fields(visitor.0);
+
+        if let Some(trace) = span.extensions_mut().get_mut::<SpanTrace>() {
+            trace.entry.fields.extend(fields);
+        }
+    }
+
+    fn on_event(&self, event: &tracing::Event<'_>, ctx: Context<'_, S>) {
+        let mut visitor = FieldsVisitor::default();
+        event.record(&mut visitor);
+        let level = *event.metadata().level();
+        let grpc_code = if is_failure(&level) {
+            visitor.0.values().find_map(|value| grpc_code(value))
+        } else if visitor.0.contains_key("response") {
+            Some("Ok".to_owned())
+        } else {
+            None
+        };
+        let trace_event = TraceEvent {
+            level: level.to_string(),
+            fields: self.collect_fields(visitor.0),
+        };
+
+        match ctx.event_span(event) {
+            Some(span) => {
+                if let Some(trace) = span.extensions_mut().get_mut::<SpanTrace>() {
+                    if is_failure(&level) {
+                        trace.entry.status = "error";
+                    }
+                    if grpc_code.is_some() {
+                        trace.entry.grpc_code = grpc_code;
+                    }
+                    trace.entry.events.push(trace_event);
+                }
+            }
+            // Events outside of any traced span are written as they are
+            None => self.write_entry(&TraceEntry {
+                started_at: unix_millis(),
+                target: event.metadata().target().to_owned(),
+                name: event.metadata().name().to_owned(),
+                duration_ms: 0,
+                status: if is_failure(&level) { "error" } else { "ok" },
+                grpc_code,
+                fields: BTreeMap::new(),
+                events: vec![trace_event],
+            }),
+        }
+    }
+
+    fn on_close(&self, id: Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(&id) else {
+            return;
+        };
+        let Some(mut trace) = span.extensions_mut().remove::<SpanTrace>() else {
+            return;
+        };
+        trace.entry.duration_ms = trace.started.elapsed().as_millis();
+        self.write_entry(&trace.entry);
+    }
+}
@@ -0,0 +1,73 @@
+//! DAPI traffic counting for the traffic meter.
+//!
+//! Requests and responses traced by the DAPI client are counted with the size
+//! of their debug representation, which is measured without being kept.
+
+use std::fmt::{self, Debug, Write};
+
+use tracing::{
+    field::{Field, Visit},
+    span::{Attributes, Id, Record},
+    Level, Subscriber,
+};
+use tracing_subscriber::{filter::Targets, layer::Context, Layer};
+
+use crate::backend::traffic::{self, Channel};
+
+/// Targets counted by the meter.
+pub(super) fn targets() -> Targets {
+    Targets::new().with_target("rs_dapi_client", Level::TRACE)
+}
+
+/// Counts written characters only.
+#[derive(Default)]
+struct SizeCounter(usize);
+
+impl Write for SizeCounter {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.0 += s.len();
+        Ok(())
+    }
+}
+
+struct PayloadsVisitor;
+
+impl PayloadsVisitor {
+    fn record_size(&self, field: &Field, size: usize) {
+        match field.name() {
+            "request" => traffic::record_request(Channel::Dapi, size),
+            "response" => traffic::record_response(Channel::Dapi, size),
+            _ => {}
+        }
+    }
+}
+
+impl Visit for PayloadsVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.record_size(field, value.len());
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        if matches!(field.name(), "request" | "response") {
+            let mut counter = SizeCounter::default();
+            let _ = write!(counter, "{:?}", value);
+            self.record_size(field, counter.0);
+        }
+    }
+}
+
+pub(super) struct TrafficLayer;
+
+impl<S: Subscriber> Layer<S> for TrafficLayer {
+    fn on_new_span(&self, attrs: &Attributes<'_>, _id: &Id, _ctx: Context<'_, S>) {
+        attrs.record(&mut PayloadsVisitor);
+    }
+
+    fn on_record(&self, _id: &Id, values: &Record<'_>, _ctx: Context<'_, S>) {
+        values.record(&mut PayloadsVisitor);
+    }
+
+    fn on_event(&self, event: &tracing::Event<'_>, _ctx: Context<'_, S>) {
+        event.record(&mut PayloadsVisitor);
+    }
+}
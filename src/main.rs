@@ -1,29 +1,71 @@
-use std::{fs::File, panic, time::Duration};
+use std::{collections::BTreeMap, fs::File, panic, path::PathBuf, process, time::Duration};
 
-use crossterm::event::{Event as TuiEvent, EventStream};
+use clap::Parser;
+use crossterm::event::{Event as TuiEvent, EventStream, MouseEvent, MouseEventKind};
 use dash_sdk::{RequestSettings, SdkBuilder};
 use dpp::{identity::accessors::IdentityGettersV0, version::PlatformVersion};
-use futures::{future::OptionFuture, select, FutureExt, StreamExt};
+use futures::{
+    future::{FusedFuture, OptionFuture},
+    select, FutureExt, StreamExt,
+};
 use rs_platform_explorer::{
-    backend::{insight::InsightAPIClient, Backend},
-    config::Config,
+    backend::{insight::InsightAPIClient, keep_funded::KeepFundedTask, Backend, Task},
+    config::{Config, StartupScreen},
+    logging,
+    notification::Notifier,
     ui::{IdentityBalance, Ui, UiFeedback},
     Event,
 };
 
+const PROGRESS_REDRAW_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Typing pause after which a form checks its input.
+const FORM_CHECK_DELAY: Duration = Duration::from_millis(400);
+
+#[derive(Parser, Debug)]
+#[clap(about, long_about = None)]
+struct Args {
+    #[arg(
+        long,
+        value_enum,
+        help = "Screen to open on, overrides the startup screen option."
+    )]
+    screen: Option<StartupScreen>,
+    #[arg(
+        long,
+        value_name = "PATH",
+        help = "Connect to the local network of a dashmate configuration, ~/.dashmate/config.json \
+                if no path is given."
+    )]
+    dashmate: Option<Option<PathBuf>>,
+    #[arg(
+        long,
+        value_name = "NAME",
+        conflicts_with = "dashmate",
+        help = "Network profile to use, overrides the profile option."
+    )]
+    profile: Option<String>,
+}
+
 #[tokio::main]
 async fn main() {
+    let args = Args::parse();
+
+    // Load configuration
+    let mut config = match (args.dashmate, args.profile) {
+        (Some(path), _) => Config::load_with_dashmate(path),
+        (None, Some(profile)) => Config::load_profile(&profile, &BTreeMap::new()),
+        (None, None) => Config::load(),
+    }
+    .unwrap_or_else(|e| {
+        eprintln!("{}", e);
+        process::exit(1)
+    });
+
     // Initialize logger
     let log_file = File::create("explorer.log").expect("create log file");
 
-    let subscriber = tracing_subscriber::fmt()
-        .with_env_filter("info")
-        .with_writer(log_file)
-        .with_ansi(false)
-        .finish();
-
-    tracing::subscriber::set_global_default(subscriber)
-        .expect("Unable to set global default subscriber");
+    logging::init(log_file, &config);
 
     // Test log statement
     tracing::info!("Logger initialized successfully");
@@ -32,10 +74,12 @@ async fn main() {
     let default_panic_hook = panic::take_hook();
 
     panic::set_hook(Box::new(move |panic_info| {
-        let message = panic_info
-            .payload()
+        let payload = panic_info.payload();
+        let message = payload
             .downcast_ref::<&str>()
-            .unwrap_or(&"unknown");
+            .copied()
+            .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+            .unwrap_or("unknown");
 
         let location = panic_info
             .location()
@@ -50,9 +94,27 @@ async fn main() {
         default_panic_hook(panic_info);
     }));
 
-    // Load configuration
-    let config = Config::load();
+    // Switching the network profile starts over with a new SDK and state
+    // Options saved from the settings screen are kept in effect over the
+    // environment
+    let mut screen = args.screen;
+    let mut saved_options = BTreeMap::new();
+    while let Some(profile) = run(config.clone(), screen.take(), &mut saved_options).await {
+        tracing::info!("switching to profile {}", profile);
+        match Config::load_profile(&profile, &saved_options) {
+            Ok(profile_config) => config = profile_config,
+            Err(e) => tracing::error!("Unable to switch to profile {}: {}", profile, e),
+        }
+    }
+}
 
+/// Runs the explorer until the user quits, returning the network profile to
+/// switch to if asked. Options saved meanwhile are added to `saved_options`.
+async fn run(
+    config: Config,
+    screen: Option<StartupScreen>,
+    saved_options: &mut BTreeMap<String, String>,
+) -> Option<String> {
     // Setup Platform SDK
     let address_list = config.dapi_address_list();
     let request_settings = RequestSettings {
@@ -102,29 +164,225 @@ async fn main() {
         .as_ref()
         .map(|identity| IdentityBalance::from_credits(identity.balance()));
 
-    let mut ui = Ui::new(initial_identity_balance);
+    let mut ui = Ui::new(initial_identity_balance, &backend.config);
+    ui.open_startup_screen(
+        backend.state(),
+        screen.unwrap_or(backend.config.startup_screen),
+    )
+    .await;
+
+    let mut notifier = Notifier::new(&backend.config);
 
     let mut active = true;
+    let mut profile_switch = None;
 
     let mut terminal_event_stream = EventStream::new().fuse();
-    let mut backend_task: OptionFuture<_> = None.into();
+
+    // The startup task runs as if submitted by the user, without blocking the UI
+    let mut backend_task: OptionFuture<_> = backend
+        .config
+        .startup_task
+        .map(|startup_task| {
+            let task = Task::from(startup_task);
+            notifier.task_started(&task);
+            backend.run_task(task).boxed_local().fuse()
+        })
+        .into();
     let mut ui_debounced_redraw: OptionFuture<_> = None.into();
 
+    // Keep-funded checks run alongside user tasks, as a top up may take minutes
+    let keep_funded_interval = backend.config.keep_funded_check_interval();
+    let mut keep_funded_timer: OptionFuture<_> = None.into();
+    let mut keep_funded_check: OptionFuture<_> = None.into();
+
+    // Reorg checks poll the Core chain regardless of user tasks as well
+    let reorg_check_interval = backend.config.reorg_check_interval();
+    let mut reorg_timer: OptionFuture<_> = None.into();
+    let mut reorg_check: OptionFuture<_> = None.into();
+
+    // DAPI connections are warmed up on startup and kept alive in background,
+    // results are only logged
+    let keepalive_interval = backend.config.dapi_keepalive_interval();
+    let mut keepalive_timer: OptionFuture<_> = None.into();
+    let mut keepalive: OptionFuture<_> = Some(
+        backend
+            .run_background_task(Task::WarmUpConnections)
+            .boxed_local()
+            .fuse(),
+    )
+    .into();
+
+    // Chain heights shown in the status bar are polled in background too, the
+    // first poll runs right away
+    let chain_status_interval = backend.config.chain_status_interval();
+    let mut chain_status_timer: OptionFuture<_> = None.into();
+    let mut chain_status_poll: OptionFuture<_> = chain_status_interval
+        .map(|_| {
+            backend
+                .run_background_task(Task::PollChainStatus)
+                .boxed_local()
+                .fuse()
+        })
+        .into();
+
+    // Once the UI is idle for a while, the current screen may prefetch data
+    // that is likely needed next, results are only logged
+    let prefetch_idle_delay = backend.config.prefetch_idle_delay();
+    let mut idle_timer: OptionFuture<_> = None.into();
+    let mut prefetch: OptionFuture<_> = None.into();
+
+    // Progress reported by a running task, the traffic meter and toasts are
+    // shown in the status bar, which is redrawn periodically to keep them
+    // current
+    let mut progress_timer: OptionFuture<_> = None.into();
+
+    // Forms may check their input in background as it's typed, the check is
+    // restarted on each change
+    let mut form_check: OptionFuture<_> = None.into();
+    let traffic_meter = backend.config.traffic_meter;
+
     while active {
+        if keep_funded_timer.is_terminated() && keep_funded_check.is_terminated() {
+            keep_funded_timer = Some(
+                tokio::time::sleep(keep_funded_interval)
+                    .boxed_local()
+                    .fuse(),
+            )
+            .into();
+        }
+
+        if reorg_timer.is_terminated() && reorg_check.is_terminated() {
+            reorg_timer = Some(
+                tokio::time::sleep(reorg_check_interval)
+                    .boxed_local()
+                    .fuse(),
+            )
+            .into();
+        }
+
+        if let Some(interval) = keepalive_interval {
+            if keepalive_timer.is_terminated() && keepalive.is_terminated() {
+                keepalive_timer = Some(tokio::time::sleep(interval).boxed_local().fuse()).into();
+            }
+        }
+
+        if let Some(interval) = chain_status_interval {
+            if chain_status_timer.is_terminated() && chain_status_poll.is_terminated() {
+                chain_status_timer = Some(tokio::time::sleep(interval).boxed_local().fuse()).into();
+            }
+        }
+
+        if (!backend_task.is_terminated() || traffic_meter || ui.has_toast())
+            && progress_timer.is_terminated()
+        {
+            progress_timer = Some(
+                tokio::time::sleep(PROGRESS_REDRAW_INTERVAL)
+                    .boxed_local()
+                    .fuse(),
+            )
+            .into();
+        }
+
         let event = select! {
             terminal_event = terminal_event_stream.next() => match terminal_event {
                 None => panic!("terminal event stream closed unexpectedly"),
                 Some(Err(_)) => panic!("terminal event stream closed unexpectedly"),
                 Some(Ok(TuiEvent::Resize(_, _))) => {ui.redraw(); continue },
                 Some(Ok(TuiEvent::Key(key_event))) => Some(Event::Key(key_event.into())),
+                // Mouse moves aren't used, only clicks and the wheel
+                Some(Ok(TuiEvent::Mouse(
+                    mouse_event @ MouseEvent {
+                        kind:
+                            MouseEventKind::Down(_)
+                            | MouseEventKind::ScrollUp
+                            | MouseEventKind::ScrollDown,
+                        ..
+                    },
+                ))) => Some(Event::Mouse(mouse_event)),
                 _ => None
             },
             backend_task_finished = backend_task => backend_task_finished.map(Event::Backend),
             ui_redraw = ui_debounced_redraw => ui_redraw.map(|_| Event::RedrawDebounceTimeout),
+            _ = keep_funded_timer => {
+                keep_funded_check = Some(
+                    backend
+                        .run_background_task(Task::KeepFunded(KeepFundedTask::Check))
+                        .boxed_local()
+                        .fuse(),
+                )
+                .into();
+                continue;
+            },
+            keep_funded_checked = keep_funded_check => keep_funded_checked.map(Event::Backend),
+            _ = reorg_timer => {
+                reorg_check = Some(
+                    backend
+                        .run_background_task(Task::CheckReorgs)
+                        .boxed_local()
+                        .fuse(),
+                )
+                .into();
+                continue;
+            },
+            reorg_checked = reorg_check => reorg_checked.map(Event::Backend),
+            _ = keepalive_timer => {
+                keepalive = Some(
+                    backend
+                        .run_background_task(Task::WarmUpConnections)
+                        .boxed_local()
+                        .fuse(),
+                )
+                .into();
+                continue;
+            },
+            _ = keepalive => continue,
+            _ = chain_status_timer => {
+                chain_status_poll = Some(
+                    backend
+                        .run_background_task(Task::PollChainStatus)
+                        .boxed_local()
+                        .fuse(),
+                )
+                .into();
+                continue;
+            },
+            chain_status_polled = chain_status_poll => chain_status_polled.map(Event::Backend),
+            _ = idle_timer => {
+                if backend_task.is_terminated() && prefetch.is_terminated() {
+                    if let Some(task) = ui.prefetch_task() {
+                        prefetch =
+                            Some(backend.run_background_task(task).boxed_local().fuse()).into();
+                    }
+                }
+                continue;
+            },
+            _ = prefetch => continue,
+            form_checked = form_check => {
+                if let Some(event) = form_checked {
+                    ui.on_form_check_result(&event);
+                }
+                continue;
+            },
+            _ = progress_timer => {
+                ui.redraw();
+                continue;
+            },
         };
 
+        // User input and task results restart the idle countdown
+        if let (Some(delay), Some(Event::Key(_) | Event::Mouse(_) | Event::Backend(_))) =
+            (prefetch_idle_delay, &event)
+        {
+            idle_timer = Some(tokio::time::sleep(delay).boxed_local().fuse()).into();
+        }
+
         let ui_feedback = match event {
-            Some(event @ (Event::Backend(_) | Event::Key(_))) => {
+            Some(Event::Backend(backend_event)) => {
+                notifier.on_backend_event(&backend_event);
+                ui.on_event(backend.state(), Event::Backend(backend_event))
+                    .await
+            }
+            Some(event @ (Event::Key(_) | Event::Mouse(_))) => {
                 ui.on_event(backend.state(), event).await
             }
             Some(Event::RedrawDebounceTimeout) => {
@@ -134,9 +392,27 @@ async fn main() {
             _ => UiFeedback::None,
         };
 
+        if let Some(task) = ui.form_check_task() {
+            let check = backend.run_task(task);
+            form_check = Some(
+                async move {
+                    tokio::time::sleep(FORM_CHECK_DELAY).await;
+                    check.await
+                }
+                .boxed_local()
+                .fuse(),
+            )
+            .into();
+        }
+
         match ui_feedback {
             UiFeedback::Quit => active = false,
+            UiFeedback::SwitchProfile(profile) => {
+                profile_switch = Some(profile);
+                active = false;
+            }
             UiFeedback::ExecuteTask(task) => {
+                notifier.task_started(&task);
                 backend_task = Some(backend.run_task(task.clone()).boxed_local().fuse()).into();
                 ui.redraw();
             }
@@ -151,4 +427,7 @@ async fn main() {
             UiFeedback::None => (),
         }
     }
+
+    saved_options.extend(backend.saved_options().await);
+    profile_switch
 }
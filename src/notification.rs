@@ -0,0 +1,123 @@
+//! Notifications on long running tasks completion.
+//!
+//! Running a strategy may take a while, so the user can be pulled back to the
+//! explorer with a terminal bell or a desktop notification (OSC 9 escape
+//! sequence, supported by iTerm2, Windows Terminal, kitty and others). Strategy
+//! runs halted by the safety monitor are notified whatever their duration.
+
+use std::{
+    io::{self, Write},
+    time::{Duration, Instant},
+};
+
+use crate::{
+    backend::{BackendEvent, StrategyCompletionResult, StrategyTask, Task},
+    config::{Config, NotifyMethod},
+};
+
+/// Tracks the currently running task to decide whether its completion is worth
+/// a notification.
+pub struct Notifier {
+    threshold: Option<Duration>,
+    method: NotifyMethod,
+    running_task: Option<(Instant, String)>,
+}
+
+impl Notifier {
+    pub fn new(config: &Config) -> Self {
+        Notifier {
+            threshold: config.notify_after_seconds.map(Duration::from_secs),
+            method: config.notify_method,
+            running_task: None,
+        }
+    }
+
+    /// Remember when the task was started.
+    pub fn task_started(&mut self, task: &Task) {
+        if self.threshold.is_some() {
+            self.running_task = Some((Instant::now(), task_description(task)));
+        }
+    }
+
+    /// Fires a notification if the event finishes a task that took longer than
+    /// configured.
+    pub fn on_backend_event(&mut self, event: &BackendEvent) {
+        let Some(threshold) = self.threshold else {
+            return;
+        };
+
+        let outcome = match event {
+            BackendEvent::StrategyCompleted {
+                result: StrategyCompletionResult::PartiallyCompleted { .. },
+                ..
+            } => "halted",
+            BackendEvent::TaskCompleted {
+                execution_result: Err(_),
+                ..
+            }
+            | BackendEvent::StrategyError { .. } => "failed",
+            BackendEvent::TaskCompleted { .. }
+            | BackendEvent::TaskCompletedStateChange { .. }
+            | BackendEvent::StrategyCompleted { .. } => "completed",
+            _ => return,
+        };
+
+        if let Some((started, description)) = self.running_task.take() {
+            let elapsed = started.elapsed();
+            // A halted run is worth attention however long it took
+            if elapsed >= threshold || outcome == "halted" {
+                let message = format!(
+                    "Platform Explorer: {} {} after {}s",
+                    description,
+                    outcome,
+                    elapsed.as_secs()
+                );
+                if let Err(e) = self.notify(&message) {
+                    tracing::warn!("Failed to send notification: {}", e);
+                }
+            }
+        }
+    }
+
+    fn notify(&self, message: &str) -> io::Result<()> {
+        let mut stdout = io::stdout();
+        if matches!(self.method, NotifyMethod::Desktop | NotifyMethod::Both) {
+            // Control characters would terminate the sequence early
+            let message: String = message.chars().filter(|c| !c.is_control()).collect();
+            write!(stdout, "\x1b]9;{}\x07", message)?;
+        }
+        if matches!(self.method, NotifyMethod::Bell | NotifyMethod::Both) {
+            write!(stdout, "\x07")?;
+        }
+        stdout.flush()
+    }
+}
+
+fn task_description(task: &Task) -> String {
+    match task {
+        Task::Strategy(
+            StrategyTask::RunStrategy(strategy_name, ..)
+            | StrategyTask::ResumeStrategy(strategy_name, ..)
+            | StrategyTask::RunContractVariants(strategy_name, ..),
+        ) => {
+            format!("strategy '{}'", strategy_name)
+        }
+        Task::Strategy(_) => "strategy task".to_owned(),
+        Task::Identity(_) | Task::FetchIdentityById(..) | Task::KeepFunded(_) => {
+            "identity task".to_owned()
+        }
+        Task::Wallet(_) | Task::CheckReorgs | Task::AssetLock(_) => "wallet task".to_owned(),
+        Task::Contract(_) | Task::SchemaBuilder(_) => "contract task".to_owned(),
+        Task::Document(_) | Task::Bookmark(_) => "document task".to_owned(),
+        Task::PlatformInfo(_) => "platform info task".to_owned(),
+        Task::Network(_) => "network task".to_owned(),
+        Task::SetLogFilter(_) => "log filter update".to_owned(),
+        Task::Settings(_) => "settings update".to_owned(),
+        Task::Tutorial(_) => "tutorial update".to_owned(),
+        Task::Search(_) => "search".to_owned(),
+        Task::Export(_) => "export".to_owned(),
+        Task::FetchWithProof(_) => "fetch with proof".to_owned(),
+        Task::WarmUpConnections => "connections warm-up".to_owned(),
+        Task::PollChainStatus => "chain status poll".to_owned(),
+    }
+}
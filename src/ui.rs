@@ -4,27 +4,55 @@
 //! and form. At a time only one of them will occupy the application's UI, both
 //! explained in details in their modules.
 
+mod debounce;
+mod density;
 mod form;
+#[cfg(test)]
+mod harness;
+mod i18n;
+mod mouse;
+mod palette;
+mod pane;
+mod proofs;
+mod recent;
 mod screen;
 mod status_bar;
 pub(crate) mod views;
 
-use std::{mem, ops::Deref, time::Instant};
+use std::{io, mem, ops::Deref, time::Instant};
+
+use crossterm::event::{DisableMouseCapture, EnableMouseCapture};
 
 use dpp::identity::accessors::IdentityGettersV0;
+#[cfg(test)]
+use tuirealm::tui::{backend::TestBackend, Terminal};
 use tuirealm::{
+    event::{Key, KeyEvent, KeyModifiers},
     terminal::TerminalBridge,
-    tui::prelude::{Constraint, Direction, Layout},
+    tui::{
+        prelude::{Alignment, Constraint, Direction, Layout},
+        widgets::{Paragraph, Wrap},
+    },
+    Frame,
 };
 
 use self::{
+    debounce::{Debounced, DuplicateTaskFormController, TaskDebounce},
     form::{Form, FormController, FormStatus},
+    palette::{Palette, PalettePick, PaletteStatus, MAIN_MENU},
+    pane::{Pane, Split},
+    recent::{RecentEntities, RecentEntity, Switcher, SwitcherStatus},
     screen::{Screen, ScreenController, ScreenFeedback},
     status_bar::StatusBarState,
-    views::{main::MainScreenController, strategies::StrategiesScreenController},
+    views::{
+        contracts::ContractsScreenController, identities::IdentitiesScreenController,
+        main::MainScreenController, strategies::StrategiesScreenController,
+        wallet::WalletScreenController,
+    },
 };
 use crate::{
     backend::{AppState, AppStateUpdate, BackendEvent, Task},
+    config::{Config, StartupScreen},
     Event,
 };
 
@@ -43,16 +71,36 @@ impl IdentityBalance {
     }
 }
 
+/// Smaller terminals get a resize prompt instead of the UI.
+const MIN_WIDTH: u16 = 40;
+const MIN_HEIGHT: u16 = 12;
+
+/// Output the UI is drawn to.
+enum UiTerminal {
+    Bridge(TerminalBridge),
+    /// In-memory buffer for UI tests
+    #[cfg(test)]
+    Test(Terminal<TestBackend>),
+}
+
 /// TUI entry point that handles terminal events as well as terminal output,
 /// linking UI parts together.
 pub struct Ui {
     redraw_ts: Instant,
-    terminal: TerminalBridge,
+    terminal: UiTerminal,
     status_bar_state: StatusBarState,
     screen: Screen<Box<dyn ScreenController>>,
     form: Option<Form<Box<dyn FormController>>>,
+    /// Command palette shown over the screen
+    palette: Option<Palette>,
+    /// Recent entities switcher shown over the screen
+    switcher: Option<Switcher>,
+    recent: RecentEntities,
     blocked: bool,
     screen_stack: Vec<Screen<Box<dyn ScreenController>>>,
+    /// Second pane of a split workspace, the one above is the focused pane
+    split: Option<Split>,
+    debounce: TaskDebounce,
 }
 
 /// UI updates delivered to the main application loop.
@@ -60,30 +108,79 @@ pub enum UiFeedback {
     Redraw,
     Quit,
     ExecuteTask(Task),
+    /// Restart with another network profile
+    SwitchProfile(String),
     None,
 }
 
 impl Ui {
     pub fn redraw(&mut self) {
-        self.terminal
-            .raw_mut()
-            .draw(|frame| {
-                let layout = Layout::default()
-                    .direction(Direction::Vertical)
-                    .constraints([Constraint::Min(10), Constraint::Max(3)].as_ref())
-                    .split(frame.size());
-
-                if let Some(form) = &mut self.form {
-                    form.view(frame, layout[0]);
-                } else {
-                    self.screen.view(frame, layout[0])
-                };
-                self.status_bar_state.view(frame, layout[1]);
-            })
-            .expect("unable to draw to terminal");
+        let Ui {
+            terminal,
+            screen,
+            form,
+            palette,
+            switcher,
+            status_bar_state,
+            split,
+            ..
+        } = self;
+
+        let draw = |frame: &mut Frame| {
+            let size = frame.size();
+            if size.width < MIN_WIDTH || size.height < MIN_HEIGHT {
+                let prompt = format!(
+                    "{} {}x{} ({}x{})",
+                    i18n::tr("Terminal is too small, please resize it to at least"),
+                    MIN_WIDTH,
+                    MIN_HEIGHT,
+                    size.width,
+                    size.height
+                );
+                frame.render_widget(
+                    Paragraph::new(prompt)
+                        .alignment(Alignment::Center)
+                        .wrap(Wrap { trim: true }),
+                    size,
+                );
+                return;
+            }
+
+            let layout = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Min(10), Constraint::Max(3)].as_ref())
+                .split(frame.size());
+
+            let screen_area = match split {
+                Some(split) => {
+                    let (focused_area, other_area) =
+                        split.layout(frame, layout[0], status_bar_state.breadcrumbs());
+                    split.other.screen.view(frame, other_area);
+                    focused_area
+                }
+                None => layout[0],
+            };
+            if let Some(switcher) = switcher {
+                switcher.view(frame, screen_area);
+            } else if let Some(palette) = palette {
+                palette.view(frame, screen_area);
+            } else if let Some(form) = form {
+                form.view(frame, screen_area);
+            } else {
+                screen.view(frame, screen_area)
+            };
+            status_bar_state.view(frame, layout[1]);
+        };
+
+        match terminal {
+            UiTerminal::Bridge(terminal) => terminal.raw_mut().draw(draw),
+            #[cfg(test)]
+            UiTerminal::Test(terminal) => terminal.draw(draw),
+        }
+        .expect("unable to draw to terminal");
     }
 
-    pub fn new(initial_identity_balance: Option<IdentityBalance>) -> Self {
+    pub fn new(initial_identity_balance: Option<IdentityBalance>, config: &Config) -> Self {
         let mut terminal = TerminalBridge::new().expect("cannot initialize terminal app");
         terminal
             .enter_alternate_screen()
@@ -91,6 +188,23 @@ impl Ui {
         terminal
             .enable_raw_mode()
             .expect("cannot enable terminal raw mode");
+        crossterm::execute!(io::stdout(), EnableMouseCapture)
+            .expect("cannot enable terminal mouse capture");
+
+        Self::with_terminal(
+            UiTerminal::Bridge(terminal),
+            initial_identity_balance,
+            config,
+        )
+    }
+
+    fn with_terminal(
+        terminal: UiTerminal,
+        initial_identity_balance: Option<IdentityBalance>,
+        config: &Config,
+    ) -> Self {
+        i18n::set_language(config.language);
+        density::set_density(config.display_density);
 
         let main_screen_controller = MainScreenController::new();
 
@@ -98,6 +212,7 @@ impl Ui {
             .map(StatusBarState::with_balance)
             .unwrap_or_default();
 
+        status_bar_state.set_profile(config.profile_name().to_owned());
         status_bar_state.add_child(main_screen_controller.name());
 
         let screen = Screen::new(Box::new(main_screen_controller) as Box<dyn ScreenController>);
@@ -108,14 +223,249 @@ impl Ui {
             status_bar_state,
             screen,
             form: None,
+            palette: None,
+            switcher: None,
+            recent: RecentEntities::default(),
             blocked: false,
             screen_stack: Vec::new(),
+            split: None,
+            debounce: TaskDebounce::new(
+                config.duplicate_task_window(),
+                config.duplicate_task_action,
+            ),
         };
 
         ui.redraw();
         ui
     }
 
+    /// Opens the screen to start on above the main menu, so going back leads
+    /// to the menu as usual.
+    pub async fn open_startup_screen(
+        &mut self,
+        app_state: impl Deref<Target = AppState>,
+        startup_screen: StartupScreen,
+    ) {
+        let builder = match startup_screen {
+            StartupScreen::Main => return,
+            StartupScreen::Contracts => ContractsScreenController::builder(),
+            StartupScreen::Identities => IdentitiesScreenController::builder(),
+            StartupScreen::Wallet => WalletScreenController::builder(),
+            StartupScreen::Strategies => StrategiesScreenController::builder(),
+        };
+        let controller = builder(app_state.deref()).await;
+        self.status_bar_state.add_child(controller.name());
+        let old_screen = mem::replace(&mut self.screen, Screen::new(controller));
+        self.screen_stack.push(old_screen);
+        self.redraw();
+    }
+
+    /// Background task of the current screen to run while the UI is idle,
+    /// nothing is prefetched behind a form or a blocking task.
+    pub fn prefetch_task(&self) -> Option<Task> {
+        if self.form.is_some() || self.blocked {
+            return None;
+        }
+        self.screen.controller.prefetch_task()
+    }
+
+    /// Task checking the input of the open form as it's typed.
+    pub fn form_check_task(&mut self) -> Option<Task> {
+        self.form.as_mut()?.take_check_task()
+    }
+
+    /// Passes the result of a form check to the form if it's still open.
+    pub fn on_form_check_result(&mut self, event: &BackendEvent) {
+        let (
+            Some(form),
+            BackendEvent::TaskCompleted {
+                task,
+                execution_result,
+            },
+        ) = (&mut self.form, event)
+        else {
+            return;
+        };
+        if form.on_check_result(task, execution_result) {
+            self.redraw();
+        }
+    }
+
+    /// Whether the status bar shows a toast, redrawn periodically until it
+    /// expires.
+    pub fn has_toast(&self) -> bool {
+        self.status_bar_state.toast().is_some()
+    }
+
+    fn copy_to_clipboard(&mut self, text: String) {
+        let message = match cli_clipboard::set_contents(text) {
+            Ok(()) => "Copied".to_owned(),
+            Err(e) => format!("Failed to copy to clipboard: {}", e),
+        };
+        self.status_bar_state.show_toast(message);
+    }
+
+    /// Splits the workspace with a new pane on the main menu which takes the
+    /// focus, or closes the other pane if already split.
+    fn toggle_split(&mut self) {
+        if self.split.take().is_none() {
+            self.split = Some(Split {
+                other: Pane::main_menu(),
+                focused_left: true,
+            });
+            self.switch_pane();
+        }
+    }
+
+    fn switch_pane(&mut self) {
+        let Some(split) = &mut self.split else {
+            return;
+        };
+        mem::swap(&mut self.screen, &mut split.other.screen);
+        mem::swap(&mut self.screen_stack, &mut split.other.screen_stack);
+        self.status_bar_state
+            .swap_breadcrumbs(&mut split.other.breadcrumbs);
+        split.focused_left = !split.focused_left;
+    }
+
+    /// Going back from the first screen of a pane closes it instead of
+    /// quitting, returns whether there was another pane to focus.
+    fn close_focused_pane(&mut self) -> bool {
+        if self.split.is_none() {
+            return false;
+        }
+        self.switch_pane();
+        self.split = None;
+        true
+    }
+
+    /// Passes the task to execution unless it's a duplicate of a recent one.
+    fn submit_task(&mut self, task: Task, block: bool) -> UiFeedback {
+        match self.debounce.check(&task) {
+            Debounced::Run => {
+                if block {
+                    self.status_bar_state.block();
+                    self.blocked = true;
+                }
+                UiFeedback::ExecuteTask(task)
+            }
+            Debounced::Confirm => {
+                self.form = Some(Form::new(Box::new(DuplicateTaskFormController::new(
+                    task, block,
+                ))));
+                UiFeedback::Redraw
+            }
+            Debounced::Coalesce => UiFeedback::None,
+        }
+    }
+
+    /// Applies what the screen asked for in return of an event.
+    async fn on_screen_feedback(
+        &mut self,
+        app_state: &AppState,
+        feedback: ScreenFeedback,
+    ) -> UiFeedback {
+        match feedback {
+            ScreenFeedback::NextScreen(controller_builder) => {
+                let controller = controller_builder(app_state).await;
+                self.status_bar_state.add_child(controller.name());
+                let old_screen = mem::replace(&mut self.screen, Screen::new(controller));
+                self.screen_stack.push(old_screen);
+                UiFeedback::Redraw
+            }
+            ScreenFeedback::PreviousScreen => {
+                self.status_bar_state.to_parent();
+
+                let current_screen_name = self.screen.controller.name();
+                let previous_screen_name = self.screen_stack.last().map(|s| s.controller.name());
+
+                if current_screen_name == "Strategy" && previous_screen_name == Some("Strategies") {
+                    // Rebuild the StrategiesScreenController when navigating back from
+                    // SelectedStrategyScreenController
+                    let new_controller = StrategiesScreenController::new(app_state).await;
+                    self.screen_stack.pop(); // Remove the old StrategiesScreenController from the stack
+                    self.screen = Screen::new(Box::new(new_controller));
+                } else {
+                    // Regular back navigation
+                    if let Some(previous_screen) = self.screen_stack.pop() {
+                        self.screen = previous_screen;
+                    } else if !self.close_focused_pane() {
+                        // Exit if no previous screen
+                        return UiFeedback::Quit;
+                    }
+                }
+
+                UiFeedback::Redraw
+            }
+            ScreenFeedback::Form(controller) => {
+                self.form = Some(Form::new(controller));
+                UiFeedback::Redraw
+            }
+            ScreenFeedback::FormThenNextScreen { form, screen } => {
+                self.form = Some(Form::new(form));
+
+                let controller = screen(app_state).await;
+                self.status_bar_state.add_child(controller.name());
+                let old_screen = mem::replace(&mut self.screen, Screen::new(controller));
+                self.screen_stack.push(old_screen);
+
+                UiFeedback::Redraw
+            }
+            ScreenFeedback::Task { task, block } => self.submit_task(task, block),
+            ScreenFeedback::Redraw => UiFeedback::Redraw,
+            ScreenFeedback::Quit => UiFeedback::Quit,
+            ScreenFeedback::SwitchProfile(profile) => UiFeedback::SwitchProfile(profile),
+            ScreenFeedback::Copy(text) => {
+                self.copy_to_clipboard(text);
+                UiFeedback::Redraw
+            }
+            ScreenFeedback::None => UiFeedback::None,
+        }
+    }
+
+    /// Presses the keys of the palette pick, on a new main menu screen if the
+    /// pick comes from the menus. Keys stop at the first one that doesn't lead
+    /// to another screen, as it either runs the action or isn't available.
+    async fn run_palette_pick(&mut self, app_state: &AppState, pick: PalettePick) -> UiFeedback {
+        if pick.from_main_menu && self.screen.controller.name() != MAIN_MENU {
+            let controller = Box::new(MainScreenController::new()) as Box<dyn ScreenController>;
+            self.status_bar_state.add_child(controller.name());
+            let old_screen = mem::replace(&mut self.screen, Screen::new(controller));
+            self.screen_stack.push(old_screen);
+        }
+        for key in pick.keys {
+            let feedback = self.screen.on_event(&Event::Key(key));
+            let next_screen = matches!(feedback, ScreenFeedback::NextScreen(_));
+            let ui_feedback = self.on_screen_feedback(app_state, feedback).await;
+            if !next_screen {
+                return match ui_feedback {
+                    UiFeedback::None => UiFeedback::Redraw,
+                    ui_feedback => ui_feedback,
+                };
+            }
+        }
+        UiFeedback::Redraw
+    }
+
+    /// Opens the picked entity on top of the current screen, entities gone
+    /// since they were viewed are dropped from the list.
+    async fn open_recent(&mut self, app_state: &AppState, entity: RecentEntity) -> UiFeedback {
+        let Some(feedbacks) = entity.clone().open(app_state).await else {
+            self.recent.remove(&entity);
+            self.status_bar_state
+                .show_toast("No longer available".to_owned());
+            return UiFeedback::Redraw;
+        };
+        let mut ui_feedback = UiFeedback::None;
+        for feedback in feedbacks {
+            ui_feedback = self.on_screen_feedback(app_state, feedback).await;
+        }
+        match ui_feedback {
+            UiFeedback::None => UiFeedback::Redraw,
+            ui_feedback => ui_feedback,
+        }
+    }
+
     pub async fn on_event<'s>(
         &mut self,
         app_state: impl Deref<Target = AppState>,
@@ -137,6 +487,25 @@ impl Ui {
             redraw = true;
         }
 
+        if let Event::Backend(
+            BackendEvent::TaskCompleted {
+                task,
+                execution_result,
+            }
+            | BackendEvent::TaskCompletedStateChange {
+                task,
+                execution_result,
+                ..
+            },
+        ) = &event
+        {
+            app_state
+                .task_history
+                .lock()
+                .await
+                .record_result(task, execution_result.is_ok());
+        }
+
         // A special treatment for loaded identity app state update: status bar should
         // be updated as well
         if let Event::Backend(
@@ -190,6 +559,29 @@ impl Ui {
             self.status_bar_state.set_balance_error();
         }
 
+        // Chain status is polled in background and only shown in the status bar
+        if let Event::Backend(BackendEvent::AppStateUpdated(AppStateUpdate::ChainStatus(
+            chain_status,
+        ))) = &event
+        {
+            self.status_bar_state.set_chain_status(chain_status.clone());
+            redraw = true;
+        }
+
+        // Tutorial progress is driven by backend events, so the hint is refreshed on
+        // each of them
+        if let Event::Backend(_) = &event {
+            self.status_bar_state.set_tutorial_hint(
+                app_state
+                    .tutorial
+                    .lock()
+                    .await
+                    .as_ref()
+                    .map(|tutorial| tutorial.status_hint()),
+            );
+            redraw = true;
+        }
+
         // Update all the stacked screens with the relevant state
         if let Event::Backend(
             BackendEvent::AppStateUpdated(_) | BackendEvent::TaskCompletedStateChange { .. },
@@ -200,19 +592,48 @@ impl Ui {
             }
         }
 
+        // The other pane of a split workspace keeps following backend events
+        if let (Some(split), Event::Backend(_)) = (&mut self.split, &event) {
+            split.other.on_backend_event(&event);
+            redraw = true;
+        }
+
         if self.blocked {
             return UiFeedback::None;
         }
 
-        let ui_feedback = if let (Some(form), Event::Key(event)) = (&mut self.form, &event) {
+        let ui_feedback = if let (Some(switcher), Event::Key(event)) = (&mut self.switcher, &event)
+        {
+            match switcher.on_event(*event) {
+                SwitcherStatus::Picked(entity) => {
+                    self.switcher = None;
+                    self.open_recent(app_state.deref(), entity).await
+                }
+                SwitcherStatus::Redraw => UiFeedback::Redraw,
+                SwitcherStatus::None => UiFeedback::None,
+                SwitcherStatus::Exit => {
+                    self.switcher = None;
+                    UiFeedback::Redraw
+                }
+            }
+        } else if let (Some(palette), Event::Key(event)) = (&mut self.palette, &event) {
+            match palette.on_event(*event) {
+                PaletteStatus::Picked(pick) => {
+                    self.palette = None;
+                    self.run_palette_pick(app_state.deref(), pick).await
+                }
+                PaletteStatus::Redraw => UiFeedback::Redraw,
+                PaletteStatus::None => UiFeedback::None,
+                PaletteStatus::Exit => {
+                    self.palette = None;
+                    UiFeedback::Redraw
+                }
+            }
+        } else if let (Some(form), Event::Key(event)) = (&mut self.form, &event) {
             match form.on_event(*event) {
                 FormStatus::Done { task, block } => {
                     self.form = None;
-                    if block {
-                        self.status_bar_state.block();
-                        self.blocked = true;
-                    }
-                    UiFeedback::ExecuteTask(task)
+                    self.submit_task(task, block)
                 }
                 FormStatus::NextScreen(controller_builder) => {
                     self.form = None;
@@ -227,7 +648,7 @@ impl Ui {
                     self.status_bar_state.to_parent();
                     if let Some(previous_screen) = self.screen_stack.pop() {
                         self.screen = previous_screen;
-                    } else {
+                    } else if !self.close_focused_pane() {
                         // Exit if no previous screen
                         return UiFeedback::Quit;
                     }
@@ -237,72 +658,67 @@ impl Ui {
                 FormStatus::None => UiFeedback::None,
                 FormStatus::Exit => {
                     self.form = None;
+                    self.debounce.cancel_confirmation();
                     UiFeedback::Redraw
                 }
             }
+        } else if matches!(event, Event::Mouse(_))
+            && (self.switcher.is_some() || self.palette.is_some() || self.form.is_some())
+        {
+            // The mouse is for screens only, overlays are keyboard driven
+            UiFeedback::None
+        } else if let Event::Key(KeyEvent {
+            code: Key::Char('k'),
+            modifiers: KeyModifiers::CONTROL,
+        }) = &event
+        {
+            // The palette opens on any screen but not in forms
+            self.palette = Some(Palette::new(&self.screen.controller));
+            UiFeedback::Redraw
+        } else if let Event::Key(
+            KeyEvent {
+                code: Key::Tab,
+                modifiers: KeyModifiers::CONTROL,
+            }
+            | KeyEvent {
+                code: Key::Function(4),
+                modifiers: KeyModifiers::NONE,
+            },
+        ) = &event
+        {
+            // The switcher opens on any screen but not in forms, F4 is for terminals
+            // sending Ctrl-Tab as a plain Tab
+            self.switcher = Some(Switcher::new(&self.recent));
+            UiFeedback::Redraw
+        } else if let Event::Key(KeyEvent {
+            code: Key::Function(key @ (2 | 3)),
+            modifiers: KeyModifiers::NONE,
+        }) = &event
+        {
+            // Workspace keys work on any screen but not in forms
+            if *key == 2 {
+                self.toggle_split();
+            } else {
+                self.switch_pane();
+            }
+            UiFeedback::Redraw
         } else {
-            match self.screen.on_event(&event) {
-                ScreenFeedback::NextScreen(controller_builder) => {
-                    let controller = controller_builder(app_state.deref()).await;
-                    self.status_bar_state.add_child(controller.name());
-                    let old_screen = mem::replace(&mut self.screen, Screen::new(controller));
-                    self.screen_stack.push(old_screen);
-                    UiFeedback::Redraw
-                }
-                ScreenFeedback::PreviousScreen => {
-                    self.status_bar_state.to_parent();
-
-                    let current_screen_name = self.screen.controller.name();
-                    let previous_screen_name =
-                        self.screen_stack.last().map(|s| s.controller.name());
-
-                    if current_screen_name == "Strategy"
-                        && previous_screen_name == Some("Strategies")
-                    {
-                        // Rebuild the StrategiesScreenController when navigating back from
-                        // SelectedStrategyScreenController
-                        let new_controller =
-                            StrategiesScreenController::new(app_state.deref()).await;
-                        self.screen_stack.pop(); // Remove the old StrategiesScreenController from the stack
-                        self.screen = Screen::new(Box::new(new_controller));
-                    } else {
-                        // Regular back navigation
-                        if let Some(previous_screen) = self.screen_stack.pop() {
-                            self.screen = previous_screen;
-                        } else {
-                            // Exit if no previous screen
-                            return UiFeedback::Quit;
-                        }
-                    }
-
-                    UiFeedback::Redraw
-                }
-                ScreenFeedback::Form(controller) => {
-                    self.form = Some(Form::new(controller));
-                    UiFeedback::Redraw
-                }
-                ScreenFeedback::FormThenNextScreen { form, screen } => {
-                    self.form = Some(Form::new(form));
+            let feedback = self.screen.on_event(&event);
+            self.on_screen_feedback(app_state.deref(), feedback).await
+        };
 
-                    let controller = screen(app_state.deref()).await;
-                    self.status_bar_state.add_child(controller.name());
-                    let old_screen = mem::replace(&mut self.screen, Screen::new(controller));
-                    self.screen_stack.push(old_screen);
+        // Entities shown on the focused screen are listed for the switcher
+        if let Some(entity) = self.screen.controller.take_viewed_entity() {
+            self.recent.visit(entity);
+        }
 
-                    UiFeedback::Redraw
-                }
-                ScreenFeedback::Task { task, block } => {
-                    if block {
-                        self.status_bar_state.block();
-                        self.blocked = true;
-                    }
-                    UiFeedback::ExecuteTask(task)
-                }
-                ScreenFeedback::Redraw => UiFeedback::Redraw,
-                ScreenFeedback::Quit => UiFeedback::Quit,
-                ScreenFeedback::None => UiFeedback::None,
-            }
-        };
+        if let UiFeedback::ExecuteTask(task) = &ui_feedback {
+            app_state
+                .task_history
+                .lock()
+                .await
+                .record(task, self.blocked);
+        }
 
         if matches!(ui_feedback, UiFeedback::None) && redraw {
             UiFeedback::Redraw
@@ -314,8 +730,15 @@ impl Ui {
 
 impl Drop for Ui {
     fn drop(&mut self) {
-        let _ = self.terminal.leave_alternate_screen();
-        let _ = self.terminal.disable_raw_mode();
-        let _ = self.terminal.clear_screen();
+        match &mut self.terminal {
+            UiTerminal::Bridge(terminal) => {
+                let _ = crossterm::execute!(io::stdout(), DisableMouseCapture);
+                let _ = terminal.leave_alternate_screen();
+                let _ = terminal.disable_raw_mode();
+                let _ = terminal.clear_screen();
+            }
+            #[cfg(test)]
+            UiTerminal::Test(_) => {}
+        }
     }
 }
@@ -0,0 +1,134 @@
+//! Protection from accidental double submission of tasks.
+//!
+//! Only tasks sending transactions are checked, as running those twice spends
+//! twice. A task is a duplicate if an identical one (same variant and
+//! parameters) was submitted within the configured window. Depending on
+//! configuration the duplicate is either dropped or the user is asked whether
+//! to run it again.
+
+use std::{
+    borrow::Cow,
+    collections::VecDeque,
+    time::{Duration, Instant},
+};
+
+use tuirealm::{event::KeyEvent, tui::prelude::Rect, Frame};
+
+use super::form::{FormController, FormStatus, Input, InputStatus, SelectInput};
+use crate::{backend::Task, config::DuplicateTaskAction};
+
+/// Outcome of a task submission check.
+pub(super) enum Debounced {
+    Run,
+    Confirm,
+    Coalesce,
+}
+
+pub(super) struct TaskDebounce {
+    window: Option<Duration>,
+    action: DuplicateTaskAction,
+    /// Fingerprints of recently submitted tasks with submission times
+    recent: VecDeque<(u64, Instant)>,
+    /// Fingerprint of a duplicate the user is asked about
+    awaiting_confirmation: Option<u64>,
+}
+
+impl TaskDebounce {
+    pub(super) fn new(window: Option<Duration>, action: DuplicateTaskAction) -> Self {
+        TaskDebounce {
+            window,
+            action,
+            recent: VecDeque::new(),
+            awaiting_confirmation: None,
+        }
+    }
+
+    pub(super) fn check(&mut self, task: &Task) -> Debounced {
+        let Some(window) = self.window.filter(|_| task.sends_transaction()) else {
+            return Debounced::Run;
+        };
+
+        let now = Instant::now();
+        while matches!(self.recent.front(), Some((_, at)) if now.duration_since(*at) > window) {
+            self.recent.pop_front();
+        }
+
+        let fingerprint = task.fingerprint();
+        if self.awaiting_confirmation == Some(fingerprint) {
+            self.awaiting_confirmation = None;
+            self.recent.push_back((fingerprint, now));
+            return Debounced::Run;
+        }
+
+        let is_duplicate = self.recent.iter().any(|(recent, _)| *recent == fingerprint);
+
+        match (is_duplicate, self.action) {
+            (false, _) => {
+                self.recent.push_back((fingerprint, now));
+                Debounced::Run
+            }
+            (true, DuplicateTaskAction::Confirm) => {
+                self.awaiting_confirmation = Some(fingerprint);
+                Debounced::Confirm
+            }
+            (true, DuplicateTaskAction::Coalesce) => {
+                tracing::warn!("dropped duplicate {} task", task.kind());
+                Debounced::Coalesce
+            }
+        }
+    }
+
+    /// Forgets the duplicate the user was asked about as it was declined.
+    pub(super) fn cancel_confirmation(&mut self) {
+        self.awaiting_confirmation = None;
+    }
+}
+
+pub(super) struct DuplicateTaskFormController {
+    task: Task,
+    block: bool,
+    input: SelectInput<String>,
+}
+
+impl DuplicateTaskFormController {
+    pub(super) fn new(task: Task, block: bool) -> Self {
+        DuplicateTaskFormController {
+            task,
+            block,
+            input: SelectInput::new(vec!["No".to_owned(), "Yes".to_owned()]),
+        }
+    }
+}
+
+impl FormController for DuplicateTaskFormController {
+    fn on_event(&mut self, event: KeyEvent) -> FormStatus {
+        match self.input.on_event(event) {
+            InputStatus::Done(answer) if answer == "Yes" => FormStatus::Done {
+                task: self.task.clone(),
+                block: self.block,
+            },
+            InputStatus::Done(_) => FormStatus::Exit,
+            status => status.into(),
+        }
+    }
+
+    fn form_name(&self) -> &'static str {
+        "Duplicate task"
+    }
+
+    fn step_view(&mut self, frame: &mut Frame, area: Rect) {
+        self.input.view(frame, area)
+    }
+
+    fn step_name(&self) -> Cow<'_, str> {
+        "Same task was submitted moments ago, run it again?".into()
+    }
+
+    fn step_index(&self) -> u8 {
+        0
+    }
+
+    fn steps_number(&self) -> u8 {
+        1
+    }
+}
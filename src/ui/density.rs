@@ -0,0 +1,30 @@
+//! Display density of lists and tables.
+//! In compact density list, table and info widgets are drawn without borders
+//! and the command pallet takes only the rows its keys need, so long lists
+//! show more rows on short terminals.
+
+use std::sync::RwLock;
+
+use tuirealm::props::{BorderSides, Borders};
+
+use crate::config::Density;
+
+static DENSITY: RwLock<Density> = RwLock::new(Density::Comfortable);
+
+pub(crate) fn set_density(density: Density) {
+    *DENSITY.write().expect("density lock poisoned") = density;
+}
+
+pub(crate) fn density() -> Density {
+    *DENSITY.read().expect("density lock poisoned")
+}
+
+pub(crate) fn is_compact() -> bool {
+    density() == Density::Compact
+}
+
+/// Widget borders for the current density, `compact` sides are usually a
+/// separator from a neighbouring widget.
+pub(crate) fn borders(comfortable: BorderSides, compact: BorderSides) -> Borders {
+    Borders::default().sides(if is_compact() { compact } else { comfortable })
+}
@@ -1,9 +1,13 @@
 //! Form component defintion.
 
+mod schema;
 mod utils;
 mod widgets;
 
-use std::ops::{Deref, DerefMut};
+use std::{
+    borrow::Cow,
+    ops::{Deref, DerefMut},
+};
 
 use tuirealm::{
     event::KeyEvent,
@@ -13,14 +17,15 @@ use tuirealm::{
 };
 
 pub(crate) use self::{
+    schema::SchemaForm,
     utils::{ComposedInput, Field},
     widgets::{
         select::SelectInput,
         text::{parsers, TextInput},
     },
 };
-use super::screen::ScreenControllerBuilder;
-use crate::backend::Task;
+use super::{density, i18n::tr, screen::ScreenControllerBuilder};
+use crate::backend::{CompletedTaskPayload, Task};
 
 /// Trait of every component suitable for processing user input.
 /// Serves as a building block of a form as one may require several of them
@@ -75,13 +80,30 @@ impl<C: FormController> Form<C> {
         self.controller.on_event(event)
     }
 
+    pub(crate) fn take_check_task(&mut self) -> Option<Task> {
+        self.controller.take_check_task()
+    }
+
+    pub(crate) fn on_check_result(
+        &mut self,
+        task: &Task,
+        execution_result: &Result<CompletedTaskPayload, String>,
+    ) -> bool {
+        self.controller.on_check_result(task, execution_result)
+    }
+
     pub(crate) fn view(&mut self, frame: &mut Frame, area: Rect) {
+        let sides = if density::is_compact() {
+            BorderSides::TOP
+        } else {
+            BorderSides::ALL
+        };
         let block = Block::new()
-            .borders(BorderSides::ALL)
+            .borders(sides)
             .title(format!(
                 "{}: {} [{} / {}]",
-                self.controller.form_name(),
-                self.controller.step_name(),
+                tr(self.controller.form_name()),
+                tr(&self.controller.step_name()),
                 self.controller.step_index() + 1,
                 self.controller.steps_number()
             ))
@@ -118,13 +140,28 @@ pub(crate) trait FormController {
     fn step_view(&mut self, frame: &mut Frame, area: Rect);
 
     /// Current step title
-    fn step_name(&self) -> &'static str;
+    fn step_name(&self) -> Cow<'_, str>;
 
     /// Current step index
     fn step_index(&self) -> u8;
 
     /// Number of all form steps
     fn steps_number(&self) -> u8;
+
+    /// Task checking the input as it's typed, taken once per change and run
+    /// in background, a newer check replaces a pending one
+    fn take_check_task(&mut self) -> Option<Task> {
+        None
+    }
+
+    /// Process the result of a check task, returns if a redraw is needed
+    fn on_check_result(
+        &mut self,
+        _task: &Task,
+        _execution_result: &Result<CompletedTaskPayload, String>,
+    ) -> bool {
+        false
+    }
 }
 
 impl FormController for Box<dyn FormController> {
@@ -140,7 +177,7 @@ impl FormController for Box<dyn FormController> {
         self.deref().form_name()
     }
 
-    fn step_name(&self) -> &'static str {
+    fn step_name(&self) -> Cow<'_, str> {
         self.deref().step_name()
     }
 
@@ -151,4 +188,16 @@ impl FormController for Box<dyn FormController> {
     fn steps_number(&self) -> u8 {
         self.deref().steps_number()
     }
+
+    fn take_check_task(&mut self) -> Option<Task> {
+        self.deref_mut().take_check_task()
+    }
+
+    fn on_check_result(
+        &mut self,
+        task: &Task,
+        execution_result: &Result<CompletedTaskPayload, String>,
+    ) -> bool {
+        self.deref_mut().on_check_result(task, execution_result)
+    }
 }
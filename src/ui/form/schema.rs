@@ -0,0 +1,420 @@
+//! Forms generated from JSON schemas.
+//!
+//! Document type schemas describe everything needed to ask a user for a
+//! document, so instead of writing a form per use case one step is made per
+//! schema property, nested objects are walked down to their properties. The
+//! result is a platform value map ready to build a document from. To edit a
+//! document each step starts with its current value.
+
+use dpp::{
+    platform_value::{string_encoding::Encoding, Value, ValueMap},
+    prelude::Identifier,
+};
+use serde_json::Value as JsonValue;
+use tuirealm::{event::KeyEvent, tui::prelude::Rect, Frame};
+
+use super::{parsers::TextInputParser, Input, InputStatus, SelectInput, TextInput};
+
+const IDENTIFIER_MEDIA_TYPE: &str = "application/x.dash.dpp.identifier";
+
+/// Label of the choice to leave an optional property unset.
+const UNSET_CHOICE: &str = "<unset>";
+
+#[derive(Debug, Clone)]
+enum FieldKind {
+    String {
+        min_length: Option<u64>,
+        max_length: Option<u64>,
+    },
+    Integer,
+    Number,
+    Boolean,
+    Enum(Vec<JsonValue>),
+    Identifier,
+    ByteArray,
+    /// Array of simple items, entered as a JSON array
+    Array(Box<FieldKind>),
+    /// Anything else is entered as JSON
+    Json,
+}
+
+impl FieldKind {
+    fn from_schema(schema: &JsonValue) -> Self {
+        if let Some(variants) = schema.get("enum").and_then(JsonValue::as_array) {
+            return FieldKind::Enum(variants.clone());
+        }
+        match schema.get("type").and_then(JsonValue::as_str) {
+            Some("string") => FieldKind::String {
+                min_length: schema.get("minLength").and_then(JsonValue::as_u64),
+                max_length: schema.get("maxLength").and_then(JsonValue::as_u64),
+            },
+            Some("integer") => FieldKind::Integer,
+            Some("number") => FieldKind::Number,
+            Some("boolean") => FieldKind::Boolean,
+            Some("array") if schema.get("byteArray") == Some(&JsonValue::Bool(true)) => {
+                if schema.get("contentMediaType").and_then(JsonValue::as_str)
+                    == Some(IDENTIFIER_MEDIA_TYPE)
+                {
+                    FieldKind::Identifier
+                } else {
+                    FieldKind::ByteArray
+                }
+            }
+            Some("array") => match schema.get("items").map(FieldKind::from_schema) {
+                Some(FieldKind::Json | FieldKind::Array(_)) | None => FieldKind::Json,
+                Some(item) => FieldKind::Array(Box::new(item)),
+            },
+            _ => FieldKind::Json,
+        }
+    }
+
+    fn placeholder(&self) -> &'static str {
+        match self {
+            FieldKind::String { .. } => "Text",
+            FieldKind::Integer => "Integer",
+            FieldKind::Number => "Number",
+            FieldKind::Identifier => "Identifier (base58)",
+            FieldKind::ByteArray => "Bytes (hex)",
+            FieldKind::Array(_) => "JSON array",
+            FieldKind::Boolean | FieldKind::Enum(_) | FieldKind::Json => "JSON",
+        }
+    }
+
+    /// Text to edit a current value with, as it would be entered.
+    fn format(&self, value: &Value) -> String {
+        match (self, value) {
+            (FieldKind::String { .. }, Value::Text(text)) => text.clone(),
+            (FieldKind::Identifier, value) => value
+                .to_identifier()
+                .map(|id| id.to_string(Encoding::Base58))
+                .unwrap_or_default(),
+            (FieldKind::ByteArray, Value::Bytes(bytes)) => hex::encode(bytes),
+            (FieldKind::ByteArray, Value::Bytes32(bytes)) => hex::encode(bytes),
+            (FieldKind::Array(item), Value::Array(values)) => {
+                let items = values
+                    .iter()
+                    .map(|value| match item.as_ref() {
+                        FieldKind::String { .. } | FieldKind::Identifier | FieldKind::ByteArray => {
+                            JsonValue::String(item.format(value))
+                        }
+                        _ => serde_json::to_value(value).unwrap_or_default(),
+                    })
+                    .collect();
+                JsonValue::Array(items).to_string()
+            }
+            (_, value) => serde_json::to_string(value).unwrap_or_default(),
+        }
+    }
+
+    fn parse(&self, input: &str) -> Result<Value, String> {
+        match self {
+            FieldKind::String {
+                min_length,
+                max_length,
+            } => {
+                let length = input.chars().count() as u64;
+                if let Some(min) = min_length.filter(|min| length < *min) {
+                    return Err(format!("At least {} characters", min));
+                }
+                if let Some(max) = max_length.filter(|max| length > *max) {
+                    return Err(format!("At most {} characters", max));
+                }
+                Ok(Value::Text(input.to_owned()))
+            }
+            FieldKind::Integer => input
+                .trim()
+                .parse()
+                .map(Value::I64)
+                .map_err(|_| "Cannot parse as an integer".to_owned()),
+            FieldKind::Number => input
+                .trim()
+                .parse()
+                .map(Value::Float)
+                .map_err(|_| "Cannot parse as a number".to_owned()),
+            FieldKind::Identifier => Identifier::from_string(input.trim(), Encoding::Base58)
+                .map(|id| Value::Identifier(id.to_buffer()))
+                .map_err(|_| "Cannot parse as a base58 identifier".to_owned()),
+            FieldKind::ByteArray => hex::decode(input.trim())
+                .map(Value::Bytes)
+                .map_err(|_| "Cannot parse as hex bytes".to_owned()),
+            FieldKind::Array(_) if input.trim().is_empty() => Ok(Value::Array(Vec::new())),
+            FieldKind::Array(item) => serde_json::from_str::<Vec<JsonValue>>(input)
+                .map_err(|e| format!("Invalid JSON array: {}", e))?
+                .into_iter()
+                .map(|value| match (item.as_ref(), value) {
+                    (FieldKind::Boolean | FieldKind::Enum(_) | FieldKind::Json, value) => {
+                        Ok(Value::from(value))
+                    }
+                    (item, JsonValue::String(text)) => item.parse(&text),
+                    (item, value) => item.parse(&value.to_string()),
+                })
+                .collect::<Result<_, _>>()
+                .map(Value::Array),
+            FieldKind::Boolean | FieldKind::Enum(_) | FieldKind::Json => {
+                serde_json::from_str::<JsonValue>(input)
+                    .map(Value::from)
+                    .map_err(|e| format!("Invalid JSON: {}", e))
+            }
+        }
+    }
+}
+
+/// A schema property to fill, nested ones have their full path.
+#[derive(Debug, Clone)]
+struct SchemaField {
+    path: Vec<String>,
+    name: String,
+    kind: FieldKind,
+    required: bool,
+}
+
+/// Collects fields of object schema properties in their declared positions.
+fn collect_fields(schema: &JsonValue, prefix: &[String], fields: &mut Vec<SchemaField>) {
+    let Some(properties) = schema.get("properties").and_then(JsonValue::as_object) else {
+        return;
+    };
+    let required: Vec<&str> = schema
+        .get("required")
+        .and_then(JsonValue::as_array)
+        .map(|required| required.iter().filter_map(JsonValue::as_str).collect())
+        .unwrap_or_default();
+
+    let mut properties: Vec<_> = properties.iter().collect();
+    properties.sort_by_key(|(name, property)| {
+        (
+            property
+                .get("position")
+                .and_then(JsonValue::as_u64)
+                .unwrap_or(u64::MAX),
+            name.to_owned(),
+        )
+    });
+
+    for (name, property) in properties {
+        let mut path = prefix.to_vec();
+        path.push(name.clone());
+        if property.get("type").and_then(JsonValue::as_str) == Some("object")
+            && property.get("properties").is_some()
+        {
+            collect_fields(property, &path, fields);
+            continue;
+        }
+        let required = required.contains(&name.as_str());
+        let label = if required {
+            path.join(".")
+        } else {
+            format!("{} (optional)", path.join("."))
+        };
+        fields.push(SchemaField {
+            path,
+            name: label,
+            kind: FieldKind::from_schema(property),
+            required,
+        });
+    }
+}
+
+/// Inserts the value into nested maps along the path.
+fn insert_at_path(map: &mut ValueMap, path: &[String], value: Value) {
+    let (key, rest) = path.split_first().expect("path is not empty");
+    if rest.is_empty() {
+        map.push((Value::Text(key.clone()), value));
+        return;
+    }
+    let index = match map
+        .iter()
+        .position(|(k, _)| matches!(k, Value::Text(text) if text == key))
+    {
+        Some(index) => index,
+        None => {
+            map.push((Value::Text(key.clone()), Value::Map(ValueMap::new())));
+            map.len() - 1
+        }
+    };
+    if let Value::Map(inner) = &mut map[index].1 {
+        insert_at_path(inner, rest, value);
+    }
+}
+
+/// Value of nested maps along the path.
+fn value_at_path<'v>(value: &'v Value, path: &[String]) -> Option<&'v Value> {
+    path.iter().try_fold(value, |value, key| match value {
+        Value::Map(map) => map
+            .iter()
+            .find(|(k, _)| matches!(k, Value::Text(text) if text == key))
+            .map(|(_, value)| value),
+        _ => None,
+    })
+}
+
+struct FieldParser {
+    kind: FieldKind,
+    required: bool,
+}
+
+impl TextInputParser for FieldParser {
+    type Output = Option<Value>;
+
+    fn parse_input(&self, input: &str) -> Result<Self::Output, String> {
+        if input.is_empty() {
+            return if self.required {
+                Err("Value is required".to_owned())
+            } else {
+                Ok(None)
+            };
+        }
+        self.kind.parse(input).map(Some)
+    }
+}
+
+enum FieldInput {
+    Text(TextInput<FieldParser>),
+    /// Labels of choices and their values
+    Select(SelectInput<String>, Vec<(String, Option<Value>)>),
+}
+
+impl FieldInput {
+    fn new(field: &SchemaField, current: Option<&Value>) -> Self {
+        let choices: Vec<(String, Option<Value>)> = match &field.kind {
+            FieldKind::Boolean => vec![
+                ("true".to_owned(), Some(Value::Bool(true))),
+                ("false".to_owned(), Some(Value::Bool(false))),
+            ],
+            FieldKind::Enum(variants) => variants
+                .iter()
+                .map(|variant| (variant.to_string(), Some(Value::from(variant.clone()))))
+                .collect(),
+            kind => {
+                return FieldInput::Text(TextInput::new_str_value_with_parser(
+                    FieldParser {
+                        kind: kind.clone(),
+                        required: field.required,
+                    },
+                    kind.placeholder(),
+                    &current.map(|value| kind.format(value)).unwrap_or_default(),
+                ))
+            }
+        };
+        let choices: Vec<_> = (!field.required)
+            .then(|| (UNSET_CHOICE.to_owned(), None))
+            .into_iter()
+            .chain(choices)
+            .collect();
+        let line = choices
+            .iter()
+            .position(|(_, value)| value.as_ref() == current)
+            .unwrap_or_default();
+        FieldInput::Select(
+            SelectInput::new_init_line(
+                choices.iter().map(|(label, _)| label.clone()).collect(),
+                line,
+            ),
+            choices,
+        )
+    }
+}
+
+/// Multi-step input of a JSON schema properties.
+pub(crate) struct SchemaForm {
+    fields: Vec<SchemaField>,
+    index: usize,
+    input: FieldInput,
+    properties: ValueMap,
+    /// Properties map of a document being edited, fields start with its values
+    current: Option<Value>,
+}
+
+impl SchemaForm {
+    pub(crate) fn new(schema: &JsonValue) -> Result<Self, String> {
+        Self::new_with_current(schema, None)
+    }
+
+    /// Form to edit properties of a document, each step is filled with the
+    /// current value.
+    pub(crate) fn new_with_current(
+        schema: &JsonValue,
+        current: Option<Value>,
+    ) -> Result<Self, String> {
+        let mut fields = Vec::new();
+        collect_fields(schema, &[], &mut fields);
+        let first = fields
+            .first()
+            .ok_or_else(|| "Schema has no properties to fill".to_owned())?;
+        Ok(SchemaForm {
+            input: FieldInput::new(first, Self::current_value(&current, first)),
+            fields,
+            index: 0,
+            properties: ValueMap::new(),
+            current,
+        })
+    }
+
+    fn current_value<'v>(current: &'v Option<Value>, field: &SchemaField) -> Option<&'v Value> {
+        current
+            .as_ref()
+            .and_then(|current| value_at_path(current, &field.path))
+    }
+
+    pub(crate) fn step_name(&self) -> &str {
+        &self.fields[self.index].name
+    }
+
+    pub(crate) fn step_index(&self) -> u8 {
+        self.index as u8
+    }
+
+    pub(crate) fn steps_number(&self) -> u8 {
+        self.fields.len() as u8
+    }
+}
+
+impl Input for SchemaForm {
+    type Output = Value;
+
+    fn on_event(&mut self, event: KeyEvent) -> InputStatus<Self::Output> {
+        let status = match &mut self.input {
+            FieldInput::Text(input) => input.on_event(event),
+            FieldInput::Select(input, choices) => match input.on_event(event) {
+                InputStatus::Done(label) => InputStatus::Done(
+                    choices
+                        .iter()
+                        .find(|(l, _)| *l == label)
+                        .and_then(|(_, value)| value.clone()),
+                ),
+                InputStatus::Redraw => InputStatus::Redraw,
+                InputStatus::None => InputStatus::None,
+                InputStatus::Exit => InputStatus::Exit,
+            },
+        };
+
+        match status {
+            InputStatus::Done(value) => {
+                if let Some(value) = value {
+                    insert_at_path(&mut self.properties, &self.fields[self.index].path, value);
+                }
+                self.index += 1;
+                match self.fields.get(self.index) {
+                    Some(field) => {
+                        self.input =
+                            FieldInput::new(field, Self::current_value(&self.current, field));
+                        InputStatus::Redraw
+                    }
+                    None => {
+                        self.index -= 1;
+                        InputStatus::Done(Value::Map(std::mem::take(&mut self.properties)))
+                    }
+                }
+            }
+            InputStatus::Redraw => InputStatus::Redraw,
+            InputStatus::None => InputStatus::None,
+            InputStatus::Exit => InputStatus::Exit,
+        }
+    }
+
+    fn view(&mut self, frame: &mut Frame, area: Rect) {
+        match &mut self.input {
+            FieldInput::Text(input) => input.view(frame, area),
+            FieldInput::Select(input, _) => input.view(frame, area),
+        }
+    }
+}
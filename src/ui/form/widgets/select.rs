@@ -2,6 +2,7 @@
 
 use std::{fmt::Display, marker::PhantomData};
 
+use crossterm::event::MouseEvent;
 use tuirealm::{
     command::{Cmd, Direction},
     event::{Key, KeyEvent, KeyModifiers},
@@ -10,12 +11,17 @@ use tuirealm::{
     AttrValue, Attribute, Frame, MockComponent,
 };
 
-use crate::ui::form::{Input, InputStatus};
+use crate::ui::{
+    form::{Input, InputStatus},
+    mouse::{self, MouseAction},
+};
 
 pub(crate) struct SelectInput<V: Display + Clone> {
     input: tui_realm_stdlib::List,
     variants: Vec<V>,
     _variants_type: PhantomData<V>,
+    /// Where the list was last drawn
+    area: Rect,
 }
 
 impl<V: Display + Clone> SelectInput<V> {
@@ -41,6 +47,41 @@ impl<V: Display + Clone> SelectInput<V> {
             input,
             variants,
             _variants_type: PhantomData,
+            area: Rect::default(),
+        }
+    }
+
+    /// Index of the highlighted variant.
+    pub(crate) fn selected_index(&self) -> usize {
+        self.input.state().unwrap_one().unwrap_usize()
+    }
+
+    /// The wheel moves the highlight, a click highlights the clicked variant
+    /// or selects it if it's highlighted already.
+    pub(crate) fn on_mouse(&mut self, event: &MouseEvent) -> InputStatus<V> {
+        match mouse::action_within(event, self.area) {
+            Some(MouseAction::ScrollUp) => {
+                self.input.perform(Cmd::Move(Direction::Up));
+                InputStatus::Redraw
+            }
+            Some(MouseAction::ScrollDown) => {
+                self.input.perform(Cmd::Move(Direction::Down));
+                InputStatus::Redraw
+            }
+            Some(MouseAction::Click(row)) => {
+                let selected = self.selected_index();
+                match mouse::list_row(row, self.area.height, selected, self.variants.len()) {
+                    Some(index) if index == selected => {
+                        InputStatus::Done(self.variants[index].clone())
+                    }
+                    Some(index) => {
+                        mouse::select_row(&mut self.input, selected, index);
+                        InputStatus::Redraw
+                    }
+                    None => InputStatus::None,
+                }
+            }
+            None => InputStatus::None,
         }
     }
 }
@@ -91,6 +132,7 @@ impl<V: Display + Clone> Input for SelectInput<V> {
     }
 
     fn view(&mut self, frame: &mut Frame, area: Rect) {
+        self.area = area;
         self.input.view(frame, area);
     }
 }
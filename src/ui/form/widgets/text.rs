@@ -2,12 +2,12 @@
 
 pub(crate) mod parsers;
 
-use std::{fmt::Display, str::FromStr};
+use std::{fmt::Display, fs, path::Path, str::FromStr};
 
 use tuirealm::{
     command::{self, Cmd},
     event::{Key, KeyEvent, KeyModifiers},
-    props::{BorderSides, Borders, Color, Style},
+    props::{BorderSides, Borders, Color, InputType, Style},
     tui::{
         prelude::{Constraint, Direction, Layout, Rect},
         widgets::Paragraph,
@@ -22,6 +22,8 @@ pub(crate) struct TextInput<P> {
     input: tui_realm_stdlib::Input,
     error_msg: Option<String>,
     parser: P,
+    /// Complete file paths on Tab
+    path_completion: bool,
 }
 
 impl<T> TextInput<DefaultTextInputParser<T>>
@@ -80,10 +82,32 @@ impl<P: TextInputParser> TextInput<P> {
             input,
             parser,
             error_msg: None,
+            path_completion: false,
         }
     }
 
-    fn set_error(&mut self, error_msg: String) {
+    /// Hides typed characters, for secrets like passphrases.
+    pub(crate) fn masked(mut self) -> Self {
+        self.input.attr(
+            Attribute::InputType,
+            AttrValue::InputType(InputType::Password('*')),
+        );
+        self
+    }
+
+    /// Completes file paths on Tab, as much as matching entries agree.
+    pub(crate) fn with_path_completion(mut self) -> Self {
+        self.path_completion = true;
+        self
+    }
+
+    /// Text typed so far.
+    pub(crate) fn value(&self) -> String {
+        self.input.state().unwrap_one().unwrap_string()
+    }
+
+    /// Shows the error until the input changes.
+    pub(crate) fn set_error(&mut self, error_msg: String) {
         self.input
             .attr(Attribute::Foreground, AttrValue::Color(Color::Red));
         self.error_msg = Some(error_msg);
@@ -96,6 +120,37 @@ impl<P: TextInputParser> TextInput<P> {
     }
 }
 
+/// Longest path all entries of the directory starting with the typed name
+/// share, a directory gets its separator once it's the only match. Hidden
+/// entries are left out unless their name is started.
+fn complete_path(value: &str) -> Option<String> {
+    let (dir, prefix) = match value.rfind('/') {
+        Some(separator) => value.split_at(separator + 1),
+        None => ("", value),
+    };
+    let names: Vec<String> = fs::read_dir(if dir.is_empty() { "." } else { dir })
+        .ok()?
+        .filter_map(|entry| entry.ok()?.file_name().into_string().ok())
+        .filter(|name| {
+            name.starts_with(prefix) && (!name.starts_with('.') || prefix.starts_with('.'))
+        })
+        .collect();
+    let (first, rest) = names.split_first()?;
+    let common_len = rest.iter().fold(first.len(), |len, name| {
+        first[..len]
+            .chars()
+            .zip(name.chars())
+            .take_while(|(a, b)| a == b)
+            .map(|(c, _)| c.len_utf8())
+            .sum()
+    });
+    let mut completed = format!("{}{}", dir, &first[..common_len]);
+    if rest.is_empty() && Path::new(&completed).is_dir() {
+        completed.push('/');
+    }
+    Some(completed)
+}
+
 impl<P: TextInputParser> Input for TextInput<P> {
     type Output = P::Output;
 
@@ -140,6 +195,22 @@ impl<P: TextInputParser> Input for TextInput<P> {
                 InputStatus::Redraw
             }
 
+            KeyEvent {
+                code: Key::Tab,
+                modifiers: KeyModifiers::NONE,
+            } if self.path_completion => {
+                let value = self.input.state().unwrap_one().unwrap_string();
+                match complete_path(&value) {
+                    Some(completed) if completed != value => {
+                        self.reset_error();
+                        self.input
+                            .attr(Attribute::Value, AttrValue::String(completed));
+                        InputStatus::Redraw
+                    }
+                    _ => InputStatus::None,
+                }
+            }
+
             KeyEvent {
                 code: Key::Char('q'),
                 modifiers: KeyModifiers::CONTROL,
@@ -0,0 +1,230 @@
+//! UI test harness.
+//!
+//! Drives [Ui] with synthetic key presses and backend events, rendering into
+//! an in-memory buffer instead of the terminal. Tests assert on the text of
+//! the rendered buffer, so a test for a new screen is a few key presses and
+//! checks of what `harness.render()` shows.
+
+use tempfile::TempDir;
+use tuirealm::{
+    event::{Key, KeyEvent, KeyModifiers},
+    tui::{backend::TestBackend, Terminal},
+};
+
+use super::{Ui, UiFeedback, UiTerminal};
+use crate::{
+    backend::{AppState, BackendEvent, Task},
+    config::Config,
+    Event,
+};
+
+const WIDTH: u16 = 120;
+const HEIGHT: u16 = 40;
+const CONFIG_PREFIX: &str = "EXPLORER_";
+
+/// Minimal valid configuration, nothing is connected to in tests.
+fn test_config() -> Config {
+    envy::prefixed(CONFIG_PREFIX)
+        .from_iter(
+            [
+                ("DAPI_ADDRESSES", "http://127.0.0.1:2443"),
+                ("CORE_HOST", "127.0.0.1"),
+                ("CORE_RPC_PORT", "20002"),
+                ("CORE_RPC_USER", "dashmate"),
+                ("CORE_RPC_PASSWORD", "password"),
+                ("INSIGHT_API_URL", "http://127.0.0.1:3001/insight-api"),
+                ("NETWORK", "local"),
+            ]
+            .into_iter()
+            .map(|(key, value)| (format!("{}{}", CONFIG_PREFIX, key), value.to_owned())),
+        )
+        .expect("valid test configuration")
+}
+
+pub(super) struct UiHarness {
+    ui: Ui,
+    app_state: AppState,
+    /// Tasks the UI asked to execute, in order
+    pub(super) tasks: Vec<Task>,
+    /// Set once the UI asked to quit
+    pub(super) quit: bool,
+    // Holds the local Drive of the app state, removed on drop
+    _drive_dir: TempDir,
+}
+
+impl UiHarness {
+    pub(super) fn new() -> Self {
+        Self::with_size(WIDTH, HEIGHT)
+    }
+
+    /// Harness with a terminal of custom dimensions.
+    pub(super) fn with_size(width: u16, height: u16) -> Self {
+        let config = test_config();
+        let drive_dir = TempDir::new().expect("create temporary directory");
+        let app_state = AppState::new_with_drive_path(&config, drive_dir.path().join("drive"));
+        let terminal =
+            Terminal::new(TestBackend::new(width, height)).expect("create test terminal");
+
+        UiHarness {
+            ui: Ui::with_terminal(UiTerminal::Test(terminal), None, &config),
+            app_state,
+            tasks: Vec::new(),
+            quit: false,
+            _drive_dir: drive_dir,
+        }
+    }
+
+    async fn on_event(&mut self, event: Event<'_>) {
+        match self.ui.on_event(&self.app_state, event).await {
+            UiFeedback::ExecuteTask(task) => self.tasks.push(task),
+            UiFeedback::Quit => self.quit = true,
+            UiFeedback::Redraw | UiFeedback::SwitchProfile(_) | UiFeedback::None => {}
+        }
+    }
+
+    /// Presses a key without modifiers.
+    pub(super) async fn key(&mut self, code: Key) {
+        self.on_event(Event::Key(KeyEvent {
+            code,
+            modifiers: KeyModifiers::NONE,
+        }))
+        .await
+    }
+
+    /// Types a text char by char.
+    pub(super) async fn type_text(&mut self, text: &str) {
+        for c in text.chars() {
+            self.key(Key::Char(c)).await;
+        }
+    }
+
+    /// Delivers a backend event as if a task has finished.
+    pub(super) async fn backend_event(&mut self, event: BackendEvent<'_>) {
+        self.on_event(Event::Backend(event)).await
+    }
+
+    /// Draws the UI and returns the rendered buffer as text, trailing
+    /// whitespace of each line is trimmed.
+    pub(super) fn render(&mut self) -> String {
+        self.ui.redraw();
+
+        let UiTerminal::Test(terminal) = &self.ui.terminal else {
+            unreachable!("harness always uses the test terminal");
+        };
+        let buffer = terminal.backend().buffer();
+
+        (0..buffer.area.height)
+            .map(|y| {
+                (0..buffer.area.width)
+                    .map(|x| buffer.get(x, y).symbol())
+                    .collect::<String>()
+                    .trim_end()
+                    .to_owned()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+mod tests {
+    use tuirealm::event::Key;
+
+    use super::UiHarness;
+    use crate::backend::{platform_info::PlatformInfoTask, BackendEvent, Task};
+
+    #[tokio::test]
+    async fn main_screen() {
+        let mut harness = UiHarness::new();
+
+        let rendered = harness.render();
+
+        assert!(rendered.contains("Main"));
+        assert!(rendered.contains("Welcome to Platform TUI!"));
+        for command in ["Quit", "Identities", "Contracts", "Task history"] {
+            assert!(rendered.contains(command), "missing command {}", command);
+        }
+    }
+
+    #[tokio::test]
+    async fn navigate_and_back() {
+        let mut harness = UiHarness::new();
+
+        harness.key(Key::Char('p')).await;
+        assert!(harness.render().contains("Platform Information"));
+
+        harness.key(Key::Char('q')).await;
+        let rendered = harness.render();
+        assert!(!rendered.contains("Platform Information"));
+        assert!(!harness.quit);
+
+        harness.key(Key::Char('q')).await;
+        assert!(harness.quit);
+    }
+
+    #[tokio::test]
+    async fn task_result_is_shown() {
+        let mut harness = UiHarness::new();
+
+        harness.key(Key::Char('p')).await;
+        harness.key(Key::Char('c')).await;
+        assert!(matches!(
+            harness.tasks.as_slice(),
+            [Task::PlatformInfo(PlatformInfoTask::FetchCurrentEpochInfo)]
+        ));
+
+        harness
+            .backend_event(BackendEvent::TaskCompleted {
+                task: Task::PlatformInfo(PlatformInfoTask::FetchCurrentEpochInfo),
+                execution_result: Ok("Current epoch: 42".into()),
+            })
+            .await;
+
+        let rendered = harness.render();
+        assert!(rendered.contains("Platform Information"));
+        assert!(rendered.contains("Current epoch: 42"));
+    }
+
+    #[tokio::test]
+    async fn form_submits_task() {
+        let mut harness = UiHarness::new();
+
+        harness.key(Key::Char('p')).await;
+        harness.key(Key::Char('i')).await;
+        harness.type_text("7").await;
+        let rendered = harness.render();
+        assert!(rendered.contains("Epoch number: Input epoch number [1 / 1]"));
+        assert!(rendered
+            .lines()
+            .any(|line| line.trim_start_matches(['│', ' ']).starts_with('7')));
+
+        harness.key(Key::Enter).await;
+        assert!(matches!(
+            harness.tasks.as_slice(),
+            [Task::PlatformInfo(
+                PlatformInfoTask::FetchSpecificEpochInfo(7)
+            )]
+        ));
+    }
+
+    #[tokio::test]
+    async fn tiny_terminal_shows_resize_prompt() {
+        let mut harness = UiHarness::with_size(30, 8);
+
+        let rendered = harness.render();
+        assert!(rendered.contains("too small"));
+        assert!(!rendered.contains("Main"));
+    }
+
+    #[tokio::test]
+    async fn short_terminal_collapses_into_tabs() {
+        let mut harness = UiHarness::with_size(80, 16);
+
+        assert!(harness.render().contains("[Info] | Commands"));
+
+        harness.key(Key::Tab).await;
+        let rendered = harness.render();
+        assert!(rendered.contains("Info | [Commands]"));
+        assert!(rendered.contains("Quit"));
+        assert!(!rendered.contains("Welcome to Platform TUI!"));
+    }
+}
@@ -0,0 +1,504 @@
+//! Localization of UI strings.
+//! Screen titles, command descriptions, form titles and error messages are
+//! written in English in code and looked up in the catalog of the configured
+//! language when drawn. Strings missing in a catalog are shown untranslated,
+//! error messages are also matched by their prefix before the first colon, as
+//! the rest is usually a formatted error from a dependency.
+
+use std::sync::RwLock;
+
+use crate::config::Language;
+
+static LANGUAGE: RwLock<Language> = RwLock::new(Language::En);
+
+pub(crate) fn set_language(language: Language) {
+    *LANGUAGE.write().expect("language lock poisoned") = language;
+}
+
+/// Translates an UI string into the configured language.
+pub(crate) fn tr(text: &str) -> &str {
+    let language = *LANGUAGE.read().expect("language lock poisoned");
+    match language {
+        Language::En => None,
+        Language::Es => es(text),
+    }
+    .unwrap_or(text)
+}
+
+/// Translates a message that may be a static text or a `prefix: details`
+/// formatted one.
+pub(crate) fn tr_message(text: &str) -> String {
+    let translated = tr(text);
+    if translated != text {
+        return translated.to_owned();
+    }
+    match text.split_once(": ") {
+        Some((prefix, details)) => format!("{}: {}", tr(prefix), details),
+        None => text.to_owned(),
+    }
+}
+
+fn es(text: &str) -> Option<&'static str> {
+    let translated = match text {
+        // Screen titles
+        "Main menu" => "Menú principal",
+        "Identities" => "Identidades",
+        "Contracts" => "Contratos",
+        "Strategies" => "Estrategias",
+        "Strategy" => "Estrategia",
+        "Wallet" => "Billetera",
+        "Platform Information" => "Información de Platform",
+        "Version Upgrade" => "Actualización de versión",
+        "System Contracts" => "Contratos del sistema",
+        "Documents queryset" => "Consulta de documentos",
+        "Bookmarks" => "Marcadores",
+        "Schema builder" => "Constructor de esquemas",
+        "Search" => "Búsqueda",
+        "Settings" => "Ajustes",
+        "Tutorial" => "Tutorial",
+        "Keep funded" => "Mantener fondos",
+        "Operations" => "Operaciones",
+        "Start contracts" => "Contratos iniciales",
+        "Start identities" => "Identidades iniciales",
+        "Identity inserts" => "Inserciones de identidades",
+        "Compare reports" => "Comparar informes",
+        "Contract variants" => "Variantes de contratos",
+        "Funding plan" => "Plan de financiación",
+        "Dependency graph" => "Grafo de dependencias",
+        "Task history" => "Historial de tareas",
+        "Network" => "Red",
+        "Evonodes" => "Evonodos",
+        "Evonode rewards" => "Recompensas del evonodo",
+        "Network profiles" => "Perfiles de red",
+        "Connection settings" => "Ajustes de conexión",
+        "Wallet UTXOs" => "UTXO de la billetera",
+        "Asset lock inspector" => "Inspector de bloqueos de activos",
+
+        // Commands and toggles
+        "Quit" => "Salir",
+        "Back" => "Volver",
+        "Back to Main" => "Volver al menú principal",
+        "Back to Contracts" => "Volver a contratos",
+        "Back to Identities" => "Volver a identidades",
+        "Back to Strategies" => "Volver a estrategias",
+        "Back to Strategy" => "Volver a la estrategia",
+        "Back to Settings" => "Volver a ajustes",
+        "Scroll down" => "Desplazar abajo",
+        "Scroll up" => "Desplazar arriba",
+        "Scroll doc down" => "Desplazar documento abajo",
+        "Scroll doc up" => "Desplazar documento arriba",
+        "Add" => "Añadir",
+        "Add/edit" => "Añadir/editar",
+        "Add specific" => "Añadir específico",
+        "Add x random" => "Añadir x aleatorios",
+        "Add index" => "Añadir índice",
+        "Add property" => "Añadir propiedad",
+        "Add Identity key" | "Add identity key" => "Añadir clave de identidad",
+        "Add wallet by private key" | "Add wallet with private key" => {
+            "Añadir billetera con clave privada"
+        }
+        "Auto add docs to contracts" => "Añadir documentos a contratos automáticamente",
+        "Bookmark" => "Marcador",
+        "Bookmark document" => "Guardar documento en marcadores",
+        "Broadcast Random Documents" | "Broadcast random documents" => {
+            "Difundir documentos aleatorios"
+        }
+        "Check schema update" => "Comprobar actualización de esquema",
+        "Clear" => "Limpiar",
+        "Clear all" => "Limpiar todo",
+        "Clear loaded identity" => "Descargar identidad",
+        "Clear loaded wallet" => "Descargar billetera",
+        "Clear picked UTXOs" => "Descartar UTXO elegidos",
+        "Clone strategy" | "Clone this strategy" => "Clonar estrategia",
+        "Compare run reports" | "Compare strategy reports" => "Comparar informes de ejecución",
+        "Compose document" => "Componer documento",
+        "Continue identity registration" => "Continuar registro de identidad",
+        "Continue identity top up" => "Continuar recarga de identidad",
+        "Copy contract ID" => "Copiar ID del contrato",
+        "Copy contract JSON" => "Copiar JSON del contrato",
+        "Copy document ID" => "Copiar ID del documento",
+        "Copy document JSON" => "Copiar JSON del documento",
+        "Copy Identity ID" | "Copy identity ID" => "Copiar ID de identidad",
+        "Copy info" => "Copiar información",
+        "Copy Receive Address" => "Copiar dirección de recepción",
+        "Create document" => "Crear documento",
+        "Create new strategy" | "New strategy" => "Nueva estrategia",
+        "New strategy from template" => "Nueva estrategia desde plantilla",
+        "Current version voting" => "Votación de versión actual",
+        "Network parameters changelog" => "Cambios de parámetros de la red",
+        "Cycle forecast blocks" => "Cambiar bloques de previsión",
+        "Delete a strategy" => "Eliminar una estrategia",
+        "Delete document" => "Eliminar documento",
+        "Delete marked" | "Delete marked documents" => "Eliminar marcados",
+        "Edit and rerun" => "Editar y volver a ejecutar",
+        "Edit connection settings" => "Editar ajustes de conexión",
+        "Examine document type of the data contract" => "Examinar tipo de documento del contrato",
+        "Execute funding plan" => "Ejecutar plan de financiación",
+        "Export a strategy" => "Exportar una estrategia",
+        "Export epochs to CSV" => "Exportar épocas a CSV",
+        "Export identity bundle" => "Exportar paquete de identidad",
+        "Export identity keys" => "Exportar claves de identidad",
+        "Export marked" | "Export marked documents" => "Exportar marcados",
+        "Export signing audit log" => "Exportar registro de auditoría de firmas",
+        "Export to file" => "Exportar a archivo",
+        "Fetch DPNS contract" => "Obtener contrato DPNS",
+        "Fetch Dashpay contract" => "Obtener contrato Dashpay",
+        "Fetch contract" | "Fetch contract by ID" | "Get Contract by ID" => {
+            "Obtener contrato por ID"
+        }
+        "Fetch system contract" => "Obtener contrato del sistema",
+        "Fetch current Platform epoch info" => "Obtener época actual de Platform",
+        "Fetch previous Platform epoch info" => "Obtener época anterior de Platform",
+        "Filter tasks" => "Filtrar tareas",
+        "Follow edge" => "Seguir arista",
+        "Get Documents by Query" => "Obtener documentos por consulta",
+        "Get Identity by ID" | "Get identity by ID" => "Obtener identidad por ID",
+        "Get more utxos" => "Obtener más UTXO",
+        "Identity petnames" => "Apodos de identidades",
+        "Identity refresh" => "Actualizar identidad",
+        "Import a strategy" | "Import strategy" => "Importar estrategia",
+        "Join references" => "Unir referencias",
+        "Load document type" => "Cargar tipo de documento",
+        "Load identity bundle" => "Cargar paquete de identidad",
+        "Mark all / none" => "Marcar todos / ninguno",
+        "Mark document" => "Marcar documento",
+        "Next contract" => "Contrato siguiente",
+        "Next edge" => "Arista siguiente",
+        "Next evonode" => "Evonodo siguiente",
+        "Next document" => "Documento siguiente",
+        "Next page" => "Página siguiente",
+        "Next profile" => "Perfil siguiente",
+        "Next task" => "Tarea siguiente",
+        "Next result" => "Resultado siguiente",
+        "Next UTXO" => "UTXO siguiente",
+        "Open node" => "Abrir nodo",
+        "Open result" => "Abrir resultado",
+        "Pick / unpick UTXO for asset locks" => "Elegir / descartar UTXO para bloqueos de activos",
+        "Plan actors funding" => "Planificar financiación de actores",
+        "Prev contract" => "Contrato anterior",
+        "Prev document" => "Documento anterior",
+        "Prev page" => "Página anterior",
+        "Prev profile" => "Perfil anterior",
+        "Prev task" => "Tarea anterior",
+        "Prev edge" => "Arista anterior",
+        "Prev evonode" => "Evonodo anterior",
+        "Previous node" => "Nodo anterior",
+        "Prev result" => "Resultado anterior",
+        "Prev UTXO" => "UTXO anterior",
+        "Query" => "Consultar",
+        "Query ours" => "Consultar propios",
+        "Query builder" => "Constructor de consultas",
+        "Re-fetch all bookmarks" => "Volver a obtener todos los marcadores",
+        "Re-fetch marked with proof" => "Volver a obtener marcados con prueba",
+        "Re-fetch proof status" => "Volver a obtener el estado de la prueba",
+        "Refresh" => "Actualizar",
+        "Refresh all contracts" => "Actualizar todos los contratos",
+        "Refresh evonodes" => "Actualizar evonodos",
+        "Refresh locked balance" => "Actualizar saldo bloqueado",
+        "Refresh upgrade status" => "Actualizar estado de la actualización",
+        "Refresh unconfirmed balance" => "Actualizar saldo no confirmado",
+        "Refresh wallet utxos and balance" => "Actualizar UTXO y saldo de la billetera",
+        "Register DPNS Name" | "Register DPNS name" => "Registrar nombre DPNS",
+        "Register contract" => "Registrar contrato",
+        "Register contract from file" => "Registrar contrato desde archivo",
+        "Register identity" => "Registrar identidad",
+        "Register x documents to all contracts" => "Registrar x documentos en todos los contratos",
+        "Remove" => "Quitar",
+        "Remove a contract" | "Remove contract" => "Quitar contrato",
+        "Remove bookmark" => "Quitar marcador",
+        "Remove last" => "Quitar último",
+        "Remove last index" => "Quitar último índice",
+        "Remove last property" => "Quitar última propiedad",
+        "Replace document" => "Reemplazar documento",
+        "Rerun strategy" => "Volver a ejecutar estrategia",
+        "Rerun as is" => "Volver a ejecutar sin cambios",
+        "Resume halted run" => "Reanudar ejecución detenida",
+        "Rewards of configured evonode" => "Recompensas del evonodo configurado",
+        "Run contract variants" => "Ejecutar variantes de contratos",
+        "Run strategy" => "Ejecutar estrategia",
+        "Save binary field" => "Guardar campo binario",
+        "Search cached contracts and documents" => "Buscar en contratos y documentos en caché",
+        "Select a strategy" => "Seleccionar una estrategia",
+        "Set balance" => "Fijar saldo",
+        "Set daily cap" => "Fijar límite diario",
+        "Set document type name" => "Fijar nombre del tipo de documento",
+        "Set identity petname" => "Fijar apodo de identidad",
+        "Set log filter" => "Fijar filtro de registro",
+        "Set start identities balances" => "Fijar saldos de identidades iniciales",
+        "Signing audit log" => "Registro de auditoría de firmas",
+        "Split wallet UTXOs" => "Dividir UTXO de la billetera",
+        "Sponsor identity registration" => "Patrocinar registro de identidad",
+        "Stop after current step" => "Detener tras el paso actual",
+        "Start tutorial from the beginning" => "Empezar el tutorial desde el principio",
+        "Stop tutorial" => "Detener tutorial",
+        "Switch display density" => "Cambiar densidad de visualización",
+        "Switch proof details" => "Cambiar detalles de pruebas",
+        "Switch to profile" => "Cambiar al perfil",
+        "Switch wallet" => "Cambiar de billetera",
+        "Top up known identities" => "Recargar identidades conocidas",
+        "Transaction fee rate" => "Tarifa de transacción",
+        "Transfer Credits" | "Transfer credits" => "Transferir créditos",
+        "Transition size padding" => "Relleno del tamaño de transiciones",
+        "UTXOs and coin control" => "UTXO y control de monedas",
+        "Unwatch identity" => "Dejar de vigilar identidad",
+        "Update contract" => "Actualizar contrato",
+        "Verify key status" => "Verificar estado de claves",
+        "Verify page proof" => "Verificar prueba de la página",
+        "Warm-up and cool-down" => "Calentamiento y enfriamiento",
+        "Watch identity" => "Vigilar identidad",
+        "Whitelist withdrawal address" => "Autorizar dirección de retiro",
+        "Withdraw balance" => "Retirar saldo",
+        "block timeline" => "cronología de bloques",
+        "enabled" => "activado",
+        "expand proof" => "expandir prueba",
+        "with proof" => "con prueba",
+
+        // Forms and their steps
+        "Address" => "Dirección",
+        "Also write raw serialized bytes" => "Escribir también los bytes serializados",
+        "Base 58 ID" => "ID en base 58",
+        "Binary field" => "Campo binario",
+        "Chance per block" => "Probabilidad por bloque",
+        "Condition field" => "Campo de la condición",
+        "Condition operator" => "Operador de la condición",
+        "Condition value" => "Valor de la condición",
+        "Confirm deletion" => "Confirmar eliminación",
+        "Confirm withdrawal" => "Confirmar retiro",
+        "Contract create operation" => "Operación de creación de contrato",
+        "Contract file" => "Archivo de contrato",
+        "Contract ID" => "ID del contrato",
+        "Contract name" => "Nombre del contrato",
+        "Contract update doc types operation" => "Operación de actualización de tipos de documento",
+        "Contract update new fields operation" => "Operación de nuevos campos del contrato",
+        "DPNS Name" => "Nombre DPNS",
+        "Daily cap" => "Límite diario",
+        "Desired number" => "Cantidad deseada",
+        "Document insert random" => "Inserción aleatoria de documentos",
+        "Document replace churn" => "Reemplazo continuo de documentos",
+        "Document type" => "Tipo de documento",
+        "Document type name" => "Nombre del tipo de documento",
+        "Documents count" => "Cantidad de documentos",
+        "Duplicate task" => "Tarea duplicada",
+        "Epoch number" | "Input epoch number" => "Número de época",
+        "Epochs count" => "Cantidad de épocas",
+        "File path" => "Ruta del archivo",
+        "Filter directives" => "Directivas de filtro",
+        "Filter" => "Filtro",
+        "Filter task history" => "Filtrar historial de tareas",
+        "First epoch" => "Primera época",
+        "Format" => "Formato",
+        "Funding" => "Financiación",
+        "Funding amount" => "Monto de financiación",
+        "Identity inserts for strategy" => "Inserciones de identidades de la estrategia",
+        "Identity keys updates operation" => "Operación de actualización de claves",
+        "Identity registration" => "Registro de identidad",
+        "Identity ID" => "ID de identidad",
+        "Identity top up" => "Recarga de identidad",
+        "Identity top up operation" => "Operación de recarga de identidad",
+        "Identity transfer operation" => "Operación de transferencia de identidad",
+        "Identity withdrawal" => "Retiro de identidad",
+        "Identity withdrawal operation" => "Operación de retiro de identidad",
+        "Limit" => "Límite",
+        "Name" => "Nombre",
+        "Number of blocks to fund" => "Número de bloques a financiar",
+        "Order by field" => "Campo de ordenamiento",
+        "Order direction" => "Dirección del orden",
+        "Order by" => "Ordenar por",
+        "Plan strategy funding" => "Planificar financiación de la estrategia",
+        "Platform information" => "Información de Platform",
+        "Private key" => "Clave privada",
+        "Proposed schema file" => "Archivo de esquema propuesto",
+        "Public keys" => "Claves públicas",
+        "Quantity (in Dash)" => "Cantidad (en Dash)",
+        "Random start contracts for strategy" => "Contratos iniciales aleatorios de la estrategia",
+        "Resume strategy" => "Reanudar estrategia",
+        "Same task was submitted moments ago, run it again?" => {
+            "La misma tarea se envió hace un momento, ¿ejecutarla de nuevo?"
+        }
+        "Select contract" => "Seleccionar contrato",
+        "Starting contract" => "Contrato inicial",
+        "Start at" => "Comenzar en",
+        "Start contracts for strategy" => "Contratos iniciales de la estrategia",
+        "Start identities for strategy" => "Identidades iniciales de la estrategia",
+        "Strategy deletion" => "Eliminación de estrategia",
+        "Strategy export" => "Exportación de estrategia",
+        "Strategy name" => "Nombre de la estrategia",
+        "Template" => "Plantilla",
+        "Target serialized size" => "Tamaño serializado objetivo",
+        "Transitions per block" => "Transiciones por bloque",
+        "Contract" => "Contrato",
+        "Strategy selection" => "Selección de estrategia",
+        "Top up amount" => "Monto de recarga",
+        "Url" => "URL",
+        "Wallet label" => "Etiqueta de la billetera",
+        "Wallet passphrase" => "Frase de contraseña de la billetera",
+        "Whitelisted destination" => "Destino autorizado",
+        "Withdrawal amount" => "Monto de retiro",
+        "With proof" => "Con prueba",
+
+        // Layout
+        "Info" => "Información",
+        "Commands" => "Comandos",
+        "Tab to switch" => "Tab para cambiar",
+        "to switch pane" => "para cambiar de panel",
+        "to close the other pane" => "para cerrar el otro panel",
+        "more" => "más",
+        "Proof" => "Prueba",
+        "Command palette" => "Paleta de comandos",
+        "No matches" => "Sin coincidencias",
+        "Recent entities" => "Entidades recientes",
+        "No recent entities" => "Sin entidades recientes",
+        "Identity" => "Identidad",
+        "Document" => "Documento",
+        "Terminal is too small, please resize it to at least" => {
+            "La terminal es demasiado pequeña, agrándela al menos a"
+        }
+
+        // Status bar
+        "Executing a task, please wait" => "Ejecutando una tarea, espere por favor",
+        "No identity" => "Sin identidad",
+        "Platform balance" => "Saldo en Platform",
+        "Balance refresh error" => "Error al actualizar el saldo",
+        "syncing" => "sincronizando",
+        "Copied" => "Copiado",
+
+        // Error messages
+        "No identity loaded" | "No loaded identity" => "No hay una identidad cargada",
+        "No longer available" => "Ya no está disponible",
+        "No wallet loaded" => "No hay una billetera cargada",
+        "No public keys provided" => "No se proporcionaron claves públicas",
+        "A master authentication key is required" => {
+            "Se requiere una clave maestra de autenticación"
+        }
+        "Keys are neither JSON nor a readable file" => {
+            "Las claves no son JSON ni un archivo legible"
+        }
+        "Invalid keys file" => "Archivo de claves inválido",
+        "No wallets registered yet" => "Aún no hay billeteras registradas",
+        "Data contract not found" => "Contrato no encontrado",
+        "Document type not found" => "Tipo de documento no encontrado",
+        "Document type name is not set" => "El nombre del tipo de documento no está fijado",
+        "Document type has no properties" => "El tipo de documento no tiene propiedades",
+        "Document type schema is unavailable" => {
+            "El esquema del tipo de documento no está disponible"
+        }
+        "No document selected" => "No hay un documento seleccionado",
+        "No large binary fields in the document" => "El documento no tiene campos binarios grandes",
+        "No such field" => "No existe el campo",
+        "Not a byte array field" => "No es un campo de arreglo de bytes",
+        "Unable to write field bytes" => "No se pudieron escribir los bytes del campo",
+        "Unknown operator" => "Operador desconocido",
+        "Invalid identifier" => "Identificador inválido",
+        "Invalid start document ID" => "ID de documento inicial inválido",
+        "Invalid condition" => "Condición inválida",
+        "Invalid order by clause" => "Cláusula de orden inválida",
+        "Invalid parameter" => "Parámetro inválido",
+        "The task has no parameters to edit" => "La tarea no tiene parámetros para editar",
+        "Invalid query" => "Consulta inválida",
+        "No more documents" => "No hay más documentos",
+        "Already on the first page" => "Ya está en la primera página",
+        "Pages are only available for queries ordered by document ID" => {
+            "Las páginas solo están disponibles para consultas ordenadas por ID de documento"
+        }
+        "Not available with the simulated platform" => "No disponible con la plataforma simulada",
+        "Withdrawals contract not found" => "Contrato de retiros no encontrado",
+        "Unable to estimate the withdrawal" => "No se pudo estimar el retiro",
+        "Core RPC request failed" => "Falló la solicitud RPC a Core",
+        "Core RPC authentication failed" => "Falló la autenticación RPC en Core",
+        "Invalid Core RPC response" => "Respuesta RPC de Core inválida",
+        "No evonodes in the masternode list" => "No hay evonodos en la lista de masternodos",
+        "No UTXOs in the wallet" => "No hay UTXO en la billetera",
+        "UTXO not in the wallet" => "El UTXO no está en la billetera",
+        "No evonode configured" => "No hay un evonodo configurado",
+        "Invalid ProTxHash" => "ProTxHash inválido",
+        "Evonode not found in the masternode list" => {
+            "Evonodo no encontrado en la lista de masternodos"
+        }
+        "Tenderdash RPC request failed" => "Falló la solicitud RPC a Tenderdash",
+        "Invalid Tenderdash RPC response" => "Respuesta RPC de Tenderdash inválida",
+        "No join rules for document type" => "No hay reglas de unión para el tipo de documento",
+        "Threshold and top up amount must be positive" => {
+            "El umbral y el monto de recarga deben ser positivos"
+        }
+        "State transition failed" => "Falló la transición de estado",
+        "Invalid state transition result response" => {
+            "Respuesta de resultado de la transición de estado inválida"
+        }
+        "Unable to verify state transition proof" => {
+            "No se pudo verificar la prueba de la transición de estado"
+        }
+        "Already using this profile" => "Ya se está usando este perfil",
+        "No DAPI addresses given" => "No se indicaron direcciones DAPI",
+        "Invalid DAPI address" => "Dirección DAPI inválida",
+        "Invalid Core host" => "Host de Core inválido",
+        "Invalid Core RPC port" => "Puerto RPC de Core inválido",
+        "Core RPC user is empty" => "El usuario RPC de Core está vacío",
+        "Invalid Insight API URL" => "URL de la API de Insight inválida",
+        "No known identities to top up" => "No hay identidades conocidas para recargar",
+        "Failed to copy Identity Id" => "No se pudo copiar el ID de identidad",
+        "Failed to split UTXOS properly" => "No se pudieron dividir los UTXO",
+        "Invalid identity id" | "Invalid identity ID" => "ID de identidad inválido",
+        "Invalid contract id" => "ID de contrato inválido",
+        "Invalid document id" => "ID de documento inválido",
+        "Invalid private key" => "Clave privada inválida",
+        "Invalid WIF key" => "Clave WIF inválida",
+        "Invalid hex key" => "Clave hexadecimal inválida",
+        "Invalid schema" => "Esquema inválido",
+        "Invalid proposed schema JSON" => "JSON de esquema propuesto inválido",
+        "Unable to read proposed schema file" => "No se pudo leer el archivo de esquema propuesto",
+        "Invalid contract JSON" => "JSON de contrato inválido",
+        "Unable to read contract file" => "No se pudo leer el archivo de contrato",
+        "Contract file must contain an object of document schemas" => {
+            "El archivo de contrato debe contener un objeto de esquemas de documentos"
+        }
+        "Contract file path has no file name" => {
+            "La ruta del archivo de contrato no tiene nombre de archivo"
+        }
+        "Strategy error" => "Error de estrategia",
+        "Unable to reload log filter" => "No se pudo recargar el filtro de registro",
+        "Unable to get identity nonce" => "No se pudo obtener el nonce de la identidad",
+        "Passphrase must not be empty" => "La frase de contraseña no debe estar vacía",
+        "Wrong passphrase or corrupted data" => "Frase de contraseña incorrecta o datos dañados",
+        "Unable to get identity contract nonce" => {
+            "No se pudo obtener el nonce de contrato de la identidad"
+        }
+        "Schema update would be rejected" => "La actualización del esquema sería rechazada",
+        "Unable to fetch fee rate estimates" => "No se pudieron obtener las tarifas estimadas",
+        "Unable to create state transition" => "No se pudo crear la transición de estado",
+        "Failed to fetch strategy" => "No se pudo obtener la estrategia",
+        "Failed to fetch strategy data" => "No se pudieron obtener los datos de la estrategia",
+        "Failed to serialize strategy" => "No se pudo serializar la estrategia",
+        "Failed to write strategy to file" => "No se pudo escribir la estrategia en el archivo",
+        "Failed to serialize report" => "No se pudo serializar el informe",
+        "Failed to write report" => "No se pudo escribir el informe",
+        "Failed to write export file" => "No se pudo escribir el archivo de exportación",
+        "Failed to write attestation file" => "No se pudo escribir el archivo de atestación",
+        "Failed to serialize bookmarks" => "No se pudieron serializar los marcadores",
+        "Failed to save bookmarks" => "No se pudieron guardar los marcadores",
+        "Failed to serialize strategy phases" => {
+            "No se pudieron serializar las fases de la estrategia"
+        }
+        "Failed to save strategy phases" => "No se pudieron guardar las fases de la estrategia",
+        "Warm-up and cool-down leave nothing of the run" => {
+            "El calentamiento y el enfriamiento no dejan nada de la ejecución"
+        }
+        "DPNS error" => "Error de DPNS",
+        "Label is too short" => "La etiqueta es demasiado corta",
+        "Label is too long" => "La etiqueta es demasiado larga",
+        "Invalid character in label" => "Carácter inválido en la etiqueta",
+        "Label starts or ends with a hyphen" => "La etiqueta empieza o termina con un guion",
+        "Name is already registered" => "El nombre ya está registrado",
+        "Failed to fetch DPNS contract" => "No se pudo obtener el contrato DPNS",
+        "DPNS contract not found" => "No se encontró el contrato DPNS",
+        "Failed to look up DPNS name" => "No se pudo buscar el nombre DPNS",
+        "Failed to copy to clipboard" => "No se pudo copiar al portapapeles",
+        "Failed to create export directory" => "No se pudo crear el directorio de exportación",
+        "Identity not found" => "No se encontró la identidad",
+        "Unable to verify proof" => "No se pudo verificar la prueba",
+        "Invalid response" => "Respuesta inválida",
+        "State transition rejected" => "Transición de estado rechazada",
+
+        _ => return None,
+    };
+    Some(translated)
+}
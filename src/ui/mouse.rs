@@ -0,0 +1,76 @@
+//! Mouse input.
+//! The wheel scrolls as arrow keys do and left clicks select list rows.
+//! Widgets reacting to the mouse keep the area they were last drawn in to tell
+//! whether the mouse points at them.
+
+use crossterm::event::{MouseButton, MouseEvent, MouseEventKind};
+use tuirealm::{
+    command::{Cmd, Direction},
+    event::{Key, KeyEvent, KeyModifiers},
+    props::Borders,
+    tui::{prelude::Rect, widgets::Block},
+    MockComponent,
+};
+
+pub(crate) enum MouseAction {
+    ScrollUp,
+    ScrollDown,
+    /// Left click on a row, counted from the top of the area
+    Click(u16),
+}
+
+/// Action of the mouse event, `None` if it happened outside of the area.
+pub(crate) fn action_within(event: &MouseEvent, area: Rect) -> Option<MouseAction> {
+    if event.column < area.x
+        || event.column >= area.x + area.width
+        || event.row < area.y
+        || event.row >= area.y + area.height
+    {
+        return None;
+    }
+    match event.kind {
+        MouseEventKind::ScrollUp => Some(MouseAction::ScrollUp),
+        MouseEventKind::ScrollDown => Some(MouseAction::ScrollDown),
+        MouseEventKind::Down(MouseButton::Left) => Some(MouseAction::Click(event.row - area.y)),
+        _ => None,
+    }
+}
+
+/// Arrow key the wheel stands for, for screens scrolling with arrow keys.
+pub(crate) fn wheel_key(event: &MouseEvent) -> Option<KeyEvent> {
+    let code = match event.kind {
+        MouseEventKind::ScrollUp => Key::Up,
+        MouseEventKind::ScrollDown => Key::Down,
+        _ => return None,
+    };
+    Some(KeyEvent {
+        code,
+        modifiers: KeyModifiers::NONE,
+    })
+}
+
+/// Area of a widget within its borders.
+pub(crate) fn inner_area(area: Rect, borders: &Borders) -> Rect {
+    Block::new().borders(borders.sides).inner(area)
+}
+
+/// Index of the clicked row of a list of `len` rows drawn `height` rows high.
+/// Lists are drawn from their first row, unless the selected one is below the
+/// area: then they're scrolled to draw it on the last row.
+pub(crate) fn list_row(row: u16, height: u16, selected: usize, len: usize) -> Option<usize> {
+    let offset = (selected + 1).saturating_sub(height as usize);
+    let index = offset + row as usize;
+    (index < len).then_some(index)
+}
+
+/// Moves the list highlight from the selected row to another one.
+pub(crate) fn select_row(list: &mut tui_realm_stdlib::List, selected: usize, row: usize) {
+    let direction = if row > selected {
+        Direction::Down
+    } else {
+        Direction::Up
+    };
+    for _ in 0..selected.abs_diff(row) {
+        list.perform(Cmd::Move(direction));
+    }
+}
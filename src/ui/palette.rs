@@ -0,0 +1,341 @@
+//! Command palette.
+//! Ctrl-K opens an overlay that fuzzy matches what's typed over the screens
+//! and actions of the explorer and over the commands of the current screen.
+//! Picking an entry presses its keys for the user, starting from the main menu
+//! for entries found deeper in the menus, so any screen is a few letters away.
+
+use tuirealm::{
+    event::{Key, KeyEvent, KeyModifiers},
+    props::{Alignment, BorderSides, Color, Style},
+    tui::{
+        prelude::{Constraint, Direction, Layout, Modifier, Rect},
+        text::Line,
+        widgets::{Block, Paragraph},
+    },
+    Frame,
+};
+
+use super::{
+    density,
+    i18n::tr,
+    screen::{ScreenCommandKey, ScreenController},
+};
+
+/// Screens and actions reached from the main menu: where they are, their
+/// name, and keys pressed from the main menu to get there.
+const ENTRIES: [(&str, &str, &str); 57] = [
+    ("Main menu", "Main menu", ""),
+    ("Main menu", "Identities", "i"),
+    ("Main menu", "Contracts", "c"),
+    ("Main menu", "Strategies", "s"),
+    ("Main menu", "Wallet", "w"),
+    ("Main menu", "Version Upgrade", "v"),
+    ("Main menu", "Platform information", "p"),
+    ("Main menu", "Network", "n"),
+    ("Main menu", "Settings", "o"),
+    ("Main menu", "Tutorial", "t"),
+    ("Main menu", "Search", "/"),
+    ("Main menu", "Bookmarks", "b"),
+    ("Main menu", "Task history", "h"),
+    ("Identities", "Get Identity by ID", "ii"),
+    ("Identities", "Transfer credits", "it"),
+    ("Identities", "Register DPNS name", "ir"),
+    ("Identities", "Keep funded", "ik"),
+    ("Identities", "Verify key status", "iv"),
+    ("Identities", "Load identity bundle", "il"),
+    ("Identities", "Signing audit log", "ia"),
+    ("Identities", "Identity petnames", "im"),
+    ("Identities", "Sponsor identity registration", "is"),
+    ("Identities", "Top up known identities", "ib"),
+    ("Contracts", "Fetch system contract", "cs"),
+    ("System Contracts", "Fetch DPNS contract", "csn"),
+    ("System Contracts", "Fetch Dashpay contract", "csp"),
+    ("Contracts", "Fetch contract", "cf"),
+    ("Contracts", "Get Contract by ID", "cff"),
+    ("Contracts", "Refresh all contracts", "cu"),
+    ("Contracts", "Schema builder", "cn"),
+    ("Contracts", "Register contract from file", "cl"),
+    ("Contracts", "Dependency graph", "cg"),
+    ("Strategies", "New strategy", "sn"),
+    ("Strategies", "New strategy from template", "st"),
+    ("Strategies", "Import a strategy", "si"),
+    ("Strategies", "Compare run reports", "sc"),
+    ("Strategies", "Select a strategy", "ss"),
+    ("Wallet", "Register identity", "wi"),
+    ("Wallet", "Identity top up", "wt"),
+    ("Wallet", "Withdraw balance", "ww"),
+    ("Wallet", "Add Identity key", "wk"),
+    ("Wallet", "Export identity keys", "wy"),
+    ("Wallet", "Add wallet by private key", "wa"),
+    ("Wallet", "Switch wallet", "ws"),
+    ("Wallet", "UTXOs and coin control", "wx"),
+    ("Wallet", "Asset lock inspector", "wp"),
+    ("Wallet", "Transaction fee rate", "wf"),
+    (
+        "Platform Information",
+        "Fetch current Platform epoch info",
+        "pc",
+    ),
+    ("Platform Information", "Current version voting", "pv"),
+    ("Platform Information", "Network parameters changelog", "pl"),
+    ("Platform Information", "Export epochs to CSV", "px"),
+    ("Network", "Evonodes", "ne"),
+    ("Network", "Rewards of configured evonode", "nw"),
+    ("Settings", "Set log filter", "ol"),
+    ("Settings", "Switch display density", "od"),
+    ("Settings", "Switch proof details", "ov"),
+    ("Settings", "Network profiles", "on"),
+];
+
+pub(super) const MAIN_MENU: &str = "Main menu";
+
+/// Keys to press to run the picked entry.
+pub(super) struct PalettePick {
+    /// Keys are pressed on a new main menu screen rather than on the current
+    /// one
+    pub(super) from_main_menu: bool,
+    pub(super) keys: Vec<KeyEvent>,
+}
+
+pub(super) enum PaletteStatus {
+    Picked(PalettePick),
+    Redraw,
+    None,
+    Exit,
+}
+
+struct PaletteEntry {
+    /// Translated name and location, matched against the query
+    text: String,
+    location: &'static str,
+    name: &'static str,
+    from_main_menu: bool,
+    keys: Vec<KeyEvent>,
+}
+
+/// Key event of a command keybinding, only single keys are supported.
+fn keybinding_event(keybinding: &str) -> Option<KeyEvent> {
+    let (code, modifiers) = match keybinding {
+        "Enter" => (Key::Enter, KeyModifiers::NONE),
+        _ => match keybinding.strip_prefix("C-") {
+            Some(key) => (Key::Char(single_char(key)?), KeyModifiers::CONTROL),
+            None => (Key::Char(single_char(keybinding)?), KeyModifiers::NONE),
+        },
+    };
+    Some(KeyEvent { code, modifiers })
+}
+
+fn single_char(text: &str) -> Option<char> {
+    let mut chars = text.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) => Some(c),
+        _ => None,
+    }
+}
+
+/// Score of the query matched as a subsequence of the text, `None` if it
+/// doesn't match. Consecutive characters and word starts score higher, spaces
+/// of the query are ignored.
+fn fuzzy_score(query: &str, text: &str) -> Option<u32> {
+    let text: Vec<char> = text.to_lowercase().chars().collect();
+    let mut score = 0;
+    let mut position = 0;
+    for c in query.to_lowercase().chars().filter(|c| !c.is_whitespace()) {
+        let index = (position..text.len()).find(|&i| text[i] == c)?;
+        score += 1;
+        if index > 0 && index == position {
+            score += 3;
+        }
+        if index == 0 || text[index - 1] == ' ' {
+            score += 2;
+        }
+        position = index + 1;
+    }
+    Some(score)
+}
+
+pub(super) struct Palette {
+    query: String,
+    entries: Vec<PaletteEntry>,
+    /// Indices of entries matching the query, best first
+    matches: Vec<usize>,
+    selected: usize,
+}
+
+impl Palette {
+    /// Palette over the commands of the current screen followed by the
+    /// screens and actions of the menus.
+    pub(super) fn new(screen: &impl ScreenController) -> Self {
+        // Main menu commands are among the menu entries already
+        let commands = if screen.name() == MAIN_MENU {
+            &[]
+        } else {
+            screen.command_keys()
+        };
+        let screen_entries = commands.iter().filter_map(|key: &ScreenCommandKey| {
+            Some((
+                screen.name(),
+                key.description,
+                false,
+                vec![keybinding_event(key.keybinding)?],
+            ))
+        });
+        let menu_entries = ENTRIES.iter().map(|(location, name, keys)| {
+            (
+                *location,
+                *name,
+                true,
+                keys.chars()
+                    .map(|c| KeyEvent {
+                        code: Key::Char(c),
+                        modifiers: KeyModifiers::NONE,
+                    })
+                    .collect(),
+            )
+        });
+        let entries = screen_entries
+            .chain(menu_entries)
+            .map(|(location, name, from_main_menu, keys)| PaletteEntry {
+                text: format!("{} {}", tr(name), tr(location)),
+                location,
+                name,
+                from_main_menu,
+                keys,
+            })
+            .collect();
+
+        let mut palette = Palette {
+            query: String::new(),
+            entries,
+            matches: Vec::new(),
+            selected: 0,
+        };
+        palette.update_matches();
+        palette
+    }
+
+    fn update_matches(&mut self) {
+        let mut scored: Vec<(u32, usize)> = self
+            .entries
+            .iter()
+            .enumerate()
+            .filter_map(|(i, entry)| Some((fuzzy_score(&self.query, &entry.text)?, i)))
+            .collect();
+        // Sorting is stable, so equal scores keep the current screen first
+        scored.sort_by(|(a, _), (b, _)| b.cmp(a));
+        self.matches = scored.into_iter().map(|(_, i)| i).collect();
+        self.selected = 0;
+    }
+
+    pub(super) fn on_event(&mut self, event: KeyEvent) -> PaletteStatus {
+        match event {
+            KeyEvent { code: Key::Esc, .. }
+            | KeyEvent {
+                code: Key::Char('k'),
+                modifiers: KeyModifiers::CONTROL,
+            } => PaletteStatus::Exit,
+            KeyEvent {
+                code: Key::Enter, ..
+            } => match self.matches.get(self.selected) {
+                Some(&i) => {
+                    let entry = &self.entries[i];
+                    PaletteStatus::Picked(PalettePick {
+                        from_main_menu: entry.from_main_menu,
+                        keys: entry.keys.clone(),
+                    })
+                }
+                None => PaletteStatus::None,
+            },
+            KeyEvent { code: Key::Up, .. }
+            | KeyEvent {
+                code: Key::Char('p'),
+                modifiers: KeyModifiers::CONTROL,
+            } => {
+                self.selected = self.selected.saturating_sub(1);
+                PaletteStatus::Redraw
+            }
+            KeyEvent {
+                code: Key::Down, ..
+            }
+            | KeyEvent {
+                code: Key::Char('n'),
+                modifiers: KeyModifiers::CONTROL,
+            } => {
+                if self.selected + 1 < self.matches.len() {
+                    self.selected += 1;
+                }
+                PaletteStatus::Redraw
+            }
+            KeyEvent {
+                code: Key::Backspace,
+                ..
+            } => {
+                self.query.pop();
+                self.update_matches();
+                PaletteStatus::Redraw
+            }
+            KeyEvent {
+                code: Key::Char(c), ..
+            } => {
+                self.query.push(c);
+                self.update_matches();
+                PaletteStatus::Redraw
+            }
+            _ => PaletteStatus::None,
+        }
+    }
+
+    pub(super) fn view(&self, frame: &mut Frame, area: Rect) {
+        let sides = if density::is_compact() {
+            BorderSides::TOP
+        } else {
+            BorderSides::ALL
+        };
+        let block = Block::new()
+            .borders(sides)
+            .title(tr("Command palette"))
+            .title_alignment(Alignment::Left);
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        let layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(2), Constraint::Min(1)].as_ref())
+            .split(inner);
+
+        frame.render_widget(
+            Paragraph::new(format!("> {}", self.query)).style(Style::default().fg(Color::Yellow)),
+            layout[0],
+        );
+
+        if self.matches.is_empty() {
+            frame.render_widget(Paragraph::new(tr("No matches")), layout[1]);
+            return;
+        }
+        // The list scrolls to keep the selected entry in view
+        let height = layout[1].height.max(1) as usize;
+        let offset = (self.selected + 1).saturating_sub(height);
+        let lines: Vec<Line> = self
+            .matches
+            .iter()
+            .enumerate()
+            .skip(offset)
+            .take(height)
+            .map(|(position, &i)| {
+                let entry = &self.entries[i];
+                let text = format!("{}  ({})", tr(entry.name), tr(entry.location));
+                if position == self.selected {
+                    Line::styled(
+                        text,
+                        Style::default()
+                            .fg(Color::Magenta)
+                            .add_modifier(Modifier::BOLD | Modifier::REVERSED),
+                    )
+                } else {
+                    Line::from(text)
+                }
+            })
+            .collect();
+        frame.render_widget(Paragraph::new(lines), layout[1]);
+    }
+}
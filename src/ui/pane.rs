@@ -0,0 +1,120 @@
+//! Split workspace panes.
+//!
+//! The screen area can be split into two panes side by side, each with its own
+//! screen stack, so related screens are watched at once. Only the focused pane
+//! takes keys and shows forms, the other one keeps following backend events.
+//! Switching the focus swaps the other pane with the UI's current screens, so
+//! the rest of the UI only ever deals with the focused pane.
+
+use tuirealm::{
+    props::{Color, Style},
+    tui::{
+        prelude::{Constraint, Direction, Layout, Modifier, Rect},
+        widgets::Paragraph,
+    },
+    Frame,
+};
+
+use super::{
+    i18n::tr,
+    screen::{Screen, ScreenController},
+    views::main::MainScreenController,
+};
+use crate::{backend::BackendEvent, Event};
+
+/// Pane not in focus, with the breadcrumbs the status bar shows for it once
+/// focused.
+pub(super) struct Pane {
+    pub(super) screen: Screen<Box<dyn ScreenController>>,
+    pub(super) screen_stack: Vec<Screen<Box<dyn ScreenController>>>,
+    pub(super) breadcrumbs: Vec<&'static str>,
+}
+
+impl Pane {
+    /// New panes start on the main menu.
+    pub(super) fn main_menu() -> Self {
+        let controller = MainScreenController::new();
+        let breadcrumbs = vec![controller.name()];
+        Pane {
+            screen: Screen::new(Box::new(controller)),
+            screen_stack: Vec::new(),
+            breadcrumbs,
+        }
+    }
+
+    /// Passes a backend event to the pane's screens like the focused pane
+    /// does, except what the screen asks for in return is left out.
+    pub(super) fn on_backend_event(&mut self, event: &Event) {
+        self.screen.on_event(event);
+        if let Event::Backend(
+            BackendEvent::AppStateUpdated(_) | BackendEvent::TaskCompletedStateChange { .. },
+        ) = event
+        {
+            for screen in self.screen_stack.iter_mut() {
+                screen.on_event(event);
+            }
+        }
+    }
+}
+
+pub(super) struct Split {
+    pub(super) other: Pane,
+    /// The focused pane is the left one
+    pub(super) focused_left: bool,
+}
+
+impl Split {
+    /// Areas of the focused pane and of the other one below their headers,
+    /// the headers are drawn along.
+    pub(super) fn layout(
+        &self,
+        frame: &mut Frame,
+        area: Rect,
+        focused_breadcrumbs: &[&'static str],
+    ) -> (Rect, Rect) {
+        let halves = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)].as_ref())
+            .split(area);
+        let (focused, other) = if self.focused_left {
+            (halves[0], halves[1])
+        } else {
+            (halves[1], halves[0])
+        };
+        (
+            view_header(frame, focused, focused_breadcrumbs, true),
+            view_header(frame, other, &self.other.breadcrumbs, false),
+        )
+    }
+}
+
+/// Draws the pane's header line, returns the area left for the pane.
+fn view_header(frame: &mut Frame, area: Rect, breadcrumbs: &[&'static str], focused: bool) -> Rect {
+    let layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(1), Constraint::Min(1)].as_ref())
+        .split(area);
+
+    let title = breadcrumbs
+        .iter()
+        .map(|name| tr(name))
+        .collect::<Vec<_>>()
+        .join(" / ");
+    let header = if focused {
+        Paragraph::new(format!(
+            " > {}  (F3 {}, F2 {})",
+            title,
+            tr("to switch pane"),
+            tr("to close the other pane")
+        ))
+        .style(
+            Style::default()
+                .fg(Color::Magenta)
+                .add_modifier(Modifier::BOLD),
+        )
+    } else {
+        Paragraph::new(format!("   {}", title))
+    };
+    frame.render_widget(header, layout[0]);
+    layout[1]
+}
@@ -0,0 +1,77 @@
+//! Proof details of fetched entities.
+//! While proof details are on, identities, contracts and documents are fetched
+//! along with details of the proof they were verified with, screens may also
+//! do so for a single query. The proof is shown in a panel below the entity,
+//! collapsed to a summary line unless expanded.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use tuirealm::{
+    props::{Alignment, BorderSides, Color, TextSpan},
+    tui::prelude::{Constraint, Direction, Layout, Rect},
+    AttrValue, Attribute, Frame, MockComponent,
+};
+
+use super::{density, i18n::tr};
+use crate::backend::proofs::ProofDetails;
+
+static PROOF_DETAILS: AtomicBool = AtomicBool::new(false);
+
+pub(crate) fn set_proof_details(enabled: bool) {
+    PROOF_DETAILS.store(enabled, Ordering::Relaxed);
+}
+
+pub(crate) fn proof_details() -> bool {
+    PROOF_DETAILS.load(Ordering::Relaxed)
+}
+
+/// Panel showing the proof of the entity above it.
+pub(crate) struct ProofPanel {
+    proof: ProofDetails,
+    expanded: bool,
+}
+
+impl ProofPanel {
+    pub(crate) fn new(proof: ProofDetails, expanded: bool) -> Self {
+        ProofPanel { proof, expanded }
+    }
+
+    pub(crate) fn set_expanded(&mut self, expanded: bool) {
+        self.expanded = expanded;
+    }
+
+    /// Draws the panel at the bottom of the area, returns the area left above
+    /// it.
+    pub(crate) fn view(&self, frame: &mut Frame, area: Rect) -> Rect {
+        let text = if self.expanded {
+            self.proof.details()
+        } else {
+            self.proof.summary()
+        };
+        let rows: Vec<TextSpan> = text.lines().map(TextSpan::new).collect();
+        // Compact panels keep a top border to separate them from the entity
+        let borders = if density::is_compact() { 1 } else { 2 };
+
+        let layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(
+                [
+                    Constraint::Min(1),
+                    Constraint::Length(rows.len() as u16 + borders),
+                ]
+                .as_ref(),
+            )
+            .split(area);
+
+        let mut panel = tui_realm_stdlib::Textarea::default()
+            .foreground(Color::Green)
+            .title(tr("Proof"), Alignment::Left)
+            .text_rows(&rows);
+        panel.attr(
+            Attribute::Borders,
+            AttrValue::Borders(density::borders(BorderSides::ALL, BorderSides::TOP)),
+        );
+        panel.view(frame, layout[1]);
+        layout[0]
+    }
+}
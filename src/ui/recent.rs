@@ -0,0 +1,292 @@
+//! Recent entities switcher.
+//! Identities, contracts, document types and documents are listed as they're
+//! viewed, most recent first. Ctrl-Tab, or F4 where terminals send it as Tab,
+//! opens an overlay to jump back to any of them, starting on the one viewed
+//! before the current one as editors do for their buffers.
+
+use std::collections::{BTreeMap, VecDeque};
+
+use dash_sdk::platform::DocumentQuery;
+use dpp::{
+    data_contract::accessors::v0::DataContractV0Getters,
+    document::{Document, DocumentV0Getters},
+    platform_value::string_encoding::Encoding,
+};
+use futures::FutureExt;
+use tuirealm::{
+    event::{Key, KeyEvent, KeyModifiers},
+    props::{Alignment, BorderSides, Color, Style},
+    tui::{
+        prelude::{Modifier, Rect},
+        text::Line,
+        widgets::{Block, Paragraph},
+    },
+    Frame,
+};
+
+use super::{
+    density,
+    i18n::tr,
+    screen::{ScreenController, ScreenFeedback},
+    views::{
+        contracts::{
+            ContractsScreenController, DocumentTypeScreenController,
+            SelectDocumentTypeFormController,
+        },
+        documents::DocumentsQuerysetScreenController,
+        identities::IdentitiesScreenController,
+    },
+};
+use crate::backend::{AppState, Task};
+
+/// Number of entities kept.
+const MAX_RECENT: usize = 10;
+
+#[derive(Clone)]
+pub(crate) enum RecentEntity {
+    /// Base58 identity ID
+    Identity(String),
+    Contract {
+        name: String,
+    },
+    DocumentType {
+        contract_name: String,
+        document_type_name: String,
+    },
+    Document {
+        query: DocumentQuery,
+        document: Document,
+    },
+}
+
+impl RecentEntity {
+    fn is_same(&self, other: &RecentEntity) -> bool {
+        match (self, other) {
+            (RecentEntity::Identity(a), RecentEntity::Identity(b)) => a == b,
+            (RecentEntity::Contract { name: a }, RecentEntity::Contract { name: b }) => a == b,
+            (
+                RecentEntity::DocumentType {
+                    contract_name: contract_a,
+                    document_type_name: document_type_a,
+                },
+                RecentEntity::DocumentType {
+                    contract_name: contract_b,
+                    document_type_name: document_type_b,
+                },
+            ) => contract_a == contract_b && document_type_a == document_type_b,
+            (
+                RecentEntity::Document { document: a, .. },
+                RecentEntity::Document { document: b, .. },
+            ) => a.id() == b.id(),
+            _ => false,
+        }
+    }
+
+    /// Name of the entity and what kind of entity it is.
+    fn label(&self) -> (String, &'static str) {
+        match self {
+            RecentEntity::Identity(id) => (id.clone(), "Identity"),
+            RecentEntity::Contract { name } => (name.clone(), "Contract"),
+            RecentEntity::DocumentType {
+                contract_name,
+                document_type_name,
+            } => (
+                format!("{} / {}", contract_name, document_type_name),
+                "Document type",
+            ),
+            RecentEntity::Document { query, document } => (
+                format!(
+                    "{} {}",
+                    query.document_type_name,
+                    document.id().to_string(Encoding::Base58)
+                ),
+                "Document",
+            ),
+        }
+    }
+
+    /// What the screen would ask for to show the entity again, `None` if its
+    /// contract was removed since. Identities are fetched again as they may
+    /// have changed.
+    pub(super) async fn open(self, app_state: &AppState) -> Option<Vec<ScreenFeedback>> {
+        let feedbacks = match self {
+            RecentEntity::Identity(id) => vec![
+                ScreenFeedback::NextScreen(IdentitiesScreenController::builder()),
+                ScreenFeedback::Task {
+                    task: Task::FetchIdentityById(id, false),
+                    block: true,
+                },
+            ],
+            RecentEntity::Contract { name } => {
+                let document_type_names = app_state
+                    .known_contracts
+                    .lock()
+                    .await
+                    .get(&name)?
+                    .document_types()
+                    .keys()
+                    .cloned()
+                    .collect();
+                vec![ScreenFeedback::FormThenNextScreen {
+                    form: Box::new(SelectDocumentTypeFormController::new(
+                        name,
+                        document_type_names,
+                    )),
+                    screen: ContractsScreenController::builder(),
+                }]
+            }
+            RecentEntity::DocumentType {
+                contract_name,
+                document_type_name,
+            } => {
+                app_state
+                    .known_contracts
+                    .lock()
+                    .await
+                    .get(&contract_name)?
+                    .document_type_for_name(&document_type_name)
+                    .ok()?;
+                vec![ScreenFeedback::NextScreen(
+                    DocumentTypeScreenController::builder(contract_name, document_type_name),
+                )]
+            }
+            RecentEntity::Document { query, document } => {
+                let documents = BTreeMap::from([(document.id(), Some(document))]);
+                vec![ScreenFeedback::NextScreen(Box::new(move |_| {
+                    async move {
+                        Box::new(DocumentsQuerysetScreenController::new(query, documents))
+                            as Box<dyn ScreenController>
+                    }
+                    .boxed()
+                }))]
+            }
+        };
+        Some(feedbacks)
+    }
+}
+
+/// Recently viewed entities, most recent first.
+#[derive(Default)]
+pub(super) struct RecentEntities(VecDeque<RecentEntity>);
+
+impl RecentEntities {
+    /// Moves the entity to the front, dropping the oldest one past the limit.
+    pub(super) fn visit(&mut self, entity: RecentEntity) {
+        self.remove(&entity);
+        self.0.push_front(entity);
+        self.0.truncate(MAX_RECENT);
+    }
+
+    pub(super) fn remove(&mut self, entity: &RecentEntity) {
+        self.0.retain(|recent| !recent.is_same(entity));
+    }
+}
+
+pub(super) enum SwitcherStatus {
+    Picked(RecentEntity),
+    Redraw,
+    None,
+    Exit,
+}
+
+/// Overlay listing recent entities to pick one.
+pub(super) struct Switcher {
+    entities: Vec<RecentEntity>,
+    selected: usize,
+}
+
+impl Switcher {
+    pub(super) fn new(recent: &RecentEntities) -> Self {
+        let entities: Vec<RecentEntity> = recent.0.iter().cloned().collect();
+        Switcher {
+            selected: 1.min(entities.len().saturating_sub(1)),
+            entities,
+        }
+    }
+
+    fn select_next(&mut self) {
+        if !self.entities.is_empty() {
+            self.selected = (self.selected + 1) % self.entities.len();
+        }
+    }
+
+    fn select_previous(&mut self) {
+        if !self.entities.is_empty() {
+            self.selected = (self.selected + self.entities.len() - 1) % self.entities.len();
+        }
+    }
+
+    pub(super) fn on_event(&mut self, event: KeyEvent) -> SwitcherStatus {
+        match event {
+            KeyEvent { code: Key::Esc, .. } => SwitcherStatus::Exit,
+            KeyEvent {
+                code: Key::Enter, ..
+            } => match self.entities.get(self.selected) {
+                Some(entity) => SwitcherStatus::Picked(entity.clone()),
+                None => SwitcherStatus::Exit,
+            },
+            KeyEvent {
+                code: Key::Tab | Key::Down | Key::Function(4),
+                ..
+            }
+            | KeyEvent {
+                code: Key::Char('n'),
+                modifiers: KeyModifiers::CONTROL,
+            } => {
+                self.select_next();
+                SwitcherStatus::Redraw
+            }
+            KeyEvent {
+                code: Key::BackTab | Key::Up,
+                ..
+            }
+            | KeyEvent {
+                code: Key::Char('p'),
+                modifiers: KeyModifiers::CONTROL,
+            } => {
+                self.select_previous();
+                SwitcherStatus::Redraw
+            }
+            _ => SwitcherStatus::None,
+        }
+    }
+
+    pub(super) fn view(&self, frame: &mut Frame, area: Rect) {
+        let sides = if density::is_compact() {
+            BorderSides::TOP
+        } else {
+            BorderSides::ALL
+        };
+        let block = Block::new()
+            .borders(sides)
+            .title(tr("Recent entities"))
+            .title_alignment(Alignment::Left);
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        if self.entities.is_empty() {
+            frame.render_widget(Paragraph::new(tr("No recent entities")), inner);
+            return;
+        }
+        let lines: Vec<Line> = self
+            .entities
+            .iter()
+            .enumerate()
+            .map(|(i, entity)| {
+                let (name, kind) = entity.label();
+                let text = format!("{}  ({})", name, tr(kind));
+                if i == self.selected {
+                    Line::styled(
+                        text,
+                        Style::default()
+                            .fg(Color::Magenta)
+                            .add_modifier(Modifier::BOLD | Modifier::REVERSED),
+                    )
+                } else {
+                    Line::from(text)
+                }
+            })
+            .collect();
+        frame.render_widget(Paragraph::new(lines), inner);
+    }
+}
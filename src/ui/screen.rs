@@ -8,12 +8,16 @@ use std::ops::{Deref, DerefMut};
 
 use futures::future::BoxFuture;
 use tuirealm::{
-    tui::prelude::{Constraint, Direction, Layout, Rect},
+    event::{Key, KeyEvent, KeyModifiers},
+    tui::{
+        prelude::{Constraint, Direction, Layout, Rect},
+        widgets::Paragraph,
+    },
     Frame,
 };
 
 use self::widgets::command_pallet;
-use super::{form::FormController, Event};
+use super::{density, form::FormController, i18n::tr, mouse, recent::RecentEntity, Event};
 use crate::backend::{AppState, Task};
 
 /// Screen is the unit of navigation and representation in the TUI.
@@ -25,27 +29,129 @@ use crate::backend::{AppState, Task};
 /// Because all the screens are the same thing to draw it's one generic type,
 /// however, they're different about what keys to show and how to process them,
 /// so we use a generic [ScreenController] here.
+///
+/// On short terminals both blocks don't fit, so they're collapsed into tabs
+/// switched with Tab key.
 pub(crate) struct Screen<C: ScreenController> {
     pub controller: C,
+    /// Screen area was too short for both blocks on the last draw
+    compact: bool,
+    /// Command pallet tab is shown instead of the info in compact mode
+    commands_tab: bool,
+}
+
+/// Screens shorter than that are drawn in compact mode.
+const COMPACT_HEIGHT: u16 = 20;
+
+/// Whether `r` refreshes the screen, which is the case unless the screen binds
+/// it to another command. F5 refreshes any screen.
+fn refreshes_with_r(controller: &impl ScreenController) -> bool {
+    !controller
+        .command_keys()
+        .iter()
+        .any(|key| key.keybinding == "r")
+}
+
+/// Keys refreshing the screen, for display.
+fn refresh_keybinding(controller: &impl ScreenController) -> Keybinding {
+    if refreshes_with_r(controller) {
+        "F5 / r"
+    } else {
+        "F5"
+    }
+}
+
+fn is_refresh_key(event: &KeyEvent, controller: &impl ScreenController) -> bool {
+    match event {
+        KeyEvent {
+            code: Key::Function(5),
+            modifiers: KeyModifiers::NONE,
+        } => true,
+        KeyEvent {
+            code: Key::Char('r'),
+            modifiers: KeyModifiers::NONE,
+        } => refreshes_with_r(controller),
+        _ => false,
+    }
 }
 
 impl<C: ScreenController> Screen<C> {
     pub(super) fn new(controller: C) -> Self {
-        Screen { controller }
+        Screen {
+            controller,
+            compact: false,
+            commands_tab: false,
+        }
     }
 
     pub(super) fn view(&mut self, frame: &mut Frame, area: Rect) {
+        self.compact = area.height < COMPACT_HEIGHT;
+        if self.compact {
+            return self.view_compact(frame, area);
+        }
+
+        // In compact density the pallet doesn't keep spare rows
+        let pallet_constraint = if density::is_compact() {
+            Constraint::Length(command_pallet::height(area.width, &self.controller).min(10))
+        } else {
+            Constraint::Max(10)
+        };
         let layout = Layout::default()
             .direction(Direction::Vertical)
-            .constraints([Constraint::Min(10), Constraint::Max(10)].as_ref())
+            .constraints([Constraint::Min(10), pallet_constraint].as_ref())
             .split(area);
 
         self.controller.view(frame, layout[0]);
         command_pallet::view(frame, layout[1], &self.controller);
     }
 
+    fn view_compact(&mut self, frame: &mut Frame, area: Rect) {
+        let layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(1), Constraint::Min(1)].as_ref())
+            .split(area);
+
+        let (info, commands) = (tr("Info"), tr("Commands"));
+        let tabs = if self.commands_tab {
+            format!(" {} | [{}]  ({})", info, commands, tr("Tab to switch"))
+        } else {
+            format!(" [{}] | {}  ({})", info, commands, tr("Tab to switch"))
+        };
+        frame.render_widget(Paragraph::new(tabs), layout[0]);
+
+        if self.commands_tab {
+            command_pallet::view(frame, layout[1], &self.controller);
+        } else {
+            self.controller.view(frame, layout[1]);
+        }
+    }
+
     pub(super) fn on_event(&mut self, event: &Event) -> ScreenFeedback {
-        self.controller.on_event(event)
+        if let Event::Key(KeyEvent {
+            code: Key::Tab,
+            modifiers: KeyModifiers::NONE,
+        }) = event
+        {
+            if self.compact {
+                self.commands_tab = !self.commands_tab;
+                return ScreenFeedback::Redraw;
+            }
+        }
+        if let Event::Key(key_event) = event {
+            if is_refresh_key(key_event, &self.controller) {
+                if let Some(task) = self.controller.refresh_task() {
+                    return ScreenFeedback::Task { task, block: true };
+                }
+            }
+        }
+        let feedback = self.controller.on_event(event);
+        // Screens not handling the wheel scroll with it as with arrow keys
+        if let (ScreenFeedback::None, Event::Mouse(mouse_event)) = (&feedback, event) {
+            if let Some(key_event) = mouse::wheel_key(mouse_event) {
+                return self.controller.on_event(&Event::Key(key_event));
+            }
+        }
+        feedback
     }
 }
 
@@ -62,8 +168,27 @@ pub(crate) trait ScreenController {
     fn toggle_keys(&self) -> &[ScreenToggleKey];
 
     /// Process key event, returning details on what's needed to be updated on
-    /// UI.
+    /// UI. Mouse wheel events left unhandled are processed again as arrow
+    /// keys.
     fn on_event(&mut self, event: &Event) -> ScreenFeedback;
+
+    /// Task fetching data the user is likely to need next on this screen, run
+    /// in background once the UI is idle.
+    fn prefetch_task(&self) -> Option<Task> {
+        None
+    }
+
+    /// Task fetching again the data shown on this screen, issued with the
+    /// refresh key. Only caches of the data it fetches are invalidated.
+    fn refresh_task(&self) -> Option<Task> {
+        None
+    }
+
+    /// Entity shown on this screen if it changed since the last call, listed
+    /// among recent entities to switch back to.
+    fn take_viewed_entity(&mut self) -> Option<RecentEntity> {
+        None
+    }
 }
 
 impl ScreenController for Box<dyn ScreenController> {
@@ -86,6 +211,18 @@ impl ScreenController for Box<dyn ScreenController> {
     fn on_event(&mut self, event: &Event) -> ScreenFeedback {
         self.deref_mut().on_event(event)
     }
+
+    fn prefetch_task(&self) -> Option<Task> {
+        self.deref().prefetch_task()
+    }
+
+    fn refresh_task(&self) -> Option<Task> {
+        self.deref().refresh_task()
+    }
+
+    fn take_viewed_entity(&mut self) -> Option<RecentEntity> {
+        self.deref_mut().take_viewed_entity()
+    }
 }
 
 type Keybinding = &'static str;
@@ -136,6 +273,10 @@ pub(crate) enum ScreenFeedback {
     }, // TODO task should define whether it blocks or not
     Redraw,
     Quit,
+    /// Restart the explorer with another network profile
+    SwitchProfile(String),
+    /// Copy the text to the system clipboard
+    Copy(String),
     None,
 }
 
@@ -2,31 +2,81 @@
 
 use itertools::Itertools;
 use tui_realm_stdlib::Table;
-use tuirealm::{props::TextSpan, tui::prelude::Rect, Frame, MockComponent};
+use tuirealm::{
+    props::{BorderSides, TextSpan},
+    tui::prelude::Rect,
+    Frame, MockComponent,
+};
 
-use crate::ui::screen::ScreenController;
+use crate::ui::{
+    density,
+    i18n::tr,
+    screen::{refresh_keybinding, ScreenController},
+};
 
 const KEYS_PER_ROW: usize = 3;
 
+/// Narrower cells would cut most of key descriptions.
+const MIN_CELL_WIDTH: u16 = 24;
+
+/// Rows taken by the table borders.
+fn borders_height() -> u16 {
+    if density::is_compact() {
+        0
+    } else {
+        2
+    }
+}
+
+fn keys_per_row(width: u16) -> usize {
+    ((width / MIN_CELL_WIDTH) as usize).clamp(1, KEYS_PER_ROW)
+}
+
+/// Rows the pallet needs to show all keys of the screen at the given width.
+pub(crate) fn height(width: u16, controller: &impl ScreenController) -> u16 {
+    let keys = controller.command_keys().len()
+        + controller.toggle_keys().len()
+        + usize::from(controller.refresh_task().is_some());
+    keys.div_ceil(keys_per_row(width)) as u16 + borders_height()
+}
+
 pub(crate) fn view(frame: &mut Frame, area: Rect, controller: &impl ScreenController) {
     let commands = controller.command_keys().to_owned();
     let toggles = controller.toggle_keys().to_owned();
 
-    let mut table_vec = Vec::new();
-
-    for row in &commands
+    let mut spans: Vec<TextSpan> = commands
         .iter()
-        .map(|c| TextSpan::new(format!("{} - {}", c.keybinding, c.description)))
+        .map(|c| TextSpan::new(format!("{} - {}", c.keybinding, tr(c.description))))
+        .chain(controller.refresh_task().map(|_| {
+            TextSpan::new(format!(
+                "{} - {}",
+                refresh_keybinding(controller),
+                tr("Refresh")
+            ))
+        }))
         .chain(toggles.iter().map(|t| {
-            let span = TextSpan::new(format!("{} - {}", t.keybinding, t.description)).italic();
+            let span = TextSpan::new(format!("{} - {}", t.keybinding, tr(t.description))).italic();
             if t.toggle {
                 span.bold()
             } else {
                 span
             }
         }))
-        .chunks(KEYS_PER_ROW)
-    {
+        .collect();
+
+    // Fewer keys per row on narrow terminals and, if they still don't fit, the
+    // rest is replaced with a "more" indicator
+    let keys_per_row = keys_per_row(area.width);
+    let capacity = area.height.saturating_sub(borders_height()) as usize * keys_per_row;
+    if capacity > 0 && spans.len() > capacity {
+        let hidden = spans.len() - capacity + 1;
+        spans.truncate(capacity - 1);
+        spans.push(TextSpan::new(format!("… +{} {}", hidden, tr("more"))).italic());
+    }
+
+    let mut table_vec = Vec::new();
+
+    for row in &spans.into_iter().chunks(keys_per_row) {
         let mut row_vec = Vec::new();
         for span in row {
             row_vec.push(span);
@@ -35,5 +85,8 @@ pub(crate) fn view(frame: &mut Frame, area: Rect, controller: &impl ScreenContro
         table_vec.push(row_vec);
     }
 
-    Table::default().table(table_vec).view(frame, area);
+    Table::default()
+        .borders(density::borders(BorderSides::ALL, BorderSides::NONE))
+        .table(table_vec)
+        .view(frame, area);
 }
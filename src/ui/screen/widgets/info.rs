@@ -4,25 +4,39 @@ use tui_realm_stdlib::Textarea;
 use tuirealm::{
     command::{Cmd, Direction},
     event::{Key, KeyEvent, KeyModifiers},
-    props::{Color, TextSpan},
+    props::{BorderSides, Color, TextSpan},
     tui::prelude::Rect,
-    Frame, MockComponent,
+    AttrValue, Attribute, Frame, MockComponent,
 };
 
-use crate::backend::CompletedTaskPayload;
+use crate::{
+    backend::{petnames::annotate, CompletedTaskPayload},
+    ui::{density, i18n::tr_message},
+};
 
 /// Textarea to represent relevant information for each screen.
 pub(crate) struct Info {
     component: Textarea,
     scrollable: bool,
+    /// Text as given, without petnames, for copying
+    text: String,
 }
 
 fn str_to_spans(s: &str) -> Vec<TextSpan> {
     s.lines().map(|line| TextSpan::new(line)).collect()
 }
 
+/// Identity IDs having a petname are shown as the petname.
+fn annotated_spans(s: &str) -> Vec<TextSpan> {
+    str_to_spans(&annotate(s))
+}
+
 impl Info {
     pub(crate) fn view(&mut self, frame: &mut Frame, area: Rect) {
+        self.component.attr(
+            Attribute::Borders,
+            AttrValue::Borders(density::borders(BorderSides::ALL, BorderSides::NONE)),
+        );
         self.component.view(frame, area)
     }
 
@@ -30,31 +44,50 @@ impl Info {
         let component = Textarea::default()
             .highlighted_str(">")
             .foreground(Color::Red)
-            .text_rows(&str_to_spans(text));
+            .text_rows(&annotated_spans(&tr_message(text)));
         Info {
             component,
             scrollable: true,
+            text: text.to_owned(),
         }
     }
 
     pub(crate) fn new_scrollable(text: &str) -> Info {
+        let component = Textarea::default()
+            .highlighted_str(">")
+            .text_rows(&annotated_spans(text));
+        Info {
+            component,
+            scrollable: true,
+            text: text.to_owned(),
+        }
+    }
+
+    /// Scrollable text shown as is, identity IDs aren't replaced by petnames.
+    pub(crate) fn new_verbatim(text: &str) -> Info {
         let component = Textarea::default()
             .highlighted_str(">")
             .text_rows(&str_to_spans(text));
         Info {
             component,
             scrollable: true,
+            text: text.to_owned(),
         }
     }
 
     pub(crate) fn new_fixed(text: &str) -> Info {
-        let component = Textarea::default().text_rows(&str_to_spans(text));
+        let component = Textarea::default().text_rows(&annotated_spans(text));
         Info {
             component,
             scrollable: false,
+            text: text.to_owned(),
         }
     }
 
+    pub(crate) fn text(&self) -> &str {
+        &self.text
+    }
+
     pub(crate) fn new_from_result(result: &Result<CompletedTaskPayload, String>) -> Info {
         match result {
             Ok(payload) => Info::new_scrollable(&payload.to_string()),
@@ -1,6 +1,9 @@
 //! Status bar component definitions.
 
-use std::fmt::{self, Display};
+use std::{
+    fmt::{self, Display},
+    time::{Duration, Instant},
+};
 
 use tui_realm_stdlib::Label;
 use tuirealm::{
@@ -12,13 +15,26 @@ use tuirealm::{
     Frame, MockComponent,
 };
 
-use super::IdentityBalance;
+use super::{
+    i18n::{tr, tr_message},
+    IdentityBalance,
+};
+use crate::backend::{chain_status::ChainStatus, progress, traffic};
+
+/// How long a toast replaces the breadcrumbs.
+const TOAST_DURATION: Duration = Duration::from_secs(2);
 
 #[derive(Default)]
 pub(crate) struct StatusBarState {
+    profile: String,
     breadcrumbs: Vec<&'static str>,
+    tutorial_hint: Option<String>,
     blocked: bool,
     identity_loaded_balance: IdentityBalanceStatus,
+    /// Latest polled chain status, `None` until the first poll completes
+    chain_status: Option<ChainStatus>,
+    /// Short message about an action done in place, with the time it was shown
+    toast: Option<(String, Instant)>,
 }
 
 enum IdentityBalanceStatus {
@@ -36,15 +52,38 @@ impl Default for IdentityBalanceStatus {
 impl Display for IdentityBalanceStatus {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            IdentityBalanceStatus::NoIdentity => write!(f, "No identity"),
+            IdentityBalanceStatus::NoIdentity => write!(f, "{}", tr("No identity")),
             IdentityBalanceStatus::Balance(balance) => {
-                write!(f, "Platform balance: {}", balance.dash_str())
+                write!(f, "{}: {}", tr("Platform balance"), balance.dash_str())
             }
-            IdentityBalanceStatus::RefreshError => write!(f, "Balance refresh error"),
+            IdentityBalanceStatus::RefreshError => write!(f, "{}", tr("Balance refresh error")),
         }
     }
 }
 
+fn chain_status_str(chain_status: &ChainStatus) -> String {
+    let core = match &chain_status.core {
+        Some(core) => match core.sync_progress {
+            Some(progress) => format!(
+                "{} ({} {:.0}%)",
+                core.height,
+                tr("syncing"),
+                progress * 100.0
+            ),
+            None => core.height.to_string(),
+        },
+        None => "-".to_owned(),
+    };
+    let platform = chain_status
+        .platform_height
+        .map(|height| height.to_string())
+        .unwrap_or_else(|| "-".to_owned());
+    format!(
+        "{} | Core {} | Platform {}",
+        chain_status.network, core, platform
+    )
+}
+
 impl StatusBarState {
     pub(crate) fn with_balance(balance: IdentityBalance) -> Self {
         StatusBarState {
@@ -53,6 +92,14 @@ impl StatusBarState {
         }
     }
 
+    pub(crate) fn set_profile(&mut self, profile: String) {
+        self.profile = profile;
+    }
+
+    pub(crate) fn set_tutorial_hint(&mut self, hint: Option<String>) {
+        self.tutorial_hint = hint;
+    }
+
     pub(crate) fn update_balance(&mut self, balance: IdentityBalance) {
         self.identity_loaded_balance = IdentityBalanceStatus::Balance(balance);
     }
@@ -65,6 +112,22 @@ impl StatusBarState {
         self.identity_loaded_balance = IdentityBalanceStatus::RefreshError;
     }
 
+    pub(crate) fn set_chain_status(&mut self, chain_status: ChainStatus) {
+        self.chain_status = Some(chain_status);
+    }
+
+    pub(crate) fn show_toast(&mut self, message: String) {
+        self.toast = Some((message, Instant::now()));
+    }
+
+    /// Toast still shown, the status bar needs a redraw once it expires.
+    pub(crate) fn toast(&self) -> Option<&str> {
+        self.toast
+            .as_ref()
+            .filter(|(_, shown_at)| shown_at.elapsed() < TOAST_DURATION)
+            .map(|(message, _)| message.as_str())
+    }
+
     pub(crate) fn block(&mut self) {
         self.blocked = true;
     }
@@ -81,29 +144,77 @@ impl StatusBarState {
         self.breadcrumbs.pop();
     }
 
+    pub(crate) fn breadcrumbs(&self) -> &[&'static str] {
+        &self.breadcrumbs
+    }
+
+    /// Shows the breadcrumbs of another pane, keeping the current ones there.
+    pub(crate) fn swap_breadcrumbs(&mut self, breadcrumbs: &mut Vec<&'static str>) {
+        std::mem::swap(&mut self.breadcrumbs, breadcrumbs);
+    }
+
     pub(crate) fn view(&self, frame: &mut Frame, area: Rect) {
         let block = Block::new().borders(BorderSides::ALL);
 
+        // The traffic meter and the chain status take the middle if available
+        let traffic = traffic::display();
+        let chain_status = self.chain_status.as_ref().map(chain_status_str);
+        let mut constraints = vec![Constraint::Min(20)];
+        if traffic.is_some() {
+            constraints.push(Constraint::Max(56));
+        }
+        if chain_status.is_some() {
+            constraints.push(Constraint::Max(48));
+        }
+        constraints.push(Constraint::Max(40));
         let layout = Layout::default()
             .horizontal_margin(1)
             .direction(Direction::Horizontal)
-            .constraints([Constraint::Min(20), Constraint::Max(40)].as_ref())
+            .constraints(constraints)
             .split(block.inner(area));
 
-        let breadcrumbs_str = self.breadcrumbs.join(" / ");
+        let mut breadcrumbs_str = format!(
+            "[{}] {}",
+            self.profile,
+            self.breadcrumbs
+                .iter()
+                .map(|name| tr(name))
+                .collect::<Vec<_>>()
+                .join(" / ")
+        );
+        if let Some(hint) = &self.tutorial_hint {
+            breadcrumbs_str.push_str(" | ");
+            breadcrumbs_str.push_str(hint);
+        }
 
         if self.blocked {
+            let message = progress::current()
+                .unwrap_or_else(|| tr("Executing a task, please wait").to_owned());
             Label::default()
-                .text("Executing a task, please wait")
+                .text(&message)
                 .modifiers(Modifier::RAPID_BLINK) // TODO: doesn't work lol
+        } else if let Some(toast) = self.toast() {
+            Label::default().text(&tr_message(toast))
         } else {
             Label::default().text(&breadcrumbs_str)
         }
         .view(frame, layout[0]);
 
+        let mut middle = layout[1..layout.len() - 1].iter();
+        if let Some(traffic) = traffic {
+            Label::default()
+                .text(&traffic)
+                .view(frame, *middle.next().expect("traffic meter area"));
+        }
+        if let Some(chain_status) = chain_status {
+            Label::default()
+                .text(&chain_status)
+                .view(frame, *middle.next().expect("chain status area"));
+        }
+
         Label::default()
             .text(&self.identity_loaded_balance.to_string())
-            .view(frame, layout[1]);
+            .view(frame, layout[layout.len() - 1]);
 
         frame.render_widget(block, area);
     }
@@ -2,11 +2,19 @@
 //! Each module contains definitions of screens and forms related to a
 //! subdomain.
 
+pub(crate) mod bookmarks;
 pub(crate) mod contracts;
 pub(crate) mod documents;
+pub(crate) mod export;
 pub(crate) mod identities;
 pub(crate) mod main;
+pub(crate) mod network;
 pub(crate) mod platform_info;
+pub(crate) mod search;
+pub(crate) mod settings;
 // pub(crate) mod strategies;
 pub(crate) mod strategies;
+pub(crate) mod task_history;
+pub(crate) mod tutorial;
+pub(crate) mod version_upgrade;
 pub(crate) mod wallet;
@@ -0,0 +1,227 @@
+//! Document bookmarks screen and forms.
+
+use std::borrow::Cow;
+
+use dash_sdk::platform::DocumentQuery;
+use dpp::document::Document;
+use tuirealm::{
+    event::{Key, KeyEvent, KeyModifiers},
+    tui::prelude::Rect,
+    Frame,
+};
+
+use crate::{
+    backend::{bookmarks::BookmarkTask, AppState, AppStateUpdate, BackendEvent, Task},
+    ui::{
+        form::{
+            parsers::DefaultTextInputParser, ComposedInput, Field, FormController, FormStatus,
+            Input, InputStatus, SelectInput, TextInput,
+        },
+        screen::{
+            utils::impl_builder, widgets::info::Info, ScreenCommandKey, ScreenController,
+            ScreenFeedback, ScreenToggleKey,
+        },
+    },
+    Event,
+};
+
+const COMMAND_KEYS: [ScreenCommandKey; 5] = [
+    ScreenCommandKey::new("q", "Back to Main"),
+    ScreenCommandKey::new("r", "Re-fetch all bookmarks"),
+    ScreenCommandKey::new("x", "Remove bookmark"),
+    ScreenCommandKey::new("↓", "Scroll down"),
+    ScreenCommandKey::new("↑", "Scroll up"),
+];
+
+pub(crate) struct BookmarksScreenController {
+    info: Info,
+    names: Vec<String>,
+}
+
+impl_builder!(BookmarksScreenController);
+
+impl BookmarksScreenController {
+    pub(crate) async fn new(app_state: &AppState) -> Self {
+        let bookmarks = app_state.bookmarks.lock().await;
+        BookmarksScreenController {
+            info: Info::new_scrollable(&bookmarks.display()),
+            names: bookmarks.names(),
+        }
+    }
+}
+
+impl ScreenController for BookmarksScreenController {
+    fn name(&self) -> &'static str {
+        "Bookmarks"
+    }
+
+    fn command_keys(&self) -> &[ScreenCommandKey] {
+        COMMAND_KEYS.as_ref()
+    }
+
+    fn toggle_keys(&self) -> &[ScreenToggleKey] {
+        &[]
+    }
+
+    fn on_event(&mut self, event: &Event) -> ScreenFeedback {
+        match event {
+            Event::Key(KeyEvent {
+                code: Key::Char('q'),
+                modifiers: KeyModifiers::NONE,
+            }) => ScreenFeedback::PreviousScreen,
+
+            Event::Key(KeyEvent {
+                code: Key::Char('r'),
+                modifiers: KeyModifiers::NONE,
+            }) => ScreenFeedback::Task {
+                task: Task::Bookmark(BookmarkTask::RefreshAll),
+                block: true,
+            },
+
+            Event::Key(KeyEvent {
+                code: Key::Char('x'),
+                modifiers: KeyModifiers::NONE,
+            }) if !self.names.is_empty() => ScreenFeedback::Form(Box::new(
+                RemoveBookmarkFormController::new(self.names.clone()),
+            )),
+
+            Event::Key(k) => {
+                if self.info.on_event(k) {
+                    ScreenFeedback::Redraw
+                } else {
+                    ScreenFeedback::None
+                }
+            }
+
+            Event::Backend(BackendEvent::TaskCompletedStateChange {
+                app_state_update: AppStateUpdate::Bookmarks(bookmarks),
+                ..
+            }) => {
+                self.info = Info::new_scrollable(&bookmarks.display());
+                self.names = bookmarks.names();
+                ScreenFeedback::Redraw
+            }
+
+            Event::Backend(BackendEvent::TaskCompleted {
+                task: Task::Bookmark(_),
+                execution_result,
+            }) => {
+                self.info = Info::new_from_result(execution_result);
+                ScreenFeedback::Redraw
+            }
+
+            _ => ScreenFeedback::None,
+        }
+    }
+
+    fn view(&mut self, frame: &mut Frame, area: Rect) {
+        self.info.view(frame, area)
+    }
+
+    fn refresh_task(&self) -> Option<Task> {
+        Some(Task::Bookmark(BookmarkTask::RefreshAll))
+    }
+}
+
+pub(crate) struct BookmarkDocumentFormController {
+    query: DocumentQuery,
+    document: Document,
+    input: ComposedInput<(
+        Field<TextInput<DefaultTextInputParser<String>>>,
+        Field<TextInput<DefaultTextInputParser<String>>>,
+    )>,
+}
+
+impl BookmarkDocumentFormController {
+    pub(crate) fn new(query: DocumentQuery, document: Document) -> Self {
+        BookmarkDocumentFormController {
+            query,
+            document,
+            input: ComposedInput::new((
+                Field::new("Bookmark name", TextInput::new("Name")),
+                Field::new("Note", TextInput::new("Note, may be empty")),
+            )),
+        }
+    }
+}
+
+impl FormController for BookmarkDocumentFormController {
+    fn on_event(&mut self, event: KeyEvent) -> FormStatus {
+        match self.input.on_event(event) {
+            InputStatus::Done((name, note)) => FormStatus::Done {
+                task: Task::Bookmark(BookmarkTask::Add {
+                    name,
+                    note,
+                    query: self.query.clone(),
+                    document: self.document.clone(),
+                }),
+                block: false,
+            },
+            status => status.into(),
+        }
+    }
+
+    fn form_name(&self) -> &'static str {
+        "Bookmark document"
+    }
+
+    fn step_view(&mut self, frame: &mut Frame, area: Rect) {
+        self.input.view(frame, area)
+    }
+
+    fn step_name(&self) -> Cow<'_, str> {
+        self.input.step_name().into()
+    }
+
+    fn step_index(&self) -> u8 {
+        self.input.step_index()
+    }
+
+    fn steps_number(&self) -> u8 {
+        2
+    }
+}
+
+struct RemoveBookmarkFormController {
+    input: SelectInput<String>,
+}
+
+impl RemoveBookmarkFormController {
+    fn new(names: Vec<String>) -> Self {
+        RemoveBookmarkFormController {
+            input: SelectInput::new(names),
+        }
+    }
+}
+
+impl FormController for RemoveBookmarkFormController {
+    fn on_event(&mut self, event: KeyEvent) -> FormStatus {
+        match self.input.on_event(event) {
+            InputStatus::Done(name) => FormStatus::Done {
+                task: Task::Bookmark(BookmarkTask::Remove(name)),
+                block: false,
+            },
+            status => status.into(),
+        }
+    }
+
+    fn form_name(&self) -> &'static str {
+        "Remove bookmark"
+    }
+
+    fn step_view(&mut self, frame: &mut Frame, area: Rect) {
+        self.input.view(frame, area)
+    }
+
+    fn step_name(&self) -> Cow<'_, str> {
+        "Bookmark".into()
+    }
+
+    fn step_index(&self) -> u8 {
+        0
+    }
+
+    fn steps_number(&self) -> u8 {
+        1
+    }
+}
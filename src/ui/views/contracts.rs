@@ -1,10 +1,13 @@
 //! Contracts views.
 
+mod dependency_graph;
 mod document_type;
 mod fetch_contract;
 mod fetch_system_contract;
+mod schema_builder;
 
 use std::{
+    borrow::Cow,
     collections::BTreeMap,
     fmt::{self, Display},
 };
@@ -15,27 +18,39 @@ use dpp::{
 };
 use tuirealm::{
     event::{Key, KeyEvent, KeyModifiers},
-    tui::prelude::Rect,
+    tui::prelude::{Constraint, Direction, Layout, Rect},
     Frame,
 };
 
+pub(crate) use self::document_type::{
+    DocumentTypeScreenController, SelectDocumentTypeFormController,
+};
 use self::{
-    document_type::SelectDocumentTypeFormController, fetch_contract::FetchContractScreenController,
+    dependency_graph::DependencyGraphFormController, fetch_contract::FetchContractScreenController,
     fetch_system_contract::FetchSystemContractScreenController,
+    schema_builder::SchemaBuilderScreenController,
 };
 use crate::{
-    backend::{AppState, AppStateUpdate, BackendEvent, ContractTask, Task},
+    backend::{
+        as_json_string, export::ExportedEntity, AppState, AppStateUpdate, BackendEvent,
+        ContractTask, Task,
+    },
     ui::{
-        form::{FormController, FormStatus, Input, InputStatus, SelectInput},
+        form::{
+            parsers::DefaultTextInputParser, ComposedInput, Field, FormController, FormStatus,
+            Input, InputStatus, SelectInput, TextInput,
+        },
+        recent::RecentEntity,
         screen::{
             utils::impl_builder, widgets::info::Info, ScreenCommandKey, ScreenController,
             ScreenFeedback, ScreenToggleKey,
         },
+        views::export::ExportEntityFormController,
     },
     Event,
 };
 
-const COMMAND_KEYS: [ScreenCommandKey; 7] = [
+const COMMAND_KEYS: [ScreenCommandKey; 15] = [
     ScreenCommandKey::new("q", "Back to Main"),
     ScreenCommandKey::new("s", "Fetch system contract"),
     ScreenCommandKey::new("f", "Fetch contract"),
@@ -43,6 +58,14 @@ const COMMAND_KEYS: [ScreenCommandKey; 7] = [
     ScreenCommandKey::new("↑ / C-p", "Prev contract"),
     ScreenCommandKey::new("Enter", "Select contract"),
     ScreenCommandKey::new("r", "Remove a contract"),
+    ScreenCommandKey::new("u", "Refresh all contracts"),
+    ScreenCommandKey::new("c", "Check schema update"),
+    ScreenCommandKey::new("n", "Schema builder"),
+    ScreenCommandKey::new("l", "Register contract from file"),
+    ScreenCommandKey::new("g", "Dependency graph"),
+    ScreenCommandKey::new("y", "Copy contract ID"),
+    ScreenCommandKey::new("C-y", "Copy contract JSON"),
+    ScreenCommandKey::new("C-e", "Export to file"),
 ];
 
 /// Data contract name (identifier in app state) wrapper for better display
@@ -78,6 +101,11 @@ impl Display for DataContractEntry {
 pub(crate) struct ContractsScreenController {
     select: Option<SelectInput<DataContractEntry>>,
     known_contracts: BTreeMap<String, DataContract>,
+    /// Changes found by the latest refresh of all contracts or schema update
+    /// check
+    report_info: Option<Info>,
+    /// Contract selected since recent entities were updated last
+    viewed_contract: Option<String>,
 }
 
 impl_builder!(ContractsScreenController);
@@ -96,9 +124,18 @@ impl ContractsScreenController {
         ContractsScreenController {
             select,
             known_contracts,
+            report_info: None,
+            viewed_contract: None,
         }
     }
 
+    /// Contract highlighted in the list, entries follow the order of known
+    /// contracts.
+    fn highlighted_contract(&self) -> Option<&DataContract> {
+        let index = self.select.as_ref()?.selected_index();
+        self.known_contracts.values().nth(index)
+    }
+
     fn contract_entries_vec<'a>(
         known_contracts: impl IntoIterator<Item = (String, &'a DataContract)>,
     ) -> Vec<DataContractEntry> {
@@ -107,10 +144,40 @@ impl ContractsScreenController {
             .map(|(name, contract)| DataContractEntry::new(name, contract))
             .collect()
     }
+
+    /// Selecting a contract continues with its document types.
+    fn select_feedback(&mut self, status: InputStatus<DataContractEntry>) -> ScreenFeedback {
+        match status {
+            InputStatus::Done(DataContractEntry {
+                name,
+                document_type_names,
+                ..
+            }) => {
+                self.viewed_contract = Some(name.clone());
+                ScreenFeedback::Form(Box::new(SelectDocumentTypeFormController::new(
+                    name,
+                    document_type_names,
+                )))
+            }
+            InputStatus::Redraw => ScreenFeedback::Redraw,
+            _ => ScreenFeedback::None,
+        }
+    }
 }
 
 impl ScreenController for ContractsScreenController {
     fn view(&mut self, frame: &mut Frame, area: Rect) {
+        let area = if let Some(report_info) = &mut self.report_info {
+            let layout = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Min(3), Constraint::Percentage(30)].as_ref())
+                .split(area);
+            report_info.view(frame, layout[1]);
+            layout[0]
+        } else {
+            area
+        };
+
         if let Some(select) = &mut self.select {
             select.view(frame, area)
         } else {
@@ -147,6 +214,23 @@ impl ScreenController for ContractsScreenController {
                 modifiers: KeyModifiers::NONE,
             }) => ScreenFeedback::NextScreen(FetchContractScreenController::builder()),
 
+            Event::Key(KeyEvent {
+                code: Key::Char('n'),
+                modifiers: KeyModifiers::NONE,
+            }) => ScreenFeedback::NextScreen(SchemaBuilderScreenController::builder()),
+
+            Event::Key(KeyEvent {
+                code: Key::Char('l'),
+                modifiers: KeyModifiers::NONE,
+            }) => ScreenFeedback::Form(Box::new(RegisterContractFileFormController::new())),
+
+            Event::Key(KeyEvent {
+                code: Key::Char('g'),
+                modifiers: KeyModifiers::NONE,
+            }) if !self.known_contracts.is_empty() => ScreenFeedback::Form(Box::new(
+                DependencyGraphFormController::new(self.known_contracts.keys().cloned().collect()),
+            )),
+
             Event::Key(KeyEvent {
                 code: Key::Char('r'),
                 modifiers: KeyModifiers::NONE,
@@ -160,24 +244,67 @@ impl ScreenController for ContractsScreenController {
                 ScreenFeedback::Form(Box::new(RemoveContractFormController::new(contract_names)))
             }
 
-            Event::Key(event) => {
-                if let Some(select) = &mut self.select {
-                    match select.on_event(*event) {
-                        InputStatus::Done(DataContractEntry {
-                            name,
-                            document_type_names,
-                            ..
-                        }) => ScreenFeedback::Form(Box::new(
-                            SelectDocumentTypeFormController::new(name, document_type_names),
-                        )),
-                        InputStatus::Redraw => ScreenFeedback::Redraw,
-                        _ => ScreenFeedback::None,
-                    }
-                } else {
-                    ScreenFeedback::None
-                }
+            Event::Key(KeyEvent {
+                code: Key::Char('u'),
+                modifiers: KeyModifiers::NONE,
+            }) if !self.known_contracts.is_empty() => ScreenFeedback::Task {
+                task: Task::Contract(ContractTask::RefreshContracts),
+                block: true,
+            },
+
+            Event::Key(KeyEvent {
+                code: Key::Char('c'),
+                modifiers: KeyModifiers::NONE,
+            }) if !self.known_contracts.is_empty() => {
+                ScreenFeedback::Form(Box::new(CheckSchemaUpdateFormController::new(
+                    self.known_contracts.keys().cloned().collect(),
+                )))
             }
 
+            Event::Key(KeyEvent {
+                code: Key::Char('y'),
+                modifiers: KeyModifiers::NONE,
+            }) => match self.highlighted_contract() {
+                Some(contract) => {
+                    ScreenFeedback::Copy(contract.id_ref().to_string(Encoding::Base58))
+                }
+                None => ScreenFeedback::None,
+            },
+
+            Event::Key(KeyEvent {
+                code: Key::Char('y'),
+                modifiers: KeyModifiers::CONTROL,
+            }) => match self.highlighted_contract() {
+                Some(contract) => ScreenFeedback::Copy(as_json_string(contract)),
+                None => ScreenFeedback::None,
+            },
+
+            Event::Key(KeyEvent {
+                code: Key::Char('e'),
+                modifiers: KeyModifiers::CONTROL,
+            }) => match self.highlighted_contract() {
+                Some(contract) => ScreenFeedback::Form(Box::new(ExportEntityFormController::new(
+                    ExportedEntity::Contract(contract.clone()),
+                ))),
+                None => ScreenFeedback::None,
+            },
+
+            Event::Key(event) => match &mut self.select {
+                Some(select) => {
+                    let status = select.on_event(*event);
+                    self.select_feedback(status)
+                }
+                None => ScreenFeedback::None,
+            },
+
+            Event::Mouse(event) => match &mut self.select {
+                Some(select) => {
+                    let status = select.on_mouse(event);
+                    self.select_feedback(status)
+                }
+                None => ScreenFeedback::None,
+            },
+
             Event::Backend(
                 BackendEvent::AppStateUpdated(AppStateUpdate::KnownContracts(known_contracts))
                 | BackendEvent::TaskCompletedStateChange {
@@ -185,6 +312,17 @@ impl ScreenController for ContractsScreenController {
                     ..
                 },
             ) => {
+                if let Event::Backend(BackendEvent::TaskCompletedStateChange {
+                    task:
+                        Task::Contract(
+                            ContractTask::RefreshContracts | ContractTask::RegisterContract(_),
+                        ),
+                    execution_result,
+                    ..
+                }) = event
+                {
+                    self.report_info = Some(Info::new_from_result(execution_result));
+                }
                 self.select = if !known_contracts.is_empty() {
                     Some(SelectInput::new(Self::contract_entries_vec(
                         known_contracts.iter().map(|(k, v)| (k.clone(), v)),
@@ -196,9 +334,31 @@ impl ScreenController for ContractsScreenController {
                 ScreenFeedback::Redraw
             }
 
+            Event::Backend(BackendEvent::TaskCompleted {
+                task:
+                    Task::Contract(
+                        ContractTask::CheckSchemaUpdate { .. } | ContractTask::RegisterContract(_),
+                    )
+                    | Task::Export(_),
+                execution_result,
+            }) => {
+                self.report_info = Some(Info::new_from_result(execution_result));
+                ScreenFeedback::Redraw
+            }
+
             _ => ScreenFeedback::None,
         }
     }
+
+    fn refresh_task(&self) -> Option<Task> {
+        Some(Task::Contract(ContractTask::RefreshContracts))
+    }
+
+    fn take_viewed_entity(&mut self) -> Option<RecentEntity> {
+        self.viewed_contract
+            .take()
+            .map(|name| RecentEntity::Contract { name })
+    }
 }
 
 pub(super) struct RemoveContractFormController {
@@ -233,8 +393,108 @@ impl FormController for RemoveContractFormController {
         self.input.view(frame, area)
     }
 
-    fn step_name(&self) -> &'static str {
-        ""
+    fn step_name(&self) -> Cow<'_, str> {
+        "".into()
+    }
+
+    fn step_index(&self) -> u8 {
+        0
+    }
+
+    fn steps_number(&self) -> u8 {
+        1
+    }
+}
+
+struct CheckSchemaUpdateFormController {
+    input: ComposedInput<(
+        Field<SelectInput<String>>,
+        Field<TextInput<DefaultTextInputParser<String>>>,
+    )>,
+}
+
+impl CheckSchemaUpdateFormController {
+    fn new(contract_names: Vec<String>) -> Self {
+        CheckSchemaUpdateFormController {
+            input: ComposedInput::new((
+                Field::new("Contract", SelectInput::new(contract_names)),
+                Field::new(
+                    "Proposed schema file",
+                    TextInput::new("Path to JSON with document schemas"),
+                ),
+            )),
+        }
+    }
+}
+
+impl FormController for CheckSchemaUpdateFormController {
+    fn on_event(&mut self, event: KeyEvent) -> FormStatus {
+        match self.input.on_event(event) {
+            InputStatus::Done((contract_name, schema_path)) => FormStatus::Done {
+                task: Task::Contract(ContractTask::CheckSchemaUpdate {
+                    contract_name,
+                    schema_path,
+                }),
+                block: false,
+            },
+            status => status.into(),
+        }
+    }
+
+    fn form_name(&self) -> &'static str {
+        "Check schema update"
+    }
+
+    fn step_view(&mut self, frame: &mut Frame, area: Rect) {
+        self.input.view(frame, area)
+    }
+
+    fn step_name(&self) -> Cow<'_, str> {
+        self.input.step_name().into()
+    }
+
+    fn step_index(&self) -> u8 {
+        self.input.step_index()
+    }
+
+    fn steps_number(&self) -> u8 {
+        2
+    }
+}
+
+struct RegisterContractFileFormController {
+    input: TextInput<DefaultTextInputParser<String>>,
+}
+
+impl RegisterContractFileFormController {
+    fn new() -> Self {
+        RegisterContractFileFormController {
+            input: TextInput::new("Path to contract JSON (Tab to complete)").with_path_completion(),
+        }
+    }
+}
+
+impl FormController for RegisterContractFileFormController {
+    fn on_event(&mut self, event: KeyEvent) -> FormStatus {
+        match self.input.on_event(event) {
+            InputStatus::Done(path) => FormStatus::Done {
+                task: Task::Contract(ContractTask::RegisterContract(path)),
+                block: true,
+            },
+            status => status.into(),
+        }
+    }
+
+    fn form_name(&self) -> &'static str {
+        "Register contract from file"
+    }
+
+    fn step_view(&mut self, frame: &mut Frame, area: Rect) {
+        self.input.view(frame, area)
+    }
+
+    fn step_name(&self) -> Cow<'_, str> {
+        "Contract file".into()
     }
 
     fn step_index(&self) -> u8 {
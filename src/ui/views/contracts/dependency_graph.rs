@@ -0,0 +1,234 @@
+//! Dependency graph of known contracts, navigable along its edges.
+
+use std::{borrow::Cow, collections::BTreeMap};
+
+use dpp::platform_value::string_encoding::Encoding;
+use futures::FutureExt;
+use tuirealm::{
+    event::{Key, KeyEvent, KeyModifiers},
+    tui::prelude::{Constraint, Direction, Layout, Rect},
+    Frame,
+};
+
+use super::SelectDocumentTypeFormController;
+use crate::{
+    backend::{
+        dependencies::{DependencyGraph, GraphNode, Neighbour},
+        AppState, BackendEvent, Task,
+    },
+    ui::{
+        form::{FormController, FormStatus, Input, InputStatus, SelectInput},
+        screen::{
+            widgets::info::Info, ScreenCommandKey, ScreenController, ScreenFeedback,
+            ScreenToggleKey,
+        },
+        views::documents::DocumentsQuerysetScreenController,
+    },
+    Event,
+};
+
+const COMMAND_KEYS: [ScreenCommandKey; 6] = [
+    ScreenCommandKey::new("q", "Back to Contracts"),
+    ScreenCommandKey::new("↓ / C-n", "Next edge"),
+    ScreenCommandKey::new("↑ / C-p", "Prev edge"),
+    ScreenCommandKey::new("Enter", "Follow edge"),
+    ScreenCommandKey::new("b", "Previous node"),
+    ScreenCommandKey::new("o", "Open node"),
+];
+
+pub(crate) struct DependencyGraphScreenController {
+    graph: DependencyGraph,
+    node: GraphNode,
+    /// Nodes visited before the current one
+    history: Vec<GraphNode>,
+    select: Option<SelectInput<Neighbour>>,
+    info: Info,
+}
+
+impl DependencyGraphScreenController {
+    pub(crate) async fn new(app_state: &AppState, contract_name: String) -> Self {
+        let graph = DependencyGraph::build(
+            &*app_state.known_contracts.lock().await,
+            &*app_state.documents_cache.lock().await,
+        );
+        let mut controller = DependencyGraphScreenController {
+            graph,
+            node: GraphNode::Contract(contract_name),
+            history: Vec::new(),
+            select: None,
+            info: Info::new_fixed(""),
+        };
+        controller.show_node();
+        controller
+    }
+
+    fn show_node(&mut self) {
+        let neighbours = self.graph.neighbours(&self.node);
+        self.select = (!neighbours.is_empty()).then(|| SelectInput::new(neighbours));
+        self.info = Info::new_scrollable(&self.graph.describe(&self.node));
+    }
+
+    fn go_to(&mut self, node: GraphNode) {
+        let previous = std::mem::replace(&mut self.node, node);
+        self.history.push(previous);
+        self.show_node();
+    }
+
+    fn open_node(&self) -> ScreenFeedback {
+        match &self.node {
+            GraphNode::Contract(name) => {
+                ScreenFeedback::Form(Box::new(SelectDocumentTypeFormController::new(
+                    name.clone(),
+                    self.graph.document_type_names(name),
+                )))
+            }
+            GraphNode::Document(id) => {
+                let Some((query, document)) = self.graph.document(id) else {
+                    return ScreenFeedback::None;
+                };
+                let documents = BTreeMap::from([(*id, Some(document))]);
+                ScreenFeedback::NextScreen(Box::new(move |_| {
+                    async move {
+                        Box::new(DocumentsQuerysetScreenController::new(query, documents))
+                            as Box<dyn ScreenController>
+                    }
+                    .boxed()
+                }))
+            }
+            GraphNode::Identity(id) => ScreenFeedback::Task {
+                task: Task::FetchIdentityById(id.to_string(Encoding::Base58), false),
+                block: true,
+            },
+        }
+    }
+}
+
+impl ScreenController for DependencyGraphScreenController {
+    fn name(&self) -> &'static str {
+        "Dependency graph"
+    }
+
+    fn command_keys(&self) -> &[ScreenCommandKey] {
+        COMMAND_KEYS.as_ref()
+    }
+
+    fn toggle_keys(&self) -> &[ScreenToggleKey] {
+        &[]
+    }
+
+    fn on_event(&mut self, event: &Event) -> ScreenFeedback {
+        match event {
+            Event::Key(KeyEvent {
+                code: Key::Char('q'),
+                modifiers: KeyModifiers::NONE,
+            }) => ScreenFeedback::PreviousScreen,
+
+            Event::Key(KeyEvent {
+                code: Key::Char('b'),
+                modifiers: KeyModifiers::NONE,
+            }) => {
+                if let Some(previous) = self.history.pop() {
+                    self.node = previous;
+                    self.show_node();
+                    ScreenFeedback::Redraw
+                } else {
+                    ScreenFeedback::None
+                }
+            }
+
+            Event::Key(KeyEvent {
+                code: Key::Char('o'),
+                modifiers: KeyModifiers::NONE,
+            }) => self.open_node(),
+
+            Event::Key(event) => {
+                if let Some(select) = &mut self.select {
+                    match select.on_event(*event) {
+                        InputStatus::Done(Neighbour { node, .. }) => {
+                            self.go_to(node);
+                            ScreenFeedback::Redraw
+                        }
+                        InputStatus::Redraw => ScreenFeedback::Redraw,
+                        _ => ScreenFeedback::None,
+                    }
+                } else {
+                    ScreenFeedback::None
+                }
+            }
+
+            Event::Backend(BackendEvent::TaskCompleted {
+                task: Task::FetchIdentityById(..),
+                execution_result,
+            }) => {
+                self.info = Info::new_from_result(execution_result);
+                ScreenFeedback::Redraw
+            }
+
+            _ => ScreenFeedback::None,
+        }
+    }
+
+    fn view(&mut self, frame: &mut Frame, area: Rect) {
+        let layout = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)].as_ref())
+            .split(area);
+
+        if let Some(select) = &mut self.select {
+            select.view(frame, layout[0]);
+        } else {
+            Info::new_fixed("No edges known for this node").view(frame, layout[0]);
+        }
+        self.info.view(frame, layout[1]);
+    }
+}
+
+pub(super) struct DependencyGraphFormController {
+    input: SelectInput<String>,
+}
+
+impl DependencyGraphFormController {
+    pub(super) fn new(contracts: Vec<String>) -> Self {
+        Self {
+            input: SelectInput::new(contracts),
+        }
+    }
+}
+
+impl FormController for DependencyGraphFormController {
+    fn on_event(&mut self, event: KeyEvent) -> FormStatus {
+        match self.input.on_event(event) {
+            InputStatus::Done(contract_name) => {
+                FormStatus::NextScreen(Box::new(move |app_state| {
+                    async move {
+                        Box::new(
+                            DependencyGraphScreenController::new(app_state, contract_name).await,
+                        ) as Box<dyn ScreenController>
+                    }
+                    .boxed()
+                }))
+            }
+            status => status.into(),
+        }
+    }
+
+    fn form_name(&self) -> &'static str {
+        "Dependency graph"
+    }
+
+    fn step_view(&mut self, frame: &mut Frame, area: Rect) {
+        self.input.view(frame, area)
+    }
+
+    fn step_name(&self) -> Cow<'_, str> {
+        "Starting contract".into()
+    }
+
+    fn step_index(&self) -> u8 {
+        0
+    }
+
+    fn steps_number(&self) -> u8 {
+        1
+    }
+}
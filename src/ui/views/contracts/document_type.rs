@@ -1,6 +1,9 @@
 //! UI defenitions for selected data contract.
 
 mod broadcast_random_documents;
+mod query_builder;
+
+use std::borrow::Cow;
 
 use dpp::{
     data_contract::{
@@ -19,32 +22,41 @@ use tuirealm::{
     Frame,
 };
 
-use self::broadcast_random_documents::BroadcastRandomDocumentsCountForm;
+use self::{
+    broadcast_random_documents::BroadcastRandomDocumentsCountForm,
+    query_builder::QueryBuilderFormController,
+};
 use crate::{
     backend::{
-        as_json_string, documents::DocumentTask, AppState, BackendEvent, CompletedTaskPayload, Task,
+        as_json_string,
+        documents::{binary::document_json, DocumentTask},
+        AppState, BackendEvent, CompletedTaskPayload, Task,
     },
     ui::{
         form::{
-            parsers::DocumentQueryTextInputParser, FormController, FormStatus, Input, InputStatus,
-            SelectInput, TextInput,
+            parsers::{DocumentQueryTextInputParser, TextInputParser},
+            FormController, FormStatus, Input, InputStatus, SelectInput, TextInput,
         },
+        recent::RecentEntity,
         screen::{
-            widgets::info::Info, ScreenCommandKey, ScreenController, ScreenFeedback,
-            ScreenToggleKey,
+            widgets::info::Info, ScreenCommandKey, ScreenController, ScreenControllerBuilder,
+            ScreenFeedback, ScreenToggleKey,
+        },
+        views::documents::{
+            document_form::{DocumentFormAction, DocumentFormController},
+            DocumentsQuerysetScreenController,
         },
-        views::documents::DocumentsQuerysetScreenController,
     },
     Event,
 };
 
-pub(super) struct SelectDocumentTypeFormController {
+pub(crate) struct SelectDocumentTypeFormController {
     input: SelectInput<String>,
     contract_name: String,
 }
 
 impl SelectDocumentTypeFormController {
-    pub(super) fn new(contract_name: String, document_type_names: Vec<String>) -> Self {
+    pub(crate) fn new(contract_name: String, document_type_names: Vec<String>) -> Self {
         SelectDocumentTypeFormController {
             input: SelectInput::new(document_type_names),
             contract_name,
@@ -56,28 +68,10 @@ impl FormController for SelectDocumentTypeFormController {
     fn on_event(&mut self, event: KeyEvent) -> FormStatus {
         match self.input.on_event(event) {
             InputStatus::Done(document_type_name) => {
-                let contract_name = self.contract_name.clone();
-                let document_type_name = document_type_name.clone();
-
-                FormStatus::NextScreen(Box::new(|app_state| {
-                    async {
-                        Box::new(
-                            DocumentTypeScreenController::new(
-                                app_state
-                                    .loaded_identity
-                                    .lock()
-                                    .await
-                                    .as_ref()
-                                    .map(|identity| identity.id()),
-                                contract_name,
-                                document_type_name,
-                                app_state,
-                            )
-                            .await,
-                        ) as Box<dyn ScreenController>
-                    }
-                    .boxed()
-                }))
+                FormStatus::NextScreen(DocumentTypeScreenController::builder(
+                    self.contract_name.clone(),
+                    document_type_name.clone(),
+                ))
             }
             status => status.into(),
         }
@@ -91,8 +85,8 @@ impl FormController for SelectDocumentTypeFormController {
         self.input.view(frame, area)
     }
 
-    fn step_name(&self) -> &'static str {
-        "Document type"
+    fn step_name(&self) -> Cow<'_, str> {
+        "Document type".into()
     }
 
     fn step_index(&self) -> u8 {
@@ -104,24 +98,56 @@ impl FormController for SelectDocumentTypeFormController {
     }
 }
 
-const COMMANDS: [ScreenCommandKey; 4] = [
+const COMMANDS: [ScreenCommandKey; 7] = [
     ScreenCommandKey::new("q", "Back to Contracts"),
     ScreenCommandKey::new("f", "Query"),
     ScreenCommandKey::new("o", "Query ours"),
+    ScreenCommandKey::new("w", "Query builder"),
     ScreenCommandKey::new("b", "Broadcast Random Documents"),
+    ScreenCommandKey::new("n", "Compose document"),
+    ScreenCommandKey::new("c", "Create document"),
 ];
 
-pub(super) struct DocumentTypeScreenController {
+pub(crate) struct DocumentTypeScreenController {
     identity_identifier: Option<Identifier>,
     data_contract: DataContract,
     data_contract_name: String,
     document_type: DocumentType,
     document_type_name: String,
     info: Info,
+    /// Whether the document type is among recent entities already
+    listed: bool,
 }
 
 impl DocumentTypeScreenController {
-    pub(super) async fn new(
+    /// Screen of a known contract's document type, queries are made for the
+    /// loaded identity if any.
+    pub(crate) fn builder(
+        data_contract_name: String,
+        document_type_name: String,
+    ) -> ScreenControllerBuilder {
+        Box::new(|app_state| {
+            async {
+                Box::new(
+                    DocumentTypeScreenController::new(
+                        app_state
+                            .loaded_identity
+                            .lock()
+                            .await
+                            .as_ref()
+                            .map(|identity| identity.id()),
+                        data_contract_name,
+                        document_type_name,
+                        app_state,
+                    )
+                    .await,
+                ) as Box<dyn ScreenController>
+            }
+            .boxed()
+        })
+    }
+
+    async fn new(
         identity_identifier: Option<Identifier>,
         data_contract_name: String,
         document_type_name: String,
@@ -146,6 +172,7 @@ impl DocumentTypeScreenController {
             document_type,
             document_type_name,
             info,
+            listed: false,
         }
     }
 }
@@ -183,6 +210,14 @@ impl ScreenController for DocumentTypeScreenController {
                 None,
             ))),
 
+            Event::Key(KeyEvent {
+                code: Key::Char('w'),
+                modifiers: KeyModifiers::NONE,
+            }) => ScreenFeedback::Form(Box::new(QueryBuilderFormController::new(
+                self.data_contract.clone(),
+                &self.document_type,
+            ))),
+
             Event::Key(KeyEvent {
                 code: Key::Char('b'),
                 modifiers: KeyModifiers::NONE,
@@ -200,6 +235,36 @@ impl ScreenController for DocumentTypeScreenController {
                 self.identity_identifier.clone(),
             ))),
 
+            Event::Key(KeyEvent {
+                code: Key::Char(key @ ('n' | 'c')),
+                modifiers: KeyModifiers::NONE,
+            }) => {
+                let data_contract_name = self.data_contract_name.clone();
+                let document_type_name = self.document_type_name.clone();
+                let action = if *key == 'n' {
+                    DocumentFormAction::Compose {
+                        data_contract_name,
+                        document_type_name,
+                    }
+                } else {
+                    DocumentFormAction::Create {
+                        data_contract_name,
+                        document_type_name,
+                    }
+                };
+                match DocumentFormController::new(
+                    &self.data_contract,
+                    &self.document_type_name,
+                    action,
+                ) {
+                    Ok(form) => ScreenFeedback::Form(Box::new(form)),
+                    Err(e) => {
+                        self.info = Info::new_error(&e);
+                        ScreenFeedback::Redraw
+                    }
+                }
+            }
+
             // Forward event to upper part of the screen for scrolls and stuff
             Event::Key(k) => {
                 if self.info.on_event(k) {
@@ -211,27 +276,73 @@ impl ScreenController for DocumentTypeScreenController {
 
             // Backend events handling
             Event::Backend(BackendEvent::TaskCompleted {
-                task: Task::Document(DocumentTask::QueryDocuments(_)),
+                task: Task::Document(DocumentTask::QueryDocuments(document_query)),
                 execution_result: Ok(CompletedTaskPayload::Documents(documents)),
             }) => {
+                let document_query = document_query.clone();
                 let documents = documents.clone();
                 ScreenFeedback::NextScreen(Box::new(move |_| {
                     async move {
-                        Box::new(DocumentsQuerysetScreenController::new(documents))
-                            as Box<dyn ScreenController>
+                        Box::new(DocumentsQuerysetScreenController::new(
+                            document_query,
+                            documents,
+                        )) as Box<dyn ScreenController>
                     }
                     .boxed()
                 }))
             }
 
             Event::Backend(BackendEvent::TaskCompleted {
-                task: Task::Document(DocumentTask::QueryDocuments(_)),
+                task: Task::Document(DocumentTask::QueryDocumentsWithClauses(clauses)),
+                execution_result: Ok(CompletedTaskPayload::Documents(documents)),
+            }) => match clauses.document_query() {
+                Ok(document_query) => {
+                    let documents = documents.clone();
+                    ScreenFeedback::NextScreen(Box::new(move |_| {
+                        async move {
+                            Box::new(DocumentsQuerysetScreenController::new(
+                                document_query,
+                                documents,
+                            )) as Box<dyn ScreenController>
+                        }
+                        .boxed()
+                    }))
+                }
+                Err(e) => {
+                    self.info = Info::new_error(&e);
+                    ScreenFeedback::Redraw
+                }
+            },
+
+            Event::Backend(BackendEvent::TaskCompleted {
+                task:
+                    Task::Document(
+                        DocumentTask::QueryDocuments(_)
+                        | DocumentTask::QueryDocumentsWithClauses(_),
+                    ),
                 execution_result: Err(e),
             }) => {
                 self.info = Info::new_error(&e);
                 ScreenFeedback::Redraw
             }
 
+            Event::Backend(BackendEvent::TaskCompleted {
+                task:
+                    Task::Document(
+                        DocumentTask::ComposeDocument { .. }
+                        | DocumentTask::BroadcastDocument { .. },
+                    ),
+                execution_result,
+            }) => {
+                self.info = match execution_result {
+                    Ok(CompletedTaskPayload::Document(document)) => {
+                        Info::new_scrollable(&document_json(document))
+                    }
+                    result => Info::new_from_result(result),
+                };
+                ScreenFeedback::Redraw
+            }
+
             Event::Backend(
                 BackendEvent::TaskCompleted {
                     task: Task::Document(DocumentTask::BroadcastRandomDocuments { .. }),
@@ -250,6 +361,25 @@ impl ScreenController for DocumentTypeScreenController {
             _ => ScreenFeedback::None,
         }
     }
+
+    /// The default query of the document type is the likely next step.
+    fn prefetch_task(&self) -> Option<Task> {
+        DocumentQueryTextInputParser::new(self.data_contract.clone())
+            .parse_input(&format!("Select * from {}", self.document_type.name()))
+            .ok()
+            .map(|query| Task::Document(DocumentTask::PrefetchDocuments(query)))
+    }
+
+    fn take_viewed_entity(&mut self) -> Option<RecentEntity> {
+        if self.listed {
+            return None;
+        }
+        self.listed = true;
+        Some(RecentEntity::DocumentType {
+            contract_name: self.data_contract_name.clone(),
+            document_type_name: self.document_type_name.clone(),
+        })
+    }
 }
 
 struct QueryDocumentTypeFormController {
@@ -299,8 +429,8 @@ impl FormController for QueryDocumentTypeFormController {
         self.input.view(frame, area)
     }
 
-    fn step_name(&self) -> &'static str {
-        "Query"
+    fn step_name(&self) -> Cow<'_, str> {
+        "Query".into()
     }
 
     fn step_index(&self) -> u8 {
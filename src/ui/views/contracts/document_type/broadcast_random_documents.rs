@@ -1,5 +1,7 @@
 //! Form to setup broadcasting of random documents.
 
+use std::borrow::Cow;
+
 use tuirealm::{event::KeyEvent, tui::prelude::Rect, Frame};
 
 use crate::{
@@ -48,8 +50,8 @@ impl FormController for BroadcastRandomDocumentsCountForm {
         self.input.view(frame, area);
     }
 
-    fn step_name(&self) -> &'static str {
-        "Documents count"
+    fn step_name(&self) -> Cow<'_, str> {
+        "Documents count".into()
     }
 
     fn step_index(&self) -> u8 {
@@ -0,0 +1,226 @@
+//! Form to compose a document query clause by clause.
+
+use std::borrow::Cow;
+
+use dpp::{
+    data_contract::document_type::{accessors::DocumentTypeV0Getters, DocumentType},
+    prelude::DataContract,
+};
+use tuirealm::{event::KeyEvent, tui::prelude::Rect, Frame};
+
+use crate::{
+    backend::{
+        documents::{
+            clauses::{QueryClauses, SYSTEM_FIELDS, WHERE_OPERATORS},
+            DocumentTask,
+        },
+        Task,
+    },
+    ui::form::{
+        parsers::DefaultTextInputParser, FormController, FormStatus, Input, InputStatus,
+        SelectInput, TextInput,
+    },
+};
+
+const NO_MORE_CONDITIONS: &str = "No more conditions";
+const NO_MORE_ORDERING: &str = "No more ordering";
+const ASCENDING: &str = "Ascending";
+const DESCENDING: &str = "Descending";
+
+enum QueryBuilderFormStep {
+    WhereField(SelectInput<String>),
+    WhereOperator(SelectInput<String>),
+    WhereValue(TextInput<DefaultTextInputParser<String>>),
+    OrderByField(SelectInput<String>),
+    OrderByDirection(SelectInput<String>),
+    Limit(TextInput<DefaultTextInputParser<u32>>),
+    StartAt(TextInput<DefaultTextInputParser<String>>),
+}
+
+pub(super) struct QueryBuilderFormController {
+    step: QueryBuilderFormStep,
+    fields: Vec<String>,
+    clauses: QueryClauses,
+    /// Field and operator of the condition waiting for its value
+    pending_condition: (String, String),
+    pending_order_by_field: String,
+}
+
+impl QueryBuilderFormController {
+    pub(super) fn new(data_contract: DataContract, document_type: &DocumentType) -> Self {
+        let fields: Vec<String> = SYSTEM_FIELDS
+            .into_iter()
+            .map(ToOwned::to_owned)
+            .chain(document_type.flattened_properties().keys().cloned())
+            .collect();
+        QueryBuilderFormController {
+            step: QueryBuilderFormStep::WhereField(Self::field_input(&fields, NO_MORE_CONDITIONS)),
+            fields,
+            clauses: QueryClauses {
+                data_contract,
+                document_type_name: document_type.name().clone(),
+                where_clauses: Vec::new(),
+                order_by: Vec::new(),
+                limit: 0,
+                start_at: None,
+            },
+            pending_condition: Default::default(),
+            pending_order_by_field: String::new(),
+        }
+    }
+
+    /// Selection of a field, or of the option to move on to the next clause
+    /// kind.
+    fn field_input(fields: &[String], done_option: &str) -> SelectInput<String> {
+        SelectInput::new(
+            [done_option.to_owned()]
+                .into_iter()
+                .chain(fields.iter().cloned())
+                .collect(),
+        )
+    }
+}
+
+impl FormController for QueryBuilderFormController {
+    fn on_event(&mut self, event: KeyEvent) -> FormStatus {
+        match &mut self.step {
+            QueryBuilderFormStep::WhereField(input) => match input.on_event(event) {
+                InputStatus::Done(field) if field == NO_MORE_CONDITIONS => {
+                    self.step = QueryBuilderFormStep::OrderByField(Self::field_input(
+                        &self.fields,
+                        NO_MORE_ORDERING,
+                    ));
+                    FormStatus::Redraw
+                }
+                InputStatus::Done(field) => {
+                    self.pending_condition.0 = field;
+                    self.step = QueryBuilderFormStep::WhereOperator(SelectInput::new(
+                        WHERE_OPERATORS.into_iter().map(ToOwned::to_owned).collect(),
+                    ));
+                    FormStatus::Redraw
+                }
+                status => status.into(),
+            },
+            QueryBuilderFormStep::WhereOperator(input) => match input.on_event(event) {
+                InputStatus::Done(operator) => {
+                    self.pending_condition.1 = operator;
+                    self.step = QueryBuilderFormStep::WhereValue(TextInput::new(
+                        "JSON value or text, base 58 for identifiers",
+                    ));
+                    FormStatus::Redraw
+                }
+                status => status.into(),
+            },
+            QueryBuilderFormStep::WhereValue(input) => match input.on_event(event) {
+                InputStatus::Done(value) => {
+                    let (field, operator) = std::mem::take(&mut self.pending_condition);
+                    self.clauses.where_clauses.push((field, operator, value));
+                    self.step = QueryBuilderFormStep::WhereField(Self::field_input(
+                        &self.fields,
+                        NO_MORE_CONDITIONS,
+                    ));
+                    FormStatus::Redraw
+                }
+                status => status.into(),
+            },
+            QueryBuilderFormStep::OrderByField(input) => match input.on_event(event) {
+                InputStatus::Done(field) if field == NO_MORE_ORDERING => {
+                    self.step = QueryBuilderFormStep::Limit(TextInput::new_init_value(
+                        "Number of documents, 0 for the default",
+                        0,
+                    ));
+                    FormStatus::Redraw
+                }
+                InputStatus::Done(field) => {
+                    self.pending_order_by_field = field;
+                    self.step = QueryBuilderFormStep::OrderByDirection(SelectInput::new(vec![
+                        ASCENDING.to_owned(),
+                        DESCENDING.to_owned(),
+                    ]));
+                    FormStatus::Redraw
+                }
+                status => status.into(),
+            },
+            QueryBuilderFormStep::OrderByDirection(input) => match input.on_event(event) {
+                InputStatus::Done(direction) => {
+                    self.clauses.order_by.push((
+                        std::mem::take(&mut self.pending_order_by_field),
+                        direction == ASCENDING,
+                    ));
+                    self.step = QueryBuilderFormStep::OrderByField(Self::field_input(
+                        &self.fields,
+                        NO_MORE_ORDERING,
+                    ));
+                    FormStatus::Redraw
+                }
+                status => status.into(),
+            },
+            QueryBuilderFormStep::Limit(input) => match input.on_event(event) {
+                InputStatus::Done(limit) => {
+                    self.clauses.limit = limit;
+                    self.step = QueryBuilderFormStep::StartAt(TextInput::new(
+                        "Document ID to start at, empty to start from the first one",
+                    ));
+                    FormStatus::Redraw
+                }
+                status => status.into(),
+            },
+            QueryBuilderFormStep::StartAt(input) => match input.on_event(event) {
+                InputStatus::Done(start_at) => {
+                    self.clauses.start_at = Some(start_at).filter(|id| !id.trim().is_empty());
+                    FormStatus::Done {
+                        task: Task::Document(DocumentTask::QueryDocumentsWithClauses(
+                            self.clauses.clone(),
+                        )),
+                        block: true,
+                    }
+                }
+                status => status.into(),
+            },
+        }
+    }
+
+    fn form_name(&self) -> &'static str {
+        "Query builder"
+    }
+
+    fn step_view(&mut self, frame: &mut Frame, area: Rect) {
+        match &mut self.step {
+            QueryBuilderFormStep::WhereField(input) => input.view(frame, area),
+            QueryBuilderFormStep::WhereOperator(input) => input.view(frame, area),
+            QueryBuilderFormStep::WhereValue(input) => input.view(frame, area),
+            QueryBuilderFormStep::OrderByField(input) => input.view(frame, area),
+            QueryBuilderFormStep::OrderByDirection(input) => input.view(frame, area),
+            QueryBuilderFormStep::Limit(input) => input.view(frame, area),
+            QueryBuilderFormStep::StartAt(input) => input.view(frame, area),
+        }
+    }
+
+    fn step_name(&self) -> Cow<'_, str> {
+        match self.step {
+            QueryBuilderFormStep::WhereField(_) => "Condition field".into(),
+            QueryBuilderFormStep::WhereOperator(_) => "Condition operator".into(),
+            QueryBuilderFormStep::WhereValue(_) => "Condition value".into(),
+            QueryBuilderFormStep::OrderByField(_) => "Order by field".into(),
+            QueryBuilderFormStep::OrderByDirection(_) => "Order direction".into(),
+            QueryBuilderFormStep::Limit(_) => "Limit".into(),
+            QueryBuilderFormStep::StartAt(_) => "Start at".into(),
+        }
+    }
+
+    fn step_index(&self) -> u8 {
+        match self.step {
+            QueryBuilderFormStep::WhereField(_) => 0,
+            QueryBuilderFormStep::WhereOperator(_) => 1,
+            QueryBuilderFormStep::WhereValue(_) => 2,
+            QueryBuilderFormStep::OrderByField(_) => 3,
+            QueryBuilderFormStep::OrderByDirection(_) => 4,
+            QueryBuilderFormStep::Limit(_) => 5,
+            QueryBuilderFormStep::StartAt(_) => 6,
+        }
+    }
+
+    fn steps_number(&self) -> u8 {
+        7
+    }
+}
@@ -1,5 +1,7 @@
 //! Contract fetching screen module.
 
+use std::borrow::Cow;
+
 use tuirealm::{
     event::{Key, KeyEvent, KeyModifiers},
     tui::prelude::Rect,
@@ -8,12 +10,13 @@ use tuirealm::{
 
 use crate::ui::views::contracts::ContractTask::FetchContract;
 use crate::{
-    backend::{BackendEvent, Task},
+    backend::{proofs::ProvedFetch, BackendEvent, CompletedTaskPayload, Task},
     ui::{
         form::{
             parsers::DefaultTextInputParser, FormController, FormStatus, Input, InputStatus,
             TextInput,
         },
+        proofs::{self, ProofPanel},
         screen::{
             utils::impl_builder_no_args, widgets::info::Info, ScreenCommandKey, ScreenController,
             ScreenFeedback, ScreenToggleKey,
@@ -28,21 +31,35 @@ const COMMAND_KEYS: [ScreenCommandKey; 2] = [
 ];
 
 pub(crate) struct FetchContractScreenController {
+    toggle_keys: [ScreenToggleKey; 2],
     info: Info,
+    /// Proof of the contract shown, if fetched with proof details
+    proof: Option<ProofPanel>,
 }
 
 impl_builder_no_args!(FetchContractScreenController);
 
 impl FetchContractScreenController {
     pub(crate) fn new() -> Self {
+        let mut toggle_keys = [
+            ScreenToggleKey::new("p", "with proof"),
+            ScreenToggleKey::new("o", "expand proof"),
+        ];
+        toggle_keys[0].toggle = proofs::proof_details();
         Self {
+            toggle_keys,
             info: Info::new_fixed("Fetch contracts"),
+            proof: None,
         }
     }
 }
 
 impl ScreenController for FetchContractScreenController {
     fn view(&mut self, frame: &mut Frame, area: Rect) {
+        let area = match &self.proof {
+            Some(proof) => proof.view(frame, area),
+            None => area,
+        };
         self.info.view(frame, area)
     }
 
@@ -55,7 +72,7 @@ impl ScreenController for FetchContractScreenController {
     }
 
     fn toggle_keys(&self) -> &[ScreenToggleKey] {
-        &[]
+        self.toggle_keys.as_ref()
     }
 
     fn on_event(&mut self, event: &Event) -> ScreenFeedback {
@@ -68,7 +85,28 @@ impl ScreenController for FetchContractScreenController {
             Event::Key(KeyEvent {
                 code: Key::Char('f'),
                 modifiers: KeyModifiers::NONE,
-            }) => ScreenFeedback::Form(Box::new(GetContractByIdFormController::new())),
+            }) => ScreenFeedback::Form(Box::new(GetContractByIdFormController::new(
+                self.toggle_keys[0].toggle,
+            ))),
+
+            Event::Key(KeyEvent {
+                code: Key::Char('p'),
+                modifiers: KeyModifiers::NONE,
+            }) => {
+                self.toggle_keys[0].toggle = !self.toggle_keys[0].toggle;
+                ScreenFeedback::Redraw
+            }
+
+            Event::Key(KeyEvent {
+                code: Key::Char('o'),
+                modifiers: KeyModifiers::NONE,
+            }) => {
+                self.toggle_keys[1].toggle = !self.toggle_keys[1].toggle;
+                if let Some(proof) = &mut self.proof {
+                    proof.set_expanded(self.toggle_keys[1].toggle);
+                }
+                ScreenFeedback::Redraw
+            }
 
             Event::Backend(
                 BackendEvent::TaskCompleted {
@@ -82,6 +120,21 @@ impl ScreenController for FetchContractScreenController {
                 },
             ) => {
                 self.info = Info::new_from_result(execution_result);
+                self.proof = None;
+                ScreenFeedback::Redraw
+            }
+
+            Event::Backend(BackendEvent::TaskCompleted {
+                task: Task::FetchWithProof(ProvedFetch::Contract(_)),
+                execution_result,
+            }) => {
+                self.info = Info::new_from_result(execution_result);
+                self.proof = match execution_result {
+                    Ok(CompletedTaskPayload::Proved { proof, .. }) => {
+                        Some(ProofPanel::new(proof.clone(), self.toggle_keys[1].toggle))
+                    }
+                    _ => None,
+                };
                 ScreenFeedback::Redraw
             }
             _ => ScreenFeedback::None,
@@ -91,12 +144,14 @@ impl ScreenController for FetchContractScreenController {
 
 pub(crate) struct GetContractByIdFormController {
     input: TextInput<DefaultTextInputParser<String>>, // TODO: b58 parser
+    with_proof: bool,
 }
 
 impl GetContractByIdFormController {
-    fn new() -> Self {
+    fn new(with_proof: bool) -> Self {
         Self {
             input: TextInput::new("base58 id"),
+            with_proof,
         }
     }
 }
@@ -105,7 +160,11 @@ impl FormController for GetContractByIdFormController {
     fn on_event(&mut self, event: KeyEvent) -> FormStatus {
         match self.input.on_event(event) {
             InputStatus::Done(value) => FormStatus::Done {
-                task: Task::Contract(FetchContract(value)),
+                task: if self.with_proof {
+                    Task::FetchWithProof(ProvedFetch::Contract(value))
+                } else {
+                    Task::Contract(FetchContract(value))
+                },
                 block: true,
             },
             status => status.into(),
@@ -120,8 +179,8 @@ impl FormController for GetContractByIdFormController {
         "Get Contract by ID"
     }
 
-    fn step_name(&self) -> &'static str {
-        "Base 58 ID"
+    fn step_name(&self) -> Cow<'_, str> {
+        "Base 58 ID".into()
     }
 
     fn step_index(&self) -> u8 {
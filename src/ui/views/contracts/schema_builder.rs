@@ -0,0 +1,500 @@
+//! Document type schema builder screen and forms.
+
+use std::borrow::Cow;
+
+use tuirealm::{
+    event::{Key, KeyEvent, KeyModifiers},
+    tui::prelude::Rect,
+    Frame,
+};
+
+use crate::{
+    backend::{
+        as_json_string,
+        schema_builder::{IndexDraft, PropertyDraft, PropertyType, SchemaBuilderTask},
+        AppState, AppStateUpdate, BackendEvent, Task,
+    },
+    ui::{
+        form::{
+            parsers::DefaultTextInputParser, ComposedInput, Field, FormController, FormStatus,
+            Input, InputStatus, SelectInput, TextInput,
+        },
+        screen::{
+            utils::impl_builder, widgets::info::Info, ScreenCommandKey, ScreenController,
+            ScreenFeedback, ScreenToggleKey,
+        },
+    },
+    Event,
+};
+
+const COMMAND_KEYS: [ScreenCommandKey; 10] = [
+    ScreenCommandKey::new("q", "Back to Contracts"),
+    ScreenCommandKey::new("n", "Set document type name"),
+    ScreenCommandKey::new("p", "Add property"),
+    ScreenCommandKey::new("x", "Remove last property"),
+    ScreenCommandKey::new("i", "Add index"),
+    ScreenCommandKey::new("d", "Remove last index"),
+    ScreenCommandKey::new("c", "Clear"),
+    ScreenCommandKey::new("r", "Register contract"),
+    ScreenCommandKey::new("l", "Load document type"),
+    ScreenCommandKey::new("u", "Update contract"),
+];
+
+pub(super) struct SchemaBuilderScreenController {
+    info: Info,
+    /// Known contracts to load a document type from or update
+    contract_names: Vec<String>,
+}
+
+impl_builder!(SchemaBuilderScreenController);
+
+impl SchemaBuilderScreenController {
+    pub(super) async fn new(app_state: &AppState) -> Self {
+        let draft = app_state.schema_draft.lock().await;
+        SchemaBuilderScreenController {
+            info: Info::new_scrollable(&as_json_string(&draft.documents_schema())),
+            contract_names: app_state
+                .known_contracts
+                .lock()
+                .await
+                .keys()
+                .cloned()
+                .collect(),
+        }
+    }
+}
+
+impl ScreenController for SchemaBuilderScreenController {
+    fn name(&self) -> &'static str {
+        "Schema builder"
+    }
+
+    fn command_keys(&self) -> &[ScreenCommandKey] {
+        COMMAND_KEYS.as_ref()
+    }
+
+    fn toggle_keys(&self) -> &[ScreenToggleKey] {
+        &[]
+    }
+
+    fn on_event(&mut self, event: &Event) -> ScreenFeedback {
+        match event {
+            Event::Key(KeyEvent {
+                code: Key::Char('q'),
+                modifiers: KeyModifiers::NONE,
+            }) => ScreenFeedback::PreviousScreen,
+            Event::Key(KeyEvent {
+                code: Key::Char('n'),
+                modifiers: KeyModifiers::NONE,
+            }) => ScreenFeedback::Form(Box::new(DocumentTypeNameFormController::new())),
+            Event::Key(KeyEvent {
+                code: Key::Char('p'),
+                modifiers: KeyModifiers::NONE,
+            }) => ScreenFeedback::Form(Box::new(AddPropertyFormController::new())),
+            Event::Key(KeyEvent {
+                code: Key::Char('x'),
+                modifiers: KeyModifiers::NONE,
+            }) => ScreenFeedback::Task {
+                task: Task::SchemaBuilder(SchemaBuilderTask::RemoveLastProperty),
+                block: false,
+            },
+            Event::Key(KeyEvent {
+                code: Key::Char('i'),
+                modifiers: KeyModifiers::NONE,
+            }) => ScreenFeedback::Form(Box::new(AddIndexFormController::new())),
+            Event::Key(KeyEvent {
+                code: Key::Char('d'),
+                modifiers: KeyModifiers::NONE,
+            }) => ScreenFeedback::Task {
+                task: Task::SchemaBuilder(SchemaBuilderTask::RemoveLastIndex),
+                block: false,
+            },
+            Event::Key(KeyEvent {
+                code: Key::Char('c'),
+                modifiers: KeyModifiers::NONE,
+            }) => ScreenFeedback::Task {
+                task: Task::SchemaBuilder(SchemaBuilderTask::Clear),
+                block: false,
+            },
+            Event::Key(KeyEvent {
+                code: Key::Char('r'),
+                modifiers: KeyModifiers::NONE,
+            }) => ScreenFeedback::Form(Box::new(RegisterContractFormController::new())),
+            Event::Key(KeyEvent {
+                code: Key::Char('l'),
+                modifiers: KeyModifiers::NONE,
+            }) if !self.contract_names.is_empty() => ScreenFeedback::Form(Box::new(
+                LoadDocumentTypeFormController::new(self.contract_names.clone()),
+            )),
+            Event::Key(KeyEvent {
+                code: Key::Char('u'),
+                modifiers: KeyModifiers::NONE,
+            }) if !self.contract_names.is_empty() => ScreenFeedback::Form(Box::new(
+                UpdateContractFormController::new(self.contract_names.clone()),
+            )),
+            Event::Backend(BackendEvent::TaskCompletedStateChange {
+                task: Task::SchemaBuilder(_),
+                execution_result,
+                app_state_update: AppStateUpdate::KnownContracts(known_contracts),
+            }) => {
+                self.contract_names = known_contracts.keys().cloned().collect();
+                self.info = Info::new_from_result(execution_result);
+                ScreenFeedback::Redraw
+            }
+            Event::Backend(
+                BackendEvent::TaskCompleted {
+                    task: Task::SchemaBuilder(_),
+                    execution_result,
+                }
+                | BackendEvent::TaskCompletedStateChange {
+                    task: Task::SchemaBuilder(_),
+                    execution_result,
+                    ..
+                },
+            ) => {
+                self.info = Info::new_from_result(execution_result);
+                ScreenFeedback::Redraw
+            }
+            Event::Key(k) => {
+                if self.info.on_event(k) {
+                    ScreenFeedback::Redraw
+                } else {
+                    ScreenFeedback::None
+                }
+            }
+            _ => ScreenFeedback::None,
+        }
+    }
+
+    fn view(&mut self, frame: &mut Frame, area: Rect) {
+        self.info.view(frame, area)
+    }
+}
+
+struct DocumentTypeNameFormController {
+    input: TextInput<DefaultTextInputParser<String>>,
+}
+
+impl DocumentTypeNameFormController {
+    fn new() -> Self {
+        DocumentTypeNameFormController {
+            input: TextInput::new("document type name"),
+        }
+    }
+}
+
+impl FormController for DocumentTypeNameFormController {
+    fn on_event(&mut self, event: KeyEvent) -> FormStatus {
+        match self.input.on_event(event) {
+            InputStatus::Done(name) => FormStatus::Done {
+                task: Task::SchemaBuilder(SchemaBuilderTask::SetDocumentTypeName(name)),
+                block: false,
+            },
+            status => status.into(),
+        }
+    }
+
+    fn form_name(&self) -> &'static str {
+        "Document type name"
+    }
+
+    fn step_view(&mut self, frame: &mut Frame, area: Rect) {
+        self.input.view(frame, area)
+    }
+
+    fn step_name(&self) -> Cow<'_, str> {
+        "Name".into()
+    }
+
+    fn step_index(&self) -> u8 {
+        0
+    }
+
+    fn steps_number(&self) -> u8 {
+        1
+    }
+}
+
+struct AddPropertyFormController {
+    input: ComposedInput<(
+        Field<TextInput<DefaultTextInputParser<String>>>,
+        Field<SelectInput<PropertyType>>,
+        Field<SelectInput<String>>,
+        Field<TextInput<DefaultTextInputParser<u64>>>,
+    )>,
+}
+
+impl AddPropertyFormController {
+    fn new() -> Self {
+        AddPropertyFormController {
+            input: ComposedInput::new((
+                Field::new("Property name", TextInput::new("property name")),
+                Field::new(
+                    "Property type",
+                    SelectInput::new(vec![
+                        PropertyType::String,
+                        PropertyType::Integer,
+                        PropertyType::Number,
+                        PropertyType::Boolean,
+                        PropertyType::ByteArray,
+                    ]),
+                ),
+                Field::new(
+                    "Required",
+                    SelectInput::new(vec!["Yes".to_string(), "No".to_string()]),
+                ),
+                Field::new(
+                    "Max length / maximum / max items",
+                    TextInput::new("0 for no limit"),
+                ),
+            )),
+        }
+    }
+}
+
+impl FormController for AddPropertyFormController {
+    fn on_event(&mut self, event: KeyEvent) -> FormStatus {
+        match self.input.on_event(event) {
+            InputStatus::Done((name, property_type, required, limit)) => FormStatus::Done {
+                task: Task::SchemaBuilder(SchemaBuilderTask::AddProperty(PropertyDraft {
+                    name,
+                    property_type,
+                    required: required == "Yes",
+                    limit: (limit > 0).then_some(limit),
+                })),
+                block: false,
+            },
+            status => status.into(),
+        }
+    }
+
+    fn form_name(&self) -> &'static str {
+        "Add property"
+    }
+
+    fn step_view(&mut self, frame: &mut Frame, area: Rect) {
+        self.input.view(frame, area)
+    }
+
+    fn step_name(&self) -> Cow<'_, str> {
+        self.input.step_name().into()
+    }
+
+    fn step_index(&self) -> u8 {
+        self.input.step_index()
+    }
+
+    fn steps_number(&self) -> u8 {
+        4
+    }
+}
+
+struct AddIndexFormController {
+    input: ComposedInput<(
+        Field<TextInput<DefaultTextInputParser<String>>>,
+        Field<TextInput<DefaultTextInputParser<String>>>,
+        Field<SelectInput<String>>,
+    )>,
+}
+
+impl AddIndexFormController {
+    fn new() -> Self {
+        AddIndexFormController {
+            input: ComposedInput::new((
+                Field::new("Index name", TextInput::new("index name")),
+                Field::new(
+                    "Indexed properties",
+                    TextInput::new("comma separated property names"),
+                ),
+                Field::new(
+                    "Unique",
+                    SelectInput::new(vec!["No".to_string(), "Yes".to_string()]),
+                ),
+            )),
+        }
+    }
+}
+
+impl FormController for AddIndexFormController {
+    fn on_event(&mut self, event: KeyEvent) -> FormStatus {
+        match self.input.on_event(event) {
+            InputStatus::Done((name, properties, unique)) => FormStatus::Done {
+                task: Task::SchemaBuilder(SchemaBuilderTask::AddIndex(IndexDraft {
+                    name,
+                    properties: properties
+                        .split(',')
+                        .map(|property| property.trim().to_owned())
+                        .filter(|property| !property.is_empty())
+                        .collect(),
+                    unique: unique == "Yes",
+                })),
+                block: false,
+            },
+            status => status.into(),
+        }
+    }
+
+    fn form_name(&self) -> &'static str {
+        "Add index"
+    }
+
+    fn step_view(&mut self, frame: &mut Frame, area: Rect) {
+        self.input.view(frame, area)
+    }
+
+    fn step_name(&self) -> Cow<'_, str> {
+        self.input.step_name().into()
+    }
+
+    fn step_index(&self) -> u8 {
+        self.input.step_index()
+    }
+
+    fn steps_number(&self) -> u8 {
+        3
+    }
+}
+
+struct RegisterContractFormController {
+    input: TextInput<DefaultTextInputParser<String>>,
+}
+
+impl RegisterContractFormController {
+    fn new() -> Self {
+        RegisterContractFormController {
+            input: TextInput::new("name to save the contract under"),
+        }
+    }
+}
+
+impl FormController for RegisterContractFormController {
+    fn on_event(&mut self, event: KeyEvent) -> FormStatus {
+        match self.input.on_event(event) {
+            InputStatus::Done(contract_name) => FormStatus::Done {
+                task: Task::SchemaBuilder(SchemaBuilderTask::RegisterContract(contract_name)),
+                block: true,
+            },
+            status => status.into(),
+        }
+    }
+
+    fn form_name(&self) -> &'static str {
+        "Register contract"
+    }
+
+    fn step_view(&mut self, frame: &mut Frame, area: Rect) {
+        self.input.view(frame, area)
+    }
+
+    fn step_name(&self) -> Cow<'_, str> {
+        "Contract name".into()
+    }
+
+    fn step_index(&self) -> u8 {
+        0
+    }
+
+    fn steps_number(&self) -> u8 {
+        1
+    }
+}
+
+struct LoadDocumentTypeFormController {
+    input: ComposedInput<(
+        Field<SelectInput<String>>,
+        Field<TextInput<DefaultTextInputParser<String>>>,
+    )>,
+}
+
+impl LoadDocumentTypeFormController {
+    fn new(contract_names: Vec<String>) -> Self {
+        LoadDocumentTypeFormController {
+            input: ComposedInput::new((
+                Field::new("Contract", SelectInput::new(contract_names)),
+                Field::new(
+                    "Document type name",
+                    TextInput::new("document type to edit or a new one"),
+                ),
+            )),
+        }
+    }
+}
+
+impl FormController for LoadDocumentTypeFormController {
+    fn on_event(&mut self, event: KeyEvent) -> FormStatus {
+        match self.input.on_event(event) {
+            InputStatus::Done((contract_name, document_type_name)) => FormStatus::Done {
+                task: Task::SchemaBuilder(SchemaBuilderTask::LoadDocumentType {
+                    contract_name,
+                    document_type_name,
+                }),
+                block: false,
+            },
+            status => status.into(),
+        }
+    }
+
+    fn form_name(&self) -> &'static str {
+        "Load document type"
+    }
+
+    fn step_view(&mut self, frame: &mut Frame, area: Rect) {
+        self.input.view(frame, area)
+    }
+
+    fn step_name(&self) -> Cow<'_, str> {
+        self.input.step_name().into()
+    }
+
+    fn step_index(&self) -> u8 {
+        self.input.step_index()
+    }
+
+    fn steps_number(&self) -> u8 {
+        2
+    }
+}
+
+struct UpdateContractFormController {
+    input: SelectInput<String>,
+}
+
+impl UpdateContractFormController {
+    fn new(contract_names: Vec<String>) -> Self {
+        UpdateContractFormController {
+            input: SelectInput::new(contract_names),
+        }
+    }
+}
+
+impl FormController for UpdateContractFormController {
+    fn on_event(&mut self, event: KeyEvent) -> FormStatus {
+        match self.input.on_event(event) {
+            InputStatus::Done(contract_name) => FormStatus::Done {
+                task: Task::SchemaBuilder(SchemaBuilderTask::UpdateContract(contract_name)),
+                block: true,
+            },
+            status => status.into(),
+        }
+    }
+
+    fn form_name(&self) -> &'static str {
+        "Update contract"
+    }
+
+    fn step_view(&mut self, frame: &mut Frame, area: Rect) {
+        self.input.view(frame, area)
+    }
+
+    fn step_name(&self) -> Cow<'_, str> {
+        "Contract".into()
+    }
+
+    fn step_index(&self) -> u8 {
+        0
+    }
+
+    fn steps_number(&self) -> u8 {
+        1
+    }
+}
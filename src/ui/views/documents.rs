@@ -1,51 +1,123 @@
 //! View for fetched documents navigation and inspection.
 
-use std::collections::BTreeMap;
+pub(crate) mod document_form;
 
-use dpp::{document::Document, platform_value::string_encoding::Encoding, prelude::Identifier};
+use std::{
+    borrow::Cow,
+    collections::{BTreeMap, BTreeSet},
+};
+
+use dapi_grpc::platform::v0::get_documents_request::get_documents_request_v0::Start;
+use dash_sdk::platform::DocumentQuery;
+use dpp::{
+    document::{Document, DocumentV0Getters},
+    platform_value::string_encoding::Encoding,
+    prelude::Identifier,
+};
 use tuirealm::{
     command::{self, Cmd},
     event::{Key, KeyEvent, KeyModifiers},
-    props::{BorderSides, Borders, Color, TextSpan},
+    props::{Alignment, BorderSides, Color, TextSpan},
     tui::prelude::{Constraint, Direction, Layout, Rect},
     AttrValue, Attribute, Frame, MockComponent,
 };
 
+use self::document_form::{DocumentFormAction, DocumentFormController};
 use crate::{
-    backend::as_json_string,
-    ui::screen::{
-        widgets::info::Info, ScreenCommandKey, ScreenController, ScreenFeedback, ScreenToggleKey,
+    backend::{
+        as_json_string,
+        documents::{
+            binary::{document_json, large_binary_fields},
+            bulk::ExportFormat,
+            prefetch::query_key,
+            DocumentTask,
+        },
+        export::ExportedEntity,
+        proofs::ProvedFetch,
+        BackendEvent, CompletedTaskPayload, Task,
+    },
+    ui::{
+        density,
+        form::{
+            parsers::DefaultTextInputParser, ComposedInput, Field, FormController, FormStatus,
+            Input, InputStatus, SelectInput, TextInput,
+        },
+        mouse::{self, MouseAction},
+        proofs::{self, ProofPanel},
+        recent::RecentEntity,
+        screen::{
+            widgets::info::Info, ScreenCommandKey, ScreenController, ScreenFeedback,
+            ScreenToggleKey,
+        },
+        views::{bookmarks::BookmarkDocumentFormController, export::ExportEntityFormController},
     },
     Event,
 };
 
-const COMMAND_KEYS: [ScreenCommandKey; 5] = [
+const COMMAND_KEYS: [ScreenCommandKey; 21] = [
     ScreenCommandKey::new("q", "Back to Contracts"),
     ScreenCommandKey::new("C-n", "Next document"),
     ScreenCommandKey::new("C-p", "Prev document"),
+    ScreenCommandKey::new("n", "Next page"),
+    ScreenCommandKey::new("p", "Prev page"),
     ScreenCommandKey::new("↓", "Scroll doc down"),
     ScreenCommandKey::new("↑", "Scroll doc up"),
+    ScreenCommandKey::new("Space", "Mark document"),
+    ScreenCommandKey::new("a", "Mark all / none"),
+    ScreenCommandKey::new("e", "Export marked"),
+    ScreenCommandKey::new("r", "Re-fetch marked with proof"),
+    ScreenCommandKey::new("d", "Delete marked"),
+    ScreenCommandKey::new("b", "Bookmark document"),
+    ScreenCommandKey::new("u", "Replace document"),
+    ScreenCommandKey::new("x", "Delete document"),
+    ScreenCommandKey::new("j", "Join references"),
+    ScreenCommandKey::new("s", "Save binary field"),
+    ScreenCommandKey::new("y", "Copy document ID"),
+    ScreenCommandKey::new("C-y", "Copy document JSON"),
+    ScreenCommandKey::new("C-e", "Export to file"),
+    ScreenCommandKey::new("v", "Verify page proof"),
 ];
 
+/// Document of the batch with large byte arrays summarized, deleted ones are
+/// `null`.
+fn document_text(document: &Option<Document>) -> String {
+    document
+        .as_ref()
+        .map(document_json)
+        .unwrap_or_else(|| as_json_string(document))
+}
+
+/// Page of the queryset waiting for its documents.
+enum PageRequest {
+    Next,
+    Previous,
+}
+
 pub(crate) struct DocumentsQuerysetScreenController {
+    document_query: DocumentQuery,
+    /// Start cursors of the pages before the current one
+    previous_pages: Vec<Option<Start>>,
+    page_request: Option<PageRequest>,
+    document_ids: Vec<Identifier>,
     current_batch: Vec<Option<Document>>,
+    marked: BTreeSet<usize>,
     document_select: tui_realm_stdlib::List,
+    /// Where document rows were last drawn, within the list borders
+    document_select_area: Rect,
     document_view: Info,
+    toggle_keys: [ScreenToggleKey; 1],
+    /// Proof of the current page, if verified
+    proof: Option<ProofPanel>,
+    /// Document listed among recent entities last
+    listed_document: Option<Identifier>,
 }
 
 impl DocumentsQuerysetScreenController {
-    pub(crate) fn new(current_batch: BTreeMap<Identifier, Option<Document>>) -> Self {
+    pub(crate) fn new(
+        document_query: DocumentQuery,
+        current_batch: BTreeMap<Identifier, Option<Document>>,
+    ) -> Self {
         let mut document_select = tui_realm_stdlib::List::default()
-            .rows(
-                current_batch
-                    .keys()
-                    .map(|v| vec![TextSpan::new(v.to_string(Encoding::Base58))])
-                    .collect(),
-            )
-            .borders(
-                Borders::default()
-                    .sides(BorderSides::LEFT | BorderSides::TOP | BorderSides::BOTTOM),
-            )
             .selected_line(0)
             .highlighted_color(Color::Magenta);
         document_select.attr(Attribute::Scroll, AttrValue::Flag(true));
@@ -54,14 +126,127 @@ impl DocumentsQuerysetScreenController {
         let document_view = Info::new_scrollable(
             &current_batch
                 .first_key_value()
-                .map(|(_, v)| as_json_string(v))
+                .map(|(_, v)| document_text(v))
                 .unwrap_or_else(String::new),
         );
 
-        DocumentsQuerysetScreenController {
-            current_batch: current_batch.into_values().collect(),
+        let (document_ids, current_batch) = current_batch.into_iter().unzip();
+
+        let mut controller = DocumentsQuerysetScreenController {
+            document_query,
+            previous_pages: Vec::new(),
+            page_request: None,
+            document_ids,
+            current_batch,
+            marked: BTreeSet::new(),
             document_select,
+            document_select_area: Rect::default(),
             document_view,
+            toggle_keys: [ScreenToggleKey::new("o", "expand proof")],
+            proof: None,
+            listed_document: None,
+        };
+        controller.update_rows();
+        controller
+    }
+
+    fn selected_index(&self) -> usize {
+        self.document_select.state().unwrap_one().unwrap_usize()
+    }
+
+    fn update_rows(&mut self) {
+        let rows = self
+            .document_ids
+            .iter()
+            .enumerate()
+            .map(|(i, id)| {
+                let mark = if self.marked.contains(&i) { "*" } else { " " };
+                vec![TextSpan::new(format!(
+                    "[{}] {}",
+                    mark,
+                    id.to_string(Encoding::Base58)
+                ))]
+            })
+            .collect();
+        self.document_select
+            .attr(Attribute::Content, AttrValue::Table(rows));
+    }
+
+    /// Marked documents that were found, deleted ones have nothing to act upon.
+    fn marked_documents(&self) -> Vec<Document> {
+        self.marked
+            .iter()
+            .filter_map(|i| self.current_batch.get(*i).cloned().flatten())
+            .collect()
+    }
+
+    fn marked_ids(&self) -> Vec<Identifier> {
+        self.marked
+            .iter()
+            .filter_map(|i| self.document_ids.get(*i).copied())
+            .collect()
+    }
+
+    fn selected_document(&self) -> Option<Document> {
+        self.current_batch
+            .get(self.selected_index())
+            .cloned()
+            .flatten()
+    }
+
+    /// Query of the page after the current one, starting after its last
+    /// document. Documents come sorted by their ids, so pages are known only
+    /// for queries without ordering clauses.
+    fn next_page_query(&self) -> Result<DocumentQuery, &'static str> {
+        if !self.document_query.order_by_clauses.is_empty() {
+            return Err("Pages are only available for queries ordered by document ID");
+        }
+        if self.document_query.limit != 0
+            && self.document_ids.len() < self.document_query.limit as usize
+        {
+            return Err("No more documents");
+        }
+        let last_id = self.document_ids.last().ok_or("No more documents")?;
+        let mut next_page = self.document_query.clone();
+        next_page.start = Some(Start::StartAfter(last_id.to_vec()));
+        Ok(next_page)
+    }
+
+    fn request_page(&mut self, query: DocumentQuery, request: PageRequest) -> ScreenFeedback {
+        self.page_request = Some(request);
+        ScreenFeedback::Task {
+            task: Task::Document(DocumentTask::QueryDocuments(query)),
+            block: true,
+        }
+    }
+
+    /// Replaces documents of the screen with those of another page.
+    fn show_page(
+        &mut self,
+        document_query: DocumentQuery,
+        batch: &BTreeMap<Identifier, Option<Document>>,
+    ) {
+        self.document_query = document_query;
+        (self.document_ids, self.current_batch) =
+            batch.iter().map(|(id, doc)| (*id, doc.clone())).unzip();
+        self.marked.clear();
+        self.proof = None;
+        self.update_rows();
+        self.document_select
+            .perform(Cmd::GoTo(command::Position::Begin));
+        self.update_document_view();
+    }
+
+    /// Task fetching the current page with proof details, if they're on for
+    /// every fetch.
+    fn page_proof_feedback(&self) -> ScreenFeedback {
+        if proofs::proof_details() {
+            ScreenFeedback::Task {
+                task: Task::FetchWithProof(ProvedFetch::Documents(self.document_query.clone())),
+                block: false,
+            }
+        } else {
+            ScreenFeedback::Redraw
         }
     }
 
@@ -69,8 +254,8 @@ impl DocumentsQuerysetScreenController {
         self.document_view = Info::new_scrollable(
             &self
                 .current_batch
-                .get(self.document_select.state().unwrap_one().unwrap_usize())
-                .map(|v| as_json_string(&v))
+                .get(self.selected_index())
+                .map(document_text)
                 .unwrap_or_else(String::new),
         );
     }
@@ -83,8 +268,26 @@ impl ScreenController for DocumentsQuerysetScreenController {
             .constraints([Constraint::Max(40), Constraint::Min(1)].as_ref())
             .split(area);
 
+        let borders = density::borders(
+            BorderSides::LEFT | BorderSides::TOP | BorderSides::BOTTOM,
+            BorderSides::RIGHT,
+        );
+        self.document_select_area = mouse::inner_area(layout[0], &borders);
+        self.document_select
+            .attr(Attribute::Borders, AttrValue::Borders(borders));
+        self.document_select.attr(
+            Attribute::Title,
+            AttrValue::Title((
+                format!("Page {}", self.previous_pages.len() + 1),
+                Alignment::Left,
+            )),
+        );
         self.document_select.view(frame, layout[0]);
-        self.document_view.view(frame, layout[1]);
+        let view_area = match &self.proof {
+            Some(proof) => proof.view(frame, layout[1]),
+            None => layout[1],
+        };
+        self.document_view.view(frame, view_area);
     }
 
     fn name(&self) -> &'static str {
@@ -96,7 +299,7 @@ impl ScreenController for DocumentsQuerysetScreenController {
     }
 
     fn toggle_keys(&self) -> &[ScreenToggleKey] {
-        &[]
+        self.toggle_keys.as_ref()
     }
 
     fn on_event(&mut self, event: &Event) -> ScreenFeedback {
@@ -106,6 +309,25 @@ impl ScreenController for DocumentsQuerysetScreenController {
                 modifiers: KeyModifiers::NONE,
             }) => ScreenFeedback::PreviousScreen,
 
+            // Proof of the page
+            Event::Key(KeyEvent {
+                code: Key::Char('v'),
+                modifiers: KeyModifiers::NONE,
+            }) => ScreenFeedback::Task {
+                task: Task::FetchWithProof(ProvedFetch::Documents(self.document_query.clone())),
+                block: true,
+            },
+            Event::Key(KeyEvent {
+                code: Key::Char('o'),
+                modifiers: KeyModifiers::NONE,
+            }) => {
+                self.toggle_keys[0].toggle = !self.toggle_keys[0].toggle;
+                if let Some(proof) = &mut self.proof {
+                    proof.set_expanded(self.toggle_keys[0].toggle);
+                }
+                ScreenFeedback::Redraw
+            }
+
             // Document view keys
             Event::Key(
                 key_event @ KeyEvent {
@@ -136,7 +358,536 @@ impl ScreenController for DocumentsQuerysetScreenController {
                 self.update_document_view();
                 ScreenFeedback::Redraw
             }
+
+            // Document selection with the mouse, the wheel elsewhere scrolls
+            // the document
+            Event::Mouse(event) => {
+                let selected = self.selected_index();
+                let rows = self.document_ids.len();
+                let index = match mouse::action_within(event, self.document_select_area) {
+                    Some(MouseAction::ScrollUp) => selected.checked_sub(1),
+                    Some(MouseAction::ScrollDown) => Some(selected + 1).filter(|i| *i < rows),
+                    Some(MouseAction::Click(row)) => {
+                        mouse::list_row(row, self.document_select_area.height, selected, rows)
+                    }
+                    None => return ScreenFeedback::None,
+                };
+                if let Some(index) = index {
+                    mouse::select_row(&mut self.document_select, selected, index);
+                    self.update_document_view();
+                }
+                ScreenFeedback::Redraw
+            }
+
+            // Pages
+            Event::Key(KeyEvent {
+                code: Key::Char('n'),
+                modifiers: KeyModifiers::NONE,
+            }) => match self.next_page_query() {
+                Ok(next_page) => self.request_page(next_page, PageRequest::Next),
+                Err(e) => {
+                    self.document_view = Info::new_error(e);
+                    ScreenFeedback::Redraw
+                }
+            },
+            Event::Key(KeyEvent {
+                code: Key::Char('p'),
+                modifiers: KeyModifiers::NONE,
+            }) => match self.previous_pages.last() {
+                Some(start) => {
+                    let mut previous_page = self.document_query.clone();
+                    previous_page.start = start.clone();
+                    self.request_page(previous_page, PageRequest::Previous)
+                }
+                None => {
+                    self.document_view = Info::new_error("Already on the first page");
+                    ScreenFeedback::Redraw
+                }
+            },
+
+            // Selection marks
+            Event::Key(KeyEvent {
+                code: Key::Char(' '),
+                modifiers: KeyModifiers::NONE,
+            }) => {
+                let index = self.selected_index();
+                if !self.marked.remove(&index) && index < self.document_ids.len() {
+                    self.marked.insert(index);
+                }
+                self.update_rows();
+                ScreenFeedback::Redraw
+            }
+            Event::Key(KeyEvent {
+                code: Key::Char('a'),
+                modifiers: KeyModifiers::NONE,
+            }) => {
+                if self.marked.len() == self.document_ids.len() {
+                    self.marked.clear();
+                } else {
+                    self.marked = (0..self.document_ids.len()).collect();
+                }
+                self.update_rows();
+                ScreenFeedback::Redraw
+            }
+
+            // Actions on marked documents
+            Event::Key(KeyEvent {
+                code: Key::Char('e' | 'r' | 'd'),
+                modifiers: KeyModifiers::NONE,
+            }) if self.marked.is_empty() => {
+                self.document_view = Info::new_error("No documents marked");
+                ScreenFeedback::Redraw
+            }
+            Event::Key(KeyEvent {
+                code: Key::Char('e'),
+                modifiers: KeyModifiers::NONE,
+            }) => ScreenFeedback::Form(Box::new(ExportDocumentsFormController::new(
+                self.marked_documents(),
+            ))),
+            Event::Key(KeyEvent {
+                code: Key::Char('r'),
+                modifiers: KeyModifiers::NONE,
+            }) => ScreenFeedback::Task {
+                task: Task::Document(DocumentTask::RefetchDocuments(
+                    self.document_query.clone(),
+                    self.marked_ids(),
+                )),
+                block: true,
+            },
+            Event::Key(KeyEvent {
+                code: Key::Char('d'),
+                modifiers: KeyModifiers::NONE,
+            }) => ScreenFeedback::Form(Box::new(DeleteDocumentsFormController::new(
+                "Delete marked documents",
+                DocumentTask::DeleteDocuments(self.document_query.clone(), self.marked_documents()),
+            ))),
+
+            Event::Key(KeyEvent {
+                code: Key::Char('b'),
+                modifiers: KeyModifiers::NONE,
+            }) => match self.selected_document() {
+                Some(document) => ScreenFeedback::Form(Box::new(
+                    BookmarkDocumentFormController::new(self.document_query.clone(), document),
+                )),
+                None => {
+                    self.document_view = Info::new_error("No document to bookmark");
+                    ScreenFeedback::Redraw
+                }
+            },
+
+            // Copies of the selected document, with its byte arrays in full
+            Event::Key(KeyEvent {
+                code: Key::Char('y'),
+                modifiers: KeyModifiers::NONE,
+            }) => match self.document_ids.get(self.selected_index()) {
+                Some(id) => ScreenFeedback::Copy(id.to_string(Encoding::Base58)),
+                None => ScreenFeedback::None,
+            },
+            Event::Key(KeyEvent {
+                code: Key::Char('y'),
+                modifiers: KeyModifiers::CONTROL,
+            }) => match self.selected_document() {
+                Some(document) => ScreenFeedback::Copy(as_json_string(&document)),
+                None => {
+                    self.document_view = Info::new_error("No document selected");
+                    ScreenFeedback::Redraw
+                }
+            },
+            Event::Key(KeyEvent {
+                code: Key::Char('e'),
+                modifiers: KeyModifiers::CONTROL,
+            }) => match self.selected_document() {
+                Some(document) => ScreenFeedback::Form(Box::new(ExportEntityFormController::new(
+                    ExportedEntity::Document {
+                        document,
+                        data_contract: self.document_query.data_contract.clone(),
+                        document_type_name: self.document_query.document_type_name.clone(),
+                    },
+                ))),
+                None => {
+                    self.document_view = Info::new_error("No document selected");
+                    ScreenFeedback::Redraw
+                }
+            },
+
+            // Actions on the selected document
+            Event::Key(KeyEvent {
+                code: Key::Char('u' | 'x' | 'j' | 's'),
+                modifiers: KeyModifiers::NONE,
+            }) if self.selected_document().is_none() => {
+                self.document_view = Info::new_error("No document selected");
+                ScreenFeedback::Redraw
+            }
+            Event::Key(KeyEvent {
+                code: Key::Char('u'),
+                modifiers: KeyModifiers::NONE,
+            }) => {
+                let document = self.selected_document().expect("checked above");
+                match DocumentFormController::new(
+                    &self.document_query.data_contract,
+                    &self.document_query.document_type_name,
+                    DocumentFormAction::Replace {
+                        document_query: self.document_query.clone(),
+                        document,
+                    },
+                ) {
+                    Ok(form) => ScreenFeedback::Form(Box::new(form)),
+                    Err(e) => {
+                        self.document_view = Info::new_error(&e);
+                        ScreenFeedback::Redraw
+                    }
+                }
+            }
+            Event::Key(KeyEvent {
+                code: Key::Char('x'),
+                modifiers: KeyModifiers::NONE,
+            }) => ScreenFeedback::Form(Box::new(DeleteDocumentsFormController::new(
+                "Delete document",
+                DocumentTask::DeleteDocument(
+                    self.document_query.clone(),
+                    self.selected_document().expect("checked above"),
+                ),
+            ))),
+            Event::Key(KeyEvent {
+                code: Key::Char('j'),
+                modifiers: KeyModifiers::NONE,
+            }) => ScreenFeedback::Task {
+                task: Task::Document(DocumentTask::JoinDocument(
+                    self.document_query.clone(),
+                    self.selected_document().expect("checked above"),
+                )),
+                block: true,
+            },
+            Event::Key(KeyEvent {
+                code: Key::Char('s'),
+                modifiers: KeyModifiers::NONE,
+            }) => {
+                let document = self.selected_document().expect("checked above");
+                let fields = large_binary_fields(&document);
+                if fields.is_empty() {
+                    self.document_view = Info::new_error("No large binary fields in the document");
+                    ScreenFeedback::Redraw
+                } else {
+                    ScreenFeedback::Form(Box::new(SaveBinaryFieldFormController::new(
+                        document, fields,
+                    )))
+                }
+            }
+
+            Event::Backend(
+                BackendEvent::TaskCompleted {
+                    task: Task::Bookmark(_),
+                    execution_result,
+                }
+                | BackendEvent::TaskCompletedStateChange {
+                    task: Task::Bookmark(_),
+                    execution_result,
+                    ..
+                },
+            ) => {
+                self.document_view = Info::new_from_result(execution_result);
+                ScreenFeedback::Redraw
+            }
+
+            Event::Backend(BackendEvent::TaskCompleted {
+                task: Task::Document(DocumentTask::QueryDocuments(document_query)),
+                execution_result,
+            }) if self.page_request.is_some() => {
+                let request = self.page_request.take();
+                match execution_result {
+                    Ok(CompletedTaskPayload::Documents(documents)) => {
+                        match request {
+                            Some(PageRequest::Next) => {
+                                self.previous_pages.push(self.document_query.start.clone())
+                            }
+                            _ => {
+                                self.previous_pages.pop();
+                            }
+                        }
+                        self.show_page(document_query.clone(), documents);
+                        self.page_proof_feedback()
+                    }
+                    result => {
+                        self.document_view = Info::new_from_result(result);
+                        ScreenFeedback::Redraw
+                    }
+                }
+            }
+
+            // A refreshed page keeps the position among pages
+            Event::Backend(BackendEvent::TaskCompleted {
+                task: Task::Document(DocumentTask::RefreshDocuments(document_query)),
+                execution_result,
+            }) => match execution_result {
+                Ok(CompletedTaskPayload::Documents(documents)) => {
+                    self.show_page(document_query.clone(), documents);
+                    self.page_proof_feedback()
+                }
+                result => {
+                    self.document_view = Info::new_from_result(result);
+                    ScreenFeedback::Redraw
+                }
+            },
+
+            // Proofs of other queries, like the previous page's, are left out
+            Event::Backend(BackendEvent::TaskCompleted {
+                task: Task::FetchWithProof(ProvedFetch::Documents(document_query)),
+                execution_result,
+            }) if query_key(document_query) == query_key(&self.document_query) => {
+                match execution_result {
+                    Ok(CompletedTaskPayload::Proved { proof, .. }) => {
+                        self.proof =
+                            Some(ProofPanel::new(proof.clone(), self.toggle_keys[0].toggle))
+                    }
+                    result => self.document_view = Info::new_from_result(result),
+                }
+                ScreenFeedback::Redraw
+            }
+
+            // Re-fetched documents replace the stale ones
+            Event::Backend(BackendEvent::TaskCompleted {
+                task: Task::Document(DocumentTask::RefetchDocuments(..)),
+                execution_result: Ok(CompletedTaskPayload::Documents(documents)),
+            }) => {
+                for (id, document) in documents {
+                    if let Some(index) = self.document_ids.iter().position(|i| i == id) {
+                        self.current_batch[index] = document.clone();
+                    }
+                }
+                self.update_document_view();
+                ScreenFeedback::Redraw
+            }
+            // Replaced and deleted documents are updated in place
+            Event::Backend(BackendEvent::TaskCompleted {
+                task: Task::Document(DocumentTask::ReplaceDocument { document, .. }),
+                execution_result: Ok(CompletedTaskPayload::Document(replacement)),
+            }) => {
+                if let Some(index) = self.document_ids.iter().position(|id| *id == document.id()) {
+                    self.current_batch[index] = Some(replacement.clone());
+                }
+                self.update_document_view();
+                ScreenFeedback::Redraw
+            }
+            Event::Backend(BackendEvent::TaskCompleted {
+                task: Task::Document(DocumentTask::DeleteDocument(_, document)),
+                execution_result: execution_result @ Ok(_),
+            }) => {
+                if let Some(index) = self.document_ids.iter().position(|id| *id == document.id()) {
+                    self.current_batch[index] = None;
+                }
+                self.document_view = Info::new_from_result(execution_result);
+                ScreenFeedback::Redraw
+            }
+            Event::Backend(BackendEvent::TaskCompleted {
+                task:
+                    Task::Document(
+                        DocumentTask::RefetchDocuments(..)
+                        | DocumentTask::ExportDocuments(..)
+                        | DocumentTask::DeleteDocuments(..)
+                        | DocumentTask::ReplaceDocument { .. }
+                        | DocumentTask::DeleteDocument(..)
+                        | DocumentTask::JoinDocument(..)
+                        | DocumentTask::SaveBinaryField { .. },
+                    )
+                    | Task::Export(_),
+                execution_result,
+            }) => {
+                self.document_view = Info::new_from_result(execution_result);
+                ScreenFeedback::Redraw
+            }
+
             _ => ScreenFeedback::None,
         }
     }
+
+    /// Next page of the query, known only for a full batch of a query with a
+    /// limit.
+    fn prefetch_task(&self) -> Option<Task> {
+        if self.document_query.limit == 0 {
+            return None;
+        }
+        let next_page = self.next_page_query().ok()?;
+        Some(Task::Document(DocumentTask::PrefetchDocuments(next_page)))
+    }
+
+    /// The current page, results the query gave before are dropped.
+    fn refresh_task(&self) -> Option<Task> {
+        Some(Task::Document(DocumentTask::RefreshDocuments(
+            self.document_query.clone(),
+        )))
+    }
+
+    fn take_viewed_entity(&mut self) -> Option<RecentEntity> {
+        let document = self.selected_document()?;
+        if self.listed_document == Some(document.id()) {
+            return None;
+        }
+        self.listed_document = Some(document.id());
+        Some(RecentEntity::Document {
+            query: self.document_query.clone(),
+            document,
+        })
+    }
+}
+
+struct ExportDocumentsFormController {
+    documents: Vec<Document>,
+    input: SelectInput<ExportFormat>,
+}
+
+impl ExportDocumentsFormController {
+    fn new(documents: Vec<Document>) -> Self {
+        ExportDocumentsFormController {
+            documents,
+            input: SelectInput::new(vec![ExportFormat::Json, ExportFormat::Csv]),
+        }
+    }
+}
+
+impl FormController for ExportDocumentsFormController {
+    fn on_event(&mut self, event: KeyEvent) -> FormStatus {
+        match self.input.on_event(event) {
+            InputStatus::Done(format) => FormStatus::Done {
+                task: Task::Document(DocumentTask::ExportDocuments(
+                    std::mem::take(&mut self.documents),
+                    format,
+                )),
+                block: false,
+            },
+            status => status.into(),
+        }
+    }
+
+    fn form_name(&self) -> &'static str {
+        "Export marked documents"
+    }
+
+    fn step_view(&mut self, frame: &mut Frame, area: Rect) {
+        self.input.view(frame, area)
+    }
+
+    fn step_name(&self) -> Cow<'_, str> {
+        "Format".into()
+    }
+
+    fn step_index(&self) -> u8 {
+        0
+    }
+
+    fn steps_number(&self) -> u8 {
+        1
+    }
+}
+
+struct SaveBinaryFieldFormController {
+    document: Document,
+    input: ComposedInput<(
+        Field<SelectInput<String>>,
+        Field<TextInput<DefaultTextInputParser<String>>>,
+    )>,
+}
+
+impl SaveBinaryFieldFormController {
+    fn new(document: Document, fields: Vec<String>) -> Self {
+        SaveBinaryFieldFormController {
+            document,
+            input: ComposedInput::new((
+                Field::new("Binary field", SelectInput::new(fields)),
+                Field::new(
+                    "File path",
+                    TextInput::new("Path to save bytes to (Tab to complete)")
+                        .with_path_completion(),
+                ),
+            )),
+        }
+    }
+}
+
+impl FormController for SaveBinaryFieldFormController {
+    fn on_event(&mut self, event: KeyEvent) -> FormStatus {
+        match self.input.on_event(event) {
+            InputStatus::Done((field, path)) => FormStatus::Done {
+                task: Task::Document(DocumentTask::SaveBinaryField {
+                    document: self.document.clone(),
+                    field,
+                    path,
+                }),
+                block: false,
+            },
+            status => status.into(),
+        }
+    }
+
+    fn form_name(&self) -> &'static str {
+        "Save binary field"
+    }
+
+    fn step_view(&mut self, frame: &mut Frame, area: Rect) {
+        self.input.view(frame, area)
+    }
+
+    fn step_name(&self) -> Cow<'_, str> {
+        self.input.step_name().into()
+    }
+
+    fn step_index(&self) -> u8 {
+        self.input.step_index()
+    }
+
+    fn steps_number(&self) -> u8 {
+        2
+    }
+}
+
+/// Confirmation of a deletion task.
+struct DeleteDocumentsFormController {
+    form_name: &'static str,
+    task: Option<DocumentTask>,
+    input: SelectInput<String>,
+}
+
+impl DeleteDocumentsFormController {
+    fn new(form_name: &'static str, task: DocumentTask) -> Self {
+        DeleteDocumentsFormController {
+            form_name,
+            task: Some(task),
+            input: SelectInput::new(vec!["No".to_owned(), "Yes".to_owned()]),
+        }
+    }
+}
+
+impl FormController for DeleteDocumentsFormController {
+    fn on_event(&mut self, event: KeyEvent) -> FormStatus {
+        match self.input.on_event(event) {
+            InputStatus::Done(confirmation) if confirmation == "Yes" => match self.task.take() {
+                Some(task) => FormStatus::Done {
+                    task: Task::Document(task),
+                    block: true,
+                },
+                None => FormStatus::Exit,
+            },
+            InputStatus::Done(_) => FormStatus::Exit,
+            status => status.into(),
+        }
+    }
+
+    fn form_name(&self) -> &'static str {
+        self.form_name
+    }
+
+    fn step_view(&mut self, frame: &mut Frame, area: Rect) {
+        self.input.view(frame, area)
+    }
+
+    fn step_name(&self) -> Cow<'_, str> {
+        "Confirm deletion".into()
+    }
+
+    fn step_index(&self) -> u8 {
+        0
+    }
+
+    fn steps_number(&self) -> u8 {
+        1
+    }
 }
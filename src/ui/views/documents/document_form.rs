@@ -0,0 +1,133 @@
+//! Form to fill a document of the document type schema, one step per property.
+
+use std::borrow::Cow;
+
+use dash_sdk::platform::DocumentQuery;
+use dpp::{
+    data_contract::accessors::v0::DataContractV0Getters,
+    document::{Document, DocumentV0Getters},
+    platform_value::Value,
+    prelude::DataContract,
+};
+use tuirealm::{event::KeyEvent, tui::prelude::Rect, Frame};
+
+use crate::{
+    backend::{documents::DocumentTask, Task},
+    ui::form::{FormController, FormStatus, Input, InputStatus, SchemaForm},
+};
+
+/// What's done with the filled document.
+pub(crate) enum DocumentFormAction {
+    /// Check the document is valid only
+    Compose {
+        data_contract_name: String,
+        document_type_name: String,
+    },
+    /// Broadcast a new document
+    Create {
+        data_contract_name: String,
+        document_type_name: String,
+    },
+    /// Replace properties of the fetched document
+    Replace {
+        document_query: DocumentQuery,
+        document: Document,
+    },
+}
+
+pub(crate) struct DocumentFormController {
+    input: SchemaForm,
+    action: DocumentFormAction,
+}
+
+impl DocumentFormController {
+    pub(crate) fn new(
+        data_contract: &DataContract,
+        document_type_name: &str,
+        action: DocumentFormAction,
+    ) -> Result<Self, String> {
+        let schema = data_contract
+            .document_schemas()
+            .get(document_type_name)
+            .and_then(|schema| serde_json::to_value(schema).ok())
+            .ok_or_else(|| "Document type schema is unavailable".to_owned())?;
+        let current = match &action {
+            DocumentFormAction::Replace { document, .. } => Some(Value::Map(
+                document
+                    .properties()
+                    .iter()
+                    .map(|(name, value)| (Value::Text(name.clone()), value.clone()))
+                    .collect(),
+            )),
+            _ => None,
+        };
+        Ok(DocumentFormController {
+            input: SchemaForm::new_with_current(&schema, current)?,
+            action,
+        })
+    }
+}
+
+impl FormController for DocumentFormController {
+    fn on_event(&mut self, event: KeyEvent) -> FormStatus {
+        match self.input.on_event(event) {
+            InputStatus::Done(properties) => {
+                let task = match &self.action {
+                    DocumentFormAction::Compose {
+                        data_contract_name,
+                        document_type_name,
+                    } => DocumentTask::ComposeDocument {
+                        data_contract_name: data_contract_name.clone(),
+                        document_type_name: document_type_name.clone(),
+                        properties,
+                    },
+                    DocumentFormAction::Create {
+                        data_contract_name,
+                        document_type_name,
+                    } => DocumentTask::BroadcastDocument {
+                        data_contract_name: data_contract_name.clone(),
+                        document_type_name: document_type_name.clone(),
+                        properties,
+                    },
+                    DocumentFormAction::Replace {
+                        document_query,
+                        document,
+                    } => DocumentTask::ReplaceDocument {
+                        document_query: document_query.clone(),
+                        document: document.clone(),
+                        properties,
+                    },
+                };
+                FormStatus::Done {
+                    task: Task::Document(task),
+                    block: true,
+                }
+            }
+            status => status.into(),
+        }
+    }
+
+    fn form_name(&self) -> &'static str {
+        match self.action {
+            DocumentFormAction::Compose { .. } => "Compose document",
+            DocumentFormAction::Create { .. } => "Create document",
+            DocumentFormAction::Replace { .. } => "Replace document",
+        }
+    }
+
+    fn step_view(&mut self, frame: &mut Frame, area: Rect) {
+        self.input.view(frame, area)
+    }
+
+    fn step_name(&self) -> Cow<'_, str> {
+        self.input.step_name().into()
+    }
+
+    fn step_index(&self) -> u8 {
+        self.input.step_index()
+    }
+
+    fn steps_number(&self) -> u8 {
+        self.input.steps_number()
+    }
+}
@@ -0,0 +1,86 @@
+//! Form to export the entity shown on a screen into a file.
+
+use std::borrow::Cow;
+
+use tuirealm::{event::KeyEvent, tui::prelude::Rect, Frame};
+
+use crate::{
+    backend::{
+        export::{ExportTask, ExportedEntity},
+        Task,
+    },
+    ui::form::{
+        parsers::DefaultTextInputParser, ComposedInput, Field, FormController, FormStatus, Input,
+        InputStatus, SelectInput, TextInput,
+    },
+};
+
+pub(crate) struct ExportEntityFormController {
+    entity: Option<ExportedEntity>,
+    input: ComposedInput<(
+        Field<TextInput<DefaultTextInputParser<String>>>,
+        Field<SelectInput<String>>,
+    )>,
+}
+
+impl ExportEntityFormController {
+    pub(crate) fn new(entity: ExportedEntity) -> Self {
+        let default_path = entity.default_path();
+        ExportEntityFormController {
+            entity: Some(entity),
+            input: ComposedInput::new((
+                Field::new(
+                    "File path",
+                    TextInput::new_init_value(
+                        "Path of the JSON file (Tab to complete)",
+                        default_path,
+                    )
+                    .with_path_completion(),
+                ),
+                Field::new(
+                    "Also write raw serialized bytes",
+                    SelectInput::new(vec!["No".to_owned(), "Yes".to_owned()]),
+                ),
+            )),
+        }
+    }
+}
+
+impl FormController for ExportEntityFormController {
+    fn on_event(&mut self, event: KeyEvent) -> FormStatus {
+        match self.input.on_event(event) {
+            InputStatus::Done((path, raw)) => match self.entity.take() {
+                Some(entity) => FormStatus::Done {
+                    task: Task::Export(ExportTask {
+                        entity,
+                        path,
+                        raw: raw == "Yes",
+                    }),
+                    block: false,
+                },
+                None => FormStatus::Exit,
+            },
+            status => status.into(),
+        }
+    }
+
+    fn form_name(&self) -> &'static str {
+        "Export to file"
+    }
+
+    fn step_view(&mut self, frame: &mut Frame, area: Rect) {
+        self.input.view(frame, area)
+    }
+
+    fn step_name(&self) -> Cow<'_, str> {
+        self.input.step_name().into()
+    }
+
+    fn step_index(&self) -> u8 {
+        self.input.step_index()
+    }
+
+    fn steps_number(&self) -> u8 {
+        2
+    }
+}
@@ -1,45 +1,90 @@
 //! UI definitions related to identities.
 
+mod keep_funded;
+
+use std::borrow::Cow;
+
 use tuirealm::{
     event::{Key, KeyEvent, KeyModifiers},
-    tui::prelude::Rect,
+    props::{Color, Style},
+    tui::{
+        prelude::{Constraint, Direction, Layout, Rect},
+        widgets::Paragraph,
+    },
     Frame,
 };
 
+use self::keep_funded::KeepFundedScreenController;
 use crate::{
-    backend::{identities::IdentityTask, BackendEvent, Task},
+    backend::{
+        export::ExportedEntity,
+        identities::{dpns_check::validate_label, IdentityTask},
+        proofs::ProvedFetch,
+        BackendEvent, CompletedTaskPayload, Task,
+    },
     ui::{
         form::{
             parsers::DefaultTextInputParser, ComposedInput, Field, FormController, FormStatus,
             Input, InputStatus, TextInput,
         },
+        i18n::tr_message,
+        proofs::{self, ProofPanel},
+        recent::RecentEntity,
         screen::{
             utils::impl_builder_no_args, widgets::info::Info, ScreenCommandKey, ScreenController,
             ScreenFeedback, ScreenToggleKey,
         },
+        views::export::ExportEntityFormController,
     },
     Event,
 };
 
-const COMMAND_KEYS: [ScreenCommandKey; 4] = [
+const COMMAND_KEYS: [ScreenCommandKey; 17] = [
     ScreenCommandKey::new("q", "Back to Main"),
     ScreenCommandKey::new("i", "Get Identity by ID"),
     ScreenCommandKey::new("t", "Transfer credits"),
     ScreenCommandKey::new("r", "Register DPNS name"),
+    ScreenCommandKey::new("k", "Keep funded"),
+    ScreenCommandKey::new("v", "Verify key status"),
+    ScreenCommandKey::new("e", "Export identity bundle"),
+    ScreenCommandKey::new("l", "Load identity bundle"),
+    ScreenCommandKey::new("a", "Signing audit log"),
+    ScreenCommandKey::new("x", "Export signing audit log"),
+    ScreenCommandKey::new("n", "Set identity petname"),
+    ScreenCommandKey::new("m", "Identity petnames"),
+    ScreenCommandKey::new("s", "Sponsor identity registration"),
+    ScreenCommandKey::new("b", "Top up known identities"),
+    ScreenCommandKey::new("y", "Copy identity ID"),
+    ScreenCommandKey::new("C-y", "Copy info"),
+    ScreenCommandKey::new("C-e", "Export to file"),
 ];
 
 pub(crate) struct IdentitiesScreenController {
-    toggle_keys: [ScreenToggleKey; 1],
+    toggle_keys: [ScreenToggleKey; 2],
     info: Info,
+    /// ID of the identity fetched last
+    identity_id: Option<String>,
+    /// ID of the identity listed among recent entities last
+    listed_identity: Option<String>,
+    /// Proof of the identity shown, if fetched with proof details
+    proof: Option<ProofPanel>,
 }
 
 impl_builder_no_args!(IdentitiesScreenController);
 
 impl IdentitiesScreenController {
     pub(crate) fn new() -> Self {
+        let mut toggle_keys = [
+            ScreenToggleKey::new("p", "with proof"),
+            ScreenToggleKey::new("o", "expand proof"),
+        ];
+        toggle_keys[0].toggle = proofs::proof_details();
         IdentitiesScreenController {
-            toggle_keys: [ScreenToggleKey::new("p", "with proof")],
+            toggle_keys,
             info: Info::new_fixed("Identity management commands"),
+            identity_id: None,
+            listed_identity: None,
+            proof: None,
         }
     }
 }
@@ -67,7 +112,9 @@ impl ScreenController for IdentitiesScreenController {
             Event::Key(KeyEvent {
                 code: Key::Char('i'),
                 modifiers: KeyModifiers::NONE,
-            }) => ScreenFeedback::Form(Box::new(GetIdentityByIdFormController::new())),
+            }) => ScreenFeedback::Form(Box::new(GetIdentityByIdFormController::new(
+                self.toggle_keys[0].toggle,
+            ))),
 
             Event::Key(KeyEvent {
                 code: Key::Char('t'),
@@ -82,11 +129,104 @@ impl ScreenController for IdentitiesScreenController {
                 ScreenFeedback::Redraw
             }
 
+            Event::Key(KeyEvent {
+                code: Key::Char('o'),
+                modifiers: KeyModifiers::NONE,
+            }) => {
+                self.toggle_keys[1].toggle = !self.toggle_keys[1].toggle;
+                if let Some(proof) = &mut self.proof {
+                    proof.set_expanded(self.toggle_keys[1].toggle);
+                }
+                ScreenFeedback::Redraw
+            }
+
             Event::Key(KeyEvent {
                 code: Key::Char('r'),
                 modifiers: KeyModifiers::NONE,
             }) => ScreenFeedback::Form(Box::new(RegisterDPNSNameFormController::new())),
 
+            Event::Key(KeyEvent {
+                code: Key::Char('k'),
+                modifiers: KeyModifiers::NONE,
+            }) => ScreenFeedback::NextScreen(KeepFundedScreenController::builder()),
+
+            Event::Key(KeyEvent {
+                code: Key::Char('v'),
+                modifiers: KeyModifiers::NONE,
+            }) => ScreenFeedback::Form(Box::new(CheckKeyStatusFormController::new())),
+
+            Event::Key(KeyEvent {
+                code: Key::Char('e'),
+                modifiers: KeyModifiers::NONE,
+            }) => ScreenFeedback::Form(Box::new(IdentityBundleFormController::new(true))),
+
+            Event::Key(KeyEvent {
+                code: Key::Char('l'),
+                modifiers: KeyModifiers::NONE,
+            }) => ScreenFeedback::Form(Box::new(IdentityBundleFormController::new(false))),
+
+            Event::Key(KeyEvent {
+                code: Key::Char('a'),
+                modifiers: KeyModifiers::NONE,
+            }) => ScreenFeedback::Task {
+                task: Task::Identity(IdentityTask::ShowSigningAudit),
+                block: false,
+            },
+
+            Event::Key(KeyEvent {
+                code: Key::Char('x'),
+                modifiers: KeyModifiers::NONE,
+            }) => ScreenFeedback::Task {
+                task: Task::Identity(IdentityTask::ExportSigningAudit),
+                block: false,
+            },
+
+            Event::Key(KeyEvent {
+                code: Key::Char('n'),
+                modifiers: KeyModifiers::NONE,
+            }) => ScreenFeedback::Form(Box::new(SetPetnameFormController::new())),
+
+            Event::Key(KeyEvent {
+                code: Key::Char('m'),
+                modifiers: KeyModifiers::NONE,
+            }) => ScreenFeedback::Task {
+                task: Task::Identity(IdentityTask::ShowPetnames),
+                block: false,
+            },
+
+            Event::Key(KeyEvent {
+                code: Key::Char('s'),
+                modifiers: KeyModifiers::NONE,
+            }) => ScreenFeedback::Form(Box::new(SponsorIdentityFormController::new())),
+
+            Event::Key(KeyEvent {
+                code: Key::Char('b'),
+                modifiers: KeyModifiers::NONE,
+            }) => ScreenFeedback::Form(Box::new(TopUpKnownIdentitiesFormController::new())),
+
+            Event::Key(KeyEvent {
+                code: Key::Char('y'),
+                modifiers: KeyModifiers::NONE,
+            }) => match &self.identity_id {
+                Some(identity_id) => ScreenFeedback::Copy(identity_id.clone()),
+                None => ScreenFeedback::None,
+            },
+
+            Event::Key(KeyEvent {
+                code: Key::Char('y'),
+                modifiers: KeyModifiers::CONTROL,
+            }) => ScreenFeedback::Copy(self.info.text().to_owned()),
+
+            Event::Key(KeyEvent {
+                code: Key::Char('e'),
+                modifiers: KeyModifiers::CONTROL,
+            }) => match &self.identity_id {
+                Some(identity_id) => ScreenFeedback::Form(Box::new(
+                    ExportEntityFormController::new(ExportedEntity::Identity(identity_id.clone())),
+                )),
+                None => ScreenFeedback::None,
+            },
+
             Event::Key(k) => {
                 let redraw_info = self.info.on_event(k);
                 if redraw_info {
@@ -97,10 +237,27 @@ impl ScreenController for IdentitiesScreenController {
             }
 
             Event::Backend(BackendEvent::TaskCompleted {
-                task: Task::FetchIdentityById(..),
+                task: Task::FetchIdentityById(identity_id, _),
                 execution_result,
             }) => {
+                self.identity_id = execution_result.is_ok().then(|| identity_id.clone());
                 self.info = Info::new_from_result(execution_result);
+                self.proof = None;
+                ScreenFeedback::Redraw
+            }
+
+            Event::Backend(BackendEvent::TaskCompleted {
+                task: Task::FetchWithProof(ProvedFetch::Identity(identity_id)),
+                execution_result,
+            }) => {
+                self.identity_id = execution_result.is_ok().then(|| identity_id.clone());
+                self.info = Info::new_from_result(execution_result);
+                self.proof = match execution_result {
+                    Ok(CompletedTaskPayload::Proved { proof, .. }) => {
+                        Some(ProofPanel::new(proof.clone(), self.toggle_keys[1].toggle))
+                    }
+                    _ => None,
+                };
                 ScreenFeedback::Redraw
             }
 
@@ -109,6 +266,7 @@ impl ScreenController for IdentitiesScreenController {
                 execution_result,
             }) => {
                 self.info = Info::new_from_result(execution_result);
+                self.proof = None;
                 ScreenFeedback::Redraw
             }
 
@@ -118,6 +276,17 @@ impl ScreenController for IdentitiesScreenController {
                 app_state_update: _,
             }) => {
                 self.info = Info::new_from_result(execution_result);
+                self.proof = None;
+                ScreenFeedback::Redraw
+            }
+
+            Event::Backend(BackendEvent::TaskCompletedStateChange {
+                task: Task::Identity(IdentityTask::ImportBundle { .. }),
+                execution_result,
+                app_state_update: _,
+            }) => {
+                self.info = Info::new_from_result(execution_result);
+                self.proof = None;
                 ScreenFeedback::Redraw
             }
 
@@ -127,14 +296,26 @@ impl ScreenController for IdentitiesScreenController {
                 app_state_update: _,
             }) => {
                 self.info = Info::new_from_result(execution_result);
+                self.proof = None;
                 ScreenFeedback::Redraw
             }
 
+            // Petnames list shows full IDs
             Event::Backend(BackendEvent::TaskCompleted {
-                task: Task::Identity(_),
+                task: Task::Identity(IdentityTask::ShowPetnames),
+                execution_result: Ok(petnames),
+            }) => {
+                self.info = Info::new_verbatim(&petnames.to_string());
+                self.proof = None;
+                ScreenFeedback::Redraw
+            }
+
+            Event::Backend(BackendEvent::TaskCompleted {
+                task: Task::Identity(_) | Task::Export(_),
                 execution_result,
             }) => {
                 self.info = Info::new_from_result(execution_result);
+                self.proof = None;
                 ScreenFeedback::Redraw
             }
 
@@ -143,18 +324,32 @@ impl ScreenController for IdentitiesScreenController {
     }
 
     fn view(&mut self, frame: &mut Frame, area: Rect) {
+        let area = match &self.proof {
+            Some(proof) => proof.view(frame, area),
+            None => area,
+        };
         self.info.view(frame, area)
     }
+
+    fn take_viewed_entity(&mut self) -> Option<RecentEntity> {
+        if self.listed_identity == self.identity_id {
+            return None;
+        }
+        self.listed_identity = self.identity_id.clone();
+        self.identity_id.clone().map(RecentEntity::Identity)
+    }
 }
 
 pub(crate) struct GetIdentityByIdFormController {
     input: TextInput<DefaultTextInputParser<String>>, // TODO: b58 parser
+    with_proof: bool,
 }
 
 impl GetIdentityByIdFormController {
-    fn new() -> Self {
+    fn new(with_proof: bool) -> Self {
         GetIdentityByIdFormController {
             input: TextInput::new("base58 id"),
+            with_proof,
         }
     }
 }
@@ -163,7 +358,11 @@ impl FormController for GetIdentityByIdFormController {
     fn on_event(&mut self, event: KeyEvent) -> FormStatus {
         match self.input.on_event(event) {
             InputStatus::Done(value) => FormStatus::Done {
-                task: Task::FetchIdentityById(value, false),
+                task: if self.with_proof {
+                    Task::FetchWithProof(ProvedFetch::Identity(value))
+                } else {
+                    Task::FetchIdentityById(value, false)
+                },
                 block: true,
             },
             status => status.into(),
@@ -178,8 +377,8 @@ impl FormController for GetIdentityByIdFormController {
         "Get identity by ID"
     }
 
-    fn step_name(&self) -> &'static str {
-        "Base 58 ID"
+    fn step_name(&self) -> Cow<'_, str> {
+        "Base 58 ID".into()
     }
 
     fn step_index(&self) -> u8 {
@@ -231,8 +430,197 @@ impl FormController for TransferCreditsFormController {
         "Transfer Credits"
     }
 
-    fn step_name(&self) -> &'static str {
-        self.input.step_name()
+    fn step_name(&self) -> Cow<'_, str> {
+        self.input.step_name().into()
+    }
+
+    fn step_index(&self) -> u8 {
+        self.input.step_index()
+    }
+
+    fn steps_number(&self) -> u8 {
+        2
+    }
+}
+
+/// Registration of an identity of someone else's public keys funded by the
+/// loaded wallet.
+pub(crate) struct SponsorIdentityFormController {
+    input: ComposedInput<(
+        Field<TextInput<DefaultTextInputParser<String>>>,
+        Field<TextInput<DefaultTextInputParser<f64>>>,
+        Field<TextInput<DefaultTextInputParser<String>>>,
+    )>,
+}
+
+impl SponsorIdentityFormController {
+    fn new() -> Self {
+        Self {
+            input: ComposedInput::new((
+                Field::new(
+                    "Paste the public keys JSON or enter the path of a JSON file with them",
+                    TextInput::new("Public keys"),
+                ),
+                Field::new(
+                    "Enter the amount to fund the identity with in Dash (Ex: .5)",
+                    TextInput::new("Quantity (in Dash)"),
+                ),
+                Field::new(
+                    "Enter the file path to write the transition to",
+                    TextInput::new("File path"),
+                ),
+            )),
+        }
+    }
+}
+
+impl FormController for SponsorIdentityFormController {
+    fn on_event(&mut self, event: KeyEvent) -> FormStatus {
+        match self.input.on_event(event) {
+            InputStatus::Done((keys, amount, path)) => FormStatus::Done {
+                task: Task::Identity(IdentityTask::SponsorIdentity {
+                    keys,
+                    amount: (amount * 100000000.0) as u64,
+                    path,
+                }),
+                block: true,
+            },
+            status => status.into(),
+        }
+    }
+
+    fn step_view(&mut self, frame: &mut Frame, area: tuirealm::tui::prelude::Rect) {
+        self.input.view(frame, area);
+    }
+
+    fn form_name(&self) -> &'static str {
+        "Sponsor identity registration"
+    }
+
+    fn step_name(&self) -> Cow<'_, str> {
+        self.input.step_name().into()
+    }
+
+    fn step_index(&self) -> u8 {
+        self.input.step_index()
+    }
+
+    fn steps_number(&self) -> u8 {
+        3
+    }
+}
+
+/// Top up of many known identities to the same balance from the loaded
+/// wallet.
+pub(crate) struct TopUpKnownIdentitiesFormController {
+    input: ComposedInput<(
+        Field<TextInput<DefaultTextInputParser<f64>>>,
+        Field<TextInput<DefaultTextInputParser<String>>>,
+    )>,
+}
+
+impl TopUpKnownIdentitiesFormController {
+    fn new() -> Self {
+        Self {
+            input: ComposedInput::new((
+                Field::new(
+                    "Enter the target balance of each identity in Dash (Ex: .5)",
+                    TextInput::new("Quantity (in Dash)"),
+                ),
+                Field::new(
+                    "Enter comma separated base58 IDs, leave empty for all known identities",
+                    TextInput::new("Base58 IDs"),
+                ),
+            )),
+        }
+    }
+}
+
+impl FormController for TopUpKnownIdentitiesFormController {
+    fn on_event(&mut self, event: KeyEvent) -> FormStatus {
+        match self.input.on_event(event) {
+            InputStatus::Done((target, identity_ids)) => FormStatus::Done {
+                task: Task::Identity(IdentityTask::TopUpKnownIdentities {
+                    target_credits: (target * 100000000000.0) as u64,
+                    identity_ids: identity_ids
+                        .split(',')
+                        .map(str::trim)
+                        .filter(|id| !id.is_empty())
+                        .map(ToOwned::to_owned)
+                        .collect(),
+                }),
+                block: true,
+            },
+            status => status.into(),
+        }
+    }
+
+    fn step_view(&mut self, frame: &mut Frame, area: tuirealm::tui::prelude::Rect) {
+        self.input.view(frame, area);
+    }
+
+    fn form_name(&self) -> &'static str {
+        "Top up known identities"
+    }
+
+    fn step_name(&self) -> Cow<'_, str> {
+        self.input.step_name().into()
+    }
+
+    fn step_index(&self) -> u8 {
+        self.input.step_index()
+    }
+
+    fn steps_number(&self) -> u8 {
+        2
+    }
+}
+
+pub(crate) struct SetPetnameFormController {
+    input: ComposedInput<(
+        Field<TextInput<DefaultTextInputParser<String>>>,
+        Field<TextInput<DefaultTextInputParser<String>>>,
+    )>,
+}
+
+impl SetPetnameFormController {
+    fn new() -> Self {
+        Self {
+            input: ComposedInput::new((
+                Field::new("Enter the identity base58 ID", TextInput::new("Base58 ID")),
+                Field::new(
+                    "Enter the petname, leave empty to remove it",
+                    TextInput::new("Petname"),
+                ),
+            )),
+        }
+    }
+}
+
+impl FormController for SetPetnameFormController {
+    fn on_event(&mut self, event: KeyEvent) -> FormStatus {
+        match self.input.on_event(event) {
+            InputStatus::Done((identity_id, petname)) => FormStatus::Done {
+                task: Task::Identity(IdentityTask::SetPetname {
+                    identity_id,
+                    petname,
+                }),
+                block: false,
+            },
+            status => status.into(),
+        }
+    }
+
+    fn step_view(&mut self, frame: &mut Frame, area: tuirealm::tui::prelude::Rect) {
+        self.input.view(frame, area);
+    }
+
+    fn form_name(&self) -> &'static str {
+        "Set identity petname"
+    }
+
+    fn step_name(&self) -> Cow<'_, str> {
+        self.input.step_name().into()
     }
 
     fn step_index(&self) -> u8 {
@@ -246,6 +634,11 @@ impl FormController for TransferCreditsFormController {
 
 pub(crate) struct RegisterDPNSNameFormController {
     input: TextInput<DefaultTextInputParser<String>>,
+    /// Label the status is about
+    label: String,
+    /// Label validity, then availability once checked
+    status: Result<String, String>,
+    check_task: Option<Task>,
 }
 
 impl RegisterDPNSNameFormController {
@@ -254,31 +647,73 @@ impl RegisterDPNSNameFormController {
             input: TextInput::new(
                 "DPNS name (example: enter \"something\" if you want \"something.dash\")",
             ),
+            label: String::new(),
+            status: Ok(String::new()),
+            check_task: None,
         }
     }
+
+    /// Validates the label locally and checks its availability once it's
+    /// valid.
+    fn update_status(&mut self) {
+        let label = self.input.value();
+        if label == self.label {
+            return;
+        }
+        self.status = if label.is_empty() {
+            Ok(String::new())
+        } else {
+            validate_label(&label).map(|_| "Checking availability...".to_owned())
+        };
+        self.check_task = match &self.status {
+            Ok(_) if !label.is_empty() => {
+                Some(Task::Identity(IdentityTask::CheckDPNSName(label.clone())))
+            }
+            _ => None,
+        };
+        self.label = label;
+    }
 }
 
 impl FormController for RegisterDPNSNameFormController {
     fn on_event(&mut self, event: KeyEvent) -> FormStatus {
         match self.input.on_event(event) {
-            InputStatus::Done(value) => FormStatus::Done {
-                task: Task::Identity(IdentityTask::RegisterDPNSName(value)),
-                block: true,
+            InputStatus::Done(value) => match validate_label(&value) {
+                Ok(_) => FormStatus::Done {
+                    task: Task::Identity(IdentityTask::RegisterDPNSName(value)),
+                    block: true,
+                },
+                Err(e) => {
+                    self.input.set_error(e);
+                    FormStatus::Redraw
+                }
             },
-            status => status.into(),
+            status => {
+                self.update_status();
+                status.into()
+            }
         }
     }
 
     fn step_view(&mut self, frame: &mut Frame, area: tuirealm::tui::prelude::Rect) {
-        self.input.view(frame, area);
+        let layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(3), Constraint::Length(3)].as_ref())
+            .split(area);
+        self.input.view(frame, layout[0]);
+        let status = match &self.status {
+            Ok(status) => Paragraph::new(status.as_str()),
+            Err(e) => Paragraph::new(tr_message(e)).style(Style::default().fg(Color::Red)),
+        };
+        frame.render_widget(status, layout[1]);
     }
 
     fn form_name(&self) -> &'static str {
         "Register DPNS Name"
     }
 
-    fn step_name(&self) -> &'static str {
-        "DPNS Name"
+    fn step_name(&self) -> Cow<'_, str> {
+        "DPNS Name".into()
     }
 
     fn step_index(&self) -> u8 {
@@ -288,4 +723,143 @@ impl FormController for RegisterDPNSNameFormController {
     fn steps_number(&self) -> u8 {
         1
     }
+
+    fn take_check_task(&mut self) -> Option<Task> {
+        self.check_task.take()
+    }
+
+    fn on_check_result(
+        &mut self,
+        task: &Task,
+        execution_result: &Result<CompletedTaskPayload, String>,
+    ) -> bool {
+        match task {
+            // Results of labels typed over since are stale
+            Task::Identity(IdentityTask::CheckDPNSName(label)) if *label == self.label => {
+                self.status = execution_result
+                    .as_ref()
+                    .map(|payload| payload.to_string())
+                    .map_err(Clone::clone);
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+pub(crate) struct CheckKeyStatusFormController {
+    input: ComposedInput<(
+        Field<TextInput<DefaultTextInputParser<String>>>,
+        Field<TextInput<DefaultTextInputParser<String>>>,
+    )>,
+}
+
+impl CheckKeyStatusFormController {
+    fn new() -> Self {
+        Self {
+            input: ComposedInput::new((
+                Field::new("Enter the identity base58 ID", TextInput::new("Base58 ID")),
+                Field::new(
+                    "Enter the key ID or the public key data in hex",
+                    TextInput::new("Key ID or hex data"),
+                ),
+            )),
+        }
+    }
+}
+
+impl FormController for CheckKeyStatusFormController {
+    fn on_event(&mut self, event: KeyEvent) -> FormStatus {
+        match self.input.on_event(event) {
+            InputStatus::Done((identity_id, key)) => FormStatus::Done {
+                task: Task::Identity(IdentityTask::CheckKeyStatus { identity_id, key }),
+                block: true,
+            },
+            status => status.into(),
+        }
+    }
+
+    fn step_view(&mut self, frame: &mut Frame, area: tuirealm::tui::prelude::Rect) {
+        self.input.view(frame, area);
+    }
+
+    fn form_name(&self) -> &'static str {
+        "Verify key status"
+    }
+
+    fn step_name(&self) -> Cow<'_, str> {
+        self.input.step_name().into()
+    }
+
+    fn step_index(&self) -> u8 {
+        self.input.step_index()
+    }
+
+    fn steps_number(&self) -> u8 {
+        2
+    }
+}
+
+/// Export or import of an encrypted identity bundle.
+pub(crate) struct IdentityBundleFormController {
+    input: ComposedInput<(
+        Field<TextInput<DefaultTextInputParser<String>>>,
+        Field<TextInput<DefaultTextInputParser<String>>>,
+    )>,
+    export: bool,
+}
+
+impl IdentityBundleFormController {
+    fn new(export: bool) -> Self {
+        Self {
+            input: ComposedInput::new((
+                Field::new("Enter the bundle file path", TextInput::new("File path")),
+                Field::new(
+                    "Enter the bundle passphrase",
+                    TextInput::new("Passphrase").masked(),
+                ),
+            )),
+            export,
+        }
+    }
+}
+
+impl FormController for IdentityBundleFormController {
+    fn on_event(&mut self, event: KeyEvent) -> FormStatus {
+        match self.input.on_event(event) {
+            InputStatus::Done((path, passphrase)) => FormStatus::Done {
+                task: Task::Identity(if self.export {
+                    IdentityTask::ExportBundle { path, passphrase }
+                } else {
+                    IdentityTask::ImportBundle { path, passphrase }
+                }),
+                block: true,
+            },
+            status => status.into(),
+        }
+    }
+
+    fn step_view(&mut self, frame: &mut Frame, area: tuirealm::tui::prelude::Rect) {
+        self.input.view(frame, area);
+    }
+
+    fn form_name(&self) -> &'static str {
+        if self.export {
+            "Export identity bundle"
+        } else {
+            "Load identity bundle"
+        }
+    }
+
+    fn step_name(&self) -> Cow<'_, str> {
+        self.input.step_name().into()
+    }
+
+    fn step_index(&self) -> u8 {
+        self.input.step_index()
+    }
+
+    fn steps_number(&self) -> u8 {
+        2
+    }
 }
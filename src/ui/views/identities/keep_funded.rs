@@ -0,0 +1,275 @@
+//! Keep-funded mode screen and forms.
+
+use std::borrow::Cow;
+
+use tuirealm::{
+    event::{Key, KeyEvent, KeyModifiers},
+    tui::prelude::Rect,
+    Frame,
+};
+
+use crate::{
+    backend::{keep_funded::KeepFundedTask, AppState, AppStateUpdate, BackendEvent, Task},
+    ui::{
+        form::{
+            parsers::DefaultTextInputParser, ComposedInput, Field, FormController, FormStatus,
+            Input, InputStatus, TextInput,
+        },
+        screen::{
+            utils::impl_builder, widgets::info::Info, ScreenCommandKey, ScreenController,
+            ScreenFeedback, ScreenToggleKey,
+        },
+    },
+    Event,
+};
+
+const COMMAND_KEYS: [ScreenCommandKey; 4] = [
+    ScreenCommandKey::new("q", "Back to Identities"),
+    ScreenCommandKey::new("w", "Watch identity"),
+    ScreenCommandKey::new("u", "Unwatch identity"),
+    ScreenCommandKey::new("c", "Set daily cap"),
+];
+
+pub(super) struct KeepFundedScreenController {
+    toggle_keys: [ScreenToggleKey; 1],
+    info: Info,
+}
+
+impl_builder!(KeepFundedScreenController);
+
+impl KeepFundedScreenController {
+    pub(super) async fn new(app_state: &AppState) -> Self {
+        let keep_funded = app_state.keep_funded.lock().await;
+        let mut toggle_keys = [ScreenToggleKey::new("e", "enabled")];
+        toggle_keys[0].toggle = keep_funded.enabled();
+
+        KeepFundedScreenController {
+            toggle_keys,
+            info: Info::new_scrollable(&keep_funded.display()),
+        }
+    }
+}
+
+impl ScreenController for KeepFundedScreenController {
+    fn name(&self) -> &'static str {
+        "Keep funded"
+    }
+
+    fn command_keys(&self) -> &[ScreenCommandKey] {
+        COMMAND_KEYS.as_ref()
+    }
+
+    fn toggle_keys(&self) -> &[ScreenToggleKey] {
+        self.toggle_keys.as_ref()
+    }
+
+    fn on_event(&mut self, event: &Event) -> ScreenFeedback {
+        match event {
+            Event::Key(KeyEvent {
+                code: Key::Char('q'),
+                modifiers: KeyModifiers::NONE,
+            }) => ScreenFeedback::PreviousScreen,
+
+            Event::Key(KeyEvent {
+                code: Key::Char('e'),
+                modifiers: KeyModifiers::NONE,
+            }) => {
+                self.toggle_keys[0].toggle = !self.toggle_keys[0].toggle;
+                ScreenFeedback::Task {
+                    task: Task::KeepFunded(KeepFundedTask::SetEnabled(self.toggle_keys[0].toggle)),
+                    block: false,
+                }
+            }
+
+            Event::Key(KeyEvent {
+                code: Key::Char('w'),
+                modifiers: KeyModifiers::NONE,
+            }) => ScreenFeedback::Form(Box::new(WatchIdentityFormController::new())),
+
+            Event::Key(KeyEvent {
+                code: Key::Char('u'),
+                modifiers: KeyModifiers::NONE,
+            }) => ScreenFeedback::Form(Box::new(UnwatchIdentityFormController::new())),
+
+            Event::Key(KeyEvent {
+                code: Key::Char('c'),
+                modifiers: KeyModifiers::NONE,
+            }) => ScreenFeedback::Form(Box::new(DailyCapFormController::new())),
+
+            Event::Key(k) => {
+                if self.info.on_event(k) {
+                    ScreenFeedback::Redraw
+                } else {
+                    ScreenFeedback::None
+                }
+            }
+
+            Event::Backend(BackendEvent::TaskCompleted {
+                task: Task::KeepFunded(_),
+                execution_result,
+            }) => {
+                self.info = Info::new_from_result(execution_result);
+                ScreenFeedback::Redraw
+            }
+
+            Event::Backend(BackendEvent::AppStateUpdated(AppStateUpdate::KeepFunded(
+                keep_funded,
+            ))) => {
+                self.info = Info::new_scrollable(&keep_funded.display());
+                ScreenFeedback::Redraw
+            }
+
+            _ => ScreenFeedback::None,
+        }
+    }
+
+    fn view(&mut self, frame: &mut Frame, area: Rect) {
+        self.info.view(frame, area)
+    }
+}
+
+struct WatchIdentityFormController {
+    input: ComposedInput<(
+        Field<TextInput<DefaultTextInputParser<String>>>,
+        Field<TextInput<DefaultTextInputParser<f64>>>,
+        Field<TextInput<DefaultTextInputParser<f64>>>,
+    )>,
+}
+
+impl WatchIdentityFormController {
+    fn new() -> Self {
+        WatchIdentityFormController {
+            input: ComposedInput::new((
+                Field::new("Identity to watch", TextInput::new("Base58 ID")),
+                Field::new(
+                    "Top up when the balance is below, in Dash (Ex: .5)",
+                    TextInput::new("Threshold in Dash"),
+                ),
+                Field::new(
+                    "Top up amount in Dash (Ex: .5)",
+                    TextInput::new("Amount in Dash"),
+                ),
+            )),
+        }
+    }
+}
+
+impl FormController for WatchIdentityFormController {
+    fn on_event(&mut self, event: KeyEvent) -> FormStatus {
+        match self.input.on_event(event) {
+            InputStatus::Done((identity_id, threshold, top_up)) => FormStatus::Done {
+                task: Task::KeepFunded(KeepFundedTask::Watch(identity_id, threshold, top_up)),
+                block: false,
+            },
+            status => status.into(),
+        }
+    }
+
+    fn form_name(&self) -> &'static str {
+        "Watch identity"
+    }
+
+    fn step_view(&mut self, frame: &mut Frame, area: Rect) {
+        self.input.view(frame, area)
+    }
+
+    fn step_name(&self) -> Cow<'_, str> {
+        self.input.step_name().into()
+    }
+
+    fn step_index(&self) -> u8 {
+        self.input.step_index()
+    }
+
+    fn steps_number(&self) -> u8 {
+        3
+    }
+}
+
+struct UnwatchIdentityFormController {
+    input: TextInput<DefaultTextInputParser<String>>,
+}
+
+impl UnwatchIdentityFormController {
+    fn new() -> Self {
+        UnwatchIdentityFormController {
+            input: TextInput::new("Base58 ID"),
+        }
+    }
+}
+
+impl FormController for UnwatchIdentityFormController {
+    fn on_event(&mut self, event: KeyEvent) -> FormStatus {
+        match self.input.on_event(event) {
+            InputStatus::Done(identity_id) => FormStatus::Done {
+                task: Task::KeepFunded(KeepFundedTask::Unwatch(identity_id)),
+                block: false,
+            },
+            status => status.into(),
+        }
+    }
+
+    fn form_name(&self) -> &'static str {
+        "Unwatch identity"
+    }
+
+    fn step_view(&mut self, frame: &mut Frame, area: Rect) {
+        self.input.view(frame, area)
+    }
+
+    fn step_name(&self) -> Cow<'_, str> {
+        "Base 58 ID".into()
+    }
+
+    fn step_index(&self) -> u8 {
+        0
+    }
+
+    fn steps_number(&self) -> u8 {
+        1
+    }
+}
+
+struct DailyCapFormController {
+    input: TextInput<DefaultTextInputParser<f64>>,
+}
+
+impl DailyCapFormController {
+    fn new() -> Self {
+        DailyCapFormController {
+            input: TextInput::new("Daily cap in Dash"),
+        }
+    }
+}
+
+impl FormController for DailyCapFormController {
+    fn on_event(&mut self, event: KeyEvent) -> FormStatus {
+        match self.input.on_event(event) {
+            InputStatus::Done(cap) => FormStatus::Done {
+                task: Task::KeepFunded(KeepFundedTask::SetDailyCap(cap)),
+                block: false,
+            },
+            status => status.into(),
+        }
+    }
+
+    fn form_name(&self) -> &'static str {
+        "Set daily cap"
+    }
+
+    fn step_view(&mut self, frame: &mut Frame, area: Rect) {
+        self.input.view(frame, area)
+    }
+
+    fn step_name(&self) -> Cow<'_, str> {
+        "Daily cap".into()
+    }
+
+    fn step_index(&self) -> u8 {
+        0
+    }
+
+    fn steps_number(&self) -> u8 {
+        1
+    }
+}
@@ -1,5 +1,7 @@
 //! Identity keys.
 
+use std::borrow::Cow;
+
 use strategy_tests::{
     frequency::Frequency,
     operations::{Operation, OperationType},
@@ -59,8 +61,8 @@ impl FormController for ViewIdentityKeysScreenController {
         self.input.view(frame, area)
     }
 
-    fn step_name(&self) -> &'static str {
-        self.input.step_name()
+    fn step_name(&self) -> Cow<'_, str> {
+        self.input.step_name().into()
     }
 
     fn step_index(&self) -> u8 {
@@ -6,9 +6,13 @@ use tuirealm::{
     Frame,
 };
 
-use super::{contracts::ContractsScreenController, wallet::WalletScreenController};
+use super::{
+    bookmarks::BookmarksScreenController, contracts::ContractsScreenController,
+    wallet::WalletScreenController,
+};
 use crate::ui::views::strategies::StrategiesScreenController;
 use crate::{
+    backend::{platform_info::PlatformInfoTask, BackendEvent, Task},
     ui::{
         screen::{
             utils::impl_builder_no_args, widgets::info::Info, ScreenCommandKey, ScreenController,
@@ -16,14 +20,20 @@ use crate::{
         },
         views::{
             identities::IdentitiesScreenController,
+            network::NetworkScreenController,
             platform_info::PlatformInfoScreenController,
+            search::SearchScreenController,
+            settings::SettingsScreenController,
+            task_history::TaskHistoryScreenController,
+            tutorial::TutorialScreenController,
+            version_upgrade::VersionUpgradeScreenController,
             //            strategies::StrategiesScreenController,
         },
     },
     Event,
 };
 
-const COMMAND_KEYS: [ScreenCommandKey; 7] = [
+const COMMAND_KEYS: [ScreenCommandKey; 13] = [
     ScreenCommandKey::new("q", "Quit"),
     ScreenCommandKey::new("i", "Identities"),
     ScreenCommandKey::new("c", "Contracts"),
@@ -31,6 +41,12 @@ const COMMAND_KEYS: [ScreenCommandKey; 7] = [
     ScreenCommandKey::new("w", "Wallet"),
     ScreenCommandKey::new("v", "Version Upgrade"),
     ScreenCommandKey::new("p", "Platform information"),
+    ScreenCommandKey::new("n", "Network"),
+    ScreenCommandKey::new("o", "Settings"),
+    ScreenCommandKey::new("t", "Tutorial"),
+    ScreenCommandKey::new("/", "Search"),
+    ScreenCommandKey::new("b", "Bookmarks"),
+    ScreenCommandKey::new("h", "Task history"),
 ];
 
 pub(crate) struct MainScreenController {
@@ -51,6 +67,16 @@ Bold italics are flags that are enabled.
 
 Text inputs with completions support both arrows and Ctrl+n / Ctrl+p keys for selection.
 Use q to go back from completion list or once again to leave input at all.
+
+Ctrl+k opens a command palette to search screens and actions by name and go to them directly.
+
+Ctrl+Tab (or F4) lists identities, contracts, document types and documents viewed last to jump back to one.
+
+The mouse wheel scrolls lists and texts, clicks select list rows. Hold Shift to select text.
+
+F2 splits the screen into two panes to watch related screens at once, F3 switches between them.
+
+New here? Press t for a guided tutorial.
 "#,
             ),
         }
@@ -97,11 +123,50 @@ impl ScreenController for MainScreenController {
             Event::Key(KeyEvent {
                 code: Key::Char('v'),
                 modifiers: KeyModifiers::NONE,
-            }) => ScreenFeedback::None,
+            }) => ScreenFeedback::Task {
+                task: Task::PlatformInfo(PlatformInfoTask::FetchProtocolUpgradeStatus),
+                block: true,
+            },
             Event::Key(KeyEvent {
                 code: Key::Char('p'),
                 modifiers: KeyModifiers::NONE,
             }) => ScreenFeedback::NextScreen(PlatformInfoScreenController::builder()),
+            Event::Key(KeyEvent {
+                code: Key::Char('n'),
+                modifiers: KeyModifiers::NONE,
+            }) => ScreenFeedback::NextScreen(NetworkScreenController::builder()),
+            Event::Key(KeyEvent {
+                code: Key::Char('o'),
+                modifiers: KeyModifiers::NONE,
+            }) => ScreenFeedback::NextScreen(SettingsScreenController::builder()),
+            Event::Key(KeyEvent {
+                code: Key::Char('t'),
+                modifiers: KeyModifiers::NONE,
+            }) => ScreenFeedback::NextScreen(TutorialScreenController::builder()),
+            Event::Key(KeyEvent {
+                code: Key::Char('/'),
+                modifiers: KeyModifiers::NONE,
+            }) => ScreenFeedback::NextScreen(SearchScreenController::builder()),
+            Event::Key(KeyEvent {
+                code: Key::Char('b'),
+                modifiers: KeyModifiers::NONE,
+            }) => ScreenFeedback::NextScreen(BookmarksScreenController::builder()),
+            Event::Key(KeyEvent {
+                code: Key::Char('h'),
+                modifiers: KeyModifiers::NONE,
+            }) => ScreenFeedback::NextScreen(TaskHistoryScreenController::builder()),
+            Event::Backend(BackendEvent::TaskCompleted {
+                task: Task::PlatformInfo(PlatformInfoTask::FetchProtocolUpgradeStatus),
+                execution_result,
+            }) => match execution_result {
+                Ok(status) => ScreenFeedback::NextScreen(VersionUpgradeScreenController::builder(
+                    status.to_string(),
+                )),
+                Err(e) => {
+                    self.info = Info::new_error(e);
+                    ScreenFeedback::Redraw
+                }
+            },
             _ => ScreenFeedback::None,
         }
     }
@@ -0,0 +1,366 @@
+//! Screens of the network nodes.
+
+use std::fmt::{self, Display};
+
+use futures::FutureExt;
+use tuirealm::{
+    event::{Key, KeyEvent, KeyModifiers},
+    tui::prelude::{Constraint, Direction, Layout, Rect},
+    Frame,
+};
+
+use crate::{
+    backend::{
+        network::{Evonode, NetworkTask},
+        BackendEvent, CompletedTaskPayload, Task,
+    },
+    ui::{
+        form::{Input, InputStatus, SelectInput},
+        screen::{
+            utils::impl_builder_no_args, widgets::info::Info, ScreenCommandKey, ScreenController,
+            ScreenControllerBuilder, ScreenFeedback, ScreenToggleKey,
+        },
+    },
+    Event,
+};
+
+const NETWORK_COMMAND_KEYS: [ScreenCommandKey; 3] = [
+    ScreenCommandKey::new("q", "Back"),
+    ScreenCommandKey::new("e", "Evonodes"),
+    ScreenCommandKey::new("w", "Rewards of configured evonode"),
+];
+
+const EVONODES_COMMAND_KEYS: [ScreenCommandKey; 5] = [
+    ScreenCommandKey::new("q", "Back"),
+    ScreenCommandKey::new("r", "Refresh evonodes"),
+    ScreenCommandKey::new("w", "Evonode rewards"),
+    ScreenCommandKey::new("↓ / C-n", "Next evonode"),
+    ScreenCommandKey::new("↑ / C-p", "Prev evonode"),
+];
+
+const REWARDS_COMMAND_KEYS: [ScreenCommandKey; 3] = [
+    ScreenCommandKey::new("q", "Back"),
+    ScreenCommandKey::new("↓", "Scroll down"),
+    ScreenCommandKey::new("↑", "Scroll up"),
+];
+
+const HELP: &str = r#"Nodes of the network, as listed by the Dash Core node the explorer is
+connected to over RPC.
+
+Rewards are reported for the evonode configured with EXPLORER_EVONODE_PRO_TX_HASH
+or the one selected in the evonodes list."#;
+
+pub(crate) struct NetworkScreenController {
+    info: Info,
+}
+
+impl_builder_no_args!(NetworkScreenController);
+
+impl NetworkScreenController {
+    pub(crate) fn new() -> Self {
+        NetworkScreenController {
+            info: Info::new_fixed(HELP),
+        }
+    }
+}
+
+impl ScreenController for NetworkScreenController {
+    fn name(&self) -> &'static str {
+        "Network"
+    }
+
+    fn command_keys(&self) -> &[ScreenCommandKey] {
+        NETWORK_COMMAND_KEYS.as_ref()
+    }
+
+    fn toggle_keys(&self) -> &[ScreenToggleKey] {
+        &[]
+    }
+
+    fn on_event(&mut self, event: &Event) -> ScreenFeedback {
+        match event {
+            Event::Key(KeyEvent {
+                code: Key::Char('q'),
+                modifiers: KeyModifiers::NONE,
+            }) => ScreenFeedback::PreviousScreen,
+
+            Event::Key(KeyEvent {
+                code: Key::Char('e'),
+                modifiers: KeyModifiers::NONE,
+            }) => ScreenFeedback::Task {
+                task: Task::Network(NetworkTask::FetchEvonodes),
+                block: true,
+            },
+
+            Event::Key(KeyEvent {
+                code: Key::Char('w'),
+                modifiers: KeyModifiers::NONE,
+            }) => ScreenFeedback::Task {
+                task: Task::Network(NetworkTask::FetchEvonodeRewards(None)),
+                block: true,
+            },
+
+            Event::Backend(BackendEvent::TaskCompleted {
+                task: Task::Network(NetworkTask::FetchEvonodes),
+                execution_result,
+            }) => match execution_result {
+                Ok(CompletedTaskPayload::Evonodes(evonodes)) => {
+                    ScreenFeedback::NextScreen(EvonodesScreenController::builder(evonodes.clone()))
+                }
+                result => {
+                    self.info = Info::new_from_result(result);
+                    ScreenFeedback::Redraw
+                }
+            },
+
+            Event::Backend(BackendEvent::TaskCompleted {
+                task: Task::Network(NetworkTask::FetchEvonodeRewards(pro_tx_hash)),
+                execution_result,
+            }) => match execution_result {
+                Ok(CompletedTaskPayload::String(report)) => ScreenFeedback::NextScreen(
+                    EvonodeRewardsScreenController::builder(pro_tx_hash.clone(), report.clone()),
+                ),
+                result => {
+                    self.info = Info::new_from_result(result);
+                    ScreenFeedback::Redraw
+                }
+            },
+
+            _ => ScreenFeedback::None,
+        }
+    }
+
+    fn view(&mut self, frame: &mut Frame, area: Rect) {
+        self.info.view(frame, area)
+    }
+}
+
+/// Evonode wrapper for display in a list
+#[derive(Clone)]
+struct EvonodeItem(Evonode);
+
+impl Display for EvonodeItem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {:<22} PoSe {:>4}{}",
+            self.0.pro_tx_hash,
+            self.0.service,
+            self.0.pose_penalty,
+            if self.0.is_banned() { " banned" } else { "" }
+        )
+    }
+}
+
+struct EvonodesScreenController {
+    evonodes: Vec<Evonode>,
+    select: Option<SelectInput<EvonodeItem>>,
+    info: Info,
+}
+
+impl EvonodesScreenController {
+    /// Screen listing the fetched evonodes.
+    fn builder(evonodes: Vec<Evonode>) -> ScreenControllerBuilder {
+        Box::new(move |_| {
+            async move {
+                let mut screen = EvonodesScreenController {
+                    evonodes: Vec::new(),
+                    select: None,
+                    info: Info::new_fixed(""),
+                };
+                screen.show(evonodes);
+                Box::new(screen) as Box<dyn ScreenController>
+            }
+            .boxed()
+        })
+    }
+
+    fn show(&mut self, evonodes: Vec<Evonode>) {
+        self.select = (!evonodes.is_empty())
+            .then(|| SelectInput::new(evonodes.iter().cloned().map(EvonodeItem).collect()));
+        self.evonodes = evonodes;
+        self.show_details();
+    }
+
+    fn show_details(&mut self) {
+        self.info = match &self.select {
+            Some(select) => Info::new_fixed(&self.evonodes[select.selected_index()].details()),
+            None => Info::new_fixed("No evonodes in the masternode list"),
+        };
+    }
+}
+
+impl ScreenController for EvonodesScreenController {
+    fn name(&self) -> &'static str {
+        "Evonodes"
+    }
+
+    fn command_keys(&self) -> &[ScreenCommandKey] {
+        EVONODES_COMMAND_KEYS.as_ref()
+    }
+
+    fn toggle_keys(&self) -> &[ScreenToggleKey] {
+        &[]
+    }
+
+    fn on_event(&mut self, event: &Event) -> ScreenFeedback {
+        match event {
+            Event::Key(KeyEvent {
+                code: Key::Char('q'),
+                modifiers: KeyModifiers::NONE,
+            }) => ScreenFeedback::PreviousScreen,
+
+            Event::Key(KeyEvent {
+                code: Key::Char('r'),
+                modifiers: KeyModifiers::NONE,
+            }) => ScreenFeedback::Task {
+                task: Task::Network(NetworkTask::FetchEvonodes),
+                block: true,
+            },
+
+            Event::Key(KeyEvent {
+                code: Key::Char('w'),
+                modifiers: KeyModifiers::NONE,
+            }) => match &self.select {
+                Some(select) => ScreenFeedback::Task {
+                    task: Task::Network(NetworkTask::FetchEvonodeRewards(Some(
+                        self.evonodes[select.selected_index()].pro_tx_hash.clone(),
+                    ))),
+                    block: true,
+                },
+                None => ScreenFeedback::None,
+            },
+
+            Event::Key(event) => match &mut self.select {
+                Some(select) => match select.on_event(*event) {
+                    InputStatus::Redraw => {
+                        self.show_details();
+                        ScreenFeedback::Redraw
+                    }
+                    _ => ScreenFeedback::None,
+                },
+                None => ScreenFeedback::None,
+            },
+
+            Event::Backend(BackendEvent::TaskCompleted {
+                task: Task::Network(NetworkTask::FetchEvonodes),
+                execution_result,
+            }) => {
+                match execution_result {
+                    Ok(CompletedTaskPayload::Evonodes(evonodes)) => self.show(evonodes.clone()),
+                    result => self.info = Info::new_from_result(result),
+                }
+                ScreenFeedback::Redraw
+            }
+
+            Event::Backend(BackendEvent::TaskCompleted {
+                task: Task::Network(NetworkTask::FetchEvonodeRewards(pro_tx_hash)),
+                execution_result,
+            }) => match execution_result {
+                Ok(CompletedTaskPayload::String(report)) => ScreenFeedback::NextScreen(
+                    EvonodeRewardsScreenController::builder(pro_tx_hash.clone(), report.clone()),
+                ),
+                result => {
+                    self.info = Info::new_from_result(result);
+                    ScreenFeedback::Redraw
+                }
+            },
+
+            _ => ScreenFeedback::None,
+        }
+    }
+
+    fn view(&mut self, frame: &mut Frame, area: Rect) {
+        let Some(select) = &mut self.select else {
+            self.info.view(frame, area);
+            return;
+        };
+        let layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(3), Constraint::Length(10)].as_ref())
+            .split(area);
+        select.view(frame, layout[0]);
+        self.info.view(frame, layout[1]);
+    }
+
+    fn refresh_task(&self) -> Option<Task> {
+        Some(Task::Network(NetworkTask::FetchEvonodes))
+    }
+}
+
+/// Rewards report of an evonode, refreshed with the refresh key.
+struct EvonodeRewardsScreenController {
+    /// The configured evonode if `None`
+    pro_tx_hash: Option<String>,
+    info: Info,
+}
+
+impl EvonodeRewardsScreenController {
+    fn builder(pro_tx_hash: Option<String>, report: String) -> ScreenControllerBuilder {
+        Box::new(move |_| {
+            async move {
+                Box::new(EvonodeRewardsScreenController {
+                    pro_tx_hash,
+                    info: Info::new_scrollable(&report),
+                }) as Box<dyn ScreenController>
+            }
+            .boxed()
+        })
+    }
+}
+
+impl ScreenController for EvonodeRewardsScreenController {
+    fn name(&self) -> &'static str {
+        "Evonode rewards"
+    }
+
+    fn command_keys(&self) -> &[ScreenCommandKey] {
+        REWARDS_COMMAND_KEYS.as_ref()
+    }
+
+    fn toggle_keys(&self) -> &[ScreenToggleKey] {
+        &[]
+    }
+
+    fn on_event(&mut self, event: &Event) -> ScreenFeedback {
+        match event {
+            Event::Key(KeyEvent {
+                code: Key::Char('q'),
+                modifiers: KeyModifiers::NONE,
+            }) => ScreenFeedback::PreviousScreen,
+
+            Event::Key(
+                key_event @ KeyEvent {
+                    code: Key::Down | Key::Up,
+                    modifiers: KeyModifiers::NONE,
+                },
+            ) => {
+                self.info.on_event(key_event);
+                ScreenFeedback::Redraw
+            }
+
+            Event::Backend(BackendEvent::TaskCompleted {
+                task: Task::Network(NetworkTask::FetchEvonodeRewards(_)),
+                execution_result,
+            }) => {
+                self.info = match execution_result {
+                    Ok(CompletedTaskPayload::String(report)) => Info::new_scrollable(report),
+                    result => Info::new_from_result(result),
+                };
+                ScreenFeedback::Redraw
+            }
+
+            _ => ScreenFeedback::None,
+        }
+    }
+
+    fn view(&mut self, frame: &mut Frame, area: Rect) {
+        self.info.view(frame, area)
+    }
+
+    fn refresh_task(&self) -> Option<Task> {
+        Some(Task::Network(NetworkTask::FetchEvonodeRewards(
+            self.pro_tx_hash.clone(),
+        )))
+    }
+}
@@ -1,5 +1,7 @@
 //! Platform info views.
 
+use std::borrow::Cow;
+
 use tuirealm::{
     event::{Key, KeyEvent, KeyModifiers},
     tui::prelude::Rect,
@@ -9,14 +11,15 @@ use tuirealm::{
 use crate::{
     backend::{
         platform_info::PlatformInfoTask::{
-            FetchCurrentEpochInfo, FetchCurrentVersionVotingState, FetchSpecificEpochInfo,
+            ExportEpochs, FetchCurrentEpochInfo, FetchCurrentVersionVotingState,
+            FetchSpecificEpochInfo, ShowNetworkChangelog,
         },
         AppState, BackendEvent, Task,
     },
     ui::{
         form::{
-            parsers::DefaultTextInputParser, FormController, FormStatus, Input, InputStatus,
-            TextInput,
+            parsers::DefaultTextInputParser, ComposedInput, Field, FormController, FormStatus,
+            Input, InputStatus, TextInput,
         },
         screen::{
             utils::impl_builder, widgets::info::Info, ScreenCommandKey, ScreenController,
@@ -26,11 +29,13 @@ use crate::{
     Event,
 };
 
-const COMMAND_KEYS: [ScreenCommandKey; 4] = [
+const COMMAND_KEYS: [ScreenCommandKey; 6] = [
     ScreenCommandKey::new("q", "Back to Main"),
     ScreenCommandKey::new("c", "Fetch current Platform epoch info"),
     ScreenCommandKey::new("i", "Fetch previous Platform epoch info"),
     ScreenCommandKey::new("v", "Current version voting"),
+    ScreenCommandKey::new("l", "Network parameters changelog"),
+    ScreenCommandKey::new("x", "Export epochs to CSV"),
 ];
 
 pub(crate) struct PlatformInfoScreenController {
@@ -83,11 +88,24 @@ impl ScreenController for PlatformInfoScreenController {
                 block: true,
             },
 
+            Event::Key(KeyEvent {
+                code: Key::Char('l'),
+                modifiers: KeyModifiers::NONE,
+            }) => ScreenFeedback::Task {
+                task: Task::PlatformInfo(ShowNetworkChangelog),
+                block: true,
+            },
+
             Event::Key(KeyEvent {
                 code: Key::Char('i'),
                 modifiers: KeyModifiers::NONE,
             }) => ScreenFeedback::Form(Box::new(EpochNumberChooserFormController::new())),
 
+            Event::Key(KeyEvent {
+                code: Key::Char('x'),
+                modifiers: KeyModifiers::NONE,
+            }) => ScreenFeedback::Form(Box::new(ExportEpochsFormController::new())),
+
             Event::Backend(BackendEvent::TaskCompleted {
                 task: Task::PlatformInfo(_),
                 execution_result,
@@ -137,8 +155,8 @@ impl FormController for EpochNumberChooserFormController {
         self.input.view(frame, area)
     }
 
-    fn step_name(&self) -> &'static str {
-        "Input epoch number"
+    fn step_name(&self) -> Cow<'_, str> {
+        "Input epoch number".into()
     }
 
     fn step_index(&self) -> u8 {
@@ -149,3 +167,53 @@ impl FormController for EpochNumberChooserFormController {
         1
     }
 }
+
+struct ExportEpochsFormController {
+    input: ComposedInput<(
+        Field<TextInput<DefaultTextInputParser<u16>>>,
+        Field<TextInput<DefaultTextInputParser<u32>>>,
+    )>,
+}
+
+impl ExportEpochsFormController {
+    fn new() -> Self {
+        ExportEpochsFormController {
+            input: ComposedInput::new((
+                Field::new("First epoch", TextInput::new_init_value("Epoch number", 0)),
+                Field::new("Epochs count", TextInput::new_init_value("Count", 100)),
+            )),
+        }
+    }
+}
+
+impl FormController for ExportEpochsFormController {
+    fn on_event(&mut self, event: KeyEvent) -> FormStatus {
+        match self.input.on_event(event) {
+            InputStatus::Done((first_epoch, count)) => FormStatus::Done {
+                task: Task::PlatformInfo(ExportEpochs(first_epoch, count)),
+                block: true,
+            },
+            status => status.into(),
+        }
+    }
+
+    fn form_name(&self) -> &'static str {
+        "Export epochs to CSV"
+    }
+
+    fn step_view(&mut self, frame: &mut Frame, area: Rect) {
+        self.input.view(frame, area)
+    }
+
+    fn step_name(&self) -> Cow<'_, str> {
+        self.input.step_name().into()
+    }
+
+    fn step_index(&self) -> u8 {
+        self.input.step_index()
+    }
+
+    fn steps_number(&self) -> u8 {
+        self.input.steps_number()
+    }
+}
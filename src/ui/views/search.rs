@@ -0,0 +1,204 @@
+//! Search screen across locally cached contracts and documents.
+
+use std::{
+    borrow::Cow,
+    collections::BTreeMap,
+    fmt::{self, Display},
+};
+
+use dpp::document::DocumentV0Getters;
+use futures::FutureExt;
+use tuirealm::{
+    event::{Key, KeyEvent, KeyModifiers},
+    tui::prelude::Rect,
+    Frame,
+};
+
+use crate::{
+    backend::{
+        search::{SearchHit, SearchTarget, SearchTask},
+        BackendEvent, CompletedTaskPayload, Task,
+    },
+    ui::{
+        form::{
+            parsers::DefaultTextInputParser, FormController, FormStatus, Input, InputStatus,
+            SelectInput, TextInput,
+        },
+        screen::{
+            utils::impl_builder_no_args, widgets::info::Info, ScreenCommandKey, ScreenController,
+            ScreenFeedback, ScreenToggleKey,
+        },
+        views::{
+            contracts::SelectDocumentTypeFormController,
+            documents::DocumentsQuerysetScreenController,
+        },
+    },
+    Event,
+};
+
+const COMMAND_KEYS: [ScreenCommandKey; 5] = [
+    ScreenCommandKey::new("q", "Back"),
+    ScreenCommandKey::new("/", "Search"),
+    ScreenCommandKey::new("↓ / C-n", "Next result"),
+    ScreenCommandKey::new("↑ / C-p", "Prev result"),
+    ScreenCommandKey::new("Enter", "Open result"),
+];
+
+const HELP: &str = r#"Search fetched data contracts (names, document types and property names)
+and documents fetched during this session (string values).
+
+Every word of the search must match, best matches come first."#;
+
+/// Search result wrapper for display in a list
+#[derive(Clone)]
+struct SearchHitEntry(SearchHit);
+
+impl Display for SearchHitEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{:>3}] {}", self.0.score, self.0.summary)
+    }
+}
+
+pub(crate) struct SearchScreenController {
+    results: Option<SelectInput<SearchHitEntry>>,
+    info: Info,
+}
+
+impl_builder_no_args!(SearchScreenController);
+
+impl SearchScreenController {
+    pub(crate) fn new() -> Self {
+        SearchScreenController {
+            results: None,
+            info: Info::new_fixed(HELP),
+        }
+    }
+
+    fn open(hit: SearchHit) -> ScreenFeedback {
+        match hit.target {
+            SearchTarget::Contract {
+                name,
+                document_type_names,
+            } => ScreenFeedback::Form(Box::new(SelectDocumentTypeFormController::new(
+                name,
+                document_type_names,
+            ))),
+            SearchTarget::Document { query, document } => {
+                let documents = BTreeMap::from([(document.id(), Some(document))]);
+                ScreenFeedback::NextScreen(Box::new(move |_| {
+                    async move {
+                        Box::new(DocumentsQuerysetScreenController::new(query, documents))
+                            as Box<dyn ScreenController>
+                    }
+                    .boxed()
+                }))
+            }
+        }
+    }
+}
+
+impl ScreenController for SearchScreenController {
+    fn name(&self) -> &'static str {
+        "Search"
+    }
+
+    fn command_keys(&self) -> &[ScreenCommandKey] {
+        COMMAND_KEYS.as_ref()
+    }
+
+    fn toggle_keys(&self) -> &[ScreenToggleKey] {
+        &[]
+    }
+
+    fn on_event(&mut self, event: &Event) -> ScreenFeedback {
+        match event {
+            Event::Key(KeyEvent {
+                code: Key::Char('q'),
+                modifiers: KeyModifiers::NONE,
+            }) => ScreenFeedback::PreviousScreen,
+
+            Event::Key(KeyEvent {
+                code: Key::Char('/'),
+                modifiers: KeyModifiers::NONE,
+            }) => ScreenFeedback::Form(Box::new(SearchFormController::new())),
+
+            Event::Key(event) => match &mut self.results {
+                Some(results) => match results.on_event(*event) {
+                    InputStatus::Done(SearchHitEntry(hit)) => Self::open(hit),
+                    InputStatus::Redraw => ScreenFeedback::Redraw,
+                    _ => ScreenFeedback::None,
+                },
+                None => ScreenFeedback::None,
+            },
+
+            Event::Backend(BackendEvent::TaskCompleted {
+                task: Task::Search(_),
+                execution_result: Ok(CompletedTaskPayload::SearchResults(hits)),
+            }) => {
+                if hits.is_empty() {
+                    self.results = None;
+                    self.info = Info::new_fixed("Nothing found");
+                } else {
+                    self.results = Some(SelectInput::new(
+                        hits.iter().cloned().map(SearchHitEntry).collect(),
+                    ));
+                }
+                ScreenFeedback::Redraw
+            }
+
+            _ => ScreenFeedback::None,
+        }
+    }
+
+    fn view(&mut self, frame: &mut Frame, area: Rect) {
+        if let Some(results) = &mut self.results {
+            results.view(frame, area)
+        } else {
+            self.info.view(frame, area)
+        }
+    }
+}
+
+struct SearchFormController {
+    input: TextInput<DefaultTextInputParser<String>>,
+}
+
+impl SearchFormController {
+    fn new() -> Self {
+        SearchFormController {
+            input: TextInput::new("words to search for"),
+        }
+    }
+}
+
+impl FormController for SearchFormController {
+    fn on_event(&mut self, event: KeyEvent) -> FormStatus {
+        match self.input.on_event(event) {
+            InputStatus::Done(query) => FormStatus::Done {
+                task: Task::Search(SearchTask::Search(query)),
+                block: false,
+            },
+            status => status.into(),
+        }
+    }
+
+    fn form_name(&self) -> &'static str {
+        "Search cached contracts and documents"
+    }
+
+    fn step_view(&mut self, frame: &mut Frame, area: Rect) {
+        self.input.view(frame, area)
+    }
+
+    fn step_name(&self) -> Cow<'_, str> {
+        "Search".into()
+    }
+
+    fn step_index(&self) -> u8 {
+        0
+    }
+
+    fn steps_number(&self) -> u8 {
+        1
+    }
+}
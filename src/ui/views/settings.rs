@@ -0,0 +1,466 @@
+//! Settings screen.
+
+use std::borrow::Cow;
+
+use futures::FutureExt;
+use tuirealm::{
+    event::{Key, KeyEvent, KeyModifiers},
+    tui::prelude::{Constraint, Direction, Layout, Rect},
+    Frame,
+};
+
+use crate::{
+    backend::{
+        settings::{ConnectionSettings, SettingsTask},
+        BackendEvent, CompletedTaskPayload, Task,
+    },
+    config::{Config, Density},
+    logging,
+    ui::{
+        density,
+        form::{
+            parsers::DefaultTextInputParser, ComposedInput, Field, FormController, FormStatus,
+            Input, InputStatus, SelectInput, TextInput,
+        },
+        proofs,
+        screen::{
+            utils::impl_builder_no_args, widgets::info::Info, ScreenCommandKey, ScreenController,
+            ScreenControllerBuilder, ScreenFeedback, ScreenToggleKey,
+        },
+    },
+    Event,
+};
+
+const COMMAND_KEYS: [ScreenCommandKey; 6] = [
+    ScreenCommandKey::new("q", "Back to Main"),
+    ScreenCommandKey::new("l", "Set log filter"),
+    ScreenCommandKey::new("d", "Switch display density"),
+    ScreenCommandKey::new("v", "Switch proof details"),
+    ScreenCommandKey::new("n", "Network profiles"),
+    ScreenCommandKey::new("c", "Connection settings"),
+];
+
+const CONNECTION_COMMAND_KEYS: [ScreenCommandKey; 2] = [
+    ScreenCommandKey::new("q", "Back to Settings"),
+    ScreenCommandKey::new("e", "Edit connection settings"),
+];
+
+const PROFILES_COMMAND_KEYS: [ScreenCommandKey; 4] = [
+    ScreenCommandKey::new("q", "Back to Settings"),
+    ScreenCommandKey::new("Enter", "Switch to profile"),
+    ScreenCommandKey::new("↓ / C-n", "Next profile"),
+    ScreenCommandKey::new("↑ / C-p", "Prev profile"),
+];
+
+pub(crate) struct SettingsScreenController {
+    info: Info,
+}
+
+impl_builder_no_args!(SettingsScreenController);
+
+impl SettingsScreenController {
+    pub(crate) fn new() -> Self {
+        SettingsScreenController {
+            info: Info::new_fixed(&display_settings()),
+        }
+    }
+}
+
+impl ScreenController for SettingsScreenController {
+    fn name(&self) -> &'static str {
+        "Settings"
+    }
+
+    fn command_keys(&self) -> &[ScreenCommandKey] {
+        COMMAND_KEYS.as_ref()
+    }
+
+    fn toggle_keys(&self) -> &[ScreenToggleKey] {
+        &[]
+    }
+
+    fn on_event(&mut self, event: &Event) -> ScreenFeedback {
+        match event {
+            Event::Key(KeyEvent {
+                code: Key::Char('q'),
+                modifiers: KeyModifiers::NONE,
+            }) => ScreenFeedback::PreviousScreen,
+            Event::Key(KeyEvent {
+                code: Key::Char('l'),
+                modifiers: KeyModifiers::NONE,
+            }) => ScreenFeedback::Form(Box::new(LogFilterFormController::new())),
+            Event::Key(KeyEvent {
+                code: Key::Char('n'),
+                modifiers: KeyModifiers::NONE,
+            }) => ScreenFeedback::NextScreen(ProfilesScreenController::builder()),
+            Event::Key(KeyEvent {
+                code: Key::Char('c'),
+                modifiers: KeyModifiers::NONE,
+            }) => ScreenFeedback::Task {
+                task: Task::Settings(SettingsTask::ShowConnection),
+                block: true,
+            },
+            Event::Key(KeyEvent {
+                code: Key::Char('d'),
+                modifiers: KeyModifiers::NONE,
+            }) => {
+                // Lasts for the session, the configured density is used on
+                // next start
+                density::set_density(match density::density() {
+                    Density::Comfortable => Density::Compact,
+                    Density::Compact => Density::Comfortable,
+                });
+                self.info = Info::new_fixed(&display_settings());
+                ScreenFeedback::Redraw
+            }
+            Event::Key(KeyEvent {
+                code: Key::Char('v'),
+                modifiers: KeyModifiers::NONE,
+            }) => {
+                // Lasts for the session, screens opened afterwards fetch with
+                // proof details by default
+                proofs::set_proof_details(!proofs::proof_details());
+                self.info = Info::new_fixed(&display_settings());
+                ScreenFeedback::Redraw
+            }
+            Event::Backend(BackendEvent::TaskCompleted {
+                task: Task::SetLogFilter(_),
+                execution_result: Err(e),
+            }) => {
+                self.info = Info::new_error(e);
+                ScreenFeedback::Redraw
+            }
+            Event::Backend(BackendEvent::TaskCompleted {
+                task: Task::SetLogFilter(_),
+                ..
+            }) => {
+                self.info = Info::new_fixed(&display_settings());
+                ScreenFeedback::Redraw
+            }
+            Event::Backend(BackendEvent::TaskCompleted {
+                task: Task::Settings(SettingsTask::ShowConnection),
+                execution_result,
+            }) => match execution_result {
+                Ok(CompletedTaskPayload::ConnectionSettings(settings)) => {
+                    ScreenFeedback::NextScreen(ConnectionSettingsScreenController::builder(
+                        settings.clone(),
+                    ))
+                }
+                result => {
+                    self.info = Info::new_from_result(result);
+                    ScreenFeedback::Redraw
+                }
+            },
+            _ => ScreenFeedback::None,
+        }
+    }
+
+    fn view(&mut self, frame: &mut Frame, area: Rect) {
+        self.info.view(frame, area)
+    }
+}
+
+fn display_settings() -> String {
+    format!(
+        "Log filter: {}\nDisplay density: {}\nProof details: {}",
+        logging::current_filter().unwrap_or_else(|| "unknown".to_owned()),
+        match density::density() {
+            Density::Comfortable => "comfortable",
+            Density::Compact => "compact",
+        },
+        if proofs::proof_details() { "on" } else { "off" }
+    )
+}
+
+/// Network profiles of the configuration, switching to another one restarts
+/// the explorer with its SDK and state.
+struct ProfilesScreenController {
+    current_profile: String,
+    select: Option<SelectInput<String>>,
+    info: Info,
+}
+
+impl ProfilesScreenController {
+    fn builder() -> ScreenControllerBuilder {
+        Box::new(|app_state| {
+            let current_profile = app_state.profile.clone();
+            async move {
+                let profiles = Config::available_profiles();
+                let select = (!profiles.is_empty()).then(|| {
+                    let line = profiles
+                        .iter()
+                        .position(|profile| *profile == current_profile)
+                        .unwrap_or_default();
+                    SelectInput::new_init_line(profiles, line)
+                });
+                let info = if select.is_none() {
+                    Info::new_fixed(
+                        "No network profiles configured, options of a profile are prefixed with \
+                         its name, e.g. TESTNET_EXPLORER_DAPI_ADDRESSES, or follow a \
+                         [profile.testnet] line in the .env file",
+                    )
+                } else {
+                    Info::new_fixed(&format!("Current profile: {}", current_profile))
+                };
+                Box::new(ProfilesScreenController {
+                    current_profile,
+                    select,
+                    info,
+                }) as Box<dyn ScreenController>
+            }
+            .boxed()
+        })
+    }
+}
+
+impl ScreenController for ProfilesScreenController {
+    fn name(&self) -> &'static str {
+        "Network profiles"
+    }
+
+    fn command_keys(&self) -> &[ScreenCommandKey] {
+        PROFILES_COMMAND_KEYS.as_ref()
+    }
+
+    fn toggle_keys(&self) -> &[ScreenToggleKey] {
+        &[]
+    }
+
+    fn on_event(&mut self, event: &Event) -> ScreenFeedback {
+        match event {
+            Event::Key(KeyEvent {
+                code: Key::Char('q'),
+                modifiers: KeyModifiers::NONE,
+            }) => ScreenFeedback::PreviousScreen,
+            Event::Key(event) => match &mut self.select {
+                Some(select) => match select.on_event(*event) {
+                    InputStatus::Done(profile) if profile == self.current_profile => {
+                        self.info = Info::new_error("Already using this profile");
+                        ScreenFeedback::Redraw
+                    }
+                    InputStatus::Done(profile) => ScreenFeedback::SwitchProfile(profile),
+                    InputStatus::Redraw => ScreenFeedback::Redraw,
+                    _ => ScreenFeedback::None,
+                },
+                None => ScreenFeedback::None,
+            },
+            _ => ScreenFeedback::None,
+        }
+    }
+
+    fn view(&mut self, frame: &mut Frame, area: Rect) {
+        let Some(select) = &mut self.select else {
+            self.info.view(frame, area);
+            return;
+        };
+        let layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(3), Constraint::Length(3)].as_ref())
+            .split(area);
+        select.view(frame, layout[0]);
+        self.info.view(frame, layout[1]);
+    }
+}
+
+/// Connection settings from the configuration, edited settings are saved into
+/// the `.env` file and take effect on next start.
+struct ConnectionSettingsScreenController {
+    settings: ConnectionSettings,
+    info: Info,
+}
+
+impl ConnectionSettingsScreenController {
+    fn builder(settings: ConnectionSettings) -> ScreenControllerBuilder {
+        Box::new(move |_| {
+            async move {
+                let info = Info::new_fixed(&settings.details());
+                Box::new(ConnectionSettingsScreenController { settings, info })
+                    as Box<dyn ScreenController>
+            }
+            .boxed()
+        })
+    }
+}
+
+impl ScreenController for ConnectionSettingsScreenController {
+    fn name(&self) -> &'static str {
+        "Connection settings"
+    }
+
+    fn command_keys(&self) -> &[ScreenCommandKey] {
+        CONNECTION_COMMAND_KEYS.as_ref()
+    }
+
+    fn toggle_keys(&self) -> &[ScreenToggleKey] {
+        &[]
+    }
+
+    fn on_event(&mut self, event: &Event) -> ScreenFeedback {
+        match event {
+            Event::Key(KeyEvent {
+                code: Key::Char('q'),
+                modifiers: KeyModifiers::NONE,
+            }) => ScreenFeedback::PreviousScreen,
+            Event::Key(KeyEvent {
+                code: Key::Char('e'),
+                modifiers: KeyModifiers::NONE,
+            }) => ScreenFeedback::Form(Box::new(ConnectionSettingsFormController::new(
+                &self.settings,
+            ))),
+            Event::Backend(BackendEvent::TaskCompleted {
+                task: Task::Settings(SettingsTask::SaveConnection(_)),
+                execution_result,
+            }) => {
+                self.info = match execution_result {
+                    Ok(CompletedTaskPayload::ConnectionSettings(settings)) => {
+                        self.settings = settings.clone();
+                        Info::new_fixed(&format!(
+                            "{}\n\nSaved into .env, takes effect on next start or profile switch",
+                            settings.details()
+                        ))
+                    }
+                    result => Info::new_from_result(result),
+                };
+                ScreenFeedback::Redraw
+            }
+            _ => ScreenFeedback::None,
+        }
+    }
+
+    fn view(&mut self, frame: &mut Frame, area: Rect) {
+        self.info.view(frame, area)
+    }
+}
+
+struct ConnectionSettingsFormController {
+    input: ComposedInput<(
+        Field<TextInput<DefaultTextInputParser<String>>>,
+        Field<TextInput<DefaultTextInputParser<String>>>,
+        Field<TextInput<DefaultTextInputParser<u16>>>,
+        Field<TextInput<DefaultTextInputParser<String>>>,
+        Field<TextInput<DefaultTextInputParser<String>>>,
+    )>,
+    wallet_private_key_set: bool,
+}
+
+impl ConnectionSettingsFormController {
+    fn new(settings: &ConnectionSettings) -> Self {
+        ConnectionSettingsFormController {
+            input: ComposedInput::new((
+                Field::new(
+                    "DAPI addresses",
+                    TextInput::new_init_value(
+                        "Comma-separated URLs",
+                        settings.dapi_addresses.clone(),
+                    ),
+                ),
+                Field::new(
+                    "Core host",
+                    TextInput::new_init_value("Hostname or IP", settings.core_host.clone()),
+                ),
+                Field::new(
+                    "Core RPC port",
+                    TextInput::new_init_value("Port number", settings.core_rpc_port),
+                ),
+                Field::new(
+                    "Core RPC user",
+                    TextInput::new_init_value("Username", settings.core_rpc_user.clone()),
+                ),
+                Field::new(
+                    "Insight API URL",
+                    TextInput::new_init_value("URL", settings.insight_api_url.clone()),
+                ),
+            )),
+            wallet_private_key_set: settings.wallet_private_key_set,
+        }
+    }
+}
+
+impl FormController for ConnectionSettingsFormController {
+    fn on_event(&mut self, event: KeyEvent) -> FormStatus {
+        match self.input.on_event(event) {
+            InputStatus::Done((
+                dapi_addresses,
+                core_host,
+                core_rpc_port,
+                core_rpc_user,
+                insight_api_url,
+            )) => FormStatus::Done {
+                task: Task::Settings(SettingsTask::SaveConnection(ConnectionSettings {
+                    dapi_addresses,
+                    core_host,
+                    core_rpc_port,
+                    core_rpc_user,
+                    insight_api_url,
+                    wallet_private_key_set: self.wallet_private_key_set,
+                })),
+                block: false,
+            },
+            status => status.into(),
+        }
+    }
+
+    fn form_name(&self) -> &'static str {
+        "Edit connection settings"
+    }
+
+    fn step_view(&mut self, frame: &mut Frame, area: Rect) {
+        self.input.view(frame, area)
+    }
+
+    fn step_name(&self) -> Cow<'_, str> {
+        self.input.step_name().into()
+    }
+
+    fn step_index(&self) -> u8 {
+        self.input.step_index()
+    }
+
+    fn steps_number(&self) -> u8 {
+        self.input.steps_number()
+    }
+}
+
+struct LogFilterFormController {
+    input: TextInput<DefaultTextInputParser<String>>,
+}
+
+impl LogFilterFormController {
+    fn new() -> Self {
+        LogFilterFormController {
+            input: TextInput::new("RUST_LOG syntax, e.g. info,dash_sdk=debug"),
+        }
+    }
+}
+
+impl FormController for LogFilterFormController {
+    fn on_event(&mut self, event: KeyEvent) -> FormStatus {
+        match self.input.on_event(event) {
+            InputStatus::Done(directives) => FormStatus::Done {
+                task: Task::SetLogFilter(directives),
+                block: false,
+            },
+            status => status.into(),
+        }
+    }
+
+    fn form_name(&self) -> &'static str {
+        "Set log filter"
+    }
+
+    fn step_view(&mut self, frame: &mut Frame, area: Rect) {
+        self.input.view(frame, area)
+    }
+
+    fn step_name(&self) -> Cow<'_, str> {
+        "Filter directives".into()
+    }
+
+    fn step_index(&self) -> u8 {
+        0
+    }
+
+    fn steps_number(&self) -> u8 {
+        1
+    }
+}
@@ -1,11 +1,18 @@
 //! Screens and forms related to strategies manipulation.
 
+mod compare_reports;
+mod contract_variants;
+mod forecast;
+mod funding_plan;
 mod identity_inserts;
 mod operations;
 mod run_strategy;
 pub mod selected_strategy;
 mod start_contracts;
 mod start_identities;
+mod timeline;
+
+use std::borrow::Cow;
 
 use tuirealm::{
     event::{Key, KeyEvent, KeyModifiers},
@@ -13,9 +20,12 @@ use tuirealm::{
     Frame,
 };
 
-use self::selected_strategy::SelectedStrategyScreenController;
+use self::{
+    compare_reports::{CompareReportsFormController, CompareReportsScreenController},
+    selected_strategy::SelectedStrategyScreenController,
+};
 use crate::{
-    backend::{AppState, AppStateUpdate, BackendEvent},
+    backend::{reports, AppState, AppStateUpdate, BackendEvent},
     ui::screen::{
         utils::impl_builder, widgets::info::Info, ScreenCommandKey, ScreenController,
         ScreenFeedback, ScreenToggleKey,
@@ -24,17 +34,22 @@ use crate::{
 };
 
 use crate::{
-    backend::{StrategyTask, Task},
+    backend::{
+        strategies::templates::{StrategyTemplate, TemplateParameters},
+        StrategyTask, Task,
+    },
     ui::form::{
-        parsers::DefaultTextInputParser, FormController, FormStatus, Input, InputStatus,
-        SelectInput, TextInput,
+        parsers::DefaultTextInputParser, ComposedInput, Field, FormController, FormStatus, Input,
+        InputStatus, SelectInput, TextInput,
     },
 };
 
-const COMMAND_KEYS: [ScreenCommandKey; 6] = [
+const COMMAND_KEYS: [ScreenCommandKey; 8] = [
     ScreenCommandKey::new("q", "Back to Main"),
     ScreenCommandKey::new("n", "New strategy"),
+    ScreenCommandKey::new("t", "New strategy from template"),
     ScreenCommandKey::new("i", "Import a strategy"),
+    ScreenCommandKey::new("c", "Compare run reports"),
     ScreenCommandKey::new("e", "Export a strategy"),
     ScreenCommandKey::new("s", "Select a strategy"),
     ScreenCommandKey::new("d", "Delete a strategy"),
@@ -44,6 +59,7 @@ pub(crate) struct StrategiesScreenController {
     info: Info,
     available_strategies: Vec<String>,
     selected_strategy: Option<String>,
+    known_contracts: Vec<String>,
 }
 
 impl_builder!(StrategiesScreenController);
@@ -62,11 +78,19 @@ impl StrategiesScreenController {
         };
 
         let info = Info::new_fixed(&info_text);
+        let known_contracts = app_state
+            .known_contracts
+            .lock()
+            .await
+            .keys()
+            .cloned()
+            .collect();
 
         StrategiesScreenController {
             info,
             available_strategies: strategies,
             selected_strategy: None,
+            known_contracts,
         }
     }
 }
@@ -78,7 +102,7 @@ impl ScreenController for StrategiesScreenController {
 
     fn command_keys(&self) -> &[ScreenCommandKey] {
         if self.available_strategies.is_empty() {
-            &COMMAND_KEYS[..3] // Exclude certain operations when there are no available strategies
+            &COMMAND_KEYS[..5] // Exclude certain operations when there are no available strategies
         } else {
             COMMAND_KEYS.as_ref()
         }
@@ -101,6 +125,15 @@ impl ScreenController for StrategiesScreenController {
                 form: Box::new(NewStrategyFormController::new()),
                 screen: SelectedStrategyScreenController::builder(),
             },
+            Event::Key(KeyEvent {
+                code: Key::Char('t'),
+                modifiers: KeyModifiers::NONE,
+            }) => ScreenFeedback::FormThenNextScreen {
+                form: Box::new(StrategyTemplateFormController::new(
+                    self.known_contracts.clone(),
+                )),
+                screen: SelectedStrategyScreenController::builder(),
+            },
             Event::Key(KeyEvent {
                 code: Key::Char('i'),
                 modifiers: KeyModifiers::NONE,
@@ -108,6 +141,23 @@ impl ScreenController for StrategiesScreenController {
                 form: Box::new(ImportStrategyFormController::new()),
                 screen: SelectedStrategyScreenController::builder(),
             },
+            Event::Key(KeyEvent {
+                code: Key::Char('c'),
+                modifiers: KeyModifiers::NONE,
+            }) => {
+                let reports = reports::list_reports();
+                if reports.is_empty() {
+                    self.info = Info::new_error(
+                        "No strategy reports found, reports are saved after each successful run",
+                    );
+                    ScreenFeedback::Redraw
+                } else {
+                    ScreenFeedback::FormThenNextScreen {
+                        form: Box::new(CompareReportsFormController::new(reports)),
+                        screen: CompareReportsScreenController::builder(),
+                    }
+                }
+            }
             Event::Key(KeyEvent {
                 code: Key::Char('e'),
                 modifiers: KeyModifiers::NONE,
@@ -231,8 +281,8 @@ impl FormController for NewStrategyFormController {
         self.input.view(frame, area)
     }
 
-    fn step_name(&self) -> &'static str {
-        "Strategy name"
+    fn step_name(&self) -> Cow<'_, str> {
+        "Strategy name".into()
     }
 
     fn step_index(&self) -> u8 {
@@ -244,6 +294,132 @@ impl FormController for NewStrategyFormController {
     }
 }
 
+/// Picks a template, then asks for the strategy name, its parameters and the
+/// contract if the template needs one.
+struct StrategyTemplateFormController {
+    known_contracts: Vec<String>,
+    template_input: SelectInput<StrategyTemplate>,
+    template: Option<StrategyTemplate>,
+    parameters_input: ComposedInput<(
+        Field<TextInput<DefaultTextInputParser<String>>>,
+        Field<TextInput<DefaultTextInputParser<u16>>>,
+        Field<TextInput<DefaultTextInputParser<u16>>>,
+    )>,
+    parameters: Option<(String, u16, u16)>,
+    contract_input: SelectInput<String>,
+}
+
+impl StrategyTemplateFormController {
+    fn new(known_contracts: Vec<String>) -> Self {
+        // Templates working on a contract are offered only if there is one
+        let templates = StrategyTemplate::ALL
+            .into_iter()
+            .filter(|template| !template.requires_contract() || !known_contracts.is_empty())
+            .collect();
+
+        StrategyTemplateFormController {
+            template_input: SelectInput::new(templates),
+            template: None,
+            parameters_input: ComposedInput::new((
+                Field::new("Strategy name", TextInput::new("strategy name")),
+                Field::new(
+                    "Start identities",
+                    TextInput::new_init_value("Enter a whole number", 10),
+                ),
+                Field::new(
+                    "Transitions per block",
+                    TextInput::new_init_value("Enter a whole number", 5),
+                ),
+            )),
+            parameters: None,
+            contract_input: SelectInput::new(known_contracts.clone()),
+            known_contracts,
+        }
+    }
+
+    fn done(&self, template: StrategyTemplate, contract_name: Option<String>) -> FormStatus {
+        let (strategy_name, start_identities, transitions_per_block) =
+            self.parameters.clone().expect("parameters are set");
+        FormStatus::Done {
+            task: Task::Strategy(StrategyTask::CreateStrategyFromTemplate(
+                strategy_name,
+                template,
+                TemplateParameters {
+                    start_identities,
+                    transitions_per_block,
+                    contract_name,
+                },
+            )),
+            block: false,
+        }
+    }
+}
+
+impl FormController for StrategyTemplateFormController {
+    fn on_event(&mut self, event: KeyEvent) -> FormStatus {
+        match (self.template, &self.parameters) {
+            (None, _) => match self.template_input.on_event(event) {
+                InputStatus::Done(template) => {
+                    self.template = Some(template);
+                    FormStatus::Redraw
+                }
+                status => status.into(),
+            },
+            (Some(template), None) => match self.parameters_input.on_event(event) {
+                InputStatus::Done(parameters) => {
+                    self.parameters = Some(parameters);
+                    if template.requires_contract() {
+                        FormStatus::Redraw
+                    } else {
+                        self.done(template, None)
+                    }
+                }
+                status => status.into(),
+            },
+            (Some(template), Some(_)) => match self.contract_input.on_event(event) {
+                InputStatus::Done(contract_name) => self.done(template, Some(contract_name)),
+                status => status.into(),
+            },
+        }
+    }
+
+    fn form_name(&self) -> &'static str {
+        "New strategy from template"
+    }
+
+    fn step_view(&mut self, frame: &mut Frame, area: Rect) {
+        match (self.template, &self.parameters) {
+            (None, _) => self.template_input.view(frame, area),
+            (Some(_), None) => self.parameters_input.view(frame, area),
+            (Some(_), Some(_)) => self.contract_input.view(frame, area),
+        }
+    }
+
+    fn step_name(&self) -> Cow<'_, str> {
+        match (self.template, &self.parameters) {
+            (None, _) => "Template".into(),
+            (Some(_), None) => self.parameters_input.step_name().into(),
+            (Some(_), Some(_)) => "Contract".into(),
+        }
+    }
+
+    fn step_index(&self) -> u8 {
+        match (self.template, &self.parameters) {
+            (None, _) => 0,
+            (Some(_), None) => 1 + self.parameters_input.step_index(),
+            (Some(_), Some(_)) => 4,
+        }
+    }
+
+    fn steps_number(&self) -> u8 {
+        match self.template {
+            Some(template) if !template.requires_contract() => 4,
+            _ if self.known_contracts.is_empty() => 4,
+            _ => 5,
+        }
+    }
+}
+
 pub(crate) struct ImportStrategyFormController {
     input: TextInput<DefaultTextInputParser<String>>,
 }
@@ -275,8 +451,8 @@ impl FormController for ImportStrategyFormController {
         self.input.view(frame, area)
     }
 
-    fn step_name(&self) -> &'static str {
-        "Url"
+    fn step_name(&self) -> Cow<'_, str> {
+        "Url".into()
     }
 
     fn step_index(&self) -> u8 {
@@ -320,8 +496,8 @@ impl FormController for ExportStrategyFormController {
         self.input.view(frame, area)
     }
 
-    fn step_name(&self) -> &'static str {
-        ""
+    fn step_name(&self) -> Cow<'_, str> {
+        "".into()
     }
 
     fn step_index(&self) -> u8 {
@@ -365,8 +541,8 @@ impl FormController for SelectStrategyFormController {
         self.input.view(frame, area)
     }
 
-    fn step_name(&self) -> &'static str {
-        ""
+    fn step_name(&self) -> Cow<'_, str> {
+        "".into()
     }
 
     fn step_index(&self) -> u8 {
@@ -438,11 +614,11 @@ impl FormController for DeleteStrategyFormController {
         }
     }
 
-    fn step_name(&self) -> &'static str {
+    fn step_name(&self) -> Cow<'_, str> {
         match self.step {
-            0 => "Select Strategy",
-            1 => "Confirm Deletion",
-            _ => "",
+            0 => "Select Strategy".into(),
+            1 => "Confirm Deletion".into(),
+            _ => "".into(),
         }
     }
 
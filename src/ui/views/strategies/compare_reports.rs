@@ -0,0 +1,124 @@
+//! Strategy reports comparison screen and form.
+
+use std::borrow::Cow;
+
+use tuirealm::{
+    event::{Key, KeyEvent, KeyModifiers},
+    tui::prelude::Rect,
+    Frame,
+};
+
+use crate::{
+    backend::{BackendEvent, StrategyTask, Task},
+    ui::form::{ComposedInput, Field, FormController, FormStatus, Input, InputStatus, SelectInput},
+    ui::screen::{
+        utils::impl_builder_no_args, widgets::info::Info, ScreenCommandKey, ScreenController,
+        ScreenFeedback, ScreenToggleKey,
+    },
+    Event,
+};
+
+const COMMAND_KEYS: [ScreenCommandKey; 1] = [ScreenCommandKey::new("q", "Back to Strategies")];
+
+pub(crate) struct CompareReportsScreenController {
+    info: Info,
+}
+
+impl_builder_no_args!(CompareReportsScreenController);
+
+impl CompareReportsScreenController {
+    pub(crate) fn new() -> Self {
+        Self {
+            info: Info::new_fixed("Select two reports to compare."),
+        }
+    }
+}
+
+impl ScreenController for CompareReportsScreenController {
+    fn name(&self) -> &'static str {
+        "Compare reports"
+    }
+
+    fn command_keys(&self) -> &[ScreenCommandKey] {
+        COMMAND_KEYS.as_ref()
+    }
+
+    fn toggle_keys(&self) -> &[ScreenToggleKey] {
+        &[]
+    }
+
+    fn on_event(&mut self, event: &Event) -> ScreenFeedback {
+        match event {
+            Event::Key(KeyEvent {
+                code: Key::Char('q'),
+                modifiers: KeyModifiers::NONE,
+            }) => ScreenFeedback::PreviousScreen,
+            Event::Backend(BackendEvent::TaskCompleted {
+                task: Task::Strategy(StrategyTask::CompareReports(..)),
+                execution_result,
+            }) => {
+                self.info = Info::new_from_result(execution_result);
+                ScreenFeedback::Redraw
+            }
+            Event::Key(k) => {
+                if self.info.on_event(k) {
+                    ScreenFeedback::Redraw
+                } else {
+                    ScreenFeedback::None
+                }
+            }
+            _ => ScreenFeedback::None,
+        }
+    }
+
+    fn view(&mut self, frame: &mut Frame, area: Rect) {
+        self.info.view(frame, area)
+    }
+}
+
+pub(crate) struct CompareReportsFormController {
+    input: ComposedInput<(Field<SelectInput<String>>, Field<SelectInput<String>>)>,
+}
+
+impl CompareReportsFormController {
+    pub(crate) fn new(reports: Vec<String>) -> Self {
+        Self {
+            input: ComposedInput::new((
+                Field::new("Baseline report (A)", SelectInput::new(reports.clone())),
+                Field::new("Report to compare (B)", SelectInput::new(reports)),
+            )),
+        }
+    }
+}
+
+impl FormController for CompareReportsFormController {
+    fn on_event(&mut self, event: KeyEvent) -> FormStatus {
+        match self.input.on_event(event) {
+            InputStatus::Done((report_a, report_b)) => FormStatus::Done {
+                task: Task::Strategy(StrategyTask::CompareReports(report_a, report_b)),
+                block: false,
+            },
+            status => status.into(),
+        }
+    }
+
+    fn form_name(&self) -> &'static str {
+        "Compare strategy reports"
+    }
+
+    fn step_view(&mut self, frame: &mut Frame, area: Rect) {
+        self.input.view(frame, area)
+    }
+
+    fn step_name(&self) -> Cow<'_, str> {
+        self.input.step_name().into()
+    }
+
+    fn step_index(&self) -> u8 {
+        self.input.step_index()
+    }
+
+    fn steps_number(&self) -> u8 {
+        2
+    }
+}
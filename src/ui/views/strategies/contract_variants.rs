@@ -0,0 +1,156 @@
+//! Screen and form to run a strategy against variants of its start contracts.
+
+use std::borrow::Cow;
+
+use tuirealm::{
+    event::{Key, KeyEvent, KeyModifiers},
+    tui::prelude::Rect,
+    Frame,
+};
+
+use crate::{
+    backend::{BackendEvent, StrategyTask, Task},
+    ui::form::{
+        parsers::DefaultTextInputParser, ComposedInput, Field, FormController, FormStatus, Input,
+        InputStatus, SelectInput, TextInput,
+    },
+    ui::screen::{
+        utils::impl_builder_no_args, widgets::info::Info, ScreenCommandKey, ScreenController,
+        ScreenFeedback, ScreenToggleKey,
+    },
+    Event,
+};
+
+const COMMAND_KEYS: [ScreenCommandKey; 1] = [ScreenCommandKey::new("q", "Back to Strategy")];
+
+pub(crate) struct ContractVariantsScreenController {
+    info: Info,
+}
+
+impl_builder_no_args!(ContractVariantsScreenController);
+
+impl ContractVariantsScreenController {
+    pub(crate) fn new() -> Self {
+        Self {
+            info: Info::new_fixed(
+                "Running the strategy against each contract variant, please wait.",
+            ),
+        }
+    }
+}
+
+impl ScreenController for ContractVariantsScreenController {
+    fn name(&self) -> &'static str {
+        "Contract variants"
+    }
+
+    fn command_keys(&self) -> &[ScreenCommandKey] {
+        COMMAND_KEYS.as_ref()
+    }
+
+    fn toggle_keys(&self) -> &[ScreenToggleKey] {
+        &[]
+    }
+
+    fn on_event(&mut self, event: &Event) -> ScreenFeedback {
+        match event {
+            Event::Key(KeyEvent {
+                code: Key::Char('q'),
+                modifiers: KeyModifiers::NONE,
+            }) => ScreenFeedback::PreviousScreen,
+            Event::Backend(BackendEvent::TaskCompleted {
+                task: Task::Strategy(StrategyTask::RunContractVariants(..)),
+                execution_result,
+            }) => {
+                self.info = Info::new_from_result(execution_result);
+                ScreenFeedback::Redraw
+            }
+            Event::Backend(BackendEvent::StrategyError { error }) => {
+                self.info = Info::new_error(&format!("Strategy error: {}", error));
+                ScreenFeedback::Redraw
+            }
+            Event::Key(k) => {
+                if self.info.on_event(k) {
+                    ScreenFeedback::Redraw
+                } else {
+                    ScreenFeedback::None
+                }
+            }
+            _ => ScreenFeedback::None,
+        }
+    }
+
+    fn view(&mut self, frame: &mut Frame, area: Rect) {
+        self.info.view(frame, area)
+    }
+}
+
+/// Form to run the strategy once per contract variant with the same
+/// parameters.
+pub(super) struct ContractVariantsFormController {
+    input: ComposedInput<(
+        Field<SelectInput<String>>,
+        Field<TextInput<DefaultTextInputParser<u64>>>,
+        Field<SelectInput<String>>,
+    )>,
+    selected_strategy: String,
+}
+
+impl ContractVariantsFormController {
+    pub(super) fn new(selected_strategy: String) -> Self {
+        ContractVariantsFormController {
+            input: ComposedInput::new((
+                Field::new(
+                    "Execute strategy per block or per second?",
+                    SelectInput::new(vec!["Block".to_string(), "Second".to_string()]),
+                ),
+                Field::new(
+                    "Number of blocks or seconds to run each variant",
+                    TextInput::new("Enter a whole number"),
+                ),
+                Field::new(
+                    "Verify state transition proofs? (Only applies to block mode)",
+                    SelectInput::new(vec!["No".to_string(), "Yes".to_string()]),
+                ),
+            )),
+            selected_strategy,
+        }
+    }
+}
+
+impl FormController for ContractVariantsFormController {
+    fn on_event(&mut self, event: KeyEvent) -> FormStatus {
+        match self.input.on_event(event) {
+            InputStatus::Done((mode, count, verify_proofs)) => FormStatus::Done {
+                task: Task::Strategy(StrategyTask::RunContractVariants(
+                    self.selected_strategy.clone(),
+                    count,
+                    verify_proofs == "Yes",
+                    mode == "Block",
+                )),
+                block: true,
+            },
+            status => status.into(),
+        }
+    }
+
+    fn form_name(&self) -> &'static str {
+        "Run contract variants"
+    }
+
+    fn step_view(&mut self, frame: &mut Frame, area: Rect) {
+        self.input.view(frame, area)
+    }
+
+    fn step_name(&self) -> Cow<'_, str> {
+        self.input.step_name().into()
+    }
+
+    fn step_index(&self) -> u8 {
+        self.input.step_index()
+    }
+
+    fn steps_number(&self) -> u8 {
+        3
+    }
+}
@@ -0,0 +1,37 @@
+//! Credits forecast display of a strategy.
+
+use crate::{backend::strategies::forecast::CreditsForecast, ui::IdentityBalance};
+
+/// Block counts to cycle through for the forecast.
+pub(super) const FORECAST_BLOCKS: [u64; 4] = [10, 50, 100, 500];
+
+pub(super) fn display_forecast(
+    forecast: &CreditsForecast,
+    identity_balance: Option<u64>,
+) -> String {
+    let mut lines = vec![
+        format!("Credits forecast for {} blocks (approx):", forecast.blocks),
+        format!(
+            "    State transition fees: {}",
+            IdentityBalance::from_credits(forecast.fees).dash_str()
+        ),
+        format!(
+            "    Identities funding from wallet: {}",
+            IdentityBalance::from_credits(forecast.funding).dash_str()
+        ),
+    ];
+
+    match identity_balance {
+        Some(balance) if balance < forecast.fees => lines.push(format!(
+            "    WARNING: loaded identity balance ({}) is not enough to cover the fees",
+            IdentityBalance::from_credits(balance).dash_str()
+        )),
+        Some(balance) => lines.push(format!(
+            "    Loaded identity balance: {}",
+            IdentityBalance::from_credits(balance).dash_str()
+        )),
+        None => lines.push("    No identity loaded to compare the balance with".to_owned()),
+    }
+
+    lines.join("\n")
+}
@@ -0,0 +1,178 @@
+//! Funding plan screen and form of a strategy run.
+
+use std::borrow::Cow;
+
+use tuirealm::{
+    event::{Key, KeyEvent, KeyModifiers},
+    tui::prelude::Rect,
+    Frame,
+};
+
+use crate::{
+    backend::{AppState, AppStateUpdate, BackendEvent, StrategyTask, Task},
+    ui::{
+        form::{
+            parsers::DefaultTextInputParser, FormController, FormStatus, Input, InputStatus,
+            TextInput,
+        },
+        screen::{
+            utils::impl_builder, widgets::info::Info, ScreenCommandKey, ScreenController,
+            ScreenFeedback, ScreenToggleKey,
+        },
+    },
+    Event,
+};
+
+const COMMAND_KEYS: [ScreenCommandKey; 2] = [
+    ScreenCommandKey::new("q", "Back to Strategy"),
+    ScreenCommandKey::new("x", "Execute funding plan"),
+];
+
+const COMMAND_KEYS_EXECUTING: [ScreenCommandKey; 2] = [
+    ScreenCommandKey::new("q", "Back to Strategy"),
+    ScreenCommandKey::new("s", "Stop after current step"),
+];
+
+pub(crate) struct FundingPlanScreenController {
+    info: Info,
+    executing: bool,
+}
+
+impl_builder!(FundingPlanScreenController);
+
+impl FundingPlanScreenController {
+    pub(crate) async fn new(app_state: &AppState) -> Self {
+        let info = match app_state.funding_plan.lock().await.as_ref() {
+            Some(plan) => Info::new_fixed(&plan.display()),
+            None => Info::new_fixed("Planning funding, please wait."),
+        };
+
+        Self {
+            info,
+            executing: false,
+        }
+    }
+}
+
+impl ScreenController for FundingPlanScreenController {
+    fn name(&self) -> &'static str {
+        "Funding plan"
+    }
+
+    fn command_keys(&self) -> &[ScreenCommandKey] {
+        if self.executing {
+            COMMAND_KEYS_EXECUTING.as_ref()
+        } else {
+            COMMAND_KEYS.as_ref()
+        }
+    }
+
+    fn toggle_keys(&self) -> &[ScreenToggleKey] {
+        &[]
+    }
+
+    fn on_event(&mut self, event: &Event) -> ScreenFeedback {
+        match event {
+            Event::Key(KeyEvent {
+                code: Key::Char('q'),
+                modifiers: KeyModifiers::NONE,
+            }) => ScreenFeedback::PreviousScreen,
+            Event::Key(KeyEvent {
+                code: Key::Char('x'),
+                modifiers: KeyModifiers::NONE,
+            }) if !self.executing => {
+                self.executing = true;
+                ScreenFeedback::Task {
+                    task: Task::Strategy(StrategyTask::ExecuteFundingStep),
+                    block: true,
+                }
+            }
+            Event::Key(KeyEvent {
+                code: Key::Char('s'),
+                modifiers: KeyModifiers::NONE,
+            }) if self.executing => {
+                self.executing = false;
+                ScreenFeedback::Redraw
+            }
+            Event::Backend(BackendEvent::TaskCompletedStateChange {
+                app_state_update: AppStateUpdate::FundingPlan(funding_plan),
+                ..
+            }) => {
+                let Some(plan) = funding_plan.as_ref() else {
+                    return ScreenFeedback::None;
+                };
+                self.info = Info::new_fixed(&plan.display());
+                // Steps are executed one task at a time so the progress is
+                // shown in between
+                if self.executing && plan.has_next_step() {
+                    ScreenFeedback::Task {
+                        task: Task::Strategy(StrategyTask::ExecuteFundingStep),
+                        block: true,
+                    }
+                } else {
+                    self.executing = false;
+                    ScreenFeedback::Redraw
+                }
+            }
+            Event::Backend(BackendEvent::StrategyError { error }) => {
+                self.executing = false;
+                self.info = Info::new_error(&format!("Funding plan error: {}", &error));
+                ScreenFeedback::Redraw
+            }
+            _ => ScreenFeedback::None,
+        }
+    }
+
+    fn view(&mut self, frame: &mut Frame, area: Rect) {
+        self.info.view(frame, area)
+    }
+}
+
+pub(super) struct FundingPlanFormController {
+    input: TextInput<DefaultTextInputParser<u64>>,
+    selected_strategy: String,
+}
+
+impl FundingPlanFormController {
+    pub(super) fn new(selected_strategy: String) -> Self {
+        FundingPlanFormController {
+            input: TextInput::new("Enter a whole number"),
+            selected_strategy,
+        }
+    }
+}
+
+impl FormController for FundingPlanFormController {
+    fn on_event(&mut self, event: KeyEvent) -> FormStatus {
+        match self.input.on_event(event) {
+            InputStatus::Done(blocks) => FormStatus::Done {
+                task: Task::Strategy(StrategyTask::PlanFunding(
+                    self.selected_strategy.clone(),
+                    blocks,
+                )),
+                block: true,
+            },
+            status => status.into(),
+        }
+    }
+
+    fn form_name(&self) -> &'static str {
+        "Plan strategy funding"
+    }
+
+    fn step_view(&mut self, frame: &mut Frame, area: Rect) {
+        self.input.view(frame, area)
+    }
+
+    fn step_name(&self) -> Cow<'_, str> {
+        "Number of blocks to fund".into()
+    }
+
+    fn step_index(&self) -> u8 {
+        0
+    }
+
+    fn steps_number(&self) -> u8 {
+        1
+    }
+}
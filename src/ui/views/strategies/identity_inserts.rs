@@ -1,5 +1,7 @@
 //! Strategy's identity inserts form.
 
+use std::borrow::Cow;
+
 use tuirealm::{
     event::{Key, KeyEvent, KeyModifiers},
     tui::prelude::Rect,
@@ -236,8 +238,8 @@ impl FormController for StrategyIdentityInsertsFormController {
         self.input.view(frame, area)
     }
 
-    fn step_name(&self) -> &'static str {
-        self.input.step_name()
+    fn step_name(&self) -> Cow<'_, str> {
+        self.input.step_name().into()
     }
 
     fn step_index(&self) -> u8 {
@@ -9,7 +9,7 @@ mod identity_transfer;
 mod identity_update;
 mod identity_withdrawal;
 
-use std::collections::BTreeMap;
+use std::{borrow::Cow, collections::BTreeMap};
 
 use dash_sdk::platform::DataContract;
 use dpp::data_contract::document_type::random_document::{
@@ -303,7 +303,7 @@ fn format_operation_name(op_type: &StrategyOperationType) -> String {
             let op_type = match op.action {
                 DocumentAction::DocumentActionInsertRandom(..) => "InsertRandom",
                 DocumentAction::DocumentActionDelete => "Delete",
-                // DocumentAction::DocumentActionReplace => "Replace",
+                DocumentAction::DocumentActionReplace => "Replace",
                 _ => "Unknown",
             };
             format!(
@@ -335,6 +335,7 @@ fn format_operation_name(op_type: &StrategyOperationType) -> String {
 #[derive(Debug, strum::Display, Clone, strum::EnumIter, Copy)]
 enum OperationType {
     Document,
+    DocumentReplace,
     IdentityTopUp,
     IdentityAddKeys,
     IdentityDisableKeys,
@@ -363,6 +364,7 @@ impl StrategyAddOperationFormController {
     ) -> Self {
         let operation_types = vec![
             "Document".to_string(),
+            "DocumentReplace (documents created earlier in the run)".to_string(),
             "IdentityTopUp".to_string(),
             "IdentityAddKeys".to_string(),
             "IdentityDisableKeys".to_string(),
@@ -389,6 +391,14 @@ impl StrategyAddOperationFormController {
                 self.known_contracts.clone(),
                 self.supporting_contracts.clone(),
                 self.strategy_contract_names.clone(),
+                false,
+            )),
+            OperationType::DocumentReplace => Box::new(StrategyOpDocumentFormController::new(
+                self.strategy_name.clone(),
+                self.known_contracts.clone(),
+                self.supporting_contracts.clone(),
+                self.strategy_contract_names.clone(),
+                true,
             )),
             OperationType::IdentityTopUp => Box::new(StrategyOpIdentityTopUpFormController::new(
                 self.strategy_name.clone(),
@@ -435,6 +445,9 @@ impl FormController for StrategyAddOperationFormController {
                 InputStatus::Done(op_type) => {
                     let operation_type = match op_type.as_str() {
                         "Document" => OperationType::Document,
+                        "DocumentReplace (documents created earlier in the run)" => {
+                            OperationType::DocumentReplace
+                        }
                         "IdentityTopUp" => OperationType::IdentityTopUp,
                         "IdentityAddKeys" => OperationType::IdentityAddKeys,
                         "IdentityDisableKeys" => OperationType::IdentityDisableKeys,
@@ -476,11 +489,11 @@ impl FormController for StrategyAddOperationFormController {
         }
     }
 
-    fn step_name(&self) -> &'static str {
+    fn step_name(&self) -> Cow<'_, str> {
         if let Some(form) = &self.op_specific_form {
             form.step_name()
         } else {
-            "Select operation"
+            "Select operation".into()
         }
     }
 
@@ -581,8 +594,8 @@ impl FormController for StrategyAutomaticDocumentsFormController {
         self.input.view(frame, area)
     }
 
-    fn step_name(&self) -> &'static str {
-        self.input.step_name()
+    fn step_name(&self) -> Cow<'_, str> {
+        self.input.step_name().into()
     }
 
     fn step_index(&self) -> u8 {
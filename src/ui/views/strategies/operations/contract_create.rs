@@ -1,6 +1,6 @@
 //! Data contract create operations form for strategy.
 
-use std::cmp::min;
+use std::{borrow::Cow, cmp::min};
 
 use dpp::data_contract::document_type::v0::random_document_type::{
     FieldMinMaxBounds, FieldTypeWeights, RandomDocumentTypeParameters,
@@ -122,8 +122,8 @@ impl FormController for StrategyOpContractCreateFormController {
         self.input.view(frame, area)
     }
 
-    fn step_name(&self) -> &'static str {
-        self.input.step_name()
+    fn step_name(&self) -> Cow<'_, str> {
+        self.input.step_name().into()
     }
 
     fn step_index(&self) -> u8 {
@@ -1,6 +1,6 @@
 //! Data contract update doc types operation form for strategy.
 
-use std::{cmp::min, collections::BTreeMap};
+use std::{borrow::Cow, cmp::min, collections::BTreeMap};
 
 use dpp::data_contract::{
     document_type::v0::random_document_type::{
@@ -143,8 +143,8 @@ impl FormController for StrategyOpContractUpdateDocTypesFormController {
         self.input.view(frame, area)
     }
 
-    fn step_name(&self) -> &'static str {
-        self.input.step_name()
+    fn step_name(&self) -> Cow<'_, str> {
+        self.input.step_name().into()
     }
 
     fn step_index(&self) -> u8 {
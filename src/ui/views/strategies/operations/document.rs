@@ -1,6 +1,6 @@
 //! Forms for strategy operations related to document operations.
 
-use std::collections::BTreeMap;
+use std::{borrow::Cow, collections::BTreeMap};
 
 use dash_sdk::platform::DataContract;
 use dpp::data_contract::{
@@ -23,6 +23,8 @@ use crate::{
 pub(super) struct StrategyOpDocumentFormController {
     input: SelectInput<String>,
     contract_specific_form: Option<Box<dyn FormController>>,
+    /// Replace documents created earlier in the run instead of inserting new ones
+    replace: bool,
     strategy_name: String,
     known_contracts: BTreeMap<String, DataContract>,
     supporting_contracts: BTreeMap<String, DataContract>,
@@ -34,6 +36,7 @@ impl StrategyOpDocumentFormController {
         known_contracts: BTreeMap<String, DataContract>,
         supporting_contracts: BTreeMap<String, DataContract>,
         strategy_contract_names: StrategyContractNames,
+        replace: bool,
     ) -> Self {
         // Collect known_contracts and supporting_contracts names for the form
         let mut contract_names: Vec<String> = known_contracts.keys().cloned().collect();
@@ -67,6 +70,7 @@ impl StrategyOpDocumentFormController {
         Self {
             input: SelectInput::new(contract_names),
             contract_specific_form: None,
+            replace,
             strategy_name: selected_strategy_name,
             known_contracts,
             supporting_contracts,
@@ -74,11 +78,20 @@ impl StrategyOpDocumentFormController {
     }
 
     fn set_contract_form(&mut self, contract: DataContract, document_types: Vec<String>) {
-        self.contract_specific_form = Some(Box::new(DocumentTypeFormController::new(
-            self.strategy_name.clone(),
-            contract,
-            document_types,
-        )));
+        let form: Box<dyn FormController> = if self.replace {
+            Box::new(DocumentReplaceFormController::new(
+                self.strategy_name.clone(),
+                contract,
+                document_types,
+            ))
+        } else {
+            Box::new(DocumentTypeFormController::new(
+                self.strategy_name.clone(),
+                contract,
+                document_types,
+            ))
+        };
+        self.contract_specific_form = Some(form);
     }
 }
 
@@ -127,11 +140,11 @@ impl FormController for StrategyOpDocumentFormController {
         }
     }
 
-    fn step_name(&self) -> &'static str {
+    fn step_name(&self) -> Cow<'_, str> {
         if let Some(form) = &self.contract_specific_form {
             form.step_name()
         } else {
-            "Select contract"
+            "Select contract".into()
         }
     }
 
@@ -262,8 +275,92 @@ impl FormController for DocumentTypeFormController {
         self.input.view(frame, area)
     }
 
-    fn step_name(&self) -> &'static str {
-        self.input.step_name()
+    fn step_name(&self) -> Cow<'_, str> {
+        self.input.step_name().into()
+    }
+
+    fn step_index(&self) -> u8 {
+        self.input.step_index()
+    }
+
+    fn steps_number(&self) -> u8 {
+        self.input.steps_number()
+    }
+}
+
+pub(super) struct DocumentReplaceFormController {
+    input: ComposedInput<(
+        Field<SelectInput<String>>, // Document types
+        Field<SelectInput<u16>>,    // Times per block
+        Field<SelectInput<f64>>,    // Chance per block
+    )>,
+    selected_strategy_name: String,
+    selected_contract: DataContract,
+}
+
+impl DocumentReplaceFormController {
+    pub(super) fn new(
+        selected_strategy_name: String,
+        selected_contract: DataContract,
+        document_types: Vec<String>,
+    ) -> Self {
+        Self {
+            input: ComposedInput::new((
+                Field::new("Select Document Type", SelectInput::new(document_types)),
+                Field::new(
+                    "Replaces per block",
+                    SelectInput::new(vec![1, 2, 5, 10, 20, 24]),
+                ),
+                Field::new(
+                    "Chance per block",
+                    SelectInput::new(vec![1.0, 0.75, 0.5, 0.25, 0.1]),
+                ),
+            )),
+            selected_strategy_name,
+            selected_contract,
+        }
+    }
+}
+
+impl FormController for DocumentReplaceFormController {
+    fn on_event(&mut self, event: KeyEvent) -> FormStatus {
+        match self.input.on_event(event) {
+            InputStatus::Done((document_type, times_per_block, chance_per_block)) => {
+                FormStatus::Done {
+                    task: Task::Strategy(StrategyTask::AddOperation {
+                        strategy_name: self.selected_strategy_name.clone(),
+                        operation: Operation {
+                            op_type: OperationType::Document(DocumentOp {
+                                contract: self.selected_contract.clone(),
+                                document_type: self
+                                    .selected_contract
+                                    .document_type_cloned_for_name(&document_type)
+                                    .expect("Expected the document type to be there"),
+                                action: DocumentAction::DocumentActionReplace,
+                            }),
+                            frequency: Frequency {
+                                times_per_block_range: times_per_block..times_per_block + 1,
+                                chance_per_block: Some(chance_per_block),
+                            },
+                        },
+                    }),
+                    block: false,
+                }
+            }
+            status => status.into(),
+        }
+    }
+
+    fn form_name(&self) -> &'static str {
+        "Document replace churn"
+    }
+
+    fn step_view(&mut self, frame: &mut Frame, area: Rect) {
+        self.input.view(frame, area)
+    }
+
+    fn step_name(&self) -> Cow<'_, str> {
+        self.input.step_name().into()
     }
 
     fn step_index(&self) -> u8 {
@@ -1,5 +1,7 @@
 //! Identity top up form for strategy.
 
+use std::borrow::Cow;
+
 use strategy_tests::{
     frequency::Frequency,
     operations::{Operation, OperationType},
@@ -62,8 +64,8 @@ impl FormController for StrategyOpIdentityTopUpFormController {
         self.input.view(frame, area)
     }
 
-    fn step_name(&self) -> &'static str {
-        self.input.step_name()
+    fn step_name(&self) -> Cow<'_, str> {
+        self.input.step_name().into()
     }
 
     fn step_index(&self) -> u8 {
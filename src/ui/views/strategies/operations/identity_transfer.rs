@@ -1,5 +1,7 @@
 //! Identity transfer operations form for strategy.
 
+use std::borrow::Cow;
+
 use strategy_tests::{
     frequency::Frequency,
     operations::{Operation, OperationType},
@@ -53,8 +55,8 @@ impl FormController for StrategyOpIdentityTransferFormController {
         self.input.view(frame, area)
     }
 
-    fn step_name(&self) -> &'static str {
-        "Chance per block"
+    fn step_name(&self) -> Cow<'_, str> {
+        "Chance per block".into()
     }
 
     fn step_index(&self) -> u8 {
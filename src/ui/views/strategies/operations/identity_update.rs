@@ -1,5 +1,7 @@
 //! Forms for strategy operations related to identity updates.
 
+use std::borrow::Cow;
+
 use strategy_tests::{
     frequency::Frequency,
     operations::{IdentityUpdateOp, Operation, OperationType},
@@ -86,8 +88,8 @@ impl FormController for StrategyOpIdentityUpdateFormController {
         self.input.view(frame, area)
     }
 
-    fn step_name(&self) -> &'static str {
-        self.input.step_name()
+    fn step_name(&self) -> Cow<'_, str> {
+        self.input.step_name().into()
     }
 
     fn step_index(&self) -> u8 {
@@ -1,5 +1,7 @@
 //! Run strategy screen and forms.
 
+use std::borrow::Cow;
+
 use tuirealm::{
     event::{Key, KeyEvent, KeyModifiers},
     tui::prelude::Rect,
@@ -19,7 +21,7 @@ use crate::{
     Event,
 };
 
-use super::selected_strategy::SelectedStrategyScreenController;
+use super::{selected_strategy::SelectedStrategyScreenController, timeline::render_timeline};
 
 const COMMAND_KEYS: [ScreenCommandKey; 2] = [
     ScreenCommandKey::new("q", "Back to Strategy"),
@@ -30,6 +32,9 @@ pub(crate) struct RunStrategyScreenController {
     info: Info,
     strategy_running: bool,
     selected_strategy: Option<String>,
+    toggle_keys: [ScreenToggleKey; 1],
+    /// Summary and block timeline of the last run
+    run_texts: Option<(String, String)>,
 }
 
 impl_builder!(RunStrategyScreenController);
@@ -53,6 +58,18 @@ impl RunStrategyScreenController {
             info,
             strategy_running,
             selected_strategy,
+            toggle_keys: [ScreenToggleKey::new("t", "block timeline")],
+            run_texts: None,
+        }
+    }
+
+    fn show_run_texts(&mut self) {
+        if let Some((summary, timeline)) = &self.run_texts {
+            self.info = if self.toggle_keys[0].toggle {
+                Info::new_scrollable(timeline)
+            } else {
+                Info::new_fixed(summary)
+            };
         }
     }
 }
@@ -67,7 +84,7 @@ impl ScreenController for RunStrategyScreenController {
     }
 
     fn toggle_keys(&self) -> &[ScreenToggleKey] {
-        &[]
+        self.toggle_keys.as_ref()
     }
 
     fn on_event(&mut self, event: &Event) -> ScreenFeedback {
@@ -87,13 +104,21 @@ impl ScreenController for RunStrategyScreenController {
                         .expect("No selected strategy available"),
                 )))
             }
+            Event::Key(KeyEvent {
+                code: Key::Char('t'),
+                modifiers: KeyModifiers::NONE,
+            }) => {
+                self.toggle_keys[0].toggle = !self.toggle_keys[0].toggle;
+                self.show_run_texts();
+                ScreenFeedback::Redraw
+            }
             Event::Backend(BackendEvent::StrategyCompleted {
                 strategy_name,
                 result,
             }) => {
                 self.strategy_running = false;
 
-                let display_text = match result {
+                let (display_text, timeline) = match result {
                     StrategyCompletionResult::Success {
                         block_mode,
                         final_block_height,
@@ -107,15 +132,34 @@ impl ScreenController for RunStrategyScreenController {
                         init_time,
                         dash_spent_identity,
                         dash_spent_wallet,
+                        resource_usage,
+                        block_transition_counts,
+                        block_inclusion_counts,
+                        block_deferred_counts,
+                        phases,
                     } => {
                         let mode = match block_mode {
                             true => String::from("block"),
                             false => String::from("time"),
                         };
-                        format!(
+                        let resource_usage = resource_usage
+                            .as_ref()
+                            .map(|usage| {
+                                format!("\n\nExplorer resource usage:\n{}", usage.display())
+                            })
+                            .unwrap_or_default();
+                        let phases = if phases.is_empty() {
+                            String::new()
+                        } else {
+                            format!(
+                                "\n{}",
+                                phases.display(if *block_mode { "blocks" } else { "seconds" })
+                            )
+                        };
+                        let summary = format!(
                             "Strategy '{}' completed:\n\nMode: {}\nState transitions attempted: {}\nState \
                              transitions succeeded: {}\nNumber of blocks (or loops): {}\nRun time: \
-                             {}s\nInitialization time: {}\nAttempted rate (approx): {} tx/s\nSuccessful rate: {} tx/s\nSuccess percentage: {}%\nDash spent (Identity): {}\nDash spent (Wallet): {}",
+                             {}s\nInitialization time: {}\nAttempted rate (approx): {} tx/s\nSuccessful rate: {} tx/s\nSuccess percentage: {}%\nDash spent (Identity): {}\nDash spent (Wallet): {}{}{}",
                             strategy_name,
                             mode,
                             transition_count,
@@ -128,28 +172,57 @@ impl ScreenController for RunStrategyScreenController {
                             success_percent,
                             dash_spent_identity,
                             dash_spent_wallet,
-                        )
+                            phases,
+                            resource_usage,
+                        );
+                        let timeline = render_timeline(
+                            *final_block_height,
+                            block_transition_counts,
+                            block_inclusion_counts,
+                            block_deferred_counts,
+                        );
+                        (summary, timeline)
                     }
                     StrategyCompletionResult::PartiallyCompleted {
                         reached_block_height,
                         reason,
+                        block_transition_counts,
+                        block_inclusion_counts,
+                        block_deferred_counts,
+                        ..
                     } => {
-                        format!(
+                        let summary = format!(
                             "Strategy '{}' failed to complete. Reached block height {}. Reason: {}",
                             strategy_name, reached_block_height, reason
-                        )
+                        );
+                        let timeline = render_timeline(
+                            *reached_block_height,
+                            block_transition_counts,
+                            block_inclusion_counts,
+                            block_deferred_counts,
+                        );
+                        (summary, timeline)
                     }
                 };
 
-                self.info = Info::new_fixed(&display_text);
+                self.run_texts = Some((display_text, timeline));
+                self.show_run_texts();
                 ScreenFeedback::Redraw
             }
             Event::Backend(BackendEvent::StrategyError { error }) => {
                 self.strategy_running = false;
 
+                self.run_texts = None;
                 self.info = Info::new_error(&format!("Strategy error: {}", &error));
                 ScreenFeedback::Redraw
             }
+            Event::Key(k) => {
+                if self.info.on_event(k) {
+                    ScreenFeedback::Redraw
+                } else {
+                    ScreenFeedback::None
+                }
+            }
             _ => ScreenFeedback::None,
         }
     }
@@ -167,6 +240,7 @@ pub(super) struct RunStrategyFormController {
         Field<SelectInput<String>>,
         Field<TextInput<DefaultTextInputParser<u64>>>,
         Field<SelectInput<String>>,
+        Field<TextInput<DefaultTextInputParser<u16>>>,
         Field<SelectInput<String>>,
     )>,
     selected_strategy: String,
@@ -188,6 +262,10 @@ impl RunStrategyFormController {
                     "Verify state transition proofs? (Only applies to block mode)",
                     SelectInput::new(vec!["No".to_string(), "Yes".to_string()]),
                 ),
+                Field::new(
+                    "Target transitions per observed block, 0 to not pace (Only applies to block mode)",
+                    TextInput::new("Enter a whole number"),
+                ),
                 Field::new(
                     "Confirm you would like to run the strategy",
                     SelectInput::new(vec!["No".to_string(), "Yes".to_string()]),
@@ -201,7 +279,8 @@ impl RunStrategyFormController {
 impl FormController for RunStrategyFormController {
     fn on_event(&mut self, event: KeyEvent) -> FormStatus {
         match self.input.on_event(event) {
-            InputStatus::Done((mode, num_blocks, verify_proofs, confirm)) => {
+            InputStatus::Done((mode, num_blocks, verify_proofs, per_block, confirm)) => {
+                let transitions_per_block = (per_block > 0).then_some(per_block);
                 if confirm == "Yes" {
                     if verify_proofs == "Yes" {
                         if mode == "Block" {
@@ -212,6 +291,7 @@ impl FormController for RunStrategyFormController {
                                     num_blocks,
                                     true,
                                     true,
+                                    transitions_per_block,
                                 )),
                                 block: true,
                             }
@@ -223,6 +303,7 @@ impl FormController for RunStrategyFormController {
                                     num_blocks,
                                     true,
                                     false,
+                                    transitions_per_block,
                                 )),
                                 block: true,
                             }
@@ -236,6 +317,7 @@ impl FormController for RunStrategyFormController {
                                     num_blocks,
                                     false,
                                     true,
+                                    transitions_per_block,
                                 )),
                                 block: true,
                             }
@@ -247,6 +329,7 @@ impl FormController for RunStrategyFormController {
                                     num_blocks,
                                     false,
                                     false,
+                                    transitions_per_block,
                                 )),
                                 block: true,
                             }
@@ -270,8 +353,72 @@ impl FormController for RunStrategyFormController {
         self.input.view(frame, area)
     }
 
-    fn step_name(&self) -> &'static str {
-        self.input.step_name()
+    fn step_name(&self) -> Cow<'_, str> {
+        self.input.step_name().into()
+    }
+
+    fn step_index(&self) -> u8 {
+        self.input.step_index()
+    }
+
+    fn steps_number(&self) -> u8 {
+        5
+    }
+}
+
+/// Form to resume the halted run of the strategy, its mode and remaining
+/// blocks or seconds are those of the halted run.
+pub(super) struct ResumeStrategyFormController {
+    input: ComposedInput<(
+        Field<SelectInput<String>>,
+        Field<TextInput<DefaultTextInputParser<u16>>>,
+    )>,
+    selected_strategy: String,
+}
+
+impl ResumeStrategyFormController {
+    pub(super) fn new(selected_strategy: String) -> Self {
+        ResumeStrategyFormController {
+            input: ComposedInput::new((
+                Field::new(
+                    "Verify state transition proofs? (Only applies to block mode)",
+                    SelectInput::new(vec!["No".to_string(), "Yes".to_string()]),
+                ),
+                Field::new(
+                    "Target transitions per observed block, 0 to not pace (Only applies to block mode)",
+                    TextInput::new("Enter a whole number"),
+                ),
+            )),
+            selected_strategy,
+        }
+    }
+}
+
+impl FormController for ResumeStrategyFormController {
+    fn on_event(&mut self, event: KeyEvent) -> FormStatus {
+        match self.input.on_event(event) {
+            InputStatus::Done((verify_proofs, per_block)) => FormStatus::Done {
+                task: Task::Strategy(StrategyTask::ResumeStrategy(
+                    self.selected_strategy.clone(),
+                    verify_proofs == "Yes",
+                    (per_block > 0).then_some(per_block),
+                )),
+                block: true,
+            },
+            status => status.into(),
+        }
+    }
+
+    fn form_name(&self) -> &'static str {
+        "Resume strategy"
+    }
+
+    fn step_view(&mut self, frame: &mut Frame, area: Rect) {
+        self.input.view(frame, area)
+    }
+
+    fn step_name(&self) -> Cow<'_, str> {
+        self.input.step_name().into()
     }
 
     fn step_index(&self) -> u8 {
@@ -279,6 +426,6 @@ impl FormController for RunStrategyFormController {
     }
 
     fn steps_number(&self) -> u8 {
-        4
+        2
     }
 }
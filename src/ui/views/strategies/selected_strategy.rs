@@ -1,9 +1,10 @@
 //! Screens and forms related to strategies manipulation.
 
-use std::collections::BTreeMap;
+use std::{borrow::Cow, collections::BTreeMap};
 
 use dpp::{
-    data_contract::accessors::v0::DataContractV0Getters, platform_value::string_encoding::Encoding,
+    data_contract::accessors::v0::DataContractV0Getters, identity::accessors::IdentityGettersV0,
+    platform_value::string_encoding::Encoding,
 };
 use strategy_tests::{
     operations::{
@@ -19,13 +20,24 @@ use tuirealm::{
 };
 
 use super::{
-    identity_inserts::IdentityInsertsScreenController, operations::OperationsScreenController,
-    run_strategy::RunStrategyFormController, run_strategy::RunStrategyScreenController,
+    contract_variants::{ContractVariantsFormController, ContractVariantsScreenController},
+    forecast::{display_forecast, FORECAST_BLOCKS},
+    funding_plan::{FundingPlanFormController, FundingPlanScreenController},
+    identity_inserts::IdentityInsertsScreenController,
+    operations::OperationsScreenController,
+    run_strategy::{
+        ResumeStrategyFormController, RunStrategyFormController, RunStrategyScreenController,
+    },
     start_contracts::ContractsWithUpdatesScreenController,
     start_identities::StartIdentitiesScreenController,
 };
 use crate::{
-    backend::{AppState, AppStateUpdate, BackendEvent},
+    backend::{
+        strategies::{
+            forecast::forecast_strategy_credits, padding::display_target, phases::RunPhases,
+        },
+        AppState, AppStateUpdate, BackendEvent,
+    },
     ui::screen::{
         utils::impl_builder, widgets::info::Info, ScreenCommandKey, ScreenController,
         ScreenFeedback, ScreenToggleKey,
@@ -36,18 +48,25 @@ use crate::{
 use crate::{
     backend::{StrategyTask, Task},
     ui::form::{
-        parsers::DefaultTextInputParser, FormController, FormStatus, Input, InputStatus, TextInput,
+        parsers::DefaultTextInputParser, ComposedInput, Field, FormController, FormStatus, Input,
+        InputStatus, TextInput,
     },
 };
 
-const COMMAND_KEYS: [ScreenCommandKey; 7] = [
+const COMMAND_KEYS: [ScreenCommandKey; 13] = [
     ScreenCommandKey::new("q", "Back to Strategies"),
     ScreenCommandKey::new("r", "Run strategy"),
+    ScreenCommandKey::new("u", "Resume halted run"),
+    ScreenCommandKey::new("v", "Run contract variants"),
+    ScreenCommandKey::new("p", "Plan actors funding"),
     ScreenCommandKey::new("l", "Clone this strategy"),
     ScreenCommandKey::new("c", "Start contracts"),
     ScreenCommandKey::new("i", "Identity inserts"),
     ScreenCommandKey::new("o", "Operations"),
     ScreenCommandKey::new("s", "Start identities"),
+    ScreenCommandKey::new("f", "Cycle forecast blocks"),
+    ScreenCommandKey::new("w", "Warm-up and cool-down"),
+    ScreenCommandKey::new("d", "Transition size padding"),
 ];
 
 const COMMAND_KEYS_NO_SELECTION: [ScreenCommandKey; 1] =
@@ -57,6 +76,13 @@ pub struct SelectedStrategyScreenController {
     info: Info,
     available_strategies: Vec<String>,
     selected_strategy: Option<String>,
+    strategy_text: String,
+    strategy: Option<Strategy>,
+    identity_balance: Option<u64>,
+    forecast_blocks_index: usize,
+    phases: RunPhases,
+    /// Target size of document transitions, 0 if they aren't padded
+    padding: u32,
 }
 
 impl_builder!(SelectedStrategyScreenController);
@@ -65,29 +91,81 @@ impl SelectedStrategyScreenController {
     pub(crate) async fn new(app_state: &AppState) -> Self {
         let available_strategies_lock = app_state.available_strategies.lock().await;
         let selected_strategy_lock = app_state.selected_strategy.lock().await;
+        let identity_balance = app_state
+            .loaded_identity
+            .lock()
+            .await
+            .as_ref()
+            .map(|identity| identity.balance());
+
+        let (phases, padding) = match selected_strategy_lock.as_ref() {
+            Some(name) => (
+                app_state.strategy_phases.lock().await.get(name),
+                app_state.strategy_padding.lock().await.get(name),
+            ),
+            None => (RunPhases::default(), 0),
+        };
 
-        let info = if let Some(name) = selected_strategy_lock.as_ref() {
+        let (strategy_text, strategy) = if let Some(name) = selected_strategy_lock.as_ref() {
             let strategy = available_strategies_lock
                 .get(name.as_str())
                 .expect("inconsistent data");
             let contract_names_lock = app_state.available_strategies_contract_names.lock().await;
 
-            Info::new_fixed(&display_strategy(
-                &name,
-                strategy,
-                contract_names_lock
-                    .get(name.as_str())
-                    .expect("inconsistent data"),
-            ))
+            (
+                display_strategy(
+                    &name,
+                    strategy,
+                    contract_names_lock
+                        .get(name.as_str())
+                        .expect("inconsistent data"),
+                ),
+                Some(strategy.clone()),
+            )
         } else {
-            Info::new_fixed("No strategy selected. Go back.")
+            ("No strategy selected. Go back.".to_owned(), None)
         };
 
-        SelectedStrategyScreenController {
-            info,
+        let mut controller = SelectedStrategyScreenController {
+            info: Info::new_fixed(""),
             available_strategies: available_strategies_lock.keys().cloned().collect(),
             selected_strategy: None,
-        }
+            strategy_text,
+            strategy,
+            identity_balance,
+            forecast_blocks_index: 0,
+            phases,
+            padding,
+        };
+        controller.update_info();
+        controller
+    }
+
+    fn update_info(&mut self) {
+        let text = if let Some(strategy) = &self.strategy {
+            let forecast =
+                forecast_strategy_credits(strategy, FORECAST_BLOCKS[self.forecast_blocks_index]);
+            let phases = if self.phases.is_empty() {
+                String::new()
+            } else {
+                format!("    {}\n\n", self.phases.display("blocks or seconds"))
+            };
+            let padding = if self.padding == 0 {
+                String::new()
+            } else {
+                format!("    {}\n\n", display_target(self.padding))
+            };
+            format!(
+                "{}{}{}\n{}",
+                self.strategy_text,
+                phases,
+                padding,
+                display_forecast(&forecast, self.identity_balance)
+            )
+        } else {
+            self.strategy_text.clone()
+        };
+        self.info = Info::new_fixed(&text);
     }
 }
 
@@ -139,10 +217,78 @@ impl ScreenController for SelectedStrategyScreenController {
                 )),
                 screen: RunStrategyScreenController::builder(),
             },
+            Event::Key(KeyEvent {
+                code: Key::Char('u'),
+                modifiers: KeyModifiers::NONE,
+            }) => match &self.selected_strategy {
+                Some(strategy_name) => ScreenFeedback::FormThenNextScreen {
+                    form: Box::new(ResumeStrategyFormController::new(strategy_name.clone())),
+                    screen: RunStrategyScreenController::builder(),
+                },
+                None => ScreenFeedback::None,
+            },
+            Event::Key(KeyEvent {
+                code: Key::Char('v'),
+                modifiers: KeyModifiers::NONE,
+            }) => match &self.selected_strategy {
+                Some(strategy_name) => ScreenFeedback::FormThenNextScreen {
+                    form: Box::new(ContractVariantsFormController::new(strategy_name.clone())),
+                    screen: ContractVariantsScreenController::builder(),
+                },
+                None => ScreenFeedback::None,
+            },
+            Event::Key(KeyEvent {
+                code: Key::Char('p'),
+                modifiers: KeyModifiers::NONE,
+            }) => match &self.selected_strategy {
+                Some(strategy_name) => ScreenFeedback::FormThenNextScreen {
+                    form: Box::new(FundingPlanFormController::new(strategy_name.clone())),
+                    screen: FundingPlanScreenController::builder(),
+                },
+                None => ScreenFeedback::None,
+            },
             Event::Key(KeyEvent {
                 code: Key::Char('l'),
                 modifiers: KeyModifiers::NONE,
             }) => ScreenFeedback::Form(Box::new(CloneStrategyFormController::new())),
+            Event::Key(KeyEvent {
+                code: Key::Char('w'),
+                modifiers: KeyModifiers::NONE,
+            }) => match &self.selected_strategy {
+                Some(strategy_name) => ScreenFeedback::Form(Box::new(
+                    RunPhasesFormController::new(strategy_name.clone(), self.phases),
+                )),
+                None => ScreenFeedback::None,
+            },
+            Event::Key(KeyEvent {
+                code: Key::Char('d'),
+                modifiers: KeyModifiers::NONE,
+            }) => match &self.selected_strategy {
+                Some(strategy_name) => ScreenFeedback::Form(Box::new(
+                    TransitionPaddingFormController::new(strategy_name.clone(), self.padding),
+                )),
+                None => ScreenFeedback::None,
+            },
+            Event::Key(KeyEvent {
+                code: Key::Char('f'),
+                modifiers: KeyModifiers::NONE,
+            }) => {
+                self.forecast_blocks_index =
+                    (self.forecast_blocks_index + 1) % FORECAST_BLOCKS.len();
+                self.update_info();
+                ScreenFeedback::Redraw
+            }
+            Event::Backend(
+                BackendEvent::AppStateUpdated(AppStateUpdate::LoadedIdentity(identity))
+                | BackendEvent::TaskCompletedStateChange {
+                    app_state_update: AppStateUpdate::LoadedIdentity(identity),
+                    ..
+                },
+            ) => {
+                self.identity_balance = Some(identity.balance());
+                self.update_info();
+                ScreenFeedback::Redraw
+            }
             Event::Backend(
                 BackendEvent::AppStateUpdated(AppStateUpdate::SelectedStrategy(
                     strategy_name,
@@ -155,12 +301,10 @@ impl ScreenController for SelectedStrategyScreenController {
                     ..
                 },
             ) => {
-                self.info = Info::new_fixed(&display_strategy(
-                    &strategy_name,
-                    &strategy,
-                    &contract_names,
-                ));
+                self.strategy_text = display_strategy(&strategy_name, &strategy, &contract_names);
+                self.strategy = Some((**strategy).clone());
                 self.selected_strategy = Some(strategy_name.clone());
+                self.update_info();
                 ScreenFeedback::Redraw
             }
             Event::Backend(
@@ -173,6 +317,34 @@ impl ScreenController for SelectedStrategyScreenController {
                 self.available_strategies = strategies.keys().cloned().collect();
                 ScreenFeedback::Redraw
             }
+            Event::Backend(BackendEvent::TaskCompleted {
+                task: Task::Strategy(StrategyTask::SetRunPhases(strategy_name, phases)),
+                execution_result,
+            }) => {
+                if execution_result.is_ok()
+                    && self.selected_strategy.as_ref() == Some(strategy_name)
+                {
+                    self.phases = *phases;
+                    self.update_info();
+                } else if let Err(e) = execution_result {
+                    self.info = Info::new_error(&format!("Error: {}", e));
+                }
+                ScreenFeedback::Redraw
+            }
+            Event::Backend(BackendEvent::TaskCompleted {
+                task: Task::Strategy(StrategyTask::SetTransitionPadding(strategy_name, padding)),
+                execution_result,
+            }) => {
+                if execution_result.is_ok()
+                    && self.selected_strategy.as_ref() == Some(strategy_name)
+                {
+                    self.padding = *padding;
+                    self.update_info();
+                } else if let Err(e) = execution_result {
+                    self.info = Info::new_error(&format!("Error: {}", e));
+                }
+                ScreenFeedback::Redraw
+            }
             Event::Backend(BackendEvent::StrategyError { error }) => {
                 self.info = Info::new_error(&format!("Error: {}", &error));
                 ScreenFeedback::Redraw
@@ -260,7 +432,7 @@ fn display_strategy(
                             "InsertRandom".to_string()
                         }
                         DocumentAction::DocumentActionDelete => "Delete".to_string(),
-                        // DocumentAction::DocumentActionReplace => "Replace".to_string(),
+                        DocumentAction::DocumentActionReplace => "Replace".to_string(),
                         _ => panic!("invalid document action selected"),
                     };
                     format!(
@@ -368,8 +540,120 @@ impl FormController for CloneStrategyFormController {
         self.input.view(frame, area)
     }
 
-    fn step_name(&self) -> &'static str {
-        "Strategy name"
+    fn step_name(&self) -> Cow<'_, str> {
+        "Strategy name".into()
+    }
+
+    fn step_index(&self) -> u8 {
+        0
+    }
+
+    fn steps_number(&self) -> u8 {
+        1
+    }
+}
+
+/// Form of the warm-up and cool-down phases left out of run statistics.
+pub(crate) struct RunPhasesFormController {
+    input: ComposedInput<(
+        Field<TextInput<DefaultTextInputParser<u64>>>,
+        Field<TextInput<DefaultTextInputParser<u64>>>,
+    )>,
+    strategy_name: String,
+}
+
+impl RunPhasesFormController {
+    pub(crate) fn new(strategy_name: String, phases: RunPhases) -> Self {
+        RunPhasesFormController {
+            input: ComposedInput::new((
+                Field::new(
+                    "Warm-up blocks or seconds",
+                    TextInput::new_init_value("Enter a whole number", phases.warm_up),
+                ),
+                Field::new(
+                    "Cool-down blocks or seconds",
+                    TextInput::new_init_value("Enter a whole number", phases.cool_down),
+                ),
+            )),
+            strategy_name,
+        }
+    }
+}
+
+impl FormController for RunPhasesFormController {
+    fn on_event(&mut self, event: KeyEvent) -> FormStatus {
+        match self.input.on_event(event) {
+            InputStatus::Done((warm_up, cool_down)) => FormStatus::Done {
+                task: Task::Strategy(StrategyTask::SetRunPhases(
+                    self.strategy_name.clone(),
+                    RunPhases { warm_up, cool_down },
+                )),
+                block: false,
+            },
+            status => status.into(),
+        }
+    }
+
+    fn form_name(&self) -> &'static str {
+        "Warm-up and cool-down"
+    }
+
+    fn step_view(&mut self, frame: &mut Frame, area: Rect) {
+        self.input.view(frame, area)
+    }
+
+    fn step_name(&self) -> Cow<'_, str> {
+        self.input.step_name().into()
+    }
+
+    fn step_index(&self) -> u8 {
+        self.input.step_index()
+    }
+
+    fn steps_number(&self) -> u8 {
+        2
+    }
+}
+
+/// Form of the target size document transitions are padded to.
+pub(crate) struct TransitionPaddingFormController {
+    input: TextInput<DefaultTextInputParser<u32>>,
+    strategy_name: String,
+}
+
+impl TransitionPaddingFormController {
+    pub(crate) fn new(strategy_name: String, padding: u32) -> Self {
+        TransitionPaddingFormController {
+            input: TextInput::new_init_value("Bytes, 0 to not pad", padding),
+            strategy_name,
+        }
+    }
+}
+
+impl FormController for TransitionPaddingFormController {
+    fn on_event(&mut self, event: KeyEvent) -> FormStatus {
+        match self.input.on_event(event) {
+            InputStatus::Done(target) => FormStatus::Done {
+                task: Task::Strategy(StrategyTask::SetTransitionPadding(
+                    self.strategy_name.clone(),
+                    target,
+                )),
+                block: false,
+            },
+            status => status.into(),
+        }
+    }
+
+    fn form_name(&self) -> &'static str {
+        "Transition size padding"
+    }
+
+    fn step_view(&mut self, frame: &mut Frame, area: Rect) {
+        self.input.view(frame, area)
+    }
+
+    fn step_name(&self) -> Cow<'_, str> {
+        "Target serialized size".into()
     }
 
     fn step_index(&self) -> u8 {
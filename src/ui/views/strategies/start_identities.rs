@@ -1,5 +1,7 @@
 //! Start identities screen and forms.
 
+use std::borrow::Cow;
+
 use tuirealm::{
     event::{Key, KeyEvent, KeyModifiers},
     tui::prelude::Rect,
@@ -250,8 +252,8 @@ impl FormController for StrategyStartIdentitiesFormController {
         self.input.view(frame, area)
     }
 
-    fn step_name(&self) -> &'static str {
-        self.input.step_name()
+    fn step_name(&self) -> Cow<'_, str> {
+        self.input.step_name().into()
     }
 
     fn step_index(&self) -> u8 {
@@ -299,8 +301,8 @@ impl FormController for StrategyStartIdentitiesBalanceFormController {
         self.input.view(frame, area)
     }
 
-    fn step_name(&self) -> &'static str {
-        ""
+    fn step_name(&self) -> Cow<'_, str> {
+        "".into()
     }
 
     fn step_index(&self) -> u8 {
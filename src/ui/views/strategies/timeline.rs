@@ -0,0 +1,98 @@
+//! Strategy run block timeline.
+//! Each block of the run gets a row of horizontal bars, the transitions
+//! submitted into it, the transitions Platform included in it and those the
+//! pacer deferred to later blocks, so gaps and backlogs stand out.
+
+use std::collections::BTreeMap;
+
+/// Characters of the longest bar.
+const BAR_WIDTH: usize = 20;
+
+fn bar(count: u64, max: u64) -> String {
+    if count == 0 {
+        return format!("{:<width$}", "·", width = BAR_WIDTH + 6);
+    }
+    let length = ((count * BAR_WIDTH as u64).div_ceil(max)) as usize;
+    format!(
+        "{:<width$}",
+        format!("{} {}", "█".repeat(length), count),
+        width = BAR_WIDTH + 6
+    )
+}
+
+/// Renders the timeline of blocks from the first one transitions were
+/// submitted to up to the final one.
+pub(super) fn render_timeline(
+    final_block_height: u64,
+    submitted: &BTreeMap<u64, u64>,
+    included: &BTreeMap<u64, u64>,
+    deferred: &BTreeMap<u64, u64>,
+) -> String {
+    let counts = [submitted, included, deferred];
+    let Some(first_height) = counts
+        .iter()
+        .filter_map(|counts| counts.keys().next())
+        .min()
+        .copied()
+    else {
+        return "No state transitions were submitted during the run.".to_owned();
+    };
+    let last_height = counts
+        .iter()
+        .filter_map(|counts| counts.keys().next_back())
+        .max()
+        .copied()
+        .unwrap_or(first_height)
+        .max(final_block_height);
+    let max = counts
+        .iter()
+        .flat_map(|counts| counts.values())
+        .max()
+        .copied()
+        .unwrap_or(1)
+        .max(1);
+
+    let mut lines = vec![
+        format!(
+            "Blocks {} to {}, gaps are blocks without included transitions\n",
+            first_height, last_height
+        ),
+        format!(
+            "{:<10}{:<width$}{:<width$}{}",
+            "Height",
+            "Submitted",
+            "Included",
+            "Deferred",
+            width = BAR_WIDTH + 6
+        ),
+    ];
+    let mut gaps = 0;
+    for height in first_height..=last_height {
+        let included_count = included.get(&height).copied().unwrap_or_default();
+        if included_count == 0 {
+            gaps += 1;
+        }
+        lines.push(
+            format!(
+                "{:<10}{}{}{}",
+                height,
+                bar(submitted.get(&height).copied().unwrap_or_default(), max),
+                bar(included_count, max),
+                bar(deferred.get(&height).copied().unwrap_or_default(), max),
+            )
+            .trim_end()
+            .to_owned(),
+        );
+    }
+    lines.push(format!(
+        "\n{} of {} blocks without included transitions",
+        gaps,
+        last_height - first_height + 1
+    ));
+    if included.is_empty() {
+        lines.push(
+            "Inclusion is only known when waiting for transition results in block mode".to_owned(),
+        );
+    }
+    lines.join("\n")
+}
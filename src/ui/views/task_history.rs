@@ -0,0 +1,313 @@
+//! Screen of tasks submitted during the session and forms to rerun them.
+
+use std::{
+    borrow::Cow,
+    fmt::{self, Display},
+};
+
+use futures::FutureExt;
+use tuirealm::{
+    event::{Key, KeyEvent, KeyModifiers},
+    tui::prelude::{Constraint, Direction, Layout, Rect},
+    Frame,
+};
+
+use crate::{
+    backend::{
+        task_history::{apply_template, template, TaskHistory, TaskHistoryEntry},
+        AppState, BackendEvent, Task,
+    },
+    ui::{
+        form::{
+            parsers::DefaultTextInputParser, FormController, FormStatus, Input, InputStatus,
+            SelectInput, TextInput,
+        },
+        screen::{
+            utils::impl_builder, widgets::info::Info, ScreenCommandKey, ScreenController,
+            ScreenFeedback, ScreenToggleKey,
+        },
+    },
+    Event,
+};
+
+const COMMAND_KEYS: [ScreenCommandKey; 6] = [
+    ScreenCommandKey::new("q", "Back"),
+    ScreenCommandKey::new("/", "Filter tasks"),
+    ScreenCommandKey::new("↓ / C-n", "Next task"),
+    ScreenCommandKey::new("↑ / C-p", "Prev task"),
+    ScreenCommandKey::new("Enter", "Edit and rerun"),
+    ScreenCommandKey::new("r", "Rerun as is"),
+];
+
+/// History entry wrapper for display in a list
+#[derive(Clone)]
+struct TaskHistoryEntryItem(TaskHistoryEntry);
+
+impl Display for TaskHistoryEntryItem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0.summary())
+    }
+}
+
+pub(crate) struct TaskHistoryScreenController {
+    /// Copy of the history kept up to date with tasks rerun from the screen
+    history: TaskHistory,
+    filter: String,
+    entries: Vec<TaskHistoryEntry>,
+    select: Option<SelectInput<TaskHistoryEntryItem>>,
+    result_info: Option<Info>,
+}
+
+impl_builder!(TaskHistoryScreenController);
+
+impl TaskHistoryScreenController {
+    pub(crate) async fn new(app_state: &AppState) -> Self {
+        Self::new_filtered(app_state, String::new()).await
+    }
+
+    async fn new_filtered(app_state: &AppState, filter: String) -> Self {
+        let history = app_state.task_history.lock().await.clone();
+        let mut screen = TaskHistoryScreenController {
+            history,
+            filter,
+            entries: Vec::new(),
+            select: None,
+            result_info: None,
+        };
+        screen.update_entries();
+        screen
+    }
+
+    fn update_entries(&mut self) {
+        self.entries = self.history.search(&self.filter);
+        self.select = (!self.entries.is_empty()).then(|| {
+            SelectInput::new(
+                self.entries
+                    .iter()
+                    .cloned()
+                    .map(TaskHistoryEntryItem)
+                    .collect(),
+            )
+        });
+    }
+
+    fn rerun(&mut self, task: Task, block: bool) -> ScreenFeedback {
+        self.history.record(&task, block);
+        self.update_entries();
+        ScreenFeedback::Task { task, block }
+    }
+}
+
+impl ScreenController for TaskHistoryScreenController {
+    fn name(&self) -> &'static str {
+        "Task history"
+    }
+
+    fn command_keys(&self) -> &[ScreenCommandKey] {
+        COMMAND_KEYS.as_ref()
+    }
+
+    fn toggle_keys(&self) -> &[ScreenToggleKey] {
+        &[]
+    }
+
+    fn on_event(&mut self, event: &Event) -> ScreenFeedback {
+        match event {
+            Event::Key(KeyEvent {
+                code: Key::Char('q'),
+                modifiers: KeyModifiers::NONE,
+            }) => ScreenFeedback::PreviousScreen,
+
+            Event::Key(KeyEvent {
+                code: Key::Char('/'),
+                modifiers: KeyModifiers::NONE,
+            }) => ScreenFeedback::Form(Box::new(FilterTasksFormController::new(&self.filter))),
+
+            Event::Key(KeyEvent {
+                code: Key::Char('r'),
+                modifiers: KeyModifiers::NONE,
+            }) => match &self.select {
+                Some(select) => {
+                    let entry = self.entries[select.selected_index()].clone();
+                    self.rerun(entry.task, entry.block)
+                }
+                None => ScreenFeedback::None,
+            },
+
+            Event::Key(event) => match &mut self.select {
+                Some(select) => match select.on_event(*event) {
+                    InputStatus::Done(TaskHistoryEntryItem(entry)) => {
+                        match RerunTaskFormController::new(entry.task.clone(), entry.block) {
+                            Some(form) => ScreenFeedback::Form(Box::new(form)),
+                            None => self.rerun(entry.task, entry.block),
+                        }
+                    }
+                    InputStatus::Redraw => ScreenFeedback::Redraw,
+                    _ => ScreenFeedback::None,
+                },
+                None => ScreenFeedback::None,
+            },
+
+            Event::Backend(
+                BackendEvent::TaskCompleted {
+                    task,
+                    execution_result,
+                }
+                | BackendEvent::TaskCompletedStateChange {
+                    task,
+                    execution_result,
+                    ..
+                },
+            ) => {
+                // Results of background tasks aren't of interest here
+                if !self.history.record_result(task, execution_result.is_ok()) {
+                    return ScreenFeedback::None;
+                }
+                self.update_entries();
+                self.result_info = Some(Info::new_from_result(execution_result));
+                ScreenFeedback::Redraw
+            }
+
+            _ => ScreenFeedback::None,
+        }
+    }
+
+    fn view(&mut self, frame: &mut Frame, area: Rect) {
+        let area = if let Some(result_info) = &mut self.result_info {
+            let layout = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Min(3), Constraint::Percentage(40)].as_ref())
+                .split(area);
+            result_info.view(frame, layout[1]);
+            layout[0]
+        } else {
+            area
+        };
+
+        if let Some(select) = &mut self.select {
+            select.view(frame, area)
+        } else {
+            Info::new_fixed("No tasks submitted yet, or none matching the filter").view(frame, area)
+        }
+    }
+}
+
+struct FilterTasksFormController {
+    input: TextInput<DefaultTextInputParser<String>>,
+}
+
+impl FilterTasksFormController {
+    fn new(filter: &str) -> Self {
+        FilterTasksFormController {
+            input: TextInput::new_init_value("words to filter tasks by", filter.to_owned()),
+        }
+    }
+}
+
+impl FormController for FilterTasksFormController {
+    fn on_event(&mut self, event: KeyEvent) -> FormStatus {
+        match self.input.on_event(event) {
+            InputStatus::Done(filter) => FormStatus::NextScreen(Box::new(move |app_state| {
+                async move {
+                    Box::new(TaskHistoryScreenController::new_filtered(app_state, filter).await)
+                        as Box<dyn ScreenController>
+                }
+                .boxed()
+            })),
+            status => status.into(),
+        }
+    }
+
+    fn form_name(&self) -> &'static str {
+        "Filter task history"
+    }
+
+    fn step_view(&mut self, frame: &mut Frame, area: Rect) {
+        self.input.view(frame, area)
+    }
+
+    fn step_name(&self) -> Cow<'_, str> {
+        "Filter".into()
+    }
+
+    fn step_index(&self) -> u8 {
+        0
+    }
+
+    fn steps_number(&self) -> u8 {
+        1
+    }
+}
+
+/// Reruns a task with parameters of its template prefilled for editing.
+struct RerunTaskFormController {
+    task: Task,
+    block: bool,
+    parameters: Vec<&'static str>,
+    input: TextInput<DefaultTextInputParser<String>>,
+    values: Vec<String>,
+    initial_values: Vec<String>,
+}
+
+impl RerunTaskFormController {
+    /// `None` if the task has no parameters to edit.
+    fn new(task: Task, block: bool) -> Option<Self> {
+        let (parameters, initial_values): (Vec<_>, Vec<_>) = template(&task)?.into_iter().unzip();
+        Some(RerunTaskFormController {
+            input: TextInput::new_init_value("parameter value", initial_values[0].clone()),
+            task,
+            block,
+            parameters,
+            values: Vec::new(),
+            initial_values,
+        })
+    }
+}
+
+impl FormController for RerunTaskFormController {
+    fn on_event(&mut self, event: KeyEvent) -> FormStatus {
+        match self.input.on_event(event) {
+            InputStatus::Done(value) => {
+                self.values.push(value);
+                if let Some(next_value) = self.initial_values.get(self.values.len()) {
+                    self.input = TextInput::new_init_value("parameter value", next_value.clone());
+                    return FormStatus::Redraw;
+                }
+                match apply_template(&self.task, &self.values) {
+                    Ok(task) => FormStatus::Done {
+                        task,
+                        block: self.block,
+                    },
+                    Err(e) => {
+                        // Back to the last parameter with the error shown
+                        let value = self.values.pop().unwrap_or_default();
+                        self.input = TextInput::new_init_value("parameter value", value);
+                        self.input.set_error(e);
+                        FormStatus::Redraw
+                    }
+                }
+            }
+            status => status.into(),
+        }
+    }
+
+    fn form_name(&self) -> &'static str {
+        "Rerun task"
+    }
+
+    fn step_view(&mut self, frame: &mut Frame, area: Rect) {
+        self.input.view(frame, area)
+    }
+
+    fn step_name(&self) -> Cow<'_, str> {
+        self.parameters[self.values.len()].into()
+    }
+
+    fn step_index(&self) -> u8 {
+        self.values.len() as u8
+    }
+
+    fn steps_number(&self) -> u8 {
+        self.parameters.len() as u8
+    }
+}
@@ -0,0 +1,103 @@
+//! Guided tutorial screen.
+
+use tuirealm::{
+    event::{Key, KeyEvent, KeyModifiers},
+    tui::prelude::Rect,
+    Frame,
+};
+
+use crate::{
+    backend::{tutorial::TutorialTask, AppState, BackendEvent, Task},
+    ui::screen::{
+        utils::impl_builder, widgets::info::Info, ScreenCommandKey, ScreenController,
+        ScreenFeedback, ScreenToggleKey,
+    },
+    Event,
+};
+
+const COMMAND_KEYS: [ScreenCommandKey; 3] = [
+    ScreenCommandKey::new("q", "Back to Main"),
+    ScreenCommandKey::new("s", "Start tutorial from the beginning"),
+    ScreenCommandKey::new("x", "Stop tutorial"),
+];
+
+const INTRO: &str = r#"The tutorial walks you through the basics of Dash Platform:
+funding a wallet, registering an identity, fetching the DPNS contract,
+querying documents and checking protocol version voting.
+
+Steps are marked as done once the explorer actually executes them, the current
+step is shown in the status bar while the tutorial is running.
+
+Press s to start."#;
+
+pub(crate) struct TutorialScreenController {
+    info: Info,
+}
+
+impl_builder!(TutorialScreenController);
+
+impl TutorialScreenController {
+    pub(crate) async fn new(app_state: &AppState) -> Self {
+        let info = match app_state.tutorial.lock().await.as_ref() {
+            Some(tutorial) => Info::new_scrollable(&tutorial.display()),
+            None => Info::new_fixed(INTRO),
+        };
+        TutorialScreenController { info }
+    }
+}
+
+impl ScreenController for TutorialScreenController {
+    fn name(&self) -> &'static str {
+        "Tutorial"
+    }
+
+    fn command_keys(&self) -> &[ScreenCommandKey] {
+        COMMAND_KEYS.as_ref()
+    }
+
+    fn toggle_keys(&self) -> &[ScreenToggleKey] {
+        &[]
+    }
+
+    fn on_event(&mut self, event: &Event) -> ScreenFeedback {
+        match event {
+            Event::Key(KeyEvent {
+                code: Key::Char('q'),
+                modifiers: KeyModifiers::NONE,
+            }) => ScreenFeedback::PreviousScreen,
+            Event::Key(KeyEvent {
+                code: Key::Char('s'),
+                modifiers: KeyModifiers::NONE,
+            }) => ScreenFeedback::Task {
+                task: Task::Tutorial(TutorialTask::Start),
+                block: false,
+            },
+            Event::Key(KeyEvent {
+                code: Key::Char('x'),
+                modifiers: KeyModifiers::NONE,
+            }) => ScreenFeedback::Task {
+                task: Task::Tutorial(TutorialTask::Stop),
+                block: false,
+            },
+            Event::Key(k) => {
+                if self.info.on_event(k) {
+                    ScreenFeedback::Redraw
+                } else {
+                    ScreenFeedback::None
+                }
+            }
+            Event::Backend(BackendEvent::TaskCompleted {
+                task: Task::Tutorial(_),
+                execution_result,
+            }) => {
+                self.info = Info::new_from_result(execution_result);
+                ScreenFeedback::Redraw
+            }
+            _ => ScreenFeedback::None,
+        }
+    }
+
+    fn view(&mut self, frame: &mut Frame, area: Rect) {
+        self.info.view(frame, area)
+    }
+}
@@ -0,0 +1,104 @@
+//! Screen of protocol version upgrade progress, showing votes for the next
+//! protocol versions.
+
+use futures::FutureExt;
+use tuirealm::{
+    event::{Key, KeyEvent, KeyModifiers},
+    tui::prelude::Rect,
+    Frame,
+};
+
+use crate::{
+    backend::{platform_info::PlatformInfoTask, BackendEvent, Task},
+    ui::screen::{
+        widgets::info::Info, ScreenCommandKey, ScreenController, ScreenControllerBuilder,
+        ScreenFeedback, ScreenToggleKey,
+    },
+    Event,
+};
+
+const COMMAND_KEYS: [ScreenCommandKey; 4] = [
+    ScreenCommandKey::new("q", "Back"),
+    ScreenCommandKey::new("r", "Refresh upgrade status"),
+    ScreenCommandKey::new("↓", "Scroll down"),
+    ScreenCommandKey::new("↑", "Scroll up"),
+];
+
+pub(crate) struct VersionUpgradeScreenController {
+    info: Info,
+}
+
+impl VersionUpgradeScreenController {
+    /// Screen showing the fetched upgrade status.
+    pub(crate) fn builder(status: String) -> ScreenControllerBuilder {
+        Box::new(move |_| {
+            async move {
+                Box::new(VersionUpgradeScreenController {
+                    info: Info::new_scrollable(&status),
+                }) as Box<dyn ScreenController>
+            }
+            .boxed()
+        })
+    }
+}
+
+impl ScreenController for VersionUpgradeScreenController {
+    fn name(&self) -> &'static str {
+        "Version Upgrade"
+    }
+
+    fn command_keys(&self) -> &[ScreenCommandKey] {
+        COMMAND_KEYS.as_ref()
+    }
+
+    fn toggle_keys(&self) -> &[ScreenToggleKey] {
+        &[]
+    }
+
+    fn on_event(&mut self, event: &Event) -> ScreenFeedback {
+        match event {
+            Event::Key(KeyEvent {
+                code: Key::Char('q'),
+                modifiers: KeyModifiers::NONE,
+            }) => ScreenFeedback::PreviousScreen,
+
+            Event::Key(KeyEvent {
+                code: Key::Char('r'),
+                modifiers: KeyModifiers::NONE,
+            }) => ScreenFeedback::Task {
+                task: Task::PlatformInfo(PlatformInfoTask::FetchProtocolUpgradeStatus),
+                block: true,
+            },
+
+            Event::Key(
+                key_event @ KeyEvent {
+                    code: Key::Down | Key::Up,
+                    modifiers: KeyModifiers::NONE,
+                },
+            ) => {
+                self.info.on_event(key_event);
+                ScreenFeedback::Redraw
+            }
+
+            Event::Backend(BackendEvent::TaskCompleted {
+                task: Task::PlatformInfo(PlatformInfoTask::FetchProtocolUpgradeStatus),
+                execution_result,
+            }) => {
+                self.info = Info::new_from_result(execution_result);
+                ScreenFeedback::Redraw
+            }
+
+            _ => ScreenFeedback::None,
+        }
+    }
+
+    fn view(&mut self, frame: &mut Frame, area: Rect) {
+        self.info.view(frame, area)
+    }
+
+    fn refresh_task(&self) -> Option<Task> {
+        Some(Task::PlatformInfo(
+            PlatformInfoTask::FetchProtocolUpgradeStatus,
+        ))
+    }
+}
@@ -1,10 +1,15 @@
 //! Screens and forms related to wallet management.
 
-use dpp::dashcore::psbt::serialize::Serialize;
+use dpp::{
+    dashcore::{psbt::serialize::Serialize, Address, Network},
+    prelude::Identity,
+};
 
 mod add_identity_key;
+mod asset_lock_inspector;
+mod utxos;
 
-use std::ops::Deref;
+use std::{borrow::Cow, fmt::Display, ops::Deref};
 
 use tuirealm::{
     event::{Key, KeyEvent, KeyModifiers},
@@ -12,15 +17,23 @@ use tuirealm::{
     Frame,
 };
 
-use self::add_identity_key::AddIdentityKeyFormController;
+use self::{
+    add_identity_key::AddIdentityKeyFormController,
+    asset_lock_inspector::AssetLockInspectorScreenController, utxos::UtxosScreenController,
+};
 use crate::{
     backend::{
-        identities::IdentityTask, AppState, AppStateUpdate, BackendEvent, Task, Wallet, WalletTask,
+        asset_locks::{AssetLockTask, LockedFunds, UnusedAssetLock},
+        documents::bulk::ExportFormat,
+        identities::{withdrawal_whitelist::withdrawal_whitelist, IdentityTask},
+        reports::current_timestamp,
+        wallet::{FeeLevel, FeeSettings},
+        AppState, AppStateUpdate, BackendEvent, CompletedTaskPayload, Task, Wallet, WalletTask,
     },
     ui::{
         form::{
-            parsers::DefaultTextInputParser, FormController, FormStatus, Input, InputStatus,
-            TextInput,
+            parsers::DefaultTextInputParser, ComposedInput, Field, FormController, FormStatus,
+            Input, InputStatus, SelectInput, TextInput,
         },
         screen::{
             info_display::display_info, utils::impl_builder, widgets::info::Info, ScreenCommandKey,
@@ -30,19 +43,28 @@ use crate::{
     Event,
 };
 
-const WALLET_LOADED_COMMANDS: [ScreenCommandKey; 5] = [
+const WALLET_LOADED_COMMANDS: [ScreenCommandKey; 12] = [
     ScreenCommandKey::new("b", "Refresh wallet utxos and balance"),
+    ScreenCommandKey::new("n", "Refresh unconfirmed balance"),
+    ScreenCommandKey::new("o", "Refresh locked balance"),
+    ScreenCommandKey::new("p", "Asset lock inspector"),
     ScreenCommandKey::new("c", "Copy Receive Address"),
     ScreenCommandKey::new("i", "Register identity"),
     ScreenCommandKey::new("u", "Get more utxos"),
+    ScreenCommandKey::new("x", "UTXOs and coin control"),
+    ScreenCommandKey::new("f", "Transaction fee rate"),
     ScreenCommandKey::new("m", "Clear loaded wallet"),
+    ScreenCommandKey::new("a", "Add wallet by private key"),
+    ScreenCommandKey::new("s", "Switch wallet"),
 ];
 
-const IDENTITY_LOADED_COMMANDS: [ScreenCommandKey; 5] = [
+const IDENTITY_LOADED_COMMANDS: [ScreenCommandKey; 7] = [
     ScreenCommandKey::new("r", "Identity refresh"),
     ScreenCommandKey::new("w", "Withdraw balance"),
+    ScreenCommandKey::new("l", "Whitelist withdrawal address"),
     ScreenCommandKey::new("d", "Copy Identity ID"),
     ScreenCommandKey::new("k", "Add Identity key"),
+    ScreenCommandKey::new("y", "Export identity keys"),
     ScreenCommandKey::new("e", "Clear loaded identity"),
 ];
 
@@ -71,6 +93,7 @@ fn join_commands(
         }
     } else {
         commands.push(ScreenCommandKey::new("a", "Add wallet by private key"));
+        commands.push(ScreenCommandKey::new("s", "Switch wallet"));
     }
     commands.leak()
 }
@@ -78,21 +101,77 @@ fn join_commands(
 pub(crate) struct WalletScreenController {
     wallet_info: Info,
     identity_info: Info,
+    /// Label of the loaded wallet if it's registered
+    wallet_label: Option<String>,
     wallet_loaded: bool,
     identity_loaded: bool,
     identity_registration_in_progress: bool,
     identity_top_up_in_progress: bool,
+    /// Shown while wallet transactions affected by a core chain reorg await
+    /// re-confirmation
+    reorg_info: Option<Info>,
+    balance_info: Info,
+    /// Total and unconfirmed balance of the wallet UTXOs
+    wallet_balance: (u64, u64),
+    /// Funds held by asset locks, unknown until checked
+    locked_funds: Option<LockedFunds>,
+    fee_info: Info,
+    fee_settings: FeeSettings,
+    /// Fee rate form is shown once fresh estimates are fetched
+    fee_form_requested: bool,
+    /// Registration form is shown once unused asset locks are looked up
+    registration_form_requested: bool,
+    /// Wallet switch form is shown once registered wallets are listed
+    switch_form_requested: bool,
+    core_network: Network,
+    /// Withdrawal destinations of the loaded identity, any address if empty
+    withdrawal_whitelist: Vec<Address>,
 }
 
 impl_builder!(WalletScreenController);
 
+/// Funds of a new identity.
+#[derive(Clone)]
+enum RegistrationFunding {
+    NewAssetLock,
+    AssetLock(UnusedAssetLock),
+}
+
+impl Display for RegistrationFunding {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RegistrationFunding::NewAssetLock => write!(f, "New asset lock from wallet balance"),
+            RegistrationFunding::AssetLock(asset_lock) => {
+                write!(f, "Unused asset lock: {}", asset_lock)
+            }
+        }
+    }
+}
+
 struct RegisterIdentityFormController {
+    /// Funding choice shown first if there are unused asset locks
+    funding_input: Option<SelectInput<RegistrationFunding>>,
+    has_asset_locks: bool,
     input: TextInput<DefaultTextInputParser<f64>>,
 }
 
 impl RegisterIdentityFormController {
-    fn new() -> Self {
+    fn new(unused_asset_locks: Vec<UnusedAssetLock>) -> Self {
+        let has_asset_locks = !unused_asset_locks.is_empty();
+        let funding_input = has_asset_locks.then(|| {
+            SelectInput::new(
+                std::iter::once(RegistrationFunding::NewAssetLock)
+                    .chain(
+                        unused_asset_locks
+                            .into_iter()
+                            .map(RegistrationFunding::AssetLock),
+                    )
+                    .collect(),
+            )
+        });
         RegisterIdentityFormController {
+            funding_input,
+            has_asset_locks,
             input: TextInput::new("Quantity (in Dash)"),
         }
     }
@@ -100,6 +179,22 @@ impl RegisterIdentityFormController {
 
 impl FormController for RegisterIdentityFormController {
     fn on_event(&mut self, event: KeyEvent) -> FormStatus {
+        if let Some(funding_input) = &mut self.funding_input {
+            return match funding_input.on_event(event) {
+                InputStatus::Done(RegistrationFunding::NewAssetLock) => {
+                    self.funding_input = None;
+                    FormStatus::Redraw
+                }
+                InputStatus::Done(RegistrationFunding::AssetLock(asset_lock)) => FormStatus::Done {
+                    task: Task::Identity(IdentityTask::RegisterIdentityWithAssetLock(
+                        asset_lock.txid,
+                    )),
+                    block: true,
+                },
+                status => status.into(),
+            };
+        }
+
         match self.input.on_event(event) {
             InputStatus::Done(amount) => FormStatus::Done {
                 task: Task::Identity(IdentityTask::RegisterIdentity(
@@ -116,19 +211,27 @@ impl FormController for RegisterIdentityFormController {
     }
 
     fn step_view(&mut self, frame: &mut Frame, area: Rect) {
-        self.input.view(frame, area)
+        if let Some(funding_input) = &mut self.funding_input {
+            funding_input.view(frame, area)
+        } else {
+            self.input.view(frame, area)
+        }
     }
 
-    fn step_name(&self) -> &'static str {
-        "Funding amount"
+    fn step_name(&self) -> Cow<'_, str> {
+        if self.funding_input.is_some() {
+            "Funding".into()
+        } else {
+            "Funding amount".into()
+        }
     }
 
     fn step_index(&self) -> u8 {
-        0
+        (self.has_asset_locks && self.funding_input.is_none()) as u8
     }
 
     fn steps_number(&self) -> u8 {
-        1
+        1 + self.has_asset_locks as u8
     }
 }
 
@@ -165,8 +268,8 @@ impl FormController for TopUpIdentityFormController {
         self.input.view(frame, area)
     }
 
-    fn step_name(&self) -> &'static str {
-        "Top up amount"
+    fn step_name(&self) -> Cow<'_, str> {
+        "Top up amount".into()
     }
 
     fn step_index(&self) -> u8 {
@@ -179,12 +282,25 @@ impl FormController for TopUpIdentityFormController {
 }
 
 struct WithdrawFromIdentityFormController {
+    /// Destination choice shown first if the identity has whitelisted
+    /// addresses
+    destination_input: Option<SelectInput<String>>,
+    destination: Option<String>,
     input: TextInput<DefaultTextInputParser<f64>>,
 }
 
 impl WithdrawFromIdentityFormController {
-    fn new() -> Self {
+    fn new(withdrawal_whitelist: &[Address]) -> Self {
         WithdrawFromIdentityFormController {
+            destination_input: (!withdrawal_whitelist.is_empty()).then(|| {
+                SelectInput::new(
+                    withdrawal_whitelist
+                        .iter()
+                        .map(|address| address.to_string())
+                        .collect(),
+                )
+            }),
+            destination: None,
             input: TextInput::new("Quantity (in Dash)"),
         }
     }
@@ -192,11 +308,24 @@ impl WithdrawFromIdentityFormController {
 
 impl FormController for WithdrawFromIdentityFormController {
     fn on_event(&mut self, event: KeyEvent) -> FormStatus {
+        if self.destination.is_none() {
+            if let Some(destination_input) = &mut self.destination_input {
+                return match destination_input.on_event(event) {
+                    InputStatus::Done(destination) => {
+                        self.destination = Some(destination);
+                        FormStatus::Redraw
+                    }
+                    status => status.into(),
+                };
+            }
+        }
+
         match self.input.on_event(event) {
             InputStatus::Done(amount) => FormStatus::Done {
-                task: Task::Identity(IdentityTask::WithdrawFromIdentity(
-                    (amount * 100000000.0) as u64,
-                )),
+                task: Task::Identity(IdentityTask::EstimateWithdrawal {
+                    amount: (amount * 100000000.0) as u64,
+                    destination: self.destination.clone(),
+                }),
                 block: true,
             },
             InputStatus::Redraw => FormStatus::Redraw,
@@ -209,12 +338,174 @@ impl FormController for WithdrawFromIdentityFormController {
         "Identity withdrawal"
     }
 
+    fn step_view(&mut self, frame: &mut Frame, area: Rect) {
+        match &mut self.destination_input {
+            Some(destination_input) if self.destination.is_none() => {
+                destination_input.view(frame, area)
+            }
+            _ => self.input.view(frame, area),
+        }
+    }
+
+    fn step_name(&self) -> Cow<'_, str> {
+        if self.destination_input.is_some() && self.destination.is_none() {
+            "Whitelisted destination".into()
+        } else {
+            "Withdrawal amount".into()
+        }
+    }
+
+    fn step_index(&self) -> u8 {
+        self.destination.is_some() as u8
+    }
+
+    fn steps_number(&self) -> u8 {
+        1 + self.destination_input.is_some() as u8
+    }
+}
+
+/// Withdrawal confirmation with its estimate shown above.
+struct ConfirmWithdrawalFormController {
+    task: Option<Task>,
+    estimate: Info,
+    input: SelectInput<String>,
+}
+
+impl ConfirmWithdrawalFormController {
+    fn new(
+        amount: u64,
+        destination: Option<String>,
+        estimate: &Result<CompletedTaskPayload, String>,
+    ) -> Self {
+        ConfirmWithdrawalFormController {
+            task: Some(Task::Identity(IdentityTask::WithdrawFromIdentity {
+                amount,
+                destination,
+            })),
+            estimate: match estimate {
+                Ok(_) => Info::new_from_result(estimate),
+                Err(e) => Info::new_error(&format!("Unable to estimate the withdrawal: {}", e)),
+            },
+            input: SelectInput::new(vec!["No".to_owned(), "Yes".to_owned()]),
+        }
+    }
+}
+
+impl FormController for ConfirmWithdrawalFormController {
+    fn on_event(&mut self, event: KeyEvent) -> FormStatus {
+        match self.input.on_event(event) {
+            InputStatus::Done(confirmation) if confirmation == "Yes" => match self.task.take() {
+                Some(task) => FormStatus::Done { task, block: true },
+                None => FormStatus::Exit,
+            },
+            InputStatus::Done(_) => FormStatus::Exit,
+            status => status.into(),
+        }
+    }
+
+    fn form_name(&self) -> &'static str {
+        "Identity withdrawal"
+    }
+
+    fn step_view(&mut self, frame: &mut Frame, area: Rect) {
+        let layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(3), Constraint::Length(4)].as_ref())
+            .split(area);
+        self.estimate.view(frame, layout[0]);
+        self.input.view(frame, layout[1]);
+    }
+
+    fn step_name(&self) -> Cow<'_, str> {
+        "Confirm withdrawal".into()
+    }
+
+    fn step_index(&self) -> u8 {
+        0
+    }
+
+    fn steps_number(&self) -> u8 {
+        1
+    }
+}
+
+struct AddWithdrawalAddressFormController {
+    input: TextInput<DefaultTextInputParser<String>>,
+}
+
+impl AddWithdrawalAddressFormController {
+    fn new() -> Self {
+        AddWithdrawalAddressFormController {
+            input: TextInput::new("Core address"),
+        }
+    }
+}
+
+impl FormController for AddWithdrawalAddressFormController {
+    fn on_event(&mut self, event: KeyEvent) -> FormStatus {
+        match self.input.on_event(event) {
+            InputStatus::Done(address) => FormStatus::Done {
+                task: Task::Identity(IdentityTask::AddWithdrawalAddress(address)),
+                block: true,
+            },
+            status => status.into(),
+        }
+    }
+
+    fn form_name(&self) -> &'static str {
+        "Whitelist withdrawal address"
+    }
+
+    fn step_view(&mut self, frame: &mut Frame, area: Rect) {
+        self.input.view(frame, area)
+    }
+
+    fn step_name(&self) -> Cow<'_, str> {
+        "Address".into()
+    }
+
+    fn step_index(&self) -> u8 {
+        0
+    }
+
+    fn steps_number(&self) -> u8 {
+        1
+    }
+}
+
+struct ExportIdentityKeysFormController {
+    input: SelectInput<ExportFormat>,
+}
+
+impl ExportIdentityKeysFormController {
+    fn new() -> Self {
+        ExportIdentityKeysFormController {
+            input: SelectInput::new(vec![ExportFormat::Json, ExportFormat::Csv]),
+        }
+    }
+}
+
+impl FormController for ExportIdentityKeysFormController {
+    fn on_event(&mut self, event: KeyEvent) -> FormStatus {
+        match self.input.on_event(event) {
+            InputStatus::Done(format) => FormStatus::Done {
+                task: Task::Identity(IdentityTask::ExportKeys(format)),
+                block: false,
+            },
+            status => status.into(),
+        }
+    }
+
+    fn form_name(&self) -> &'static str {
+        "Export identity keys"
+    }
+
     fn step_view(&mut self, frame: &mut Frame, area: Rect) {
         self.input.view(frame, area)
     }
 
-    fn step_name(&self) -> &'static str {
-        "Withdrawal amount"
+    fn step_name(&self) -> Cow<'_, str> {
+        "Format".into()
     }
 
     fn step_index(&self) -> u8 {
@@ -226,8 +517,60 @@ impl FormController for WithdrawFromIdentityFormController {
     }
 }
 
+fn wallet_balance(wallet: &Wallet) -> (u64, u64) {
+    match wallet {
+        Wallet::SingleKeyWallet(wallet) => (wallet.balance(), wallet.unconfirmed_balance()),
+    }
+}
+
+/// Wallet balance split by whether funds are spendable yet, with funds that
+/// left the UTXOs into asset locks accounted for.
+fn display_balance((total, unconfirmed): (u64, u64), locked_funds: Option<&LockedFunds>) -> String {
+    let dash = |duffs: u64| format!("{:.4} Dash", duffs as f64 / 100_000_000.0);
+    let locked = match locked_funds {
+        Some(locked_funds) => format!(
+            "{} in {} asset locks, checked {}s ago",
+            dash(locked_funds.total()),
+            locked_funds.amounts.len(),
+            current_timestamp().saturating_sub(locked_funds.checked_at)
+        ),
+        None => "not checked yet".to_owned(),
+    };
+    format!(
+        "Confirmed: {}\nUnconfirmed: {}\nLocked: {}",
+        dash(total.saturating_sub(unconfirmed)),
+        dash(unconfirmed),
+        locked
+    )
+}
+
+/// Identity details followed by its withdrawal whitelist, if any.
+fn display_identity(identity: &Identity, withdrawal_whitelist: &[Address]) -> String {
+    let mut info = display_info(identity);
+    if !withdrawal_whitelist.is_empty() {
+        info.push_str("\n\nWithdrawal whitelist:");
+        for address in withdrawal_whitelist {
+            info.push_str(&format!("\n  {}", address));
+        }
+    }
+    info
+}
+
 impl WalletScreenController {
     pub(crate) async fn new(app_state: &AppState) -> Self {
+        let mut whitelist = Vec::new();
+        let balance = app_state
+            .loaded_wallet
+            .lock()
+            .await
+            .as_ref()
+            .map(wallet_balance)
+            .unwrap_or_default();
+        let locked_funds = app_state.asset_locks.lock().await.locked().cloned();
+        let wallet_label = match app_state.loaded_wallet.lock().await.as_ref() {
+            Some(wallet) => app_state.wallet_profiles.lock().await.label_of(wallet),
+            None => None,
+        };
         let (
             wallet_info,
             identity_info,
@@ -242,9 +585,10 @@ impl WalletScreenController {
                     .lock()
                     .await
                     .is_some();
+                whitelist = withdrawal_whitelist(identity, app_state.core_network);
                 (
-                    Info::new_fixed(&display_wallet(wallet)),
-                    Info::new_fixed(&display_info(identity)),
+                    Info::new_fixed(&display_wallet(wallet, wallet_label.as_deref())),
+                    Info::new_fixed(&display_identity(identity, &whitelist)),
                     true,
                     true,
                     false,
@@ -255,9 +599,15 @@ impl WalletScreenController {
                     .identity_asset_lock_private_key_in_creation
                     .lock()
                     .await
-                    .is_some();
+                    .is_some()
+                    || app_state
+                        .asset_locks
+                        .lock()
+                        .await
+                        .pending_registration()
+                        .is_some();
                 (
-                    Info::new_fixed(&display_wallet(wallet)),
+                    Info::new_fixed(&display_wallet(wallet, wallet_label.as_deref())),
                     Info::new_fixed(""),
                     true,
                     false,
@@ -276,24 +626,69 @@ impl WalletScreenController {
             )
         };
 
+        let reorg_watch = app_state.reorg_watch.lock().await;
+        let reorg_info =
+            (!reorg_watch.is_stable()).then(|| Info::new_error(&reorg_watch.display()));
+
+        let fee_settings = app_state.fee_settings.lock().await.clone();
+
         Self {
             wallet_info,
             identity_info,
+            wallet_label,
             wallet_loaded,
             identity_loaded,
             identity_registration_in_progress,
             identity_top_up_in_progress,
+            reorg_info,
+            balance_info: Info::new_fixed(&display_balance(balance, locked_funds.as_ref())),
+            wallet_balance: balance,
+            locked_funds,
+            fee_info: Info::new_fixed(&fee_settings.display()),
+            fee_settings,
+            fee_form_requested: false,
+            registration_form_requested: false,
+            switch_form_requested: false,
+            core_network: app_state.core_network,
+            withdrawal_whitelist: whitelist,
         }
     }
 }
 
 impl ScreenController for WalletScreenController {
     fn view(&mut self, frame: &mut Frame, area: Rect) {
+        let area = if let Some(reorg_info) = &mut self.reorg_info {
+            let layout = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Length(8), Constraint::Min(40)].as_ref())
+                .split(area);
+            reorg_info.view(frame, layout[0]);
+            layout[1]
+        } else {
+            area
+        };
         let layout = Layout::default()
             .direction(Direction::Vertical)
             .constraints([Constraint::Min(20), Constraint::Min(20)].as_ref())
             .split(area);
-        self.wallet_info.view(frame, layout[0]);
+        if self.wallet_loaded {
+            let wallet_layout = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints(
+                    [
+                        Constraint::Min(10),
+                        Constraint::Length(3),
+                        Constraint::Length(2),
+                    ]
+                    .as_ref(),
+                )
+                .split(layout[0]);
+            self.wallet_info.view(frame, wallet_layout[0]);
+            self.balance_info.view(frame, wallet_layout[1]);
+            self.fee_info.view(frame, wallet_layout[2]);
+        } else {
+            self.wallet_info.view(frame, layout[0]);
+        }
         self.identity_info.view(frame, layout[1]);
     }
 
@@ -324,8 +719,17 @@ impl ScreenController for WalletScreenController {
             Event::Key(KeyEvent {
                 code: Key::Char('a'),
                 modifiers: KeyModifiers::NONE,
-            }) if !self.wallet_loaded => {
-                ScreenFeedback::Form(Box::new(AddWalletPrivateKeyFormController::new()))
+            }) => ScreenFeedback::Form(Box::new(AddWalletPrivateKeyFormController::new())),
+
+            Event::Key(KeyEvent {
+                code: Key::Char('s'),
+                modifiers: KeyModifiers::NONE,
+            }) => {
+                self.switch_form_requested = true;
+                ScreenFeedback::Task {
+                    task: Task::Wallet(WalletTask::ListWallets),
+                    block: false,
+                }
             }
 
             Event::Key(KeyEvent {
@@ -336,6 +740,30 @@ impl ScreenController for WalletScreenController {
                 block: true,
             },
 
+            Event::Key(KeyEvent {
+                code: Key::Char('n'),
+                modifiers: KeyModifiers::NONE,
+            }) if self.wallet_loaded => ScreenFeedback::Task {
+                task: Task::Wallet(WalletTask::RefreshUnconfirmed),
+                block: true,
+            },
+
+            Event::Key(KeyEvent {
+                code: Key::Char('o'),
+                modifiers: KeyModifiers::NONE,
+            }) if self.wallet_loaded => ScreenFeedback::Task {
+                task: Task::AssetLock(AssetLockTask::CheckLocked),
+                block: true,
+            },
+
+            Event::Key(KeyEvent {
+                code: Key::Char('p'),
+                modifiers: KeyModifiers::NONE,
+            }) if self.wallet_loaded => ScreenFeedback::Task {
+                task: Task::AssetLock(AssetLockTask::Inspect),
+                block: true,
+            },
+
             Event::Key(KeyEvent {
                 code: Key::Char('r'),
                 modifiers: KeyModifiers::NONE,
@@ -347,25 +775,64 @@ impl ScreenController for WalletScreenController {
             Event::Key(KeyEvent {
                 code: Key::Char('w'),
                 modifiers: KeyModifiers::NONE,
+            }) if self.identity_loaded => ScreenFeedback::Form(Box::new(
+                WithdrawFromIdentityFormController::new(&self.withdrawal_whitelist),
+            )),
+
+            Event::Key(KeyEvent {
+                code: Key::Char('l'),
+                modifiers: KeyModifiers::NONE,
             }) if self.identity_loaded => {
-                ScreenFeedback::Form(Box::new(WithdrawFromIdentityFormController::new()))
+                ScreenFeedback::Form(Box::new(AddWithdrawalAddressFormController::new()))
             }
 
             Event::Key(KeyEvent {
                 code: Key::Char('i'),
                 modifiers: KeyModifiers::NONE,
-            }) => ScreenFeedback::Form(Box::new(RegisterIdentityFormController::new())),
+            }) if self.identity_registration_in_progress => ScreenFeedback::Task {
+                task: Task::Identity(IdentityTask::ContinueRegistration),
+                block: true,
+            },
+
+            Event::Key(KeyEvent {
+                code: Key::Char('i'),
+                modifiers: KeyModifiers::NONE,
+            }) => {
+                self.registration_form_requested = true;
+                ScreenFeedback::Task {
+                    task: Task::AssetLock(AssetLockTask::FindUnused),
+                    block: true,
+                }
+            }
 
             Event::Key(KeyEvent {
                 code: Key::Char('u'),
                 modifiers: KeyModifiers::NONE,
             }) => ScreenFeedback::Form(Box::new(SplitUTXOsFormController::new())),
 
+            Event::Key(KeyEvent {
+                code: Key::Char('x'),
+                modifiers: KeyModifiers::NONE,
+            }) if self.wallet_loaded => {
+                ScreenFeedback::NextScreen(UtxosScreenController::builder())
+            }
+
             Event::Key(KeyEvent {
                 code: Key::Char('t'),
                 modifiers: KeyModifiers::NONE,
             }) => ScreenFeedback::Form(Box::new(TopUpIdentityFormController::new())),
 
+            Event::Key(KeyEvent {
+                code: Key::Char('f'),
+                modifiers: KeyModifiers::NONE,
+            }) if self.wallet_loaded => {
+                self.fee_form_requested = true;
+                ScreenFeedback::Task {
+                    task: Task::Wallet(WalletTask::EstimateFees),
+                    block: true,
+                }
+            }
+
             Event::Key(KeyEvent {
                 code: Key::Char('c'),
                 modifiers: KeyModifiers::NONE,
@@ -389,6 +856,13 @@ impl ScreenController for WalletScreenController {
                 ScreenFeedback::Form(Box::new(AddIdentityKeyFormController::new()))
             }
 
+            Event::Key(KeyEvent {
+                code: Key::Char('y'),
+                modifiers: KeyModifiers::NONE,
+            }) if self.identity_loaded => {
+                ScreenFeedback::Form(Box::new(ExportIdentityKeysFormController::new()))
+            }
+
             Event::Key(KeyEvent {
                 code: Key::Char('e'),
                 modifiers: KeyModifiers::NONE,
@@ -411,6 +885,46 @@ impl ScreenController for WalletScreenController {
                 }
             }
 
+            Event::Backend(BackendEvent::TaskCompletedStateChange {
+                task:
+                    Task::Wallet(
+                        WalletTask::AddWallet { label, .. }
+                        | WalletTask::SwitchWallet { label, .. },
+                    ),
+                execution_result,
+                app_state_update: AppStateUpdate::LoadedWallet(wallet),
+            }) => {
+                self.wallet_label = Some(label.clone());
+                self.wallet_info = match execution_result {
+                    Ok(_) => Info::new_fixed(&display_wallet(&wallet, Some(label.as_str()))),
+                    Err(e) => Info::new_error(e),
+                };
+                self.wallet_balance = wallet_balance(&wallet);
+                self.balance_info = Info::new_fixed(&display_balance(
+                    self.wallet_balance,
+                    self.locked_funds.as_ref(),
+                ));
+                self.wallet_loaded = true;
+                ScreenFeedback::Redraw
+            }
+
+            Event::Backend(BackendEvent::TaskCompletedStateChange {
+                app_state_update: AppStateUpdate::WalletProfiles(wallet_profiles),
+                ..
+            }) => {
+                let labels = wallet_profiles.labels();
+                if !self.switch_form_requested {
+                    ScreenFeedback::None
+                } else if labels.is_empty() {
+                    self.switch_form_requested = false;
+                    self.wallet_info = Info::new_error("No wallets registered yet");
+                    ScreenFeedback::Redraw
+                } else {
+                    self.switch_form_requested = false;
+                    ScreenFeedback::Form(Box::new(SwitchWalletFormController::new(labels)))
+                }
+            }
+
             Event::Backend(
                 BackendEvent::AppStateUpdated(AppStateUpdate::LoadedWallet(wallet))
                 | BackendEvent::TaskCompletedStateChange {
@@ -418,13 +932,73 @@ impl ScreenController for WalletScreenController {
                     ..
                 },
             ) => {
-                self.wallet_info = Info::new_fixed(&display_wallet(&wallet));
+                self.wallet_info =
+                    Info::new_fixed(&display_wallet(&wallet, self.wallet_label.as_deref()));
+                self.wallet_balance = wallet_balance(&wallet);
+                self.balance_info = Info::new_fixed(&display_balance(
+                    self.wallet_balance,
+                    self.locked_funds.as_ref(),
+                ));
                 self.wallet_loaded = true;
                 ScreenFeedback::Redraw
             }
 
+            Event::Backend(BackendEvent::AppStateUpdated(AppStateUpdate::ReorgWatch(
+                reorg_watch,
+            ))) => {
+                self.reorg_info =
+                    (!reorg_watch.is_stable()).then(|| Info::new_error(&reorg_watch.display()));
+                ScreenFeedback::Redraw
+            }
+
+            Event::Backend(BackendEvent::TaskCompletedStateChange {
+                execution_result,
+                app_state_update: AppStateUpdate::FeeSettings(fee_settings),
+                ..
+            }) => {
+                self.fee_settings = fee_settings.clone();
+                self.fee_info = Info::new_fixed(&fee_settings.display());
+                if let Err(e) = execution_result {
+                    self.wallet_info = Info::new_error(e);
+                }
+                if self.fee_form_requested {
+                    self.fee_form_requested = false;
+                    ScreenFeedback::Form(Box::new(FeeRateFormController::new(fee_settings.clone())))
+                } else {
+                    ScreenFeedback::Redraw
+                }
+            }
+
             Event::Backend(BackendEvent::TaskCompletedStateChange {
-                task: Task::Identity(IdentityTask::RegisterIdentity(_)),
+                execution_result,
+                app_state_update: AppStateUpdate::AssetLocks(asset_locks),
+                ..
+            }) => {
+                if let Err(e) = execution_result {
+                    self.wallet_info = Info::new_error(e);
+                }
+                self.locked_funds = asset_locks.locked().cloned();
+                self.balance_info = Info::new_fixed(&display_balance(
+                    self.wallet_balance,
+                    self.locked_funds.as_ref(),
+                ));
+                if self.registration_form_requested {
+                    self.registration_form_requested = false;
+                    ScreenFeedback::Form(Box::new(RegisterIdentityFormController::new(
+                        asset_locks.unused().to_vec(),
+                    )))
+                } else {
+                    ScreenFeedback::Redraw
+                }
+            }
+
+            Event::Backend(BackendEvent::TaskCompletedStateChange {
+                task:
+                    Task::Identity(
+                        IdentityTask::RegisterIdentity(_)
+                        | IdentityTask::RegisterIdentityWithAssetLock(_)
+                        | IdentityTask::ContinueRegistration,
+                    ),
                 execution_result,
                 app_state_update: AppStateUpdate::IdentityRegistrationProgressed,
             }) => {
@@ -441,6 +1015,7 @@ impl ScreenController for WalletScreenController {
             }) => {
                 self.identity_info = Info::new_fixed("");
                 self.identity_loaded = false;
+                self.withdrawal_whitelist.clear();
                 ScreenFeedback::Redraw
             }
 
@@ -451,9 +1026,11 @@ impl ScreenController for WalletScreenController {
             }) => {
                 self.wallet_info =
                     Info::new_fixed("Wallet management commands\n\nNo wallet loaded yet");
+                self.wallet_label = None;
                 self.wallet_loaded = false;
                 self.identity_info = Info::new_fixed("");
                 self.identity_loaded = false;
+                self.withdrawal_whitelist.clear();
                 ScreenFeedback::Redraw
             }
 
@@ -464,14 +1041,53 @@ impl ScreenController for WalletScreenController {
             }) => {
                 self.identity_loaded = true;
                 self.identity_registration_in_progress = false;
+                self.withdrawal_whitelist =
+                    withdrawal_whitelist(identity.deref(), self.core_network);
                 if execution_result.is_ok() {
-                    self.identity_info = Info::new_fixed(&display_info(identity.deref()));
+                    self.identity_info = Info::new_fixed(&display_identity(
+                        identity.deref(),
+                        &self.withdrawal_whitelist,
+                    ));
                 } else {
                     self.identity_info = Info::new_from_result(execution_result);
                 }
                 ScreenFeedback::Redraw
             }
 
+            Event::Backend(BackendEvent::TaskCompleted {
+                task:
+                    Task::Identity(IdentityTask::EstimateWithdrawal {
+                        amount,
+                        destination,
+                    }),
+                execution_result,
+            }) => ScreenFeedback::Form(Box::new(ConfirmWithdrawalFormController::new(
+                *amount,
+                destination.clone(),
+                execution_result,
+            ))),
+
+            Event::Backend(BackendEvent::TaskCompleted {
+                task: Task::AssetLock(AssetLockTask::Inspect),
+                execution_result,
+            }) => match execution_result {
+                Ok(CompletedTaskPayload::String(report)) => ScreenFeedback::NextScreen(
+                    AssetLockInspectorScreenController::builder(report.clone()),
+                ),
+                result => {
+                    self.wallet_info = Info::new_from_result(result);
+                    ScreenFeedback::Redraw
+                }
+            },
+
+            Event::Backend(BackendEvent::TaskCompleted {
+                task: Task::Identity(IdentityTask::ExportKeys(_)),
+                execution_result,
+            }) => {
+                self.wallet_info = Info::new_from_result(execution_result);
+                ScreenFeedback::Redraw
+            }
+
             Event::Backend(BackendEvent::TaskCompleted {
                 task: Task::Wallet(_),
                 execution_result: Err(e),
@@ -492,17 +1108,35 @@ impl ScreenController for WalletScreenController {
             _ => ScreenFeedback::None,
         }
     }
+
+    fn refresh_task(&self) -> Option<Task> {
+        Some(Task::Wallet(WalletTask::Refresh))
+    }
 }
 
 struct AddWalletPrivateKeyFormController {
-    input: TextInput<DefaultTextInputParser<String>>, /* TODO: provide parser to always have a
-                                                       * typesafe valid output */
+    input: ComposedInput<(
+        Field<TextInput<DefaultTextInputParser<String>>>,
+        // TODO: provide parser to always have a typesafe valid output
+        Field<TextInput<DefaultTextInputParser<String>>>,
+        Field<TextInput<DefaultTextInputParser<String>>>,
+    )>,
 }
 
 impl AddWalletPrivateKeyFormController {
     fn new() -> Self {
         AddWalletPrivateKeyFormController {
-            input: TextInput::new("64 hex character or WIF private key"),
+            input: ComposedInput::new((
+                Field::new("Wallet label", TextInput::new("Label")),
+                Field::new(
+                    "Private key",
+                    TextInput::new("64 hex character or WIF private key"),
+                ),
+                Field::new(
+                    "Wallet passphrase",
+                    TextInput::new("Passphrase to encrypt the key with").masked(),
+                ),
+            )),
         }
     }
 }
@@ -510,9 +1144,13 @@ impl AddWalletPrivateKeyFormController {
 impl FormController for AddWalletPrivateKeyFormController {
     fn on_event(&mut self, event: KeyEvent) -> FormStatus {
         match self.input.on_event(event) {
-            InputStatus::Done(private_key) => FormStatus::Done {
-                task: Task::Wallet(WalletTask::AddByPrivateKey(private_key)),
-                block: false,
+            InputStatus::Done((label, private_key, passphrase)) => FormStatus::Done {
+                task: Task::Wallet(WalletTask::AddWallet {
+                    label,
+                    private_key,
+                    passphrase,
+                }),
+                block: true,
             },
             status => status.into(),
         }
@@ -526,16 +1164,69 @@ impl FormController for AddWalletPrivateKeyFormController {
         self.input.view(frame, area)
     }
 
-    fn step_name(&self) -> &'static str {
-        "Private key"
+    fn step_name(&self) -> Cow<'_, str> {
+        self.input.step_name().into()
     }
 
     fn step_index(&self) -> u8 {
-        0
+        self.input.step_index()
     }
 
     fn steps_number(&self) -> u8 {
-        1
+        3
+    }
+}
+
+struct SwitchWalletFormController {
+    input: ComposedInput<(
+        Field<SelectInput<String>>,
+        Field<TextInput<DefaultTextInputParser<String>>>,
+    )>,
+}
+
+impl SwitchWalletFormController {
+    fn new(labels: Vec<String>) -> Self {
+        SwitchWalletFormController {
+            input: ComposedInput::new((
+                Field::new("Wallet", SelectInput::new(labels)),
+                Field::new(
+                    "Wallet passphrase",
+                    TextInput::new("Passphrase the key was encrypted with").masked(),
+                ),
+            )),
+        }
+    }
+}
+
+impl FormController for SwitchWalletFormController {
+    fn on_event(&mut self, event: KeyEvent) -> FormStatus {
+        match self.input.on_event(event) {
+            InputStatus::Done((label, passphrase)) => FormStatus::Done {
+                task: Task::Wallet(WalletTask::SwitchWallet { label, passphrase }),
+                block: true,
+            },
+            status => status.into(),
+        }
+    }
+
+    fn form_name(&self) -> &'static str {
+        "Switch wallet"
+    }
+
+    fn step_view(&mut self, frame: &mut Frame, area: Rect) {
+        self.input.view(frame, area)
+    }
+
+    fn step_name(&self) -> Cow<'_, str> {
+        self.input.step_name().into()
+    }
+
+    fn step_index(&self) -> u8 {
+        self.input.step_index()
+    }
+
+    fn steps_number(&self) -> u8 {
+        2
     }
 }
 
@@ -570,8 +1261,8 @@ impl FormController for SplitUTXOsFormController {
         self.input.view(frame, area)
     }
 
-    fn step_name(&self) -> &'static str {
-        "Desired number"
+    fn step_name(&self) -> Cow<'_, str> {
+        "Desired number".into()
     }
 
     fn step_index(&self) -> u8 {
@@ -583,11 +1274,125 @@ impl FormController for SplitUTXOsFormController {
     }
 }
 
-fn display_wallet(wallet: &Wallet) -> String {
+/// Fee level choice with the rate and resulting asset lock fee displayed.
+#[derive(Clone)]
+struct FeeLevelOption {
+    level: FeeLevel,
+    rate: u64,
+    asset_lock_fee: u64,
+}
+
+impl Display for FeeLevelOption {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.level {
+            FeeLevel::Custom(_) => write!(f, "{}", self.level),
+            _ => write!(
+                f,
+                "{}: {} duffs/kB, asset lock fee {} duffs",
+                self.level, self.rate, self.asset_lock_fee
+            ),
+        }
+    }
+}
+
+struct FeeRateFormController {
+    level_input: SelectInput<FeeLevelOption>,
+    custom_rate_input: Option<TextInput<DefaultTextInputParser<u64>>>,
+}
+
+impl FeeRateFormController {
+    fn new(fee_settings: FeeSettings) -> Self {
+        let options: Vec<FeeLevelOption> = [
+            FeeLevel::Economy,
+            FeeLevel::Normal,
+            FeeLevel::Priority,
+            FeeLevel::Custom(fee_settings.rate()),
+        ]
+        .into_iter()
+        .map(|level| FeeLevelOption {
+            level,
+            rate: fee_settings.rate_for(level),
+            asset_lock_fee: fee_settings.asset_lock_fee_for(level),
+        })
+        .collect();
+        let selected = options
+            .iter()
+            .position(|option| {
+                std::mem::discriminant(&option.level) == std::mem::discriminant(&fee_settings.level)
+            })
+            .unwrap_or_default();
+
+        FeeRateFormController {
+            level_input: SelectInput::new_init_line(options, selected),
+            custom_rate_input: None,
+        }
+    }
+}
+
+impl FormController for FeeRateFormController {
+    fn on_event(&mut self, event: KeyEvent) -> FormStatus {
+        if let Some(custom_rate_input) = &mut self.custom_rate_input {
+            return match custom_rate_input.on_event(event) {
+                InputStatus::Done(rate) => FormStatus::Done {
+                    task: Task::Wallet(WalletTask::SetFeeLevel(FeeLevel::Custom(rate))),
+                    block: false,
+                },
+                status => status.into(),
+            };
+        }
+
+        match self.level_input.on_event(event) {
+            InputStatus::Done(FeeLevelOption {
+                level: FeeLevel::Custom(rate),
+                ..
+            }) => {
+                self.custom_rate_input =
+                    Some(TextInput::new_init_value("Duffs per kilobyte", rate));
+                FormStatus::Redraw
+            }
+            InputStatus::Done(FeeLevelOption { level, .. }) => FormStatus::Done {
+                task: Task::Wallet(WalletTask::SetFeeLevel(level)),
+                block: false,
+            },
+            status => status.into(),
+        }
+    }
+
+    fn form_name(&self) -> &'static str {
+        "Transaction fee rate"
+    }
+
+    fn step_view(&mut self, frame: &mut Frame, area: Rect) {
+        if let Some(custom_rate_input) = &mut self.custom_rate_input {
+            custom_rate_input.view(frame, area)
+        } else {
+            self.level_input.view(frame, area)
+        }
+    }
+
+    fn step_name(&self) -> Cow<'_, str> {
+        if self.custom_rate_input.is_some() {
+            "Custom rate".into()
+        } else {
+            "Fee level".into()
+        }
+    }
+
+    fn step_index(&self) -> u8 {
+        self.custom_rate_input.is_some() as u8
+    }
+
+    fn steps_number(&self) -> u8 {
+        2
+    }
+}
+
+fn display_wallet(wallet: &Wallet, label: Option<&str>) -> String {
     match wallet {
         Wallet::SingleKeyWallet(single_key_wallet) => {
             let description = format!(
-                "Single Key Wallet\nPublic Key: {}\nAddress: {}\nBalance: {}",
+                "Single Key Wallet{}\nPublic Key: {}\nAddress: {}\nBalance: {}",
+                label.map(|label| format!(" {}", label)).unwrap_or_default(),
                 hex::encode(single_key_wallet.public_key.serialize()),
                 single_key_wallet.address,
                 single_key_wallet.balance_dash_formatted()
@@ -1,5 +1,7 @@
 //! Definition of a form to add a key to an identity.
 
+use std::borrow::Cow;
+
 use dpp::identity::{KeyType, Purpose as KeyPurpose, SecurityLevel as KeySecurityLevel};
 use tuirealm::{event::KeyEvent, tui::prelude::Rect, Frame};
 
@@ -111,11 +113,11 @@ impl FormController for AddIdentityKeyFormController {
         }
     }
 
-    fn step_name(&self) -> &'static str {
+    fn step_name(&self) -> Cow<'_, str> {
         match self.step {
-            AddIdentityKeyFormStep::Purpose(_) => "Key purpose",
-            AddIdentityKeyFormStep::Security(_) => "Key security level",
-            AddIdentityKeyFormStep::KeyType(_) => "Key type",
+            AddIdentityKeyFormStep::Purpose(_) => "Key purpose".into(),
+            AddIdentityKeyFormStep::Security(_) => "Key security level".into(),
+            AddIdentityKeyFormStep::KeyType(_) => "Key type".into(),
         }
     }
 
@@ -0,0 +1,105 @@
+//! Screen of the asset locks of the identity registration and top up in
+//! progress.
+
+use futures::FutureExt;
+use tuirealm::{
+    event::{Key, KeyEvent, KeyModifiers},
+    tui::prelude::Rect,
+    Frame,
+};
+
+use crate::{
+    backend::{asset_locks::AssetLockTask, BackendEvent, CompletedTaskPayload, Task},
+    ui::screen::{
+        widgets::info::Info, ScreenCommandKey, ScreenController, ScreenControllerBuilder,
+        ScreenFeedback, ScreenToggleKey,
+    },
+    Event,
+};
+
+const COMMAND_KEYS: [ScreenCommandKey; 4] = [
+    ScreenCommandKey::new("q", "Back"),
+    ScreenCommandKey::new("f", "Re-fetch proof status"),
+    ScreenCommandKey::new("↓", "Scroll down"),
+    ScreenCommandKey::new("↑", "Scroll up"),
+];
+
+/// Asset locks report, refreshed with the refresh key.
+pub(super) struct AssetLockInspectorScreenController {
+    info: Info,
+}
+
+impl AssetLockInspectorScreenController {
+    pub(super) fn builder(report: String) -> ScreenControllerBuilder {
+        Box::new(move |_| {
+            async move {
+                Box::new(AssetLockInspectorScreenController {
+                    info: Info::new_scrollable(&report),
+                }) as Box<dyn ScreenController>
+            }
+            .boxed()
+        })
+    }
+}
+
+impl ScreenController for AssetLockInspectorScreenController {
+    fn name(&self) -> &'static str {
+        "Asset lock inspector"
+    }
+
+    fn command_keys(&self) -> &[ScreenCommandKey] {
+        COMMAND_KEYS.as_ref()
+    }
+
+    fn toggle_keys(&self) -> &[ScreenToggleKey] {
+        &[]
+    }
+
+    fn on_event(&mut self, event: &Event) -> ScreenFeedback {
+        match event {
+            Event::Key(KeyEvent {
+                code: Key::Char('q'),
+                modifiers: KeyModifiers::NONE,
+            }) => ScreenFeedback::PreviousScreen,
+
+            Event::Key(KeyEvent {
+                code: Key::Char('f'),
+                modifiers: KeyModifiers::NONE,
+            }) => ScreenFeedback::Task {
+                task: Task::AssetLock(AssetLockTask::RefetchProofs),
+                block: true,
+            },
+
+            Event::Key(
+                key_event @ KeyEvent {
+                    code: Key::Down | Key::Up,
+                    modifiers: KeyModifiers::NONE,
+                },
+            ) => {
+                self.info.on_event(key_event);
+                ScreenFeedback::Redraw
+            }
+
+            Event::Backend(BackendEvent::TaskCompleted {
+                task: Task::AssetLock(AssetLockTask::Inspect | AssetLockTask::RefetchProofs),
+                execution_result,
+            }) => {
+                self.info = match execution_result {
+                    Ok(CompletedTaskPayload::String(report)) => Info::new_scrollable(report),
+                    result => Info::new_from_result(result),
+                };
+                ScreenFeedback::Redraw
+            }
+
+            _ => ScreenFeedback::None,
+        }
+    }
+
+    fn view(&mut self, frame: &mut Frame, area: Rect) {
+        self.info.view(frame, area)
+    }
+
+    fn refresh_task(&self) -> Option<Task> {
+        Some(Task::AssetLock(AssetLockTask::Inspect))
+    }
+}
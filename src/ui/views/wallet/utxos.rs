@@ -0,0 +1,227 @@
+//! Screen listing the wallet UTXOs, which also picks the UTXOs funding asset
+//! locks (coin control).
+
+use std::fmt::{self, Display};
+
+use tuirealm::{
+    event::{Key, KeyEvent, KeyModifiers},
+    tui::prelude::{Constraint, Direction, Layout, Rect},
+    Frame,
+};
+
+use crate::{
+    backend::{wallet::Utxo, AppState, AppStateUpdate, BackendEvent, Task, WalletTask},
+    ui::{
+        form::{Input, InputStatus, SelectInput},
+        screen::{
+            utils::impl_builder, widgets::info::Info, ScreenCommandKey, ScreenController,
+            ScreenFeedback, ScreenToggleKey,
+        },
+    },
+    Event,
+};
+
+const COMMAND_KEYS: [ScreenCommandKey; 5] = [
+    ScreenCommandKey::new("q", "Back"),
+    ScreenCommandKey::new("m", "Pick / unpick UTXO for asset locks"),
+    ScreenCommandKey::new("c", "Clear picked UTXOs"),
+    ScreenCommandKey::new("↓ / C-n", "Next UTXO"),
+    ScreenCommandKey::new("↑ / C-p", "Prev UTXO"),
+];
+
+/// UTXO wrapper for display in a list
+#[derive(Clone)]
+struct UtxoItem(Utxo);
+
+impl Display for UtxoItem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "[{}] {} {:>14.8} Dash {:>8} conf.",
+            if self.0.coin_control { "x" } else { " " },
+            self.0.outpoint,
+            self.0.amount as f64 / 100_000_000.0,
+            self.0
+                .confirmations
+                .map(|confirmations| confirmations.to_string())
+                .unwrap_or_else(|| "?".to_owned())
+        )
+    }
+}
+
+/// Picked UTXOs summary.
+fn display_coin_control(utxos: &[Utxo]) -> String {
+    let picked: Vec<&Utxo> = utxos.iter().filter(|utxo| utxo.coin_control).collect();
+    if picked.is_empty() {
+        return "No UTXOs picked, asset locks are funded from any UTXOs".to_owned();
+    }
+    format!(
+        "Picked {} UTXOs, {:.8} Dash\nThe next asset lock spends all the picked UTXOs and only \
+         them, the rest returns as change",
+        picked.len(),
+        picked.iter().map(|utxo| utxo.amount).sum::<u64>() as f64 / 100_000_000.0
+    )
+}
+
+pub(super) struct UtxosScreenController {
+    utxos: Vec<Utxo>,
+    select: Option<SelectInput<UtxoItem>>,
+    info: Info,
+}
+
+impl_builder!(UtxosScreenController);
+
+impl UtxosScreenController {
+    async fn new(app_state: &AppState) -> Self {
+        let utxos = app_state
+            .loaded_wallet
+            .lock()
+            .await
+            .as_ref()
+            .map(|wallet| wallet.utxos())
+            .unwrap_or_default();
+        let mut screen = UtxosScreenController {
+            utxos: Vec::new(),
+            select: None,
+            info: Info::new_fixed(""),
+        };
+        screen.show(utxos);
+        screen
+    }
+
+    /// Lists the UTXOs keeping the highlighted line.
+    fn show(&mut self, utxos: Vec<Utxo>) {
+        let line = self
+            .select
+            .as_ref()
+            .map(|select| select.selected_index())
+            .unwrap_or_default()
+            .min(utxos.len().saturating_sub(1));
+        self.select = (!utxos.is_empty()).then(|| {
+            SelectInput::new_init_line(utxos.iter().cloned().map(UtxoItem).collect(), line)
+        });
+        self.info = if utxos.is_empty() {
+            Info::new_fixed("No UTXOs in the wallet")
+        } else {
+            Info::new_fixed(&display_coin_control(&utxos))
+        };
+        self.utxos = utxos;
+    }
+
+    /// Picked UTXOs with the highlighted one picked or unpicked.
+    fn toggle_highlighted(&self) -> Option<Vec<Utxo>> {
+        let highlighted = self.utxos.get(self.select.as_ref()?.selected_index())?;
+        Some(
+            self.utxos
+                .iter()
+                .filter(|utxo| utxo.coin_control != (utxo.outpoint == highlighted.outpoint))
+                .cloned()
+                .collect(),
+        )
+    }
+}
+
+impl ScreenController for UtxosScreenController {
+    fn name(&self) -> &'static str {
+        "Wallet UTXOs"
+    }
+
+    fn command_keys(&self) -> &[ScreenCommandKey] {
+        COMMAND_KEYS.as_ref()
+    }
+
+    fn toggle_keys(&self) -> &[ScreenToggleKey] {
+        &[]
+    }
+
+    fn on_event(&mut self, event: &Event) -> ScreenFeedback {
+        match event {
+            Event::Key(KeyEvent {
+                code: Key::Char('q'),
+                modifiers: KeyModifiers::NONE,
+            }) => ScreenFeedback::PreviousScreen,
+
+            Event::Key(KeyEvent {
+                code: Key::Char('m'),
+                modifiers: KeyModifiers::NONE,
+            }) => match self.toggle_highlighted() {
+                Some(picked) => ScreenFeedback::Task {
+                    task: Task::Wallet(WalletTask::SetCoinControl(
+                        picked.iter().map(|utxo| utxo.outpoint).collect(),
+                    )),
+                    block: false,
+                },
+                None => ScreenFeedback::None,
+            },
+
+            Event::Key(KeyEvent {
+                code: Key::Char('c'),
+                modifiers: KeyModifiers::NONE,
+            }) => ScreenFeedback::Task {
+                task: Task::Wallet(WalletTask::SetCoinControl(Vec::new())),
+                block: false,
+            },
+
+            Event::Key(event) => match &mut self.select {
+                Some(select) => match select.on_event(*event) {
+                    InputStatus::Redraw => ScreenFeedback::Redraw,
+                    _ => ScreenFeedback::None,
+                },
+                None => ScreenFeedback::None,
+            },
+
+            Event::Backend(
+                BackendEvent::AppStateUpdated(AppStateUpdate::LoadedWallet(wallet))
+                | BackendEvent::TaskCompletedStateChange {
+                    app_state_update: AppStateUpdate::LoadedWallet(wallet),
+                    ..
+                },
+            ) => {
+                self.show(wallet.utxos());
+                if let Event::Backend(BackendEvent::TaskCompletedStateChange {
+                    execution_result: Err(e),
+                    ..
+                }) = event
+                {
+                    self.info = Info::new_error(e);
+                }
+                ScreenFeedback::Redraw
+            }
+
+            Event::Backend(BackendEvent::TaskCompletedStateChange {
+                app_state_update: AppStateUpdate::ClearedLoadedWallet,
+                ..
+            }) => {
+                self.show(Vec::new());
+                ScreenFeedback::Redraw
+            }
+
+            Event::Backend(BackendEvent::TaskCompleted {
+                task: Task::Wallet(_),
+                execution_result: Err(e),
+            }) => {
+                self.info = Info::new_error(e);
+                ScreenFeedback::Redraw
+            }
+
+            _ => ScreenFeedback::None,
+        }
+    }
+
+    fn view(&mut self, frame: &mut Frame, area: Rect) {
+        let Some(select) = &mut self.select else {
+            self.info.view(frame, area);
+            return;
+        };
+        let layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(3), Constraint::Length(4)].as_ref())
+            .split(area);
+        select.view(frame, layout[0]);
+        self.info.view(frame, layout[1]);
+    }
+
+    fn refresh_task(&self) -> Option<Task> {
+        Some(Task::Wallet(WalletTask::Refresh))
+    }
+}